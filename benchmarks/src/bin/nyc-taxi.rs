@@ -229,114 +229,133 @@ fn create_table_expr() -> CreateExpr {
                 datatype: ColumnDataType::Int64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "tpep_pickup_datetime".to_string(),
                 datatype: ColumnDataType::Int64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "tpep_dropoff_datetime".to_string(),
                 datatype: ColumnDataType::Int64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "passenger_count".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "trip_distance".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "RatecodeID".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "store_and_fwd_flag".to_string(),
                 datatype: ColumnDataType::String as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "PULocationID".to_string(),
                 datatype: ColumnDataType::Int64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "DOLocationID".to_string(),
                 datatype: ColumnDataType::Int64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "payment_type".to_string(),
                 datatype: ColumnDataType::Int64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "fare_amount".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "extra".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "mta_tax".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "tip_amount".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "tolls_amount".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "improvement_surcharge".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "total_amount".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "congestion_surcharge".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "airport_fee".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
         ],
         time_index: "tpep_pickup_datetime".to_string(),
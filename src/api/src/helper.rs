@@ -15,27 +15,46 @@
 use common_base::BitVec;
 use common_time::timestamp::TimeUnit;
 use datatypes::prelude::ConcreteDataType;
+use datatypes::types::Decimal128Type;
 use datatypes::value::Value;
 use datatypes::vectors::VectorRef;
 use snafu::prelude::*;
 
 use crate::error::{self, Result};
 use crate::v1::column::Values;
-use crate::v1::{Column, ColumnDataType};
+use crate::v1::column_data_type_extension::TypeExt;
+use crate::v1::{Column, ColumnDataType, ColumnDataTypeExtension, DecimalTypeExtension};
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct ColumnDataTypeWrapper(ColumnDataType);
+#[derive(Debug, PartialEq)]
+pub struct ColumnDataTypeWrapper(ColumnDataType, Option<ColumnDataTypeExtension>);
 
 impl ColumnDataTypeWrapper {
+    pub fn new(datatype: ColumnDataType) -> Self {
+        Self(datatype, None)
+    }
+
     pub fn try_new(datatype: i32) -> Result<Self> {
+        Self::try_new_with_extension(datatype, None)
+    }
+
+    /// Creates the wrapper with the datatype extension carried along with the proto
+    /// datatype, like the precision and scale of decimal.
+    pub fn try_new_with_extension(
+        datatype: i32,
+        datatype_extension: Option<ColumnDataTypeExtension>,
+    ) -> Result<Self> {
         let datatype = ColumnDataType::from_i32(datatype)
             .context(error::UnknownColumnDataTypeSnafu { datatype })?;
-        Ok(Self(datatype))
+        Ok(Self(datatype, datatype_extension))
     }
 
     pub fn datatype(&self) -> ColumnDataType {
         self.0
     }
+
+    pub fn datatype_extension(&self) -> Option<ColumnDataTypeExtension> {
+        self.1.clone()
+    }
 }
 
 impl From<ColumnDataTypeWrapper> for ConcreteDataType {
@@ -57,6 +76,23 @@ impl From<ColumnDataTypeWrapper> for ConcreteDataType {
             ColumnDataType::Date => ConcreteDataType::date_datatype(),
             ColumnDataType::Datetime => ConcreteDataType::datetime_datatype(),
             ColumnDataType::Timestamp => ConcreteDataType::timestamp_millis_datatype(),
+            ColumnDataType::TimestampSecond => {
+                ConcreteDataType::timestamp_datatype(TimeUnit::Second)
+            }
+            ColumnDataType::TimestampMicrosecond => {
+                ConcreteDataType::timestamp_datatype(TimeUnit::Microsecond)
+            }
+            ColumnDataType::TimestampNanosecond => {
+                ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond)
+            }
+            ColumnDataType::Decimal128 => ConcreteDataType::Decimal128(
+                datatype
+                    .1
+                    .as_ref()
+                    .and_then(decimal128_type_from_extension)
+                    .unwrap_or_default(),
+            ),
+            ColumnDataType::Json => ConcreteDataType::json_datatype(),
         }
     }
 }
@@ -65,7 +101,8 @@ impl TryFrom<ConcreteDataType> for ColumnDataTypeWrapper {
     type Error = error::Error;
 
     fn try_from(datatype: ConcreteDataType) -> Result<Self> {
-        let datatype = ColumnDataTypeWrapper(match datatype {
+        let mut datatype_extension = None;
+        let column_datatype = match datatype {
             ConcreteDataType::Boolean(_) => ColumnDataType::Boolean,
             ConcreteDataType::Int8(_) => ColumnDataType::Int8,
             ConcreteDataType::Int16(_) => ColumnDataType::Int16,
@@ -81,12 +118,27 @@ impl TryFrom<ConcreteDataType> for ColumnDataTypeWrapper {
             ConcreteDataType::String(_) => ColumnDataType::String,
             ConcreteDataType::Date(_) => ColumnDataType::Date,
             ConcreteDataType::DateTime(_) => ColumnDataType::Datetime,
-            ConcreteDataType::Timestamp(_) => ColumnDataType::Timestamp,
+            ConcreteDataType::Timestamp(t) => match t.unit {
+                TimeUnit::Second => ColumnDataType::TimestampSecond,
+                TimeUnit::Millisecond => ColumnDataType::Timestamp,
+                TimeUnit::Microsecond => ColumnDataType::TimestampMicrosecond,
+                TimeUnit::Nanosecond => ColumnDataType::TimestampNanosecond,
+            },
+            ConcreteDataType::Decimal128(t) => {
+                datatype_extension = Some(ColumnDataTypeExtension {
+                    type_ext: Some(TypeExt::DecimalType(DecimalTypeExtension {
+                        precision: t.precision as i32,
+                        scale: t.scale as i32,
+                    })),
+                });
+                ColumnDataType::Decimal128
+            }
+            ConcreteDataType::Json(_) => ColumnDataType::Json,
             ConcreteDataType::Null(_) | ConcreteDataType::List(_) => {
                 return error::IntoColumnDataTypeSnafu { from: datatype }.fail()
             }
-        });
-        Ok(datatype)
+        };
+        Ok(ColumnDataTypeWrapper(column_datatype, datatype_extension))
     }
 }
 
@@ -157,7 +209,52 @@ impl Values {
                 ts_millis_values: Vec::with_capacity(capacity),
                 ..Default::default()
             },
+            ColumnDataType::TimestampSecond => Values {
+                ts_second_values: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
+            ColumnDataType::TimestampMicrosecond => Values {
+                ts_microsecond_values: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
+            ColumnDataType::TimestampNanosecond => Values {
+                ts_nanosecond_values: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
+            ColumnDataType::Decimal128 => Values {
+                decimal128_values: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
+            ColumnDataType::Json => Values {
+                json_values: Vec::with_capacity(capacity),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl From<i128> for crate::v1::Decimal128 {
+    fn from(value: i128) -> Self {
+        Self {
+            hi: (value >> 64) as i64,
+            lo: value as i64,
+        }
+    }
+}
+
+impl From<crate::v1::Decimal128> for i128 {
+    fn from(value: crate::v1::Decimal128) -> Self {
+        ((value.hi as i128) << 64) | (value.lo as u64 as i128)
+    }
+}
+
+/// Returns the decimal type described by the datatype extension, if any.
+pub fn decimal128_type_from_extension(ext: &ColumnDataTypeExtension) -> Option<Decimal128Type> {
+    match &ext.type_ext {
+        Some(TypeExt::DecimalType(d)) => {
+            Some(Decimal128Type::new(d.precision as u8, d.scale as u8))
         }
+        None => None,
     }
 }
 
@@ -187,9 +284,14 @@ impl Column {
             Value::Binary(val) => values.binary_values.push(val.to_vec()),
             Value::Date(val) => values.date_values.push(val.val()),
             Value::DateTime(val) => values.datetime_values.push(val.val()),
-            Value::Timestamp(val) => values
-                .ts_millis_values
-                .push(val.convert_to(TimeUnit::Millisecond)),
+            Value::Timestamp(val) => match val.unit() {
+                TimeUnit::Second => values.ts_second_values.push(val.value()),
+                TimeUnit::Millisecond => values.ts_millis_values.push(val.value()),
+                TimeUnit::Microsecond => values.ts_microsecond_values.push(val.value()),
+                TimeUnit::Nanosecond => values.ts_nanosecond_values.push(val.value()),
+            },
+            Value::Decimal128(val) => values.decimal128_values.push(val.value().into()),
+            Value::Json(val) => values.json_values.push(val.to_string()),
             Value::List(_) => unreachable!(),
         });
         self.null_mask = null_mask.into_vec();
@@ -267,138 +369,161 @@ mod tests {
     fn test_concrete_datatype_from_column_datatype() {
         assert_eq!(
             ConcreteDataType::boolean_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Boolean).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Boolean).into()
         );
         assert_eq!(
             ConcreteDataType::int8_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Int8).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Int8).into()
         );
         assert_eq!(
             ConcreteDataType::int16_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Int16).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Int16).into()
         );
         assert_eq!(
             ConcreteDataType::int32_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Int32).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Int32).into()
         );
         assert_eq!(
             ConcreteDataType::int64_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Int64).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Int64).into()
         );
         assert_eq!(
             ConcreteDataType::uint8_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Uint8).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint8).into()
         );
         assert_eq!(
             ConcreteDataType::uint16_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Uint16).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint16).into()
         );
         assert_eq!(
             ConcreteDataType::uint32_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Uint32).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint32).into()
         );
         assert_eq!(
             ConcreteDataType::uint64_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Uint64).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint64).into()
         );
         assert_eq!(
             ConcreteDataType::float32_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Float32).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Float32).into()
         );
         assert_eq!(
             ConcreteDataType::float64_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Float64).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Float64).into()
         );
         assert_eq!(
             ConcreteDataType::binary_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Binary).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Binary).into()
         );
         assert_eq!(
             ConcreteDataType::string_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::String).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::String).into()
         );
         assert_eq!(
             ConcreteDataType::date_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Date).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Date).into()
         );
         assert_eq!(
             ConcreteDataType::datetime_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Datetime).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Datetime).into()
         );
         assert_eq!(
             ConcreteDataType::timestamp_millis_datatype(),
-            ColumnDataTypeWrapper(ColumnDataType::Timestamp).into()
+            ColumnDataTypeWrapper::new(ColumnDataType::Timestamp).into()
+        );
+        assert_eq!(
+            ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond),
+            ColumnDataTypeWrapper::new(ColumnDataType::TimestampNanosecond).into()
+        );
+
+        let values = Values::with_capacity(ColumnDataType::TimestampNanosecond, 2);
+        let values = values.ts_nanosecond_values;
+        assert_eq!(2, values.capacity());
+
+        assert_eq!(
+            ConcreteDataType::json_datatype(),
+            ColumnDataTypeWrapper::new(ColumnDataType::Json).into()
+        );
+        assert_eq!(
+            ColumnDataTypeWrapper::new(ColumnDataType::Json),
+            ConcreteDataType::json_datatype().try_into().unwrap()
         );
     }
 
     #[test]
     fn test_column_datatype_from_concrete_datatype() {
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Boolean),
+            ColumnDataTypeWrapper::new(ColumnDataType::Boolean),
             ConcreteDataType::boolean_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Int8),
+            ColumnDataTypeWrapper::new(ColumnDataType::Int8),
             ConcreteDataType::int8_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Int16),
+            ColumnDataTypeWrapper::new(ColumnDataType::Int16),
             ConcreteDataType::int16_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Int32),
+            ColumnDataTypeWrapper::new(ColumnDataType::Int32),
             ConcreteDataType::int32_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Int64),
+            ColumnDataTypeWrapper::new(ColumnDataType::Int64),
             ConcreteDataType::int64_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Uint8),
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint8),
             ConcreteDataType::uint8_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Uint16),
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint16),
             ConcreteDataType::uint16_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Uint32),
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint32),
             ConcreteDataType::uint32_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Uint64),
+            ColumnDataTypeWrapper::new(ColumnDataType::Uint64),
             ConcreteDataType::uint64_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Float32),
+            ColumnDataTypeWrapper::new(ColumnDataType::Float32),
             ConcreteDataType::float32_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Float64),
+            ColumnDataTypeWrapper::new(ColumnDataType::Float64),
             ConcreteDataType::float64_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Binary),
+            ColumnDataTypeWrapper::new(ColumnDataType::Binary),
             ConcreteDataType::binary_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::String),
+            ColumnDataTypeWrapper::new(ColumnDataType::String),
             ConcreteDataType::string_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Date),
+            ColumnDataTypeWrapper::new(ColumnDataType::Date),
             ConcreteDataType::date_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Datetime),
+            ColumnDataTypeWrapper::new(ColumnDataType::Datetime),
             ConcreteDataType::datetime_datatype().try_into().unwrap()
         );
         assert_eq!(
-            ColumnDataTypeWrapper(ColumnDataType::Timestamp),
+            ColumnDataTypeWrapper::new(ColumnDataType::Timestamp),
             ConcreteDataType::timestamp_millis_datatype()
                 .try_into()
                 .unwrap()
         );
+        assert_eq!(
+            ColumnDataTypeWrapper::new(ColumnDataType::TimestampMicrosecond),
+            ConcreteDataType::timestamp_datatype(TimeUnit::Microsecond)
+                .try_into()
+                .unwrap()
+        );
 
         let result: Result<ColumnDataTypeWrapper> = ConcreteDataType::null_datatype().try_into();
         assert!(result.is_err());
@@ -416,6 +541,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_decimal128_datatype_conversion() {
+        let wrapper: ColumnDataTypeWrapper = ConcreteDataType::decimal128_datatype(20, 4)
+            .try_into()
+            .unwrap();
+        assert_eq!(ColumnDataType::Decimal128, wrapper.datatype());
+        let ext = wrapper.datatype_extension().unwrap();
+        assert_eq!(
+            Some(Decimal128Type::new(20, 4)),
+            decimal128_type_from_extension(&ext)
+        );
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(20, 4),
+            ConcreteDataType::from(wrapper)
+        );
+
+        // Falls back to the default precision and scale without extension.
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(10, 0),
+            ColumnDataTypeWrapper::new(ColumnDataType::Decimal128).into()
+        );
+    }
+
+    #[test]
+    fn test_pb_decimal128() {
+        for v in [0, 1, -1, i128::MAX, i128::MIN, 1 << 64, -(1 << 64) + 7] {
+            let pb: crate::v1::Decimal128 = v.into();
+            assert_eq!(v, i128::from(pb));
+        }
+    }
+
     #[test]
     fn test_column_put_vector() {
         use crate::v1::column::SemanticType;
@@ -429,6 +585,7 @@ mod tests {
             }),
             null_mask: vec![2],
             datatype: ColumnDataType::Boolean as i32,
+            datatype_extension: None,
         };
         let row_count = 4;
 
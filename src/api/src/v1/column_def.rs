@@ -21,7 +21,10 @@ use crate::v1::ColumnDef;
 
 impl ColumnDef {
     pub fn try_as_column_schema(&self) -> Result<ColumnSchema> {
-        let data_type = ColumnDataTypeWrapper::try_new(self.datatype)?;
+        let data_type = ColumnDataTypeWrapper::try_new_with_extension(
+            self.datatype,
+            self.datatype_extension.clone(),
+        )?;
 
         let constraint = match &self.default_constraint {
             None => None,
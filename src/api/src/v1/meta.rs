@@ -146,11 +146,13 @@ gen_set_header!(RouteRequest);
 gen_set_header!(CreateRequest);
 gen_set_header!(RangeRequest);
 gen_set_header!(DeleteRequest);
+gen_set_header!(RenameRequest);
 gen_set_header!(PutRequest);
 gen_set_header!(BatchPutRequest);
 gen_set_header!(CompareAndPutRequest);
 gen_set_header!(DeleteRangeRequest);
 gen_set_header!(MoveValueRequest);
+gen_set_header!(WatchRequest);
 
 #[cfg(test)]
 mod tests {
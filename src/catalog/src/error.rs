@@ -97,6 +97,31 @@ pub enum Error {
     #[snafu(display("Table `{}` already exists", table))]
     TableExists { table: String, backtrace: Backtrace },
 
+    #[snafu(display("Catalog {} already exists", catalog))]
+    CatalogExists {
+        catalog: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Catalog {} is not empty, table {} still exists", catalog, table))]
+    CatalogNotEmpty {
+        catalog: String,
+        table: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("User {} already exists", user))]
+    UserExists { user: String, backtrace: Backtrace },
+
+    #[snafu(display("User not found: {}", user))]
+    UserNotFound { user: String, backtrace: Backtrace },
+
+    #[snafu(display("Role {} already exists", role))]
+    RoleExists { role: String, backtrace: Backtrace },
+
+    #[snafu(display("Role not found: {}", role))]
+    RoleNotFound { role: String, backtrace: Backtrace },
+
     #[snafu(display("Schema {} already exists", schema))]
     SchemaExists {
         schema: String,
@@ -197,6 +222,17 @@ pub enum Error {
         source: datatypes::error::Error,
     },
 
+    #[snafu(display(
+        "Failed to read region stats of table {}, source: {}",
+        table_name,
+        source
+    ))]
+    ReadRegionStats {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: table::error::Error,
+    },
+
     #[snafu(display("Catalog internal error: {}", source))]
     Internal {
         #[snafu(backtrace)]
@@ -229,11 +265,18 @@ impl ErrorExt for Error {
             Error::InvalidCatalogValue { source, .. } => source.status_code(),
 
             Error::TableExists { .. } => StatusCode::TableAlreadyExists,
-            Error::SchemaExists { .. } => StatusCode::InvalidArguments,
+            Error::SchemaExists { .. }
+            | Error::CatalogExists { .. }
+            | Error::CatalogNotEmpty { .. }
+            | Error::UserExists { .. }
+            | Error::UserNotFound { .. }
+            | Error::RoleExists { .. }
+            | Error::RoleNotFound { .. } => StatusCode::InvalidArguments,
 
             Error::OpenSystemCatalog { source, .. }
             | Error::CreateSystemCatalog { source, .. }
             | Error::InsertCatalogRecord { source, .. }
+            | Error::ReadRegionStats { source, .. }
             | Error::OpenTable { source, .. }
             | Error::CreateTable { source, .. } => source.status_code(),
             Error::MetaSrv { source, .. } => source.status_code(),
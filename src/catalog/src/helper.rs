@@ -28,6 +28,10 @@ const CATALOG_KEY_PREFIX: &str = "__c";
 const SCHEMA_KEY_PREFIX: &str = "__s";
 const TABLE_GLOBAL_KEY_PREFIX: &str = "__tg";
 const TABLE_REGIONAL_KEY_PREFIX: &str = "__tr";
+const REGION_STATS_KEY_PREFIX: &str = "__rs";
+/// Prefix of the table route keys stored by metasrv, which are in the form of
+/// `{prefix}-{catalog}-{schema}-{table}-{table_id}`.
+pub const TABLE_ROUTE_PREFIX: &str = "__meta_table_route";
 
 const ALPHANUMERICS_NAME_PATTERN: &str = "[a-zA-Z_][a-zA-Z0-9_]*";
 
@@ -77,6 +81,16 @@ pub fn build_schema_prefix(catalog_name: impl AsRef<str>) -> String {
     format!("{}-{}-", SCHEMA_KEY_PREFIX, catalog_name.as_ref())
 }
 
+/// Prefix of the table global keys of all catalogs and schemas.
+pub fn build_all_table_global_prefix() -> String {
+    format!("{}-", TABLE_GLOBAL_KEY_PREFIX)
+}
+
+/// Prefix of the table route keys of all tables.
+pub fn build_all_table_route_prefix() -> String {
+    format!("{}-", TABLE_ROUTE_PREFIX)
+}
+
 pub fn build_table_global_prefix(
     catalog_name: impl AsRef<str>,
     schema_name: impl AsRef<str>,
@@ -264,6 +278,43 @@ impl SchemaKey {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SchemaValue;
 
+pub fn build_region_stats_prefix() -> String {
+    format!("{}-", REGION_STATS_KEY_PREFIX)
+}
+
+/// Key of the region statistics of a datanode, which are reported in its heartbeats.
+pub struct RegionStatsKey {
+    pub node_id: u64,
+}
+
+impl Display for RegionStatsKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(REGION_STATS_KEY_PREFIX)?;
+        f.write_str("-")?;
+        f.serialize_u64(self.node_id)
+    }
+}
+
+/// Statistics of all regions on a datanode, as of its latest heartbeat.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RegionStatsValue {
+    pub node_id: u64,
+    /// Time of the heartbeat in milliseconds.
+    pub timestamp_millis: i64,
+    pub stats: Vec<RegionStatEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RegionStatEntry {
+    pub region_id: u64,
+    pub region_number: u32,
+    pub catalog_name: String,
+    pub schema_name: String,
+    pub table_name: String,
+    pub approximate_rows: u64,
+    pub approximate_bytes: u64,
+}
+
 macro_rules! define_catalog_value {
     ( $($val_ty: ty), *) => {
             $(
@@ -291,7 +342,8 @@ define_catalog_value!(
     TableRegionalValue,
     TableGlobalValue,
     CatalogValue,
-    SchemaValue
+    SchemaValue,
+    RegionStatsValue
 );
 
 #[cfg(test)]
@@ -333,6 +385,7 @@ mod tests {
     fn test_build_prefix() {
         assert_eq!("__c-", build_catalog_prefix());
         assert_eq!("__s-CATALOG-", build_schema_prefix("CATALOG"));
+        assert_eq!("__tg-", build_all_table_global_prefix());
         assert_eq!(
             "__tg-CATALOG-SCHEMA-",
             build_table_global_prefix("CATALOG", "SCHEMA")
@@ -31,6 +31,7 @@ pub use crate::schema::{SchemaProvider, SchemaProviderRef};
 pub mod error;
 pub mod helper;
 pub mod local;
+pub mod privilege;
 pub mod remote;
 pub mod schema;
 pub mod system;
@@ -93,10 +94,19 @@ pub trait CatalogManager: CatalogList {
     /// returns whether the table deregistered.
     async fn deregister_table(&self, request: DeregisterTableRequest) -> Result<bool>;
 
+    /// Renames a table within given catalog/schema, returns whether the table renamed.
+    async fn rename_table(&self, request: RenameTableRequest) -> Result<bool>;
+
     /// Register a schema with catalog name and schema name. Retuens whether the
     /// schema registered.
     async fn register_schema(&self, request: RegisterSchemaRequest) -> Result<bool>;
 
+    /// Creates a catalog along with its default schema, returns whether the catalog created.
+    async fn create_catalog(&self, request: CreateCatalogRequest) -> Result<bool>;
+
+    /// Drops an empty catalog, returns whether the catalog dropped.
+    async fn drop_catalog(&self, request: DropCatalogRequest) -> Result<bool>;
+
     /// Register a system table, should be called before starting the manager.
     async fn register_system_table(&self, request: RegisterSystemTableRequest)
         -> error::Result<()>;
@@ -149,17 +159,56 @@ pub struct DeregisterTableRequest {
     pub table_name: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct RenameTableRequest {
+    pub catalog: String,
+    pub schema: String,
+    pub table_name: String,
+    pub new_table_name: String,
+    pub table_id: TableId,
+}
+
 #[derive(Debug, Clone)]
 pub struct RegisterSchemaRequest {
     pub catalog: String,
     pub schema: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct CreateCatalogRequest {
+    pub catalog: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct DropCatalogRequest {
+    pub catalog: String,
+}
+
 /// Formats table fully-qualified name
 pub fn format_full_table_name(catalog: &str, schema: &str, table: &str) -> String {
     format!("{}.{}.{}", catalog, schema, table)
 }
 
+/// Returns the fully-qualified name of any table in `catalog`, or `None` if the catalog has
+/// no table at all.
+pub(crate) fn find_any_table_in_catalog(
+    catalog_name: &str,
+    catalog: &CatalogProviderRef,
+) -> Result<Option<String>> {
+    for schema_name in catalog.schema_names()? {
+        if let Some(schema) = catalog.schema(&schema_name)? {
+            if let Some(table_name) = schema.table_names()?.into_iter().next() {
+                return Ok(Some(format_full_table_name(
+                    catalog_name,
+                    &schema_name,
+                    &table_name,
+                )));
+            }
+        }
+    }
+    Ok(None)
+}
+
 pub trait CatalogProviderFactory {
     fn create(&self, catalog_name: String) -> CatalogProviderRef;
 }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use std::any::Any;
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
@@ -34,20 +35,23 @@ use table::table::TableIdProvider;
 use table::TableRef;
 
 use crate::error::{
-    CatalogNotFoundSnafu, IllegalManagerStateSnafu, OpenTableSnafu, ReadSystemCatalogSnafu, Result,
-    SchemaExistsSnafu, SchemaNotFoundSnafu, SystemCatalogSnafu, SystemCatalogTypeMismatchSnafu,
-    TableExistsSnafu, TableNotFoundSnafu, UnimplementedSnafu,
+    CatalogExistsSnafu, CatalogNotEmptySnafu, CatalogNotFoundSnafu, IllegalManagerStateSnafu,
+    OpenTableSnafu, ReadSystemCatalogSnafu, Result, SchemaExistsSnafu, SchemaNotFoundSnafu,
+    SystemCatalogSnafu, SystemCatalogTypeMismatchSnafu, TableExistsSnafu, TableNotFoundSnafu,
+    UnimplementedSnafu,
 };
 use crate::local::memory::{MemoryCatalogManager, MemoryCatalogProvider, MemorySchemaProvider};
+use crate::privilege::{PrivilegeManager, PrivilegeManagerRef};
 use crate::system::{
     decode_system_catalog, Entry, SystemCatalogTable, TableEntry, ENTRY_TYPE_INDEX, KEY_INDEX,
     VALUE_INDEX,
 };
 use crate::tables::SystemCatalog;
 use crate::{
-    format_full_table_name, handle_system_table_request, CatalogList, CatalogManager,
-    CatalogProvider, CatalogProviderRef, DeregisterTableRequest, RegisterSchemaRequest,
-    RegisterSystemTableRequest, RegisterTableRequest, SchemaProvider, SchemaProviderRef,
+    find_any_table_in_catalog, format_full_table_name, handle_system_table_request, CatalogList,
+    CatalogManager, CatalogProvider, CatalogProviderRef, CreateCatalogRequest,
+    DeregisterTableRequest, DropCatalogRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
+    RegisterTableRequest, RenameTableRequest, SchemaProvider, SchemaProviderRef,
 };
 
 /// A `CatalogManager` consists of a system catalog and a bunch of user catalogs.
@@ -59,18 +63,30 @@ pub struct LocalCatalogManager {
     init_lock: Mutex<bool>,
     register_lock: Mutex<()>,
     system_table_requests: Mutex<Vec<RegisterSystemTableRequest>>,
+    privilege_manager: PrivilegeManagerRef,
 }
 
 impl LocalCatalogManager {
     /// Create a new [CatalogManager] with given user catalogs and table engine
     pub async fn try_new(engine: TableEngineRef) -> Result<Self> {
+        Self::try_new_with_node_id(engine, None).await
+    }
+
+    /// Create a new [CatalogManager] on the datanode `node_id`, the node id is shown in the
+    /// region statistics.
+    pub async fn try_new_with_node_id(
+        engine: TableEngineRef,
+        node_id: Option<u64>,
+    ) -> Result<Self> {
         let table = SystemCatalogTable::new(engine.clone()).await?;
         let memory_catalog_list = crate::local::memory::new_memory_catalog_list()?;
         let system_catalog = Arc::new(SystemCatalog::new(
             table,
             memory_catalog_list.clone(),
             engine.clone(),
+            node_id,
         ));
+        let privilege_manager = Arc::new(PrivilegeManager::new(Some(system_catalog.clone())));
         Ok(Self {
             system: system_catalog,
             catalogs: memory_catalog_list,
@@ -79,9 +95,15 @@ impl LocalCatalogManager {
             init_lock: Mutex::new(false),
             register_lock: Mutex::new(()),
             system_table_requests: Mutex::new(Vec::default()),
+            privilege_manager,
         })
     }
 
+    /// Returns the manager of users and roles persisted in system catalog.
+    pub fn privilege_manager(&self) -> PrivilegeManagerRef {
+        self.privilege_manager.clone()
+    }
+
     /// Scan all entries from system catalog table
     pub async fn init(&self) -> Result<()> {
         self.init_system_catalog()?;
@@ -185,9 +207,16 @@ impl LocalCatalogManager {
     async fn handle_system_catalog_entries(&self, entries: Vec<Entry>) -> Result<TableId> {
         let entries = Self::sort_entries(entries);
         let mut max_table_id = 0;
+        let mut dropped_catalogs = HashSet::new();
         for entry in entries {
             match entry {
                 Entry::Catalog(c) => {
+                    if c.is_deleted {
+                        info!("Skip dropped catalog: {}", c.catalog_name);
+                        let _ = self.catalogs.deregister_catalog(&c.catalog_name);
+                        dropped_catalogs.insert(c.catalog_name);
+                        continue;
+                    }
                     self.catalogs.register_catalog_if_absent(
                         c.catalog_name.clone(),
                         Arc::new(MemoryCatalogProvider::new()),
@@ -195,6 +224,9 @@ impl LocalCatalogManager {
                     info!("Register catalog: {}", c.catalog_name);
                 }
                 Entry::Schema(s) => {
+                    if dropped_catalogs.contains(&s.catalog_name) {
+                        continue;
+                    }
                     let catalog =
                         self.catalogs
                             .catalog(&s.catalog_name)?
@@ -208,9 +240,22 @@ impl LocalCatalogManager {
                     info!("Registered schema: {:?}", s);
                 }
                 Entry::Table(t) => {
+                    max_table_id = max_table_id.max(t.table_id);
+                    if t.is_deleted {
+                        // The table has been renamed and registered under another name.
+                        info!("Skip deleted table: {:?}", t);
+                        continue;
+                    }
                     self.open_and_register_table(&t).await?;
                     info!("Registered table: {:?}", t);
-                    max_table_id = max_table_id.max(t.table_id);
+                }
+                Entry::User(u) => {
+                    info!("Loaded user: {}", u.user_name);
+                    self.privilege_manager.load_user(u);
+                }
+                Entry::Role(r) => {
+                    info!("Loaded role: {}", r.role_name);
+                    self.privilege_manager.load_role(r);
                 }
             }
         }
@@ -377,6 +422,55 @@ impl CatalogManager for LocalCatalogManager {
         .fail()
     }
 
+    async fn rename_table(&self, request: RenameTableRequest) -> Result<bool> {
+        let started = self.init_lock.lock().await;
+
+        ensure!(
+            *started,
+            IllegalManagerStateSnafu {
+                msg: "Catalog manager not started",
+            }
+        );
+
+        let catalog_name = &request.catalog;
+        let schema_name = &request.schema;
+
+        let catalog = self
+            .catalogs
+            .catalog(catalog_name)?
+            .context(CatalogNotFoundSnafu { catalog_name })?;
+        let schema = catalog
+            .schema(schema_name)?
+            .with_context(|| SchemaNotFoundSnafu {
+                schema_info: format!("{}.{}", catalog_name, schema_name),
+            })?;
+
+        let _lock = self.register_lock.lock().await;
+        ensure!(
+            !schema.table_exist(&request.new_table_name)?,
+            TableExistsSnafu {
+                table: format_full_table_name(catalog_name, schema_name, &request.new_table_name),
+            }
+        );
+        let table = match schema.table(&request.table_name)? {
+            Some(table) => table,
+            None => return Ok(false),
+        };
+
+        self.system
+            .rename_table(
+                catalog_name.clone(),
+                schema_name.clone(),
+                request.table_name.clone(),
+                request.new_table_name.clone(),
+                request.table_id,
+            )
+            .await?;
+        schema.deregister_table(&request.table_name)?;
+        schema.register_table(request.new_table_name, table)?;
+        Ok(true)
+    }
+
     async fn register_schema(&self, request: RegisterSchemaRequest) -> Result<bool> {
         let started = self.init_lock.lock().await;
         ensure!(
@@ -409,6 +503,70 @@ impl CatalogManager for LocalCatalogManager {
         }
     }
 
+    async fn create_catalog(&self, request: CreateCatalogRequest) -> Result<bool> {
+        let started = self.init_lock.lock().await;
+        ensure!(
+            *started,
+            IllegalManagerStateSnafu {
+                msg: "Catalog manager not started",
+            }
+        );
+        let catalog_name = request.catalog;
+
+        {
+            let _lock = self.register_lock.lock().await;
+            ensure!(
+                self.catalog(&catalog_name)?.is_none(),
+                CatalogExistsSnafu {
+                    catalog: &catalog_name,
+                }
+            );
+            self.system.register_catalog(catalog_name.clone()).await?;
+            self.system
+                .register_schema(catalog_name.clone(), DEFAULT_SCHEMA_NAME.to_string())
+                .await?;
+
+            let catalog = Arc::new(MemoryCatalogProvider::new());
+            catalog.register_schema(
+                DEFAULT_SCHEMA_NAME.to_string(),
+                Arc::new(MemorySchemaProvider::new()),
+            )?;
+            self.catalogs.register_catalog(catalog_name, catalog)?;
+            Ok(true)
+        }
+    }
+
+    async fn drop_catalog(&self, request: DropCatalogRequest) -> Result<bool> {
+        let started = self.init_lock.lock().await;
+        ensure!(
+            *started,
+            IllegalManagerStateSnafu {
+                msg: "Catalog manager not started",
+            }
+        );
+        let catalog_name = request.catalog;
+
+        {
+            let _lock = self.register_lock.lock().await;
+            let catalog = self
+                .catalogs
+                .catalog(&catalog_name)?
+                .context(CatalogNotFoundSnafu {
+                    catalog_name: &catalog_name,
+                })?;
+            if let Some(table) = find_any_table_in_catalog(&catalog_name, &catalog)? {
+                return CatalogNotEmptySnafu {
+                    catalog: catalog_name,
+                    table,
+                }
+                .fail();
+            }
+            self.system.drop_catalog(catalog_name.clone()).await?;
+            let _ = self.catalogs.deregister_catalog(&catalog_name);
+            Ok(true)
+        }
+    }
+
     async fn register_system_table(&self, request: RegisterSystemTableRequest) -> Result<()> {
         ensure!(
             !*self.init_lock.lock().await,
@@ -466,9 +624,11 @@ mod tests {
                 schema_name: "S1".to_string(),
                 table_name: "T1".to_string(),
                 table_id: 1,
+                is_deleted: false,
             }),
             Entry::Catalog(CatalogEntry {
                 catalog_name: "C2".to_string(),
+                is_deleted: false,
             }),
             Entry::Schema(SchemaEntry {
                 catalog_name: "C1".to_string(),
@@ -480,12 +640,14 @@ mod tests {
             }),
             Entry::Catalog(CatalogEntry {
                 catalog_name: "".to_string(),
+                is_deleted: false,
             }),
             Entry::Table(TableEntry {
                 catalog_name: "C1".to_string(),
                 schema_name: "S1".to_string(),
                 table_name: "T2".to_string(),
                 table_id: 2,
+                is_deleted: false,
             }),
         ];
         let res = LocalCatalogManager::sort_entries(vec);
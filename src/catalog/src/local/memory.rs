@@ -18,18 +18,23 @@ use std::collections::HashMap;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::{Arc, RwLock};
 
-use common_catalog::consts::MIN_USER_TABLE_ID;
+use common_catalog::consts::{DEFAULT_SCHEMA_NAME, MIN_USER_TABLE_ID};
 use common_telemetry::error;
-use snafu::OptionExt;
+use snafu::{ensure, OptionExt};
 use table::metadata::TableId;
 use table::table::TableIdProvider;
 use table::TableRef;
 
-use crate::error::{CatalogNotFoundSnafu, Result, SchemaNotFoundSnafu, TableExistsSnafu};
+use crate::error::{
+    CatalogExistsSnafu, CatalogNotEmptySnafu, CatalogNotFoundSnafu, Result, SchemaNotFoundSnafu,
+    TableExistsSnafu,
+};
 use crate::schema::SchemaProvider;
 use crate::{
-    CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef, DeregisterTableRequest,
-    RegisterSchemaRequest, RegisterSystemTableRequest, RegisterTableRequest, SchemaProviderRef,
+    find_any_table_in_catalog, format_full_table_name, CatalogList, CatalogManager,
+    CatalogProvider, CatalogProviderRef, CreateCatalogRequest, DeregisterTableRequest,
+    DropCatalogRequest, RegisterSchemaRequest, RegisterSystemTableRequest, RegisterTableRequest,
+    RenameTableRequest, SchemaProviderRef,
 };
 
 /// Simple in-memory list of catalogs
@@ -106,6 +111,38 @@ impl CatalogManager for MemoryCatalogManager {
             .map(|v| v.is_some())
     }
 
+    async fn rename_table(&self, request: RenameTableRequest) -> Result<bool> {
+        let catalogs = self.catalogs.write().unwrap();
+        let catalog = catalogs
+            .get(&request.catalog)
+            .context(CatalogNotFoundSnafu {
+                catalog_name: &request.catalog,
+            })?
+            .clone();
+        let schema = catalog
+            .schema(&request.schema)?
+            .with_context(|| SchemaNotFoundSnafu {
+                schema_info: format!("{}.{}", &request.catalog, &request.schema),
+            })?;
+        ensure!(
+            !schema.table_exist(&request.new_table_name)?,
+            TableExistsSnafu {
+                table: format_full_table_name(
+                    &request.catalog,
+                    &request.schema,
+                    &request.new_table_name
+                ),
+            }
+        );
+        match schema.deregister_table(&request.table_name)? {
+            Some(table) => {
+                schema.register_table(request.new_table_name, table)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
     async fn register_schema(&self, request: RegisterSchemaRequest) -> Result<bool> {
         let catalogs = self.catalogs.write().unwrap();
         let catalog = catalogs
@@ -117,6 +154,40 @@ impl CatalogManager for MemoryCatalogManager {
         Ok(true)
     }
 
+    async fn create_catalog(&self, request: CreateCatalogRequest) -> Result<bool> {
+        let catalog = Arc::new(MemoryCatalogProvider::new());
+        catalog.register_schema(
+            DEFAULT_SCHEMA_NAME.to_string(),
+            Arc::new(MemorySchemaProvider::new()),
+        )?;
+        ensure!(
+            self.register_catalog_if_absent(request.catalog.clone(), catalog)
+                .is_none(),
+            CatalogExistsSnafu {
+                catalog: request.catalog,
+            }
+        );
+        Ok(true)
+    }
+
+    async fn drop_catalog(&self, request: DropCatalogRequest) -> Result<bool> {
+        let mut catalogs = self.catalogs.write().unwrap();
+        let catalog = catalogs
+            .get(&request.catalog)
+            .context(CatalogNotFoundSnafu {
+                catalog_name: &request.catalog,
+            })?;
+        if let Some(table) = find_any_table_in_catalog(&request.catalog, catalog)? {
+            return CatalogNotEmptySnafu {
+                catalog: request.catalog,
+                table,
+            }
+            .fail();
+        }
+        catalogs.remove(&request.catalog);
+        Ok(true)
+    }
+
     async fn register_system_table(&self, _request: RegisterSystemTableRequest) -> Result<()> {
         // TODO(ruihang): support register system table request
         Ok(())
@@ -163,6 +234,12 @@ impl MemoryCatalogManager {
             }
         }
     }
+
+    /// Removes the catalog `name`, returns the removed catalog if it exists.
+    pub fn deregister_catalog(&self, name: &str) -> Option<CatalogProviderRef> {
+        let mut catalogs = self.catalogs.write().unwrap();
+        catalogs.remove(name)
+    }
 }
 
 impl CatalogList for MemoryCatalogManager {
@@ -400,4 +477,94 @@ mod tests {
             .unwrap();
         assert!(!schema.table_exist("numbers").unwrap());
     }
+
+    #[tokio::test]
+    pub async fn test_create_and_drop_catalog() {
+        let manager = MemoryCatalogManager::default();
+        let create_req = || CreateCatalogRequest {
+            catalog: "team_a".to_string(),
+        };
+        assert!(manager.create_catalog(create_req()).await.unwrap());
+        assert!(manager
+            .schema("team_a", DEFAULT_SCHEMA_NAME)
+            .unwrap()
+            .is_some());
+        let err = manager.create_catalog(create_req()).await.unwrap_err();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        let register_table_req = RegisterTableRequest {
+            catalog: "team_a".to_string(),
+            schema: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: "numbers".to_string(),
+            table_id: 2333,
+            table: Arc::new(NumbersTable::default()),
+        };
+        manager.register_table(register_table_req).await.unwrap();
+
+        let drop_req = || DropCatalogRequest {
+            catalog: "team_a".to_string(),
+        };
+        // Drop a catalog that still has tables.
+        let err = manager.drop_catalog(drop_req()).await.unwrap_err();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+
+        let deregister_table_req = DeregisterTableRequest {
+            catalog: "team_a".to_string(),
+            schema: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: "numbers".to_string(),
+        };
+        manager
+            .deregister_table(deregister_table_req)
+            .await
+            .unwrap();
+        assert!(manager.drop_catalog(drop_req()).await.unwrap());
+        assert!(manager.catalog("team_a").unwrap().is_none());
+    }
+
+    #[tokio::test]
+    pub async fn test_catalog_rename_table() {
+        let catalog = MemoryCatalogManager::default();
+        let schema = catalog
+            .schema(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME)
+            .unwrap()
+            .unwrap();
+
+        for table_name in ["numbers", "other"] {
+            let register_table_req = RegisterTableRequest {
+                catalog: DEFAULT_CATALOG_NAME.to_string(),
+                schema: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: table_name.to_string(),
+                table_id: 2333,
+                table: Arc::new(NumbersTable::default()),
+            };
+            catalog.register_table(register_table_req).await.unwrap();
+        }
+
+        let new_rename_req = |new_table_name: &str| RenameTableRequest {
+            catalog: DEFAULT_CATALOG_NAME.to_string(),
+            schema: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: "numbers".to_string(),
+            new_table_name: new_table_name.to_string(),
+            table_id: 2333,
+        };
+        // Rename to an existing table.
+        let err = catalog
+            .rename_table(new_rename_req("other"))
+            .await
+            .unwrap_err();
+        assert_eq!(StatusCode::TableAlreadyExists, err.status_code());
+
+        assert!(catalog
+            .rename_table(new_rename_req("new_numbers"))
+            .await
+            .unwrap());
+        assert!(!schema.table_exist("numbers").unwrap());
+        assert!(schema.table_exist("new_numbers").unwrap());
+
+        // The table to rename doesn't exist.
+        assert!(!catalog
+            .rename_table(new_rename_req("new_numbers2"))
+            .await
+            .unwrap());
+    }
 }
@@ -0,0 +1,394 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Users and roles created by `CREATE USER` and `CREATE ROLE`, and privileges granted by `GRANT`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, RwLock};
+
+use common_catalog::consts::DEFAULT_CATALOG_NAME;
+use common_catalog::privilege::{GrantObject, GrantedPrivilege, Privilege, SUPERUSER};
+use futures_util::lock::Mutex;
+use snafu::{ensure, OptionExt};
+
+use crate::error::{
+    Result, RoleExistsSnafu, RoleNotFoundSnafu, UserExistsSnafu, UserNotFoundSnafu,
+};
+use crate::system::{RoleEntry, RoleEntryValue, UserEntry, UserEntryValue};
+use crate::tables::SystemCatalog;
+
+pub type PrivilegeManagerRef = Arc<PrivilegeManager>;
+
+/// Users and roles are scoped by the catalog of their tenant, so different tenants can have
+/// users or roles of the same name.
+type TenantKey = (String, String);
+
+/// Manages users, roles and the privileges granted to roles.
+///
+/// Only users created by `CREATE USER` are managed, their privileges are the union of
+/// the privileges of their roles. The [SUPERUSER] has all privileges, while other users, like
+/// those only known to the user provider of servers, have none.
+///
+/// Users and roles belong to the catalog of a tenant, those created in sessions not bound to
+/// any tenant belong to the default catalog.
+#[derive(Default)]
+pub struct PrivilegeManager {
+    /// Where users and roles are persisted, they are kept in memory only if absent.
+    system: Option<Arc<SystemCatalog>>,
+    users: RwLock<HashMap<TenantKey, BTreeSet<String>>>,
+    roles: RwLock<HashMap<TenantKey, BTreeSet<GrantedPrivilege>>>,
+    /// Serializes modifications so the persisted entries are in line with the memory.
+    write_lock: Mutex<()>,
+}
+
+impl PrivilegeManager {
+    pub fn new(system: Option<Arc<SystemCatalog>>) -> Self {
+        Self {
+            system,
+            ..Default::default()
+        }
+    }
+
+    /// Loads a user entry read from system catalog.
+    pub fn load_user(&self, entry: UserEntry) {
+        self.users
+            .write()
+            .unwrap()
+            .insert((entry.catalog_name, entry.user_name), entry.roles);
+    }
+
+    /// Loads a role entry read from system catalog.
+    pub fn load_role(&self, entry: RoleEntry) {
+        self.roles
+            .write()
+            .unwrap()
+            .insert((entry.catalog_name, entry.role_name), entry.privileges);
+    }
+
+    pub async fn create_user(&self, tenant: Option<&str>, user: &str) -> Result<()> {
+        let _lock = self.write_lock.lock().await;
+        let key = tenant_key(tenant, user);
+        ensure!(
+            user != SUPERUSER && !self.user_exists(&key),
+            UserExistsSnafu { user }
+        );
+        self.put_user(key, BTreeSet::new()).await
+    }
+
+    pub async fn create_role(&self, tenant: Option<&str>, role: &str) -> Result<()> {
+        let _lock = self.write_lock.lock().await;
+        let key = tenant_key(tenant, role);
+        ensure!(!self.role_exists(&key), RoleExistsSnafu { role });
+        self.put_role(key, BTreeSet::new()).await
+    }
+
+    /// Grants `privileges` on `object` to `role`.
+    pub async fn grant_privileges(
+        &self,
+        tenant: Option<&str>,
+        role: &str,
+        privileges: &[Privilege],
+        object: &GrantObject,
+    ) -> Result<()> {
+        let _lock = self.write_lock.lock().await;
+        let key = tenant_key(tenant, role);
+        let mut granted = self.role_privileges(&key)?;
+        granted.extend(
+            privileges
+                .iter()
+                .map(|privilege| GrantedPrivilege::new(*privilege, object.clone())),
+        );
+        self.put_role(key, granted).await
+    }
+
+    /// Revokes `privileges` on `object` from `role`, the object must be the same as the one
+    /// in GRANT, e.g. revoking on `public.cpu` doesn't affect privileges granted on `public.*`.
+    pub async fn revoke_privileges(
+        &self,
+        tenant: Option<&str>,
+        role: &str,
+        privileges: &[Privilege],
+        object: &GrantObject,
+    ) -> Result<()> {
+        let _lock = self.write_lock.lock().await;
+        let key = tenant_key(tenant, role);
+        let mut granted = self.role_privileges(&key)?;
+        for privilege in privileges {
+            granted.remove(&GrantedPrivilege::new(*privilege, object.clone()));
+        }
+        self.put_role(key, granted).await
+    }
+
+    pub async fn grant_role(&self, tenant: Option<&str>, role: &str, user: &str) -> Result<()> {
+        let _lock = self.write_lock.lock().await;
+        ensure!(
+            self.role_exists(&tenant_key(tenant, role)),
+            RoleNotFoundSnafu { role }
+        );
+        let key = tenant_key(tenant, user);
+        let mut roles = self.user_roles(&key)?;
+        roles.insert(role.to_string());
+        self.put_user(key, roles).await
+    }
+
+    pub async fn revoke_role(&self, tenant: Option<&str>, role: &str, user: &str) -> Result<()> {
+        let _lock = self.write_lock.lock().await;
+        ensure!(
+            self.role_exists(&tenant_key(tenant, role)),
+            RoleNotFoundSnafu { role }
+        );
+        let key = tenant_key(tenant, user);
+        let mut roles = self.user_roles(&key)?;
+        roles.remove(role);
+        self.put_user(key, roles).await
+    }
+
+    /// Returns whether the user is created by `CREATE USER` so its privileges are checked.
+    pub fn is_managed(&self, tenant: Option<&str>, user: &str) -> bool {
+        self.user_exists(&tenant_key(tenant, user))
+    }
+
+    /// Returns whether `user` of `tenant` has `privilege` on table `schema.table`, unmanaged
+    /// users other than the [SUPERUSER] have no privileges.
+    pub fn check(
+        &self,
+        tenant: Option<&str>,
+        user: &str,
+        privilege: Privilege,
+        schema: &str,
+        table: &str,
+    ) -> bool {
+        if user == SUPERUSER {
+            return true;
+        }
+        let catalog = tenant_catalog(tenant);
+        let users = self.users.read().unwrap();
+        let roles = match users.get(&(catalog.clone(), user.to_string())) {
+            Some(roles) => roles,
+            None => return false,
+        };
+        let granted = self.roles.read().unwrap();
+        roles
+            .iter()
+            .filter_map(|role| granted.get(&(catalog.clone(), role.clone())))
+            .flatten()
+            .any(|p| p.privilege == privilege && p.object.matches(schema, table))
+    }
+
+    fn user_exists(&self, key: &TenantKey) -> bool {
+        self.users.read().unwrap().contains_key(key)
+    }
+
+    fn role_exists(&self, key: &TenantKey) -> bool {
+        self.roles.read().unwrap().contains_key(key)
+    }
+
+    fn user_roles(&self, key: &TenantKey) -> Result<BTreeSet<String>> {
+        self.users
+            .read()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .context(UserNotFoundSnafu { user: &key.1 })
+    }
+
+    fn role_privileges(&self, key: &TenantKey) -> Result<BTreeSet<GrantedPrivilege>> {
+        self.roles
+            .read()
+            .unwrap()
+            .get(key)
+            .cloned()
+            .context(RoleNotFoundSnafu { role: &key.1 })
+    }
+
+    async fn put_user(&self, key: TenantKey, roles: BTreeSet<String>) -> Result<()> {
+        let value = UserEntryValue { roles };
+        if let Some(system) = &self.system {
+            system.put_user(&key.0, &key.1, &value).await?;
+        }
+        self.users.write().unwrap().insert(key, value.roles);
+        Ok(())
+    }
+
+    async fn put_role(&self, key: TenantKey, privileges: BTreeSet<GrantedPrivilege>) -> Result<()> {
+        let value = RoleEntryValue { privileges };
+        if let Some(system) = &self.system {
+            system.put_role(&key.0, &key.1, &value).await?;
+        }
+        self.roles.write().unwrap().insert(key, value.privileges);
+        Ok(())
+    }
+}
+
+fn tenant_catalog(tenant: Option<&str>) -> String {
+    tenant.unwrap_or(DEFAULT_CATALOG_NAME).to_string()
+}
+
+fn tenant_key(tenant: Option<&str>, name: &str) -> TenantKey {
+    (tenant_catalog(tenant), name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use common_catalog::privilege::WILDCARD;
+
+    use super::*;
+    use crate::error::Error;
+
+    #[tokio::test]
+    async fn test_check_privileges() {
+        let manager = PrivilegeManager::default();
+        manager.create_user(None, "dashboard").await.unwrap();
+        manager.create_role(None, "reader").await.unwrap();
+
+        // A managed user without roles has no privileges, neither do unmanaged users except
+        // the superuser.
+        assert!(manager.is_managed(None, "dashboard"));
+        assert!(!manager.check(None, "dashboard", Privilege::Select, "public", "cpu"));
+        assert!(!manager.is_managed(None, "admin"));
+        assert!(!manager.check(None, "admin", Privilege::Select, "public", "cpu"));
+        assert!(manager.check(None, SUPERUSER, Privilege::Drop, "public", "cpu"));
+
+        manager
+            .grant_privileges(
+                None,
+                "reader",
+                &[Privilege::Select],
+                &GrantObject::new("public", WILDCARD),
+            )
+            .await
+            .unwrap();
+        manager
+            .grant_role(None, "reader", "dashboard")
+            .await
+            .unwrap();
+        assert!(manager.check(None, "dashboard", Privilege::Select, "public", "cpu"));
+        assert!(!manager.check(None, "dashboard", Privilege::Select, "other", "cpu"));
+        assert!(!manager.check(None, "dashboard", Privilege::Drop, "public", "cpu"));
+
+        manager
+            .revoke_privileges(
+                None,
+                "reader",
+                &[Privilege::Select],
+                &GrantObject::new("public", "cpu"),
+            )
+            .await
+            .unwrap();
+        assert!(manager.check(None, "dashboard", Privilege::Select, "public", "cpu"));
+
+        manager
+            .revoke_role(None, "reader", "dashboard")
+            .await
+            .unwrap();
+        assert!(!manager.check(None, "dashboard", Privilege::Select, "public", "cpu"));
+    }
+
+    #[tokio::test]
+    async fn test_tenant_scoped_users() {
+        let manager = PrivilegeManager::default();
+        manager
+            .create_user(Some("team_a"), "dashboard")
+            .await
+            .unwrap();
+        manager.create_role(Some("team_a"), "reader").await.unwrap();
+        manager
+            .grant_privileges(
+                Some("team_a"),
+                "reader",
+                &[Privilege::Select],
+                &GrantObject::new("public", WILDCARD),
+            )
+            .await
+            .unwrap();
+        manager
+            .grant_role(Some("team_a"), "reader", "dashboard")
+            .await
+            .unwrap();
+        assert!(manager.check(
+            Some("team_a"),
+            "dashboard",
+            Privilege::Select,
+            "public",
+            "cpu"
+        ));
+
+        // Users and roles of a tenant are unknown to other tenants.
+        assert!(!manager.is_managed(None, "dashboard"));
+        assert!(!manager.check(None, "dashboard", Privilege::Select, "public", "cpu"));
+        assert!(!manager.check(
+            Some("team_b"),
+            "dashboard",
+            Privilege::Select,
+            "public",
+            "cpu"
+        ));
+        manager
+            .create_user(Some("team_b"), "dashboard")
+            .await
+            .unwrap();
+        assert!(matches!(
+            manager
+                .grant_role(Some("team_b"), "reader", "dashboard")
+                .await,
+            Err(Error::RoleNotFound { .. })
+        ));
+        assert!(!manager.check(
+            Some("team_b"),
+            "dashboard",
+            Privilege::Select,
+            "public",
+            "cpu"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_privilege_manager_errors() {
+        let manager = PrivilegeManager::default();
+        manager.create_user(None, "u").await.unwrap();
+        manager.create_role(None, "r").await.unwrap();
+
+        assert!(matches!(
+            manager.create_user(None, "u").await,
+            Err(Error::UserExists { .. })
+        ));
+        assert!(matches!(
+            manager.create_user(None, SUPERUSER).await,
+            Err(Error::UserExists { .. })
+        ));
+        assert!(matches!(
+            manager.create_role(None, "r").await,
+            Err(Error::RoleExists { .. })
+        ));
+        assert!(matches!(
+            manager.grant_role(None, "r", "unknown").await,
+            Err(Error::UserNotFound { .. })
+        ));
+        assert!(matches!(
+            manager.grant_role(None, "unknown", "u").await,
+            Err(Error::RoleNotFound { .. })
+        ));
+        assert!(matches!(
+            manager
+                .grant_privileges(
+                    None,
+                    "unknown",
+                    &Privilege::ALL,
+                    &GrantObject::new("*", "*")
+                )
+                .await,
+            Err(Error::RoleNotFound { .. })
+        ));
+    }
+}
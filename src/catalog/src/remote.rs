@@ -20,11 +20,13 @@ pub use client::MetaKvBackend;
 use futures::Stream;
 use futures_util::StreamExt;
 pub use manager::{RemoteCatalogManager, RemoteCatalogProvider, RemoteSchemaProvider};
+pub use watch::{spawn_watch, WatchHandler};
 
 use crate::error::Error;
 
 mod client;
 mod manager;
+mod watch;
 
 #[derive(Debug, Clone)]
 pub struct Kv(pub Vec<u8>, pub Vec<u8>);
@@ -13,17 +13,19 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::pin::Pin;
 use std::sync::Arc;
 
 use arc_swap::ArcSwap;
 use async_stream::stream;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, MIN_USER_TABLE_ID};
-use common_telemetry::{debug, info};
+use common_telemetry::{debug, info, warn};
 use futures::Stream;
 use futures_util::StreamExt;
-use snafu::{OptionExt, ResultExt};
+use meta_client::client::MetaClient;
+use meta_client::rpc::EventType;
+use snafu::{ensure, OptionExt, ResultExt};
 use table::engine::{EngineContext, TableEngineRef};
 use table::metadata::TableId;
 use table::requests::{CreateTableRequest, OpenTableRequest};
@@ -32,18 +34,21 @@ use table::TableRef;
 use tokio::sync::Mutex;
 
 use crate::error::{
-    CatalogNotFoundSnafu, CreateTableSnafu, InvalidCatalogValueSnafu, InvalidTableSchemaSnafu,
-    OpenTableSnafu, Result, SchemaNotFoundSnafu, TableExistsSnafu, UnimplementedSnafu,
+    CatalogExistsSnafu, CatalogNotEmptySnafu, CatalogNotFoundSnafu, CreateTableSnafu,
+    InvalidCatalogValueSnafu, InvalidTableSchemaSnafu, OpenTableSnafu, Result, SchemaNotFoundSnafu,
+    TableExistsSnafu, UnimplementedSnafu,
 };
 use crate::helper::{
-    build_catalog_prefix, build_schema_prefix, build_table_global_prefix, CatalogKey, CatalogValue,
-    SchemaKey, SchemaValue, TableGlobalKey, TableGlobalValue, TableRegionalKey, TableRegionalValue,
+    build_all_table_global_prefix, build_catalog_prefix, build_schema_prefix,
+    build_table_global_prefix, CatalogKey, CatalogValue, SchemaKey, SchemaValue, TableGlobalKey,
+    TableGlobalValue, TableRegionalKey, TableRegionalValue,
 };
-use crate::remote::{Kv, KvBackendRef};
+use crate::remote::{spawn_watch, Kv, KvBackendRef, WatchHandler};
 use crate::{
-    handle_system_table_request, CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef,
-    DeregisterTableRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
-    RegisterTableRequest, SchemaProvider, SchemaProviderRef,
+    find_any_table_in_catalog, format_full_table_name, handle_system_table_request, CatalogList,
+    CatalogManager, CatalogProvider, CatalogProviderRef, CreateCatalogRequest,
+    DeregisterTableRequest, DropCatalogRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
+    RegisterTableRequest, RenameTableRequest, SchemaProvider, SchemaProviderRef,
 };
 
 /// Catalog manager based on metasrv.
@@ -95,6 +100,77 @@ impl RemoteCatalogManager {
         }) as _
     }
 
+    /// Starts watching the catalogs and tables changed by other nodes in metasrv, the changes
+    /// are applied to the cached catalogs and opened tables. Schemas are reloaded from metasrv
+    /// on each access, so they aren't watched.
+    pub fn start_watching(self: &Arc<Self>, meta_client: Arc<MetaClient>) {
+        for prefix in [build_catalog_prefix(), build_all_table_global_prefix()] {
+            spawn_watch(meta_client.clone(), prefix, self.clone());
+        }
+    }
+
+    /// Reloads the catalog names from metasrv, the providers of the remaining catalogs are kept.
+    async fn refresh_catalogs(&self) -> Result<()> {
+        let mut catalog_names = HashSet::new();
+        let mut catalogs = self.iter_remote_catalogs().await;
+        while let Some(r) = catalogs.next().await {
+            let _ = catalog_names.insert(r?.catalog_name);
+        }
+
+        let _guard = self.mutex.lock().await;
+        let prev_catalogs = self.catalogs.load();
+        let new_catalogs = catalog_names
+            .into_iter()
+            .map(|name| {
+                let catalog = prev_catalogs
+                    .get(&name)
+                    .cloned()
+                    .unwrap_or_else(|| self.new_catalog_provider(&name));
+                (name, catalog)
+            })
+            .collect::<HashMap<_, _>>();
+        self.catalogs.store(Arc::new(new_catalogs));
+        Ok(())
+    }
+
+    /// Adds or removes the cached catalog created or dropped by other nodes.
+    async fn apply_catalog_change(&self, event_type: EventType, catalog_name: String) {
+        let _guard = self.mutex.lock().await;
+        let prev_catalogs = self.catalogs.load();
+        let mut new_catalogs = HashMap::with_capacity(prev_catalogs.len() + 1);
+        new_catalogs.clone_from(&prev_catalogs);
+        match event_type {
+            EventType::Put if !prev_catalogs.contains_key(&catalog_name) => {
+                let catalog = self.new_catalog_provider(&catalog_name);
+                let _ = new_catalogs.insert(catalog_name.clone(), catalog);
+            }
+            EventType::Delete if prev_catalogs.contains_key(&catalog_name) => {
+                let _ = new_catalogs.remove(&catalog_name);
+            }
+            _ => return,
+        }
+        self.catalogs.store(Arc::new(new_catalogs));
+        info!("Catalog {} is changed: {:?}", catalog_name, event_type);
+    }
+
+    /// Evicts the opened table which is dropped or replaced by other nodes, `table_id` is the
+    /// id of the table this datanode should serve, `None` if there is no such table.
+    async fn apply_table_change(&self, key: TableGlobalKey, table_id: Option<TableId>) {
+        let catalog = match self.catalogs.load().get(&key.catalog_name) {
+            Some(catalog) => catalog.clone(),
+            None => return,
+        };
+        let schema = match catalog.as_any().downcast_ref::<RemoteCatalogProvider>() {
+            Some(catalog) => catalog.schemas.load().get(&key.schema_name).cloned(),
+            None => None,
+        };
+        if let Some(schema) = schema {
+            if let Some(schema) = schema.as_any().downcast_ref::<RemoteSchemaProvider>() {
+                schema.evict_table(&key.table_name, table_id).await;
+            }
+        }
+    }
+
     async fn iter_remote_catalogs(
         &self,
     ) -> Pin<Box<dyn Stream<Item = Result<CatalogKey>> + Send + '_>> {
@@ -440,6 +516,34 @@ impl CatalogManager for RemoteCatalogManager {
         .fail()
     }
 
+    async fn rename_table(&self, request: RenameTableRequest) -> Result<bool> {
+        let catalog_name = &request.catalog;
+        let schema_name = &request.schema;
+        let schema_provider = self
+            .catalog(catalog_name)?
+            .context(CatalogNotFoundSnafu { catalog_name })?
+            .schema(schema_name)?
+            .with_context(|| SchemaNotFoundSnafu {
+                schema_info: format!("{}.{}", catalog_name, schema_name),
+            })?;
+        ensure!(
+            !schema_provider.table_exist(&request.new_table_name)?,
+            TableExistsSnafu {
+                table: format_full_table_name(catalog_name, schema_name, &request.new_table_name),
+            }
+        );
+        let table = match schema_provider.table(&request.table_name)? {
+            Some(table) => table,
+            None => return Ok(false),
+        };
+
+        // Only the regional entries of this datanode are moved here, the global table value is
+        // renamed by the frontend in metasrv.
+        schema_provider.register_table(request.new_table_name, table)?;
+        schema_provider.deregister_table(&request.table_name)?;
+        Ok(true)
+    }
+
     async fn register_schema(&self, request: RegisterSchemaRequest) -> Result<bool> {
         let catalog_name = request.catalog;
         let schema_name = request.schema;
@@ -451,6 +555,86 @@ impl CatalogManager for RemoteCatalogManager {
         Ok(true)
     }
 
+    async fn create_catalog(&self, request: CreateCatalogRequest) -> Result<bool> {
+        let catalog_name = request.catalog;
+        ensure!(
+            self.catalog(&catalog_name)?.is_none(),
+            CatalogExistsSnafu {
+                catalog: &catalog_name,
+            }
+        );
+
+        // The keys are written before the catalog is visible, so that other nodes watching the
+        // catalog key always find its default schema. The schemas of the provider are loaded
+        // from the backend once accessed.
+        let _guard = self.mutex.lock().await;
+        let schema_key = SchemaKey {
+            catalog_name: catalog_name.clone(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        }
+        .to_string();
+        self.backend
+            .set(
+                schema_key.as_bytes(),
+                &SchemaValue {}
+                    .as_bytes()
+                    .context(InvalidCatalogValueSnafu)?,
+            )
+            .await?;
+        let catalog_key = self.build_catalog_key(&catalog_name).to_string();
+        self.backend
+            .set(
+                catalog_key.as_bytes(),
+                &CatalogValue {}
+                    .as_bytes()
+                    .context(InvalidCatalogValueSnafu)?,
+            )
+            .await?;
+
+        let catalog_provider = self.new_catalog_provider(&catalog_name);
+        let prev_catalogs = self.catalogs.load();
+        let mut new_catalogs = HashMap::with_capacity(prev_catalogs.len() + 1);
+        new_catalogs.clone_from(&prev_catalogs);
+        new_catalogs.insert(catalog_name.clone(), catalog_provider);
+        self.catalogs.store(Arc::new(new_catalogs));
+        info!("Created catalog: {}", catalog_name);
+        Ok(true)
+    }
+
+    async fn drop_catalog(&self, request: DropCatalogRequest) -> Result<bool> {
+        let catalog_name = request.catalog;
+        let catalog_provider = self.catalog(&catalog_name)?.context(CatalogNotFoundSnafu {
+            catalog_name: &catalog_name,
+        })?;
+        if let Some(table) = find_any_table_in_catalog(&catalog_name, &catalog_provider)? {
+            return CatalogNotEmptySnafu {
+                catalog: catalog_name,
+                table,
+            }
+            .fail();
+        }
+
+        let _guard = self.mutex.lock().await;
+        for schema_name in catalog_provider.schema_names()? {
+            let schema_key = SchemaKey {
+                catalog_name: catalog_name.clone(),
+                schema_name,
+            }
+            .to_string();
+            self.backend.delete(schema_key.as_bytes()).await?;
+        }
+        let catalog_key = self.build_catalog_key(&catalog_name).to_string();
+        self.backend.delete(catalog_key.as_bytes()).await?;
+
+        let prev_catalogs = self.catalogs.load();
+        let mut new_catalogs = HashMap::with_capacity(prev_catalogs.len());
+        new_catalogs.clone_from(&prev_catalogs);
+        new_catalogs.remove(&catalog_name);
+        self.catalogs.store(Arc::new(new_catalogs));
+        info!("Dropped catalog: {}", catalog_name);
+        Ok(true)
+    }
+
     async fn register_system_table(&self, request: RegisterSystemTableRequest) -> Result<()> {
         let mut requests = self.system_table_requests.lock().await;
         requests.push(request);
@@ -483,6 +667,37 @@ impl CatalogManager for RemoteCatalogManager {
     }
 }
 
+#[async_trait::async_trait]
+impl WatchHandler for RemoteCatalogManager {
+    async fn handle(&self, event_type: EventType, key: &[u8], value: &[u8]) {
+        let key = String::from_utf8_lossy(key);
+        if let Ok(CatalogKey { catalog_name }) = CatalogKey::parse(&key) {
+            self.apply_catalog_change(event_type, catalog_name).await;
+        } else if let Ok(table_key) = TableGlobalKey::parse(&key) {
+            let table_id = match event_type {
+                EventType::Put => TableGlobalValue::from_bytes(value)
+                    .ok()
+                    .filter(|table_value| {
+                        table_value
+                            .regions_id_map
+                            .get(&self.node_id)
+                            .map(|regions| !regions.is_empty())
+                            .unwrap_or(false)
+                    })
+                    .map(|table_value| table_value.table_id()),
+                EventType::Delete => None,
+            };
+            self.apply_table_change(table_key, table_id).await;
+        }
+    }
+
+    async fn reset(&self) {
+        if let Err(e) = self.refresh_catalogs().await {
+            warn!("Failed to refresh catalogs, error: {}", e);
+        }
+    }
+}
+
 impl CatalogList for RemoteCatalogManager {
     fn as_any(&self) -> &dyn Any {
         self
@@ -564,24 +779,25 @@ impl RemoteCatalogProvider {
                 let _guard = mutex.lock().await;
                 let prev_schemas = schemas.load();
                 let mut new_schemas = HashMap::with_capacity(prev_schemas.len() + 1);
-                new_schemas.clone_from(&prev_schemas);
 
+                // Schemas dropped by other nodes are removed as well.
                 let mut remote_schemas = backend.range(schema_prefix.as_bytes());
                 while let Some(r) = remote_schemas.next().await {
                     let Kv(k, _) = r?;
                     let schema_key = SchemaKey::parse(&String::from_utf8_lossy(&k))
                         .context(InvalidCatalogValueSnafu)?;
-                    if !new_schemas.contains_key(&schema_key.schema_name) {
-                        new_schemas.insert(
-                            schema_key.schema_name.clone(),
+                    let schema = prev_schemas
+                        .get(&schema_key.schema_name)
+                        .cloned()
+                        .unwrap_or_else(|| {
                             Arc::new(RemoteSchemaProvider::new(
                                 catalog_name.clone(),
-                                schema_key.schema_name,
+                                schema_key.schema_name.clone(),
                                 node_id,
                                 backend.clone(),
-                            )),
-                        );
-                    }
+                            ))
+                        });
+                    let _ = new_schemas.insert(schema_key.schema_name, schema);
                 }
                 schemas.store(Arc::new(new_schemas));
                 Ok(())
@@ -678,6 +894,24 @@ impl RemoteSchemaProvider {
         }
     }
 
+    /// Evicts the opened table unless its id is `table_id`, only the cache is changed.
+    async fn evict_table(&self, table_name: &str, table_id: Option<TableId>) {
+        let _guard = self.mutex.lock().await;
+        let prev_tables = self.tables.load();
+        match prev_tables.get(table_name) {
+            Some(table) if Some(table.table_info().ident.table_id) != table_id => {}
+            _ => return,
+        }
+        let mut new_tables = HashMap::with_capacity(prev_tables.len());
+        new_tables.clone_from(&prev_tables);
+        let _ = new_tables.remove(table_name);
+        self.tables.store(Arc::new(new_tables));
+        info!(
+            "Evicted table {}.{}.{}",
+            self.catalog_name, self.schema_name, table_name
+        );
+    }
+
     fn build_regional_table_key(&self, table_name: impl AsRef<str>) -> TableRegionalKey {
         TableRegionalKey {
             catalog_name: self.catalog_name.clone(),
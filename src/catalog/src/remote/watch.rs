@@ -0,0 +1,76 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use common_telemetry::{info, warn};
+use meta_client::client::MetaClient;
+use meta_client::rpc::{EventType, WatchRequest};
+
+const WATCH_RETRY_INTERVAL: Duration = Duration::from_secs(3);
+
+/// Handles the changes of the keys watched in metasrv, usually by evicting the cached entries.
+#[async_trait::async_trait]
+pub trait WatchHandler: Send + Sync {
+    async fn handle(&self, event_type: EventType, key: &[u8], value: &[u8]);
+
+    /// Called once the watch stream is broken, the changes made before the stream is created
+    /// again are missed, so nothing cached is trustworthy.
+    async fn reset(&self);
+}
+
+/// Watches the keys with the `prefix` in metasrv in background, the watch is retried until the
+/// process exits.
+pub fn spawn_watch(meta_client: Arc<MetaClient>, prefix: String, handler: Arc<dyn WatchHandler>) {
+    common_runtime::spawn_bg(async move {
+        loop {
+            watch(&meta_client, &prefix, &*handler).await;
+            handler.reset().await;
+            tokio::time::sleep(WATCH_RETRY_INTERVAL).await;
+        }
+    });
+}
+
+async fn watch(meta_client: &MetaClient, prefix: &str, handler: &dyn WatchHandler) {
+    let req = WatchRequest::new().with_prefix(prefix.as_bytes());
+    let mut stream = match meta_client.watch(req).await {
+        Ok(stream) => stream,
+        Err(e) => {
+            warn!("Failed to watch key prefix {}, error: {}", prefix, e);
+            return;
+        }
+    };
+    info!("Start watching key prefix: {}", prefix);
+    loop {
+        match stream.message().await {
+            Ok(Some(mut res)) => {
+                for mut event in res.take_events() {
+                    let event_type = event.event_type();
+                    if let Some(kv) = event.take_kv() {
+                        handler.handle(event_type, kv.key(), kv.value()).await;
+                    }
+                }
+            }
+            Ok(None) => {
+                info!("Watch stream of key prefix {} is closed", prefix);
+                return;
+            }
+            Err(e) => {
+                warn!("Failed to receive watch events, error: {}", e);
+                return;
+            }
+        }
+    }
+}
@@ -13,13 +13,14 @@
 // limitations under the License.
 
 use std::any::Any;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::sync::Arc;
 
 use common_catalog::consts::{
     DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, INFORMATION_SCHEMA_NAME, SYSTEM_CATALOG_NAME,
     SYSTEM_CATALOG_TABLE_ID, SYSTEM_CATALOG_TABLE_NAME,
 };
+use common_catalog::privilege::GrantedPrivilege;
 use common_query::logical_plan::Expr;
 use common_query::physical_plan::{PhysicalPlanRef, RuntimeEnv};
 use common_recordbatch::SendableRecordBatchStream;
@@ -34,6 +35,7 @@ use snafu::{ensure, OptionExt, ResultExt};
 use table::engine::{EngineContext, TableEngineRef};
 use table::metadata::{TableId, TableInfoRef};
 use table::requests::{CreateTableRequest, InsertRequest, OpenTableRequest};
+use table::table::RegionStat;
 use table::{Table, TableRef};
 
 use crate::error::{
@@ -77,6 +79,10 @@ impl Table for SystemCatalogTable {
     fn table_info(&self) -> TableInfoRef {
         self.table_info.clone()
     }
+
+    fn region_stats(&self) -> table::Result<Vec<RegionStat>> {
+        self.table.region_stats()
+    }
 }
 
 impl SystemCatalogTable {
@@ -190,7 +196,47 @@ pub fn build_table_insert_request(full_table_name: String, table_id: TableId) ->
     build_insert_request(
         EntryType::Table,
         full_table_name.as_bytes(),
-        serde_json::to_string(&TableEntryValue { table_id })
+        serde_json::to_string(&TableEntryValue {
+            table_id,
+            is_deleted: false,
+        })
+        .unwrap()
+        .as_bytes(),
+    )
+}
+
+/// Builds a request to overwrite the entry of table `full_table_name` by a tombstone, so
+/// the table won't be opened under this name again.
+pub fn build_table_deletion_request(full_table_name: String, table_id: TableId) -> InsertRequest {
+    build_insert_request(
+        EntryType::Table,
+        full_table_name.as_bytes(),
+        serde_json::to_string(&TableEntryValue {
+            table_id,
+            is_deleted: true,
+        })
+        .unwrap()
+        .as_bytes(),
+    )
+}
+
+pub fn build_catalog_insert_request(catalog_name: String) -> InsertRequest {
+    build_insert_request(
+        EntryType::Catalog,
+        catalog_name.as_bytes(),
+        serde_json::to_string(&CatalogEntryValue { is_deleted: false })
+            .unwrap()
+            .as_bytes(),
+    )
+}
+
+/// Builds a request to overwrite the entry of catalog `catalog_name` by a tombstone, so
+/// the catalog won't be registered again.
+pub fn build_catalog_deletion_request(catalog_name: String) -> InsertRequest {
+    build_insert_request(
+        EntryType::Catalog,
+        catalog_name.as_bytes(),
+        serde_json::to_string(&CatalogEntryValue { is_deleted: true })
             .unwrap()
             .as_bytes(),
     )
@@ -207,41 +253,111 @@ pub fn build_schema_insert_request(catalog_name: String, schema_name: String) ->
     )
 }
 
+/// Builds a request to overwrite the entry of user `user_name` of the tenant owning
+/// `catalog_name` with the roles granted to it.
+pub fn build_user_insert_request(
+    catalog_name: &str,
+    user_name: &str,
+    value: &UserEntryValue,
+) -> InsertRequest {
+    let full_user_name = format!("{}.{}", catalog_name, user_name);
+    build_insert_request(
+        EntryType::User,
+        full_user_name.as_bytes(),
+        serde_json::to_string(value).unwrap().as_bytes(),
+    )
+}
+
+/// Builds a request to overwrite the entry of role `role_name` of the tenant owning
+/// `catalog_name` with the privileges granted to it.
+pub fn build_role_insert_request(
+    catalog_name: &str,
+    role_name: &str,
+    value: &RoleEntryValue,
+) -> InsertRequest {
+    let full_role_name = format!("{}.{}", catalog_name, role_name);
+    build_insert_request(
+        EntryType::Role,
+        full_role_name.as_bytes(),
+        serde_json::to_string(value).unwrap().as_bytes(),
+    )
+}
+
+/// Builds a request to rename the entry of table `full_table_name` to `new_full_table_name`,
+/// the new entry and the tombstone of the old one are written in one request.
+pub fn build_table_rename_request(
+    full_table_name: String,
+    new_full_table_name: String,
+    table_id: TableId,
+) -> InsertRequest {
+    let new_value = serde_json::to_string(&TableEntryValue {
+        table_id,
+        is_deleted: false,
+    })
+    .unwrap();
+    let old_value = serde_json::to_string(&TableEntryValue {
+        table_id,
+        is_deleted: true,
+    })
+    .unwrap();
+    build_batch_insert_request(&[
+        (
+            EntryType::Table,
+            new_full_table_name.as_bytes(),
+            new_value.as_bytes(),
+        ),
+        (
+            EntryType::Table,
+            full_table_name.as_bytes(),
+            old_value.as_bytes(),
+        ),
+    ])
+}
+
 pub fn build_insert_request(entry_type: EntryType, key: &[u8], value: &[u8]) -> InsertRequest {
+    build_batch_insert_request(&[(entry_type, key, value)])
+}
+
+/// Builds a request to insert all the `entries` of (entry type, key, value) in one write.
+pub fn build_batch_insert_request(entries: &[(EntryType, &[u8], &[u8])]) -> InsertRequest {
+    let entry_types = entries.iter().map(|e| e.0 as u8).collect::<Vec<_>>();
+    let keys = entries.iter().map(|e| e.1).collect::<Vec<_>>();
+    let values = entries.iter().map(|e| e.2).collect::<Vec<_>>();
+    let now = Timestamp::from_millis(util::current_time_millis());
+
     let mut columns_values = HashMap::with_capacity(6);
     columns_values.insert(
         "entry_type".to_string(),
-        Arc::new(UInt8Vector::from_slice(&[entry_type as u8])) as _,
+        Arc::new(UInt8Vector::from_slice(&entry_types)) as _,
     );
 
     columns_values.insert(
         "key".to_string(),
-        Arc::new(BinaryVector::from_slice(&[key])) as _,
+        Arc::new(BinaryVector::from_slice(&keys)) as _,
     );
 
     // Timestamp in key part is intentionally left to 0
     columns_values.insert(
         "timestamp".to_string(),
-        Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(0)])) as _,
+        Arc::new(TimestampVector::from_slice(&vec![
+            Timestamp::from_millis(0);
+            entries.len()
+        ])) as _,
     );
 
     columns_values.insert(
         "value".to_string(),
-        Arc::new(BinaryVector::from_slice(&[value])) as _,
+        Arc::new(BinaryVector::from_slice(&values)) as _,
     );
 
     columns_values.insert(
         "gmt_created".to_string(),
-        Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
-            util::current_time_millis(),
-        )])) as _,
+        Arc::new(TimestampVector::from_slice(&vec![now; entries.len()])) as _,
     );
 
     columns_values.insert(
         "gmt_modified".to_string(),
-        Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
-            util::current_time_millis(),
-        )])) as _,
+        Arc::new(TimestampVector::from_slice(&vec![now; entries.len()])) as _,
     );
 
     InsertRequest {
@@ -267,9 +383,20 @@ pub fn decode_system_catalog(
     match EntryType::try_from(entry_type)? {
         EntryType::Catalog => {
             // As for catalog entry, the key is a string with format: `<catalog_name>`
-            // and the value is current not used.
+            // and the value is an optional JSON string with format: `{"is_deleted": <bool>}`
             let catalog_name = key.to_string();
-            Ok(Entry::Catalog(CatalogEntry { catalog_name }))
+            let is_deleted = match value {
+                Some(value) if !value.is_empty() => {
+                    let catalog_meta: CatalogEntryValue =
+                        serde_json::from_slice(value).context(ValueDeserializeSnafu)?;
+                    catalog_meta.is_deleted
+                }
+                _ => false,
+            };
+            Ok(Entry::Catalog(CatalogEntry {
+                catalog_name,
+                is_deleted,
+            }))
         }
         EntryType::Schema => {
             // As for schema entry, the key is a string with format: `<catalog_name>.<schema_name>`
@@ -306,6 +433,40 @@ pub fn decode_system_catalog(
                 schema_name: table_parts[1].to_string(),
                 table_name: table_parts[2].to_string(),
                 table_id: table_meta.table_id,
+                is_deleted: table_meta.is_deleted,
+            }))
+        }
+
+        EntryType::User => {
+            // As for user entry, the key is a string with format: `<catalog_name>.<user_name>`
+            // and the value is a JSON string with format: `{"roles": [<role_name>, ...]}`
+            let (catalog_name, user_name) = key.split_once('.').context(InvalidKeySnafu {
+                key: Some(key.to_string()),
+            })?;
+            let value = value.context(EmptyValueSnafu)?;
+            let value: UserEntryValue =
+                serde_json::from_slice(value).context(ValueDeserializeSnafu)?;
+            Ok(Entry::User(UserEntry {
+                catalog_name: catalog_name.to_string(),
+                user_name: user_name.to_string(),
+                roles: value.roles,
+            }))
+        }
+
+        EntryType::Role => {
+            // As for role entry, the key is a string with format: `<catalog_name>.<role_name>`
+            // and the value is a JSON string with format:
+            // `{"privileges": [{"privilege": <privilege>, "object": <object>}, ...]}`
+            let (catalog_name, role_name) = key.split_once('.').context(InvalidKeySnafu {
+                key: Some(key.to_string()),
+            })?;
+            let value = value.context(EmptyValueSnafu)?;
+            let value: RoleEntryValue =
+                serde_json::from_slice(value).context(ValueDeserializeSnafu)?;
+            Ok(Entry::Role(RoleEntry {
+                catalog_name: catalog_name.to_string(),
+                role_name: role_name.to_string(),
+                privileges: value.privileges,
             }))
         }
     }
@@ -316,6 +477,8 @@ pub enum EntryType {
     Catalog = 1,
     Schema = 2,
     Table = 3,
+    User = 4,
+    Role = 5,
 }
 
 impl TryFrom<u8> for EntryType {
@@ -326,6 +489,8 @@ impl TryFrom<u8> for EntryType {
             b if b == Self::Catalog as u8 => Ok(Self::Catalog),
             b if b == Self::Schema as u8 => Ok(Self::Schema),
             b if b == Self::Table as u8 => Ok(Self::Table),
+            b if b == Self::User as u8 => Ok(Self::User),
+            b if b == Self::Role as u8 => Ok(Self::Role),
             b => InvalidEntryTypeSnafu {
                 entry_type: Some(b),
             }
@@ -339,11 +504,21 @@ pub enum Entry {
     Catalog(CatalogEntry),
     Schema(SchemaEntry),
     Table(TableEntry),
+    User(UserEntry),
+    Role(RoleEntry),
 }
 
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
 pub struct CatalogEntry {
     pub catalog_name: String,
+    pub is_deleted: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CatalogEntryValue {
+    /// Whether the entry is a tombstone of a dropped catalog.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_deleted: bool,
 }
 
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
@@ -361,15 +536,45 @@ pub struct TableEntry {
     pub schema_name: String,
     pub table_name: String,
     pub table_id: TableId,
+    pub is_deleted: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct TableEntryValue {
     pub table_id: TableId,
+    /// Whether the entry is a tombstone of a renamed table.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_deleted: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct UserEntry {
+    pub catalog_name: String,
+    pub user_name: String,
+    pub roles: BTreeSet<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UserEntryValue {
+    pub roles: BTreeSet<String>,
+}
+
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd)]
+pub struct RoleEntry {
+    pub catalog_name: String,
+    pub role_name: String,
+    pub privileges: BTreeSet<GrantedPrivilege>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct RoleEntryValue {
+    pub privileges: BTreeSet<GrantedPrivilege>,
 }
 
 #[cfg(test)]
 mod tests {
+    use common_catalog::privilege::{GrantObject, Privilege};
+    use datatypes::value::Value;
     use log_store::fs::noop::NoopLogStore;
     use mito::config::EngineConfig;
     use mito::engine::MitoEngine;
@@ -392,11 +597,70 @@ mod tests {
         .unwrap();
         if let Entry::Catalog(e) = entry {
             assert_eq!("some_catalog", e.catalog_name);
+            assert!(!e.is_deleted);
+        } else {
+            panic!("Unexpected type: {:?}", entry);
+        }
+    }
+
+    #[test]
+    pub fn test_decode_deleted_catalog() {
+        let entry = decode_system_catalog(
+            Some(EntryType::Catalog as u8),
+            Some("some_catalog".as_bytes()),
+            Some("{\"is_deleted\":true}".as_bytes()),
+        )
+        .unwrap();
+        if let Entry::Catalog(e) = entry {
+            assert_eq!("some_catalog", e.catalog_name);
+            assert!(e.is_deleted);
         } else {
             panic!("Unexpected type: {:?}", entry);
         }
     }
 
+    #[test]
+    pub fn test_user_and_role_entry_value_serde() {
+        let user = UserEntryValue {
+            roles: BTreeSet::from(["reader".to_string()]),
+        };
+        let entry = decode_system_catalog(
+            Some(EntryType::User as u8),
+            Some("greptime.dashboard".as_bytes()),
+            Some(serde_json::to_string(&user).unwrap().as_bytes()),
+        )
+        .unwrap();
+        assert_eq!(
+            Entry::User(UserEntry {
+                catalog_name: "greptime".to_string(),
+                user_name: "dashboard".to_string(),
+                roles: user.roles,
+            }),
+            entry
+        );
+
+        let role = RoleEntryValue {
+            privileges: BTreeSet::from([GrantedPrivilege::new(
+                Privilege::Select,
+                GrantObject::new("public", "*"),
+            )]),
+        };
+        let entry = decode_system_catalog(
+            Some(EntryType::Role as u8),
+            Some("greptime.reader".as_bytes()),
+            Some(serde_json::to_string(&role).unwrap().as_bytes()),
+        )
+        .unwrap();
+        assert_eq!(
+            Entry::Role(RoleEntry {
+                catalog_name: "greptime".to_string(),
+                role_name: "reader".to_string(),
+                privileges: role.privileges,
+            }),
+            entry
+        );
+    }
+
     #[test]
     pub fn test_decode_schema_entry() {
         let entry = decode_system_catalog(
@@ -428,11 +692,70 @@ mod tests {
             assert_eq!("some_schema", e.schema_name);
             assert_eq!("some_table", e.table_name);
             assert_eq!(42, e.table_id);
+            assert!(!e.is_deleted);
         } else {
             panic!("Unexpected type: {:?}", entry);
         }
     }
 
+    #[test]
+    pub fn test_decode_deleted_table() {
+        let entry = decode_system_catalog(
+            Some(EntryType::Table as u8),
+            Some("some_catalog.some_schema.some_table".as_bytes()),
+            Some("{\"table_id\":42,\"is_deleted\":true}".as_bytes()),
+        )
+        .unwrap();
+
+        if let Entry::Table(e) = entry {
+            assert_eq!(42, e.table_id);
+            assert!(e.is_deleted);
+        } else {
+            panic!("Unexpected type: {:?}", entry);
+        }
+    }
+
+    #[test]
+    pub fn test_build_table_rename_request() {
+        let request = build_table_rename_request(
+            "greptime.public.old_table".to_string(),
+            "greptime.public.new_table".to_string(),
+            42,
+        );
+        assert_eq!(SYSTEM_CATALOG_TABLE_NAME, request.table_name);
+        assert_eq!(6, request.columns_values.len());
+        let keys = &request.columns_values["key"];
+        let values = &request.columns_values["value"];
+        assert_eq!(2, keys.len());
+
+        let entry = |i| match (keys.get(i), values.get(i)) {
+            (Value::Binary(key), Value::Binary(value)) => {
+                decode_system_catalog(Some(EntryType::Table as u8), Some(&*key), Some(&*value))
+                    .unwrap()
+            }
+            _ => unreachable!(),
+        };
+        match (entry(0), entry(1)) {
+            (Entry::Table(new), Entry::Table(old)) => {
+                assert_eq!("new_table", new.table_name);
+                assert!(!new.is_deleted);
+                assert_eq!("old_table", old.table_name);
+                assert!(old.is_deleted);
+                assert_eq!(42, old.table_id);
+            }
+            entries => panic!("Unexpected entries: {:?}", entries),
+        }
+    }
+
+    #[test]
+    pub fn test_table_entry_value_serde() {
+        let value = TableEntryValue {
+            table_id: 42,
+            is_deleted: false,
+        };
+        assert_eq!("{\"table_id\":42}", serde_json::to_string(&value).unwrap());
+    }
+
     #[test]
     #[should_panic]
     pub fn test_decode_mismatch() {
@@ -449,7 +772,9 @@ mod tests {
         assert_eq!(EntryType::Catalog, EntryType::try_from(1).unwrap());
         assert_eq!(EntryType::Schema, EntryType::try_from(2).unwrap());
         assert_eq!(EntryType::Table, EntryType::try_from(3).unwrap());
-        assert!(EntryType::try_from(4).is_err());
+        assert_eq!(EntryType::User, EntryType::try_from(4).unwrap());
+        assert_eq!(EntryType::Role, EntryType::try_from(5).unwrap());
+        assert!(EntryType::try_from(6).is_err());
     }
 
     pub async fn prepare_table_engine() -> (TempDir, TableEngineRef) {
@@ -14,13 +14,20 @@
 
 // The `tables` table in system catalog keeps a record of all tables created by user.
 
+mod columns;
+mod regions;
+mod schemata;
+
 use std::any::Any;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use async_stream::stream;
-use common_catalog::consts::{INFORMATION_SCHEMA_NAME, SYSTEM_CATALOG_TABLE_NAME};
+use common_catalog::consts::{
+    INFORMATION_SCHEMA_NAME, INFORMATION_SCHEMA_TABLES_TABLE_ID, SYSTEM_CATALOG_NAME,
+    SYSTEM_CATALOG_TABLE_NAME,
+};
 use common_error::ext::BoxedError;
 use common_query::logical_plan::Expr;
 use common_query::physical_plan::PhysicalPlanRef;
@@ -34,33 +41,96 @@ use futures::Stream;
 use snafu::ResultExt;
 use table::engine::TableEngineRef;
 use table::error::TablesRecordBatchSnafu;
-use table::metadata::{TableId, TableInfoRef};
+use table::metadata::{TableId, TableInfoBuilder, TableInfoRef, TableMetaBuilder, TableType};
 use table::table::scan::SimpleTableScan;
 use table::{Table, TableRef};
 
 use crate::error::{Error, InsertCatalogRecordSnafu};
-use crate::system::{build_schema_insert_request, build_table_insert_request, SystemCatalogTable};
+use crate::system::{
+    build_catalog_deletion_request, build_catalog_insert_request, build_role_insert_request,
+    build_schema_insert_request, build_table_insert_request, build_table_rename_request,
+    build_user_insert_request, RoleEntryValue, SystemCatalogTable, UserEntryValue,
+};
+pub use crate::tables::columns::{
+    Columns, COLUMNS_TABLE_NAME, SEMANTIC_TYPE_FIELD, SEMANTIC_TYPE_TAG, SEMANTIC_TYPE_TIMESTAMP,
+};
+pub use crate::tables::regions::{Regions, REGIONS_TABLE_NAME};
+pub use crate::tables::schemata::{Schemata, SCHEMATA_TABLE_NAME};
 use crate::{
     format_full_table_name, CatalogListRef, CatalogProvider, SchemaProvider, SchemaProviderRef,
 };
 
+pub const TABLES_TABLE_NAME: &str = "tables";
+
 /// Tables holds all tables created by user.
 pub struct Tables {
     schema: SchemaRef,
+    table_info: TableInfoRef,
     catalogs: CatalogListRef,
     engine_name: String,
 }
 
 impl Tables {
     pub fn new(catalogs: CatalogListRef, engine_name: String) -> Self {
+        let schema = Arc::new(build_schema_for_tables());
         Self {
-            schema: Arc::new(build_schema_for_tables()),
+            table_info: virtual_table_info(
+                INFORMATION_SCHEMA_TABLES_TABLE_ID,
+                TABLES_TABLE_NAME,
+                schema.clone(),
+            ),
+            schema,
             catalogs,
             engine_name,
         }
     }
 }
 
+/// Builds the info of the virtual table in information schema, which has no primary key.
+fn virtual_table_info(table_id: TableId, table_name: &str, schema: SchemaRef) -> TableInfoRef {
+    let meta = TableMetaBuilder::default()
+        .schema(schema)
+        .primary_key_indices(vec![])
+        .next_column_id(0)
+        .build()
+        // Meta of virtual tables is always valid.
+        .unwrap();
+    Arc::new(
+        TableInfoBuilder::new(table_name, meta)
+            .table_id(table_id)
+            .catalog_name(SYSTEM_CATALOG_NAME)
+            .schema_name(INFORMATION_SCHEMA_NAME)
+            .table_type(TableType::View)
+            .build()
+            .unwrap(),
+    )
+}
+
+/// Returns all tables in the catalog list as `(catalog, schema, table name, table)`.
+pub fn list_tables(
+    catalogs: &CatalogListRef,
+) -> crate::error::Result<Vec<(String, String, String, TableRef)>> {
+    let mut tables = Vec::new();
+    for catalog_name in catalogs.catalog_names()? {
+        let catalog = match catalogs.catalog(&catalog_name)? {
+            Some(catalog) => catalog,
+            None => continue,
+        };
+        for schema_name in catalog.schema_names()? {
+            let schema = match catalog.schema(&schema_name)? {
+                Some(schema) => schema,
+                None => continue,
+            };
+            for table_name in schema.table_names()? {
+                if let Some(table) = schema.table(&table_name)? {
+                    tables.push((catalog_name.clone(), schema_name.clone(), table_name, table));
+                }
+            }
+        }
+    }
+    Ok(tables)
+}
+
 #[async_trait::async_trait]
 impl Table for Tables {
     fn as_any(&self) -> &dyn Any {
@@ -72,7 +142,7 @@ impl Table for Tables {
     }
 
     fn table_info(&self) -> TableInfoRef {
-        unreachable!("Tables does not support table_info method")
+        self.table_info.clone()
     }
 
     async fn scan(
@@ -193,6 +263,9 @@ impl RecordBatchStream for TablesRecordBatchStream {
 
 pub struct InformationSchema {
     pub tables: Arc<Tables>,
+    pub columns: Arc<Columns>,
+    pub schemata: Arc<Schemata>,
+    pub regions: Arc<Regions>,
     pub system: Arc<SystemCatalogTable>,
 }
 
@@ -203,14 +276,23 @@ impl SchemaProvider for InformationSchema {
 
     fn table_names(&self) -> Result<Vec<String>, Error> {
         Ok(vec![
-            "tables".to_string(),
+            TABLES_TABLE_NAME.to_string(),
+            COLUMNS_TABLE_NAME.to_string(),
+            SCHEMATA_TABLE_NAME.to_string(),
+            REGIONS_TABLE_NAME.to_string(),
             SYSTEM_CATALOG_TABLE_NAME.to_string(),
         ])
     }
 
     fn table(&self, name: &str) -> Result<Option<TableRef>, Error> {
-        if name.eq_ignore_ascii_case("tables") {
+        if name.eq_ignore_ascii_case(TABLES_TABLE_NAME) {
             Ok(Some(self.tables.clone()))
+        } else if name.eq_ignore_ascii_case(COLUMNS_TABLE_NAME) {
+            Ok(Some(self.columns.clone()))
+        } else if name.eq_ignore_ascii_case(SCHEMATA_TABLE_NAME) {
+            Ok(Some(self.schemata.clone()))
+        } else if name.eq_ignore_ascii_case(REGIONS_TABLE_NAME) {
+            Ok(Some(self.regions.clone()))
         } else if name.eq_ignore_ascii_case(SYSTEM_CATALOG_TABLE_NAME) {
             Ok(Some(self.system.clone()))
         } else {
@@ -231,8 +313,10 @@ impl SchemaProvider for InformationSchema {
     }
 
     fn table_exist(&self, name: &str) -> Result<bool, Error> {
-        Ok(name.eq_ignore_ascii_case("tables")
-            || name.eq_ignore_ascii_case(SYSTEM_CATALOG_TABLE_NAME))
+        Ok(self
+            .table_names()?
+            .iter()
+            .any(|table_name| name.eq_ignore_ascii_case(table_name)))
     }
 }
 
@@ -241,13 +325,19 @@ pub struct SystemCatalog {
 }
 
 impl SystemCatalog {
+    /// Creates the system catalog of the tables in `catalogs`, which are served by the node
+    /// `node_id`.
     pub fn new(
         system: SystemCatalogTable,
         catalogs: CatalogListRef,
         engine: TableEngineRef,
+        node_id: Option<u64>,
     ) -> Self {
         let schema = InformationSchema {
-            tables: Arc::new(Tables::new(catalogs, engine.name().to_string())),
+            tables: Arc::new(Tables::new(catalogs.clone(), engine.name().to_string())),
+            columns: Arc::new(Columns::new(catalogs.clone())),
+            schemata: Arc::new(Schemata::new(catalogs.clone())),
+            regions: Arc::new(Regions::new(catalogs, node_id)),
             system: Arc::new(system),
         };
         Self {
@@ -271,6 +361,46 @@ impl SystemCatalog {
             .context(InsertCatalogRecordSnafu)
     }
 
+    /// Registers the table under `new_table_name` and marks the entry of the old name
+    /// as deleted, both in a single write to the system catalog table.
+    pub async fn rename_table(
+        &self,
+        catalog: String,
+        schema: String,
+        table_name: String,
+        new_table_name: String,
+        table_id: TableId,
+    ) -> crate::error::Result<()> {
+        let full_table_name = format_full_table_name(&catalog, &schema, &table_name);
+        let new_full_table_name = format_full_table_name(&catalog, &schema, &new_table_name);
+        let request = build_table_rename_request(full_table_name, new_full_table_name, table_id);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)?;
+        Ok(())
+    }
+
+    pub async fn register_catalog(&self, catalog: String) -> crate::error::Result<usize> {
+        let request = build_catalog_insert_request(catalog);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
+
+    /// Marks the entry of the catalog as deleted.
+    pub async fn drop_catalog(&self, catalog: String) -> crate::error::Result<usize> {
+        let request = build_catalog_deletion_request(catalog);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
+
     pub async fn register_schema(
         &self,
         catalog: String,
@@ -283,6 +413,38 @@ impl SystemCatalog {
             .await
             .context(InsertCatalogRecordSnafu)
     }
+
+    /// Overwrites the entry of the user of the tenant owning `catalog` with the roles granted
+    /// to it.
+    pub async fn put_user(
+        &self,
+        catalog: &str,
+        user: &str,
+        value: &UserEntryValue,
+    ) -> crate::error::Result<usize> {
+        let request = build_user_insert_request(catalog, user, value);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
+
+    /// Overwrites the entry of the role of the tenant owning `catalog` with the privileges
+    /// granted to it.
+    pub async fn put_role(
+        &self,
+        catalog: &str,
+        role: &str,
+        value: &RoleEntryValue,
+    ) -> crate::error::Result<usize> {
+        let request = build_role_insert_request(catalog, role, value);
+        self.information_schema
+            .system
+            .insert(request)
+            .await
+            .context(InsertCatalogRecordSnafu)
+    }
 }
 
 impl CatalogProvider for SystemCatalog {
@@ -421,4 +583,65 @@ mod tests {
             panic!("Record batch should not be empty!")
         }
     }
+
+    #[tokio::test]
+    async fn test_columns_and_schemata() {
+        let catalog_list = new_memory_catalog_list().unwrap();
+        let schema = catalog_list
+            .catalog(DEFAULT_CATALOG_NAME)
+            .unwrap()
+            .unwrap()
+            .schema(DEFAULT_SCHEMA_NAME)
+            .unwrap()
+            .unwrap();
+        schema
+            .register_table("test_table".to_string(), Arc::new(NumbersTable::default()))
+            .unwrap();
+
+        let columns = Columns::new(catalog_list.clone());
+        let stream = columns
+            .scan(&None, &[], None)
+            .await
+            .unwrap()
+            .execute(0, Arc::new(RuntimeEnv::default()))
+            .unwrap();
+        let batches = common_recordbatch::util::collect(stream).await.unwrap();
+        assert_eq!(1, batches.len());
+        let batch = &batches[0].df_recordbatch;
+        assert_eq!(1, batch.num_rows());
+        assert_eq!(8, batch.num_columns());
+        let string_value = |column: usize| {
+            batch
+                .column(column)
+                .as_any()
+                .downcast_ref::<Utf8Array<i32>>()
+                .unwrap()
+                .value(0)
+                .to_string()
+        };
+        assert_eq!("test_table", string_value(2));
+        assert_eq!("number", string_value(3));
+        assert_eq!("UInt32", string_value(4));
+        assert_eq!(SEMANTIC_TYPE_TAG, string_value(7));
+
+        let schemata = Schemata::new(catalog_list);
+        let stream = schemata
+            .scan(&None, &[], None)
+            .await
+            .unwrap()
+            .execute(0, Arc::new(RuntimeEnv::default()))
+            .unwrap();
+        let batches = common_recordbatch::util::collect(stream).await.unwrap();
+        let batch = &batches[0].df_recordbatch;
+        assert_eq!(1, batch.num_rows());
+        assert_eq!(
+            "public",
+            batch
+                .column(1)
+                .as_any()
+                .downcast_ref::<Utf8Array<i32>>()
+                .unwrap()
+                .value(0)
+        );
+    }
 }
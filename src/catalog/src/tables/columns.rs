@@ -0,0 +1,213 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The `columns` table in information schema describes columns of all tables.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_stream::stream;
+use common_catalog::consts::INFORMATION_SCHEMA_COLUMNS_TABLE_ID;
+use common_error::ext::BoxedError;
+use common_query::logical_plan::Expr;
+use common_query::physical_plan::PhysicalPlanRef;
+use common_recordbatch::RecordBatch;
+use datatypes::data_type::DataType;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{BooleanVector, StringVector, VectorRef};
+use snafu::ResultExt;
+use table::error::TablesRecordBatchSnafu;
+use table::metadata::TableInfoRef;
+use table::table::scan::SimpleTableScan;
+use table::Table;
+
+use crate::error::Result;
+use crate::tables::{list_tables, virtual_table_info, TablesRecordBatchStream};
+use crate::CatalogListRef;
+
+pub const COLUMNS_TABLE_NAME: &str = "columns";
+
+pub const SEMANTIC_TYPE_TAG: &str = "TAG";
+pub const SEMANTIC_TYPE_FIELD: &str = "FIELD";
+pub const SEMANTIC_TYPE_TIMESTAMP: &str = "TIMESTAMP";
+
+/// Columns holds the columns of all tables.
+pub struct Columns {
+    table_info: TableInfoRef,
+    catalogs: CatalogListRef,
+}
+
+impl Columns {
+    pub fn new(catalogs: CatalogListRef) -> Self {
+        Self {
+            table_info: virtual_table_info(
+                INFORMATION_SCHEMA_COLUMNS_TABLE_ID,
+                COLUMNS_TABLE_NAME,
+                Arc::new(build_schema_for_columns()),
+            ),
+            catalogs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for Columns {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table_info.meta.schema.clone()
+    }
+
+    fn table_info(&self) -> TableInfoRef {
+        self.table_info.clone()
+    }
+
+    async fn scan(
+        &self,
+        _projection: &Option<Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> table::error::Result<PhysicalPlanRef> {
+        let columns = columns_to_record_batch(&self.catalogs)
+            .map_err(BoxedError::new)
+            .context(TablesRecordBatchSnafu)?;
+        let record_batch_res = RecordBatch::new(self.schema(), columns);
+
+        let stream = Box::pin(TablesRecordBatchStream {
+            schema: self.schema(),
+            stream: Box::pin(stream!({
+                yield record_batch_res;
+            })),
+        });
+        Ok(Arc::new(SimpleTableScan::new(stream)))
+    }
+}
+
+fn semantic_type(
+    index: usize,
+    primary_key_indices: &[usize],
+    timestamp_index: Option<usize>,
+) -> &'static str {
+    if primary_key_indices.contains(&index) {
+        SEMANTIC_TYPE_TAG
+    } else if timestamp_index == Some(index) {
+        SEMANTIC_TYPE_TIMESTAMP
+    } else {
+        SEMANTIC_TYPE_FIELD
+    }
+}
+
+/// Convert columns of all tables to `RecordBatch`.
+fn columns_to_record_batch(catalogs: &CatalogListRef) -> Result<Vec<VectorRef>> {
+    let mut catalog_names = Vec::new();
+    let mut schema_names = Vec::new();
+    let mut table_names = Vec::new();
+    let mut column_names = Vec::new();
+    let mut data_types = Vec::new();
+    let mut nullables = Vec::new();
+    let mut defaults = Vec::new();
+    let mut semantic_types = Vec::new();
+
+    for (catalog_name, schema_name, table_name, table) in list_tables(catalogs)? {
+        let table_info = table.table_info();
+        let schema = &table_info.meta.schema;
+        for (index, column_schema) in schema.column_schemas().iter().enumerate() {
+            catalog_names.push(catalog_name.clone());
+            schema_names.push(schema_name.clone());
+            table_names.push(table_name.clone());
+            column_names.push(column_schema.name.clone());
+            data_types.push(column_schema.data_type.name().to_string());
+            nullables.push(column_schema.is_nullable());
+            defaults.push(column_schema.default_constraint().map(|c| c.to_string()));
+            semantic_types.push(semantic_type(
+                index,
+                &table_info.meta.primary_key_indices,
+                schema.timestamp_index(),
+            ));
+        }
+    }
+
+    Ok(vec![
+        Arc::new(StringVector::from(catalog_names)),
+        Arc::new(StringVector::from(schema_names)),
+        Arc::new(StringVector::from(table_names)),
+        Arc::new(StringVector::from(column_names)),
+        Arc::new(StringVector::from(data_types)),
+        Arc::new(BooleanVector::from(nullables)),
+        Arc::new(StringVector::from(defaults)),
+        Arc::new(StringVector::from(semantic_types)),
+    ])
+}
+
+fn build_schema_for_columns() -> Schema {
+    let cols = vec![
+        ColumnSchema::new(
+            "catalog".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "schema".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "table_name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "column_name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "data_type".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "is_nullable".to_string(),
+            ConcreteDataType::boolean_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "column_default".to_string(),
+            ConcreteDataType::string_datatype(),
+            true,
+        ),
+        ColumnSchema::new(
+            "semantic_type".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+    ];
+    Schema::new(cols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_semantic_type() {
+        assert_eq!(SEMANTIC_TYPE_TAG, semantic_type(0, &[0], Some(1)));
+        assert_eq!(SEMANTIC_TYPE_TIMESTAMP, semantic_type(1, &[0], Some(1)));
+        assert_eq!(SEMANTIC_TYPE_FIELD, semantic_type(2, &[0], Some(1)));
+        assert_eq!(SEMANTIC_TYPE_FIELD, semantic_type(0, &[], None));
+    }
+}
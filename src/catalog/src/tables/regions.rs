@@ -0,0 +1,258 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The `regions` table in information schema shows the statistics of regions reported by
+// table engines. In distributed mode they are the stats reported by datanodes in heartbeats,
+// which are stored by meta-srv.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_stream::stream;
+use common_catalog::consts::INFORMATION_SCHEMA_REGIONS_TABLE_ID;
+use common_error::ext::BoxedError;
+use common_query::logical_plan::Expr;
+use common_query::physical_plan::PhysicalPlanRef;
+use common_recordbatch::RecordBatch;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVector, UInt32Vector, UInt64Vector, VectorRef};
+use futures::StreamExt;
+use snafu::ResultExt;
+use table::error::TablesRecordBatchSnafu;
+use table::metadata::TableInfoRef;
+use table::table::scan::SimpleTableScan;
+use table::Table;
+
+use crate::error::{InvalidCatalogValueSnafu, ReadRegionStatsSnafu, Result};
+use crate::helper::{build_region_stats_prefix, RegionStatEntry, RegionStatsValue};
+use crate::remote::{Kv, KvBackendRef};
+use crate::tables::{list_tables, virtual_table_info, TablesRecordBatchStream};
+use crate::CatalogListRef;
+
+pub const REGIONS_TABLE_NAME: &str = "regions";
+
+/// Regions holds the statistics of regions of all tables.
+pub struct Regions {
+    table_info: TableInfoRef,
+    source: RegionStatsSource,
+}
+
+enum RegionStatsSource {
+    /// Stats of the tables in the catalogs, whose regions are all served by the node `node_id`.
+    Local {
+        catalogs: CatalogListRef,
+        node_id: Option<u64>,
+    },
+    /// Stats of the tables in the catalog `catalog_name` reported by all datanodes in
+    /// heartbeats, read from the meta-srv backend.
+    Heartbeat {
+        backend: KvBackendRef,
+        catalog_name: String,
+    },
+}
+
+impl Regions {
+    /// Creates the table of the regions of the tables in `catalogs` on the node `node_id`, the
+    /// node id is `None` if it's unknown.
+    pub fn new(catalogs: CatalogListRef, node_id: Option<u64>) -> Self {
+        Self::with_source(RegionStatsSource::Local { catalogs, node_id })
+    }
+
+    /// Creates the table of the regions of the tables in the catalog on all datanodes, from the
+    /// stats stored in `backend`.
+    pub fn with_backend(backend: KvBackendRef, catalog_name: String) -> Self {
+        Self::with_source(RegionStatsSource::Heartbeat {
+            backend,
+            catalog_name,
+        })
+    }
+
+    fn with_source(source: RegionStatsSource) -> Self {
+        Self {
+            table_info: virtual_table_info(
+                INFORMATION_SCHEMA_REGIONS_TABLE_ID,
+                REGIONS_TABLE_NAME,
+                Arc::new(build_schema_for_regions()),
+            ),
+            source,
+        }
+    }
+
+    async fn region_stats(&self) -> Result<Vec<(Option<u64>, RegionStatEntry)>> {
+        match &self.source {
+            RegionStatsSource::Local { catalogs, node_id } => {
+                let mut stats = Vec::new();
+                for (catalog_name, schema_name, table_name, table) in list_tables(catalogs)? {
+                    let region_stats = table.region_stats().context(ReadRegionStatsSnafu {
+                        table_name: &table_name,
+                    })?;
+                    stats.extend(region_stats.into_iter().map(|stat| {
+                        let entry = RegionStatEntry {
+                            region_id: stat.region_id,
+                            region_number: stat.region_number,
+                            catalog_name: catalog_name.clone(),
+                            schema_name: schema_name.clone(),
+                            table_name: table_name.clone(),
+                            approximate_rows: stat.approximate_rows,
+                            approximate_bytes: stat.approximate_bytes,
+                        };
+                        (*node_id, entry)
+                    }));
+                }
+                Ok(stats)
+            }
+            RegionStatsSource::Heartbeat {
+                backend,
+                catalog_name,
+            } => {
+                let prefix = build_region_stats_prefix();
+                let mut iter = backend.range(prefix.as_bytes());
+                let mut stats = Vec::new();
+                while let Some(kv) = iter.next().await {
+                    let Kv(_, value) = kv?;
+                    let value =
+                        RegionStatsValue::from_bytes(&value).context(InvalidCatalogValueSnafu)?;
+                    let node_id = value.node_id;
+                    stats.extend(
+                        value
+                            .stats
+                            .into_iter()
+                            .filter(|entry| &entry.catalog_name == catalog_name)
+                            .map(|entry| (Some(node_id), entry)),
+                    );
+                }
+                Ok(stats)
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for Regions {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table_info.meta.schema.clone()
+    }
+
+    fn table_info(&self) -> TableInfoRef {
+        self.table_info.clone()
+    }
+
+    async fn scan(
+        &self,
+        _projection: &Option<Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> table::error::Result<PhysicalPlanRef> {
+        let columns = self
+            .region_stats()
+            .await
+            .map(regions_to_record_batch)
+            .map_err(BoxedError::new)
+            .context(TablesRecordBatchSnafu)?;
+        let record_batch_res = RecordBatch::new(self.schema(), columns);
+
+        let stream = Box::pin(TablesRecordBatchStream {
+            schema: self.schema(),
+            stream: Box::pin(stream!({
+                yield record_batch_res;
+            })),
+        });
+        Ok(Arc::new(SimpleTableScan::new(stream)))
+    }
+}
+
+/// Convert region statistics with their node ids to `RecordBatch`.
+fn regions_to_record_batch(stats: Vec<(Option<u64>, RegionStatEntry)>) -> Vec<VectorRef> {
+    let mut region_ids = Vec::new();
+    let mut catalog_names = Vec::new();
+    let mut schema_names = Vec::new();
+    let mut table_names = Vec::new();
+    let mut region_numbers = Vec::new();
+    let mut node_ids = Vec::new();
+    let mut approximate_rows = Vec::new();
+    let mut approximate_bytes = Vec::new();
+
+    for (node_id, stat) in stats {
+        region_ids.push(stat.region_id);
+        catalog_names.push(stat.catalog_name);
+        schema_names.push(stat.schema_name);
+        table_names.push(stat.table_name);
+        region_numbers.push(stat.region_number);
+        node_ids.push(node_id);
+        approximate_rows.push(stat.approximate_rows);
+        approximate_bytes.push(stat.approximate_bytes);
+    }
+
+    vec![
+        Arc::new(UInt64Vector::from_vec(region_ids)),
+        Arc::new(StringVector::from(catalog_names)),
+        Arc::new(StringVector::from(schema_names)),
+        Arc::new(StringVector::from(table_names)),
+        Arc::new(UInt32Vector::from_vec(region_numbers)),
+        Arc::new(UInt64Vector::from(node_ids)),
+        Arc::new(UInt64Vector::from_vec(approximate_rows)),
+        Arc::new(UInt64Vector::from_vec(approximate_bytes)),
+    ]
+}
+
+fn build_schema_for_regions() -> Schema {
+    let cols = vec![
+        ColumnSchema::new(
+            "region_id".to_string(),
+            ConcreteDataType::uint64_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "catalog".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "schema".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "table_name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "region_number".to_string(),
+            ConcreteDataType::uint32_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "datanode_id".to_string(),
+            ConcreteDataType::uint64_datatype(),
+            true,
+        ),
+        ColumnSchema::new(
+            "approximate_rows".to_string(),
+            ConcreteDataType::uint64_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "approximate_bytes".to_string(),
+            ConcreteDataType::uint64_datatype(),
+            false,
+        ),
+    ];
+    Schema::new(cols)
+}
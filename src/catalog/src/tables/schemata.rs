@@ -0,0 +1,131 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+// The `schemata` table in information schema lists schemas of all catalogs.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_stream::stream;
+use common_catalog::consts::INFORMATION_SCHEMA_SCHEMATA_TABLE_ID;
+use common_error::ext::BoxedError;
+use common_query::logical_plan::Expr;
+use common_query::physical_plan::PhysicalPlanRef;
+use common_recordbatch::RecordBatch;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVector, VectorRef};
+use snafu::ResultExt;
+use table::error::TablesRecordBatchSnafu;
+use table::metadata::TableInfoRef;
+use table::table::scan::SimpleTableScan;
+use table::Table;
+
+use crate::error::Result;
+use crate::tables::{virtual_table_info, TablesRecordBatchStream};
+use crate::CatalogListRef;
+
+pub const SCHEMATA_TABLE_NAME: &str = "schemata";
+
+/// Schemata holds the schemas of all catalogs.
+pub struct Schemata {
+    table_info: TableInfoRef,
+    catalogs: CatalogListRef,
+}
+
+impl Schemata {
+    pub fn new(catalogs: CatalogListRef) -> Self {
+        Self {
+            table_info: virtual_table_info(
+                INFORMATION_SCHEMA_SCHEMATA_TABLE_ID,
+                SCHEMATA_TABLE_NAME,
+                Arc::new(build_schema_for_schemata()),
+            ),
+            catalogs,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Table for Schemata {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.table_info.meta.schema.clone()
+    }
+
+    fn table_info(&self) -> TableInfoRef {
+        self.table_info.clone()
+    }
+
+    async fn scan(
+        &self,
+        _projection: &Option<Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+    ) -> table::error::Result<PhysicalPlanRef> {
+        let columns = schemata_to_record_batch(&self.catalogs)
+            .map_err(BoxedError::new)
+            .context(TablesRecordBatchSnafu)?;
+        let record_batch_res = RecordBatch::new(self.schema(), columns);
+
+        let stream = Box::pin(TablesRecordBatchStream {
+            schema: self.schema(),
+            stream: Box::pin(stream!({
+                yield record_batch_res;
+            })),
+        });
+        Ok(Arc::new(SimpleTableScan::new(stream)))
+    }
+}
+
+/// Convert schemas of all catalogs to `RecordBatch`.
+fn schemata_to_record_batch(catalogs: &CatalogListRef) -> Result<Vec<VectorRef>> {
+    let mut catalog_names = Vec::new();
+    let mut schema_names = Vec::new();
+
+    for catalog_name in catalogs.catalog_names()? {
+        let catalog = match catalogs.catalog(&catalog_name)? {
+            Some(catalog) => catalog,
+            None => continue,
+        };
+        for schema_name in catalog.schema_names()? {
+            catalog_names.push(catalog_name.clone());
+            schema_names.push(schema_name);
+        }
+    }
+
+    Ok(vec![
+        Arc::new(StringVector::from(catalog_names)),
+        Arc::new(StringVector::from(schema_names)),
+    ])
+}
+
+fn build_schema_for_schemata() -> Schema {
+    let cols = vec![
+        ColumnSchema::new(
+            "catalog_name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "schema_name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+    ];
+    Schema::new(cols)
+}
@@ -19,19 +19,29 @@ mod mock;
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
-    use std::collections::HashSet;
+    use std::collections::{HashMap, HashSet};
     use std::sync::Arc;
 
-    use catalog::helper::{CatalogKey, CatalogValue, SchemaKey, SchemaValue};
+    use catalog::helper::{
+        CatalogKey, CatalogValue, RegionStatEntry, RegionStatsKey, RegionStatsValue, SchemaKey,
+        SchemaValue, TableGlobalKey, TableGlobalValue,
+    };
     use catalog::remote::{
         KvBackend, KvBackendRef, RemoteCatalogManager, RemoteCatalogProvider, RemoteSchemaProvider,
+        WatchHandler,
     };
-    use catalog::{CatalogList, CatalogManager, RegisterTableRequest};
+    use catalog::tables::Regions;
+    use catalog::{CatalogList, CatalogManager, CreateCatalogRequest, RegisterTableRequest};
     use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use common_query::physical_plan::RuntimeEnv;
+    use datatypes::arrow::array::PrimitiveArray;
     use datatypes::schema::Schema;
     use futures_util::StreamExt;
+    use meta_client::rpc::EventType;
     use table::engine::{EngineContext, TableEngineRef};
+    use table::metadata::RawTableInfo;
     use table::requests::CreateTableRequest;
+    use table::Table;
 
     use crate::mock::{MockKvBackend, MockTableEngine};
 
@@ -75,6 +85,61 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_regions_from_heartbeat_stats() {
+        let backend = Arc::new(MockKvBackend::default());
+        let stat = |catalog_name: &str, region_number: u32| RegionStatEntry {
+            region_id: (1024 << 32) | region_number as u64,
+            region_number,
+            catalog_name: catalog_name.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: "demo".to_string(),
+            approximate_rows: 10,
+            approximate_bytes: 100,
+        };
+        for (node_id, stats) in [
+            (1, vec![stat(DEFAULT_CATALOG_NAME, 0), stat("other", 1)]),
+            (2, vec![stat(DEFAULT_CATALOG_NAME, 2)]),
+        ] {
+            let value = RegionStatsValue {
+                node_id,
+                timestamp_millis: 0,
+                stats,
+            };
+            backend
+                .set(
+                    RegionStatsKey { node_id }.to_string().as_bytes(),
+                    &value.as_bytes().unwrap(),
+                )
+                .await
+                .unwrap();
+        }
+
+        let regions = Regions::with_backend(backend, DEFAULT_CATALOG_NAME.to_string());
+        let stream = regions
+            .scan(&None, &[], None)
+            .await
+            .unwrap()
+            .execute(0, Arc::new(RuntimeEnv::default()))
+            .unwrap();
+        let batches = common_recordbatch::util::collect(stream).await.unwrap();
+        let batch = &batches[0].df_recordbatch;
+        // only the regions in the catalog are shown, with the ids of their datanodes
+        assert_eq!(2, batch.num_rows());
+        let region_numbers = batch
+            .column(4)
+            .as_any()
+            .downcast_ref::<PrimitiveArray<u32>>()
+            .unwrap();
+        assert_eq!(vec![0, 2], region_numbers.values().to_vec());
+        let node_ids = batch
+            .column(5)
+            .as_any()
+            .downcast_ref::<PrimitiveArray<u64>>()
+            .unwrap();
+        assert_eq!(vec![1, 2], node_ids.values().to_vec());
+    }
+
     async fn prepare_components(
         node_id: u64,
     ) -> (KvBackendRef, TableEngineRef, Arc<RemoteCatalogManager>) {
@@ -294,4 +359,150 @@ mod tests {
             new_catalog.schema_names().unwrap().into_iter().collect()
         )
     }
+
+    #[tokio::test]
+    async fn test_create_catalog() {
+        let node_id = 42;
+        let (backend, _, catalog_manager) = prepare_components(node_id).await;
+
+        let catalog_name = "tenant_catalog".to_string();
+        assert!(catalog_manager
+            .create_catalog(CreateCatalogRequest {
+                catalog: catalog_name.clone(),
+            })
+            .await
+            .unwrap());
+
+        // Both the catalog and its default schema are persisted.
+        let catalog_key = CatalogKey {
+            catalog_name: catalog_name.clone(),
+        }
+        .to_string();
+        assert!(backend.get(catalog_key.as_bytes()).await.unwrap().is_some());
+        let schema_key = SchemaKey {
+            catalog_name: catalog_name.clone(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+        }
+        .to_string();
+        assert!(backend.get(schema_key.as_bytes()).await.unwrap().is_some());
+        assert!(catalog_manager
+            .schema(&catalog_name, DEFAULT_SCHEMA_NAME)
+            .unwrap()
+            .is_some());
+
+        assert_matches!(
+            catalog_manager
+                .create_catalog(CreateCatalogRequest {
+                    catalog: catalog_name,
+                })
+                .await
+                .unwrap_err(),
+            catalog::error::Error::CatalogExists { .. }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_watch_changes() {
+        let node_id = 42;
+        let (_, table_engine, catalog_manager) = prepare_components(node_id).await;
+
+        // Catalogs created and dropped by other nodes.
+        let catalog_key = CatalogKey {
+            catalog_name: "other_catalog".to_string(),
+        }
+        .to_string();
+        catalog_manager
+            .handle(EventType::Put, catalog_key.as_bytes(), &[])
+            .await;
+        assert!(catalog_manager.catalog("other_catalog").unwrap().is_some());
+        catalog_manager
+            .handle(EventType::Delete, catalog_key.as_bytes(), &[])
+            .await;
+        assert!(catalog_manager.catalog("other_catalog").unwrap().is_none());
+
+        let table_name = "test_table".to_string();
+        let table = table_engine
+            .create_table(
+                &EngineContext {},
+                CreateTableRequest {
+                    id: 0,
+                    catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                    schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                    table_name: table_name.clone(),
+                    desc: None,
+                    schema: Arc::new(Schema::new(vec![])),
+                    region_numbers: vec![0],
+                    primary_key_indices: vec![],
+                    create_if_not_exists: false,
+                    table_options: Default::default(),
+                },
+            )
+            .await
+            .unwrap();
+        let register_table = || async {
+            let _ = catalog_manager
+                .register_table(RegisterTableRequest {
+                    catalog: DEFAULT_CATALOG_NAME.to_string(),
+                    schema: DEFAULT_SCHEMA_NAME.to_string(),
+                    table_name: table_name.clone(),
+                    table_id: 0,
+                    table: table.clone(),
+                })
+                .await
+                .unwrap();
+        };
+        let table_exists = || {
+            catalog_manager
+                .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, &table_name)
+                .unwrap()
+                .is_some()
+        };
+        let table_key = TableGlobalKey {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: table_name.clone(),
+        }
+        .to_string();
+        let table_value = |regions_id_map: HashMap<u64, Vec<u32>>, table_id| {
+            let mut table_info = RawTableInfo::from(table.table_info().as_ref().clone());
+            table_info.ident.table_id = table_id;
+            TableGlobalValue {
+                node_id,
+                regions_id_map,
+                table_info,
+            }
+            .as_bytes()
+            .unwrap()
+        };
+
+        register_table().await;
+        // The table served by this node is kept.
+        let value = table_value(HashMap::from([(node_id, vec![0])]), 0);
+        catalog_manager
+            .handle(EventType::Put, table_key.as_bytes(), &value)
+            .await;
+        assert!(table_exists());
+
+        // The table is recreated with another id.
+        let value = table_value(HashMap::from([(node_id, vec![0])]), 1);
+        catalog_manager
+            .handle(EventType::Put, table_key.as_bytes(), &value)
+            .await;
+        assert!(!table_exists());
+
+        // The regions are moved to other nodes.
+        register_table().await;
+        let value = table_value(HashMap::from([(node_id + 1, vec![0])]), 0);
+        catalog_manager
+            .handle(EventType::Put, table_key.as_bytes(), &value)
+            .await;
+        assert!(!table_exists());
+
+        // The table is dropped.
+        register_table().await;
+        catalog_manager
+            .handle(EventType::Delete, table_key.as_bytes(), &[])
+            .await;
+        assert!(!table_exists());
+    }
 }
@@ -30,6 +30,7 @@ async fn run() {
     let (columns, row_count) = insert_data();
 
     let expr = InsertExpr {
+        catalog_name: "greptime".to_string(),
         schema_name: "public".to_string(),
         table_name: "demo".to_string(),
         region_number: 0,
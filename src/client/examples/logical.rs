@@ -44,18 +44,21 @@ async fn run() {
                 datatype: ColumnDataType::Timestamp as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "key".to_string(),
                 datatype: ColumnDataType::Uint64 as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "value".to_string(),
                 datatype: ColumnDataType::Uint64 as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
         ],
         time_index: "timestamp".to_string(),
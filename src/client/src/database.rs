@@ -12,13 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use api::v1::codec::SelectResult as GrpcSelectResult;
 use api::v1::column::SemanticType;
 use api::v1::{
     object_expr, object_result, select_expr, DatabaseRequest, ExprHeader, InsertExpr,
-    MutateResult as GrpcMutateResult, ObjectExpr, ObjectResult as GrpcObjectResult, SelectExpr,
+    MutateResult as GrpcMutateResult, ObjectExpr, ObjectResult as GrpcObjectResult, ScriptExpr,
+    SelectExpr,
 };
 use common_error::status_code::StatusCode;
 use common_grpc_expr::column_to_vector;
@@ -89,6 +91,25 @@ impl Database {
         self.do_select(select_expr).await
     }
 
+    /// Executes the script with runtime parameters.
+    pub async fn script(
+        &self,
+        name: impl Into<String>,
+        params: HashMap<String, String>,
+    ) -> Result<ObjectResult> {
+        let header = ExprHeader {
+            version: PROTOCOL_VERSION,
+        };
+        let expr = ObjectExpr {
+            header: Some(header),
+            expr: Some(object_expr::Expr::Script(ScriptExpr {
+                name: name.into(),
+                params,
+            })),
+        };
+        self.object(expr).await?.try_into()
+    }
+
     pub async fn logical_plan(&self, logical_plan: Vec<u8>) -> Result<ObjectResult> {
         let select_expr = SelectExpr {
             expr: Some(select_expr::Expr::LogicalPlan(logical_plan)),
@@ -325,6 +346,7 @@ mod tests {
             values: Some(values(&[array.clone()]).unwrap()),
             null_mask: null_mask(&vec![array], vector.len()),
             datatype: wrapper.datatype() as i32,
+            datatype_extension: wrapper.datatype_extension(),
         }
     }
 }
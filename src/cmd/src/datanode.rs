@@ -159,6 +159,7 @@ mod tests {
             timeout_millis,
             connect_timeout_millis,
             tcp_nodelay,
+            ..
         } = options.meta_client_opts.unwrap();
 
         assert_eq!(vec!["127.0.0.1:3002".to_string()], metasrv_addr);
@@ -222,6 +223,7 @@ mod tests {
             timeout_millis,
             connect_timeout_millis,
             tcp_nodelay,
+            ..
         } = dn_opts.meta_client_opts.unwrap();
         assert_eq!(vec!["127.0.0.1:3002".to_string()], metasrv_addr);
         assert_eq!(3000, timeout_millis);
@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use clap::Parser;
+use common_grpc::tls::{TlsMode, TlsOption};
 use frontend::frontend::{Frontend, FrontendOptions};
 use frontend::grpc::GrpcOptions;
 use frontend::influxdb::InfluxdbOptions;
@@ -24,7 +25,6 @@ use frontend::opentsdb::OpentsdbOptions;
 use frontend::postgres::PostgresOptions;
 use meta_client::MetaClientOpts;
 use servers::http::HttpOptions;
-use servers::tls::{TlsMode, TlsOption};
 use servers::Mode;
 use snafu::ResultExt;
 
@@ -110,12 +110,14 @@ impl TryFrom<StartCommand> for FrontendOptions {
         if let Some(addr) = cmd.http_addr {
             opts.http_options = Some(HttpOptions {
                 addr,
+                tls: Arc::new(tls_option.clone()),
                 ..Default::default()
             });
         }
         if let Some(addr) = cmd.grpc_addr {
             opts.grpc_options = Some(GrpcOptions {
                 addr,
+                tls: Arc::new(tls_option.clone()),
                 ..Default::default()
             });
         }
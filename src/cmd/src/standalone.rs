@@ -15,8 +15,9 @@
 use std::sync::Arc;
 
 use clap::Parser;
+use common_grpc::tls::{TlsMode, TlsOption};
 use common_telemetry::info;
-use datanode::datanode::{Datanode, DatanodeOptions, ObjectStoreConfig};
+use datanode::datanode::{Datanode, DatanodeOptions, ObjectStoreConfig, WriteBufferOptions};
 use datanode::instance::InstanceRef;
 use frontend::frontend::{Frontend, FrontendOptions};
 use frontend::grpc::GrpcOptions;
@@ -28,7 +29,7 @@ use frontend::postgres::PostgresOptions;
 use frontend::prometheus::PrometheusOptions;
 use serde::{Deserialize, Serialize};
 use servers::http::HttpOptions;
-use servers::tls::{TlsMode, TlsOption};
+use servers::process::QueryOptions;
 use servers::Mode;
 use snafu::ResultExt;
 
@@ -69,9 +70,12 @@ pub struct StandaloneOptions {
     pub opentsdb_options: Option<OpentsdbOptions>,
     pub influxdb_options: Option<InfluxdbOptions>,
     pub prometheus_options: Option<PrometheusOptions>,
+    pub query_options: Option<QueryOptions>,
     pub mode: Mode,
     pub wal_dir: String,
     pub storage: ObjectStoreConfig,
+    #[serde(default)]
+    pub write_buffer: WriteBufferOptions,
     pub enable_memory_catalog: bool,
 }
 
@@ -85,9 +89,11 @@ impl Default for StandaloneOptions {
             opentsdb_options: Some(OpentsdbOptions::default()),
             influxdb_options: Some(InfluxdbOptions::default()),
             prometheus_options: Some(PrometheusOptions::default()),
+            query_options: None,
             mode: Mode::Standalone,
             wal_dir: "/tmp/greptimedb/wal".to_string(),
             storage: ObjectStoreConfig::default(),
+            write_buffer: WriteBufferOptions::default(),
             enable_memory_catalog: false,
         }
     }
@@ -103,8 +109,10 @@ impl StandaloneOptions {
             opentsdb_options: self.opentsdb_options,
             influxdb_options: self.influxdb_options,
             prometheus_options: self.prometheus_options,
+            query_options: self.query_options,
             mode: self.mode,
             meta_client_opts: None,
+            datanode_client_tls: None,
         }
     }
 
@@ -112,6 +120,7 @@ impl StandaloneOptions {
         DatanodeOptions {
             wal_dir: self.wal_dir,
             storage: self.storage,
+            write_buffer: self.write_buffer,
             enable_memory_catalog: self.enable_memory_catalog,
             ..Default::default()
         }
@@ -188,6 +197,9 @@ async fn build_frontend(
 ) -> Result<Frontend<FeInstance>> {
     let mut frontend_instance = FeInstance::new_standalone(datanode_instance.clone());
     frontend_instance.set_catalog_manager(datanode_instance.catalog_manager().clone());
+    if let Some(privilege_manager) = datanode_instance.privilege_manager() {
+        frontend_instance.set_privilege_manager(privilege_manager);
+    }
     frontend_instance.set_script_handler(datanode_instance);
     Ok(Frontend::new(fe_opts, frontend_instance))
 }
@@ -262,10 +274,20 @@ impl TryFrom<StartCommand> for FrontendOptions {
         }
 
         if let Some(mut postgres_options) = opts.postgres_options {
-            postgres_options.tls = Arc::new(tls_option);
+            postgres_options.tls = Arc::new(tls_option.clone());
             opts.postgres_options = Some(postgres_options);
         }
 
+        if let Some(mut http_options) = opts.http_options {
+            http_options.tls = Arc::new(tls_option.clone());
+            opts.http_options = Some(http_options);
+        }
+
+        if let Some(mut grpc_options) = opts.grpc_options {
+            grpc_options.tls = Arc::new(tls_option);
+            opts.grpc_options = Some(grpc_options);
+        }
+
         Ok(opts)
     }
 }
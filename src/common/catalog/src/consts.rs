@@ -25,3 +25,15 @@ pub const MIN_USER_TABLE_ID: u32 = 1024;
 pub const SYSTEM_CATALOG_TABLE_ID: u32 = 0;
 /// scripts table id
 pub const SCRIPTS_TABLE_ID: u32 = 1;
+/// script schedules status table id
+pub const SCRIPT_SCHEDULES_TABLE_ID: u32 = 2;
+/// information_schema.tables table id
+pub const INFORMATION_SCHEMA_TABLES_TABLE_ID: u32 = 3;
+/// information_schema.columns table id
+pub const INFORMATION_SCHEMA_COLUMNS_TABLE_ID: u32 = 4;
+/// information_schema.schemata table id
+pub const INFORMATION_SCHEMA_SCHEMATA_TABLE_ID: u32 = 5;
+/// information_schema.regions table id
+pub const INFORMATION_SCHEMA_REGIONS_TABLE_ID: u32 = 6;
+/// script schedule definitions table id
+pub const SCRIPT_SCHEDULE_DEFINITIONS_TABLE_ID: u32 = 7;
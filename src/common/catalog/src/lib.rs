@@ -14,3 +14,30 @@
 
 pub mod consts;
 pub mod error;
+pub mod privilege;
+
+/// Parses the database name sent by clients when connecting, which is either `<schema>`,
+/// or `<catalog>-<schema>` if the client chooses the catalog (i.e. tenant) as well.
+pub fn parse_catalog_and_schema_from_client_database_name(db: &str) -> (Option<&str>, &str) {
+    match db.split_once('-') {
+        Some((catalog, schema)) => (Some(catalog), schema),
+        None => (None, db),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_catalog_and_schema_from_client_database_name() {
+        assert_eq!(
+            (None, "public"),
+            parse_catalog_and_schema_from_client_database_name("public")
+        );
+        assert_eq!(
+            (Some("team_a"), "public"),
+            parse_catalog_and_schema_from_client_database_name("team_a-public")
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Formatter};
+
+use serde::{Deserialize, Serialize};
+
+/// Wildcard of schema or table names in a [GrantObject].
+pub const WILDCARD: &str = "*";
+
+/// The user having all privileges, which is also the user of servers without authentication.
+pub const SUPERUSER: &str = "greptime";
+
+/// Privileges on tables which can be granted to roles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Privilege {
+    Select,
+    Insert,
+    Create,
+    Drop,
+    Alter,
+}
+
+impl Privilege {
+    /// All privileges, granted by `GRANT ALL`.
+    pub const ALL: [Privilege; 5] = [
+        Privilege::Select,
+        Privilege::Insert,
+        Privilege::Create,
+        Privilege::Drop,
+        Privilege::Alter,
+    ];
+
+    /// Parses the privilege from its name in SQL, case insensitively.
+    pub fn from_name(name: &str) -> Option<Privilege> {
+        Self::ALL
+            .into_iter()
+            .find(|privilege| privilege.as_str().eq_ignore_ascii_case(name))
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Privilege::Select => "SELECT",
+            Privilege::Insert => "INSERT",
+            Privilege::Create => "CREATE",
+            Privilege::Drop => "DROP",
+            Privilege::Alter => "ALTER",
+        }
+    }
+}
+
+impl Display for Privilege {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Tables a privilege is granted on, `schema` and `table` can be the [WILDCARD].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GrantObject {
+    pub schema: String,
+    pub table: String,
+}
+
+impl GrantObject {
+    pub fn new(schema: impl Into<String>, table: impl Into<String>) -> Self {
+        Self {
+            schema: schema.into(),
+            table: table.into(),
+        }
+    }
+
+    /// Returns whether the table `schema.table` is covered by this object.
+    pub fn matches(&self, schema: &str, table: &str) -> bool {
+        (self.schema == WILDCARD || self.schema == schema)
+            && (self.table == WILDCARD || self.table == table)
+    }
+}
+
+impl Display for GrantObject {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.schema, self.table)
+    }
+}
+
+/// A privilege on some tables, granted to a role.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct GrantedPrivilege {
+    pub privilege: Privilege,
+    pub object: GrantObject,
+}
+
+impl GrantedPrivilege {
+    pub fn new(privilege: Privilege, object: GrantObject) -> Self {
+        Self { privilege, object }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_privilege_from_name() {
+        assert_eq!(Some(Privilege::Select), Privilege::from_name("select"));
+        assert_eq!(Some(Privilege::Drop), Privilege::from_name("DROP"));
+        assert_eq!(None, Privilege::from_name("truncate"));
+        for privilege in Privilege::ALL {
+            assert_eq!(Some(privilege), Privilege::from_name(privilege.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_grant_object_matches() {
+        let object = GrantObject::new("public", "cpu");
+        assert!(object.matches("public", "cpu"));
+        assert!(!object.matches("public", "memory"));
+        assert!(!object.matches("other", "cpu"));
+
+        let object = GrantObject::new("public", WILDCARD);
+        assert!(object.matches("public", "cpu"));
+        assert!(!object.matches("other", "cpu"));
+
+        let object = GrantObject::new(WILDCARD, WILDCARD);
+        assert!(object.matches("public", "cpu"));
+        assert!(object.matches("other", "memory"));
+        assert_eq!("*.*", object.to_string());
+    }
+}
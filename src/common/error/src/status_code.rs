@@ -43,6 +43,10 @@ pub enum StatusCode {
     PlanQuery = 3000,
     /// The query engine fail to execute query.
     EngineExecuteQuery = 3001,
+    /// The query is cancelled.
+    Cancelled = 3002,
+    /// The query exceeds its timeout.
+    DeadlineExceeded = 3003,
     // ====== End of query related status code =========
 
     // ====== Begin of catalog related status code =====
@@ -74,6 +78,8 @@ pub enum StatusCode {
     AuthHeaderNotFound = 7003,
     /// Invalid http authorization header
     InvalidAuthHeader = 7004,
+    /// User is not allowed to access the requested resource
+    AccessDenied = 7005,
     // ====== End of auth related status code =====
 }
 
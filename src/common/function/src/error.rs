@@ -26,6 +26,25 @@ pub enum InnerError {
         source: DataTypeError,
         backtrace: Backtrace,
     },
+
+    #[snafu(display("Invalid arguments for function {}, {}", function, err_msg))]
+    InvalidFuncArgs {
+        function: String,
+        err_msg: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Failed to parse argument {} of function {}, source: {}",
+        raw,
+        function,
+        source
+    ))]
+    ParseTimeArgument {
+        function: String,
+        raw: String,
+        source: common_time::error::Error,
+    },
 }
 
 impl ErrorExt for InnerError {
@@ -16,6 +16,7 @@ pub mod aggregate;
 pub mod expression;
 pub mod function;
 pub mod function_registry;
+mod json;
 pub mod math;
 pub mod numpy;
 #[cfg(test)]
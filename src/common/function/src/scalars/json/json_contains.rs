@@ -0,0 +1,136 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::prelude::{Signature, Volatility};
+use datatypes::json::Json;
+use datatypes::prelude::*;
+use datatypes::vectors::{BooleanVectorBuilder, VectorRef};
+
+use crate::error::Result;
+use crate::scalars::function::{Function, FunctionContext};
+use crate::scalars::json::with_json_at;
+
+/// Returns whether the json document in the first argument contains the candidate json
+/// document in the second argument, e.g. `json_contains(col, '{"a": 1}')`.
+#[derive(Clone, Debug, Default)]
+pub struct JsonContainsFunction;
+
+const NAME: &str = "json_contains";
+
+impl Function for JsonContainsFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        Ok(ConcreteDataType::boolean_datatype())
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::any(2, Volatility::Immutable)
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        let len = columns[0].len();
+        // Most queries use a literal candidate, so only parse it once.
+        let const_candidate = if columns[1].is_const() {
+            Some(with_json_at(NAME, columns, 1, 0, |json| {
+                json.to_owned_json()
+            })?)
+        } else {
+            None
+        };
+
+        let mut builder = BooleanVectorBuilder::with_capacity(len);
+        let mut row_candidate: Option<Json>;
+        for row in 0..len {
+            let candidate = match &const_candidate {
+                Some(candidate) => candidate.as_ref(),
+                None => {
+                    row_candidate =
+                        with_json_at(NAME, columns, 1, row, |json| json.to_owned_json())?;
+                    row_candidate.as_ref()
+                }
+            };
+            let value = match candidate {
+                Some(candidate) => with_json_at(NAME, columns, 0, row, |json| {
+                    json.contains(&candidate.as_json_ref())
+                })?,
+                None => None,
+            };
+            builder.push(value);
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+}
+
+impl fmt::Display for JsonContainsFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "JSON_CONTAINS")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::vectors::{ConstantVector, JsonVector, StringVector};
+
+    use super::*;
+
+    #[test]
+    fn test_json_contains() {
+        let f = JsonContainsFunction::default();
+        assert_eq!("json_contains", f.name());
+        assert_eq!(
+            ConcreteDataType::boolean_datatype(),
+            f.return_type(&[]).unwrap()
+        );
+
+        let target: VectorRef = Arc::new(JsonVector::from(vec![
+            Some(r#"{"a": 1, "b": [1, 2]}"#.parse::<Json>().unwrap()),
+            None,
+            Some(r#"{"a": 2}"#.parse::<Json>().unwrap()),
+        ]));
+        let candidate: VectorRef = Arc::new(ConstantVector::new(
+            Arc::new(StringVector::from(vec![r#"{"a": 1}"#])),
+            3,
+        ));
+        let vector = f
+            .eval(FunctionContext::default(), &[target.clone(), candidate])
+            .unwrap();
+        assert_eq!(3, vector.len());
+        assert_eq!(Value::Boolean(true), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!(Value::Boolean(false), vector.get(2));
+
+        let candidate: VectorRef = Arc::new(StringVector::from(vec![
+            Some(r#"{"b": 2}"#),
+            Some("1"),
+            None,
+        ]));
+        let vector = f
+            .eval(FunctionContext::default(), &[target.clone(), candidate])
+            .unwrap();
+        assert_eq!(Value::Boolean(true), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!(Value::Null, vector.get(2));
+
+        let candidate: VectorRef = Arc::new(StringVector::from(vec!["{", "1", "2"]));
+        assert!(f
+            .eval(FunctionContext::default(), &[target, candidate])
+            .is_err());
+    }
+}
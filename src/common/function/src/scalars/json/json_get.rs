@@ -0,0 +1,228 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::prelude::{Signature, Volatility};
+use datatypes::prelude::*;
+use datatypes::vectors::{
+    BooleanVectorBuilder, Float64VectorBuilder, Int64VectorBuilder, StringVectorBuilder, VectorRef,
+};
+
+use crate::error::Result;
+use crate::scalars::function::{Function, FunctionContext};
+use crate::scalars::json::{json_path_at, with_json_at};
+
+/// Defines a function that extracts the value at a json path and converts it to a sql type.
+///
+/// The value is null if the path doesn't exist or the value has a different json type.
+macro_rules! define_json_get {
+    (
+        $Function: ident,
+        $name: literal,
+        $display: literal,
+        $datatype: ident,
+        $Builder: ident,
+        |$json: ident| $extract: expr
+    ) => {
+        #[derive(Clone, Debug, Default)]
+        pub struct $Function;
+
+        impl Function for $Function {
+            fn name(&self) -> &str {
+                $name
+            }
+
+            fn return_type(&self, _input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+                Ok(ConcreteDataType::$datatype())
+            }
+
+            fn signature(&self) -> Signature {
+                Signature::any(2, Volatility::Immutable)
+            }
+
+            fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+                let len = columns[0].len();
+                // Most queries use a literal path, so only parse it once.
+                let const_path = if columns[1].is_const() {
+                    Some(json_path_at($name, columns, 0)?)
+                } else {
+                    None
+                };
+
+                let mut builder = $Builder::with_capacity(len);
+                let mut row_path;
+                for row in 0..len {
+                    let path = match &const_path {
+                        Some(path) => path.as_ref(),
+                        None => {
+                            row_path = json_path_at($name, columns, row)?;
+                            row_path.as_ref()
+                        }
+                    };
+                    let value = match path {
+                        Some(path) => with_json_at($name, columns, 0, row, |json| {
+                            json.get(path).and_then(|$json| $extract)
+                        })?
+                        .flatten(),
+                        None => None,
+                    };
+                    builder.push(value.as_ref().map(|v| v.as_scalar_ref()));
+                }
+                Ok(Arc::new(builder.finish()))
+            }
+        }
+
+        impl fmt::Display for $Function {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, $display)
+            }
+        }
+    };
+}
+
+define_json_get!(
+    JsonGetStringFunction,
+    "json_get_string",
+    "JSON_GET_STRING",
+    string_datatype,
+    StringVectorBuilder,
+    |json| json.as_str().map(str::to_string)
+);
+
+define_json_get!(
+    JsonGetIntFunction,
+    "json_get_int",
+    "JSON_GET_INT",
+    int64_datatype,
+    Int64VectorBuilder,
+    |json| json.as_i64()
+);
+
+define_json_get!(
+    JsonGetFloatFunction,
+    "json_get_float",
+    "JSON_GET_FLOAT",
+    float64_datatype,
+    Float64VectorBuilder,
+    |json| json.as_f64()
+);
+
+define_json_get!(
+    JsonGetBoolFunction,
+    "json_get_bool",
+    "JSON_GET_BOOL",
+    boolean_datatype,
+    BooleanVectorBuilder,
+    |json| json.as_bool()
+);
+
+#[cfg(test)]
+mod tests {
+    use datatypes::json::Json;
+    use datatypes::vectors::{ConstantVector, Int64Vector, JsonVector, StringVector};
+
+    use super::*;
+
+    fn json_vector() -> VectorRef {
+        Arc::new(JsonVector::from(vec![
+            Some(r#"{"a": {"b": "x", "c": 1, "d": 1.5, "e": true}}"#.parse::<Json>().unwrap()),
+            None,
+            Some(r#"{"a": {"b": 2}}"#.parse::<Json>().unwrap()),
+        ]))
+    }
+
+    fn const_path(path: &str) -> VectorRef {
+        Arc::new(ConstantVector::new(
+            Arc::new(StringVector::from(vec![path])),
+            3,
+        ))
+    }
+
+    #[test]
+    fn test_json_get_string() {
+        let f = JsonGetStringFunction::default();
+        assert_eq!("json_get_string", f.name());
+        assert_eq!(
+            ConcreteDataType::string_datatype(),
+            f.return_type(&[]).unwrap()
+        );
+
+        let args = vec![json_vector(), const_path("$.a.b")];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(3, vector.len());
+        assert_eq!(Value::from("x"), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+        // Type mismatch.
+        assert_eq!(Value::Null, vector.get(2));
+    }
+
+    #[test]
+    fn test_json_get_typed() {
+        let args = vec![json_vector(), const_path("$.a.c")];
+        let vector = JsonGetIntFunction::default()
+            .eval(FunctionContext::default(), &args)
+            .unwrap();
+        assert_eq!(Value::Int64(1), vector.get(0));
+        assert_eq!(Value::Null, vector.get(2));
+
+        let args = vec![json_vector(), const_path("$.a.d")];
+        let vector = JsonGetFloatFunction::default()
+            .eval(FunctionContext::default(), &args)
+            .unwrap();
+        assert_eq!(Value::from(1.5f64), vector.get(0));
+
+        let args = vec![json_vector(), const_path("$.a.e")];
+        let vector = JsonGetBoolFunction::default()
+            .eval(FunctionContext::default(), &args)
+            .unwrap();
+        assert_eq!(Value::Boolean(true), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+    }
+
+    #[test]
+    fn test_json_get_from_string() {
+        let f = JsonGetIntFunction::default();
+        let args: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec![
+                Some(r#"{"a": [1, 2]}"#),
+                Some(r#"{"b": 3}"#),
+            ])),
+            Arc::new(StringVector::from(vec![Some("$.a[1]"), Some("$.b")])),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(Value::Int64(2), vector.get(0));
+        assert_eq!(Value::Int64(3), vector.get(1));
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(StringVector::from(vec!["{"])),
+            Arc::new(StringVector::from(vec!["$.a"])),
+        ];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+    }
+
+    #[test]
+    fn test_json_get_invalid_args() {
+        let f = JsonGetStringFunction::default();
+        let args = vec![json_vector(), const_path("a.b")];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+
+        let args: Vec<VectorRef> = vec![
+            Arc::new(Int64Vector::from_slice(&[1])),
+            Arc::new(StringVector::from(vec!["$.a"])),
+        ];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+    }
+}
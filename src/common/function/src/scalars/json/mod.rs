@@ -0,0 +1,87 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod json_contains;
+mod json_get;
+
+use std::sync::Arc;
+
+use common_query::error::{InvalidInputsSnafu, UnsupportedInputDataTypeSnafu};
+use datatypes::json::{Json, JsonPath, JsonRef};
+use datatypes::value::ValueRef;
+use datatypes::vectors::VectorRef;
+use json_contains::JsonContainsFunction;
+use json_get::{
+    JsonGetBoolFunction, JsonGetFloatFunction, JsonGetIntFunction, JsonGetStringFunction,
+};
+use snafu::ResultExt;
+
+use crate::error::Result;
+use crate::scalars::function_registry::FunctionRegistry;
+
+pub(crate) struct JsonFunction;
+
+impl JsonFunction {
+    pub fn register(registry: &FunctionRegistry) {
+        registry.register(Arc::new(JsonGetStringFunction::default()));
+        registry.register(Arc::new(JsonGetIntFunction::default()));
+        registry.register(Arc::new(JsonGetFloatFunction::default()));
+        registry.register(Arc::new(JsonGetBoolFunction::default()));
+        registry.register(Arc::new(JsonContainsFunction::default()));
+    }
+}
+
+/// Calls `f` with the json document at `row` of the `index`-th argument, string values are
+/// parsed as json text.
+///
+/// Returns `None` if the value is null.
+fn with_json_at<T>(
+    function: &str,
+    columns: &[VectorRef],
+    index: usize,
+    row: usize,
+    f: impl FnOnce(JsonRef) -> T,
+) -> Result<Option<T>> {
+    match columns[index].get_ref(row) {
+        ValueRef::Null => Ok(None),
+        ValueRef::Json(json) => Ok(Some(f(json))),
+        ValueRef::String(s) => {
+            let json: Json = s.parse().context(InvalidInputsSnafu {
+                err_msg: format!("argument {} of {} is not valid json", index, function),
+            })?;
+            Ok(Some(f(json.as_json_ref())))
+        }
+        _ => unsupported(function, columns),
+    }
+}
+
+/// Returns the json path at `row` of the second argument, or `None` if the path is null.
+fn json_path_at(function: &str, columns: &[VectorRef], row: usize) -> Result<Option<JsonPath>> {
+    match columns[1].get_ref(row) {
+        ValueRef::Null => Ok(None),
+        ValueRef::String(s) => Ok(Some(s.parse().context(InvalidInputsSnafu {
+            err_msg: format!("invalid json path for {}", function),
+        })?)),
+        _ => unsupported(function, columns),
+    }
+}
+
+fn unsupported<T>(function: &str, columns: &[VectorRef]) -> Result<T> {
+    UnsupportedInputDataTypeSnafu {
+        function,
+        datatypes: columns.iter().map(|c| c.data_type()).collect::<Vec<_>>(),
+    }
+    .fail()
+    .map_err(|e| e.into())
+}
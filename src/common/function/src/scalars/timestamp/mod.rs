@@ -14,8 +14,10 @@
 
 use std::sync::Arc;
 mod from_unixtime;
+mod time_bucket;
 
 use from_unixtime::FromUnixtimeFunction;
+use time_bucket::TimeBucketFunction;
 
 use crate::scalars::function_registry::FunctionRegistry;
 
@@ -24,5 +26,6 @@ pub(crate) struct TimestampFunction;
 impl TimestampFunction {
     pub fn register(registry: &FunctionRegistry) {
         registry.register(Arc::new(FromUnixtimeFunction::default()));
+        registry.register(Arc::new(TimeBucketFunction::default()));
     }
 }
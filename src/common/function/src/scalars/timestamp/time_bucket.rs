@@ -0,0 +1,268 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//! time_bucket function.
+use std::fmt;
+use std::sync::Arc;
+
+use common_query::error::UnsupportedInputDataTypeSnafu;
+use common_query::prelude::{Signature, TypeSignature, Volatility};
+use common_time::duration::parse_duration_nanos;
+use common_time::timestamp::{TimeUnit, Timestamp};
+use datatypes::prelude::{ConcreteDataType, ScalarVectorBuilder};
+use datatypes::value::ValueRef;
+use datatypes::vectors::{TimestampVectorBuilder, VectorRef};
+use snafu::{ensure, ResultExt};
+
+use crate::error::{InvalidFuncArgsSnafu, ParseTimeArgumentSnafu, Result};
+use crate::scalars::function::{Function, FunctionContext};
+
+/// Truncates timestamps to the start of the bucket they belong to.
+///
+/// `time_bucket(interval, ts [, origin])` splits time into buckets of `interval` that are aligned
+/// to `origin` (the unix epoch by default) and returns the bucket start in the unit of `ts`.
+#[derive(Clone, Debug, Default)]
+pub struct TimeBucketFunction;
+
+const NAME: &str = "time_bucket";
+
+impl Function for TimeBucketFunction {
+    fn name(&self) -> &str {
+        NAME
+    }
+
+    fn return_type(&self, input_types: &[ConcreteDataType]) -> Result<ConcreteDataType> {
+        match input_types.get(1) {
+            Some(data_type @ ConcreteDataType::Timestamp(_)) => Ok(data_type.clone()),
+            _ => UnsupportedInputDataTypeSnafu {
+                function: NAME,
+                datatypes: input_types.to_vec(),
+            }
+            .fail()
+            .map_err(|e| e.into()),
+        }
+    }
+
+    fn signature(&self) -> Signature {
+        Signature::one_of(
+            vec![TypeSignature::Any(2), TypeSignature::Any(3)],
+            Volatility::Immutable,
+        )
+    }
+
+    fn eval(&self, _func_ctx: FunctionContext, columns: &[VectorRef]) -> Result<VectorRef> {
+        let unit = match columns[1].data_type() {
+            ConcreteDataType::Timestamp(t) => t.unit,
+            _ => {
+                return UnsupportedInputDataTypeSnafu {
+                    function: NAME,
+                    datatypes: columns.iter().map(|c| c.data_type()).collect::<Vec<_>>(),
+                }
+                .fail()
+                .map_err(|e| e.into())
+            }
+        };
+
+        let len = columns[1].len();
+        // The interval is almost always a literal, so only parse it once.
+        let const_interval = if columns[0].is_const() {
+            Some(interval_at(columns, 0, unit)?)
+        } else {
+            None
+        };
+
+        let mut builder = TimestampVectorBuilder::with_capacity_and_unit(len, unit);
+        for row in 0..len {
+            let interval = match const_interval {
+                Some(interval) => interval,
+                None => interval_at(columns, row, unit)?,
+            };
+            let ts = match columns[1].get_ref(row) {
+                ValueRef::Timestamp(ts) => Some(ts.convert_to(unit)),
+                _ => None,
+            };
+            let bucket = match (interval, ts, origin_at(columns, row, unit)?) {
+                (Some(interval), Some(ts), Some(origin)) => bucket_start(ts, interval, origin),
+                _ => None,
+            };
+            builder.push(bucket.map(|v| Timestamp::new(v, unit)));
+        }
+        Ok(Arc::new(builder.finish()))
+    }
+}
+
+impl fmt::Display for TimeBucketFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "TIME_BUCKET")
+    }
+}
+
+/// Returns the interval at `row` in `unit`, or `None` if the interval is null.
+fn interval_at(columns: &[VectorRef], row: usize, unit: TimeUnit) -> Result<Option<i64>> {
+    let raw = match columns[0].get_ref(row) {
+        ValueRef::Null => return Ok(None),
+        ValueRef::String(s) => s,
+        _ => {
+            return UnsupportedInputDataTypeSnafu {
+                function: NAME,
+                datatypes: columns.iter().map(|c| c.data_type()).collect::<Vec<_>>(),
+            }
+            .fail()
+            .map_err(|e| e.into())
+        }
+    };
+    let nanos = parse_duration_nanos(raw).context(ParseTimeArgumentSnafu {
+        function: NAME,
+        raw,
+    })?;
+    ensure!(
+        nanos % unit.factor() == 0,
+        InvalidFuncArgsSnafu {
+            function: NAME,
+            err_msg: format!(
+                "interval {} is finer than the timestamp unit {:?}",
+                raw, unit
+            ),
+        }
+    );
+    Ok(Some(nanos / unit.factor()))
+}
+
+/// Returns the origin at `row` in `unit`, the unix epoch is used if there is no origin argument.
+fn origin_at(columns: &[VectorRef], row: usize, unit: TimeUnit) -> Result<Option<i64>> {
+    let origin = match columns.get(2) {
+        None => return Ok(Some(0)),
+        Some(origin) => origin,
+    };
+    match origin.get_ref(row) {
+        ValueRef::Null => Ok(None),
+        ValueRef::Timestamp(ts) => Ok(Some(ts.convert_to(unit))),
+        ValueRef::String(s) => {
+            let ts: Timestamp = s.parse().context(ParseTimeArgumentSnafu {
+                function: NAME,
+                raw: s,
+            })?;
+            Ok(Some(ts.convert_to(unit)))
+        }
+        _ => UnsupportedInputDataTypeSnafu {
+            function: NAME,
+            datatypes: columns.iter().map(|c| c.data_type()).collect::<Vec<_>>(),
+        }
+        .fail()
+        .map_err(|e| e.into()),
+    }
+}
+
+/// Returns the start of the bucket containing `ts`, or `None` if it overflows.
+fn bucket_start(ts: i64, interval: i64, origin: i64) -> Option<i64> {
+    let (ts, interval, origin) = (ts as i128, interval as i128, origin as i128);
+    i64::try_from(origin + (ts - origin).div_euclid(interval) * interval).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::prelude::ScalarVector;
+    use datatypes::value::Value;
+    use datatypes::vectors::{ConstantVector, StringVector, TimestampVector};
+
+    use super::*;
+
+    fn const_string(s: &str, len: usize) -> VectorRef {
+        Arc::new(ConstantVector::new(
+            Arc::new(StringVector::from(vec![s])),
+            len,
+        ))
+    }
+
+    fn timestamps(values: Vec<Option<i64>>, unit: TimeUnit) -> Vec<Value> {
+        values
+            .into_iter()
+            .map(|v| v.map_or(Value::Null, |v| Value::Timestamp(Timestamp::new(v, unit))))
+            .collect()
+    }
+
+    #[test]
+    fn test_time_bucket() {
+        let f = TimeBucketFunction::default();
+        assert_eq!("time_bucket", f.name());
+        let ts_type = ConcreteDataType::timestamp_datatype(TimeUnit::Second);
+        assert_eq!(
+            ts_type,
+            f.return_type(&[ConcreteDataType::string_datatype(), ts_type.clone()])
+                .unwrap()
+        );
+        assert!(f
+            .return_type(&[
+                ConcreteDataType::string_datatype(),
+                ConcreteDataType::int64_datatype()
+            ])
+            .is_err());
+
+        let ts: VectorRef = Arc::new(TimestampVector::from_values_with_unit(
+            vec![0, 59, 60, 119, -1],
+            TimeUnit::Second,
+        ));
+        let args = vec![const_string("1m", 5), ts.clone()];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(
+            timestamps(
+                vec![Some(0), Some(0), Some(60), Some(60), Some(-60)],
+                TimeUnit::Second
+            ),
+            (0..5).map(|i| vector.get(i)).collect::<Vec<_>>()
+        );
+
+        // Buckets are aligned to the origin.
+        let args = vec![
+            const_string("1m", 5),
+            ts.clone(),
+            const_string("1970-01-01T00:00:30Z", 5),
+        ];
+        let vector = f.eval(FunctionContext::default(), &args).unwrap();
+        assert_eq!(
+            timestamps(
+                vec![Some(-30), Some(30), Some(30), Some(90), Some(-30)],
+                TimeUnit::Second
+            ),
+            (0..5).map(|i| vector.get(i)).collect::<Vec<_>>()
+        );
+
+        // Intervals finer than the timestamp unit are rejected.
+        let args = vec![const_string("500ms", 5), ts.clone()];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+        let args = vec![const_string("1 fortnight", 5), ts];
+        assert!(f.eval(FunctionContext::default(), &args).is_err());
+    }
+
+    #[test]
+    fn test_time_bucket_nulls() {
+        let f = TimeBucketFunction::default();
+        let ts: VectorRef = Arc::new(TimestampVector::from_owned_iterator(
+            vec![Some(Timestamp::from_millis(1_500)), None].into_iter(),
+        ));
+        let interval: VectorRef = Arc::new(StringVector::from(vec![None, Some("1s")]));
+        let vector = f
+            .eval(FunctionContext::default(), &[interval, ts.clone()])
+            .unwrap();
+        assert_eq!(Value::Null, vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+
+        let vector = f
+            .eval(FunctionContext::default(), &[const_string("1s", 2), ts])
+            .unwrap();
+        assert_eq!(
+            timestamps(vec![Some(1_000), None], TimeUnit::Millisecond),
+            vec![vector.get(0), vector.get(1)]
+        );
+    }
+}
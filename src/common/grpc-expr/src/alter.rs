@@ -14,22 +14,25 @@
 
 use std::sync::Arc;
 
+use api::helper::ColumnDataTypeWrapper;
 use api::v1::alter_expr::Kind;
-use api::v1::{AlterExpr, CreateExpr, DropColumns};
+use api::v1::{AlterExpr, CreateExpr, DropColumns, ModifyColumns, RenameTable, SetTableOptions};
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use datatypes::schema::{ColumnSchema, SchemaBuilder, SchemaRef};
 use snafu::{ensure, OptionExt, ResultExt};
 use table::metadata::TableId;
-use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest, CreateTableRequest};
+use table::requests::{
+    AddColumnRequest, AlterKind, AlterTableRequest, CreateTableRequest, ModifyColumnRequest,
+};
 
 use crate::error::{
-    ColumnNotFoundSnafu, CreateSchemaSnafu, InvalidColumnDefSnafu, MissingFieldSnafu,
-    MissingTimestampColumnSnafu, Result,
+    ColumnDataTypeSnafu, ColumnNotFoundSnafu, CreateSchemaSnafu, InvalidColumnDefSnafu,
+    MissingFieldSnafu, MissingTimestampColumnSnafu, Result,
 };
 
 /// Convert an [`AlterExpr`] to an optional [`AlterTableRequest`]
 pub fn alter_expr_to_request(expr: AlterExpr) -> Result<Option<AlterTableRequest>> {
-    match expr.kind {
+    let alter_kind = match expr.kind {
         Some(Kind::AddColumns(add_columns)) => {
             let add_column_requests = add_columns
                 .add_columns
@@ -52,33 +55,51 @@ pub fn alter_expr_to_request(expr: AlterExpr) -> Result<Option<AlterTableRequest
                 })
                 .collect::<Result<Vec<_>>>()?;
 
-            let alter_kind = AlterKind::AddColumns {
+            AlterKind::AddColumns {
                 columns: add_column_requests,
-            };
-
-            let request = AlterTableRequest {
-                catalog_name: expr.catalog_name,
-                schema_name: expr.schema_name,
-                table_name: expr.table_name,
-                alter_kind,
-            };
-            Ok(Some(request))
+            }
         }
-        Some(Kind::DropColumns(DropColumns { drop_columns })) => {
-            let alter_kind = AlterKind::DropColumns {
-                names: drop_columns.into_iter().map(|c| c.name).collect(),
-            };
-
-            let request = AlterTableRequest {
-                catalog_name: expr.catalog_name,
-                schema_name: expr.schema_name,
-                table_name: expr.table_name,
-                alter_kind,
-            };
-            Ok(Some(request))
+        Some(Kind::DropColumns(DropColumns { drop_columns })) => AlterKind::DropColumns {
+            names: drop_columns.into_iter().map(|c| c.name).collect(),
+        },
+        Some(Kind::RenameTable(RenameTable { new_table_name })) => {
+            AlterKind::RenameTable { new_table_name }
         }
-        None => Ok(None),
-    }
+        Some(Kind::ModifyColumns(ModifyColumns { modify_columns })) => {
+            let modify_column_requests = modify_columns
+                .into_iter()
+                .map(|mc| {
+                    let data_type = ColumnDataTypeWrapper::try_new_with_extension(
+                        mc.datatype,
+                        mc.datatype_extension,
+                    )
+                    .context(ColumnDataTypeSnafu)?;
+                    Ok(ModifyColumnRequest {
+                        column_name: mc.name,
+                        data_type: data_type.into(),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            AlterKind::ModifyColumns {
+                columns: modify_column_requests,
+            }
+        }
+        Some(Kind::SetTableOptions(SetTableOptions { table_options })) => {
+            AlterKind::SetTableOptions {
+                options: table_options,
+            }
+        }
+        None => return Ok(None),
+    };
+
+    let request = AlterTableRequest {
+        catalog_name: expr.catalog_name,
+        schema_name: expr.schema_name,
+        table_name: expr.table_name,
+        alter_kind,
+    };
+    Ok(Some(request))
 }
 
 pub fn create_table_schema(expr: &CreateExpr) -> Result<SchemaRef> {
@@ -163,7 +184,9 @@ pub fn create_expr_to_request(table_id: TableId, expr: CreateExpr) -> Result<Cre
 
 #[cfg(test)]
 mod tests {
-    use api::v1::{AddColumn, AddColumns, ColumnDataType, ColumnDef, DropColumn};
+    use std::collections::HashMap;
+
+    use api::v1::{AddColumn, AddColumns, ColumnDataType, ColumnDef, DropColumn, ModifyColumn};
     use datatypes::prelude::ConcreteDataType;
 
     use super::*;
@@ -182,6 +205,7 @@ mod tests {
                         datatype: ColumnDataType::Float64 as i32,
                         is_nullable: false,
                         default_constraint: None,
+                        datatype_extension: None,
                     }),
                     is_key: false,
                 }],
@@ -231,4 +255,76 @@ mod tests {
         assert_eq!(1, drop_names.len());
         assert_eq!("mem_usage".to_string(), drop_names.pop().unwrap());
     }
+
+    #[test]
+    fn test_rename_table_expr() {
+        let expr = AlterExpr {
+            catalog_name: None,
+            schema_name: None,
+            table_name: "monitor".to_string(),
+
+            kind: Some(Kind::RenameTable(RenameTable {
+                new_table_name: "new_monitor".to_string(),
+            })),
+        };
+
+        let alter_request = alter_expr_to_request(expr).unwrap().unwrap();
+        assert_eq!("monitor".to_string(), alter_request.table_name);
+        let new_table_name = match alter_request.alter_kind {
+            AlterKind::RenameTable { new_table_name } => new_table_name,
+            _ => unreachable!(),
+        };
+        assert_eq!("new_monitor", new_table_name);
+    }
+
+    #[test]
+    fn test_modify_column_expr() {
+        let expr = AlterExpr {
+            catalog_name: None,
+            schema_name: None,
+            table_name: "monitor".to_string(),
+
+            kind: Some(Kind::ModifyColumns(ModifyColumns {
+                modify_columns: vec![ModifyColumn {
+                    name: "mem_usage".to_string(),
+                    datatype: ColumnDataType::Float64 as i32,
+                    datatype_extension: None,
+                }],
+            })),
+        };
+
+        let alter_request = alter_expr_to_request(expr).unwrap().unwrap();
+        let mut columns = match alter_request.alter_kind {
+            AlterKind::ModifyColumns { columns } => columns,
+            _ => unreachable!(),
+        };
+        assert_eq!(1, columns.len());
+        let modify_column = columns.pop().unwrap();
+        assert_eq!("mem_usage", modify_column.column_name);
+        assert_eq!(
+            ConcreteDataType::float64_datatype(),
+            modify_column.data_type
+        );
+    }
+
+    #[test]
+    fn test_set_table_options_expr() {
+        let table_options = HashMap::from([("ttl".to_string(), "7d".to_string())]);
+        let expr = AlterExpr {
+            catalog_name: None,
+            schema_name: None,
+            table_name: "monitor".to_string(),
+
+            kind: Some(Kind::SetTableOptions(SetTableOptions {
+                table_options: table_options.clone(),
+            })),
+        };
+
+        let alter_request = alter_expr_to_request(expr).unwrap().unwrap();
+        let options = match alter_request.alter_kind {
+            AlterKind::SetTableOptions { options } => options,
+            _ => unreachable!(),
+        };
+        assert_eq!(table_options, options);
+    }
 }
@@ -16,13 +16,16 @@ use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use api::helper::ColumnDataTypeWrapper;
+use api::helper::{decimal128_type_from_extension, ColumnDataTypeWrapper};
 use api::v1::column::{SemanticType, Values};
-use api::v1::{AddColumn, AddColumns, Column, ColumnDataType, ColumnDef, CreateExpr};
+use api::v1::{
+    AddColumn, AddColumns, Column, ColumnDataType, ColumnDataTypeExtension, ColumnDef, CreateExpr,
+};
 use common_base::BitVec;
-use common_time::timestamp::Timestamp;
+use common_time::timestamp::{TimeUnit, Timestamp};
 use common_time::{Date, DateTime};
 use datatypes::data_type::ConcreteDataType;
+use datatypes::decimal::Decimal128;
 use datatypes::prelude::{ValueRef, VectorRef};
 use datatypes::schema::SchemaRef;
 use datatypes::value::Value;
@@ -40,12 +43,18 @@ const TAG_SEMANTIC_TYPE: i32 = SemanticType::Tag as i32;
 const TIMESTAMP_SEMANTIC_TYPE: i32 = SemanticType::Timestamp as i32;
 
 #[inline]
-fn build_column_def(column_name: &str, datatype: i32, nullable: bool) -> ColumnDef {
+fn build_column_def(
+    column_name: &str,
+    datatype: i32,
+    datatype_extension: Option<ColumnDataTypeExtension>,
+    nullable: bool,
+) -> ColumnDef {
     ColumnDef {
         name: column_name.to_string(),
         datatype,
         is_nullable: nullable,
         default_constraint: None,
+        datatype_extension,
     }
 }
 
@@ -57,12 +66,18 @@ pub fn find_new_columns(schema: &SchemaRef, columns: &[Column]) -> Result<Option
         column_name,
         semantic_type,
         datatype,
+        datatype_extension,
         ..
     } in columns
     {
         if schema.column_schema_by_name(column_name).is_none() && !new_columns.contains(column_name)
         {
-            let column_def = Some(build_column_def(column_name, *datatype, true));
+            let column_def = Some(build_column_def(
+                column_name,
+                *datatype,
+                datatype_extension.clone(),
+                true,
+            ));
             columns_to_add.push(AddColumn {
                 column_def,
                 is_key: *semantic_type == TAG_SEMANTIC_TYPE,
@@ -95,14 +110,19 @@ pub fn build_alter_table_request(
 }
 
 pub fn column_to_vector(column: &Column, rows: u32) -> Result<VectorRef> {
-    let wrapper = ColumnDataTypeWrapper::try_new(column.datatype).context(ColumnDataTypeSnafu)?;
+    let wrapper = ColumnDataTypeWrapper::try_new_with_extension(
+        column.datatype,
+        column.datatype_extension.clone(),
+    )
+    .context(ColumnDataTypeSnafu)?;
     let column_datatype = wrapper.datatype();
+    let data_type: ConcreteDataType = wrapper.into();
 
     let rows = rows as usize;
-    let mut vector = VectorBuilder::with_capacity(wrapper.into(), rows);
+    let mut vector = VectorBuilder::with_capacity(data_type.clone(), rows);
 
     if let Some(values) = &column.values {
-        let values = collect_column_values(column_datatype, values);
+        let values = collect_column_values(column_datatype, &data_type, values);
         let mut values_iter = values.into_iter();
 
         let null_mask = BitVec::from_slice(&column.null_mask);
@@ -127,7 +147,11 @@ pub fn column_to_vector(column: &Column, rows: u32) -> Result<VectorRef> {
     Ok(vector.finish())
 }
 
-fn collect_column_values(column_datatype: ColumnDataType, values: &Values) -> Vec<ValueRef> {
+fn collect_column_values<'a>(
+    column_datatype: ColumnDataType,
+    data_type: &ConcreteDataType,
+    values: &'a Values,
+) -> Vec<ValueRef<'a>> {
     macro_rules! collect_values {
         ($value: expr, $mapper: expr) => {
             $value.iter().map($mapper).collect::<Vec<ValueRef>>()
@@ -179,6 +203,32 @@ fn collect_column_values(column_datatype: ColumnDataType, values: &Values) -> Ve
                 Timestamp::from_millis(*v)
             ))
         }
+        ColumnDataType::TimestampSecond => {
+            collect_values!(values.ts_second_values, |v| ValueRef::Timestamp(
+                Timestamp::new(*v, TimeUnit::Second)
+            ))
+        }
+        ColumnDataType::TimestampMicrosecond => {
+            collect_values!(values.ts_microsecond_values, |v| ValueRef::Timestamp(
+                Timestamp::new(*v, TimeUnit::Microsecond)
+            ))
+        }
+        ColumnDataType::TimestampNanosecond => {
+            collect_values!(values.ts_nanosecond_values, |v| ValueRef::Timestamp(
+                Timestamp::new(*v, TimeUnit::Nanosecond)
+            ))
+        }
+        ColumnDataType::Decimal128 => {
+            let decimal_type = match data_type {
+                ConcreteDataType::Decimal128(t) => *t,
+                _ => unreachable!(),
+            };
+            collect_values!(values.decimal128_values, |v| ValueRef::Decimal128(
+                Decimal128::new(v.clone().into(), decimal_type.precision, decimal_type.scale)
+            ))
+        }
+        // Json values are in text format and parsed by the vector builder.
+        ColumnDataType::Json => collect_values!(values.json_values, |v| ValueRef::String(v)),
     }
 }
 
@@ -199,6 +249,7 @@ pub fn build_create_expr_from_insertion(
         column_name,
         semantic_type,
         datatype,
+        datatype_extension,
         ..
     } in columns
     {
@@ -221,7 +272,12 @@ pub fn build_create_expr_from_insertion(
                 _ => {}
             }
 
-            let column_def = build_column_def(column_name, *datatype, is_nullable);
+            let column_def = build_column_def(
+                column_name,
+                *datatype,
+                datatype_extension.clone(),
+                is_nullable,
+            );
             column_defs.push(column_def);
             new_columns.insert(column_name.to_string());
         }
@@ -270,6 +326,7 @@ pub fn insertion_expr_to_request(
             column_name,
             values,
             null_mask,
+            datatype_extension,
             ..
         } in columns
         {
@@ -295,7 +352,13 @@ pub fn insertion_expr_to_request(
                     ))
                 }
             };
-            add_values_to_builder(vector_builder, values, row_count as usize, null_mask)?;
+            add_values_to_builder(
+                vector_builder,
+                values,
+                datatype_extension,
+                row_count as usize,
+                null_mask,
+            )?;
         }
     }
     let columns_values = columns_builders
@@ -314,11 +377,37 @@ pub fn insertion_expr_to_request(
 fn add_values_to_builder(
     builder: &mut VectorBuilder,
     values: Values,
+    datatype_extension: Option<ColumnDataTypeExtension>,
     row_count: usize,
     null_mask: Vec<u8>,
 ) -> Result<()> {
     let data_type = builder.data_type();
-    let values = convert_values(&data_type, values);
+    let values = match &data_type {
+        ConcreteDataType::Decimal128(t) => {
+            // Decimals are written in the precision and scale of the client, rescale them
+            // to the type of the column.
+            let source_type = datatype_extension
+                .as_ref()
+                .and_then(decimal128_type_from_extension)
+                .unwrap_or(*t);
+            convert_values(&ConcreteDataType::Decimal128(source_type), values)
+                .into_iter()
+                .map(|v| match v {
+                    Value::Decimal128(d) => d
+                        .rescale(t.precision, t.scale)
+                        .map(Value::Decimal128)
+                        .context(CreateVectorSnafu),
+                    v => Ok(v),
+                })
+                .collect::<Result<Vec<_>>>()?
+        }
+        ConcreteDataType::Json(_) => values
+            .json_values
+            .into_iter()
+            .map(|v| v.parse().map(Value::Json).context(CreateVectorSnafu))
+            .collect::<Result<Vec<_>>>()?,
+        _ => convert_values(&data_type, values),
+    };
 
     if null_mask.is_empty() {
         ensure!(values.len() == row_count, IllegalInsertDataSnafu);
@@ -417,13 +506,30 @@ fn convert_values(data_type: &ConcreteDataType, values: Values) -> Vec<Value> {
             .into_iter()
             .map(|v| Value::Date(v.into()))
             .collect(),
-        ConcreteDataType::Timestamp(_) => values
-            .ts_millis_values
+        // Timestamps keep the unit they were written in, the vector builder converts them to
+        // the unit of the column.
+        ConcreteDataType::Timestamp(_) => [
+            (values.ts_second_values, TimeUnit::Second),
+            (values.ts_millis_values, TimeUnit::Millisecond),
+            (values.ts_microsecond_values, TimeUnit::Microsecond),
+            (values.ts_nanosecond_values, TimeUnit::Nanosecond),
+        ]
+        .into_iter()
+        .flat_map(|(values, unit)| {
+            values
+                .into_iter()
+                .map(move |v| Value::Timestamp(Timestamp::new(v, unit)))
+        })
+        .collect(),
+        ConcreteDataType::Decimal128(t) => values
+            .decimal128_values
             .into_iter()
-            .map(|v| Value::Timestamp(Timestamp::from_millis(v)))
+            .map(|v| Value::Decimal128(Decimal128::new(v.into(), t.precision, t.scale)))
             .collect(),
         ConcreteDataType::Null(_) => unreachable!(),
         ConcreteDataType::List(_) => unreachable!(),
+        // Parsing json may fail, so json values are converted by `add_values_to_builder()`.
+        ConcreteDataType::Json(_) => unreachable!(),
     }
 }
 
@@ -442,18 +548,20 @@ mod tests {
     use common_base::BitVec;
     use common_query::physical_plan::PhysicalPlanRef;
     use common_query::prelude::Expr;
-    use common_time::timestamp::Timestamp;
+    use common_time::timestamp::{TimeUnit, Timestamp};
     use datatypes::data_type::ConcreteDataType;
+    use datatypes::decimal::Decimal128;
     use datatypes::schema::{ColumnSchema, SchemaBuilder, SchemaRef};
     use datatypes::value::Value;
+    use datatypes::vectors::VectorBuilder;
     use snafu::ResultExt;
     use table::error::Result as TableResult;
     use table::metadata::TableInfoRef;
     use table::Table;
 
     use super::{
-        build_create_expr_from_insertion, convert_values, insertion_expr_to_request, is_null,
-        TAG_SEMANTIC_TYPE, TIMESTAMP_SEMANTIC_TYPE,
+        add_values_to_builder, build_create_expr_from_insertion, column_to_vector, convert_values,
+        insertion_expr_to_request, is_null, TAG_SEMANTIC_TYPE, TIMESTAMP_SEMANTIC_TYPE,
     };
     use crate::error;
     use crate::error::ColumnDataTypeSnafu;
@@ -648,6 +756,140 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_column_to_vector_with_timestamp_unit() {
+        let column = Column {
+            column_name: "ts".to_string(),
+            semantic_type: SemanticType::Timestamp as i32,
+            values: Some(Values {
+                ts_nanosecond_values: vec![1663840496100023100, 1663840496400340001],
+                ..Default::default()
+            }),
+            null_mask: vec![],
+            datatype: ColumnDataType::TimestampNanosecond as i32,
+            datatype_extension: None,
+        };
+
+        let vector = column_to_vector(&column, 2).unwrap();
+        assert_eq!(
+            ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond),
+            vector.data_type()
+        );
+        assert_eq!(
+            Value::Timestamp(Timestamp::new(1663840496400340001, TimeUnit::Nanosecond)),
+            vector.get(1)
+        );
+
+        let values = Values {
+            ts_second_values: vec![1663840496],
+            ..Default::default()
+        };
+        let result = convert_values(
+            &ConcreteDataType::timestamp_datatype(TimeUnit::Second),
+            values,
+        );
+        assert_eq!(
+            vec![Value::Timestamp(Timestamp::new(
+                1663840496,
+                TimeUnit::Second
+            ))],
+            result
+        );
+    }
+
+    #[test]
+    fn test_column_to_vector_with_decimal() {
+        let datatype: ColumnDataTypeWrapper = ConcreteDataType::decimal128_datatype(10, 2)
+            .try_into()
+            .unwrap();
+        let column = Column {
+            column_name: "price".to_string(),
+            semantic_type: SemanticType::Field as i32,
+            values: Some(Values {
+                decimal128_values: vec![12345.into(), (-5).into()],
+                ..Default::default()
+            }),
+            null_mask: vec![2],
+            datatype: datatype.datatype() as i32,
+            datatype_extension: datatype.datatype_extension(),
+        };
+
+        let vector = column_to_vector(&column, 3).unwrap();
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(10, 2),
+            vector.data_type()
+        );
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 2)),
+            vector.get(0)
+        );
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!("-0.05", vector.get(2).to_string());
+
+        // Values are rescaled to the decimal type of the column.
+        let mut builder =
+            VectorBuilder::with_capacity(ConcreteDataType::decimal128_datatype(12, 4), 3);
+        add_values_to_builder(
+            &mut builder,
+            column.values.clone().unwrap(),
+            column.datatype_extension.clone(),
+            3,
+            column.null_mask.clone(),
+        )
+        .unwrap();
+        let vector = builder.finish();
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(1234500, 12, 4)),
+            vector.get(0)
+        );
+
+        let mut builder =
+            VectorBuilder::with_capacity(ConcreteDataType::decimal128_datatype(3, 1), 3);
+        assert!(add_values_to_builder(
+            &mut builder,
+            column.values.unwrap(),
+            column.datatype_extension,
+            3,
+            column.null_mask,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_column_to_vector_with_json() {
+        let mut column = Column {
+            column_name: "attrs".to_string(),
+            semantic_type: SemanticType::Field as i32,
+            values: Some(Values {
+                json_values: vec![r#"{"a": {"b": 1}}"#.to_string(), "[1, 2]".to_string()],
+                ..Default::default()
+            }),
+            null_mask: vec![1],
+            datatype: ColumnDataType::Json as i32,
+            datatype_extension: None,
+        };
+
+        let vector = column_to_vector(&column, 3).unwrap();
+        assert_eq!(ConcreteDataType::json_datatype(), vector.data_type());
+        assert_eq!(Value::Null, vector.get(0));
+        assert_eq!(r#"{"a":{"b":1}}"#, vector.get(1).to_string());
+        assert_eq!("[1,2]", vector.get(2).to_string());
+
+        let mut builder = VectorBuilder::with_capacity(ConcreteDataType::json_datatype(), 3);
+        add_values_to_builder(
+            &mut builder,
+            column.values.clone().unwrap(),
+            None,
+            3,
+            column.null_mask.clone(),
+        )
+        .unwrap();
+        assert_eq!(vector, builder.finish());
+
+        column.values.as_mut().unwrap().json_values[0] = "{".to_string();
+        assert!(column_to_vector(&column, 3).is_err());
+    }
+
     #[test]
     fn test_is_null() {
         let null_mask = BitVec::from_slice(&[0b0000_0001, 0b0000_1000]);
@@ -714,6 +956,7 @@ mod tests {
             values: Some(host_vals),
             null_mask: vec![0],
             datatype: ColumnDataType::String as i32,
+            datatype_extension: None,
         };
 
         let cpu_vals = column::Values {
@@ -726,6 +969,7 @@ mod tests {
             values: Some(cpu_vals),
             null_mask: vec![2],
             datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
         };
 
         let mem_vals = column::Values {
@@ -738,6 +982,7 @@ mod tests {
             values: Some(mem_vals),
             null_mask: vec![1],
             datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
         };
 
         let ts_vals = column::Values {
@@ -750,6 +995,7 @@ mod tests {
             values: Some(ts_vals),
             null_mask: vec![0],
             datatype: ColumnDataType::Timestamp as i32,
+            datatype_extension: None,
         };
 
         (
@@ -18,8 +18,11 @@ use std::time::Duration;
 
 use dashmap::mapref::entry::Entry;
 use dashmap::DashMap;
+use serde::{Deserialize, Serialize};
 use snafu::ResultExt;
-use tonic::transport::{Channel as InnerChannel, Endpoint, Uri};
+use tonic::transport::{
+    Certificate, Channel as InnerChannel, ClientTlsConfig, Endpoint, Identity, Uri,
+};
 use tower::make::MakeConnection;
 
 use crate::error;
@@ -119,8 +122,19 @@ impl ChannelManager {
     }
 
     fn build_endpoint(&self, addr: &str) -> Result<Endpoint> {
+        let scheme = if self.config.client_tls.is_some() {
+            "https"
+        } else {
+            "http"
+        };
         let mut endpoint =
-            Endpoint::new(format!("http://{}", addr)).context(error::CreateChannelSnafu)?;
+            Endpoint::new(format!("{}://{}", scheme, addr)).context(error::CreateChannelSnafu)?;
+
+        if let Some(tls) = &self.config.client_tls {
+            endpoint = endpoint
+                .tls_config(tls.client_tls_config()?)
+                .context(error::CreateChannelSnafu)?;
+        }
 
         if let Some(dur) = self.config.timeout {
             endpoint = endpoint.timeout(dur);
@@ -174,6 +188,36 @@ pub struct ChannelConfig {
     pub http2_adaptive_window: Option<bool>,
     pub tcp_keepalive: Option<Duration>,
     pub tcp_nodelay: bool,
+    pub client_tls: Option<ClientTlsOption>,
+}
+
+/// TLS options of the client side, the server is verified by the CA certificate and the client
+/// certificate is only sent to servers requiring mutual TLS.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClientTlsOption {
+    pub server_ca_cert_path: String,
+    #[serde(default)]
+    pub client_cert_path: String,
+    #[serde(default)]
+    pub client_key_path: String,
+}
+
+impl ClientTlsOption {
+    fn client_tls_config(&self) -> Result<ClientTlsConfig> {
+        let ca = read_tls_file(&self.server_ca_cert_path)?;
+        let mut config = ClientTlsConfig::new().ca_certificate(Certificate::from_pem(ca));
+
+        if !self.client_cert_path.is_empty() && !self.client_key_path.is_empty() {
+            let cert = read_tls_file(&self.client_cert_path)?;
+            let key = read_tls_file(&self.client_key_path)?;
+            config = config.identity(Identity::from_pem(cert, key));
+        }
+        Ok(config)
+    }
+}
+
+fn read_tls_file(path: &str) -> Result<Vec<u8>> {
+    std::fs::read(path).context(error::ReadTlsFileSnafu { path })
 }
 
 impl Default for ChannelConfig {
@@ -191,6 +235,7 @@ impl Default for ChannelConfig {
             http2_adaptive_window: None,
             tcp_keepalive: None,
             tcp_nodelay: true,
+            client_tls: None,
         }
     }
 }
@@ -307,6 +352,13 @@ impl ChannelConfig {
             ..self
         }
     }
+
+    /// Set the TLS options to connect to the server, `None` for plaintext connections.
+    ///
+    /// Default is None
+    pub fn client_tls_config(self, client_tls: Option<ClientTlsOption>) -> Self {
+        Self { client_tls, ..self }
+    }
 }
 
 #[derive(Debug)]
@@ -443,6 +495,7 @@ mod tests {
                 http2_adaptive_window: None,
                 tcp_keepalive: None,
                 tcp_nodelay: true,
+                client_tls: None,
             },
             default_cfg
         );
@@ -459,7 +512,11 @@ mod tests {
             .http2_keep_alive_while_idle(true)
             .http2_adaptive_window(true)
             .tcp_keepalive(Duration::from_secs(2))
-            .tcp_nodelay(false);
+            .tcp_nodelay(false)
+            .client_tls_config(Some(ClientTlsOption {
+                server_ca_cert_path: "/path/to/ca.crt".to_string(),
+                ..Default::default()
+            }));
 
         assert_eq!(
             ChannelConfig {
@@ -475,6 +532,10 @@ mod tests {
                 http2_adaptive_window: Some(true),
                 tcp_keepalive: Some(Duration::from_secs(2)),
                 tcp_nodelay: false,
+                client_tls: Some(ClientTlsOption {
+                    server_ca_cert_path: "/path/to/ca.crt".to_string(),
+                    ..Default::default()
+                }),
             },
             cfg
         );
@@ -503,6 +564,19 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[test]
+    fn test_build_endpoint_with_invalid_tls() {
+        let pool = Arc::new(Pool::default());
+        let config = ChannelConfig::new().client_tls_config(Some(ClientTlsOption {
+            server_ca_cert_path: "/path/not/exist/ca.crt".to_string(),
+            ..Default::default()
+        }));
+        let mgr = ChannelManager { pool, config };
+
+        let err = mgr.build_endpoint("test_addr").unwrap_err();
+        assert!(matches!(err, error::Error::ReadTlsFile { .. }));
+    }
+
     #[tokio::test]
     async fn test_channel_with_connector() {
         let pool = Pool {
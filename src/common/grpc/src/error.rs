@@ -44,6 +44,13 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display("Failed to read TLS file {}, source: {}", path, source))]
+    ReadTlsFile {
+        path: String,
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Failed to collect RecordBatches, source: {}", source))]
     CollectRecordBatches {
         #[snafu(backtrace)]
@@ -65,6 +72,7 @@ impl ErrorExt for Error {
         match self {
             Error::MissingField { .. } | Error::TypeMismatch { .. } => StatusCode::InvalidArguments,
             Error::CreateChannel { .. } | Error::Conversion { .. } => StatusCode::Internal,
+            Error::ReadTlsFile { .. } => StatusCode::InvalidArguments,
             Error::CollectRecordBatches { source } => source.status_code(),
             Error::ColumnDataType { source } => source.status_code(),
         }
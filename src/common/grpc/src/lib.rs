@@ -15,6 +15,7 @@
 pub mod channel_manager;
 pub mod error;
 pub mod select;
+pub mod tls;
 pub mod writer;
 
 pub use error::Error;
@@ -26,7 +26,9 @@ use common_query::Output;
 use common_recordbatch::{RecordBatches, SendableRecordBatchStream};
 use datatypes::arrow::array::{Array, BooleanArray, PrimitiveArray};
 use datatypes::arrow_array::{BinaryArray, StringArray};
+use datatypes::json::JsonRef;
 use datatypes::schema::SchemaRef;
+use datatypes::types::JSON_EXTENSION_NAME;
 use snafu::{OptionExt, ResultExt};
 
 use crate::error::{self, ConversionSnafu, Result};
@@ -94,14 +96,15 @@ fn try_convert(record_batches: RecordBatches) -> Result<SelectResult> {
             .map(|r| r.df_recordbatch.columns()[idx].clone())
             .collect();
 
+        let datatype = ColumnDataTypeWrapper::try_from(column_schema.data_type.clone())
+            .context(error::ColumnDataTypeSnafu)?;
         let column = Column {
             column_name,
             values: Some(values(&arrays)?),
             null_mask: null_mask(&arrays, row_count),
-            datatype: ColumnDataTypeWrapper::try_from(column_schema.data_type.clone())
-                .context(error::ColumnDataTypeSnafu)?
-                .datatype() as i32,
+            datatype: datatype.datatype() as i32,
             semantic_type: get_semantic_type(&schema, idx),
+            datatype_extension: datatype.datatype_extension(),
         };
         columns.push(column);
     }
@@ -132,11 +135,11 @@ pub fn null_mask(arrays: &Vec<Arc<dyn Array>>, row_count: usize) -> Vec<u8> {
 }
 
 macro_rules! convert_arrow_array_to_grpc_vals {
-    ($data_type: expr, $arrays: ident,  $(($Type: pat, $CastType: ty, $field: ident, $MapFunction: expr)), +) => {{
+    ($data_type: expr, $arrays: ident,  $(($Type: pat $(if $guard: expr)?, $CastType: ty, $field: ident, $MapFunction: expr)), +) => {{
         use datatypes::arrow::datatypes::{DataType, TimeUnit};
         match $data_type {
             $(
-                $Type => {
+                $Type $(if $guard)? => {
                     let mut vals = Values::default();
                     for array in $arrays {
                         let array = array.as_any().downcast_ref::<$CastType>().with_context(|| ConversionSnafu {
@@ -188,7 +191,15 @@ pub fn values(arrays: &[Arc<dyn Array>]) -> Result<Values> {
         (DataType::Date32,        PrimitiveArray<i32>,    date_values,    |x| {*x as i32}),
         (DataType::Date64,        PrimitiveArray<i64>,    datetime_values,|x| {*x as i64}),
 
-        (DataType::Timestamp(TimeUnit::Millisecond, _), PrimitiveArray<i64>, ts_millis_values, |x| {*x})
+        (DataType::Timestamp(TimeUnit::Second, _),      PrimitiveArray<i64>, ts_second_values,      |x| {*x}),
+        (DataType::Timestamp(TimeUnit::Millisecond, _), PrimitiveArray<i64>, ts_millis_values,      |x| {*x}),
+        (DataType::Timestamp(TimeUnit::Microsecond, _), PrimitiveArray<i64>, ts_microsecond_values, |x| {*x}),
+        (DataType::Timestamp(TimeUnit::Nanosecond, _),  PrimitiveArray<i64>, ts_nanosecond_values,  |x| {*x}),
+
+        (DataType::Decimal(_, _), PrimitiveArray<i128>, decimal128_values, |x| {(*x).into()}),
+
+        // Json is sent in text format.
+        (DataType::Extension(name, _, _) if name == JSON_EXTENSION_NAME, BinaryArray, json_values, |x| {JsonRef::from_bytes_unchecked(x).to_string()})
     )
 }
 
@@ -199,10 +210,10 @@ mod tests {
     use common_recordbatch::{RecordBatch, RecordBatches};
     use datafusion::field_util::SchemaExt;
     use datatypes::arrow::array::{Array, BooleanArray, PrimitiveArray};
-    use datatypes::arrow::datatypes::{DataType, Field, Schema as ArrowSchema};
+    use datatypes::arrow::datatypes::{DataType, Field, Schema as ArrowSchema, TimeUnit};
     use datatypes::arrow_array::StringArray;
     use datatypes::schema::Schema;
-    use datatypes::vectors::{UInt32Vector, VectorRef};
+    use datatypes::vectors::{JsonVector, UInt32Vector, Vector, VectorRef};
 
     use crate::select::{null_mask, try_convert, values};
 
@@ -265,6 +276,28 @@ mod tests {
         assert_eq!(vec![true, false, false], values.bool_values);
     }
 
+    #[test]
+    fn test_convert_arrow_arrays_timestamp() {
+        let array = PrimitiveArray::<i64>::from(vec![Some(1663840496100023100), None])
+            .to(DataType::Timestamp(TimeUnit::Nanosecond, None));
+        let array: Arc<dyn Array> = Arc::new(array);
+
+        let values = values(&[array]).unwrap();
+
+        assert_eq!(vec![1663840496100023100], values.ts_nanosecond_values);
+        assert!(values.ts_millis_values.is_empty());
+    }
+
+    #[test]
+    fn test_convert_arrow_arrays_json() {
+        let vector = JsonVector::from(vec![Some(r#"{"a": 1}"#.parse().unwrap()), None]);
+        let array = vector.to_arrow_array();
+
+        let values = values(&[array]).unwrap();
+
+        assert_eq!(vec![r#"{"a":1}"#], values.json_values);
+    }
+
     #[test]
     fn test_convert_arrow_arrays_empty() {
         let array = BooleanArray::from(vec![None, None, None, None, None]);
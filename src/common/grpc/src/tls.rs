@@ -15,12 +15,17 @@
 use std::fs::File;
 use std::io::{BufReader, Error, ErrorKind};
 
-use rustls::{Certificate, PrivateKey, ServerConfig};
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
 use rustls_pemfile::{certs, pkcs8_private_keys};
 use serde::{Deserialize, Serialize};
 use strum::EnumString;
+use tonic::transport::ServerTlsConfig;
 
-/// TlsMode is used for Mysql and Postgres server start up.
+/// TlsMode is used for the start up of Mysql, Postgres, HTTP and gRPC servers.
+///
+/// HTTP and gRPC servers can't serve plaintext and TLS connections on the same port, so they
+/// only accept TLS connections if the mode is not [TlsMode::Disable].
 #[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, EnumString)]
 #[serde(rename_all = "snake_case")]
 pub enum TlsMode {
@@ -51,6 +56,9 @@ pub struct TlsOption {
     pub cert_path: String,
     #[serde(default)]
     pub key_path: String,
+    /// CA certificate to verify the client certificates, enables mutual TLS if present.
+    #[serde(default)]
+    pub ca_cert_path: String,
 }
 
 impl TlsOption {
@@ -76,9 +84,7 @@ impl TlsOption {
         if let TlsMode::Disable = self.mode {
             return Ok(None);
         }
-        let cert = certs(&mut BufReader::new(File::open(&self.cert_path)?))
-            .map_err(|_| Error::new(ErrorKind::InvalidInput, "invalid cert"))
-            .map(|mut certs| certs.drain(..).map(Certificate).collect())?;
+        let cert = load_certs(&self.cert_path)?;
 
         // TODO(SSebo): support more private key types
         let key = pkcs8_private_keys(&mut BufReader::new(File::open(&self.key_path)?))
@@ -86,21 +92,53 @@ impl TlsOption {
             .map(|mut keys| keys.drain(..).map(PrivateKey).next())?
             .ok_or_else(|| Error::new(ErrorKind::InvalidInput, "invalid key"))?;
 
-        // TODO(SSebo): with_client_cert_verifier if TlsMode is Required.
-        let config = ServerConfig::builder()
-            .with_safe_defaults()
-            .with_no_client_auth()
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = if self.ca_cert_path.is_empty() {
+            builder.with_no_client_auth()
+        } else {
+            let mut roots = RootCertStore::empty();
+            for ca_cert in load_certs(&self.ca_cert_path)? {
+                roots
+                    .add(&ca_cert)
+                    .map_err(|err| Error::new(ErrorKind::InvalidInput, err))?;
+            }
+            builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots))
+        };
+        let config = builder
             .with_single_cert(cert, key)
             .map_err(|err| std::io::Error::new(ErrorKind::InvalidInput, err))?;
 
         Ok(Some(config))
     }
 
+    /// Setups the TLS config of gRPC servers.
+    pub fn setup_grpc(&self) -> Result<Option<ServerTlsConfig>, Error> {
+        if let TlsMode::Disable = self.mode {
+            return Ok(None);
+        }
+        let cert = std::fs::read(&self.cert_path)?;
+        let key = std::fs::read(&self.key_path)?;
+        let mut config =
+            ServerTlsConfig::new().identity(tonic::transport::Identity::from_pem(cert, key));
+        if !self.ca_cert_path.is_empty() {
+            let ca_cert = std::fs::read(&self.ca_cert_path)?;
+            config = config.client_ca_root(tonic::transport::Certificate::from_pem(ca_cert));
+        }
+
+        Ok(Some(config))
+    }
+
     pub fn should_force_tls(&self) -> bool {
         !matches!(self.mode, TlsMode::Disable | TlsMode::Prefer)
     }
 }
 
+fn load_certs(path: &str) -> Result<Vec<Certificate>, Error> {
+    certs(&mut BufReader::new(File::open(path)?))
+        .map_err(|_| Error::new(ErrorKind::InvalidInput, "invalid cert"))
+        .map(|mut certs| certs.drain(..).map(Certificate).collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -121,6 +159,7 @@ mod tests {
                 mode: Disable,
                 cert_path: "/path/to/cert_path".to_string(),
                 key_path: "/path/to/key_path".to_string(),
+                ca_cert_path: String::new(),
             },
             TlsOption::new(
                 Some(Disable),
@@ -150,6 +189,27 @@ mod tests {
         assert!(setup.is_ok());
         let setup = setup.unwrap();
         assert!(setup.is_none());
+        assert!(t.setup_grpc().unwrap().is_none());
+    }
+
+    #[test]
+    fn test_tls_option_mutual() {
+        let s = r#"
+        {
+            "mode": "require",
+            "cert_path": "/some_dir/some.crt",
+            "key_path": "/some_dir/some.key",
+            "ca_cert_path": "/some_dir/ca.crt"
+        }
+        "#;
+
+        let t: TlsOption = serde_json::from_str(s).unwrap();
+
+        assert!(t.should_force_tls());
+        assert_eq!("/some_dir/ca.crt", t.ca_cert_path);
+        // The files don't exist.
+        assert!(t.setup().is_err());
+        assert!(t.setup_grpc().is_err());
     }
 
     #[test]
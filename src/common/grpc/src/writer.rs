@@ -42,23 +42,29 @@ impl LinesWriter {
         }
     }
 
+    /// Writes a timestamp, the value keeps the precision it was written with. Timestamps
+    /// in minute or hour are stored in second.
     pub fn write_ts(&mut self, column_name: &str, value: (i64, Precision)) -> Result<()> {
-        let (idx, column) = self.mut_column(
-            column_name,
-            ColumnDataType::Timestamp,
-            SemanticType::Timestamp,
-        );
+        let (ts, precision) = value;
+        let datatype = precision.to_column_datatype();
+        let (idx, column) = self.mut_column(column_name, datatype, SemanticType::Timestamp);
         ensure!(
-            column.datatype == ColumnDataType::Timestamp as i32,
+            column.datatype == datatype as i32,
             TypeMismatchSnafu {
                 column_name,
-                expected: "timestamp",
+                expected: format!("{:?}", datatype),
                 actual: format!("{:?}", column.datatype)
             }
         );
         // It is safe to use unwrap here, because values has been initialized in mut_column()
         let values = column.values.as_mut().unwrap();
-        values.ts_millis_values.push(to_ms_ts(value.1, value.0));
+        let ts = to_precise_ts(precision, ts);
+        match datatype {
+            ColumnDataType::TimestampSecond => values.ts_second_values.push(ts),
+            ColumnDataType::TimestampMicrosecond => values.ts_microsecond_values.push(ts),
+            ColumnDataType::TimestampNanosecond => values.ts_nanosecond_values.push(ts),
+            _ => values.ts_millis_values.push(ts),
+        }
         self.null_masks[idx].push(false);
         Ok(())
     }
@@ -213,6 +219,7 @@ impl LinesWriter {
                     values: Some(Values::with_capacity(datatype, to_insert)),
                     datatype: datatype as i32,
                     null_mask: Vec::default(),
+                    datatype_extension: None,
                 });
                 column_names.insert(column_name.to_string(), new_idx);
                 new_idx
@@ -233,6 +240,15 @@ pub fn to_ms_ts(p: Precision, ts: i64) -> i64 {
     }
 }
 
+/// Converts the timestamp to the unit of [Precision::to_column_datatype].
+pub fn to_precise_ts(p: Precision, ts: i64) -> i64 {
+    match p {
+        Precision::MINUTE => ts * 60,
+        Precision::HOUR => ts * 60 * 60,
+        _ => ts,
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Precision {
     NANOSECOND,
@@ -243,6 +259,20 @@ pub enum Precision {
     HOUR,
 }
 
+impl Precision {
+    /// Returns the timestamp column datatype that keeps values of this precision.
+    pub fn to_column_datatype(&self) -> ColumnDataType {
+        match self {
+            Precision::NANOSECOND => ColumnDataType::TimestampNanosecond,
+            Precision::MICROSECOND => ColumnDataType::TimestampMicrosecond,
+            Precision::MILLISECOND => ColumnDataType::Timestamp,
+            Precision::SECOND | Precision::MINUTE | Precision::HOUR => {
+                ColumnDataType::TimestampSecond
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use api::v1::column::SemanticType;
@@ -250,7 +280,7 @@ mod tests {
     use common_base::BitVec;
 
     use super::LinesWriter;
-    use crate::writer::{to_ms_ts, Precision};
+    use crate::writer::{to_ms_ts, to_precise_ts, Precision};
 
     #[test]
     fn test_lines_writer() {
@@ -379,4 +409,49 @@ mod tests {
             to_ms_ts(Precision::HOUR, 100110000)
         );
     }
+
+    #[test]
+    fn test_write_ts_with_precision() {
+        let mut writer = LinesWriter::with_lines(2);
+        writer
+            .write_ts("ts", (1663840496100023100, Precision::NANOSECOND))
+            .unwrap();
+        writer.commit();
+        writer
+            .write_ts("ts", (1663840496400340001, Precision::NANOSECOND))
+            .unwrap();
+        writer.commit();
+        assert!(writer
+            .write_ts("ts", (1663840496, Precision::SECOND))
+            .is_err());
+
+        let (columns, _) = writer.finish();
+        let column = &columns[0];
+        assert_eq!(ColumnDataType::TimestampNanosecond as i32, column.datatype);
+        assert_eq!(
+            vec![1663840496100023100, 1663840496400340001],
+            column.values.as_ref().unwrap().ts_nanosecond_values
+        );
+
+        let mut writer = LinesWriter::with_lines(1);
+        writer.write_ts("ts", (2, Precision::HOUR)).unwrap();
+        writer.commit();
+        let (columns, _) = writer.finish();
+        assert_eq!(ColumnDataType::TimestampSecond as i32, columns[0].datatype);
+        assert_eq!(
+            vec![7200],
+            columns[0].values.as_ref().unwrap().ts_second_values
+        );
+    }
+
+    #[test]
+    fn test_to_precise_ts() {
+        assert_eq!(100110000, to_precise_ts(Precision::NANOSECOND, 100110000));
+        assert_eq!(100110000, to_precise_ts(Precision::SECOND, 100110000));
+        assert_eq!(100110000 * 60, to_precise_ts(Precision::MINUTE, 100110000));
+        assert_eq!(
+            100110000 * 60 * 60,
+            to_precise_ts(Precision::HOUR, 100110000)
+        );
+    }
 }
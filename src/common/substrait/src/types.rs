@@ -15,8 +15,9 @@
 //! Methods that perform conversion between Substrait's type ([Type](SType)) and GreptimeDB's type ([ConcreteDataType]).
 //!
 //! Substrait use [type variation](https://substrait.io/types/type_variations/) to express different "logical types".
-//! Current we only have variations on integer types. Variation 0 (system preferred) are the same with base types, which
-//! are signed integer (i.e. I8 -> [i8]), and Variation 1 stands for unsigned integer (i.e. I8 -> [u8]).
+//! Current we only have variations on integer and binary types. Variation 0 (system preferred) are the same with base types, which
+//! are signed integer (i.e. I8 -> [i8]), and Variation 1 stands for unsigned integer (i.e. I8 -> [u8]). For binary type,
+//! Variation 1 stands for binary encoded JSON.
 
 use datafusion::scalar::ScalarValue;
 use datatypes::prelude::ConcreteDataType;
@@ -68,12 +69,16 @@ pub fn to_concrete_type(ty: &SType) -> Result<(ConcreteDataType, bool)> {
         Kind::Fp32(desc) => substrait_kind!(desc, float32_datatype),
         Kind::Fp64(desc) => substrait_kind!(desc, float64_datatype),
         Kind::String(desc) => substrait_kind!(desc, string_datatype),
-        Kind::Binary(desc) => substrait_kind!(desc, binary_datatype),
+        Kind::Binary(desc) => substrait_kind!(desc, binary_datatype, json_datatype),
         Kind::Timestamp(desc) => substrait_kind!(
             desc,
             ConcreteDataType::timestamp_datatype(Default::default())
         ),
         Kind::Date(desc) => substrait_kind!(desc, date_datatype),
+        Kind::Decimal(desc) => substrait_kind!(
+            desc,
+            ConcreteDataType::decimal128_datatype(desc.precision as u8, desc.scale as u8)
+        ),
         Kind::Time(_)
         | Kind::IntervalYear(_)
         | Kind::IntervalDay(_)
@@ -82,7 +87,6 @@ pub fn to_concrete_type(ty: &SType) -> Result<(ConcreteDataType, bool)> {
         | Kind::FixedChar(_)
         | Kind::Varchar(_)
         | Kind::FixedBinary(_)
-        | Kind::Decimal(_)
         | Kind::Struct(_)
         | Kind::List(_)
         | Kind::Map(_)
@@ -131,6 +135,17 @@ pub fn from_concrete_type(ty: ConcreteDataType, nullability: Option<bool>) -> Re
         ConcreteDataType::Timestamp(_) => {
             build_substrait_kind!(Timestamp, Timestamp, nullability, 0)
         }
+        ConcreteDataType::Decimal128(t) => Some(Kind::Decimal(s_type::Decimal {
+            scale: t.scale as i32,
+            precision: t.precision as i32,
+            type_variation_reference: 0,
+            nullability: match nullability {
+                Some(true) => Nullability::Nullable,
+                Some(false) => Nullability::Required,
+                None => Nullability::Unspecified,
+            } as _,
+        })),
+        ConcreteDataType::Json(_) => build_substrait_kind!(Binary, Binary, nullability, 1),
         ConcreteDataType::List(_) => UnsupportedConcreteTypeSnafu { ty }.fail()?,
     };
 
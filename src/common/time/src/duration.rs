@@ -0,0 +1,74 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::{ensure, OptionExt};
+
+use crate::error::{ParseDurationSnafu, Result};
+
+/// Parses a duration like `30s`, `5m` or `1 hour` into nanoseconds.
+///
+/// Supported units are `ns`, `us`, `ms`, `s`, `m`, `h`, `d`, `w` and their long names such as
+/// `second` and `minutes`. The duration must be positive.
+pub fn parse_duration_nanos(duration: &str) -> Result<i64> {
+    let invalid_duration = || ParseDurationSnafu { raw: duration };
+
+    let trimmed = duration.trim();
+    let unit_start = trimmed
+        .find(|c: char| !c.is_ascii_digit())
+        .with_context(invalid_duration)?;
+    let (value, unit) = trimmed.split_at(unit_start);
+    let value = value.parse::<i64>().ok().with_context(invalid_duration)?;
+    let unit_nanos: i64 = match unit.trim_start().to_lowercase().as_str() {
+        "ns" | "nanosecond" | "nanoseconds" => 1,
+        "us" | "microsecond" | "microseconds" => 1_000,
+        "ms" | "millisecond" | "milliseconds" => 1_000_000,
+        "s" | "second" | "seconds" => 1_000_000_000,
+        "m" | "minute" | "minutes" => 60 * 1_000_000_000,
+        "h" | "hour" | "hours" => 60 * 60 * 1_000_000_000,
+        "d" | "day" | "days" => 24 * 60 * 60 * 1_000_000_000,
+        "w" | "week" | "weeks" => 7 * 24 * 60 * 60 * 1_000_000_000,
+        _ => return invalid_duration().fail(),
+    };
+    let nanos = value
+        .checked_mul(unit_nanos)
+        .with_context(invalid_duration)?;
+    ensure!(nanos > 0, invalid_duration());
+    Ok(nanos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_duration_nanos() {
+        assert_eq!(10, parse_duration_nanos("10ns").unwrap());
+        assert_eq!(1_000, parse_duration_nanos("1us").unwrap());
+        assert_eq!(100_000_000, parse_duration_nanos("100ms").unwrap());
+        assert_eq!(30_000_000_000, parse_duration_nanos("30s").unwrap());
+        assert_eq!(300_000_000_000, parse_duration_nanos("5m").unwrap());
+        assert_eq!(300_000_000_000, parse_duration_nanos("5 minutes").unwrap());
+        assert_eq!(3_600_000_000_000, parse_duration_nanos(" 1 Hour ").unwrap());
+        assert_eq!(86_400_000_000_000, parse_duration_nanos("1d").unwrap());
+        assert_eq!(604_800_000_000_000, parse_duration_nanos("1w").unwrap());
+
+        for duration in ["", "s", "10", "10x", "0s", "-1s", "1.5s", "999999999999w"] {
+            assert!(
+                parse_duration_nanos(duration).is_err(),
+                "duration: {}",
+                duration
+            );
+        }
+    }
+}
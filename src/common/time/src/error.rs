@@ -22,6 +22,10 @@ pub enum Error {
     ParseDateStr { raw: String, source: ParseError },
     #[snafu(display("Failed to parse a string into Timestamp, raw string: {}", raw))]
     ParseTimestamp { raw: String, backtrace: Backtrace },
+    #[snafu(display("Failed to parse a string into time zone, raw string: {}", raw))]
+    ParseTimeZone { raw: String, backtrace: Backtrace },
+    #[snafu(display("Failed to parse a string into duration, raw string: {}", raw))]
+    ParseDuration { raw: String, backtrace: Backtrace },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -14,10 +14,12 @@
 
 pub mod date;
 pub mod datetime;
+pub mod duration;
 pub mod error;
 pub mod range;
 pub mod timestamp;
 pub mod timestamp_millis;
+pub mod timezone;
 pub mod util;
 
 pub use date::Date;
@@ -25,3 +27,4 @@ pub use datetime::DateTime;
 pub use range::RangeMillis;
 pub use timestamp::Timestamp;
 pub use timestamp_millis::TimestampMillis;
+pub use timezone::TimeZone;
@@ -18,10 +18,11 @@ use std::hash::{Hash, Hasher};
 use std::str::FromStr;
 
 use chrono::offset::Local;
-use chrono::{DateTime, LocalResult, NaiveDateTime, TimeZone, Utc};
+use chrono::{DateTime, LocalResult, NaiveDateTime, TimeZone as _, Utc};
 use serde::{Deserialize, Serialize};
 
 use crate::error::{Error, ParseTimestampSnafu};
+use crate::timezone::TimeZone;
 
 #[derive(Debug, Clone, Default, Copy, Serialize, Deserialize)]
 pub struct Timestamp {
@@ -49,12 +50,46 @@ impl Timestamp {
         self.value
     }
 
+    /// Converts the timestamp to a value in `unit`.
+    ///
+    /// Conversion to a coarser unit rounds towards negative infinity, so a timestamp before
+    /// Unix epoch never moves forward in time, e.g. -1ns is converted to -1ms.
     pub fn convert_to(&self, unit: TimeUnit) -> i64 {
-        // TODO(hl): May result into overflow
-        self.value * self.unit.factor() / unit.factor()
+        if self.unit.factor() >= unit.factor() {
+            // TODO(hl): May result into overflow
+            self.value * (self.unit.factor() / unit.factor())
+        } else {
+            self.value.div_euclid(unit.factor() / self.unit.factor())
+        }
+    }
+
+    /// Returns the timestamp in nanoseconds, widened to avoid overflow when comparing
+    /// timestamps of different units.
+    fn as_nanos(&self) -> i128 {
+        self.value as i128 * self.unit.factor() as i128
     }
 
     pub fn to_iso8601_string(&self) -> String {
+        self.to_timezone_aware_string(None)
+    }
+
+    /// Formats the timestamp like [Timestamp::to_iso8601_string], but in the given
+    /// time zone instead of UTC.
+    pub fn to_timezone_aware_string(&self, tz: Option<&TimeZone>) -> String {
+        self.as_formatted_string("%Y-%m-%d %H:%M:%S%.f%z", tz)
+    }
+
+    /// Formats the timestamp with a chrono format `pattern` in the given time zone,
+    /// or in UTC if `tz` is `None`.
+    pub fn as_formatted_string(&self, pattern: &str, tz: Option<&TimeZone>) -> String {
+        let datetime = self.to_chrono_datetime();
+        match tz {
+            Some(tz) => tz.format(&datetime, pattern),
+            None => format!("{}", datetime.format(pattern)),
+        }
+    }
+
+    fn to_chrono_datetime(&self) -> DateTime<Utc> {
         let nano_factor = TimeUnit::Second.factor() / TimeUnit::Nanosecond.factor();
 
         let mut secs = self.convert_to(TimeUnit::Second);
@@ -65,26 +100,12 @@ impl Timestamp {
             nsecs += nano_factor;
         }
 
-        let datetime = Utc.timestamp(secs, nsecs as u32);
-        format!("{}", datetime.format("%Y-%m-%d %H:%M:%S%.f%z"))
+        Utc.timestamp(secs, nsecs as u32)
     }
-}
-
-impl FromStr for Timestamp {
-    type Err = Error;
 
-    /// Accepts a string in RFC3339 / ISO8601 standard format and some variants and converts it to a nanosecond precision timestamp.
-    /// This code is copied from [arrow-datafusion](https://github.com/apache/arrow-datafusion/blob/arrow2/datafusion-physical-expr/src/arrow_temporal_util.rs#L71)
-    /// with some bugfixes.
-    /// Supported format:
-    /// - `2022-09-20T14:16:43.012345Z` (Zulu timezone)
-    /// - `2022-09-20T14:16:43.012345+08:00` (Explicit offset)
-    /// - `2022-09-20T14:16:43.012345` (local timezone, with T)
-    /// - `2022-09-20T14:16:43` (local timezone, no fractional seconds, with T)
-    /// - `2022-09-20 14:16:43.012345Z` (Zulu timezone, without T)
-    /// - `2022-09-20 14:16:43` (local timezone, without T)
-    /// - `2022-09-20 14:16:43.012345` (local timezone, without T)
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
+    /// Same as [Timestamp::from_str], except that strings without an explicit offset
+    /// are regarded as wall clock times in `tz` rather than in the local time zone.
+    pub fn from_str_with_time_zone(s: &str, tz: Option<&TimeZone>) -> Result<Self, Error> {
         // RFC3339 timestamp (with a T)
         if let Ok(ts) = DateTime::parse_from_rfc3339(s) {
             return Ok(Timestamp::new(ts.timestamp_nanos(), TimeUnit::Nanosecond));
@@ -97,44 +118,64 @@ impl FromStr for Timestamp {
         }
 
         if let Ok(ts) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S") {
-            return naive_datetime_to_timestamp(s, ts);
+            return naive_datetime_to_timestamp(s, ts, tz);
         }
 
         if let Ok(ts) = NaiveDateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f") {
-            return naive_datetime_to_timestamp(s, ts);
+            return naive_datetime_to_timestamp(s, ts, tz);
         }
 
         if let Ok(ts) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S") {
-            return naive_datetime_to_timestamp(s, ts);
+            return naive_datetime_to_timestamp(s, ts, tz);
         }
 
         if let Ok(ts) = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f") {
-            return naive_datetime_to_timestamp(s, ts);
+            return naive_datetime_to_timestamp(s, ts, tz);
         }
 
         ParseTimestampSnafu { raw: s }.fail()
     }
 }
 
-/// Converts the naive datetime (which has no specific timezone) to a
-/// nanosecond epoch timestamp relative to UTC.
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    /// Accepts a string in RFC3339 / ISO8601 standard format and some variants and converts it to a nanosecond precision timestamp.
+    /// This code is copied from [arrow-datafusion](https://github.com/apache/arrow-datafusion/blob/arrow2/datafusion-physical-expr/src/arrow_temporal_util.rs#L71)
+    /// with some bugfixes.
+    /// Supported format:
+    /// - `2022-09-20T14:16:43.012345Z` (Zulu timezone)
+    /// - `2022-09-20T14:16:43.012345+08:00` (Explicit offset)
+    /// - `2022-09-20T14:16:43.012345` (local timezone, with T)
+    /// - `2022-09-20T14:16:43` (local timezone, no fractional seconds, with T)
+    /// - `2022-09-20 14:16:43.012345Z` (Zulu timezone, without T)
+    /// - `2022-09-20 14:16:43` (local timezone, without T)
+    /// - `2022-09-20 14:16:43.012345` (local timezone, without T)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Timestamp::from_str_with_time_zone(s, None)
+    }
+}
+
+/// Converts the naive datetime (which has no specific timezone) in `tz`, or in the
+/// local timezone if `tz` is `None`, to a nanosecond epoch timestamp relative to UTC.
 /// This code is copied from [arrow-datafusion](https://github.com/apache/arrow-datafusion/blob/arrow2/datafusion-physical-expr/src/arrow_temporal_util.rs#L137).
 fn naive_datetime_to_timestamp(
     s: &str,
     datetime: NaiveDateTime,
+    tz: Option<&TimeZone>,
 ) -> crate::error::Result<Timestamp> {
-    let l = Local {};
-
-    match l.from_local_datetime(&datetime) {
+    let utc_datetime = match tz {
+        Some(tz) => tz.from_local_datetime(&datetime),
+        None => Local {}
+            .from_local_datetime(&datetime)
+            .map(|d| d.with_timezone(&Utc)),
+    };
+
+    match utc_datetime {
         LocalResult::None => ParseTimestampSnafu { raw: s }.fail(),
-        LocalResult::Single(local_datetime) => Ok(Timestamp::new(
-            local_datetime.with_timezone(&Utc).timestamp_nanos(),
-            TimeUnit::Nanosecond,
-        )),
-        LocalResult::Ambiguous(local_datetime, _) => Ok(Timestamp::new(
-            local_datetime.with_timezone(&Utc).timestamp_nanos(),
-            TimeUnit::Nanosecond,
-        )),
+        LocalResult::Single(utc_datetime) | LocalResult::Ambiguous(utc_datetime, _) => Ok(
+            Timestamp::new(utc_datetime.timestamp_nanos(), TimeUnit::Nanosecond),
+        ),
     }
 }
 
@@ -181,19 +222,19 @@ impl TimeUnit {
 
 impl PartialOrd for Timestamp {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        (self.value * self.unit.factor()).partial_cmp(&(other.value * other.unit.factor()))
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Timestamp {
     fn cmp(&self, other: &Self) -> Ordering {
-        (self.value * self.unit.factor()).cmp(&(other.value * other.unit.factor()))
+        self.as_nanos().cmp(&other.as_nanos())
     }
 }
 
 impl PartialEq for Timestamp {
     fn eq(&self, other: &Self) -> bool {
-        self.convert_to(TimeUnit::Nanosecond) == other.convert_to(TimeUnit::Nanosecond)
+        self.as_nanos() == other.as_nanos()
     }
 }
 
@@ -201,14 +242,14 @@ impl Eq for Timestamp {}
 
 impl Hash for Timestamp {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        state.write_i64(self.convert_to(TimeUnit::Nanosecond));
+        state.write_i128(self.as_nanos());
         state.finish();
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use chrono::Offset;
+    use chrono::{Offset, TimeZone as _};
     use serde_json::Value;
 
     use super::*;
@@ -238,6 +279,34 @@ mod tests {
         assert!(t > Timestamp::new(999, TimeUnit::Microsecond));
     }
 
+    #[test]
+    pub fn test_convert_to() {
+        let t = Timestamp::new(1_500_000, TimeUnit::Nanosecond);
+        assert_eq!(1, t.convert_to(TimeUnit::Millisecond));
+        assert_eq!(1500, t.convert_to(TimeUnit::Microsecond));
+        assert_eq!(0, t.convert_to(TimeUnit::Second));
+
+        let t = Timestamp::new(-1, TimeUnit::Nanosecond);
+        assert_eq!(-1, t.convert_to(TimeUnit::Millisecond));
+        assert_eq!(-1, t.convert_to(TimeUnit::Second));
+
+        let t = Timestamp::new(-1, TimeUnit::Second);
+        assert_eq!(-1_000_000_000, t.convert_to(TimeUnit::Nanosecond));
+        assert_eq!(-1000, t.convert_to(TimeUnit::Millisecond));
+    }
+
+    #[test]
+    pub fn test_cmp_different_units() {
+        // Comparing in nanoseconds doesn't overflow.
+        let large = Timestamp::new(i64::MAX / 1000, TimeUnit::Second);
+        assert!(large > Timestamp::new(i64::MAX, TimeUnit::Nanosecond));
+        assert_eq!(
+            Timestamp::new(1, TimeUnit::Second),
+            Timestamp::new(1_000_000_000, TimeUnit::Nanosecond)
+        );
+        assert!(Timestamp::new(-1, TimeUnit::Second) < Timestamp::new(-1, TimeUnit::Millisecond));
+    }
+
     #[test]
     pub fn test_from_i64() {
         let t: Timestamp = 42.into();
@@ -332,6 +401,47 @@ mod tests {
         assert_eq!("1969-12-31 23:59:58.999+0000", ts.to_iso8601_string());
     }
 
+    #[test]
+    fn test_from_str_with_time_zone() {
+        let tz = TimeZone::from_tz_string("+08:00").unwrap();
+        let ts = Timestamp::from_str_with_time_zone("2020-09-08 13:42:29", tz.as_ref()).unwrap();
+        assert_eq!("2020-09-08 05:42:29+0000", ts.to_iso8601_string());
+
+        let tz = TimeZone::from_tz_string("America/New_York").unwrap();
+        let ts =
+            Timestamp::from_str_with_time_zone("2020-09-08T13:42:29.042", tz.as_ref()).unwrap();
+        assert_eq!("2020-09-08 17:42:29.042+0000", ts.to_iso8601_string());
+
+        // Explicit offset wins over the time zone.
+        let ts = Timestamp::from_str_with_time_zone("2020-09-08 13:42:29Z", tz.as_ref()).unwrap();
+        assert_eq!("2020-09-08 13:42:29+0000", ts.to_iso8601_string());
+    }
+
+    #[test]
+    fn test_to_timezone_aware_string() {
+        let ts = Timestamp::from_millis(1668070237000);
+        assert_eq!(
+            "2022-11-10 08:50:37+0000",
+            ts.to_timezone_aware_string(None)
+        );
+
+        let tz = TimeZone::from_tz_string("+08:00").unwrap();
+        assert_eq!(
+            "2022-11-10 16:50:37+0800",
+            ts.to_timezone_aware_string(tz.as_ref())
+        );
+        assert_eq!(
+            "2022-11-10 16:50:37",
+            ts.as_formatted_string("%Y-%m-%d %H:%M:%S", tz.as_ref())
+        );
+
+        let tz = TimeZone::from_tz_string("Asia/Kolkata").unwrap();
+        assert_eq!(
+            "2022-11-10 14:20:37+0530",
+            ts.to_timezone_aware_string(tz.as_ref())
+        );
+    }
+
     #[test]
     fn test_serialize_to_json_value() {
         assert_eq!(
@@ -0,0 +1,156 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use chrono::{DateTime, FixedOffset, LocalResult, NaiveDateTime, TimeZone as _, Utc};
+use chrono_tz::Tz;
+
+use crate::error::{ParseTimeZoneSnafu, Result};
+
+/// Time zone of a session, either a fixed offset from UTC (`+08:00`) or a named
+/// time zone from the IANA database (`Asia/Shanghai`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZone {
+    Offset(FixedOffset),
+    Named(Tz),
+}
+
+impl TimeZone {
+    /// Parses a time zone string like `UTC`, `+08:00` or `America/New_York`.
+    /// Returns `None` for `SYSTEM`, which stands for the local time zone of the server.
+    pub fn from_tz_string(s: &str) -> Result<Option<Self>> {
+        let s = s.trim();
+        if s.eq_ignore_ascii_case("SYSTEM") {
+            return Ok(None);
+        }
+        if s.eq_ignore_ascii_case("UTC") || s == "Z" {
+            return Ok(Some(TimeZone::Named(Tz::UTC)));
+        }
+        if let Some(offset) = parse_offset(s) {
+            return Ok(Some(TimeZone::Offset(offset)));
+        }
+        Tz::from_str(s)
+            .map(|tz| Some(TimeZone::Named(tz)))
+            .map_err(|_| ParseTimeZoneSnafu { raw: s }.build())
+    }
+
+    /// Interprets `datetime` as a wall clock time in this time zone.
+    pub(crate) fn from_local_datetime(
+        &self,
+        datetime: &NaiveDateTime,
+    ) -> LocalResult<DateTime<Utc>> {
+        match self {
+            TimeZone::Offset(offset) => offset
+                .from_local_datetime(datetime)
+                .map(|d| d.with_timezone(&Utc)),
+            TimeZone::Named(tz) => tz
+                .from_local_datetime(datetime)
+                .map(|d| d.with_timezone(&Utc)),
+        }
+    }
+
+    /// Formats `datetime` as a wall clock time in this time zone.
+    pub(crate) fn format(&self, datetime: &DateTime<Utc>, pattern: &str) -> String {
+        match self {
+            TimeZone::Offset(offset) => datetime.with_timezone(offset).format(pattern).to_string(),
+            TimeZone::Named(tz) => datetime.with_timezone(tz).format(pattern).to_string(),
+        }
+    }
+}
+
+impl Display for TimeZone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TimeZone::Offset(offset) => write!(f, "{}", offset),
+            TimeZone::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+/// Parses offsets in `[+-]HH:MM` format. MySQL accepts offsets from `-13:59` to `+14:00`.
+fn parse_offset(s: &str) -> Option<FixedOffset> {
+    let sign = match s.as_bytes().first()? {
+        b'+' => 1,
+        b'-' => -1,
+        _ => return None,
+    };
+    let (hours, minutes) = s[1..].split_once(':')?;
+    if hours.is_empty()
+        || minutes.len() != 2
+        || !hours
+            .chars()
+            .chain(minutes.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if minutes >= 60 || hours * 60 + minutes > 14 * 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_tz_string() {
+        assert_eq!(None, TimeZone::from_tz_string("SYSTEM").unwrap());
+        assert_eq!(None, TimeZone::from_tz_string("system").unwrap());
+        assert_eq!(
+            Some(TimeZone::Named(Tz::UTC)),
+            TimeZone::from_tz_string("UTC").unwrap()
+        );
+        assert_eq!(
+            Some(TimeZone::Offset(FixedOffset::east(8 * 3600))),
+            TimeZone::from_tz_string("+08:00").unwrap()
+        );
+        assert_eq!(
+            Some(TimeZone::Offset(FixedOffset::west(5 * 3600 + 30 * 60))),
+            TimeZone::from_tz_string("-05:30").unwrap()
+        );
+        assert_eq!(
+            Some(TimeZone::Named(Tz::Asia__Shanghai)),
+            TimeZone::from_tz_string("Asia/Shanghai").unwrap()
+        );
+
+        assert!(TimeZone::from_tz_string("+15:00").is_err());
+        assert!(TimeZone::from_tz_string("+08:60").is_err());
+        assert!(TimeZone::from_tz_string("+0800").is_err());
+        assert!(TimeZone::from_tz_string("Mars/Olympus_Mons").is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(
+            "+08:00",
+            TimeZone::from_tz_string("+08:00")
+                .unwrap()
+                .unwrap()
+                .to_string()
+        );
+        assert_eq!(
+            "Asia/Shanghai",
+            TimeZone::from_tz_string("Asia/Shanghai")
+                .unwrap()
+                .unwrap()
+                .to_string()
+        );
+    }
+}
@@ -13,11 +13,14 @@
 // limitations under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
+use common_grpc::tls::TlsOption;
 use common_telemetry::info;
 use meta_client::MetaClientOpts;
 use serde::{Deserialize, Serialize};
 use servers::Mode;
+use storage::config::EngineConfig as StorageEngineConfig;
 
 use crate::error::Result;
 use crate::instance::{Instance, InstanceRef};
@@ -45,16 +48,57 @@ impl Default for ObjectStoreConfig {
     }
 }
 
+/// Memtable memory limits shared by all regions of the storage engine.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WriteBufferOptions {
+    /// Flushes the region with the largest mutable memtable if mutable memtables of all regions
+    /// use more bytes than this.
+    pub global_write_buffer_size: usize,
+    /// Stalls writes if memtables of all regions use more bytes than this.
+    pub global_write_buffer_reject_size: usize,
+    /// Rejects a stalled write if memory isn't released in this duration.
+    #[serde(with = "humantime_serde")]
+    pub max_write_stall_duration: Duration,
+}
+
+impl Default for WriteBufferOptions {
+    fn default() -> Self {
+        let config = StorageEngineConfig::default();
+        Self {
+            global_write_buffer_size: config.global_write_buffer_size,
+            global_write_buffer_reject_size: config.global_write_buffer_reject_size,
+            max_write_stall_duration: config.max_write_stall_duration,
+        }
+    }
+}
+
+impl From<&WriteBufferOptions> for StorageEngineConfig {
+    fn from(opts: &WriteBufferOptions) -> Self {
+        StorageEngineConfig {
+            global_write_buffer_size: opts.global_write_buffer_size,
+            global_write_buffer_reject_size: opts.global_write_buffer_reject_size,
+            max_write_stall_duration: opts.max_write_stall_duration,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DatanodeOptions {
     pub node_id: Option<u64>,
     pub rpc_addr: String,
     pub rpc_runtime_size: usize,
+    #[serde(default = "Default::default")]
+    pub rpc_tls: Arc<TlsOption>,
     pub mysql_addr: String,
     pub mysql_runtime_size: usize,
+    #[serde(default = "Default::default")]
+    pub mysql_tls: Arc<TlsOption>,
     pub meta_client_opts: Option<MetaClientOpts>,
     pub wal_dir: String,
     pub storage: ObjectStoreConfig,
+    #[serde(default)]
+    pub write_buffer: WriteBufferOptions,
     pub enable_memory_catalog: bool,
     pub mode: Mode,
 }
@@ -65,11 +109,14 @@ impl Default for DatanodeOptions {
             node_id: None,
             rpc_addr: "127.0.0.1:3001".to_string(),
             rpc_runtime_size: 8,
+            rpc_tls: Arc::new(TlsOption::default()),
             mysql_addr: "127.0.0.1:4406".to_string(),
             mysql_runtime_size: 2,
+            mysql_tls: Arc::new(TlsOption::default()),
             meta_client_opts: None,
             wal_dir: "/tmp/greptimedb/wal".to_string(),
             storage: ObjectStoreConfig::default(),
+            write_buffer: WriteBufferOptions::default(),
             enable_memory_catalog: false,
             mode: Mode::Standalone,
         }
@@ -75,6 +75,13 @@ pub enum Error {
         source: TableError,
     },
 
+    #[snafu(display("Failed to rename table {} in catalog, source: {}", table_name, source))]
+    RenameTable {
+        table_name: String,
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
     #[snafu(display("Failed to drop table {}, source: {}", table_name, source))]
     DropTable {
         table_name: String,
@@ -196,6 +203,41 @@ pub enum Error {
         source: catalog::error::Error,
     },
 
+    #[snafu(display("Failed to create catalog {}, source: {}", catalog, source))]
+    CreateCatalog {
+        catalog: String,
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
+    #[snafu(display("Failed to drop catalog {}, source: {}", catalog, source))]
+    DropCatalog {
+        catalog: String,
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
+    #[snafu(display("Access denied to catalog: {}", catalog))]
+    AccessDenied {
+        catalog: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to manage users and roles, source: {}", source))]
+    ManagePrivilege {
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
+    #[snafu(display("Users and roles are not supported in current mode"))]
+    PrivilegeNotSupported { backtrace: Backtrace },
+
+    #[snafu(display("{} is only supported by servers that track processes", statement))]
+    ProcessNotSupported {
+        statement: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Failed to register a new schema, source: {}", source))]
     RegisterSchema {
         #[snafu(backtrace)]
@@ -243,6 +285,14 @@ pub enum Error {
         source: common_time::error::Error,
     },
 
+    #[snafu(display("Failed to set variable {} to {}, source: {}", name, value, source))]
+    SetVariable {
+        name: String,
+        value: String,
+        #[snafu(backtrace)]
+        source: common_time::error::Error,
+    },
+
     #[snafu(display("Failed to access catalog, source: {}", source))]
     Catalog {
         #[snafu(backtrace)]
@@ -308,6 +358,7 @@ impl ErrorExt for Error {
             | Error::GetTable { source, .. }
             | Error::AlterTable { source, .. } => source.status_code(),
             Error::DropTable { source, .. } => source.status_code(),
+            Error::RenameTable { source, .. } => source.status_code(),
 
             Error::Insert { source, .. } => source.status_code(),
 
@@ -332,7 +383,8 @@ impl ErrorExt for Error {
             | Error::CatalogNotFound { .. }
             | Error::SchemaNotFound { .. }
             | Error::ConstraintNotSupported { .. }
-            | Error::ParseTimestamp { .. } => StatusCode::InvalidArguments,
+            | Error::ParseTimestamp { .. }
+            | Error::SetVariable { .. } => StatusCode::InvalidArguments,
 
             // TODO(yingwen): Further categorize http error.
             Error::StartServer { .. }
@@ -346,6 +398,17 @@ impl ErrorExt for Error {
             | Error::UnsupportedExpr { .. }
             | Error::Catalog { .. } => StatusCode::Internal,
 
+            Error::CreateCatalog { source, .. } | Error::DropCatalog { source, .. } => {
+                source.status_code()
+            }
+
+            Error::AccessDenied { .. } => StatusCode::AccessDenied,
+
+            Error::ManagePrivilege { source } => source.status_code(),
+            Error::PrivilegeNotSupported { .. } | Error::ProcessNotSupported { .. } => {
+                StatusCode::Unsupported
+            }
+
             Error::InitBackend { .. } => StatusCode::StorageUnavailable,
             Error::OpenLogStore { source } => source.status_code(),
             Error::StartScriptManager { source } => source.status_code(),
@@ -16,19 +16,22 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
-use api::v1::meta::{HeartbeatRequest, HeartbeatResponse, Peer};
+use api::v1::meta::{HeartbeatRequest, HeartbeatResponse, NodeStat, Peer, RegionStat, TableName};
+use catalog::tables::list_tables;
+use catalog::CatalogManagerRef;
 use common_telemetry::{error, info, warn};
 use meta_client::client::{HeartbeatSender, MetaClient};
 use snafu::ResultExt;
 
 use crate::error::{MetaClientInitSnafu, Result};
 
-#[derive(Debug, Clone, Default)]
+#[derive(Clone)]
 pub struct HeartbeatTask {
     node_id: u64,
     server_addr: String,
     running: Arc<AtomicBool>,
     meta_client: Arc<MetaClient>,
+    catalog_manager: CatalogManagerRef,
     interval: u64,
 }
 
@@ -40,12 +43,18 @@ impl Drop for HeartbeatTask {
 
 impl HeartbeatTask {
     /// Create a new heartbeat task instance.
-    pub fn new(node_id: u64, server_addr: String, meta_client: Arc<MetaClient>) -> Self {
+    pub fn new(
+        node_id: u64,
+        server_addr: String,
+        meta_client: Arc<MetaClient>,
+        catalog_manager: CatalogManagerRef,
+    ) -> Self {
         Self {
             node_id,
             server_addr,
             running: Arc::new(AtomicBool::new(false)),
             meta_client,
+            catalog_manager,
             interval: 5_000, // default interval is set to 5 secs
         }
     }
@@ -77,6 +86,50 @@ impl HeartbeatTask {
         info!("heartbeat response: {:?}", resp);
     }
 
+    /// Collects the stats of this node and all regions in it.
+    fn collect_stats(catalog_manager: &CatalogManagerRef) -> (NodeStat, Vec<RegionStat>) {
+        let tables = match list_tables(catalog_manager) {
+            Ok(tables) => tables,
+            Err(e) => {
+                error!(e; "Failed to list tables for heartbeat");
+                return (NodeStat::default(), vec![]);
+            }
+        };
+
+        let table_num = tables.len() as u64;
+        let mut region_stats = Vec::new();
+        for (catalog_name, schema_name, table_name, table) in tables {
+            let stats = match table.region_stats() {
+                Ok(stats) => stats,
+                Err(e) => {
+                    warn!(
+                        "Failed to get region stats of table {}: {:?}",
+                        table_name, e
+                    );
+                    continue;
+                }
+            };
+            region_stats.extend(stats.into_iter().map(|stat| RegionStat {
+                region_id: stat.region_id,
+                table_name: Some(TableName {
+                    catalog_name: catalog_name.clone(),
+                    schema_name: schema_name.clone(),
+                    table_name: table_name.clone(),
+                }),
+                approximate_size: stat.approximate_bytes,
+                approximate_rows: stat.approximate_rows,
+                ..Default::default()
+            }));
+        }
+
+        let node_stat = NodeStat {
+            table_num,
+            region_num: region_stats.len() as u64,
+            ..Default::default()
+        };
+        (node_stat, region_stats)
+    }
+
     /// Start heartbeat task, spawn background task.
     pub async fn start(&self) -> Result<()> {
         let running = self.running.clone();
@@ -91,15 +144,19 @@ impl HeartbeatTask {
         let node_id = self.node_id;
         let server_addr = self.server_addr.clone();
         let meta_client = self.meta_client.clone();
+        let catalog_manager = self.catalog_manager.clone();
 
         let mut tx = Self::create_streams(&meta_client, running.clone()).await?;
         common_runtime::spawn_bg(async move {
             while running.load(Ordering::Acquire) {
+                let (node_stat, region_stats) = Self::collect_stats(&catalog_manager);
                 let req = HeartbeatRequest {
                     peer: Some(Peer {
                         id: node_id,
                         addr: server_addr.clone(),
                     }),
+                    node_stat: Some(node_stat),
+                    region_stats,
                     ..Default::default()
                 };
                 if let Err(e) = tx.send(req).await {
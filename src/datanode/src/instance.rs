@@ -17,6 +17,7 @@ use std::time::Duration;
 use std::{fs, path};
 
 use backon::ExponentialBackoff;
+use catalog::privilege::{PrivilegeManager, PrivilegeManagerRef};
 use catalog::remote::MetaKvBackend;
 use catalog::CatalogManagerRef;
 use common_grpc::channel_manager::{ChannelConfig, ChannelManager};
@@ -61,6 +62,8 @@ pub struct Instance {
     pub(crate) script_executor: ScriptExecutor,
     pub(crate) table_id_provider: Option<TableIdProviderRef>,
     pub(crate) heartbeat_task: Option<HeartbeatTask>,
+    /// Manager of users and roles, only present in standalone mode.
+    pub(crate) privilege_manager: Option<PrivilegeManagerRef>,
 }
 
 pub type InstanceRef = Arc<Instance>;
@@ -87,7 +90,7 @@ impl Instance {
         let table_engine = Arc::new(DefaultEngine::new(
             TableEngineConfig::default(),
             EngineImpl::new(
-                StorageEngineConfig::default(),
+                StorageEngineConfig::from(&opts.write_buffer),
                 Arc::new(log_store),
                 object_store.clone(),
             ),
@@ -95,49 +98,69 @@ impl Instance {
         ));
 
         // create remote catalog manager
-        let (catalog_manager, factory, table_id_provider) = match opts.mode {
+        let (catalog_manager, factory, table_id_provider, privilege_manager) = match opts.mode {
             Mode::Standalone => {
                 if opts.enable_memory_catalog {
                     let catalog = Arc::new(catalog::local::MemoryCatalogManager::default());
-                    let factory = QueryEngineFactory::new(catalog.clone());
+                    let privilege_manager = Arc::new(PrivilegeManager::default());
+                    let factory = QueryEngineFactory::with_privilege_manager(
+                        catalog.clone(),
+                        Some(privilege_manager.clone()),
+                    );
 
                     (
                         catalog.clone() as CatalogManagerRef,
                         factory,
                         Some(catalog as TableIdProviderRef),
+                        Some(privilege_manager),
                     )
                 } else {
                     let catalog = Arc::new(
-                        catalog::local::LocalCatalogManager::try_new(table_engine.clone())
-                            .await
-                            .context(CatalogSnafu)?,
+                        catalog::local::LocalCatalogManager::try_new_with_node_id(
+                            table_engine.clone(),
+                            opts.node_id,
+                        )
+                        .await
+                        .context(CatalogSnafu)?,
+                    );
+                    let privilege_manager = catalog.privilege_manager();
+                    let factory = QueryEngineFactory::with_privilege_manager(
+                        catalog.clone(),
+                        Some(privilege_manager.clone()),
                     );
-                    let factory = QueryEngineFactory::new(catalog.clone());
 
                     (
                         catalog.clone() as CatalogManagerRef,
                         factory,
                         Some(catalog as TableIdProviderRef),
+                        Some(privilege_manager),
                     )
                 }
             }
 
             Mode::Distributed => {
+                let meta_client = meta_client.as_ref().unwrap().clone();
                 let catalog = Arc::new(catalog::remote::RemoteCatalogManager::new(
                     table_engine.clone(),
                     opts.node_id.context(MissingNodeIdSnafu)?,
                     Arc::new(MetaKvBackend {
-                        client: meta_client.as_ref().unwrap().clone(),
+                        client: meta_client.clone(),
                     }),
                 ));
+                catalog.start_watching(meta_client);
                 let factory = QueryEngineFactory::new(catalog.clone());
-                (catalog as CatalogManagerRef, factory, None)
+                (catalog as CatalogManagerRef, factory, None, None)
             }
         };
 
         let query_engine = factory.query_engine();
-        let script_executor =
-            ScriptExecutor::new(catalog_manager.clone(), query_engine.clone()).await?;
+        // Node id is absent in standalone mode, where all schedules are owned by the only node.
+        let script_executor = ScriptExecutor::new(
+            catalog_manager.clone(),
+            query_engine.clone(),
+            opts.node_id.unwrap_or_default(),
+        )
+        .await?;
 
         let heartbeat_task = match opts.mode {
             Mode::Standalone => None,
@@ -145,6 +168,7 @@ impl Instance {
                 opts.node_id.context(MissingNodeIdSnafu)?,
                 opts.rpc_addr.clone(),
                 meta_client.as_ref().unwrap().clone(),
+                catalog_manager.clone(),
             )),
         };
         Ok(Self {
@@ -158,6 +182,7 @@ impl Instance {
             script_executor,
             heartbeat_task,
             table_id_provider,
+            privilege_manager,
         })
     }
 
@@ -166,6 +191,7 @@ impl Instance {
             .start()
             .await
             .context(NewCatalogSnafu)?;
+        self.script_executor.start().await?;
         if let Some(task) = &self.heartbeat_task {
             task.start().await?;
         }
@@ -179,6 +205,10 @@ impl Instance {
     pub fn catalog_manager(&self) -> &CatalogManagerRef {
         &self.catalog_manager
     }
+
+    pub fn privilege_manager(&self) -> Option<PrivilegeManagerRef> {
+        self.privilege_manager.clone()
+    }
 }
 
 pub(crate) async fn new_object_store(store_config: &ObjectStoreConfig) -> Result<ObjectStore> {
@@ -250,7 +280,8 @@ async fn new_metasrv_client(node_id: u64, meta_config: &MetaClientOpts) -> Resul
     let config = ChannelConfig::new()
         .timeout(Duration::from_millis(meta_config.timeout_millis))
         .connect_timeout(Duration::from_millis(meta_config.connect_timeout_millis))
-        .tcp_nodelay(meta_config.tcp_nodelay);
+        .tcp_nodelay(meta_config.tcp_nodelay)
+        .client_tls_config(meta_config.tls.clone());
     let channel_manager = ChannelManager::with_config(config);
     let mut meta_client = MetaClientBuilder::new(cluster_id, member_id)
         .enable_heartbeat()
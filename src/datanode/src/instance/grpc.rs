@@ -12,8 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
-
 use api::result::{build_err_result, AdminResultBuilder, ObjectResultBuilder};
 use api::v1::{
     admin_expr, object_expr, select_expr, AdminExpr, AdminResult, Column, CreateDatabaseExpr,
@@ -28,7 +26,7 @@ use common_grpc_expr::insertion_expr_to_request;
 use common_query::Output;
 use query::plan::LogicalPlan;
 use servers::query_handler::{GrpcAdminHandler, GrpcQueryHandler};
-use session::context::QueryContext;
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 use substrait::{DFLogicalSubstraitConvertor, SubstraitPlan};
 use table::requests::CreateDatabaseRequest;
@@ -104,17 +102,23 @@ impl Instance {
         }
     }
 
-    async fn handle_select(&self, select_expr: SelectExpr) -> ObjectResult {
-        let result = self.do_handle_select(select_expr).await;
+    async fn handle_select(
+        &self,
+        select_expr: SelectExpr,
+        query_ctx: QueryContextRef,
+    ) -> ObjectResult {
+        let result = self.do_handle_select(select_expr, query_ctx).await;
         to_object_result(result).await
     }
 
-    async fn do_handle_select(&self, select_expr: SelectExpr) -> Result<Output> {
+    async fn do_handle_select(
+        &self,
+        select_expr: SelectExpr,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
         let expr = select_expr.expr;
         match expr {
-            Some(select_expr::Expr::Sql(sql)) => {
-                self.execute_sql(&sql, Arc::new(QueryContext::new())).await
-            }
+            Some(select_expr::Expr::Sql(sql)) => self.execute_sql(&sql, query_ctx).await,
             Some(select_expr::Expr::LogicalPlan(plan)) => self.execute_logical(plan).await,
             _ => UnsupportedExprSnafu {
                 name: format!("{:?}", expr),
@@ -128,6 +132,7 @@ impl Instance {
         create_database_expr: CreateDatabaseExpr,
     ) -> AdminResult {
         let req = CreateDatabaseRequest {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
             db_name: create_database_expr.database_name,
         };
         let result = self.sql_handler.create_database(req).await;
@@ -158,10 +163,18 @@ impl Instance {
 
 #[async_trait]
 impl GrpcQueryHandler for Instance {
-    async fn do_query(&self, query: ObjectExpr) -> servers::error::Result<ObjectResult> {
+    async fn do_query(
+        &self,
+        query: ObjectExpr,
+        query_ctx: QueryContextRef,
+    ) -> servers::error::Result<ObjectResult> {
         let object_resp = match query.expr {
             Some(object_expr::Expr::Insert(insert_expr)) => {
-                let catalog_name = DEFAULT_CATALOG_NAME;
+                let catalog_name = if insert_expr.catalog_name.is_empty() {
+                    DEFAULT_CATALOG_NAME
+                } else {
+                    &insert_expr.catalog_name
+                };
                 let schema_name = &insert_expr.schema_name;
                 let table_name = &insert_expr.table_name;
 
@@ -172,7 +185,16 @@ impl GrpcQueryHandler for Instance {
                 self.handle_insert(catalog_name, schema_name, table_name, insert_batches)
                     .await
             }
-            Some(object_expr::Expr::Select(select_expr)) => self.handle_select(select_expr).await,
+            Some(object_expr::Expr::Select(select_expr)) => {
+                self.handle_select(select_expr, query_ctx).await
+            }
+            Some(object_expr::Expr::Script(script_expr)) => {
+                let result = self
+                    .script_executor
+                    .execute_script(&script_expr.name, script_expr.params, query_ctx)
+                    .await;
+                to_object_result(result).await
+            }
             other => {
                 return servers::error::NotSupportedSnafu {
                     feat: format!("{:?}", other),
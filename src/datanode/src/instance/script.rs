@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 use async_trait::async_trait;
 use common_query::Output;
 use common_telemetry::timer;
+use servers::http::script::ScriptInfo;
 use servers::query_handler::ScriptHandler;
+use session::context::QueryContextRef;
 
 use crate::instance::Instance;
 use crate::metric;
@@ -27,8 +32,51 @@ impl ScriptHandler for Instance {
         self.script_executor.insert_script(name, script).await
     }
 
-    async fn execute_script(&self, name: &str) -> servers::error::Result<Output> {
+    async fn execute_script(
+        &self,
+        name: &str,
+        params: HashMap<String, String>,
+        query_ctx: QueryContextRef,
+    ) -> servers::error::Result<Output> {
         let _timer = timer!(metric::METRIC_RUN_SCRIPT_ELAPSED);
-        self.script_executor.execute_script(name).await
+        self.script_executor
+            .execute_script(name, params, query_ctx)
+            .await
+    }
+
+    async fn list_scripts(&self) -> servers::error::Result<Vec<ScriptInfo>> {
+        self.script_executor.list_scripts().await
+    }
+
+    async fn get_script(
+        &self,
+        name: &str,
+        version: Option<i64>,
+    ) -> servers::error::Result<ScriptInfo> {
+        self.script_executor.get_script(name, version).await
+    }
+
+    async fn list_script_versions(&self, name: &str) -> servers::error::Result<Vec<ScriptInfo>> {
+        self.script_executor.list_script_versions(name).await
+    }
+
+    async fn delete_script(&self, name: &str) -> servers::error::Result<()> {
+        self.script_executor.delete_script(name).await
+    }
+
+    async fn schedule_script(
+        &self,
+        name: &str,
+        interval: Duration,
+        target_table: &str,
+        params: HashMap<String, String>,
+    ) -> servers::error::Result<()> {
+        self.script_executor
+            .schedule_script(name, interval, target_table, params)
+            .await
+    }
+
+    async fn unschedule_script(&self, name: &str) -> servers::error::Result<()> {
+        self.script_executor.unschedule_script(name).await
     }
 }
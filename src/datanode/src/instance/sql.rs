@@ -13,7 +13,9 @@
 // limitations under the License.
 
 use async_trait::async_trait;
+use catalog::{CreateCatalogRequest, DropCatalogRequest};
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_catalog::parse_catalog_and_schema_from_client_database_name;
 use common_error::prelude::BoxedError;
 use common_query::Output;
 use common_recordbatch::RecordBatches;
@@ -23,6 +25,7 @@ use servers::query_handler::SqlQueryHandler;
 use session::context::QueryContextRef;
 use snafu::prelude::*;
 use sql::ast::ObjectName;
+use sql::statements::privilege::GrantKind;
 use sql::statements::statement::Statement;
 use table::engine::TableReference;
 use table::requests::CreateDatabaseRequest;
@@ -59,12 +62,16 @@ impl Instance {
                     self.catalog_manager.clone(),
                     *i,
                     table_ref,
+                    query_ctx.time_zone().as_ref(),
                 )?;
                 self.sql_handler.execute(request, query_ctx).await
             }
 
             Statement::CreateDatabase(c) => {
                 let request = CreateDatabaseRequest {
+                    catalog_name: query_ctx
+                        .current_catalog()
+                        .unwrap_or_else(|| DEFAULT_CATALOG_NAME.to_string()),
                     db_name: c.name.to_string(),
                 };
 
@@ -75,6 +82,37 @@ impl Instance {
                     .await
             }
 
+            Statement::CreateCatalog(c) => {
+                let catalog = c.name.to_string();
+                // Users bound to a tenant are not allowed to manage catalogs.
+                ensure!(
+                    query_ctx.tenant().is_none(),
+                    error::AccessDeniedSnafu { catalog: &catalog }
+                );
+                info!("Creating a new catalog: {}", catalog);
+
+                self.sql_handler
+                    .execute(
+                        SqlRequest::CreateCatalog(CreateCatalogRequest { catalog }),
+                        query_ctx,
+                    )
+                    .await
+            }
+            Statement::DropCatalog(c) => {
+                ensure!(
+                    query_ctx.tenant().is_none(),
+                    error::AccessDeniedSnafu { catalog: &c.name }
+                );
+                info!("Dropping catalog: {}", c.name);
+
+                self.sql_handler
+                    .execute(
+                        SqlRequest::DropCatalog(DropCatalogRequest { catalog: c.name }),
+                        query_ctx,
+                    )
+                    .await
+            }
+
             Statement::CreateTable(c) => {
                 let table_id = self
                     .table_id_provider
@@ -110,6 +148,12 @@ impl Instance {
                     .await
             }
             Statement::DropTable(drop_table) => {
+                ensure!(
+                    query_ctx.is_catalog_accessible(&drop_table.catalog_name),
+                    error::AccessDeniedSnafu {
+                        catalog: &drop_table.catalog_name,
+                    }
+                );
                 let req = self.sql_handler.drop_table_to_request(drop_table);
                 self.sql_handler
                     .execute(SqlRequest::DropTable(req), query_ctx)
@@ -138,21 +182,118 @@ impl Instance {
             Statement::ShowCreateTable(_stmt) => {
                 unimplemented!("SHOW CREATE TABLE is unimplemented yet");
             }
+            // Processes are listed and killed by the `ProcessManager` wrapping the instance.
+            Statement::ShowProcesslist(_) => error::ProcessNotSupportedSnafu {
+                statement: "SHOW PROCESSLIST",
+            }
+            .fail(),
+            Statement::Kill(_) => error::ProcessNotSupportedSnafu { statement: "KILL" }.fail(),
             Statement::Use(db) => {
+                let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(&db);
+                let catalog = catalog.map(|c| c.to_string()).unwrap_or_else(|| {
+                    query_ctx
+                        .current_catalog()
+                        .unwrap_or_else(|| DEFAULT_CATALOG_NAME.to_string())
+                });
+                ensure!(
+                    query_ctx.is_catalog_accessible(&catalog),
+                    error::AccessDeniedSnafu { catalog: &catalog }
+                );
                 ensure!(
                     self.catalog_manager
-                        .schema(DEFAULT_CATALOG_NAME, &db)
+                        .schema(&catalog, schema)
                         .context(error::CatalogSnafu)?
                         .is_some(),
                     error::SchemaNotFoundSnafu { name: &db }
                 );
 
-                query_ctx.set_current_schema(&db);
+                query_ctx.set_current_catalog(&catalog);
+                query_ctx.set_current_schema(schema);
 
                 Ok(Output::RecordBatches(RecordBatches::empty()))
             }
+            Statement::SetVariables(set_variables) => {
+                for (name, value) in set_variables.variables {
+                    query_ctx
+                        .set_variable(&name, &value)
+                        .context(error::SetVariableSnafu {
+                            name: &name,
+                            value: &value,
+                        })?;
+                }
+                Ok(Output::AffectedRows(0))
+            }
+            stmt @ (Statement::CreateUser(_)
+            | Statement::CreateRole(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_)) => self.execute_privilege_statement(stmt, query_ctx).await,
         }
     }
+
+    /// Executes `CREATE USER`, `CREATE ROLE`, `GRANT` and `REVOKE`.
+    async fn execute_privilege_statement(
+        &self,
+        stmt: Statement,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let manager = self
+            .privilege_manager
+            .as_ref()
+            .context(error::PrivilegeNotSupportedSnafu)?;
+        let current_schema = query_ctx
+            .current_schema()
+            .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string());
+        // Users and roles are created in the tenant of the session.
+        let tenant = query_ctx.tenant();
+        let tenant = tenant.as_deref();
+
+        match stmt {
+            Statement::CreateUser(c) => {
+                info!("Creating user: {}", c.name);
+                manager.create_user(tenant, &c.name).await
+            }
+            Statement::CreateRole(c) => {
+                info!("Creating role: {}", c.name);
+                manager.create_role(tenant, &c.name).await
+            }
+            Statement::Grant(grant) => match grant.kind {
+                GrantKind::Privileges { privileges, object } => {
+                    let object = object.resolve(&current_schema);
+                    info!(
+                        "Granting {:?} on {} to role {}",
+                        privileges, object, grant.grantee
+                    );
+                    manager
+                        .grant_privileges(tenant, &grant.grantee, &privileges, &object)
+                        .await
+                }
+                GrantKind::Role(role) => {
+                    info!("Granting role {} to user {}", role, grant.grantee);
+                    manager.grant_role(tenant, &role, &grant.grantee).await
+                }
+            },
+            Statement::Revoke(revoke) => match revoke.kind {
+                GrantKind::Privileges { privileges, object } => {
+                    let object = object.resolve(&current_schema);
+                    info!(
+                        "Revoking {:?} on {} from role {}",
+                        privileges, object, revoke.grantee
+                    );
+                    manager
+                        .revoke_privileges(tenant, &revoke.grantee, &privileges, &object)
+                        .await
+                }
+                GrantKind::Role(role) => {
+                    info!("Revoking role {} from user {}", role, revoke.grantee);
+                    manager.revoke_role(tenant, &role, &revoke.grantee).await
+                }
+            },
+            _ => unreachable!(),
+        }
+        .context(error::ManagePrivilegeSnafu)?;
+
+        Ok(Output::AffectedRows(0))
+    }
 }
 
 // TODO(LFC): Refactor consideration: move this function to some helper mod,
@@ -162,29 +303,15 @@ fn table_idents_to_full_name(
     obj_name: &ObjectName,
     query_ctx: QueryContextRef,
 ) -> Result<(String, String, String)> {
-    match &obj_name.0[..] {
-        [table] => Ok((
-            DEFAULT_CATALOG_NAME.to_string(),
-            query_ctx.current_schema().unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string()),
-            table.value.clone(),
-        )),
-        [schema, table] => Ok((
-            DEFAULT_CATALOG_NAME.to_string(),
-            schema.value.clone(),
-            table.value.clone(),
-        )),
-        [catalog, schema, table] => Ok((
-            catalog.value.clone(),
-            schema.value.clone(),
-            table.value.clone(),
-        )),
-        _ => error::InvalidSqlSnafu {
-            msg: format!(
-                "expect table name to be <catalog>.<schema>.<table>, <schema>.<table> or <table>, actual: {}",
-                obj_name
-            ),
-        }.fail(),
-    }
+    let full_name = sql::statements::table_idents_to_full_name_with_ctx(obj_name, &query_ctx)
+        .context(error::ParseSqlSnafu)?;
+    ensure!(
+        query_ctx.is_catalog_accessible(&full_name.0),
+        error::AccessDeniedSnafu {
+            catalog: &full_name.0,
+        }
+    );
+    Ok(full_name)
 }
 
 #[async_trait]
@@ -278,4 +405,28 @@ mod test {
             )
         );
     }
+
+    #[test]
+    fn test_table_idents_to_full_name_with_tenant() {
+        let query_ctx = Arc::new(QueryContext::new());
+        query_ctx.set_tenant("team_a");
+
+        let bare = ObjectName(vec!["my_table".into()]);
+        assert_eq!(
+            table_idents_to_full_name(&bare, query_ctx.clone()).unwrap(),
+            (
+                "team_a".to_string(),
+                DEFAULT_SCHEMA_NAME.to_string(),
+                "my_table".to_string()
+            )
+        );
+
+        let full = ObjectName(vec![
+            DEFAULT_CATALOG_NAME.into(),
+            DEFAULT_SCHEMA_NAME.into(),
+            "my_table".into(),
+        ]);
+        let err = table_idents_to_full_name(&full, query_ctx).unwrap_err();
+        assert!(matches!(err, error::Error::AccessDenied { .. }));
+    }
 }
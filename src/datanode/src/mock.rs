@@ -54,7 +54,11 @@ impl Instance {
                 .unwrap(),
         );
 
-        let factory = QueryEngineFactory::new(catalog_manager.clone());
+        let privilege_manager = catalog_manager.privilege_manager();
+        let factory = QueryEngineFactory::with_privilege_manager(
+            catalog_manager.clone(),
+            Some(privilege_manager.clone()),
+        );
         let query_engine = factory.query_engine();
 
         let sql_handler = SqlHandler::new(
@@ -62,7 +66,7 @@ impl Instance {
             catalog_manager.clone(),
             query_engine.clone(),
         );
-        let script_executor = ScriptExecutor::new(catalog_manager.clone(), query_engine.clone())
+        let script_executor = ScriptExecutor::new(catalog_manager.clone(), query_engine.clone(), 0)
             .await
             .unwrap();
 
@@ -70,6 +74,7 @@ impl Instance {
             0,
             "127.0.0.1:3302".to_string(),
             meta_client,
+            catalog_manager.clone(),
         ));
 
         let table_id_provider = Some(catalog_manager.clone() as TableIdProviderRef);
@@ -80,6 +85,7 @@ impl Instance {
             script_executor,
             heartbeat_task,
             table_id_provider,
+            privilege_manager: Some(privilege_manager),
         })
     }
 
@@ -113,13 +119,18 @@ impl Instance {
 
         let factory = QueryEngineFactory::new(catalog_manager.clone());
         let query_engine = factory.query_engine();
-        let script_executor =
-            ScriptExecutor::new(catalog_manager.clone(), query_engine.clone()).await?;
+        let script_executor = ScriptExecutor::new(
+            catalog_manager.clone(),
+            query_engine.clone(),
+            opts.node_id.unwrap_or(42),
+        )
+        .await?;
 
         let heartbeat_task = HeartbeatTask::new(
             opts.node_id.unwrap_or(42),
             opts.rpc_addr.clone(),
             meta_client.clone(),
+            catalog_manager.clone(),
         );
         Ok(Self {
             query_engine: query_engine.clone(),
@@ -132,6 +143,7 @@ impl Instance {
             script_executor,
             table_id_provider: Some(Arc::new(LocalTableIdProvider::default())),
             heartbeat_task: Some(heartbeat_task),
+            privilege_manager: None,
         })
     }
 }
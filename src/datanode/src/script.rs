@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
 use catalog::CatalogManagerRef;
 use common_query::Output;
 use query::QueryEngineRef;
+use servers::http::script::ScriptInfo;
+use session::context::QueryContextRef;
 
 use crate::error::Result;
 
@@ -28,10 +33,15 @@ mod dummy {
         pub async fn new(
             _catalog_manager: CatalogManagerRef,
             _query_engine: QueryEngineRef,
+            _node_id: u64,
         ) -> Result<Self> {
             Ok(Self {})
         }
 
+        pub async fn start(&self) -> Result<()> {
+            Ok(())
+        }
+
         pub async fn insert_script(
             &self,
             _name: &str,
@@ -40,7 +50,49 @@ mod dummy {
             servers::error::NotSupportedSnafu { feat: "script" }.fail()
         }
 
-        pub async fn execute_script(&self, _script: &str) -> servers::error::Result<Output> {
+        pub async fn execute_script(
+            &self,
+            _script: &str,
+            _params: HashMap<String, String>,
+            _query_ctx: QueryContextRef,
+        ) -> servers::error::Result<Output> {
+            servers::error::NotSupportedSnafu { feat: "script" }.fail()
+        }
+
+        pub async fn list_scripts(&self) -> servers::error::Result<Vec<ScriptInfo>> {
+            servers::error::NotSupportedSnafu { feat: "script" }.fail()
+        }
+
+        pub async fn get_script(
+            &self,
+            _name: &str,
+            _version: Option<i64>,
+        ) -> servers::error::Result<ScriptInfo> {
+            servers::error::NotSupportedSnafu { feat: "script" }.fail()
+        }
+
+        pub async fn list_script_versions(
+            &self,
+            _name: &str,
+        ) -> servers::error::Result<Vec<ScriptInfo>> {
+            servers::error::NotSupportedSnafu { feat: "script" }.fail()
+        }
+
+        pub async fn delete_script(&self, _name: &str) -> servers::error::Result<()> {
+            servers::error::NotSupportedSnafu { feat: "script" }.fail()
+        }
+
+        pub async fn schedule_script(
+            &self,
+            _name: &str,
+            _interval: Duration,
+            _target_table: &str,
+            _params: HashMap<String, String>,
+        ) -> servers::error::Result<()> {
+            servers::error::NotSupportedSnafu { feat: "script" }.fail()
+        }
+
+        pub async fn unschedule_script(&self, _name: &str) -> servers::error::Result<()> {
             servers::error::NotSupportedSnafu { feat: "script" }.fail()
         }
     }
@@ -48,29 +100,45 @@ mod dummy {
 
 #[cfg(feature = "python")]
 mod python {
+    use std::sync::Arc;
+
     use common_error::prelude::BoxedError;
     use common_telemetry::logging::error;
     use script::manager::ScriptManager;
+    use script::scheduler::ScheduleRequest;
+    use script::ScriptRecord;
     use snafu::ResultExt;
 
     use super::*;
 
     pub struct ScriptExecutor {
-        script_manager: ScriptManager,
+        script_manager: Arc<ScriptManager>,
     }
 
     impl ScriptExecutor {
+        /// Creates the executor on the node `node_id`, which owns the scripts scheduled on it.
         pub async fn new(
             catalog_manager: CatalogManagerRef,
             query_engine: QueryEngineRef,
+            node_id: u64,
         ) -> Result<Self> {
             Ok(Self {
-                script_manager: ScriptManager::new(catalog_manager, query_engine)
-                    .await
-                    .context(crate::error::StartScriptManagerSnafu)?,
+                script_manager: Arc::new(
+                    ScriptManager::new(catalog_manager, query_engine, node_id)
+                        .await
+                        .context(crate::error::StartScriptManagerSnafu)?,
+                ),
             })
         }
 
+        /// Registers the stored scripts as SQL functions and restores the schedules.
+        pub async fn start(&self) -> Result<()> {
+            self.script_manager
+                .start()
+                .await
+                .context(crate::error::StartScriptManagerSnafu)
+        }
+
         pub async fn insert_script(&self, name: &str, script: &str) -> servers::error::Result<()> {
             let _s = self
                 .script_manager
@@ -85,9 +153,14 @@ mod python {
             Ok(())
         }
 
-        pub async fn execute_script(&self, name: &str) -> servers::error::Result<Output> {
+        pub async fn execute_script(
+            &self,
+            name: &str,
+            params: HashMap<String, String>,
+            query_ctx: QueryContextRef,
+        ) -> servers::error::Result<Output> {
             self.script_manager
-                .execute(name)
+                .execute(name, params, query_ctx)
                 .await
                 .map_err(|e| {
                     error!(e; "Instance failed to execute script");
@@ -95,6 +168,108 @@ mod python {
                 })
                 .context(servers::error::ExecuteScriptSnafu { name })
         }
+
+        pub async fn list_scripts(&self) -> servers::error::Result<Vec<ScriptInfo>> {
+            let scripts = self
+                .script_manager
+                .list()
+                .await
+                .map_err(|e| {
+                    error!(e; "Instance failed to list scripts");
+                    BoxedError::new(e)
+                })
+                .context(servers::error::ListScriptsSnafu)?;
+
+            Ok(scripts
+                .into_iter()
+                .map(|s| to_script_info(s, false))
+                .collect())
+        }
+
+        pub async fn get_script(
+            &self,
+            name: &str,
+            version: Option<i64>,
+        ) -> servers::error::Result<ScriptInfo> {
+            let script = self
+                .script_manager
+                .get(name, version)
+                .await
+                .map_err(BoxedError::new)
+                .context(servers::error::FindScriptSnafu { name })?;
+
+            Ok(to_script_info(script, true))
+        }
+
+        pub async fn list_script_versions(
+            &self,
+            name: &str,
+        ) -> servers::error::Result<Vec<ScriptInfo>> {
+            let scripts = self
+                .script_manager
+                .versions(name)
+                .await
+                .map_err(BoxedError::new)
+                .context(servers::error::FindScriptSnafu { name })?;
+
+            Ok(scripts
+                .into_iter()
+                .map(|s| to_script_info(s, false))
+                .collect())
+        }
+
+        pub async fn delete_script(&self, name: &str) -> servers::error::Result<()> {
+            self.script_manager
+                .delete(name)
+                .await
+                .map_err(|e| {
+                    error!(e; "Instance failed to delete script");
+                    BoxedError::new(e)
+                })
+                .context(servers::error::DeleteScriptSnafu { name })
+        }
+
+        pub async fn schedule_script(
+            &self,
+            name: &str,
+            interval: Duration,
+            target_table: &str,
+            params: HashMap<String, String>,
+        ) -> servers::error::Result<()> {
+            let request = ScheduleRequest {
+                name: name.to_string(),
+                interval,
+                target_table: target_table.to_string(),
+                params,
+            };
+            self.script_manager
+                .schedule(request)
+                .await
+                .map_err(|e| {
+                    error!(e; "Instance failed to schedule script");
+                    BoxedError::new(e)
+                })
+                .context(servers::error::ScheduleScriptSnafu { name })
+        }
+
+        pub async fn unschedule_script(&self, name: &str) -> servers::error::Result<()> {
+            self.script_manager
+                .unschedule(name)
+                .await
+                .map_err(BoxedError::new)
+                .context(servers::error::ScheduleScriptSnafu { name })
+        }
+    }
+
+    fn to_script_info(record: ScriptRecord, with_source: bool) -> ScriptInfo {
+        ScriptInfo {
+            name: record.name,
+            engine: record.engine,
+            version: record.version,
+            gmt_created: record.gmt_created,
+            gmt_modified: record.gmt_modified,
+            script: with_source.then_some(record.script),
+        }
     }
 }
 
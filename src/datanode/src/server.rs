@@ -20,6 +20,7 @@ use common_runtime::Builder as RuntimeBuilder;
 use common_telemetry::tracing::log::info;
 use servers::grpc::GrpcServer;
 use servers::mysql::server::MysqlServer;
+use servers::process::ProcessManager;
 use servers::server::Server;
 use servers::Mode;
 use snafu::ResultExt;
@@ -46,6 +47,12 @@ impl Services {
                 .context(RuntimeResourceSnafu)?,
         );
 
+        // The queries sent by frontends are tracked, so that they can be killed and timed out.
+        let process_manager = Arc::new(
+            ProcessManager::new(instance.clone(), Default::default())
+                .with_grpc_handler(instance.clone()),
+        );
+
         let mysql_server = match opts.mode {
             Mode::Standalone => {
                 info!("Disable MySQL server on datanode when running in standalone mode");
@@ -60,16 +67,22 @@ impl Services {
                         .context(RuntimeResourceSnafu)?,
                 );
                 Some(MysqlServer::create_server(
-                    instance.clone(),
+                    process_manager.clone(),
                     mysql_io_runtime,
-                    Default::default(),
+                    opts.mysql_tls.clone(),
                     None,
                 ))
             }
         };
 
         Ok(Self {
-            grpc_server: GrpcServer::new(instance.clone(), instance, grpc_runtime),
+            grpc_server: GrpcServer::new(
+                process_manager,
+                instance,
+                grpc_runtime,
+                opts.rpc_tls.clone(),
+                None,
+            ),
             mysql_server,
         })
     }
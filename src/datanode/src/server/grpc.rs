@@ -215,6 +215,7 @@ mod tests {
             datatype: 1024,
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         };
         let result = column_def.try_as_column_schema();
         assert!(matches!(
@@ -227,6 +228,7 @@ mod tests {
             datatype: ColumnDataType::String as i32,
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         };
         let column_schema = column_def.try_as_column_schema().unwrap();
         assert_eq!(column_schema.name, "a");
@@ -239,6 +241,7 @@ mod tests {
             datatype: ColumnDataType::String as i32,
             is_nullable: true,
             default_constraint: Some(default_constraint.clone().try_into().unwrap()),
+            datatype_extension: None,
         };
         let column_schema = column_def.try_as_column_schema().unwrap();
         assert_eq!(column_schema.name, "a");
@@ -257,24 +260,28 @@ mod tests {
                 datatype: ColumnDataType::String as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "ts".to_string(),
                 datatype: ColumnDataType::Timestamp as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "cpu".to_string(),
                 datatype: ColumnDataType::Float32 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             ColumnDef {
                 name: "memory".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
         ];
         CreateExpr {
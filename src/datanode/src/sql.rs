@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use catalog::CatalogManagerRef;
+use catalog::{CatalogManagerRef, CreateCatalogRequest, DropCatalogRequest};
 use common_query::Output;
 use common_telemetry::error;
 use query::query_engine::QueryEngineRef;
@@ -38,6 +38,8 @@ pub enum SqlRequest {
     Insert(InsertRequest),
     CreateTable(CreateTableRequest),
     CreateDatabase(CreateDatabaseRequest),
+    CreateCatalog(CreateCatalogRequest),
+    DropCatalog(DropCatalogRequest),
     Alter(AlterTableRequest),
     DropTable(DropTableRequest),
     ShowDatabases(ShowDatabases),
@@ -75,10 +77,13 @@ impl SqlHandler {
             SqlRequest::Insert(req) => self.insert(req).await,
             SqlRequest::CreateTable(req) => self.create_table(req).await,
             SqlRequest::CreateDatabase(req) => self.create_database(req).await,
+            SqlRequest::CreateCatalog(req) => self.create_catalog(req).await,
+            SqlRequest::DropCatalog(req) => self.drop_catalog(req).await,
             SqlRequest::Alter(req) => self.alter(req).await,
             SqlRequest::DropTable(req) => self.drop_table(req).await,
             SqlRequest::ShowDatabases(stmt) => {
-                show_databases(stmt, self.catalog_manager.clone()).context(ExecuteSqlSnafu)
+                show_databases(stmt, self.catalog_manager.clone(), query_ctx)
+                    .context(ExecuteSqlSnafu)
             }
             SqlRequest::ShowTables(stmt) => {
                 show_tables(stmt, self.catalog_manager.clone(), query_ctx).context(ExecuteSqlSnafu)
@@ -257,7 +262,12 @@ mod tests {
             }
         };
         let request = sql_handler
-            .insert_to_request(catalog_list.clone(), *stmt, TableReference::bare("demo"))
+            .insert_to_request(
+                catalog_list.clone(),
+                *stmt,
+                TableReference::bare("demo"),
+                None,
+            )
             .unwrap();
 
         match request {
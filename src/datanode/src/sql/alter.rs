@@ -12,13 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use catalog::RenameTableRequest;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_query::Output;
+use common_telemetry::error;
 use snafu::prelude::*;
 use sql::statements::alter::{AlterTable, AlterTableOperation};
-use sql::statements::column_def_to_schema;
+use sql::statements::{
+    column_def_to_schema, sql_data_type_to_concrete_data_type, sql_options_to_map,
+};
 use table::engine::{EngineContext, TableReference};
-use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest};
+use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest, ModifyColumnRequest};
 
 use crate::error::{self, Result};
 use crate::sql::SqlHandler;
@@ -43,12 +47,47 @@ impl SqlHandler {
                 table_name: &full_table_name,
             }
         );
-        self.table_engine
-            .alter_table(&ctx, req)
-            .await
-            .context(error::AlterTableSnafu {
-                table_name: full_table_name,
-            })?;
+        let new_table_name = match &req.alter_kind {
+            AlterKind::RenameTable { new_table_name } => Some(new_table_name.clone()),
+            _ => None,
+        };
+        let table =
+            self.table_engine
+                .alter_table(&ctx, req)
+                .await
+                .context(error::AlterTableSnafu {
+                    table_name: &full_table_name,
+                })?;
+        if let Some(new_table_name) = new_table_name {
+            let rename_table_req = RenameTableRequest {
+                catalog: catalog_name.to_string(),
+                schema: schema_name.to_string(),
+                table_name: table_name.to_string(),
+                new_table_name: new_table_name.clone(),
+                table_id: table.table_info().ident.table_id,
+            };
+            if let Err(e) = self.catalog_manager.rename_table(rename_table_req).await {
+                // Renames the table in the engine back, so the engine and the catalog agree on
+                // the table name.
+                let revert_req = AlterTableRequest {
+                    catalog_name: Some(catalog_name.to_string()),
+                    schema_name: Some(schema_name.to_string()),
+                    table_name: new_table_name,
+                    alter_kind: AlterKind::RenameTable {
+                        new_table_name: table_name.to_string(),
+                    },
+                };
+                if let Err(e) = self.table_engine.alter_table(&ctx, revert_req).await {
+                    error!(
+                        "Failed to revert renaming table {}, error: {}",
+                        full_table_name, e
+                    );
+                }
+                return Err(e).context(error::RenameTableSnafu {
+                    table_name: full_table_name,
+                });
+            }
+        }
         // Tried in MySQL, it really prints "Affected Rows: 0".
         Ok(Output::AffectedRows(0))
     }
@@ -76,6 +115,22 @@ impl SqlHandler {
             AlterTableOperation::DropColumn { name } => AlterKind::DropColumns {
                 names: vec![name.value.clone()],
             },
+            AlterTableOperation::RenameTable { new_table_name } => AlterKind::RenameTable {
+                new_table_name: new_table_name.value.clone(),
+            },
+            AlterTableOperation::ModifyColumn {
+                column_name,
+                data_type,
+            } => AlterKind::ModifyColumns {
+                columns: vec![ModifyColumnRequest {
+                    column_name: column_name.value.clone(),
+                    data_type: sql_data_type_to_concrete_data_type(data_type)
+                        .context(error::ParseSqlSnafu)?,
+                }],
+            },
+            AlterTableOperation::SetTableOptions { options } => AlterKind::SetTableOptions {
+                options: sql_options_to_map(options),
+            },
         };
         Ok(AlterTableRequest {
             catalog_name: Some(table_ref.catalog.to_string()),
@@ -133,4 +188,42 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_alter_to_request_with_other_kinds() {
+        let handler = create_mock_sql_handler().await;
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 RENAME TO my_metric_2");
+        let req = handler
+            .alter_to_request(alter_table, TableReference::bare("my_metric_1"))
+            .unwrap();
+        match req.alter_kind {
+            AlterKind::RenameTable { new_table_name } => assert_eq!("my_metric_2", new_table_name),
+            _ => unreachable!(),
+        }
+
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 MODIFY COLUMN cpu BIGINT");
+        let req = handler
+            .alter_to_request(alter_table, TableReference::bare("my_metric_1"))
+            .unwrap();
+        match req.alter_kind {
+            AlterKind::ModifyColumns { columns } => {
+                assert_eq!(1, columns.len());
+                assert_eq!("cpu", columns[0].column_name);
+                assert_eq!(ConcreteDataType::int64_datatype(), columns[0].data_type);
+            }
+            _ => unreachable!(),
+        }
+
+        let alter_table = parse_sql("ALTER TABLE my_metric_1 SET (ttl = '7d')");
+        let req = handler
+            .alter_to_request(alter_table, TableReference::bare("my_metric_1"))
+            .unwrap();
+        match req.alter_kind {
+            AlterKind::SetTableOptions { options } => {
+                assert_eq!(1, options.len());
+                assert_eq!("7d", options["ttl"]);
+            }
+            _ => unreachable!(),
+        }
+    }
 }
@@ -15,8 +15,9 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
-use catalog::{RegisterSchemaRequest, RegisterTableRequest};
-use common_catalog::consts::DEFAULT_CATALOG_NAME;
+use catalog::{
+    CreateCatalogRequest, DropCatalogRequest, RegisterSchemaRequest, RegisterTableRequest,
+};
 use common_query::Output;
 use common_telemetry::tracing::info;
 use common_telemetry::tracing::log::error;
@@ -41,7 +42,7 @@ impl SqlHandler {
     pub(crate) async fn create_database(&self, req: CreateDatabaseRequest) -> Result<Output> {
         let schema = req.db_name;
         let req = RegisterSchemaRequest {
-            catalog: DEFAULT_CATALOG_NAME.to_string(),
+            catalog: req.catalog_name,
             schema: schema.clone(),
         };
         self.catalog_manager
@@ -53,6 +54,28 @@ impl SqlHandler {
         Ok(Output::AffectedRows(1))
     }
 
+    pub(crate) async fn create_catalog(&self, req: CreateCatalogRequest) -> Result<Output> {
+        let catalog = req.catalog.clone();
+        self.catalog_manager
+            .create_catalog(req)
+            .await
+            .context(error::CreateCatalogSnafu { catalog: &catalog })?;
+
+        info!("Successfully created catalog: {:?}", catalog);
+        Ok(Output::AffectedRows(1))
+    }
+
+    pub(crate) async fn drop_catalog(&self, req: DropCatalogRequest) -> Result<Output> {
+        let catalog = req.catalog.clone();
+        self.catalog_manager
+            .drop_catalog(req)
+            .await
+            .context(error::DropCatalogSnafu { catalog: &catalog })?;
+
+        info!("Successfully dropped catalog: {:?}", catalog);
+        Ok(Output::AffectedRows(1))
+    }
+
     pub(crate) async fn create_table(&self, req: CreateTableRequest) -> Result<Output> {
         let ctx = EngineContext {};
         // first check if catalog and schema exist
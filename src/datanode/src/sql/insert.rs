@@ -14,6 +14,7 @@
 
 use catalog::CatalogManagerRef;
 use common_query::Output;
+use common_time::TimeZone;
 use datatypes::prelude::{ConcreteDataType, VectorBuilder};
 use snafu::{ensure, OptionExt, ResultExt};
 use sql::ast::Value as SqlValue;
@@ -52,6 +53,7 @@ impl SqlHandler {
         catalog_manager: CatalogManagerRef,
         stmt: Insert,
         table_ref: TableReference,
+        time_zone: Option<&TimeZone>,
     ) -> Result<SqlRequest> {
         let columns = stmt.columns();
         let values = stmt.values().context(ParseSqlValueSnafu)?;
@@ -113,7 +115,7 @@ impl SqlHandler {
             for (sql_val, (column_name, data_type, builder)) in
                 row.iter().zip(columns_builders.iter_mut())
             {
-                add_row_to_vector(column_name, data_type, sql_val, builder)?;
+                add_row_to_vector(column_name, data_type, sql_val, time_zone, builder)?;
             }
         }
 
@@ -133,10 +135,12 @@ fn add_row_to_vector(
     column_name: &str,
     data_type: &ConcreteDataType,
     sql_val: &SqlValue,
+    time_zone: Option<&TimeZone>,
     builder: &mut VectorBuilder,
 ) -> Result<()> {
-    let value = statements::sql_value_to_value(column_name, data_type, sql_val)
-        .context(ParseSqlValueSnafu)?;
+    let value =
+        statements::sql_value_to_value_with_time_zone(column_name, data_type, sql_val, time_zone)
+            .context(ParseSqlValueSnafu)?;
     builder.push(&value);
 
     Ok(())
@@ -15,6 +15,9 @@
 use std::sync::Arc;
 
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
+use common_catalog::privilege::SUPERUSER;
+use common_error::ext::ErrorExt;
+use common_error::status_code::StatusCode;
 use common_query::Output;
 use common_recordbatch::util;
 use datafusion::arrow_print;
@@ -205,6 +208,52 @@ async fn test_execute_insert() {
     assert!(matches!(output, Output::AffectedRows(2)));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_set_time_zone() {
+    let instance = setup_test_instance().await;
+    let query_ctx = Arc::new(QueryContext::new());
+    query_ctx.set_current_user(SUPERUSER);
+
+    let output = instance
+        .execute_sql("SET time_zone = '+08:00'", query_ctx.clone())
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(0)));
+    assert!(instance
+        .execute_sql("SET time_zone = 'Mars/Olympus_Mons'", query_ctx.clone())
+        .await
+        .is_err());
+
+    let output = instance
+        .execute_sql(
+            "insert into demo(host, cpu, memory, ts) values ('host1', 66.6, 1024, '2022-06-15 15:02:37')",
+            query_ctx.clone(),
+        )
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let expected = vec![
+        "+-------+",
+        "| host  |",
+        "+-------+",
+        "| host1 |",
+        "+-------+",
+    ];
+    let output = instance
+        .execute_sql(
+            "select host from demo where ts = '2022-06-15 15:02:37'",
+            query_ctx,
+        )
+        .await
+        .unwrap();
+    check_output_stream(output, expected.clone()).await;
+
+    // 2022-06-15 15:02:37 in +08:00 is 1655276557000 in milliseconds.
+    let output = execute_sql(&instance, "select host from demo where ts = 1655276557000").await;
+    check_output_stream(output, expected).await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_execute_insert_query_with_i64_timestamp() {
     common_telemetry::init_default_ut_logging();
@@ -607,11 +656,73 @@ async fn test_use_database() {
     check_output_stream(output, expected).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multi_tenant_catalogs() {
+    let (opts, _guard) = test_util::create_tmp_dir_and_datanode_opts("multi_tenant_catalogs");
+    let instance = Instance::with_mock_meta_client(&opts).await.unwrap();
+    instance.start().await.unwrap();
+
+    let output = execute_sql(&instance, "create catalog team_a").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+
+    let query_ctx = Arc::new(QueryContext::new());
+    query_ctx.set_current_user(SUPERUSER);
+    query_ctx.set_tenant("team_a");
+    let execute = |sql: &'static str| instance.execute_sql(sql, query_ctx.clone());
+
+    let output = execute("create table tb1(col_i32 int, ts bigint, TIME INDEX(ts))")
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute("insert into tb1(col_i32, ts) values (1, 1655276557000)")
+        .await
+        .unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute("select col_i32 from tb1").await.unwrap();
+    let expected = vec![
+        "+---------+",
+        "| col_i32 |",
+        "+---------+",
+        "| 1       |",
+        "+---------+",
+    ];
+    check_output_stream(output, expected).await;
+
+    // Tables of other tenants are invisible.
+    assert!(execute("select number from greptime.public.numbers")
+        .await
+        .is_err());
+    let err = execute("insert into greptime.public.demo(ts) values (1)")
+        .await
+        .unwrap_err();
+    assert_eq!(StatusCode::AccessDenied, err.status_code());
+    let err = execute(r#"use "greptime-public""#).await.unwrap_err();
+    assert_eq!(StatusCode::AccessDenied, err.status_code());
+    let err = execute("create catalog team_b").await.unwrap_err();
+    assert_eq!(StatusCode::AccessDenied, err.status_code());
+
+    // The tenant is still able to access its own catalog.
+    let output = execute(r#"use "team_a-public""#).await.unwrap();
+    assert!(matches!(output, Output::RecordBatches(_)));
+
+    // Only empty catalogs can be dropped.
+    let err = instance
+        .execute_sql("drop catalog team_a", Arc::new(QueryContext::new()))
+        .await
+        .unwrap_err();
+    assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    let output = execute("drop table team_a.public.tb1").await.unwrap();
+    assert!(matches!(output, Output::AffectedRows(1)));
+    let output = execute_sql(&instance, "drop catalog team_a").await;
+    assert!(matches!(output, Output::AffectedRows(1)));
+}
+
 async fn execute_sql(instance: &Instance, sql: &str) -> Output {
     execute_sql_in_db(instance, sql, DEFAULT_SCHEMA_NAME).await
 }
 
 async fn execute_sql_in_db(instance: &Instance, sql: &str, db: &str) -> Output {
     let query_ctx = Arc::new(QueryContext::with_current_schema(db.to_string()));
+    query_ctx.set_current_user(SUPERUSER);
     instance.execute_sql(sql, query_ctx).await.unwrap()
 }
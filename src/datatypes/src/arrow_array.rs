@@ -20,8 +20,11 @@ use arrow::datatypes::DataType as ArrowDataType;
 use common_time::timestamp::Timestamp;
 use snafu::OptionExt;
 
+use crate::decimal::Decimal128;
 use crate::error::{ConversionSnafu, Result};
+use crate::json::Json;
 use crate::prelude::ConcreteDataType;
+use crate::types::JSON_EXTENSION_NAME;
 use crate::value::{ListValue, Value};
 
 pub type BinaryArray = ArrowBinaryArray<i64>;
@@ -83,6 +86,14 @@ pub fn arrow_array_get(array: &dyn Array, idx: usize) -> Result<Value> {
             };
             Value::Timestamp(Timestamp::new(value, unit))
         }
+        ArrowDataType::Decimal(precision, scale) => {
+            let value = cast_array!(array, PrimitiveArray::<i128>).value(idx);
+            Value::Decimal128(Decimal128::new(value, *precision as u8, *scale as u8))
+        }
+        ArrowDataType::Extension(name, _, _) if name == JSON_EXTENSION_NAME => {
+            let value = cast_array!(array, BinaryArray).value(idx);
+            Value::Json(Json::from_bytes(value)?)
+        }
         ArrowDataType::List(_) => {
             let array = cast_array!(array, ListArray::<i32>).value(idx);
             let inner_datatype = ConcreteDataType::try_from(array.data_type())?;
@@ -189,6 +200,16 @@ mod test {
             arrow_array_get(&array4, 0).unwrap()
         );
 
+        let array5 = PrimitiveArray::<i128>::from_data(
+            DataType::Decimal(10, 2),
+            Buffer::from_slice(&vec![12345, 2]),
+            None,
+        );
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 2)),
+            arrow_array_get(&array5, 0).unwrap()
+        );
+
         // test list array
         let data = vec![
             Some(vec![Some(1i32), Some(2), Some(3)]),
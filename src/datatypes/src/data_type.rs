@@ -22,9 +22,9 @@ use serde::{Deserialize, Serialize};
 use crate::error::{self, Error, Result};
 use crate::type_id::LogicalTypeId;
 use crate::types::{
-    BinaryType, BooleanType, DateTimeType, DateType, Float32Type, Float64Type, Int16Type,
-    Int32Type, Int64Type, Int8Type, ListType, NullType, StringType, TimestampType, UInt16Type,
-    UInt32Type, UInt64Type, UInt8Type,
+    BinaryType, BooleanType, DateTimeType, DateType, Decimal128Type, Float32Type, Float64Type,
+    Int16Type, Int32Type, Int64Type, Int8Type, JsonType, ListType, NullType, StringType,
+    TimestampType, UInt16Type, UInt32Type, UInt64Type, UInt8Type, JSON_EXTENSION_NAME,
 };
 use crate::value::Value;
 use crate::vectors::MutableVector;
@@ -55,6 +55,10 @@ pub enum ConcreteDataType {
     DateTime(DateTimeType),
     Timestamp(TimestampType),
 
+    Decimal128(Decimal128Type),
+
+    Json(JsonType),
+
     List(ListType),
 }
 
@@ -77,6 +81,8 @@ impl ConcreteDataType {
                 | ConcreteDataType::Date(_)
                 | ConcreteDataType::DateTime(_)
                 | ConcreteDataType::Timestamp(_)
+                | ConcreteDataType::Decimal128(_)
+                | ConcreteDataType::Json(_)
         )
     }
 
@@ -103,6 +109,14 @@ impl ConcreteDataType {
         )
     }
 
+    pub fn is_decimal(&self) -> bool {
+        matches!(self, ConcreteDataType::Decimal128(_))
+    }
+
+    pub fn is_json(&self) -> bool {
+        matches!(self, ConcreteDataType::Json(_))
+    }
+
     pub fn is_timestamp(&self) -> bool {
         matches!(
             self,
@@ -136,6 +150,42 @@ impl ConcreteDataType {
     pub fn is_null(&self) -> bool {
         matches!(self, ConcreteDataType::Null(NullType))
     }
+
+    /// Returns true if values of this type could be converted into values of type
+    /// `to` without losing any information, e.g. `Int32` to `Int64`.
+    ///
+    /// A type could always widen to itself.
+    pub fn can_widen_to(&self, to: &ConcreteDataType) -> bool {
+        use ConcreteDataType::*;
+
+        if self == to {
+            return true;
+        }
+
+        match self {
+            Int8(_) => matches!(to, Int16(_) | Int32(_) | Int64(_) | Float32(_) | Float64(_)),
+            Int16(_) => matches!(to, Int32(_) | Int64(_) | Float32(_) | Float64(_)),
+            Int32(_) => matches!(to, Int64(_) | Float64(_)),
+            UInt8(_) => matches!(
+                to,
+                UInt16(_)
+                    | UInt32(_)
+                    | UInt64(_)
+                    | Int16(_)
+                    | Int32(_)
+                    | Int64(_)
+                    | Float32(_)
+                    | Float64(_)
+            ),
+            UInt16(_) => matches!(
+                to,
+                UInt32(_) | UInt64(_) | Int32(_) | Int64(_) | Float32(_) | Float64(_)
+            ),
+            UInt32(_) => matches!(to, UInt64(_) | Int64(_) | Float64(_)),
+            Float32(_) => matches!(to, Float64(_)),
+            _ => false,
+        }
+    }
 }
 
 impl TryFrom<&ArrowDataType> for ConcreteDataType {
@@ -158,6 +208,12 @@ impl TryFrom<&ArrowDataType> for ConcreteDataType {
             ArrowDataType::Date32 => Self::date_datatype(),
             ArrowDataType::Date64 => Self::datetime_datatype(),
             ArrowDataType::Timestamp(u, _) => ConcreteDataType::from_arrow_time_unit(u),
+            ArrowDataType::Decimal(precision, scale) => {
+                Self::decimal128_datatype(*precision as u8, *scale as u8)
+            }
+            ArrowDataType::Extension(name, _, _) if name == JSON_EXTENSION_NAME => {
+                Self::json_datatype()
+            }
             ArrowDataType::Binary | ArrowDataType::LargeBinary => Self::binary_datatype(),
             ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 => Self::string_datatype(),
             ArrowDataType::List(field) => Self::List(ListType::new(
@@ -191,7 +247,7 @@ macro_rules! impl_new_concrete_type_functions {
 
 impl_new_concrete_type_functions!(
     Null, Boolean, UInt8, UInt16, UInt32, UInt64, Int8, Int16, Int32, Int64, Float32, Float64,
-    Binary, String, Date, DateTime
+    Binary, String, Date, DateTime, Json
 );
 
 impl ConcreteDataType {
@@ -199,6 +255,10 @@ impl ConcreteDataType {
         ConcreteDataType::List(ListType::new(inner_type))
     }
 
+    pub fn decimal128_datatype(precision: u8, scale: u8) -> Self {
+        ConcreteDataType::Decimal128(Decimal128Type::new(precision, scale))
+    }
+
     pub fn timestamp_datatype(unit: TimeUnit) -> Self {
         ConcreteDataType::Timestamp(TimestampType::new(unit))
     }
@@ -340,6 +400,10 @@ mod tests {
             ConcreteDataType::from_arrow_type(&ArrowDataType::Date32),
             ConcreteDataType::Date(_)
         ));
+        assert_eq!(
+            ConcreteDataType::json_datatype(),
+            ConcreteDataType::from_arrow_type(&ConcreteDataType::json_datatype().as_arrow_type())
+        );
     }
 
     #[test]
@@ -377,4 +441,30 @@ mod tests {
         assert!(ConcreteDataType::null_datatype().is_null());
         assert!(!ConcreteDataType::int32_datatype().is_null());
     }
+
+    #[test]
+    fn test_can_widen_to() {
+        let int32 = ConcreteDataType::int32_datatype();
+        assert!(int32.can_widen_to(&int32));
+        assert!(int32.can_widen_to(&ConcreteDataType::int64_datatype()));
+        assert!(int32.can_widen_to(&ConcreteDataType::float64_datatype()));
+        assert!(!int32.can_widen_to(&ConcreteDataType::int16_datatype()));
+        assert!(!int32.can_widen_to(&ConcreteDataType::float32_datatype()));
+        assert!(!int32.can_widen_to(&ConcreteDataType::uint64_datatype()));
+
+        assert!(
+            ConcreteDataType::uint8_datatype().can_widen_to(&ConcreteDataType::int16_datatype())
+        );
+        assert!(
+            !ConcreteDataType::uint64_datatype().can_widen_to(&ConcreteDataType::int64_datatype())
+        );
+        assert!(
+            !ConcreteDataType::int64_datatype().can_widen_to(&ConcreteDataType::float64_datatype())
+        );
+        assert!(ConcreteDataType::float32_datatype()
+            .can_widen_to(&ConcreteDataType::float64_datatype()));
+        assert!(
+            !ConcreteDataType::string_datatype().can_widen_to(&ConcreteDataType::binary_datatype())
+        );
+    }
 }
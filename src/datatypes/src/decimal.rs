@@ -0,0 +1,318 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use crate::error::{self, Result};
+
+/// Max number of decimal digits a [Decimal128] could hold.
+pub const DECIMAL128_MAX_PRECISION: u8 = 38;
+/// Precision of `DECIMAL` without explicit precision, same as MySQL.
+pub const DECIMAL128_DEFAULT_PRECISION: u8 = 10;
+/// Scale of `DECIMAL` without explicit scale, same as MySQL.
+pub const DECIMAL128_DEFAULT_SCALE: u8 = 0;
+
+/// Fixed-point decimal number, the actual value is `value / 10^scale`.
+///
+/// Decimals with different scales are compared by their numeric value, so `1.50` equals `1.5`.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Decimal128 {
+    value: i128,
+    precision: u8,
+    scale: u8,
+}
+
+impl Decimal128 {
+    pub fn new(value: i128, precision: u8, scale: u8) -> Self {
+        Self {
+            value,
+            precision,
+            scale,
+        }
+    }
+
+    /// Returns the unscaled value.
+    pub fn value(&self) -> i128 {
+        self.value
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.scale
+    }
+
+    /// Parses decimal literal like `-123.45` into a decimal of given `precision` and `scale`.
+    /// Extra fractional digits are rounded half away from zero.
+    pub fn from_str_with(s: &str, precision: u8, scale: u8) -> Result<Self> {
+        validate_precision_and_scale(precision, scale)?;
+
+        let raw = s.trim();
+        let (negative, digits) = match raw.as_bytes().first() {
+            Some(b'-') => (true, &raw[1..]),
+            Some(b'+') => (false, &raw[1..]),
+            _ => (false, raw),
+        };
+        let (int_part, frac_part) = digits.split_once('.').unwrap_or((digits, ""));
+        ensure!(
+            !(int_part.is_empty() && frac_part.is_empty())
+                && int_part.bytes().all(|b| b.is_ascii_digit())
+                && frac_part.bytes().all(|b| b.is_ascii_digit()),
+            error::InvalidDecimalSnafu {
+                reason: format!("'{}' is not a valid decimal literal", s),
+            }
+        );
+
+        let overflow = || {
+            error::InvalidDecimalSnafu {
+                reason: format!(
+                    "'{}' is out of range of DECIMAL({}, {})",
+                    s, precision, scale
+                ),
+            }
+            .build()
+        };
+        let mut value: i128 = 0;
+        let frac_digits = frac_part.bytes().chain(std::iter::repeat(b'0'));
+        for b in int_part.bytes().chain(frac_digits.take(scale as usize)) {
+            value = value
+                .checked_mul(10)
+                .and_then(|v| v.checked_add((b - b'0') as i128))
+                .ok_or_else(overflow)?;
+        }
+        if frac_part.len() > scale as usize && frac_part.as_bytes()[scale as usize] >= b'5' {
+            value = value.checked_add(1).ok_or_else(overflow)?;
+        }
+        if negative {
+            value = -value;
+        }
+
+        let decimal = Self::new(value, precision, scale);
+        ensure!(
+            decimal.fits_precision(),
+            error::InvalidDecimalSnafu {
+                reason: format!(
+                    "'{}' is out of range of DECIMAL({}, {})",
+                    s, precision, scale
+                ),
+            }
+        );
+        Ok(decimal)
+    }
+
+    /// Converts the decimal to given `precision` and `scale`, rounding half away from zero
+    /// if the scale decreases.
+    pub fn rescale(&self, precision: u8, scale: u8) -> Result<Self> {
+        validate_precision_and_scale(precision, scale)?;
+
+        let value = if scale >= self.scale {
+            pow10(scale - self.scale).and_then(|f| self.value.checked_mul(f))
+        } else {
+            pow10(self.scale - scale).map(|f| {
+                let (quotient, remainder) = (self.value / f, self.value % f);
+                if remainder.abs() >= f - remainder.abs() {
+                    quotient + self.value.signum()
+                } else {
+                    quotient
+                }
+            })
+        };
+        let decimal = value
+            .map(|v| Self::new(v, precision, scale))
+            .filter(|d| d.fits_precision());
+        decimal.ok_or_else(|| {
+            error::InvalidDecimalSnafu {
+                reason: format!(
+                    "{} is out of range of DECIMAL({}, {})",
+                    self, precision, scale
+                ),
+            }
+            .build()
+        })
+    }
+
+    /// Converts the decimal to f64, precision may be lost.
+    pub fn to_f64(&self) -> f64 {
+        self.value as f64 / 10f64.powi(self.scale as i32)
+    }
+
+    fn fits_precision(&self) -> bool {
+        match pow10(self.precision) {
+            Some(max) => self.value.abs() < max,
+            None => true,
+        }
+    }
+
+    /// Returns the value and scale with trailing fractional zeros removed, so
+    /// equal decimals always have the same normalized form.
+    fn normalized(&self) -> (i128, u8) {
+        let (mut value, mut scale) = (self.value, self.scale);
+        while scale > 0 && value % 10 == 0 {
+            value /= 10;
+            scale -= 1;
+        }
+        (value, scale)
+    }
+}
+
+impl Default for Decimal128 {
+    fn default() -> Self {
+        Self::new(0, DECIMAL128_DEFAULT_PRECISION, DECIMAL128_DEFAULT_SCALE)
+    }
+}
+
+impl Display for Decimal128 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let sign = if self.value < 0 { "-" } else { "" };
+        let digits = self.value.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return write!(f, "{}{}", sign, digits);
+        }
+
+        let digits = format!("{:0>width$}", digits, width = scale + 1);
+        let (int_part, frac_part) = digits.split_at(digits.len() - scale);
+        write!(f, "{}{}.{}", sign, int_part, frac_part)
+    }
+}
+
+impl PartialEq for Decimal128 {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal128 {}
+
+impl PartialOrd for Decimal128 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal128 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        if self.scale == other.scale {
+            return self.value.cmp(&other.value);
+        }
+
+        let (lhs, rhs) = (self.normalized(), other.normalized());
+        let scale = lhs.1.max(rhs.1);
+        let lhs_value = pow10(scale - lhs.1).and_then(|f| lhs.0.checked_mul(f));
+        let rhs_value = pow10(scale - rhs.1).and_then(|f| rhs.0.checked_mul(f));
+        match (lhs_value, rhs_value) {
+            (Some(l), Some(r)) => l.cmp(&r),
+            // The overflowed side always has a larger magnitude.
+            (None, _) => lhs.0.signum().cmp(&0),
+            (_, None) => 0.cmp(&rhs.0.signum()),
+        }
+    }
+}
+
+impl Hash for Decimal128 {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.normalized().hash(state)
+    }
+}
+
+pub(crate) fn validate_precision_and_scale(precision: u8, scale: u8) -> Result<()> {
+    ensure!(
+        precision > 0 && precision <= DECIMAL128_MAX_PRECISION && scale <= precision,
+        error::InvalidDecimalSnafu {
+            reason: format!("invalid precision {} and scale {}", precision, scale),
+        }
+    );
+    Ok(())
+}
+
+fn pow10(exp: u8) -> Option<i128> {
+    10i128.checked_pow(exp as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_decimal() {
+        let d = Decimal128::from_str_with("123.45", 10, 2).unwrap();
+        assert_eq!(12345, d.value());
+        assert_eq!("123.45", d.to_string());
+
+        let d = Decimal128::from_str_with("-0.005", 10, 3).unwrap();
+        assert_eq!(-5, d.value());
+        assert_eq!("-0.005", d.to_string());
+
+        let d = Decimal128::from_str_with("1.235", 10, 2).unwrap();
+        assert_eq!("1.24", d.to_string());
+        let d = Decimal128::from_str_with("-1.235", 10, 2).unwrap();
+        assert_eq!("-1.24", d.to_string());
+
+        let d = Decimal128::from_str_with("42", 5, 0).unwrap();
+        assert_eq!("42", d.to_string());
+        let d = Decimal128::from_str_with(".5", 5, 1).unwrap();
+        assert_eq!("0.5", d.to_string());
+
+        assert!(Decimal128::from_str_with("abc", 10, 2).is_err());
+        assert!(Decimal128::from_str_with("1.2.3", 10, 2).is_err());
+        assert!(Decimal128::from_str_with("-", 10, 2).is_err());
+        assert!(Decimal128::from_str_with("1000", 5, 2).is_err());
+        assert!(Decimal128::from_str_with("1", 39, 2).is_err());
+        assert!(Decimal128::from_str_with("1", 5, 6).is_err());
+    }
+
+    #[test]
+    fn test_rescale() {
+        let d = Decimal128::new(12345, 10, 2);
+        assert_eq!(1234500, d.rescale(10, 4).unwrap().value());
+        assert_eq!(1235, d.rescale(10, 1).unwrap().value());
+        assert_eq!(
+            -123,
+            Decimal128::new(-12345, 10, 2)
+                .rescale(10, 0)
+                .unwrap()
+                .value()
+        );
+        assert!(d.rescale(4, 2).is_err());
+        assert!(Decimal128::new(i128::MAX, 38, 0).rescale(38, 2).is_err());
+    }
+
+    #[test]
+    fn test_cmp_decimal() {
+        let a = Decimal128::new(150, 10, 2);
+        let b = Decimal128::new(15, 10, 1);
+        assert_eq!(a, b);
+        assert!(Decimal128::new(151, 10, 2) > b);
+        assert!(Decimal128::new(-2, 10, 0) < Decimal128::new(-15, 10, 1));
+        assert!(Decimal128::new(i128::MAX, 38, 0) > Decimal128::new(1, 38, 30));
+        assert!(Decimal128::new(i128::MIN + 1, 38, 0) < Decimal128::new(-1, 38, 30));
+
+        let mut set = std::collections::HashSet::new();
+        set.insert(a);
+        assert!(set.contains(&b));
+    }
+
+    #[test]
+    fn test_to_f64() {
+        assert_eq!(1.5, Decimal128::new(150, 10, 2).to_f64());
+        assert_eq!(-0.25, Decimal128::new(-25, 10, 2).to_f64());
+    }
+}
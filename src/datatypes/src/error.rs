@@ -99,6 +99,21 @@ pub enum Error {
 
     #[snafu(display("Duplicated metadata for {}", key))]
     DuplicateMeta { key: String, backtrace: Backtrace },
+
+    #[snafu(display("Invalid decimal, reason: {}", reason))]
+    InvalidDecimal {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid json, reason: {}", reason))]
+    InvalidJson {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid json path: {}", path))]
+    InvalidJsonPath { path: String, backtrace: Backtrace },
 }
 
 impl ErrorExt for Error {
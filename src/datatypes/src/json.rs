@@ -0,0 +1,602 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Binary encoded JSON.
+//!
+//! An encoded value starts with a tag byte, all integers are little endian:
+//! - `null`, `false` and `true` are just the tag.
+//! - Numbers are the tag followed by an 8 bytes `i64`, `u64` or `f64`.
+//! - Strings are the tag, an `u32` length and the UTF-8 bytes.
+//! - Arrays are the tag, an `u32` payload length, an `u32` element count and the elements.
+//! - Objects are the tag, an `u32` payload length, an `u32` entry count and the entries sorted
+//!   by key. Each entry is an `u32` key length, the key and the value.
+//!
+//! Containers record their payload length, so looking up a path skips the siblings without
+//! decoding them.
+
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+use common_base::bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value as JsonValue};
+use snafu::{ensure, OptionExt, ResultExt};
+
+use crate::error::{self, Error, Result};
+
+const TAG_NULL: u8 = 0;
+const TAG_FALSE: u8 = 1;
+const TAG_TRUE: u8 = 2;
+const TAG_INT: u8 = 3;
+const TAG_UINT: u8 = 4;
+const TAG_FLOAT: u8 = 5;
+const TAG_STRING: u8 = 6;
+const TAG_ARRAY: u8 = 7;
+const TAG_OBJECT: u8 = 8;
+
+/// Size of the tag, payload length and element count of a container.
+const CONTAINER_HEADER_SIZE: usize = 9;
+
+/// Owned binary encoded JSON document.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Json(Bytes);
+
+impl Json {
+    /// Encodes the json value.
+    pub fn from_value(value: &JsonValue) -> Self {
+        let mut buf = Vec::new();
+        encode(value, &mut buf);
+        Self(buf.into())
+    }
+
+    /// Creates from encoded bytes, returns error if the bytes is not a valid encoded document.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        ensure!(
+            value_len(bytes) == Some(bytes.len()) && JsonRef(bytes).decode().is_some(),
+            error::InvalidJsonSnafu {
+                reason: "malformed binary json",
+            }
+        );
+        Ok(Self(bytes.into()))
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn as_json_ref(&self) -> JsonRef<'_> {
+        JsonRef(&self.0)
+    }
+
+    pub fn to_value(&self) -> JsonValue {
+        self.as_json_ref().to_value()
+    }
+}
+
+impl Default for Json {
+    fn default() -> Self {
+        Self::from_value(&JsonValue::Null)
+    }
+}
+
+impl FromStr for Json {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value: JsonValue =
+            serde_json::from_str(s).context(error::DeserializeSnafu { json: s })?;
+        Ok(Self::from_value(&value))
+    }
+}
+
+impl Display for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_json_ref())
+    }
+}
+
+impl Debug for Json {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Json({})", self)
+    }
+}
+
+impl From<JsonValue> for Json {
+    fn from(value: JsonValue) -> Self {
+        Self::from_value(&value)
+    }
+}
+
+/// Reference to a binary encoded JSON document or a nested value inside it.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct JsonRef<'a>(&'a [u8]);
+
+impl<'a> JsonRef<'a> {
+    /// Wraps the encoded bytes without validation, accessors return `None` (or json null)
+    /// if the bytes is malformed.
+    pub fn from_bytes_unchecked(bytes: &'a [u8]) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+
+    pub fn to_owned_json(&self) -> Json {
+        Json(self.0.into())
+    }
+
+    /// Decodes into a json value, malformed bytes is decoded as json null.
+    pub fn to_value(&self) -> JsonValue {
+        self.decode().unwrap_or(JsonValue::Null)
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.tag() == Some(TAG_NULL)
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self.tag()? {
+            TAG_TRUE => Some(true),
+            TAG_FALSE => Some(false),
+            _ => None,
+        }
+    }
+
+    /// Returns the string value, other types are not converted.
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self.tag()? {
+            TAG_STRING => {
+                let len = read_u32(self.0, 1)? as usize;
+                std::str::from_utf8(self.0.get(5..5 + len)?).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the integer value, floats without fractional part are also converted.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self.tag()? {
+            TAG_INT => Some(i64::from_le_bytes(read_8(self.0)?)),
+            TAG_UINT => i64::try_from(u64::from_le_bytes(read_8(self.0)?)).ok(),
+            TAG_FLOAT => {
+                let v = f64::from_le_bytes(read_8(self.0)?);
+                (v.fract() == 0.0 && v >= i64::MIN as f64 && v < i64::MAX as f64)
+                    .then_some(v as i64)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<f64> {
+        match self.tag()? {
+            TAG_INT => Some(i64::from_le_bytes(read_8(self.0)?) as f64),
+            TAG_UINT => Some(u64::from_le_bytes(read_8(self.0)?) as f64),
+            TAG_FLOAT => Some(f64::from_le_bytes(read_8(self.0)?)),
+            _ => None,
+        }
+    }
+
+    /// Returns the value at `path`, or `None` if the path does not exist.
+    pub fn get(&self, path: &JsonPath) -> Option<JsonRef<'a>> {
+        path.0.iter().try_fold(*self, |value, item| match item {
+            PathItem::Key(key) => value.get_field(key),
+            PathItem::Index(index) => value.get_index(*index),
+        })
+    }
+
+    /// Returns the field of an object.
+    pub fn get_field(&self, key: &str) -> Option<JsonRef<'a>> {
+        if self.tag()? != TAG_OBJECT {
+            return None;
+        }
+        self.entries()?
+            .take_while(|(k, _)| *k <= key)
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Returns the element of an array.
+    pub fn get_index(&self, index: usize) -> Option<JsonRef<'a>> {
+        if self.tag()? != TAG_ARRAY {
+            return None;
+        }
+        self.elements()?.nth(index)
+    }
+
+    /// Returns whether `candidate` is contained in this value, following MySQL's
+    /// `JSON_CONTAINS`:
+    /// - An object contains another object if it has all the keys of the candidate and each
+    ///   value contains the value of the candidate.
+    /// - An array contains a candidate array if every element of the candidate is contained
+    ///   by some element, and contains a non array candidate if any element contains it.
+    /// - Otherwise the scalars must be equal, numbers are compared by value.
+    pub fn contains(&self, candidate: &JsonRef) -> bool {
+        match (self.tag(), candidate.tag()) {
+            (Some(TAG_OBJECT), Some(TAG_OBJECT)) => match candidate.entries() {
+                Some(mut entries) => entries.all(|(key, value)| {
+                    self.get_field(key)
+                        .map(|v| v.contains(&value))
+                        .unwrap_or(false)
+                }),
+                None => false,
+            },
+            (Some(TAG_ARRAY), Some(TAG_ARRAY)) => match candidate.elements() {
+                Some(mut elements) => elements.all(|c| self.contains(&c)),
+                None => false,
+            },
+            (Some(TAG_ARRAY), Some(_)) => match self.elements() {
+                Some(mut elements) => elements.any(|e| e.contains(candidate)),
+                None => false,
+            },
+            (Some(TAG_INT | TAG_UINT | TAG_FLOAT), Some(TAG_INT | TAG_UINT | TAG_FLOAT)) => {
+                self.as_f64() == candidate.as_f64()
+            }
+            (Some(TAG_STRING), Some(TAG_STRING)) => self.as_str() == candidate.as_str(),
+            (Some(t1), Some(t2)) => t1 == t2 && t1 <= TAG_TRUE,
+            _ => false,
+        }
+    }
+
+    fn tag(&self) -> Option<u8> {
+        self.0.first().copied()
+    }
+
+    fn elements(&self) -> Option<ElementIter<'a>> {
+        Some(ElementIter {
+            buf: self.0.get(CONTAINER_HEADER_SIZE..container_end(self.0)?)?,
+            remaining: read_u32(self.0, 5)?,
+        })
+    }
+
+    fn entries(&self) -> Option<EntryIter<'a>> {
+        Some(EntryIter {
+            buf: self.0.get(CONTAINER_HEADER_SIZE..container_end(self.0)?)?,
+            remaining: read_u32(self.0, 5)?,
+        })
+    }
+
+    fn decode(&self) -> Option<JsonValue> {
+        let value = match self.tag()? {
+            TAG_NULL => JsonValue::Null,
+            TAG_FALSE => JsonValue::Bool(false),
+            TAG_TRUE => JsonValue::Bool(true),
+            TAG_INT => JsonValue::from(i64::from_le_bytes(read_8(self.0)?)),
+            TAG_UINT => JsonValue::from(u64::from_le_bytes(read_8(self.0)?)),
+            TAG_FLOAT => Number::from_f64(f64::from_le_bytes(read_8(self.0)?))
+                .map(JsonValue::Number)
+                .unwrap_or(JsonValue::Null),
+            TAG_STRING => JsonValue::String(self.as_str()?.to_string()),
+            TAG_ARRAY => {
+                let mut elements = self.elements()?;
+                let values = elements
+                    .by_ref()
+                    .map(|e| e.decode())
+                    .collect::<Option<Vec<_>>>()?;
+                if elements.remaining > 0 {
+                    return None;
+                }
+                JsonValue::Array(values)
+            }
+            TAG_OBJECT => {
+                let mut entries = self.entries()?;
+                let map = entries
+                    .by_ref()
+                    .map(|(k, v)| v.decode().map(|v| (k.to_string(), v)))
+                    .collect::<Option<Map<_, _>>>()?;
+                if entries.remaining > 0 {
+                    return None;
+                }
+                JsonValue::Object(map)
+            }
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+impl<'a> Display for JsonRef<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_value())
+    }
+}
+
+impl<'a> Debug for JsonRef<'a> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "JsonRef({})", self)
+    }
+}
+
+struct ElementIter<'a> {
+    buf: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> Iterator for ElementIter<'a> {
+    type Item = JsonRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let len = value_len(self.buf)?;
+        let (value, rest) = self.buf.split_at(len);
+        self.buf = rest;
+        self.remaining -= 1;
+        Some(JsonRef(value))
+    }
+}
+
+struct EntryIter<'a> {
+    buf: &'a [u8],
+    remaining: u32,
+}
+
+impl<'a> Iterator for EntryIter<'a> {
+    type Item = (&'a str, JsonRef<'a>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let key_len = read_u32(self.buf, 0)? as usize;
+        let key = std::str::from_utf8(self.buf.get(4..4 + key_len)?).ok()?;
+        let rest = &self.buf[4 + key_len..];
+        let len = value_len(rest)?;
+        let (value, rest) = rest.split_at(len);
+        self.buf = rest;
+        self.remaining -= 1;
+        Some((key, JsonRef(value)))
+    }
+}
+
+fn read_u32(buf: &[u8], offset: usize) -> Option<u32> {
+    buf.get(offset..offset + 4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()))
+}
+
+fn read_8(buf: &[u8]) -> Option<[u8; 8]> {
+    buf.get(1..9).map(|b| b.try_into().unwrap())
+}
+
+fn container_end(buf: &[u8]) -> Option<usize> {
+    Some(5 + read_u32(buf, 1)? as usize)
+}
+
+/// Returns the length of the encoded value at the start of `buf`.
+fn value_len(buf: &[u8]) -> Option<usize> {
+    let len = match *buf.first()? {
+        TAG_NULL | TAG_FALSE | TAG_TRUE => 1,
+        TAG_INT | TAG_UINT | TAG_FLOAT => 9,
+        TAG_STRING | TAG_ARRAY | TAG_OBJECT => 5 + read_u32(buf, 1)? as usize,
+        _ => return None,
+    };
+    (len <= buf.len()).then_some(len)
+}
+
+fn encode(value: &JsonValue, buf: &mut Vec<u8>) {
+    match value {
+        JsonValue::Null => buf.push(TAG_NULL),
+        JsonValue::Bool(false) => buf.push(TAG_FALSE),
+        JsonValue::Bool(true) => buf.push(TAG_TRUE),
+        JsonValue::Number(n) => {
+            if let Some(v) = n.as_i64() {
+                buf.push(TAG_INT);
+                buf.extend_from_slice(&v.to_le_bytes());
+            } else if let Some(v) = n.as_u64() {
+                buf.push(TAG_UINT);
+                buf.extend_from_slice(&v.to_le_bytes());
+            } else {
+                buf.push(TAG_FLOAT);
+                buf.extend_from_slice(&n.as_f64().unwrap_or_default().to_le_bytes());
+            }
+        }
+        JsonValue::String(s) => {
+            buf.push(TAG_STRING);
+            buf.extend_from_slice(&(s.len() as u32).to_le_bytes());
+            buf.extend_from_slice(s.as_bytes());
+        }
+        JsonValue::Array(values) => encode_container(buf, TAG_ARRAY, values.len(), |buf| {
+            values.iter().for_each(|v| encode(v, buf))
+        }),
+        JsonValue::Object(map) => {
+            // The map may keep the insertion order, so sort the keys explicitly.
+            let mut entries = map.iter().collect::<Vec<_>>();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            encode_container(buf, TAG_OBJECT, entries.len(), |buf| {
+                for (k, v) in entries {
+                    buf.extend_from_slice(&(k.len() as u32).to_le_bytes());
+                    buf.extend_from_slice(k.as_bytes());
+                    encode(v, buf);
+                }
+            })
+        }
+    }
+}
+
+fn encode_container(buf: &mut Vec<u8>, tag: u8, count: usize, f: impl FnOnce(&mut Vec<u8>)) {
+    let start = buf.len();
+    buf.push(tag);
+    buf.extend_from_slice(&[0; 4]);
+    buf.extend_from_slice(&(count as u32).to_le_bytes());
+    f(buf);
+    let payload_len = (buf.len() - start - 5) as u32;
+    buf[start + 1..start + 5].copy_from_slice(&payload_len.to_le_bytes());
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum PathItem {
+    Key(String),
+    Index(usize),
+}
+
+/// Path to a value inside a JSON document, like `$.a.b[0]` or `$["a key"]`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsonPath(Vec<PathItem>);
+
+impl FromStr for JsonPath {
+    type Err = Error;
+
+    fn from_str(path: &str) -> Result<Self> {
+        let invalid = || error::InvalidJsonPathSnafu { path }.build();
+        let mut chars = path.trim().chars().peekable();
+        ensure!(
+            chars.next() == Some('$'),
+            error::InvalidJsonPathSnafu { path }
+        );
+
+        let mut items = Vec::new();
+        while let Some(c) = chars.next() {
+            match c {
+                '.' => {
+                    let key = if chars.peek() == Some(&'"') {
+                        chars.next();
+                        read_quoted(&mut chars, '"')
+                            .context(error::InvalidJsonPathSnafu { path })?
+                    } else {
+                        let mut key = String::new();
+                        while let Some(c) = chars.next_if(|c| !matches!(c, '.' | '[' | ']')) {
+                            key.push(c);
+                        }
+                        key
+                    };
+                    ensure!(!key.is_empty(), error::InvalidJsonPathSnafu { path });
+                    items.push(PathItem::Key(key));
+                }
+                '[' => {
+                    let item = match chars.peek() {
+                        Some(&quote) if quote == '"' || quote == '\'' => {
+                            chars.next();
+                            PathItem::Key(read_quoted(&mut chars, quote).ok_or_else(invalid)?)
+                        }
+                        _ => {
+                            let mut index = String::new();
+                            while let Some(c) = chars.next_if(|c| *c != ']') {
+                                index.push(c);
+                            }
+                            PathItem::Index(index.trim().parse().map_err(|_| invalid())?)
+                        }
+                    };
+                    ensure!(
+                        chars.next() == Some(']'),
+                        error::InvalidJsonPathSnafu { path }
+                    );
+                    items.push(item);
+                }
+                _ => return Err(invalid()),
+            }
+        }
+        Ok(Self(items))
+    }
+}
+
+/// Reads a quoted string until the closing `quote`, backslash escapes the next char.
+fn read_quoted(chars: &mut impl Iterator<Item = char>, quote: char) -> Option<String> {
+    let mut s = String::new();
+    loop {
+        match chars.next()? {
+            '\\' => s.push(chars.next()?),
+            c if c == quote => return Some(s),
+            c => s.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn path(s: &str) -> JsonPath {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_encode_decode() {
+        let value = json!({
+            "b": [1, -2, 18446744073709551615u64, 1.5, "x", null, true, false],
+            "a": {"nested": {"key": "value"}},
+            "": {},
+            "empty": [],
+        });
+        let json = Json::from_value(&value);
+        assert_eq!(value, json.to_value());
+        assert_eq!(json, Json::from_bytes(json.as_bytes()).unwrap());
+        assert_eq!(
+            r#"{"":{},"a":{"nested":{"key":"value"}},"b":[1,-2,18446744073709551615,1.5,"x",null,true,false],"empty":[]}"#,
+            json.to_string()
+        );
+
+        assert!(Json::from_bytes(&json.as_bytes()[..10]).is_err());
+        assert!(Json::from_bytes(&[42]).is_err());
+        assert!(Json::from_bytes(&[]).is_err());
+        assert!("{".parse::<Json>().is_err());
+        assert_eq!("null", Json::default().to_string());
+    }
+
+    #[test]
+    fn test_get_by_path() {
+        let json: Json = r#"{"a": {"b": [10, {"c": "hello"}], "x y": 2.0}, "d": true}"#
+            .parse()
+            .unwrap();
+        let json = json.as_json_ref();
+
+        assert_eq!(Some(10), json.get(&path("$.a.b[0]")).unwrap().as_i64());
+        assert_eq!(
+            Some("hello"),
+            json.get(&path("$.a.b[1].c")).unwrap().as_str()
+        );
+        assert_eq!(Some(2), json.get(&path("$.a[\"x y\"]")).unwrap().as_i64());
+        assert_eq!(Some(2.0), json.get(&path("$.a.\"x y\"")).unwrap().as_f64());
+        assert_eq!(Some(true), json.get(&path("$['d']")).unwrap().as_bool());
+        assert_eq!(json, json.get(&path("$")).unwrap());
+
+        assert!(json.get(&path("$.a.b[2]")).is_none());
+        assert!(json.get(&path("$.a.c")).is_none());
+        assert!(json.get(&path("$.d.e")).is_none());
+        assert!(json.get(&path("$.a.b[0]")).unwrap().as_str().is_none());
+        assert_eq!(None, json.get(&path("$.a.b[1].c")).unwrap().as_i64());
+    }
+
+    #[test]
+    fn test_parse_invalid_path() {
+        for p in ["", "a.b", "$.", "$.a[", "$[x]", "$.a]", "$[\"a]", "$a"] {
+            assert!(p.parse::<JsonPath>().is_err(), "path: {}", p);
+        }
+    }
+
+    #[test]
+    fn test_contains() {
+        let contains = |target: &str, candidate: &str| {
+            let target: Json = target.parse().unwrap();
+            let candidate: Json = candidate.parse().unwrap();
+            target.as_json_ref().contains(&candidate.as_json_ref())
+        };
+
+        assert!(contains(r#"{"a": 1, "b": {"c": [1, 2]}}"#, r#"{"a": 1.0}"#));
+        assert!(contains(
+            r#"{"a": 1, "b": {"c": [1, 2]}}"#,
+            r#"{"b": {"c": 2}}"#
+        ));
+        assert!(!contains(r#"{"a": 1, "b": {"c": [1, 2]}}"#, r#"{"a": 2}"#));
+        assert!(!contains(r#"{"a": 1}"#, r#"{"b": 1}"#));
+        assert!(contains(r#"[1, "x", [true]]"#, r#"["x", 1]"#));
+        assert!(contains(r#"[1, "x", [true]]"#, "true"));
+        assert!(!contains(r#"[1, "x"]"#, r#"[1, "y"]"#));
+        assert!(contains("null", "null"));
+        assert!(!contains("false", "true"));
+        assert!(!contains(r#""1""#, "1"));
+    }
+}
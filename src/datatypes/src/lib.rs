@@ -17,7 +17,9 @@
 
 pub mod arrow_array;
 pub mod data_type;
+pub mod decimal;
 pub mod error;
+pub mod json;
 pub mod macros;
 pub mod prelude;
 mod scalars;
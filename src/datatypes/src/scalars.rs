@@ -16,6 +16,8 @@ use std::any::Any;
 
 use common_time::{Date, DateTime, Timestamp};
 
+use crate::decimal::Decimal128;
+use crate::json::{Json, JsonRef};
 use crate::prelude::*;
 use crate::value::{ListValue, ListValueRef};
 use crate::vectors::*;
@@ -321,6 +323,50 @@ impl<'a> ScalarRef<'a> for Timestamp {
     }
 }
 
+impl Scalar for Decimal128 {
+    type VectorType = Decimal128Vector;
+    type RefType<'a> = Decimal128;
+
+    fn as_scalar_ref(&self) -> Self::RefType<'_> {
+        *self
+    }
+
+    fn upcast_gat<'short, 'long: 'short>(long: Self::RefType<'long>) -> Self::RefType<'short> {
+        long
+    }
+}
+
+impl<'a> ScalarRef<'a> for Decimal128 {
+    type VectorType = Decimal128Vector;
+    type ScalarType = Decimal128;
+
+    fn to_owned_scalar(&self) -> Self::ScalarType {
+        *self
+    }
+}
+
+impl Scalar for Json {
+    type VectorType = JsonVector;
+    type RefType<'a> = JsonRef<'a>;
+
+    fn as_scalar_ref(&self) -> Self::RefType<'_> {
+        self.as_json_ref()
+    }
+
+    fn upcast_gat<'short, 'long: 'short>(long: Self::RefType<'long>) -> Self::RefType<'short> {
+        long
+    }
+}
+
+impl<'a> ScalarRef<'a> for JsonRef<'a> {
+    type VectorType = JsonVector;
+    type ScalarType = Json;
+
+    fn to_owned_scalar(&self) -> Self::ScalarType {
+        self.to_owned_json()
+    }
+}
+
 impl Scalar for ListValue {
     type VectorType = ListVector;
     type RefType<'a> = ListValueRef<'a>;
@@ -44,6 +44,12 @@ pub enum LogicalTypeId {
 
     Timestamp,
 
+    /// Fixed-point decimal with up to 38 digits.
+    Decimal128,
+
+    /// Binary encoded JSON.
+    Json,
+
     List,
 }
 
@@ -75,6 +81,8 @@ impl LogicalTypeId {
             LogicalTypeId::Date => ConcreteDataType::date_datatype(),
             LogicalTypeId::DateTime => ConcreteDataType::datetime_datatype(),
             LogicalTypeId::Timestamp => ConcreteDataType::timestamp_millis_datatype(), // to timestamp type with default time unit
+            LogicalTypeId::Decimal128 => ConcreteDataType::decimal128_datatype(38, 10),
+            LogicalTypeId::Json => ConcreteDataType::json_datatype(),
             LogicalTypeId::List => {
                 ConcreteDataType::list_datatype(ConcreteDataType::null_datatype())
             }
@@ -16,6 +16,8 @@ mod binary_type;
 mod boolean_type;
 mod date;
 mod datetime;
+mod decimal_type;
+mod json_type;
 mod list_type;
 mod null_type;
 mod primitive_traits;
@@ -27,6 +29,8 @@ pub use binary_type::BinaryType;
 pub use boolean_type::BooleanType;
 pub use date::DateType;
 pub use datetime::DateTimeType;
+pub use decimal_type::Decimal128Type;
+pub use json_type::{JsonType, JSON_EXTENSION_NAME};
 pub use list_type::ListType;
 pub use null_type::NullType;
 pub use primitive_traits::{OrdPrimitive, Primitive};
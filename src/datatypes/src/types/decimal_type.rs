@@ -0,0 +1,107 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use arrow::datatypes::DataType as ArrowDataType;
+use serde::{Deserialize, Serialize};
+
+use crate::data_type::DataType;
+use crate::decimal::{self, Decimal128, DECIMAL128_DEFAULT_PRECISION, DECIMAL128_DEFAULT_SCALE};
+use crate::error::Result;
+use crate::prelude::{LogicalTypeId, MutableVector, Value};
+use crate::vectors::Decimal128VectorBuilder;
+
+/// Fixed-point decimal type with at most 38 digits, `scale` of them after the decimal point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Decimal128Type {
+    pub precision: u8,
+    pub scale: u8,
+}
+
+impl Decimal128Type {
+    pub fn new(precision: u8, scale: u8) -> Self {
+        Self { precision, scale }
+    }
+
+    /// Creates a decimal type, returns error if `precision` or `scale` is out of range.
+    pub fn try_new(precision: u8, scale: u8) -> Result<Self> {
+        decimal::validate_precision_and_scale(precision, scale)?;
+        Ok(Self::new(precision, scale))
+    }
+}
+
+impl Default for Decimal128Type {
+    fn default() -> Self {
+        Self::new(DECIMAL128_DEFAULT_PRECISION, DECIMAL128_DEFAULT_SCALE)
+    }
+}
+
+impl DataType for Decimal128Type {
+    fn name(&self) -> &str {
+        "Decimal128"
+    }
+
+    fn logical_type_id(&self) -> LogicalTypeId {
+        LogicalTypeId::Decimal128
+    }
+
+    fn default_value(&self) -> Value {
+        Value::Decimal128(Decimal128::new(0, self.precision, self.scale))
+    }
+
+    fn as_arrow_type(&self) -> ArrowDataType {
+        ArrowDataType::Decimal(self.precision as usize, self.scale as usize)
+    }
+
+    fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
+        Box::new(Decimal128VectorBuilder::with_capacity_and_type(
+            capacity, *self,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::{ConcreteDataType, ValueRef};
+
+    #[test]
+    fn test_decimal_type() {
+        let t = Decimal128Type::new(10, 2);
+        assert_eq!(LogicalTypeId::Decimal128, t.logical_type_id());
+        assert_eq!(ArrowDataType::Decimal(10, 2), t.as_arrow_type());
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(0, 10, 2)),
+            t.default_value()
+        );
+
+        assert!(Decimal128Type::try_new(38, 38).is_ok());
+        assert!(Decimal128Type::try_new(0, 0).is_err());
+        assert!(Decimal128Type::try_new(39, 0).is_err());
+        assert!(Decimal128Type::try_new(5, 6).is_err());
+    }
+
+    #[test]
+    fn test_create_mutable_vector() {
+        let mut builder = Decimal128Type::new(10, 2).create_mutable_vector(3);
+        builder
+            .push_value_ref(ValueRef::Decimal128(Decimal128::new(15, 10, 1)))
+            .unwrap();
+        builder.push_value_ref(ValueRef::Null).unwrap();
+        let v = builder.to_vector();
+        assert_eq!(ConcreteDataType::decimal128_datatype(10, 2), v.data_type());
+        // Value is converted to the scale of the vector.
+        assert_eq!(Value::Decimal128(Decimal128::new(150, 10, 2)), v.get(0));
+        assert_eq!(Value::Null, v.get(1));
+    }
+}
@@ -17,7 +17,7 @@ use common_time::timestamp::{TimeUnit, Timestamp};
 use serde::{Deserialize, Serialize};
 
 use crate::data_type::DataType;
-use crate::prelude::{LogicalTypeId, MutableVector, ScalarVectorBuilder, Value};
+use crate::prelude::{LogicalTypeId, MutableVector, Value};
 use crate::vectors::TimestampVectorBuilder;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -54,7 +54,9 @@ impl DataType for TimestampType {
     }
 
     fn create_mutable_vector(&self, capacity: usize) -> Box<dyn MutableVector> {
-        Box::new(TimestampVectorBuilder::with_capacity(capacity))
+        Box::new(TimestampVectorBuilder::with_capacity_and_unit(
+            capacity, self.unit,
+        ))
     }
 }
 
@@ -116,10 +118,19 @@ mod tests {
             .push_value_ref(ValueRef::Timestamp(Timestamp::new(96, TimeUnit::Second)))
             .unwrap();
         let v = builder.to_vector();
-        assert_eq!(ConcreteDataType::timestamp_millis_datatype(), v.data_type());
-        assert_eq!(Value::Timestamp(Timestamp::from_millis(42)), v.get(0));
+        assert_eq!(
+            ConcreteDataType::timestamp_datatype(TimeUnit::Microsecond),
+            v.data_type()
+        );
+        assert_eq!(
+            Value::Timestamp(Timestamp::new(42_000, TimeUnit::Microsecond)),
+            v.get(0)
+        );
         assert_eq!(Value::Null, v.get(1));
-        // Push a timestamp with different unit will convert the value to value with time unit millisecond.
-        assert_eq!(Value::Timestamp(Timestamp::from_millis(96_000)), v.get(2));
+        // Push a timestamp with different unit will convert the value to the unit of the vector.
+        assert_eq!(
+            Value::Timestamp(Timestamp::new(96_000_000, TimeUnit::Microsecond)),
+            v.get(2)
+        );
     }
 }
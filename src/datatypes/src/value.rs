@@ -23,7 +23,9 @@ use datafusion_common::ScalarValue;
 pub use ordered_float::OrderedFloat;
 use serde::{Deserialize, Serialize};
 
+use crate::decimal::Decimal128;
 use crate::error::{self, Result};
+use crate::json::{Json, JsonRef};
 use crate::prelude::*;
 use crate::type_id::LogicalTypeId;
 use crate::vectors::ListVector;
@@ -60,6 +62,10 @@ pub enum Value {
     DateTime(DateTime),
     Timestamp(Timestamp),
 
+    Decimal128(Decimal128),
+
+    Json(Json),
+
     List(ListValue),
 }
 
@@ -90,6 +96,8 @@ impl Display for Value {
             Value::Date(v) => write!(f, "{}", v),
             Value::DateTime(v) => write!(f, "{}", v),
             Value::Timestamp(v) => write!(f, "{}", v.to_iso8601_string()),
+            Value::Decimal128(v) => write!(f, "{}", v),
+            Value::Json(v) => write!(f, "{}", v),
             Value::List(v) => {
                 let default = Box::new(vec![]);
                 let items = v.items().as_ref().unwrap_or(&default);
@@ -129,6 +137,8 @@ impl Value {
             Value::Date(_) => ConcreteDataType::date_datatype(),
             Value::DateTime(_) => ConcreteDataType::datetime_datatype(),
             Value::Timestamp(v) => ConcreteDataType::timestamp_datatype(v.unit()),
+            Value::Decimal128(v) => ConcreteDataType::decimal128_datatype(v.precision(), v.scale()),
+            Value::Json(_) => ConcreteDataType::json_datatype(),
         }
     }
 
@@ -170,6 +180,8 @@ impl Value {
             Value::DateTime(v) => ValueRef::DateTime(*v),
             Value::List(v) => ValueRef::List(ListValueRef::Ref { val: v }),
             Value::Timestamp(v) => ValueRef::Timestamp(*v),
+            Value::Decimal128(v) => ValueRef::Decimal128(*v),
+            Value::Json(v) => ValueRef::Json(v.as_json_ref()),
         }
     }
 
@@ -194,6 +206,8 @@ impl Value {
             Value::Date(_) => LogicalTypeId::Date,
             Value::DateTime(_) => LogicalTypeId::DateTime,
             Value::Timestamp(_) => LogicalTypeId::Timestamp,
+            Value::Decimal128(_) => LogicalTypeId::Decimal128,
+            Value::Json(_) => LogicalTypeId::Json,
         }
     }
 }
@@ -223,6 +237,8 @@ macro_rules! impl_ord_for_value_like {
                 ($Type::Date(v1), $Type::Date(v2)) => v1.cmp(v2),
                 ($Type::DateTime(v1), $Type::DateTime(v2)) => v1.cmp(v2),
                 ($Type::Timestamp(v1), $Type::Timestamp(v2)) => v1.cmp(v2),
+                ($Type::Decimal128(v1), $Type::Decimal128(v2)) => v1.cmp(v2),
+                ($Type::Json(v1), $Type::Json(v2)) => v1.cmp(v2),
                 ($Type::List(v1), $Type::List(v2)) => v1.cmp(v2),
                 _ => panic!(
                     "Cannot compare different values {:?} and {:?}",
@@ -302,6 +318,18 @@ impl From<Timestamp> for Value {
     }
 }
 
+impl From<Decimal128> for Value {
+    fn from(v: Decimal128) -> Self {
+        Value::Decimal128(v)
+    }
+}
+
+impl From<Json> for Value {
+    fn from(v: Json) -> Self {
+        Value::Json(v)
+    }
+}
+
 impl From<&[u8]> for Value {
     fn from(bytes: &[u8]) -> Value {
         Value::Binary(bytes.into())
@@ -331,6 +359,9 @@ impl TryFrom<Value> for serde_json::Value {
             Value::DateTime(v) => serde_json::Value::Number(v.val().into()),
             Value::List(v) => serde_json::to_value(v)?,
             Value::Timestamp(v) => serde_json::to_value(v.value())?,
+            // Serialize as string to keep the exact value.
+            Value::Decimal128(v) => serde_json::Value::String(v.to_string()),
+            Value::Json(v) => v.to_value(),
         };
 
         Ok(json_value)
@@ -386,6 +417,48 @@ impl Ord for ListValue {
     }
 }
 
+impl Value {
+    /// Converts this value into a [ScalarValue], returns error if the value is null
+    /// or a list since the type of the scalar value can't be inferred.
+    pub fn try_to_scalar_value(&self) -> Result<ScalarValue> {
+        let scalar = match self {
+            Value::Boolean(v) => ScalarValue::Boolean(Some(*v)),
+            Value::UInt8(v) => ScalarValue::UInt8(Some(*v)),
+            Value::UInt16(v) => ScalarValue::UInt16(Some(*v)),
+            Value::UInt32(v) => ScalarValue::UInt32(Some(*v)),
+            Value::UInt64(v) => ScalarValue::UInt64(Some(*v)),
+            Value::Int8(v) => ScalarValue::Int8(Some(*v)),
+            Value::Int16(v) => ScalarValue::Int16(Some(*v)),
+            Value::Int32(v) => ScalarValue::Int32(Some(*v)),
+            Value::Int64(v) => ScalarValue::Int64(Some(*v)),
+            Value::Float32(v) => ScalarValue::Float32(Some(v.0)),
+            Value::Float64(v) => ScalarValue::Float64(Some(v.0)),
+            Value::String(v) => ScalarValue::Utf8(Some(v.as_utf8().to_string())),
+            Value::Binary(v) => ScalarValue::LargeBinary(Some(v.to_vec())),
+            Value::Date(v) => ScalarValue::Date32(Some(v.val())),
+            Value::DateTime(v) => ScalarValue::Date64(Some(v.val())),
+            Value::Timestamp(v) => match v.unit() {
+                TimeUnit::Second => ScalarValue::TimestampSecond(Some(v.value()), None),
+                TimeUnit::Millisecond => ScalarValue::TimestampMillisecond(Some(v.value()), None),
+                TimeUnit::Microsecond => ScalarValue::TimestampMicrosecond(Some(v.value()), None),
+                TimeUnit::Nanosecond => ScalarValue::TimestampNanosecond(Some(v.value()), None),
+            },
+            Value::Decimal128(v) => {
+                ScalarValue::Decimal128(Some(v.value()), v.precision() as usize, v.scale() as usize)
+            }
+            Value::Json(v) => ScalarValue::LargeBinary(Some(v.as_bytes().to_vec())),
+            Value::Null | Value::List(_) => {
+                return error::ConversionSnafu {
+                    from: format!("Value::{:?}", self.data_type()),
+                }
+                .fail();
+            }
+        };
+
+        Ok(scalar)
+    }
+}
+
 impl TryFrom<ScalarValue> for Value {
     type Error = error::Error;
 
@@ -435,6 +508,9 @@ impl TryFrom<ScalarValue> for Value {
             ScalarValue::TimestampNanosecond(t, _) => t
                 .map(|x| Value::Timestamp(Timestamp::new(x, TimeUnit::Nanosecond)))
                 .unwrap_or(Value::Null),
+            ScalarValue::Decimal128(d, precision, scale) => d
+                .map(|x| Value::Decimal128(Decimal128::new(x, precision as u8, scale as u8)))
+                .unwrap_or(Value::Null),
             _ => {
                 return error::UnsupportedArrowTypeSnafu {
                     arrow_type: v.get_datatype(),
@@ -472,6 +548,8 @@ pub enum ValueRef<'a> {
     Date(Date),
     DateTime(DateTime),
     Timestamp(Timestamp),
+    Decimal128(Decimal128),
+    Json(JsonRef<'a>),
     List(ListValueRef<'a>),
 }
 
@@ -527,6 +605,16 @@ impl<'a> ValueRef<'a> {
         impl_as_for_value_ref!(self, Timestamp)
     }
 
+    /// Cast itself to [Decimal128].
+    pub fn as_decimal128(&self) -> Result<Option<Decimal128>> {
+        impl_as_for_value_ref!(self, Decimal128)
+    }
+
+    /// Cast itself to [JsonRef].
+    pub fn as_json(&self) -> Result<Option<JsonRef>> {
+        impl_as_for_value_ref!(self, Json)
+    }
+
     /// Cast itself to [ListValueRef].
     pub fn as_list(&self) -> Result<Option<ListValueRef>> {
         impl_as_for_value_ref!(self, List)
@@ -864,11 +952,19 @@ mod tests {
                 .unwrap()
         );
 
-        let result: Result<Value> = ScalarValue::Decimal128(Some(1), 0, 0).try_into();
-        result
-            .unwrap_err()
-            .to_string()
-            .contains("Unsupported arrow data type, type: Decimal(0, 0)");
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 2)),
+            ScalarValue::Decimal128(Some(12345), 10, 2)
+                .try_into()
+                .unwrap()
+        );
+        assert_eq!(
+            Value::Null,
+            ScalarValue::Decimal128(None, 10, 2).try_into().unwrap()
+        );
+
+        let result: Result<Value> = ScalarValue::IntervalYearMonth(Some(1)).try_into();
+        assert!(result.is_err());
     }
 
     #[test]
@@ -992,6 +1088,14 @@ mod tests {
             &ConcreteDataType::timestamp_millis_datatype(),
             &Value::Timestamp(Timestamp::from_millis(1)),
         );
+        check_type_and_value(
+            &ConcreteDataType::decimal128_datatype(10, 2),
+            &Value::Decimal128(Decimal128::new(12345, 10, 2)),
+        );
+        check_type_and_value(
+            &ConcreteDataType::json_datatype(),
+            &Value::Json(Json::default()),
+        );
     }
 
     #[test]
@@ -1087,6 +1191,14 @@ mod tests {
             serde_json::Value::Number(1.into()),
             to_json(Value::Timestamp(Timestamp::from_millis(1)))
         );
+        assert_eq!(
+            serde_json::Value::String("-123.45".to_string()),
+            to_json(Value::Decimal128(Decimal128::new(-12345, 10, 2)))
+        );
+        assert_eq!(
+            serde_json::json!({"a": [1, "b"]}),
+            to_json(Value::Json(r#"{"a": [1, "b"]}"#.parse().unwrap()))
+        );
 
         let json_value: serde_json::Value =
             serde_json::from_str(r#"{"items":[{"Int32":123}],"datatype":{"Int32":{}}}"#).unwrap();
@@ -1290,6 +1402,14 @@ mod tests {
             Value::Timestamp(Timestamp::new(1000, TimeUnit::Millisecond)).to_string(),
             "1970-01-01 00:00:01+0000"
         );
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(5, 10, 3)).to_string(),
+            "0.005"
+        );
+        assert_eq!(
+            Value::Json(r#"{"b": 1, "a": null}"#.parse().unwrap()).to_string(),
+            r#"{"a":null,"b":1}"#
+        );
         assert_eq!(
             Value::List(ListValue::new(
                 Some(Box::new(vec![Value::Int8(1), Value::Int8(2)])),
@@ -1307,4 +1427,23 @@ mod tests {
             "Timestamp[]"
         );
     }
+
+    #[test]
+    fn test_try_to_scalar_value() {
+        assert_eq!(
+            ScalarValue::Int64(Some(10)),
+            Value::Int64(10).try_to_scalar_value().unwrap()
+        );
+        assert_eq!(
+            ScalarValue::Utf8(Some("hello".to_string())),
+            Value::from("hello").try_to_scalar_value().unwrap()
+        );
+        assert_eq!(
+            ScalarValue::TimestampMillisecond(Some(1000), None),
+            Value::Timestamp(Timestamp::new(1000, TimeUnit::Millisecond))
+                .try_to_scalar_value()
+                .unwrap()
+        );
+        assert!(Value::Null.try_to_scalar_value().is_err());
+    }
 }
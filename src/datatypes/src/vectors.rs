@@ -18,8 +18,10 @@ mod builder;
 pub mod constant;
 pub mod date;
 pub mod datetime;
+mod decimal;
 mod eq;
 mod helper;
+mod json;
 mod list;
 pub mod mutable;
 pub mod null;
@@ -40,7 +42,9 @@ pub use builder::VectorBuilder;
 pub use constant::*;
 pub use date::*;
 pub use datetime::*;
+pub use decimal::*;
 pub use helper::Helper;
+pub use json::*;
 pub use list::*;
 pub use mutable::MutableVector;
 pub use null::*;
@@ -26,10 +26,11 @@ use crate::value::Value;
 use crate::vectors::date::DateVectorBuilder;
 use crate::vectors::datetime::DateTimeVectorBuilder;
 use crate::vectors::{
-    BinaryVectorBuilder, BooleanVectorBuilder, Float32VectorBuilder, Float64VectorBuilder,
-    Int16VectorBuilder, Int32VectorBuilder, Int64VectorBuilder, Int8VectorBuilder, MutableVector,
-    NullVector, StringVectorBuilder, TimestampVectorBuilder, UInt16VectorBuilder,
-    UInt32VectorBuilder, UInt64VectorBuilder, UInt8VectorBuilder, VectorRef,
+    BinaryVectorBuilder, BooleanVectorBuilder, Decimal128VectorBuilder, Float32VectorBuilder,
+    Float64VectorBuilder, Int16VectorBuilder, Int32VectorBuilder, Int64VectorBuilder,
+    Int8VectorBuilder, JsonVectorBuilder, MutableVector, NullVector, StringVectorBuilder,
+    TimestampVectorBuilder, UInt16VectorBuilder, UInt32VectorBuilder, UInt64VectorBuilder,
+    UInt8VectorBuilder, VectorRef,
 };
 
 pub enum VectorBuilder {
@@ -55,6 +56,10 @@ pub enum VectorBuilder {
     Date(DateVectorBuilder),
     DateTime(DateTimeVectorBuilder),
     Timestamp(TimestampVectorBuilder),
+
+    Decimal128(Decimal128VectorBuilder),
+
+    Json(JsonVectorBuilder),
 }
 
 impl VectorBuilder {
@@ -110,8 +115,14 @@ impl VectorBuilder {
             ConcreteDataType::DateTime(_) => {
                 VectorBuilder::DateTime(DateTimeVectorBuilder::with_capacity(capacity))
             }
-            ConcreteDataType::Timestamp(_) => {
-                VectorBuilder::Timestamp(TimestampVectorBuilder::with_capacity(capacity))
+            ConcreteDataType::Timestamp(t) => VectorBuilder::Timestamp(
+                TimestampVectorBuilder::with_capacity_and_unit(capacity, t.unit),
+            ),
+            ConcreteDataType::Decimal128(t) => VectorBuilder::Decimal128(
+                Decimal128VectorBuilder::with_capacity_and_type(capacity, t),
+            ),
+            ConcreteDataType::Json(_) => {
+                VectorBuilder::Json(JsonVectorBuilder::with_capacity(capacity))
             }
             _ => unimplemented!(),
         }
@@ -136,6 +147,8 @@ impl VectorBuilder {
             VectorBuilder::Date(b) => b.data_type(),
             VectorBuilder::DateTime(b) => b.data_type(),
             VectorBuilder::Timestamp(b) => b.data_type(),
+            VectorBuilder::Decimal128(b) => b.data_type(),
+            VectorBuilder::Json(b) => b.data_type(),
         }
     }
 
@@ -165,8 +178,11 @@ impl VectorBuilder {
             (VectorBuilder::DateTime(b), Value::Int64(v)) => b.push(Some(DateTime::new(*v))),
             (VectorBuilder::Timestamp(b), Value::Timestamp(t)) => b.push(Some(*t)),
             (VectorBuilder::Timestamp(b), Value::Int64(v)) => {
-                b.push(Some(Timestamp::from_millis(*v)))
+                let unit = b.unit();
+                b.push(Some(Timestamp::new(*v, unit)))
             }
+            (VectorBuilder::Decimal128(b), Value::Decimal128(v)) => b.push(Some(*v)),
+            (VectorBuilder::Json(b), Value::Json(v)) => b.push(Some(v.as_json_ref())),
 
             _ => panic!(
                 "Value {:?} does not match builder type {:?}",
@@ -204,6 +220,8 @@ impl VectorBuilder {
             VectorBuilder::Date(b) => b.push_value_ref(value),
             VectorBuilder::DateTime(b) => b.push_value_ref(value),
             VectorBuilder::Timestamp(b) => b.push_value_ref(value),
+            VectorBuilder::Decimal128(b) => b.push_value_ref(value),
+            VectorBuilder::Json(b) => b.push_value_ref(value),
         }
     }
 
@@ -226,6 +244,8 @@ impl VectorBuilder {
             VectorBuilder::Date(b) => b.push(None),
             VectorBuilder::DateTime(b) => b.push(None),
             VectorBuilder::Timestamp(b) => b.push(None),
+            VectorBuilder::Decimal128(b) => b.push(None),
+            VectorBuilder::Json(b) => b.push(None),
         }
     }
 
@@ -248,6 +268,8 @@ impl VectorBuilder {
             VectorBuilder::Date(b) => Arc::new(b.finish()),
             VectorBuilder::DateTime(b) => Arc::new(b.finish()),
             VectorBuilder::Timestamp(b) => Arc::new(b.finish()),
+            VectorBuilder::Decimal128(b) => Arc::new(b.finish()),
+            VectorBuilder::Json(b) => Arc::new(b.finish()),
         }
     }
 }
@@ -0,0 +1,428 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::slice::Iter;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, MutableArray, MutablePrimitiveArray, PrimitiveArray};
+use arrow::bitmap::utils::ZipValidity;
+use arrow::datatypes::DataType as ArrowDataType;
+use snafu::OptionExt;
+
+use crate::data_type::{ConcreteDataType, DataType};
+use crate::decimal::Decimal128;
+use crate::error::{self, Result};
+use crate::prelude::{
+    MutableVector, ScalarVector, ScalarVectorBuilder, Validity, Value, ValueRef, Vector, VectorRef,
+};
+use crate::serialize::Serializable;
+use crate::types::Decimal128Type;
+use crate::vectors;
+
+/// `Decimal128Vector` stores unscaled decimal values, all values in the vector share the
+/// precision and scale of the vector.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Decimal128Vector {
+    array: PrimitiveArray<i128>,
+    data_type: Decimal128Type,
+}
+
+impl Decimal128Vector {
+    /// Creates a vector from arrow array, the precision and scale are inferred from the array's
+    /// data type and fall back to the default decimal type if the array is not a decimal array.
+    pub fn new(array: PrimitiveArray<i128>) -> Self {
+        let data_type = match array.data_type() {
+            ArrowDataType::Decimal(precision, scale) => {
+                Decimal128Type::new(*precision as u8, *scale as u8)
+            }
+            _ => Decimal128Type::default(),
+        };
+        Self::new_with_type(array, data_type)
+    }
+
+    pub fn new_with_type(array: PrimitiveArray<i128>, data_type: Decimal128Type) -> Self {
+        let array = array.to(data_type.as_arrow_type());
+        Self { array, data_type }
+    }
+
+    pub fn try_from_arrow_array(array: impl AsRef<dyn Array>) -> Result<Self> {
+        Ok(Self::new(
+            array
+                .as_ref()
+                .as_any()
+                .downcast_ref::<PrimitiveArray<i128>>()
+                .with_context(|| error::ConversionSnafu {
+                    from: format!("{:?}", array.as_ref().data_type()),
+                })?
+                .clone(),
+        ))
+    }
+
+    /// Creates a vector of given `precision` and `scale` from unscaled values.
+    pub fn from_values_with_type<I: IntoIterator<Item = i128>>(
+        iter: I,
+        precision: u8,
+        scale: u8,
+    ) -> Self {
+        Self::new_with_type(
+            PrimitiveArray::from_values(iter),
+            Decimal128Type::new(precision, scale),
+        )
+    }
+
+    pub fn precision(&self) -> u8 {
+        self.data_type.precision
+    }
+
+    pub fn scale(&self) -> u8 {
+        self.data_type.scale
+    }
+
+    pub(crate) fn as_arrow(&self) -> &dyn Array {
+        &self.array
+    }
+
+    fn decimal(&self, value: i128) -> Decimal128 {
+        Decimal128::new(value, self.data_type.precision, self.data_type.scale)
+    }
+}
+
+impl Vector for Decimal128Vector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::Decimal128(self.data_type)
+    }
+
+    fn vector_type_name(&self) -> String {
+        "Decimal128Vector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn to_arrow_array(&self) -> ArrayRef {
+        Arc::new(self.array.clone())
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        Box::new(self.array.clone())
+    }
+
+    fn validity(&self) -> Validity {
+        vectors::impl_validity_for_vector!(self.array)
+    }
+
+    fn memory_size(&self) -> usize {
+        self.array.values().len() * std::mem::size_of::<i128>()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.array.is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        Arc::new(Self {
+            array: self.array.slice(offset, length),
+            data_type: self.data_type,
+        })
+    }
+
+    fn get(&self, index: usize) -> Value {
+        match self.get_data(index) {
+            Some(v) => Value::Decimal128(v),
+            None => Value::Null,
+        }
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        match self.get_data(index) {
+            Some(v) => ValueRef::Decimal128(v),
+            None => ValueRef::Null,
+        }
+    }
+}
+
+impl Serializable for Decimal128Vector {
+    fn serialize_to_json(&self) -> Result<Vec<serde_json::Value>> {
+        // Serialize as string to keep the exact value.
+        Ok(self
+            .iter_data()
+            .map(|v| match v {
+                None => serde_json::Value::Null,
+                Some(v) => serde_json::Value::String(v.to_string()),
+            })
+            .collect::<Vec<_>>())
+    }
+}
+
+impl ScalarVector for Decimal128Vector {
+    type OwnedItem = Decimal128;
+    type RefItem<'a> = Decimal128;
+    type Iter<'a> = Decimal128Iter<'a>;
+    type Builder = Decimal128VectorBuilder;
+
+    fn get_data(&self, idx: usize) -> Option<Self::RefItem<'_>> {
+        if self.array.is_valid(idx) {
+            Some(self.decimal(self.array.value(idx)))
+        } else {
+            None
+        }
+    }
+
+    fn iter_data(&self) -> Self::Iter<'_> {
+        Decimal128Iter {
+            iter: self.array.iter(),
+            data_type: self.data_type,
+        }
+    }
+}
+
+pub struct Decimal128Iter<'a> {
+    iter: ZipValidity<'a, &'a i128, Iter<'a, i128>>,
+    data_type: Decimal128Type,
+}
+
+impl<'a> Iterator for Decimal128Iter<'a> {
+    type Item = Option<Decimal128>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let data_type = self.data_type;
+        self.iter
+            .next()
+            .map(|v| v.map(|v| Decimal128::new(*v, data_type.precision, data_type.scale)))
+    }
+}
+
+pub struct Decimal128VectorBuilder {
+    mutable_array: MutablePrimitiveArray<i128>,
+    data_type: Decimal128Type,
+}
+
+impl Decimal128VectorBuilder {
+    pub fn with_capacity_and_type(capacity: usize, data_type: Decimal128Type) -> Self {
+        Self {
+            mutable_array: MutablePrimitiveArray::with_capacity_from(
+                capacity,
+                data_type.as_arrow_type(),
+            ),
+            data_type,
+        }
+    }
+
+    /// Converts the decimal to the precision and scale of the builder.
+    fn convert(&self, value: Decimal128) -> Result<i128> {
+        if value.precision() == self.data_type.precision && value.scale() == self.data_type.scale {
+            return Ok(value.value());
+        }
+        value
+            .rescale(self.data_type.precision, self.data_type.scale)
+            .map(|v| v.value())
+    }
+}
+
+impl MutableVector for Decimal128VectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::Decimal128(self.data_type)
+    }
+
+    fn len(&self) -> usize {
+        self.mutable_array.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        let value = value
+            .as_decimal128()?
+            .map(|v| self.convert(v))
+            .transpose()?;
+        self.mutable_array.push(value);
+        Ok(())
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        let concrete_vector = vector
+            .as_any()
+            .downcast_ref::<Decimal128Vector>()
+            .with_context(|| error::CastTypeSnafu {
+                msg: format!(
+                    "Failed to convert vector from {} to Decimal128Vector",
+                    vector.vector_type_name()
+                ),
+            })?;
+
+        if concrete_vector.data_type == self.data_type {
+            let slice = concrete_vector.array.slice(offset, length);
+            self.mutable_array.extend_trusted_len(slice.iter());
+        } else {
+            for v in concrete_vector.iter_data().skip(offset).take(length) {
+                let v = v.map(|v| self.convert(v)).transpose()?;
+                self.mutable_array.push(v);
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ScalarVectorBuilder for Decimal128VectorBuilder {
+    type VectorType = Decimal128Vector;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self::with_capacity_and_type(capacity, Decimal128Type::default())
+    }
+
+    /// Pushes a decimal into vector builder, the value is converted to the precision and
+    /// scale of the builder.
+    ///
+    /// # Panics
+    /// Panics if the value is out of range of the decimal type of the builder.
+    fn push(&mut self, value: Option<<Self::VectorType as ScalarVector>::RefItem<'_>>) {
+        let value = value.map(|v| self.convert(v).unwrap());
+        self.mutable_array.push(value);
+    }
+
+    fn finish(&mut self) -> Self::VectorType {
+        Self::VectorType {
+            array: std::mem::take(&mut self.mutable_array).into(),
+            data_type: self.data_type,
+        }
+    }
+}
+
+pub(crate) fn replicate_decimal128(vector: &Decimal128Vector, offsets: &[usize]) -> VectorRef {
+    assert_eq!(offsets.len(), vector.len());
+
+    let mut builder =
+        Decimal128VectorBuilder::with_capacity_and_type(vector.len(), vector.data_type);
+    let mut previous_offset = 0;
+    for (i, offset) in offsets.iter().enumerate() {
+        let data = vector.get_data(i);
+        for _ in previous_offset..*offset {
+            builder.push(data);
+        }
+        previous_offset = *offset;
+    }
+    builder.to_vector()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_decimal_vector() {
+        let mut builder =
+            Decimal128VectorBuilder::with_capacity_and_type(3, Decimal128Type::new(10, 2));
+        builder.push(Some(Decimal128::new(12345, 10, 2)));
+        builder.push(None);
+        builder.push(Some(Decimal128::new(5, 10, 1)));
+
+        let vector = builder.finish();
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(10, 2),
+            vector.data_type()
+        );
+        assert_eq!(3, vector.len());
+        assert_eq!(10, vector.precision());
+        assert_eq!(2, vector.scale());
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(12345, 10, 2)),
+            vector.get(0)
+        );
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!(
+            ValueRef::Decimal128(Decimal128::new(50, 10, 2)),
+            vector.get_ref(2)
+        );
+        assert_eq!(
+            vec![
+                Some(Decimal128::new(12345, 10, 2)),
+                None,
+                Some(Decimal128::new(50, 10, 2)),
+            ],
+            vector.iter_data().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            r#"["123.45",null,"0.50"]"#,
+            serde_json::to_string(&vector.serialize_to_json().unwrap()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_decimal_vector_arrow_conversion() {
+        let vector = Decimal128Vector::from_values_with_type([1, 2, 3], 20, 4);
+        let array = vector.to_arrow_array();
+        assert_eq!(&ArrowDataType::Decimal(20, 4), array.data_type());
+
+        let vector2 = Decimal128Vector::try_from_arrow_array(&array).unwrap();
+        assert_eq!(vector, vector2);
+        assert_eq!(
+            ConcreteDataType::decimal128_datatype(20, 4),
+            vector2.data_type()
+        );
+    }
+
+    #[test]
+    fn test_push_out_of_range() {
+        let mut builder =
+            Decimal128VectorBuilder::with_capacity_and_type(1, Decimal128Type::new(3, 2));
+        assert!(builder
+            .push_value_ref(ValueRef::Decimal128(Decimal128::new(1000, 10, 0)))
+            .is_err());
+        assert!(builder.push_value_ref(ValueRef::Int32(1)).is_err());
+
+        let vector = Decimal128Vector::from_values_with_type([100, 200], 10, 2);
+        let mut builder =
+            Decimal128VectorBuilder::with_capacity_and_type(2, Decimal128Type::new(10, 0));
+        builder.extend_slice_of(&vector, 0, 2).unwrap();
+        assert_eq!(
+            vec![
+                Some(Decimal128::new(1, 10, 0)),
+                Some(Decimal128::new(2, 10, 0))
+            ],
+            builder.finish().iter_data().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_replicate_decimal() {
+        let vector = Decimal128Vector::from_values_with_type([1, 2], 10, 2);
+        let replicated = replicate_decimal128(&vector, &[2, 3]);
+        assert_eq!(3, replicated.len());
+        assert_eq!(vector.data_type(), replicated.data_type());
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(1, 10, 2)),
+            replicated.get(1)
+        );
+        assert_eq!(
+            Value::Decimal128(Decimal128::new(2, 10, 2)),
+            replicated.get(2)
+        );
+    }
+}
@@ -16,8 +16,8 @@ use std::sync::Arc;
 
 use crate::data_type::DataType;
 use crate::vectors::{
-    BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, ListVector,
-    PrimitiveVector, StringVector, TimestampVector, Vector,
+    BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, Decimal128Vector,
+    JsonVector, ListVector, PrimitiveVector, StringVector, TimestampVector, Vector,
 };
 use crate::with_match_primitive_type_id;
 
@@ -77,6 +77,8 @@ fn equal(lhs: &dyn Vector, rhs: &dyn Vector) -> bool {
         Date(_) => is_vector_eq!(DateVector, lhs, rhs),
         DateTime(_) => is_vector_eq!(DateTimeVector, lhs, rhs),
         Timestamp(_) => is_vector_eq!(TimestampVector, lhs, rhs),
+        Decimal128(_) => is_vector_eq!(Decimal128Vector, lhs, rhs),
+        Json(_) => is_vector_eq!(JsonVector, lhs, rhs),
         List(_) => is_vector_eq!(ListVector, lhs, rhs),
         UInt8(_) | UInt16(_) | UInt32(_) | UInt64(_) | Int8(_) | Int16(_) | Int32(_) | Int64(_)
         | Float32(_) | Float64(_) => {
@@ -133,6 +135,15 @@ mod tests {
         assert_vector_ref_eq(Arc::new(DateVector::from(vec![Some(100), Some(120)])));
         assert_vector_ref_eq(Arc::new(DateTimeVector::from(vec![Some(100), Some(120)])));
         assert_vector_ref_eq(Arc::new(TimestampVector::from_values([100, 120])));
+        assert_vector_ref_eq(Arc::new(Decimal128Vector::from_values_with_type(
+            [100, 120],
+            10,
+            2,
+        )));
+        assert_vector_ref_eq(Arc::new(JsonVector::from(vec![
+            Some("{}".parse().unwrap()),
+            None,
+        ])));
 
         let mut arrow_array = MutableListArray::<i32, MutablePrimitiveArray<i64>>::new();
         arrow_array
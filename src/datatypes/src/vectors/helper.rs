@@ -17,7 +17,7 @@
 use std::any::Any;
 use std::sync::Arc;
 
-use arrow::array::Array;
+use arrow::array::{Array, PrimitiveArray};
 use arrow::compute;
 use arrow::datatypes::DataType as ArrowDataType;
 use datafusion_common::ScalarValue;
@@ -26,6 +26,7 @@ use snafu::{OptionExt, ResultExt};
 use crate::arrow_array::StringArray;
 use crate::error::{ConversionSnafu, Result, UnknownVectorSnafu};
 use crate::scalars::*;
+use crate::types::{Decimal128Type, JSON_EXTENSION_NAME};
 use crate::vectors::date::DateVector;
 use crate::vectors::datetime::DateTimeVector;
 use crate::vectors::*;
@@ -161,6 +162,13 @@ impl Helper {
             ScalarValue::Date64(v) => {
                 ConstantVector::new(Arc::new(DateTimeVector::from(vec![v])), length)
             }
+            ScalarValue::Decimal128(v, precision, scale) => ConstantVector::new(
+                Arc::new(Decimal128Vector::new_with_type(
+                    PrimitiveArray::from(vec![v]),
+                    Decimal128Type::new(precision as u8, scale as u8),
+                )),
+                length,
+            ),
             _ => {
                 return ConversionSnafu {
                     from: format!("Unsupported scalar value: {}", value),
@@ -202,6 +210,12 @@ impl Helper {
             ArrowDataType::Timestamp(_, _) => {
                 Arc::new(TimestampVector::try_from_arrow_array(array)?)
             }
+            ArrowDataType::Decimal(_, _) => {
+                Arc::new(Decimal128Vector::try_from_arrow_array(array)?)
+            }
+            ArrowDataType::Extension(name, _, _) if name == JSON_EXTENSION_NAME => {
+                Arc::new(JsonVector::try_from_arrow_array(array)?)
+            }
             _ => unimplemented!("Arrow array datatype: {:?}", array.as_ref().data_type()),
         })
     }
@@ -0,0 +1,305 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, BinaryValueIter, MutableArray};
+use arrow::bitmap::utils::ZipValidity;
+use snafu::OptionExt;
+
+use crate::arrow_array::{BinaryArray, MutableBinaryArray};
+use crate::data_type::{ConcreteDataType, DataType};
+use crate::error::{self, Result};
+use crate::json::{Json, JsonRef};
+use crate::scalars::{ScalarVector, ScalarVectorBuilder};
+use crate::serialize::Serializable;
+use crate::types::JsonType;
+use crate::value::{Value, ValueRef};
+use crate::vectors::{self, MutableVector, Validity, Vector, VectorRef};
+
+/// Vector of binary encoded JSON documents.
+///
+/// The underlying arrow array is a large binary array tagged with the json extension type.
+#[derive(Debug, PartialEq)]
+pub struct JsonVector {
+    array: BinaryArray,
+}
+
+impl JsonVector {
+    pub(crate) fn as_arrow(&self) -> &dyn Array {
+        &self.array
+    }
+
+    pub fn try_from_arrow_array(array: impl AsRef<dyn Array>) -> Result<Self> {
+        Ok(Self::from(
+            array
+                .as_ref()
+                .as_any()
+                .downcast_ref::<BinaryArray>()
+                .with_context(|| error::ConversionSnafu {
+                    from: format!("{:?}", array.as_ref().data_type()),
+                })?
+                .clone(),
+        ))
+    }
+}
+
+impl From<BinaryArray> for JsonVector {
+    fn from(array: BinaryArray) -> Self {
+        let array = BinaryArray::from_data(
+            JsonType.as_arrow_type(),
+            array.offsets().clone(),
+            array.values().clone(),
+            array.validity().cloned(),
+        );
+        Self { array }
+    }
+}
+
+impl From<Vec<Option<Json>>> for JsonVector {
+    fn from(data: Vec<Option<Json>>) -> Self {
+        Self::from_owned_iterator(data.into_iter())
+    }
+}
+
+impl Vector for JsonVector {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::json_datatype()
+    }
+
+    fn vector_type_name(&self) -> String {
+        "JsonVector".to_string()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn len(&self) -> usize {
+        self.array.len()
+    }
+
+    fn to_arrow_array(&self) -> ArrayRef {
+        Arc::new(self.array.clone())
+    }
+
+    fn to_boxed_arrow_array(&self) -> Box<dyn Array> {
+        Box::new(self.array.clone())
+    }
+
+    fn validity(&self) -> Validity {
+        vectors::impl_validity_for_vector!(self.array)
+    }
+
+    fn memory_size(&self) -> usize {
+        self.array.values().len() + self.array.offsets().len() * std::mem::size_of::<i64>()
+    }
+
+    fn is_null(&self, row: usize) -> bool {
+        self.array.is_null(row)
+    }
+
+    fn slice(&self, offset: usize, length: usize) -> VectorRef {
+        Arc::new(Self {
+            array: self.array.slice(offset, length),
+        })
+    }
+
+    fn get(&self, index: usize) -> Value {
+        match self.get_data(index) {
+            Some(v) => Value::Json(v.to_owned_json()),
+            None => Value::Null,
+        }
+    }
+
+    fn get_ref(&self, index: usize) -> ValueRef {
+        match self.get_data(index) {
+            Some(v) => ValueRef::Json(v),
+            None => ValueRef::Null,
+        }
+    }
+}
+
+impl ScalarVector for JsonVector {
+    type OwnedItem = Json;
+    type RefItem<'a> = JsonRef<'a>;
+    type Iter<'a> = JsonIter<'a>;
+    type Builder = JsonVectorBuilder;
+
+    fn get_data(&self, idx: usize) -> Option<Self::RefItem<'_>> {
+        if self.array.is_valid(idx) {
+            Some(JsonRef::from_bytes_unchecked(self.array.value(idx)))
+        } else {
+            None
+        }
+    }
+
+    fn iter_data(&self) -> Self::Iter<'_> {
+        JsonIter {
+            iter: self.array.iter(),
+        }
+    }
+}
+
+pub struct JsonIter<'a> {
+    iter: ZipValidity<'a, &'a [u8], BinaryValueIter<'a, i64>>,
+}
+
+impl<'a> Iterator for JsonIter<'a> {
+    type Item = Option<JsonRef<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|v| v.map(JsonRef::from_bytes_unchecked))
+    }
+}
+
+pub struct JsonVectorBuilder {
+    mutable_array: MutableBinaryArray,
+}
+
+impl MutableVector for JsonVectorBuilder {
+    fn data_type(&self) -> ConcreteDataType {
+        ConcreteDataType::json_datatype()
+    }
+
+    fn len(&self) -> usize {
+        self.mutable_array.len()
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn to_vector(&mut self) -> VectorRef {
+        Arc::new(self.finish())
+    }
+
+    /// Pushes a json value, string values are parsed as json text.
+    fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
+        match value {
+            ValueRef::String(s) => {
+                let json: Json = s.parse()?;
+                self.mutable_array.push(Some(json.as_bytes()));
+            }
+            _ => self
+                .mutable_array
+                .push(value.as_json()?.map(|v| v.as_bytes())),
+        }
+        Ok(())
+    }
+
+    fn extend_slice_of(&mut self, vector: &dyn Vector, offset: usize, length: usize) -> Result<()> {
+        vectors::impl_extend_for_builder!(self.mutable_array, vector, JsonVector, offset, length)
+    }
+}
+
+impl ScalarVectorBuilder for JsonVectorBuilder {
+    type VectorType = JsonVector;
+
+    fn with_capacity(capacity: usize) -> Self {
+        Self {
+            mutable_array: MutableBinaryArray::with_capacity(capacity),
+        }
+    }
+
+    fn push(&mut self, value: Option<<Self::VectorType as ScalarVector>::RefItem<'_>>) {
+        self.mutable_array.push(value.map(|v| v.as_bytes()));
+    }
+
+    fn finish(&mut self) -> Self::VectorType {
+        let array: BinaryArray = std::mem::take(&mut self.mutable_array).into();
+        JsonVector::from(array)
+    }
+}
+
+impl Serializable for JsonVector {
+    fn serialize_to_json(&self) -> Result<Vec<serde_json::Value>> {
+        Ok(self
+            .iter_data()
+            .map(|v| match v {
+                None => serde_json::Value::Null,
+                Some(v) => v.to_value(),
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::JSON_EXTENSION_NAME;
+
+    fn json(s: &str) -> Json {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_json_vector_build_get() {
+        let mut builder = JsonType.create_mutable_vector(3);
+        builder
+            .push_value_ref(ValueRef::Json(json(r#"{"a": 1}"#).as_json_ref()))
+            .unwrap();
+        builder.push_value_ref(ValueRef::Null).unwrap();
+        builder.push_value_ref(ValueRef::String("[1, 2]")).unwrap();
+        assert!(builder.push_value_ref(ValueRef::String("{")).is_err());
+        assert!(builder.push_value_ref(ValueRef::Int32(1)).is_err());
+        let vector = builder.to_vector();
+
+        assert_eq!(3, vector.len());
+        assert_eq!(ConcreteDataType::json_datatype(), vector.data_type());
+        assert_eq!(Value::Json(json(r#"{"a": 1}"#)), vector.get(0));
+        assert_eq!(Value::Null, vector.get(1));
+        assert_eq!(
+            ValueRef::Json(json("[1, 2]").as_json_ref()),
+            vector.get_ref(2)
+        );
+        assert_eq!(
+            r#"[{"a":1},null,[1,2]]"#,
+            serde_json::to_string(&vector.serialize_to_json().unwrap()).unwrap()
+        );
+
+        let vector = vector.as_any().downcast_ref::<JsonVector>().unwrap();
+        assert_eq!(
+            vec![Some(json(r#"{"a": 1}"#)), None, Some(json("[1, 2]"))],
+            vector
+                .iter_data()
+                .map(|v| v.map(|v| v.to_owned_json()))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_json_vector_arrow_conversion() {
+        let vector = JsonVector::from(vec![Some(json("1")), None]);
+        let array = vector.to_arrow_array();
+        assert!(matches!(
+            array.data_type(),
+            arrow::datatypes::DataType::Extension(name, _, _) if name == JSON_EXTENSION_NAME
+        ));
+
+        let vector2 = JsonVector::try_from_arrow_array(&array).unwrap();
+        assert_eq!(vector, vector2);
+
+        let mut builder = JsonVectorBuilder::with_capacity(2);
+        builder.extend_slice_of(&vector, 0, 2).unwrap();
+        assert_eq!(vector, builder.finish());
+    }
+}
@@ -21,8 +21,9 @@ use common_base::BitVec;
 use crate::error::Result;
 use crate::types::PrimitiveElement;
 use crate::vectors::{
-    BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, ListVector,
-    NullVector, PrimitiveVector, StringVector, TimestampVector, Vector, VectorRef,
+    BinaryVector, BooleanVector, ConstantVector, DateTimeVector, DateVector, Decimal128Vector,
+    JsonVector, ListVector, NullVector, PrimitiveVector, StringVector, TimestampVector, Vector,
+    VectorRef,
 };
 
 /// Vector compute operations.
@@ -80,11 +81,13 @@ macro_rules! impl_scalar_vector_op {
 impl_scalar_vector_op!(
     { BinaryVector, replicate_scalar },
     { BooleanVector, replicate_scalar },
+    { JsonVector, replicate_scalar },
     { ListVector, replicate_scalar },
     { StringVector, replicate_scalar },
     { DateVector, replicate_date },
     { DateTimeVector, replicate_datetime },
-    { TimestampVector, replicate_timestamp }
+    { TimestampVector, replicate_timestamp },
+    { Decimal128Vector, replicate_decimal128 }
 );
 
 impl VectorOp for ConstantVector {
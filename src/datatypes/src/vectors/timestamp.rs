@@ -16,6 +16,7 @@ use std::any::Any;
 use std::sync::Arc;
 
 use arrow::array::{Array, ArrayRef, PrimitiveArray};
+use arrow::datatypes::{DataType as ArrowDataType, TimeUnit as ArrowTimeUnit};
 use common_time::timestamp::{TimeUnit, Timestamp};
 use snafu::OptionExt;
 
@@ -29,16 +30,29 @@ use crate::serialize::Serializable;
 use crate::types::TimestampType;
 use crate::vectors::{PrimitiveIter, PrimitiveVector, PrimitiveVectorBuilder};
 
-/// `TimestampVector` stores timestamp in millisecond since UNIX Epoch.
+/// `TimestampVector` stores timestamp since UNIX Epoch in the time unit of the vector,
+/// which defaults to millisecond.
 #[derive(Debug, Clone, PartialEq)]
 pub struct TimestampVector {
     array: PrimitiveVector<i64>,
+    unit: TimeUnit,
 }
 
 impl TimestampVector {
+    /// Creates a vector from arrow array, the time unit is inferred from the array's
+    /// data type and falls back to millisecond if the array is not a timestamp array.
     pub fn new(array: PrimitiveArray<i64>) -> Self {
+        let unit = match array.data_type() {
+            ArrowDataType::Timestamp(unit, _) => time_unit_from_arrow(unit),
+            _ => TimeUnit::Millisecond,
+        };
+        Self::new_with_unit(array, unit)
+    }
+
+    pub fn new_with_unit(array: PrimitiveArray<i64>, unit: TimeUnit) -> Self {
         Self {
             array: PrimitiveVector { array },
+            unit,
         }
     }
 
@@ -56,13 +70,22 @@ impl TimestampVector {
     }
 
     pub fn from_values<I: IntoIterator<Item = i64>>(iter: I) -> Self {
+        Self::from_values_with_unit(iter, TimeUnit::Millisecond)
+    }
+
+    pub fn from_values_with_unit<I: IntoIterator<Item = i64>>(iter: I, unit: TimeUnit) -> Self {
         Self {
             array: PrimitiveVector {
                 array: PrimitiveArray::from_values(iter),
             },
+            unit,
         }
     }
 
+    pub fn unit(&self) -> TimeUnit {
+        self.unit
+    }
+
     pub(crate) fn as_arrow(&self) -> &dyn Array {
         self.array.as_arrow()
     }
@@ -70,7 +93,7 @@ impl TimestampVector {
 
 impl Vector for TimestampVector {
     fn data_type(&self) -> ConcreteDataType {
-        ConcreteDataType::timestamp_millis_datatype()
+        ConcreteDataType::timestamp_datatype(self.unit)
     }
 
     fn vector_type_name(&self) -> String {
@@ -89,7 +112,7 @@ impl Vector for TimestampVector {
         let validity = self.array.array.validity().cloned();
         let buffer = self.array.array.values().clone();
         Arc::new(PrimitiveArray::new(
-            TimestampType::new(TimeUnit::Millisecond).as_arrow_type(),
+            TimestampType::new(self.unit).as_arrow_type(),
             buffer,
             validity,
         ))
@@ -99,7 +122,7 @@ impl Vector for TimestampVector {
         let validity = self.array.array.validity().cloned();
         let values = self.array.array.values().clone();
         Box::new(PrimitiveArray::new(
-            arrow::datatypes::DataType::Timestamp(arrow::datatypes::TimeUnit::Millisecond, None),
+            TimestampType::new(self.unit).as_arrow_type(),
             values,
             validity,
         ))
@@ -122,13 +145,14 @@ impl Vector for TimestampVector {
             array: PrimitiveVector {
                 array: self.array.array.slice(offset, length),
             },
+            unit: self.unit,
         })
     }
 
     fn get(&self, index: usize) -> Value {
         match self.array.get(index) {
             Value::Null => Value::Null,
-            Value::Int64(v) => Value::Timestamp(Timestamp::from_millis(v)),
+            Value::Int64(v) => Value::Timestamp(Timestamp::new(v, self.unit)),
             _ => {
                 unreachable!()
             }
@@ -137,7 +161,7 @@ impl Vector for TimestampVector {
 
     fn get_ref(&self, index: usize) -> ValueRef {
         match self.array.get(index) {
-            Value::Int64(v) => ValueRef::Timestamp(Timestamp::from_millis(v)),
+            Value::Int64(v) => ValueRef::Timestamp(Timestamp::new(v, self.unit)),
             Value::Null => ValueRef::Null,
             _ => unreachable!(),
         }
@@ -164,35 +188,54 @@ impl ScalarVector for TimestampVector {
     type Builder = TimestampVectorBuilder;
 
     fn get_data(&self, idx: usize) -> Option<Self::RefItem<'_>> {
-        self.array.get_data(idx).map(Timestamp::from_millis)
+        self.array
+            .get_data(idx)
+            .map(|v| Timestamp::new(v, self.unit))
     }
 
     fn iter_data(&self) -> Self::Iter<'_> {
         TimestampDataIter {
             iter: self.array.iter_data(),
+            unit: self.unit,
         }
     }
 }
 
 pub struct TimestampDataIter<'a> {
     iter: PrimitiveIter<'a, i64>,
+    unit: TimeUnit,
 }
 
 impl<'a> Iterator for TimestampDataIter<'a> {
     type Item = Option<Timestamp>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.iter.next().map(|v| v.map(Timestamp::from_millis))
+        let unit = self.unit;
+        self.iter.next().map(|v| v.map(|v| Timestamp::new(v, unit)))
     }
 }
 
 pub struct TimestampVectorBuilder {
     buffer: PrimitiveVectorBuilder<i64>,
+    unit: TimeUnit,
+}
+
+impl TimestampVectorBuilder {
+    pub fn with_capacity_and_unit(capacity: usize, unit: TimeUnit) -> Self {
+        Self {
+            buffer: PrimitiveVectorBuilder::with_capacity(capacity),
+            unit,
+        }
+    }
+
+    pub fn unit(&self) -> TimeUnit {
+        self.unit
+    }
 }
 
 impl MutableVector for TimestampVectorBuilder {
     fn data_type(&self) -> ConcreteDataType {
-        ConcreteDataType::timestamp_millis_datatype()
+        ConcreteDataType::timestamp_datatype(self.unit)
     }
 
     fn len(&self) -> usize {
@@ -212,12 +255,8 @@ impl MutableVector for TimestampVectorBuilder {
     }
 
     fn push_value_ref(&mut self, value: ValueRef) -> Result<()> {
-        // TODO(hl): vector and vector builder should also support customized time unit.
-        self.buffer.push(
-            value
-                .as_timestamp()?
-                .map(|t| t.convert_to(TimeUnit::Millisecond)),
-        );
+        self.buffer
+            .push(value.as_timestamp()?.map(|t| t.convert_to(self.unit)));
         Ok(())
     }
 
@@ -232,8 +271,14 @@ impl MutableVector for TimestampVectorBuilder {
                 ),
             })?;
 
-        self.buffer
-            .extend_slice_of(&concrete_vector.array, offset, length)?;
+        if concrete_vector.unit == self.unit {
+            self.buffer
+                .extend_slice_of(&concrete_vector.array, offset, length)?;
+        } else {
+            for ts in concrete_vector.iter_data().skip(offset).take(length) {
+                self.buffer.push(ts.map(|t| t.convert_to(self.unit)));
+            }
+        }
         Ok(())
     }
 }
@@ -242,21 +287,19 @@ impl ScalarVectorBuilder for TimestampVectorBuilder {
     type VectorType = TimestampVector;
 
     fn with_capacity(capacity: usize) -> Self {
-        Self {
-            buffer: PrimitiveVectorBuilder::with_capacity(capacity),
-        }
+        Self::with_capacity_and_unit(capacity, TimeUnit::Millisecond)
     }
 
-    /// Pushes a Timestamp value into vector builder. The timestamp must be with time unit
-    /// `Second`/`MilliSecond`/`Microsecond`.
+    /// Pushes a Timestamp value into vector builder, the value is converted to the time unit
+    /// of the builder.
     fn push(&mut self, value: Option<<Self::VectorType as ScalarVector>::RefItem<'_>>) {
-        self.buffer
-            .push(value.map(|v| v.convert_to(TimeUnit::Millisecond)));
+        self.buffer.push(value.map(|v| v.convert_to(self.unit)));
     }
 
     fn finish(&mut self) -> Self::VectorType {
         Self::VectorType {
             array: self.buffer.finish(),
+            unit: self.unit,
         }
     }
 }
@@ -267,7 +310,19 @@ pub(crate) fn replicate_timestamp(vector: &TimestampVector, offsets: &[usize]) -
         offsets,
         vector.data_type(),
     );
-    Arc::new(TimestampVector { array })
+    Arc::new(TimestampVector {
+        array,
+        unit: vector.unit,
+    })
+}
+
+fn time_unit_from_arrow(unit: &ArrowTimeUnit) -> TimeUnit {
+    match unit {
+        ArrowTimeUnit::Second => TimeUnit::Second,
+        ArrowTimeUnit::Millisecond => TimeUnit::Millisecond,
+        ArrowTimeUnit::Microsecond => TimeUnit::Microsecond,
+        ArrowTimeUnit::Nanosecond => TimeUnit::Nanosecond,
+    }
 }
 
 #[cfg(test)]
@@ -316,4 +371,42 @@ mod tests {
         let vector2 = TimestampVector::try_from_arrow_array(&arrow).unwrap();
         assert_eq!(vector, vector2);
     }
+
+    #[test]
+    fn test_timestamp_vector_with_unit() {
+        let mut builder = TimestampVectorBuilder::with_capacity_and_unit(2, TimeUnit::Nanosecond);
+        builder.push(Some(Timestamp::new(1, TimeUnit::Millisecond)));
+        builder.push(Some(Timestamp::new(42, TimeUnit::Nanosecond)));
+        let vector = builder.finish();
+        assert_eq!(
+            ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond),
+            vector.data_type()
+        );
+        assert_eq!(
+            Value::Timestamp(Timestamp::new(1_000_000, TimeUnit::Nanosecond)),
+            vector.get(0)
+        );
+        assert_eq!(
+            ArrowDataType::Timestamp(ArrowTimeUnit::Nanosecond, None),
+            vector.to_arrow_array().data_type().clone()
+        );
+
+        let vector2 = TimestampVector::try_from_arrow_array(vector.to_arrow_array()).unwrap();
+        assert_eq!(TimeUnit::Nanosecond, vector2.unit());
+        assert_eq!(
+            Some(Timestamp::new(42, TimeUnit::Nanosecond)),
+            vector2.get_data(1)
+        );
+
+        let mut builder = TimestampVectorBuilder::with_capacity(2);
+        builder.extend_slice_of(&vector2, 0, 2).unwrap();
+        let vector3 = builder.finish();
+        assert_eq!(
+            vec![
+                Some(Timestamp::new(1, TimeUnit::Millisecond)),
+                Some(Timestamp::new(0, TimeUnit::Millisecond))
+            ],
+            vector3.iter_data().collect::<Vec<_>>()
+        );
+    }
 }
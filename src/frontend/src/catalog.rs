@@ -21,21 +21,33 @@ use catalog::helper::{
     build_catalog_prefix, build_schema_prefix, build_table_global_prefix, CatalogKey, SchemaKey,
     TableGlobalKey, TableGlobalValue,
 };
+use catalog::local::MemorySchemaProvider;
 use catalog::remote::{Kv, KvBackendRef};
+use catalog::tables::{Regions, REGIONS_TABLE_NAME};
 use catalog::{
-    CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef, DeregisterTableRequest,
-    RegisterSchemaRequest, RegisterSystemTableRequest, RegisterTableRequest, SchemaProvider,
-    SchemaProviderRef,
+    CatalogList, CatalogManager, CatalogProvider, CatalogProviderRef, CreateCatalogRequest,
+    DeregisterTableRequest, DropCatalogRequest, RegisterSchemaRequest, RegisterSystemTableRequest,
+    RegisterTableRequest, RenameTableRequest, SchemaProvider, SchemaProviderRef,
 };
+use common_catalog::consts::INFORMATION_SCHEMA_NAME;
+use common_error::prelude::BoxedError;
 use futures::StreamExt;
 use meta_client::rpc::TableName;
 use snafu::prelude::*;
 use table::TableRef;
 
 use crate::datanode::DatanodeClients;
+use crate::error;
 use crate::table::route::TableRoutes;
 use crate::table::DistTable;
 
+fn not_supported<T>(feat: &str) -> catalog::error::Result<T> {
+    error::NotSupportedSnafu { feat }
+        .fail()
+        .map_err(BoxedError::new)
+        .context(catalog_err::InternalSnafu)
+}
+
 #[derive(Clone)]
 pub struct FrontendCatalogManager {
     backend: KvBackendRef,
@@ -90,6 +102,16 @@ impl CatalogManager for FrontendCatalogManager {
         unimplemented!()
     }
 
+    async fn rename_table(&self, request: RenameTableRequest) -> catalog::error::Result<bool> {
+        let table_name = TableName::new(request.catalog, request.schema, request.table_name);
+        self.table_routes
+            .rename_route(&table_name, &request.new_table_name)
+            .await
+            .map_err(BoxedError::new)
+            .context(catalog_err::InternalSnafu)?;
+        Ok(true)
+    }
+
     async fn register_schema(
         &self,
         _request: RegisterSchemaRequest,
@@ -97,6 +119,15 @@ impl CatalogManager for FrontendCatalogManager {
         unimplemented!()
     }
 
+    async fn create_catalog(&self, _request: CreateCatalogRequest) -> catalog::error::Result<bool> {
+        // Catalogs are created by DistInstance in metasrv directly.
+        not_supported("CREATE CATALOG by frontend catalog manager")
+    }
+
+    async fn drop_catalog(&self, _request: DropCatalogRequest) -> catalog::error::Result<bool> {
+        not_supported("DROP CATALOG by frontend catalog manager")
+    }
+
     async fn register_system_table(
         &self,
         _request: RegisterSystemTableRequest,
@@ -203,6 +234,7 @@ impl CatalogProvider for FrontendCatalogProvider {
                         .context(InvalidCatalogValueSnafu)?;
                     res.insert(key.schema_name);
                 }
+                res.insert(INFORMATION_SCHEMA_NAME.to_string());
                 Ok(res.into_iter().collect())
             })
         })
@@ -220,6 +252,14 @@ impl CatalogProvider for FrontendCatalogProvider {
     }
 
     fn schema(&self, name: &str) -> catalog::error::Result<Option<SchemaProviderRef>> {
+        if name.eq_ignore_ascii_case(INFORMATION_SCHEMA_NAME) {
+            // Regions are read from the stats reported by datanodes in heartbeats.
+            let schema = MemorySchemaProvider::new();
+            let regions = Regions::with_backend(self.backend.clone(), self.catalog_name.clone());
+            let _ = schema.register_table(REGIONS_TABLE_NAME.to_string(), Arc::new(regions))?;
+            return Ok(Some(Arc::new(schema)));
+        }
+
         let all_schemas = self.schema_names()?;
         if all_schemas.contains(&name.to_string()) {
             Ok(Some(Arc::new(FrontendSchemaProvider {
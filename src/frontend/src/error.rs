@@ -298,6 +298,19 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display("Access denied to catalog: {}", catalog))]
+    AccessDenied {
+        catalog: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Permission denied for user {}: {}", user, action))]
+    PermissionDenied {
+        user: String,
+        action: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Failed to find schema, schema info: {}", schema_info))]
     SchemaNotFound {
         schema_info: String,
@@ -419,6 +432,9 @@ pub enum Error {
     #[snafu(display("Missing meta_client_opts section in config"))]
     MissingMetasrvOpts { backtrace: Backtrace },
 
+    #[snafu(display("Unsupported feature in distributed mode: {}", feat))]
+    NotSupported { feat: String, backtrace: Backtrace },
+
     #[snafu(display("Failed to convert AlterExpr to AlterRequest, source: {}", source))]
     AlterExprToRequest {
         #[snafu(backtrace)]
@@ -442,6 +458,14 @@ pub enum Error {
         #[snafu(backtrace)]
         source: servers::error::Error,
     },
+
+    #[snafu(display("Failed to set variable {} to {}, source: {}", name, value, source))]
+    SetVariable {
+        name: String,
+        value: String,
+        #[snafu(backtrace)]
+        source: common_time::error::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -457,7 +481,8 @@ impl ErrorExt for Error {
             | Error::FindPartitionColumn { .. }
             | Error::ColumnValuesNumberMismatch { .. }
             | Error::CatalogManager { .. }
-            | Error::RegionKeysSize { .. } => StatusCode::InvalidArguments,
+            | Error::RegionKeysSize { .. }
+            | Error::SetVariable { .. } => StatusCode::InvalidArguments,
 
             Error::RuntimeResource { source, .. } => source.status_code(),
 
@@ -512,6 +537,7 @@ impl ErrorExt for Error {
             }
             Error::BumpTableId { source, .. } => source.status_code(),
             Error::SchemaNotFound { .. } => StatusCode::InvalidArguments,
+            Error::AccessDenied { .. } | Error::PermissionDenied { .. } => StatusCode::AccessDenied,
             Error::CatalogNotFound { .. } => StatusCode::InvalidArguments,
             Error::CreateTable { source, .. }
             | Error::Select { source, .. }
@@ -529,6 +555,7 @@ impl ErrorExt for Error {
                 source.status_code()
             }
             Error::MissingMetasrvOpts { .. } => StatusCode::InvalidArguments,
+            Error::NotSupported { .. } => StatusCode::Unsupported,
             Error::AlterExprToRequest { source, .. } => source.status_code(),
             Error::LeaderNotFound { .. } => StatusCode::StorageUnavailable,
             Error::TableAlreadyExist { .. } => StatusCode::TableAlreadyExists,
@@ -16,23 +16,29 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use api::helper::ColumnDataTypeWrapper;
-use api::v1::{Column, ColumnDataType, CreateExpr};
+use api::v1::{Column, CreateExpr};
 use datatypes::schema::ColumnSchema;
+use session::context::QueryContext;
 use snafu::{ensure, ResultExt};
 use sql::statements::create::{CreateTable, TIME_INDEX};
-use sql::statements::{column_def_to_schema, table_idents_to_full_name};
+use sql::statements::{column_def_to_schema, table_idents_to_full_name_with_ctx};
 use sqlparser::ast::{ColumnDef, TableConstraint};
 
 use crate::error::{
     BuildCreateExprOnInsertionSnafu, ColumnDataTypeSnafu, ConvertColumnDefaultConstraintSnafu,
     InvalidSqlSnafu, ParseSqlSnafu, Result,
 };
+use crate::instance::privilege::ensure_catalog_accessible;
 
 pub type CreateExprFactoryRef = Arc<dyn CreateExprFactory + Send + Sync>;
 
 #[async_trait::async_trait]
 pub trait CreateExprFactory {
-    async fn create_expr_by_stmt(&self, stmt: &CreateTable) -> Result<CreateExpr>;
+    async fn create_expr_by_stmt(
+        &self,
+        stmt: &CreateTable,
+        query_ctx: &QueryContext,
+    ) -> Result<CreateExpr>;
 
     async fn create_expr_by_columns(
         &self,
@@ -48,8 +54,12 @@ pub struct DefaultCreateExprFactory;
 
 #[async_trait::async_trait]
 impl CreateExprFactory for DefaultCreateExprFactory {
-    async fn create_expr_by_stmt(&self, stmt: &CreateTable) -> Result<CreateExpr> {
-        create_to_expr(None, vec![0], stmt)
+    async fn create_expr_by_stmt(
+        &self,
+        stmt: &CreateTable,
+        query_ctx: &QueryContext,
+    ) -> Result<CreateExpr> {
+        create_to_expr(None, vec![0], stmt, query_ctx)
     }
 
     async fn create_expr_by_columns(
@@ -73,14 +83,17 @@ impl CreateExprFactory for DefaultCreateExprFactory {
     }
 }
 
-/// Convert `CreateTable` statement to `CreateExpr` gRPC request.
+/// Convert `CreateTable` statement to `CreateExpr` gRPC request, the table is created in the
+/// catalog and schema of the session unless they are specified.
 fn create_to_expr(
     table_id: Option<u32>,
     region_ids: Vec<u32>,
     create: &CreateTable,
+    query_ctx: &QueryContext,
 ) -> Result<CreateExpr> {
     let (catalog_name, schema_name, table_name) =
-        table_idents_to_full_name(&create.name).context(ParseSqlSnafu)?;
+        table_idents_to_full_name_with_ctx(&create.name, query_ctx).context(ParseSqlSnafu)?;
+    ensure_catalog_accessible(&catalog_name, query_ctx)?;
 
     let time_index = find_time_index(&create.constraints)?;
     let expr = CreateExpr {
@@ -155,12 +168,8 @@ fn columns_to_expr(
 
     let column_datatypes = column_schemas
         .iter()
-        .map(|c| {
-            ColumnDataTypeWrapper::try_from(c.data_type.clone())
-                .map(|w| w.datatype())
-                .context(ColumnDataTypeSnafu)
-        })
-        .collect::<Result<Vec<ColumnDataType>>>()?;
+        .map(|c| ColumnDataTypeWrapper::try_from(c.data_type.clone()).context(ColumnDataTypeSnafu))
+        .collect::<Result<Vec<ColumnDataTypeWrapper>>>()?;
 
     column_schemas
         .iter()
@@ -168,7 +177,8 @@ fn columns_to_expr(
         .map(|(schema, datatype)| {
             Ok(api::v1::ColumnDef {
                 name: schema.name.clone(),
-                datatype: datatype as i32,
+                datatype: datatype.datatype() as i32,
+                datatype_extension: datatype.datatype_extension(),
                 is_nullable: schema.is_nullable(),
                 default_constraint: match schema.default_constraint() {
                     None => None,
@@ -178,6 +188,7 @@ fn columns_to_expr(
                         },
                     )?),
                 },
+                datatype_extension: None,
             })
         })
         .collect()
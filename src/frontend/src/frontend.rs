@@ -14,11 +14,13 @@
 
 use std::sync::Arc;
 
+use common_grpc::channel_manager::ClientTlsOption;
 use common_telemetry::info;
 use meta_client::MetaClientOpts;
 use serde::{Deserialize, Serialize};
 use servers::auth::UserProviderRef;
 use servers::http::HttpOptions;
+use servers::process::QueryOptions;
 use servers::Mode;
 use snafu::prelude::*;
 
@@ -41,8 +43,12 @@ pub struct FrontendOptions {
     pub opentsdb_options: Option<OpentsdbOptions>,
     pub influxdb_options: Option<InfluxdbOptions>,
     pub prometheus_options: Option<PrometheusOptions>,
+    pub query_options: Option<QueryOptions>,
     pub mode: Mode,
     pub meta_client_opts: Option<MetaClientOpts>,
+    /// TLS options to connect to datanodes, `None` for plaintext connections.
+    #[serde(default)]
+    pub datanode_client_tls: Option<ClientTlsOption>,
 }
 
 impl Default for FrontendOptions {
@@ -55,8 +61,10 @@ impl Default for FrontendOptions {
             opentsdb_options: Some(OpentsdbOptions::default()),
             influxdb_options: Some(InfluxdbOptions::default()),
             prometheus_options: Some(PrometheusOptions::default()),
+            query_options: None,
             mode: Mode::Standalone,
             meta_client_opts: None,
+            datanode_client_tls: None,
         }
     }
 }
@@ -12,12 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
+use common_grpc::tls::TlsOption;
 use serde::{Deserialize, Serialize};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct GrpcOptions {
     pub addr: String,
     pub runtime_size: usize,
+    #[serde(default = "Default::default")]
+    pub tls: Arc<TlsOption>,
 }
 
 impl Default for GrpcOptions {
@@ -25,6 +30,7 @@ impl Default for GrpcOptions {
         Self {
             addr: "127.0.0.1:4001".to_string(),
             runtime_size: 8,
+            tls: Arc::new(TlsOption::default()),
         }
     }
 }
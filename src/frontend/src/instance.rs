@@ -15,13 +15,16 @@
 pub(crate) mod distributed;
 mod influxdb;
 mod opentsdb;
+pub(crate) mod privilege;
 mod prometheus;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use api::result::{ObjectResultBuilder, PROTOCOL_VERSION};
 use api::v1::alter_expr::Kind;
+use api::v1::codec::SelectResult;
 use api::v1::object_expr::Expr;
 use api::v1::{
     admin_expr, AddColumns, AdminExpr, AdminResult, AlterExpr, Column, CreateDatabaseExpr,
@@ -29,13 +32,16 @@ use api::v1::{
     ObjectResult as GrpcObjectResult,
 };
 use async_trait::async_trait;
+use catalog::privilege::PrivilegeManagerRef;
 use catalog::remote::MetaKvBackend;
 use catalog::{CatalogManagerRef, CatalogProviderRef, SchemaProviderRef};
 use client::admin::admin_result_to_output;
 use client::ObjectResult;
 use common_catalog::consts::DEFAULT_CATALOG_NAME;
+use common_catalog::parse_catalog_and_schema_from_client_database_name;
 use common_error::prelude::BoxedError;
 use common_grpc::channel_manager::{ChannelConfig, ChannelManager};
+use common_grpc::select::to_object_result;
 use common_query::Output;
 use common_recordbatch::RecordBatches;
 use common_telemetry::{debug, info};
@@ -43,19 +49,22 @@ use datanode::instance::InstanceRef as DnInstanceRef;
 use distributed::DistInstance;
 use meta_client::client::{MetaClient, MetaClientBuilder};
 use meta_client::MetaClientOpts;
+use servers::http::script::ScriptInfo;
 use servers::query_handler::{
     GrpcAdminHandler, GrpcAdminHandlerRef, GrpcQueryHandler, GrpcQueryHandlerRef,
     InfluxdbLineProtocolHandler, OpentsdbProtocolHandler, PrometheusProtocolHandler, ScriptHandler,
     ScriptHandlerRef, SqlQueryHandler, SqlQueryHandlerRef,
 };
 use servers::{error as server_error, Mode};
-use session::context::QueryContextRef;
+use session::context::{QueryContext, QueryContextRef};
 use snafu::prelude::*;
 use sql::dialect::GenericDialect;
 use sql::parser::ParserContext;
 use sql::statements::create::Partitions;
 use sql::statements::insert::Insert;
+use sql::statements::set_variables::SetVariables;
 use sql::statements::statement::Statement;
+use sql::statements::table_idents_to_full_name_with_ctx;
 
 use crate::catalog::FrontendCatalogManager;
 use crate::datanode::DatanodeClients;
@@ -66,6 +75,7 @@ use crate::error::{
 };
 use crate::expr_factory::{CreateExprFactoryRef, DefaultCreateExprFactory};
 use crate::frontend::FrontendOptions;
+use crate::instance::privilege::check_catalog_access;
 use crate::sql::insert_to_request;
 use crate::table::insert::insert_request_to_insert_batch;
 use crate::table::route::TableRoutes;
@@ -94,6 +104,8 @@ pub struct Instance {
     catalog_manager: Option<CatalogManagerRef>,
     /// Script handler is None in distributed mode, only works on standalone mode.
     script_handler: Option<ScriptHandlerRef>,
+    /// Manager of users and roles, privileges of sessions are not checked if it's None.
+    privilege_manager: Option<PrivilegeManagerRef>,
     create_expr_factory: CreateExprFactoryRef,
     // TODO(fys): it should be a trait that corresponds to two implementations:
     // Standalone and Distributed, then the code behind it doesn't need to use so
@@ -116,7 +128,12 @@ impl Instance {
             client: meta_client.clone(),
         });
         let table_routes = Arc::new(TableRoutes::new(meta_client.clone()));
-        let datanode_clients = Arc::new(DatanodeClients::new());
+        table_routes.start_watching();
+        let datanode_clients = Arc::new(DatanodeClients::with_channel_manager(
+            ChannelManager::with_config(
+                ChannelConfig::new().client_tls_config(opts.datanode_client_tls.clone()),
+            ),
+        ));
         let catalog_manager = Arc::new(FrontendCatalogManager::new(
             meta_backend,
             table_routes,
@@ -130,6 +147,9 @@ impl Instance {
         Ok(Instance {
             catalog_manager: Some(catalog_manager),
             script_handler: None,
+            // Users and grants aren't persisted through meta-srv yet, so privileges are not
+            // checked in distributed mode.
+            privilege_manager: None,
             create_expr_factory: Arc::new(DefaultCreateExprFactory),
             mode: Mode::Distributed,
             dist_instance: Some(dist_instance),
@@ -140,11 +160,11 @@ impl Instance {
     }
 
     async fn create_meta_client(opts: &FrontendOptions) -> Result<Arc<MetaClient>> {
-        let metasrv_addr = &opts
+        let meta_client_opts = opts
             .meta_client_opts
             .as_ref()
-            .context(MissingMetasrvOptsSnafu)?
-            .metasrv_addrs;
+            .context(MissingMetasrvOptsSnafu)?;
+        let metasrv_addr = &meta_client_opts.metasrv_addrs;
         info!(
             "Creating Frontend instance in distributed mode with Meta server addr {:?}",
             metasrv_addr
@@ -154,7 +174,8 @@ impl Instance {
         let channel_config = ChannelConfig::new()
             .timeout(Duration::from_millis(meta_config.timeout_millis))
             .connect_timeout(Duration::from_millis(meta_config.connect_timeout_millis))
-            .tcp_nodelay(meta_config.tcp_nodelay);
+            .tcp_nodelay(meta_config.tcp_nodelay)
+            .client_tls_config(meta_client_opts.tls.clone());
         let channel_manager = ChannelManager::with_config(channel_config);
 
         let mut meta_client = MetaClientBuilder::new(0, 0)
@@ -173,6 +194,7 @@ impl Instance {
         Instance {
             catalog_manager: None,
             script_handler: None,
+            privilege_manager: None,
             create_expr_factory: Arc::new(DefaultCreateExprFactory),
             mode: Mode::Standalone,
             dist_instance: None,
@@ -202,6 +224,14 @@ impl Instance {
         self.script_handler = Some(handler);
     }
 
+    pub fn set_privilege_manager(&mut self, privilege_manager: PrivilegeManagerRef) {
+        debug_assert!(
+            self.privilege_manager.is_none(),
+            "Privilege manager can be set only once!"
+        );
+        self.privilege_manager = Some(privilege_manager);
+    }
+
     /// Handle create expr.
     pub async fn handle_create_table(
         &self,
@@ -260,7 +290,11 @@ impl Instance {
     /// Handle insert. for 'values' insertion, create/alter the destination table on demand.
     async fn handle_insert(&self, mut insert_expr: InsertExpr) -> Result<Output> {
         let table_name = &insert_expr.table_name;
-        let catalog_name = DEFAULT_CATALOG_NAME;
+        let catalog_name = if insert_expr.catalog_name.is_empty() {
+            DEFAULT_CATALOG_NAME
+        } else {
+            &insert_expr.catalog_name
+        };
         let schema_name = &insert_expr.schema_name;
 
         let columns = &insert_expr.columns;
@@ -276,9 +310,13 @@ impl Instance {
             }),
             expr: Some(Expr::Insert(insert_expr)),
         };
-        let result = GrpcQueryHandler::do_query(&*self.grpc_query_handler, query)
-            .await
-            .context(error::InvokeGrpcServerSnafu)?;
+        let result = GrpcQueryHandler::do_query(
+            &*self.grpc_query_handler,
+            query,
+            QueryContext::internal_arc(),
+        )
+        .await
+        .context(error::InvokeGrpcServerSnafu)?;
         let result: ObjectResult = result.try_into().context(InsertSnafu)?;
         result.try_into().context(InsertSnafu)
     }
@@ -419,13 +457,19 @@ impl Instance {
             })
     }
 
-    async fn sql_dist_insert(&self, insert: Box<Insert>) -> Result<usize> {
-        let (catalog, schema, table) = insert.full_table_name().context(error::ParseSqlSnafu)?;
+    async fn sql_dist_insert(
+        &self,
+        insert: Box<Insert>,
+        query_ctx: QueryContextRef,
+    ) -> Result<usize> {
+        let (catalog, schema, table) = insert
+            .full_table_name(&query_ctx)
+            .context(error::ParseSqlSnafu)?;
 
         let catalog_provider = self.get_catalog(&catalog)?;
         let schema_provider = Self::get_schema(catalog_provider, &schema)?;
 
-        let insert_request = insert_to_request(&schema_provider, *insert)?;
+        let insert_request = insert_to_request(&schema_provider, *insert, &query_ctx)?;
 
         let (columns, _row_count) =
             crate::table::insert::insert_request_to_insert_batch(&insert_request)?;
@@ -449,26 +493,38 @@ impl Instance {
         catalog: &str,
         schema: &str,
         insert: Box<Insert>,
+        query_ctx: QueryContextRef,
     ) -> Result<(Vec<Column>, u32)> {
         let catalog_provider = self.get_catalog(catalog)?;
         let schema_provider = Self::get_schema(catalog_provider, schema)?;
 
-        let insert_request = insert_to_request(&schema_provider, *insert)?;
+        let insert_request = insert_to_request(&schema_provider, *insert, &query_ctx)?;
         insert_request_to_insert_batch(&insert_request)
     }
 
     fn handle_use(&self, db: String, query_ctx: QueryContextRef) -> Result<Output> {
         let catalog_manager = &self.catalog_manager;
         if let Some(catalog_manager) = catalog_manager {
+            let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(&db);
+            let catalog = catalog.map(|c| c.to_string()).unwrap_or_else(|| {
+                query_ctx
+                    .current_catalog()
+                    .unwrap_or_else(|| DEFAULT_CATALOG_NAME.to_string())
+            });
+            ensure!(
+                query_ctx.is_catalog_accessible(&catalog),
+                error::AccessDeniedSnafu { catalog: &catalog }
+            );
             ensure!(
                 catalog_manager
-                    .schema(DEFAULT_CATALOG_NAME, &db)
+                    .schema(&catalog, schema)
                     .context(error::CatalogSnafu)?
                     .is_some(),
                 error::SchemaNotFoundSnafu { schema_info: &db }
             );
 
-            query_ctx.set_current_schema(&db);
+            query_ctx.set_current_catalog(&catalog);
+            query_ctx.set_current_schema(schema);
 
             Ok(Output::RecordBatches(RecordBatches::empty()))
         } else {
@@ -476,6 +532,44 @@ impl Instance {
             unimplemented!()
         }
     }
+
+    fn handle_set_variables(
+        &self,
+        set_variables: SetVariables,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        for (name, value) in set_variables.variables {
+            query_ctx
+                .set_variable(&name, &value)
+                .context(error::SetVariableSnafu {
+                    name: &name,
+                    value: &value,
+                })?;
+        }
+        Ok(Output::AffectedRows(0))
+    }
+
+    /// Executes the select `sql` for protocols that query through SQL, like OpenTSDB and
+    /// InfluxDB, the privileges of the session are checked as other queries.
+    pub(crate) async fn select(
+        &self,
+        sql: String,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<SelectResult> {
+        let output = SqlQueryHandler::do_query(self, &sql, query_ctx).await;
+        let object_result = to_object_result(output)
+            .await
+            .try_into()
+            .map_err(BoxedError::new)
+            .context(server_error::ExecuteQuerySnafu { query: &sql })?;
+        match object_result {
+            ObjectResult::Select(select_result) => Ok(select_result),
+            ObjectResult::Mutate(_) => server_error::InternalSnafu {
+                err_msg: format!("expect select result for query: {}", sql),
+            }
+            .fail(),
+        }
+    }
 }
 
 #[async_trait]
@@ -508,11 +602,22 @@ impl SqlQueryHandler for Instance {
         query_ctx: QueryContextRef,
     ) -> server_error::Result<Output> {
         let stmt = parse_stmt(query)
+            .and_then(|stmt| {
+                check_catalog_access(&stmt, &query_ctx)?;
+                self.check_privileges(&stmt, &query_ctx)?;
+                Ok(stmt)
+            })
             .map_err(BoxedError::new)
             .context(server_error::ExecuteQuerySnafu { query })?;
 
         match stmt {
             Statement::CreateDatabase(_)
+            | Statement::CreateCatalog(_)
+            | Statement::DropCatalog(_)
+            | Statement::CreateUser(_)
+            | Statement::CreateRole(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_)
             | Statement::ShowDatabases(_)
             | Statement::CreateTable(_)
             | Statement::ShowTables(_)
@@ -524,7 +629,7 @@ impl SqlQueryHandler for Instance {
             Statement::Insert(insert) => match self.mode {
                 Mode::Standalone => {
                     let (catalog_name, schema_name, table_name) = insert
-                        .full_table_name()
+                        .full_table_name(&query_ctx)
                         .context(error::ParseSqlSnafu)
                         .map_err(BoxedError::new)
                         .context(server_error::ExecuteInsertSnafu {
@@ -532,11 +637,12 @@ impl SqlQueryHandler for Instance {
                         })?;
 
                     let (columns, row_count) = self
-                        .stmt_to_insert_batch(&catalog_name, &schema_name, insert)
+                        .stmt_to_insert_batch(&catalog_name, &schema_name, insert, query_ctx)
                         .map_err(BoxedError::new)
                         .context(server_error::ExecuteQuerySnafu { query })?;
 
                     let expr = InsertExpr {
+                        catalog_name,
                         schema_name,
                         table_name,
                         region_number: 0,
@@ -547,7 +653,7 @@ impl SqlQueryHandler for Instance {
                 }
                 Mode::Distributed => {
                     let affected = self
-                        .sql_dist_insert(insert)
+                        .sql_dist_insert(insert, query_ctx)
                         .await
                         .map_err(BoxedError::new)
                         .context(server_error::ExecuteInsertSnafu {
@@ -557,9 +663,16 @@ impl SqlQueryHandler for Instance {
                 }
             },
             Statement::Alter(alter_stmt) => {
-                let expr = AlterExpr::try_from(alter_stmt)
+                let (catalog_name, schema_name, _) =
+                    table_idents_to_full_name_with_ctx(alter_stmt.table_name(), &query_ctx)
+                        .context(error::ParseSqlSnafu)
+                        .map_err(BoxedError::new)
+                        .context(server_error::ExecuteAlterSnafu { query })?;
+                let mut expr = AlterExpr::try_from(alter_stmt)
                     .map_err(BoxedError::new)
                     .context(server_error::ExecuteAlterSnafu { query })?;
+                expr.catalog_name = Some(catalog_name);
+                expr.schema_name = Some(schema_name);
                 let expr = AdminExpr {
                     header: Some(ExprHeader {
                         version: PROTOCOL_VERSION,
@@ -584,10 +697,14 @@ impl SqlQueryHandler for Instance {
                 let result = self.grpc_admin_handler.exec_admin_request(expr).await?;
                 admin_result_to_output(result).context(error::InvalidAdminResultSnafu)
             }
-            Statement::ShowCreateTable(_) => {
+            // Processes are listed and killed by the `ProcessManager` wrapping the instance.
+            Statement::ShowCreateTable(_) | Statement::ShowProcesslist(_) | Statement::Kill(_) => {
                 return server_error::NotSupportedSnafu { feat: query }.fail();
             }
             Statement::Use(db) => self.handle_use(db, query_ctx),
+            Statement::SetVariables(set_variables) => {
+                self.handle_set_variables(set_variables, query_ctx)
+            }
         }
         .map_err(BoxedError::new)
         .context(server_error::ExecuteQuerySnafu { query })
@@ -607,9 +724,92 @@ impl ScriptHandler for Instance {
         }
     }
 
-    async fn execute_script(&self, script: &str) -> server_error::Result<Output> {
+    async fn execute_script(
+        &self,
+        script: &str,
+        params: HashMap<String, String>,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Output> {
+        if let Some(handler) = &self.script_handler {
+            handler.execute_script(script, params, query_ctx).await
+        } else {
+            server_error::NotSupportedSnafu {
+                feat: "Script execution in Frontend",
+            }
+            .fail()
+        }
+    }
+
+    async fn list_scripts(&self) -> server_error::Result<Vec<ScriptInfo>> {
         if let Some(handler) = &self.script_handler {
-            handler.execute_script(script).await
+            handler.list_scripts().await
+        } else {
+            server_error::NotSupportedSnafu {
+                feat: "Script execution in Frontend",
+            }
+            .fail()
+        }
+    }
+
+    async fn get_script(
+        &self,
+        name: &str,
+        version: Option<i64>,
+    ) -> server_error::Result<ScriptInfo> {
+        if let Some(handler) = &self.script_handler {
+            handler.get_script(name, version).await
+        } else {
+            server_error::NotSupportedSnafu {
+                feat: "Script execution in Frontend",
+            }
+            .fail()
+        }
+    }
+
+    async fn list_script_versions(&self, name: &str) -> server_error::Result<Vec<ScriptInfo>> {
+        if let Some(handler) = &self.script_handler {
+            handler.list_script_versions(name).await
+        } else {
+            server_error::NotSupportedSnafu {
+                feat: "Script execution in Frontend",
+            }
+            .fail()
+        }
+    }
+
+    async fn delete_script(&self, name: &str) -> server_error::Result<()> {
+        if let Some(handler) = &self.script_handler {
+            handler.delete_script(name).await
+        } else {
+            server_error::NotSupportedSnafu {
+                feat: "Script execution in Frontend",
+            }
+            .fail()
+        }
+    }
+
+    async fn schedule_script(
+        &self,
+        name: &str,
+        interval: Duration,
+        target_table: &str,
+        params: HashMap<String, String>,
+    ) -> server_error::Result<()> {
+        if let Some(handler) = &self.script_handler {
+            handler
+                .schedule_script(name, interval, target_table, params)
+                .await
+        } else {
+            server_error::NotSupportedSnafu {
+                feat: "Script execution in Frontend",
+            }
+            .fail()
+        }
+    }
+
+    async fn unschedule_script(&self, name: &str) -> server_error::Result<()> {
+        if let Some(handler) = &self.script_handler {
+            handler.unschedule_script(name).await
         } else {
             server_error::NotSupportedSnafu {
                 feat: "Script execution in Frontend",
@@ -621,7 +821,11 @@ impl ScriptHandler for Instance {
 
 #[async_trait]
 impl GrpcQueryHandler for Instance {
-    async fn do_query(&self, query: ObjectExpr) -> server_error::Result<GrpcObjectResult> {
+    async fn do_query(
+        &self,
+        query: ObjectExpr,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<GrpcObjectResult> {
         let expr = query
             .clone()
             .expr
@@ -630,6 +834,7 @@ impl GrpcQueryHandler for Instance {
             })?;
         match expr {
             Expr::Insert(insert_expr) => {
+                self.check_insert_exprs(std::slice::from_ref(&insert_expr), &query_ctx)?;
                 let output = self
                     .handle_insert(insert_expr.clone())
                     .await
@@ -645,7 +850,7 @@ impl GrpcQueryHandler for Instance {
                 };
                 Ok(object_result)
             }
-            _ => GrpcQueryHandler::do_query(&*self.grpc_query_handler, query).await,
+            _ => GrpcQueryHandler::do_query(&*self.grpc_query_handler, query, query_ctx).await,
         }
     }
 }
@@ -674,14 +879,13 @@ mod tests {
     };
     use datatypes::schema::ColumnDefaultConstraint;
     use datatypes::value::Value;
-    use session::context::QueryContext;
 
     use super::*;
     use crate::tests;
 
     #[tokio::test]
     async fn test_execute_sql() {
-        let query_ctx = Arc::new(QueryContext::new());
+        let query_ctx = tests::superuser_query_ctx();
 
         let instance = tests::create_frontend_instance().await;
 
@@ -788,6 +992,7 @@ mod tests {
             null_mask: vec![2],
             semantic_type: SemanticType::Field as i32,
             datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
         };
         let expected_mem_col = Column {
             column_name: "memory".to_string(),
@@ -798,6 +1003,7 @@ mod tests {
             null_mask: vec![4],
             semantic_type: SemanticType::Field as i32,
             datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
         };
         let expected_disk_col = Column {
             column_name: "disk_util".to_string(),
@@ -846,6 +1052,7 @@ mod tests {
         ];
         let row_count = 4;
         let insert_expr = InsertExpr {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
             schema_name: "public".to_string(),
             table_name: "demo".to_string(),
             region_number: 0,
@@ -856,9 +1063,10 @@ mod tests {
             header: Some(ExprHeader::default()),
             expr: Some(object_expr::Expr::Insert(insert_expr)),
         };
-        let result = GrpcQueryHandler::do_query(&*instance, object_expr)
-            .await
-            .unwrap();
+        let result =
+            GrpcQueryHandler::do_query(&*instance, object_expr, tests::superuser_query_ctx())
+                .await
+                .unwrap();
         assert_matches!(
             result.result,
             Some(object_result::Result::Mutate(MutateResult {
@@ -874,9 +1082,10 @@ mod tests {
                 expr: Some(select_expr::Expr::Sql("select * from demo".to_string())),
             })),
         };
-        let result = GrpcQueryHandler::do_query(&*instance, object_expr)
-            .await
-            .unwrap();
+        let result =
+            GrpcQueryHandler::do_query(&*instance, object_expr, tests::superuser_query_ctx())
+                .await
+                .unwrap();
         match result.result {
             Some(object_result::Result::Select(select_result)) => {
                 let select_result: SelectResult = (*select_result.raw_data).try_into().unwrap();
@@ -909,18 +1118,21 @@ mod tests {
                 datatype: ColumnDataType::String as i32,
                 is_nullable: false,
                 default_constraint: None,
+                datatype_extension: None,
             },
             GrpcColumnDef {
                 name: "cpu".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             GrpcColumnDef {
                 name: "memory".to_string(),
                 datatype: ColumnDataType::Float64 as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
             GrpcColumnDef {
                 name: "disk_util".to_string(),
@@ -931,12 +1143,14 @@ mod tests {
                         .try_into()
                         .unwrap(),
                 ),
+                datatype_extension: None,
             },
             GrpcColumnDef {
                 name: "ts".to_string(),
                 datatype: ColumnDataType::Timestamp as i32,
                 is_nullable: true,
                 default_constraint: None,
+                datatype_extension: None,
             },
         ];
         CreateExpr {
@@ -18,12 +18,14 @@ use std::sync::Arc;
 use api::helper::ColumnDataTypeWrapper;
 use api::result::AdminResultBuilder;
 use api::v1::{
-    admin_expr, AdminExpr, AdminResult, AlterExpr, CreateDatabaseExpr, CreateExpr, ObjectExpr,
-    ObjectResult,
+    admin_expr, alter_expr, AdminExpr, AdminResult, AlterExpr, CreateDatabaseExpr, CreateExpr,
+    ObjectExpr, ObjectResult, RenameTable,
 };
 use async_trait::async_trait;
-use catalog::helper::{SchemaKey, SchemaValue, TableGlobalKey, TableGlobalValue};
-use catalog::CatalogList;
+use catalog::helper::{
+    CatalogKey, CatalogValue, SchemaKey, SchemaValue, TableGlobalKey, TableGlobalValue,
+};
+use catalog::{CatalogList, CatalogManager, RenameTableRequest};
 use chrono::DateTime;
 use client::admin::{admin_result_to_output, Admin};
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
@@ -53,8 +55,8 @@ use crate::catalog::FrontendCatalogManager;
 use crate::datanode::DatanodeClients;
 use crate::error::{
     self, CatalogEntrySerdeSnafu, CatalogNotFoundSnafu, CatalogSnafu, ColumnDataTypeSnafu,
-    PrimaryKeyNotFoundSnafu, RequestMetaSnafu, Result, SchemaNotFoundSnafu, StartMetaClientSnafu,
-    TableNotFoundSnafu,
+    NotSupportedSnafu, PrimaryKeyNotFoundSnafu, RequestMetaSnafu, Result, SchemaNotFoundSnafu,
+    StartMetaClientSnafu, TableNotFoundSnafu,
 };
 use crate::expr_factory::{CreateExprFactory, DefaultCreateExprFactory};
 use crate::instance::parse_stmt;
@@ -154,14 +156,47 @@ impl DistInstance {
                 let expr = CreateDatabaseExpr {
                     database_name: stmt.name.to_string(),
                 };
-                self.handle_create_database(expr).await?;
+                let catalog_name = query_ctx
+                    .current_catalog()
+                    .unwrap_or_else(|| DEFAULT_CATALOG_NAME.to_string());
+                self.handle_create_database(&catalog_name, expr).await?;
+                Ok(Output::AffectedRows(1))
+            }
+            Statement::CreateCatalog(stmt) => {
+                let catalog_name = stmt.name.to_string();
+                ensure!(
+                    query_ctx.tenant().is_none(),
+                    error::AccessDeniedSnafu {
+                        catalog: &catalog_name,
+                    }
+                );
+                self.handle_create_catalog(catalog_name).await?;
                 Ok(Output::AffectedRows(1))
             }
+            Statement::DropCatalog(_) => {
+                return NotSupportedSnafu {
+                    feat: "DROP CATALOG in distributed mode",
+                }
+                .fail();
+            }
+            Statement::CreateUser(_)
+            | Statement::CreateRole(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_) => {
+                return NotSupportedSnafu {
+                    feat: "Users and roles in distributed mode",
+                }
+                .fail();
+            }
             Statement::CreateTable(stmt) => {
-                let create_expr = &mut DefaultCreateExprFactory.create_expr_by_stmt(&stmt).await?;
+                let create_expr = &mut DefaultCreateExprFactory
+                    .create_expr_by_stmt(&stmt, &query_ctx)
+                    .await?;
                 Ok(self.create_table(create_expr, stmt.partitions).await?)
             }
-            Statement::ShowDatabases(stmt) => show_databases(stmt, self.catalog_manager.clone()),
+            Statement::ShowDatabases(stmt) => {
+                show_databases(stmt, self.catalog_manager.clone(), query_ctx)
+            }
             Statement::ShowTables(stmt) => {
                 show_tables(stmt, self.catalog_manager.clone(), query_ctx)
             }
@@ -174,10 +209,35 @@ impl DistInstance {
         .context(error::ExecuteSqlSnafu { sql })
     }
 
+    /// Handles distributed catalog creation, the catalog is created with a default schema.
+    async fn handle_create_catalog(&self, catalog_name: String) -> Result<()> {
+        let client = self
+            .meta_client
+            .store_client()
+            .context(StartMetaClientSnafu)?;
+
+        let key = CatalogKey {
+            catalog_name: catalog_name.clone(),
+        };
+        let request = PutRequest::default()
+            .with_key(key.to_string())
+            .with_value(CatalogValue {}.as_bytes().context(CatalogEntrySerdeSnafu)?);
+        client.put(request.into()).await.context(RequestMetaSnafu)?;
+
+        let expr = CreateDatabaseExpr {
+            database_name: DEFAULT_SCHEMA_NAME.to_string(),
+        };
+        self.handle_create_database(&catalog_name, expr).await
+    }
+
     /// Handles distributed database creation
-    async fn handle_create_database(&self, expr: CreateDatabaseExpr) -> Result<()> {
+    async fn handle_create_database(
+        &self,
+        catalog_name: &str,
+        expr: CreateDatabaseExpr,
+    ) -> Result<()> {
         let key = SchemaKey {
-            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            catalog_name: catalog_name.to_string(),
             schema_name: expr.database_name,
         };
         let value = SchemaValue {};
@@ -216,8 +276,43 @@ impl DistInstance {
         let dist_table = table
             .as_any()
             .downcast_ref::<DistTable>()
-            .expect("Table impl must be DistTable in distributed mode");
-        dist_table.alter_by_expr(expr).await?;
+            .context(NotSupportedSnafu {
+                feat: format!("altering table {}", table_name),
+            })?;
+
+        let new_table_name = match &expr.kind {
+            Some(alter_expr::Kind::RenameTable(rename)) => rename.new_table_name.clone(),
+            _ => {
+                dist_table.alter_by_expr(expr).await?;
+                return Ok(AdminResultBuilder::default().mutate_result(0, 0).build());
+            }
+        };
+
+        // Renames the regions in datanodes first, then atomically moves the table metadata
+        // in meta-srv to the new name. The regions are renamed back if the latter fails, so
+        // the table is still accessible by the old name.
+        dist_table.alter_by_expr(expr.clone()).await?;
+        let request = RenameTableRequest {
+            catalog: catalog_name.to_string(),
+            schema: schema_name.to_string(),
+            table_name: table_name.to_string(),
+            new_table_name: new_table_name.clone(),
+            table_id: table.table_info().ident.table_id,
+        };
+        if let Err(e) = self.catalog_manager.rename_table(request).await {
+            let mut revert = expr.clone();
+            revert.table_name = new_table_name;
+            revert.kind = Some(alter_expr::Kind::RenameTable(RenameTable {
+                new_table_name: table_name.to_string(),
+            }));
+            if let Err(e) = dist_table.alter_by_expr(revert).await {
+                error!(
+                    "Failed to revert renaming table {}, error: {}",
+                    table_name, e
+                );
+            }
+            return Err(e).context(CatalogSnafu);
+        }
         Ok(AdminResultBuilder::default().mutate_result(0, 0).build())
     }
 
@@ -310,7 +405,11 @@ impl SqlQueryHandler for DistInstance {
 
 #[async_trait]
 impl GrpcQueryHandler for DistInstance {
-    async fn do_query(&self, _: ObjectExpr) -> server_error::Result<ObjectResult> {
+    async fn do_query(
+        &self,
+        _: ObjectExpr,
+        _: QueryContextRef,
+    ) -> server_error::Result<ObjectResult> {
         unimplemented!()
     }
 }
@@ -326,7 +425,7 @@ impl GrpcAdminHandler for DistInstance {
             })?;
         match expr {
             admin_expr::Expr::CreateDatabase(create_database) => self
-                .handle_create_database(create_database)
+                .handle_create_database(DEFAULT_CATALOG_NAME, create_database)
                 .await
                 .map(|_| AdminResultBuilder::default().mutate_result(1, 0).build()),
             admin_expr::Expr::Alter(alter) => self.handle_alter_table(alter).await,
@@ -475,7 +574,11 @@ fn find_partition_entries(
         for column in column_defs {
             let column_name = &column.name;
             let data_type = ConcreteDataType::from(
-                ColumnDataTypeWrapper::try_new(column.datatype).context(ColumnDataTypeSnafu)?,
+                ColumnDataTypeWrapper::try_new_with_extension(
+                    column.datatype,
+                    column.datatype_extension.clone(),
+                )
+                .context(ColumnDataTypeSnafu)?,
             );
             column_name_and_type.push((column_name, data_type));
         }
@@ -564,7 +667,7 @@ ENGINE=mito",
             match &result[0] {
                 Statement::CreateTable(c) => {
                     let expr = DefaultCreateExprFactory
-                        .create_expr_by_stmt(c)
+                        .create_expr_by_stmt(c, &QueryContext::new())
                         .await
                         .unwrap();
                     let partitions = parse_partitions(&expr, c.partitions.clone()).unwrap();
@@ -597,25 +700,20 @@ ENGINE=mito",
             .unwrap();
         match output {
             Output::RecordBatches(r) => {
-                let expected1 = vec![
+                let pretty = r.pretty_print();
+                let mut lines = pretty.lines().collect::<Vec<_>>();
+                // schemas are listed in arbitrary order
+                lines[3..6].sort_unstable();
+                let expected = vec![
                     "+---------------------+",
                     "| Schemas             |",
                     "+---------------------+",
+                    "| information_schema  |",
                     "| public              |",
                     "| test_show_databases |",
                     "+---------------------+",
                 ];
-                let expected2 = vec![
-                    "+---------------------+",
-                    "| Schemas             |",
-                    "+---------------------+",
-                    "| test_show_databases |",
-                    "| public              |",
-                    "+---------------------+",
-                ];
-                let pretty = r.pretty_print();
-                let lines = pretty.lines().collect::<Vec<_>>();
-                assert!(lines == expected1 || lines == expected2)
+                assert_eq!(expected, lines);
             }
             _ => unreachable!(),
         }
@@ -674,4 +772,71 @@ ENGINE=mito",
             assert_show_tables(x.clone()).await
         }
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rename_table() {
+        let (dist_instance, datanode_instances) = create_dist_instance().await;
+
+        let sql = "create database test_rename_table";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .unwrap();
+
+        let sql = "
+            CREATE TABLE greptime.test_rename_table.dist_numbers (
+                ts BIGINT,
+                n INT,
+                TIME INDEX (ts),
+            )
+            PARTITION BY RANGE COLUMNS (n) (
+                PARTITION r0 VALUES LESS THAN (10),
+                PARTITION r1 VALUES LESS THAN (MAXVALUE),
+            )
+            ENGINE=mito";
+        dist_instance
+            .handle_sql(sql, QueryContext::arc())
+            .await
+            .unwrap();
+
+        let expr = AlterExpr {
+            catalog_name: Some("greptime".to_string()),
+            schema_name: Some("test_rename_table".to_string()),
+            table_name: "dist_numbers".to_string(),
+            kind: Some(alter_expr::Kind::RenameTable(RenameTable {
+                new_table_name: "new_numbers".to_string(),
+            })),
+        };
+        dist_instance.handle_alter_table(expr).await.unwrap();
+
+        async fn assert_show_tables(instance: SqlQueryHandlerRef) {
+            let sql = "show tables in test_rename_table";
+            let output = instance.do_query(sql, QueryContext::arc()).await.unwrap();
+            match output {
+                Output::RecordBatches(r) => {
+                    let expected = vec![
+                        "+-------------+",
+                        "| Tables      |",
+                        "+-------------+",
+                        "| new_numbers |",
+                        "+-------------+",
+                    ];
+                    assert_eq!(r.pretty_print().lines().collect::<Vec<_>>(), expected);
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        assert_show_tables(Arc::new(dist_instance.clone())).await;
+        for x in datanode_instances.values() {
+            assert_show_tables(x.clone()).await
+        }
+
+        let table_routes = dist_instance.catalog_manager().table_routes();
+        let table_name = TableName::new("greptime", "test_rename_table", "new_numbers");
+        let route = table_routes.get_route(&table_name).await.unwrap();
+        assert_eq!(table_name, route.table.table_name);
+        let table_name = TableName::new("greptime", "test_rename_table", "dist_numbers");
+        assert!(table_routes.get_route(&table_name).await.is_err());
+    }
 }
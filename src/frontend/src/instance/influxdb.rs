@@ -18,23 +18,36 @@ use api::v1::{Column, InsertExpr};
 use async_trait::async_trait;
 use common_catalog::consts::DEFAULT_CATALOG_NAME;
 use common_error::prelude::BoxedError;
+use common_grpc::writer::Precision;
 use common_grpc_expr::column_to_vector;
+use common_telemetry::logging;
+use common_time::util::current_time_millis;
+use servers::influxdb::query::{
+    InfluxqlRequest, InfluxqlResponse, SelectStatement, Series, StatementResult,
+};
 use servers::influxdb::InfluxdbRequest;
 use servers::query_handler::InfluxdbLineProtocolHandler;
 use servers::{error as server_error, Mode};
+use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
 use table::requests::InsertRequest;
 
 use crate::error;
 use crate::error::{InsertBatchToRequestSnafu, Result};
+use crate::instance::privilege::ensure_current_catalog_accessible;
 use crate::instance::Instance;
 
 #[async_trait]
 impl InfluxdbLineProtocolHandler for Instance {
-    async fn exec(&self, request: &InfluxdbRequest) -> servers::error::Result<()> {
+    async fn exec(
+        &self,
+        request: &InfluxdbRequest,
+        query_ctx: QueryContextRef,
+    ) -> servers::error::Result<()> {
+        let mut exprs: Vec<InsertExpr> = request.try_into()?;
+        self.check_insert_privileges(&mut exprs, &query_ctx)?;
         match self.mode {
             Mode::Standalone => {
-                let exprs: Vec<InsertExpr> = request.try_into()?;
                 self.handle_inserts(exprs)
                     .await
                     .map_err(BoxedError::new)
@@ -43,7 +56,7 @@ impl InfluxdbLineProtocolHandler for Instance {
                     })?;
             }
             Mode::Distributed => {
-                self.dist_insert(request.try_into()?)
+                self.dist_insert(exprs)
                     .await
                     .map_err(BoxedError::new)
                     .context(server_error::ExecuteInsertSnafu {
@@ -54,27 +67,105 @@ impl InfluxdbLineProtocolHandler for Instance {
 
         Ok(())
     }
+
+    async fn query(
+        &self,
+        request: &InfluxqlRequest,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<InfluxqlResponse> {
+        let catalog = ensure_current_catalog_accessible(&query_ctx)?;
+        let now_nanos = current_time_millis() * 1_000_000;
+        let mut results = Vec::new();
+        for (statement_id, statement) in request.statements().into_iter().enumerate() {
+            let series = self
+                .query_influxql(
+                    &catalog,
+                    &request.db,
+                    statement,
+                    now_nanos,
+                    request.epoch,
+                    query_ctx.clone(),
+                )
+                .await;
+            results.push(StatementResult::new(statement_id, series));
+        }
+        Ok(InfluxqlResponse { results })
+    }
 }
 
 impl Instance {
+    async fn query_influxql(
+        &self,
+        catalog: &str,
+        db: &str,
+        statement: &str,
+        now_nanos: i64,
+        epoch: Option<Precision>,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Vec<Series>> {
+        let select = SelectStatement::parse(statement, now_nanos)?;
+        let tag_names = self.influxdb_tag_names(catalog, db, select.measurement())?;
+        let sql = select.to_sql(db, &tag_names);
+        logging::debug!("InfluxQL query, sql: {}", sql);
+
+        let select_result = self.select(sql, query_ctx).await?;
+        select.to_series(select_result, &tag_names, epoch)
+    }
+
+    /// Returns the tag names of the measurement in `db`, or empty if it doesn't exist, so that
+    /// querying it fails later.
+    fn influxdb_tag_names(
+        &self,
+        catalog: &str,
+        db: &str,
+        measurement: &str,
+    ) -> server_error::Result<Vec<String>> {
+        let table = self
+            .get_catalog(catalog)
+            .and_then(|catalog| Self::get_schema(catalog, db))
+            .and_then(|schema| schema.table(measurement).context(error::CatalogSnafu))
+            .map_err(BoxedError::new)
+            .with_context(|_| server_error::ExecuteQuerySnafu {
+                query: format!("find InfluxDB measurement {}.{}", db, measurement),
+            })?;
+        let table = match table {
+            Some(table) => table,
+            None => return Ok(vec![]),
+        };
+
+        // Tags of InfluxDB measurements are the primary key columns.
+        let table_info = table.table_info();
+        let column_schemas = table_info.meta.schema.column_schemas();
+        Ok(table_info
+            .meta
+            .primary_key_indices
+            .iter()
+            .map(|i| column_schemas[*i].name.clone())
+            .collect())
+    }
+
     pub(crate) async fn dist_insert(&self, inserts: Vec<InsertExpr>) -> Result<usize> {
         let mut joins = Vec::with_capacity(inserts.len());
-        let catalog_name = DEFAULT_CATALOG_NAME;
 
         for insert in inserts {
             let self_clone = self.clone();
 
+            let catalog_name = if insert.catalog_name.is_empty() {
+                DEFAULT_CATALOG_NAME.to_string()
+            } else {
+                insert.catalog_name.to_string()
+            };
             let schema_name = insert.schema_name.to_string();
             let table_name = insert.table_name.to_string();
 
             let columns = &insert.columns;
             let row_count = insert.row_count;
 
-            self.create_or_alter_table_on_demand(catalog_name, &schema_name, &table_name, columns)
+            self.create_or_alter_table_on_demand(&catalog_name, &schema_name, &table_name, columns)
                 .await?;
 
             let request = Self::columns_to_request(
-                catalog_name,
+                &catalog_name,
                 &schema_name,
                 &table_name,
                 columns,
@@ -84,7 +175,7 @@ impl Instance {
             // TODO(fys): need a separate runtime here
             let self_clone = self_clone.clone();
             let join = tokio::spawn(async move {
-                let catalog = self_clone.get_catalog(catalog_name)?;
+                let catalog = self_clone.get_catalog(&catalog_name)?;
                 let schema = Self::get_schema(catalog, &schema_name)?;
                 let table = schema
                     .table(&table_name)
@@ -125,3 +216,64 @@ impl Instance {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value as JsonValue;
+
+    use super::*;
+    use crate::tests;
+
+    #[tokio::test]
+    async fn test_influxql_query() {
+        let instance = tests::create_frontend_instance().await;
+        let request = InfluxdbRequest {
+            precision: None,
+            lines: "monitor,host=host1 cpu=1.0 1000000000
+monitor,host=host2 cpu=2.0 1000000000
+monitor,host=host1 cpu=3.0 2000000000
+monitor,host=host1 cpu=5.0 61000000000"
+                .to_string(),
+            db: "public".to_string(),
+        };
+        let query_ctx = tests::superuser_query_ctx();
+        instance.exec(&request, query_ctx.clone()).await.unwrap();
+
+        let request = InfluxqlRequest {
+            db: "public".to_string(),
+            query: "SELECT mean(cpu), count(cpu) FROM monitor WHERE time >= 0 AND time < 120s \
+                    AND host =~ /^host/ GROUP BY time(1m), host fill(none); \
+                    SELECT last(cpu) FROM monitor WHERE host = 'host1'; \
+                    SELECT cpu FROM monitor WHERE host = 'host1'; DROP TABLE monitor"
+                .to_string(),
+            epoch: Some(Precision::SECOND),
+        };
+        let response = InfluxdbLineProtocolHandler::query(instance.as_ref(), &request, query_ctx)
+            .await
+            .unwrap();
+        assert_eq!(4, response.results.len());
+
+        let series = &response.results[0].series;
+        assert_eq!(2, series.len());
+        assert_eq!("host1", series[0].tags["host"]);
+        assert_eq!(
+            vec![
+                vec![JsonValue::from(0), 2.0.into(), 2.into()],
+                vec![JsonValue::from(60), 5.0.into(), 1.into()],
+            ],
+            series[0].values
+        );
+        assert_eq!("host2", series[1].tags["host"]);
+        assert_eq!(
+            vec![vec![JsonValue::from(0), 2.0.into(), 1.into()]],
+            series[1].values
+        );
+
+        assert_eq!(
+            vec![vec![JsonValue::from(0), 5.0.into()]],
+            response.results[1].series[0].values
+        );
+        assert_eq!(3, response.results[2].series[0].values.len());
+        assert!(response.results[3].error.is_some());
+    }
+}
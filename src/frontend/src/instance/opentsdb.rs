@@ -13,25 +13,43 @@
 // limitations under the License.
 
 use async_trait::async_trait;
+use catalog::SchemaProviderRef;
+use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_error::prelude::BoxedError;
-use servers::opentsdb::codec::DataPoint;
+use common_telemetry::logging;
+use common_time::util::current_time_millis;
+use servers::opentsdb::codec::{
+    self, DataPoint, OPENTSDB_TIMESTAMP_COLUMN_NAME, OPENTSDB_VALUE_COLUMN_NAME,
+};
+use servers::opentsdb::query::{self, QueryRequest, QueryResult, SuggestRequest, SuggestType};
 use servers::query_handler::OpentsdbProtocolHandler;
 use servers::{error as server_error, Mode};
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 
+use crate::instance::privilege::ensure_current_catalog_accessible;
 use crate::instance::Instance;
 
 #[async_trait]
 impl OpentsdbProtocolHandler for Instance {
-    async fn exec(&self, data_point: &DataPoint) -> server_error::Result<()> {
-        // TODO(LFC): Insert metrics in batch, then make OpentsdbLineProtocolHandler::exec received multiple data points, when
-        // metric table and tags can be created upon insertion.
+    async fn exec(
+        &self,
+        data_points: &[DataPoint],
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<()> {
+        let mut exprs = codec::data_points_to_grpc_inserts(data_points)?;
+        self.check_insert_privileges(&mut exprs, &query_ctx)?;
         match self.mode {
             Mode::Standalone => {
-                self.insert_opentsdb_metric(data_point).await?;
+                self.handle_inserts(exprs)
+                    .await
+                    .map_err(BoxedError::new)
+                    .with_context(|_| server_error::ExecuteQuerySnafu {
+                        query: format!("{:?}", data_points),
+                    })?;
             }
             Mode::Distributed => {
-                self.dist_insert(vec![data_point.as_grpc_insert()])
+                self.dist_insert(exprs)
                     .await
                     .map_err(BoxedError::new)
                     .context(server_error::ExecuteInsertSnafu {
@@ -42,30 +60,141 @@ impl OpentsdbProtocolHandler for Instance {
 
         Ok(())
     }
+
+    async fn query(
+        &self,
+        request: &QueryRequest,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Vec<QueryResult>> {
+        let catalog = ensure_current_catalog_accessible(&query_ctx)?;
+        let (start, end) = request.time_range(current_time_millis())?;
+        let schema = self.opentsdb_schema(&catalog)?;
+
+        let mut results = Vec::with_capacity(request.queries.len());
+        for sub_query in &request.queries {
+            let tagks = match Self::opentsdb_tagks(&schema, &sub_query.metric)? {
+                Some(tagks) => tagks,
+                None => {
+                    return server_error::InvalidQuerySnafu {
+                        reason: format!("unknown metric: {}", sub_query.metric),
+                    }
+                    .fail()
+                }
+            };
+
+            let series_sql =
+                query::sub_query_to_series_sql(DEFAULT_SCHEMA_NAME, sub_query, &tagks, start, end)?;
+            let series = match series_sql {
+                Some(sql) => {
+                    logging::debug!("OpenTSDB series query, sql: {}", sql);
+                    Some(self.select(sql, query_ctx.clone()).await?)
+                }
+                None => None,
+            };
+            let sql = query::sub_query_to_sql(DEFAULT_SCHEMA_NAME, sub_query, &tagks, start, end)?;
+            logging::debug!("OpenTSDB query, sql: {}", sql);
+
+            let select_result = self.select(sql, query_ctx.clone()).await?;
+            results.extend(query::select_result_to_query_results(
+                sub_query,
+                series,
+                select_result,
+                request.ms_resolution,
+            )?);
+        }
+        Ok(results)
+    }
+
+    async fn suggest(
+        &self,
+        request: &SuggestRequest,
+        query_ctx: QueryContextRef,
+    ) -> server_error::Result<Vec<String>> {
+        let catalog = ensure_current_catalog_accessible(&query_ctx)?;
+        let schema = self.opentsdb_schema(&catalog)?;
+        let tables = schema.table_names().map_err(BoxedError::new).context(
+            server_error::ExecuteQuerySnafu {
+                query: "list OpenTSDB metrics",
+            },
+        )?;
+        if request.suggest_type == SuggestType::Metrics {
+            return Ok(request.suggest(tables));
+        }
+
+        let mut metrics = Vec::with_capacity(tables.len());
+        for table in tables {
+            if let Some(tagks) = Self::opentsdb_tagks(&schema, &table)? {
+                metrics.push((table, tagks));
+            }
+        }
+        if request.suggest_type == SuggestType::Tagk {
+            return Ok(request.suggest(metrics.into_iter().flat_map(|(_, tagks)| tagks)));
+        }
+
+        // Values of all the tags are looked up by a single query.
+        let sql = match query::tagv_suggest_sql(DEFAULT_SCHEMA_NAME, &metrics, request) {
+            Some(sql) => sql,
+            None => return Ok(vec![]),
+        };
+        logging::debug!("OpenTSDB suggest, sql: {}", sql);
+        let tagvs = self.select(sql, query_ctx).await?;
+        let tagvs = query::string_column_values(&tagvs, query::TAGV_COLUMN_NAME)?;
+        Ok(request.suggest(tagvs.into_iter().flatten()))
+    }
 }
 
 impl Instance {
-    async fn insert_opentsdb_metric(&self, data_point: &DataPoint) -> server_error::Result<()> {
-        let insert_expr = data_point.as_grpc_insert();
-        self.handle_insert(insert_expr)
-            .await
+    /// Returns the schema OpenTSDB metrics are written to in the catalog.
+    fn opentsdb_schema(&self, catalog: &str) -> server_error::Result<SchemaProviderRef> {
+        self.get_catalog(catalog)
+            .and_then(|catalog| Self::get_schema(catalog, DEFAULT_SCHEMA_NAME))
+            .map_err(BoxedError::new)
+            .context(server_error::ExecuteQuerySnafu {
+                query: "find OpenTSDB schema",
+            })
+    }
+
+    /// Returns the tag names of an OpenTSDB metric table, or `None` if the table doesn't exist or
+    /// is not created by OpenTSDB ingestion.
+    fn opentsdb_tagks(
+        schema: &SchemaProviderRef,
+        metric: &str,
+    ) -> server_error::Result<Option<Vec<String>>> {
+        let table = schema
+            .table(metric)
             .map_err(BoxedError::new)
             .with_context(|_| server_error::ExecuteQuerySnafu {
-                query: format!("{:?}", data_point),
+                query: format!("find OpenTSDB metric {}", metric),
             })?;
-        Ok(())
+        let table = match table {
+            Some(table) => table,
+            None => return Ok(None),
+        };
+
+        let table_schema = table.schema();
+        let columns = table_schema
+            .column_schemas()
+            .iter()
+            .map(|c| c.name.clone())
+            .collect::<Vec<_>>();
+        if !columns.iter().any(|c| c == OPENTSDB_VALUE_COLUMN_NAME) {
+            return Ok(None);
+        }
+        Ok(Some(
+            columns
+                .into_iter()
+                .filter(|c| c != OPENTSDB_TIMESTAMP_COLUMN_NAME && c != OPENTSDB_VALUE_COLUMN_NAME)
+                .collect(),
+        ))
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
-
     use common_query::Output;
     use common_recordbatch::RecordBatches;
     use datafusion::arrow_print;
     use servers::query_handler::SqlQueryHandler;
-    use session::context::QueryContext;
 
     use super::*;
     use crate::tests;
@@ -75,21 +204,22 @@ mod tests {
         let instance = tests::create_frontend_instance().await;
         instance
             .exec(
-                &DataPoint::try_create(
-                    "put sys.if.bytes.out 1479496100 1.3E3 host=web01 interface=eth0",
-                )
-                .unwrap(),
+                &[
+                    DataPoint::try_create(
+                        "put sys.if.bytes.out 1479496100 1.3E3 host=web01 interface=eth0",
+                    )
+                    .unwrap(),
+                    DataPoint::try_create("put sys.procs.running 1479496100 42 host=web01")
+                        .unwrap(),
+                ],
+                tests::superuser_query_ctx(),
             )
             .await
             .unwrap();
-        instance
-            .exec(&DataPoint::try_create("put sys.procs.running 1479496100 42 host=web01").unwrap())
-            .await
-            .unwrap();
     }
 
     #[tokio::test]
-    async fn test_insert_opentsdb_metric() {
+    async fn test_exec_create_and_alter_table() {
         let instance = tests::create_frontend_instance().await;
 
         let data_point1 = DataPoint::new(
@@ -102,7 +232,9 @@ mod tests {
             ],
         );
         // should create new table "my_metric_1" directly
-        let result = instance.insert_opentsdb_metric(&data_point1).await;
+        let result = instance
+            .exec(&[data_point1], tests::superuser_query_ctx())
+            .await;
         assert!(result.is_ok());
 
         let data_point2 = DataPoint::new(
@@ -114,17 +246,16 @@ mod tests {
                 ("tagk3".to_string(), "tagv3".to_string()),
             ],
         );
-        // should create new column "tagk3" directly
-        let result = instance.insert_opentsdb_metric(&data_point2).await;
-        assert!(result.is_ok());
-
         let data_point3 = DataPoint::new("my_metric_1".to_string(), 3000, 3.0, vec![]);
-        // should handle null tags properly
-        let result = instance.insert_opentsdb_metric(&data_point3).await;
+        // should create new column "tagk3" directly, and handle null tags properly in the same
+        // batch
+        let result = instance
+            .exec(&[data_point2, data_point3], tests::superuser_query_ctx())
+            .await;
         assert!(result.is_ok());
 
         let output = instance
-            .do_query("select * from my_metric_1", Arc::new(QueryContext::new()))
+            .do_query("select * from my_metric_1", tests::superuser_query_ctx())
             .await
             .unwrap();
         match output {
@@ -151,4 +282,127 @@ mod tests {
             _ => unreachable!(),
         };
     }
+
+    #[tokio::test]
+    async fn test_query_and_suggest() {
+        let instance = tests::create_frontend_instance().await;
+        let data_points = vec![
+            DataPoint::new(
+                "my_metric_2".to_string(),
+                1000,
+                1.0,
+                vec![("host".to_string(), "web01".to_string())],
+            ),
+            DataPoint::new(
+                "my_metric_2".to_string(),
+                1000,
+                2.0,
+                vec![("host".to_string(), "web02".to_string())],
+            ),
+            DataPoint::new(
+                "my_metric_2".to_string(),
+                2000,
+                3.0,
+                vec![("host".to_string(), "web01".to_string())],
+            ),
+        ];
+        let query_ctx = tests::superuser_query_ctx();
+        instance
+            .exec(&data_points, query_ctx.clone())
+            .await
+            .unwrap();
+
+        let request = serde_json::from_str::<QueryRequest>(
+            r#"{
+                "start": 0,
+                "end": 10000,
+                "msResolution": true,
+                "queries": [
+                    {"aggregator": "sum", "metric": "my_metric_2"},
+                    {"aggregator": "max", "metric": "my_metric_2", "tags": {"host": "web02"}}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let results =
+            OpentsdbProtocolHandler::query(instance.as_ref(), &request, query_ctx.clone())
+                .await
+                .unwrap();
+        assert_eq!(2, results.len());
+        assert_eq!(vec!["host".to_string()], results[0].aggregate_tags);
+        assert_eq!(
+            vec![(1000, 3.0), (2000, 3.0)],
+            results[0].dps.clone().into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!("web02", results[1].tags["host"]);
+        assert_eq!(
+            vec![(1000, 2.0)],
+            results[1].dps.clone().into_iter().collect::<Vec<_>>()
+        );
+
+        // rate of each series is computed before aggregation
+        let request = serde_json::from_str::<QueryRequest>(
+            r#"{
+                "start": 0,
+                "end": 10000,
+                "queries": [
+                    {"aggregator": "sum", "metric": "my_metric_2", "rate": true},
+                    {"aggregator": "none", "metric": "my_metric_2", "downsample": "10s-max"}
+                ]
+            }"#,
+        )
+        .unwrap();
+        let results =
+            OpentsdbProtocolHandler::query(instance.as_ref(), &request, query_ctx.clone())
+                .await
+                .unwrap();
+        assert_eq!(3, results.len());
+        assert_eq!(
+            vec![(2, 2.0)],
+            results[0].dps.clone().into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!("web01", results[1].tags["host"]);
+        assert_eq!(
+            vec![(0, 3.0)],
+            results[1].dps.clone().into_iter().collect::<Vec<_>>()
+        );
+        assert_eq!("web02", results[2].tags["host"]);
+        assert_eq!(
+            vec![(0, 2.0)],
+            results[2].dps.clone().into_iter().collect::<Vec<_>>()
+        );
+
+        let request = serde_json::from_str::<QueryRequest>(
+            r#"{"start": 0, "queries": [{"aggregator": "sum", "metric": "my_metric_0"}]}"#,
+        )
+        .unwrap();
+        assert!(
+            OpentsdbProtocolHandler::query(instance.as_ref(), &request, query_ctx.clone())
+                .await
+                .is_err()
+        );
+
+        let suggest = |suggest_type: &str, q: &str| {
+            serde_json::from_str::<SuggestRequest>(&format!(
+                r#"{{"type": "{}", "q": "{}"}}"#,
+                suggest_type, q
+            ))
+            .unwrap()
+        };
+        let suggestions = instance
+            .suggest(&suggest("metrics", "my_metric_2"), query_ctx.clone())
+            .await
+            .unwrap();
+        assert_eq!(vec!["my_metric_2".to_string()], suggestions);
+        let suggestions = instance
+            .suggest(&suggest("tagk", "ho"), query_ctx.clone())
+            .await
+            .unwrap();
+        assert_eq!(vec!["host".to_string()], suggestions);
+        let suggestions = instance
+            .suggest(&suggest("tagv", "web"), query_ctx)
+            .await
+            .unwrap();
+        assert_eq!(vec!["web01".to_string(), "web02".to_string()], suggestions);
+    }
 }
@@ -0,0 +1,472 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api::v1::InsertExpr;
+use catalog::privilege::PrivilegeManager;
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_catalog::privilege::{Privilege, SUPERUSER, WILDCARD};
+use common_error::prelude::BoxedError;
+use servers::error as server_error;
+use session::context::QueryContext;
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::ObjectName;
+use sql::statements::statement::Statement;
+
+use crate::error::{AccessDeniedSnafu, PermissionDeniedSnafu, Result};
+use crate::instance::Instance;
+
+/// Ensures the session can access the catalogs the statement touches, the session of a tenant
+/// can only access the catalog of its tenant.
+pub(crate) fn check_catalog_access(stmt: &Statement, query_ctx: &QueryContext) -> Result<()> {
+    let current_catalog = current_catalog(query_ctx);
+    let catalog_of = |name: &ObjectName| match &name.0[..] {
+        [catalog, _, _] => catalog.value.clone(),
+        _ => current_catalog.clone(),
+    };
+    let catalogs = match stmt {
+        Statement::Query(query) => query.table_names().iter().map(catalog_of).collect(),
+        Statement::Explain(explain) => explain.table_names().iter().map(catalog_of).collect(),
+        Statement::Insert(insert) => vec![catalog_of(insert.table_name())],
+        Statement::CreateTable(create) => vec![catalog_of(&create.name)],
+        Statement::Alter(alter) => vec![catalog_of(alter.table_name())],
+        Statement::DropTable(drop) => vec![drop.catalog_name.clone()],
+        Statement::DescribeTable(describe) => vec![describe.catalog_name.clone()],
+        Statement::CreateCatalog(create) => vec![create.name.to_string()],
+        Statement::DropCatalog(drop) => vec![drop.name.clone()],
+        Statement::CreateDatabase(_)
+        | Statement::ShowDatabases(_)
+        | Statement::ShowTables(_)
+        | Statement::ShowCreateTable(_)
+        | Statement::ShowProcesslist(_)
+        | Statement::Kill(_)
+        | Statement::CreateUser(_)
+        | Statement::CreateRole(_)
+        | Statement::Grant(_)
+        | Statement::Revoke(_)
+        | Statement::Use(_)
+        | Statement::SetVariables(_) => vec![current_catalog.clone()],
+    };
+    catalogs
+        .iter()
+        .try_for_each(|catalog| ensure_catalog_accessible(catalog, query_ctx))
+}
+
+/// Returns the current catalog of the session, where protocols like InfluxDB, OpenTSDB and
+/// Prometheus look up and write their tables.
+pub(crate) fn current_catalog(query_ctx: &QueryContext) -> String {
+    query_ctx
+        .current_catalog()
+        .unwrap_or_else(|| DEFAULT_CATALOG_NAME.to_string())
+}
+
+/// Ensures the session can access its current catalog, and returns the catalog.
+pub(crate) fn ensure_current_catalog_accessible(
+    query_ctx: &QueryContext,
+) -> server_error::Result<String> {
+    let catalog = current_catalog(query_ctx);
+    ensure_catalog_accessible(&catalog, query_ctx)
+        .map_err(BoxedError::new)
+        .with_context(|_| server_error::ExecuteQuerySnafu {
+            query: format!("access catalog {}", catalog),
+        })?;
+    Ok(catalog)
+}
+
+pub(crate) fn ensure_catalog_accessible(catalog: &str, query_ctx: &QueryContext) -> Result<()> {
+    ensure!(
+        query_ctx.is_catalog_accessible(catalog),
+        AccessDeniedSnafu { catalog }
+    );
+    Ok(())
+}
+
+impl Instance {
+    /// Checks whether the user of the session has the privileges to execute the statement.
+    pub(crate) fn check_privileges(
+        &self,
+        stmt: &Statement,
+        query_ctx: &QueryContext,
+    ) -> Result<()> {
+        let checker = match self.privilege_checker(query_ctx)? {
+            Some(checker) => checker,
+            None => return Ok(()),
+        };
+
+        match stmt {
+            Statement::Query(query) => query
+                .table_names()
+                .iter()
+                .try_for_each(|name| checker.check_table(Privilege::Select, name)),
+            Statement::Explain(explain) => explain
+                .table_names()
+                .iter()
+                .try_for_each(|name| checker.check_table(Privilege::Select, name)),
+            Statement::Insert(insert) => {
+                checker.check_table(Privilege::Insert, insert.table_name())
+            }
+            Statement::CreateTable(create) => checker.check_table(Privilege::Create, &create.name),
+            Statement::CreateDatabase(create) => {
+                checker.check(Privilege::Create, &create.name.to_string(), WILDCARD)
+            }
+            Statement::Alter(alter) => checker.check_table(Privilege::Alter, alter.table_name()),
+            Statement::DropTable(drop) => {
+                checker.check(Privilege::Drop, &drop.schema_name, &drop.table_name)
+            }
+            Statement::CreateCatalog(_) | Statement::DropCatalog(_) => {
+                checker.deny("manage catalogs")
+            }
+            Statement::CreateUser(_)
+            | Statement::CreateRole(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_) => checker.deny("manage users and roles"),
+            Statement::ShowDatabases(_)
+            | Statement::ShowTables(_)
+            | Statement::ShowCreateTable(_)
+            | Statement::ShowProcesslist(_)
+            | Statement::Kill(_)
+            | Statement::DescribeTable(_)
+            | Statement::Use(_)
+            | Statement::SetVariables(_) => Ok(()),
+        }
+    }
+
+    /// Checks the INSERT privileges on tables written by protocols like InfluxDB, OpenTSDB
+    /// and Prometheus, and moves the insert exprs to the current catalog of the session, as
+    /// these protocols only name the schemas.
+    pub(crate) fn check_insert_privileges(
+        &self,
+        exprs: &mut [InsertExpr],
+        query_ctx: &QueryContext,
+    ) -> server_error::Result<()> {
+        let catalog = current_catalog(query_ctx);
+        for expr in exprs.iter_mut() {
+            expr.catalog_name = catalog.clone();
+        }
+        self.check_insert_exprs(exprs, query_ctx)
+    }
+
+    /// Checks whether the session can access the catalogs of the insert exprs and has the
+    /// INSERT privileges on their tables, exprs without catalogs write the default catalog.
+    pub(crate) fn check_insert_exprs(
+        &self,
+        exprs: &[InsertExpr],
+        query_ctx: &QueryContext,
+    ) -> server_error::Result<()> {
+        exprs
+            .iter()
+            .try_for_each(|expr| {
+                let catalog = if expr.catalog_name.is_empty() {
+                    DEFAULT_CATALOG_NAME
+                } else {
+                    &expr.catalog_name
+                };
+                ensure_catalog_accessible(catalog, query_ctx)
+            })
+            .and_then(|_| self.privilege_checker(query_ctx))
+            .and_then(|checker| match checker {
+                Some(checker) => exprs.iter().try_for_each(|expr| {
+                    checker.check(Privilege::Insert, &expr.schema_name, &expr.table_name)
+                }),
+                None => Ok(()),
+            })
+            .map_err(BoxedError::new)
+            .context(server_error::ExecuteInsertSnafu {
+                msg: "permission denied",
+            })
+    }
+
+    /// Returns the checker of the session, or `None` if the privileges of the session don't
+    /// need checking. Sessions without users are denied unless they are internal ones, servers
+    /// always bind the user to the sessions they create.
+    fn privilege_checker(&self, query_ctx: &QueryContext) -> Result<Option<PrivilegeChecker>> {
+        let manager = match self.privilege_manager.as_deref() {
+            Some(manager) => manager,
+            None => return Ok(None),
+        };
+        let user = match query_ctx.current_user() {
+            Some(user) => user,
+            None if query_ctx.is_internal() => return Ok(None),
+            None => {
+                return PermissionDeniedSnafu {
+                    user: "anonymous",
+                    action: "access without a user",
+                }
+                .fail()
+            }
+        };
+        if user == SUPERUSER {
+            return Ok(None);
+        }
+        Ok(Some(PrivilegeChecker {
+            manager,
+            tenant: query_ctx.tenant(),
+            user,
+            current_schema: query_ctx
+                .current_schema()
+                .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string()),
+        }))
+    }
+}
+
+struct PrivilegeChecker<'a> {
+    manager: &'a PrivilegeManager,
+    tenant: Option<String>,
+    user: String,
+    current_schema: String,
+}
+
+impl PrivilegeChecker<'_> {
+    fn check(&self, privilege: Privilege, schema: &str, table: &str) -> Result<()> {
+        ensure!(
+            self.manager
+                .check(self.tenant.as_deref(), &self.user, privilege, schema, table),
+            PermissionDeniedSnafu {
+                user: &self.user,
+                action: format!("{} on {}.{}", privilege, schema, table),
+            }
+        );
+        Ok(())
+    }
+
+    /// Checks the privilege on a maybe qualified table name, privileges are granted on
+    /// `schema.table` regardless of catalogs.
+    fn check_table(&self, privilege: Privilege, name: &ObjectName) -> Result<()> {
+        match &name.0[..] {
+            [table] => self.check(privilege, &self.current_schema, &table.value),
+            [.., schema, table] => self.check(privilege, &schema.value, &table.value),
+            [] => Ok(()),
+        }
+    }
+
+    fn deny(&self, action: &str) -> Result<()> {
+        PermissionDeniedSnafu {
+            user: &self.user,
+            action,
+        }
+        .fail()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use api::v1::object_expr::Expr;
+    use api::v1::{ExprHeader, ObjectExpr};
+    use common_query::Output;
+    use servers::influxdb::query::InfluxqlRequest;
+    use servers::influxdb::InfluxdbRequest;
+    use servers::query_handler::{GrpcQueryHandler, InfluxdbLineProtocolHandler, SqlQueryHandler};
+    use session::context::QueryContextRef;
+
+    use super::*;
+    use crate::tests;
+
+    async fn execute(instance: &Instance, sql: &str, query_ctx: &QueryContextRef) -> bool {
+        SqlQueryHandler::do_query(instance, sql, query_ctx.clone())
+            .await
+            .is_ok()
+    }
+
+    #[tokio::test]
+    async fn test_read_only_user() {
+        let instance = tests::create_frontend_instance().await;
+        let admin_ctx = tests::superuser_query_ctx();
+        for sql in [
+            "CREATE TABLE dashboard_demo(host STRING, ts TIMESTAMP, cpu DOUBLE NULL, \
+             TIME INDEX (ts), PRIMARY KEY(host)) engine=mito with(regions=1)",
+            "INSERT INTO dashboard_demo(host, cpu, ts) VALUES ('host1', 1.1, 1000)",
+            "CREATE USER dashboard",
+            "CREATE ROLE reader",
+            "GRANT SELECT ON public.* TO reader",
+            "GRANT reader TO dashboard",
+        ] {
+            assert!(execute(&instance, sql, &admin_ctx).await, "{}", sql);
+        }
+
+        let ctx = Arc::new(QueryContext::new());
+        ctx.set_current_user("dashboard");
+        let output =
+            SqlQueryHandler::do_query(&*instance, "SELECT * FROM dashboard_demo", ctx.clone())
+                .await
+                .unwrap();
+        assert!(matches!(
+            output,
+            Output::Stream(_) | Output::RecordBatches(_)
+        ));
+
+        for sql in [
+            "DROP TABLE dashboard_demo",
+            "INSERT INTO dashboard_demo(host, cpu, ts) VALUES ('host2', 2.2, 2000)",
+            "ALTER TABLE dashboard_demo ADD COLUMN memory DOUBLE",
+            "SELECT * FROM other.cpu",
+            "CREATE USER another",
+            "GRANT ALL ON *.* TO reader",
+        ] {
+            let err = SqlQueryHandler::do_query(&*instance, sql, ctx.clone())
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Permission denied"), "{}", err);
+        }
+
+        let request = InfluxdbRequest {
+            precision: None,
+            lines: "dashboard_metrics,host=host3 cpu=3.3 3000000000".to_string(),
+            db: "public".to_string(),
+        };
+        let err = InfluxdbLineProtocolHandler::exec(&*instance, &request, ctx.clone())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Permission denied"), "{}", err);
+
+        // Privileges take effect immediately after granted.
+        assert!(
+            execute(
+                &instance,
+                "GRANT INSERT ON dashboard_metrics TO reader",
+                &admin_ctx
+            )
+            .await
+        );
+        InfluxdbLineProtocolHandler::exec(&*instance, &request, ctx.clone())
+            .await
+            .unwrap();
+        assert!(!execute(&instance, "DROP TABLE dashboard_demo", &ctx).await);
+    }
+
+    #[tokio::test]
+    async fn test_deny_unmanaged_user() {
+        let instance = tests::create_frontend_instance().await;
+        let admin_ctx = tests::superuser_query_ctx();
+        assert!(
+            execute(
+                &instance,
+                "CREATE TABLE secret(host STRING, ts TIMESTAMP, TIME INDEX (ts))",
+                &admin_ctx
+            )
+            .await
+        );
+
+        let guest_ctx = Arc::new(QueryContext::new());
+        guest_ctx.set_current_user("guest");
+        for ctx in [guest_ctx.clone(), QueryContext::arc()] {
+            let err = SqlQueryHandler::do_query(&*instance, "SELECT * FROM secret", ctx)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Permission denied"), "{}", err);
+        }
+
+        // Tables are checked in planning too, whatever references them.
+        let err = instance
+            .sql_handler
+            .do_query(
+                "SELECT * FROM (SELECT host FROM secret) AS t ORDER BY (SELECT 1)",
+                guest_ctx.clone(),
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Permission denied"), "{}", err);
+
+        // So are reads of protocols.
+        let request = InfluxqlRequest {
+            db: "public".to_string(),
+            query: "SELECT * FROM secret".to_string(),
+            epoch: None,
+        };
+        let response = InfluxdbLineProtocolHandler::query(&*instance, &request, guest_ctx.clone())
+            .await
+            .unwrap();
+        let err = response.results[0].error.as_ref().unwrap();
+        assert!(err.contains("Permission denied"), "{}", err);
+
+        // And writes of gRPC, only internal sessions are trusted without users.
+        let insert = ObjectExpr {
+            header: Some(ExprHeader::default()),
+            expr: Some(Expr::Insert(InsertExpr {
+                schema_name: "public".to_string(),
+                table_name: "secret".to_string(),
+                ..Default::default()
+            })),
+        };
+        for ctx in [guest_ctx, QueryContext::arc()] {
+            let err = GrpcQueryHandler::do_query(&*instance, insert.clone(), ctx)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("Permission denied"), "{}", err);
+        }
+        let result =
+            GrpcQueryHandler::do_query(&*instance, insert, QueryContext::internal_arc()).await;
+        if let Err(err) = result {
+            assert!(!err.to_string().contains("Permission denied"), "{}", err);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tenant_catalog_access() {
+        let instance = tests::create_frontend_instance().await;
+        let ctx = Arc::new(QueryContext::new());
+        ctx.set_tenant("team_a");
+
+        for sql in [
+            "SELECT * FROM greptime.public.demo",
+            "SELECT * FROM (SELECT * FROM greptime.public.demo) AS t",
+            "EXPLAIN SELECT * FROM greptime.public.demo",
+            "INSERT INTO greptime.public.demo(host, ts) VALUES ('host1', 1000)",
+            "CREATE TABLE greptime.public.demo(host STRING, ts TIMESTAMP, TIME INDEX (ts))",
+            "ALTER TABLE greptime.public.demo ADD COLUMN cpu DOUBLE",
+            "DROP TABLE greptime.public.demo",
+            "DESCRIBE TABLE greptime.public.demo",
+            "CREATE CATALOG team_b",
+            "DROP CATALOG greptime",
+        ] {
+            let err = SqlQueryHandler::do_query(&*instance, sql, ctx.clone())
+                .await
+                .unwrap_err();
+            assert!(
+                err.to_string().contains("Access denied"),
+                "{}: {}",
+                sql,
+                err
+            );
+        }
+
+        // Protocols write to the current catalog of the session.
+        let request = InfluxdbRequest {
+            precision: None,
+            lines: "monitor,host=host1 cpu=1.1 1000000000".to_string(),
+            db: "public".to_string(),
+        };
+        let err = InfluxdbLineProtocolHandler::exec(&*instance, &request, ctx.clone())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Access denied"), "{}", err);
+
+        let admin_ctx = tests::superuser_query_ctx();
+        assert!(execute(&instance, "CREATE CATALOG team_a", &admin_ctx).await);
+        ctx.set_current_user(SUPERUSER);
+        ctx.set_current_catalog("team_a");
+        InfluxdbLineProtocolHandler::exec(&*instance, &request, ctx.clone())
+            .await
+            .unwrap();
+        assert!(execute(&instance, "SELECT * FROM team_a.public.monitor", &ctx).await);
+        assert!(
+            !execute(
+                &instance,
+                "SELECT * FROM greptime.public.monitor",
+                &admin_ctx
+            )
+            .await
+        );
+    }
+}
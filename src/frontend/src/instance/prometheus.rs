@@ -12,8 +12,6 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
-
 use api::prometheus::remote::read_request::ResponseType;
 use api::prometheus::remote::{Query, QueryResult, ReadRequest, ReadResponse, WriteRequest};
 use async_trait::async_trait;
@@ -24,9 +22,9 @@ use common_telemetry::logging;
 use prost::Message;
 use servers::error::{self, Result as ServerResult};
 use servers::prometheus::{self, Metrics};
-use servers::query_handler::{PrometheusProtocolHandler, PrometheusResponse};
+use servers::query_handler::{PrometheusProtocolHandler, PrometheusResponse, SqlQueryHandler};
 use servers::Mode;
-use session::context::QueryContext;
+use session::context::QueryContextRef;
 use snafu::{OptionExt, ResultExt};
 
 use crate::instance::Instance;
@@ -82,6 +80,7 @@ impl Instance {
         &self,
         db: &str,
         queries: &[Query],
+        query_ctx: QueryContextRef,
     ) -> ServerResult<Vec<(String, ObjectResult)>> {
         let mut results = Vec::with_capacity(queries.len());
 
@@ -93,8 +92,8 @@ impl Instance {
                 sql
             );
 
-            let query_ctx = Arc::new(QueryContext::with_current_schema(db.to_string()));
-            let output = self.sql_handler.do_query(&sql, query_ctx).await;
+            // Tables are qualified by `db` in the SQL, which is checked like other queries.
+            let output = SqlQueryHandler::do_query(self, &sql, query_ctx.clone()).await;
 
             let object_result = to_object_result(output)
                 .await
@@ -110,8 +109,14 @@ impl Instance {
 
 #[async_trait]
 impl PrometheusProtocolHandler for Instance {
-    async fn write(&self, database: &str, request: WriteRequest) -> ServerResult<()> {
-        let exprs = prometheus::write_request_to_insert_exprs(database, request.clone())?;
+    async fn write(
+        &self,
+        database: &str,
+        request: WriteRequest,
+        query_ctx: QueryContextRef,
+    ) -> ServerResult<()> {
+        let mut exprs = prometheus::write_request_to_insert_exprs(database, request.clone())?;
+        self.check_insert_privileges(&mut exprs, &query_ctx)?;
         match self.mode {
             Mode::Standalone => {
                 self.handle_inserts(exprs)
@@ -133,12 +138,17 @@ impl PrometheusProtocolHandler for Instance {
         Ok(())
     }
 
-    async fn read(&self, database: &str, request: ReadRequest) -> ServerResult<PrometheusResponse> {
+    async fn read(
+        &self,
+        database: &str,
+        request: ReadRequest,
+        query_ctx: QueryContextRef,
+    ) -> ServerResult<PrometheusResponse> {
         let response_type = negotiate_response_type(&request.accepted_response_types)?;
 
         // TODO(dennis): use read_hints to speedup query if possible
         let results = self
-            .handle_remote_queries(database, &request.queries)
+            .handle_remote_queries(database, &request.queries, query_ctx)
             .await?;
 
         match response_type {
@@ -201,7 +211,11 @@ mod tests {
             .await
             .unwrap();
 
-        instance.write(db, write_request).await.unwrap();
+        let query_ctx = tests::superuser_query_ctx();
+        instance
+            .write(db, write_request, query_ctx.clone())
+            .await
+            .unwrap();
 
         let read_request = ReadRequest {
             queries: vec![
@@ -236,7 +250,7 @@ mod tests {
             ..Default::default()
         };
 
-        let resp = instance.read(db, read_request).await.unwrap();
+        let resp = instance.read(db, read_request, query_ctx).await.unwrap();
         assert_eq!(resp.content_type, "application/x-protobuf");
         assert_eq!(resp.content_encoding, "snappy");
         let body = prometheus::snappy_decompress(&resp.body).unwrap();
@@ -23,6 +23,8 @@ use servers::http::HttpServer;
 use servers::mysql::server::MysqlServer;
 use servers::opentsdb::OpentsdbServer;
 use servers::postgres::PostgresServer;
+use servers::process::ProcessManager;
+use servers::query_handler::SqlQueryHandlerRef;
 use servers::server::Server;
 use snafu::ResultExt;
 use tokio::try_join;
@@ -45,6 +47,16 @@ impl Services {
         T: FrontendInstance,
     {
         info!("Starting frontend servers");
+        // Queries from MySQL, PostgreSQL, HTTP and gRPC are tracked, so that they can be killed.
+        let process_manager = Arc::new(
+            ProcessManager::new(
+                instance.clone(),
+                opts.query_options.clone().unwrap_or_default(),
+            )
+            .with_grpc_handler(instance.clone()),
+        );
+        let sql_handler: SqlQueryHandlerRef = process_manager.clone();
+
         let grpc_server_and_addr = if let Some(opts) = &opts.grpc_options {
             let grpc_addr = parse_addr(&opts.addr)?;
 
@@ -56,7 +68,13 @@ impl Services {
                     .context(error::RuntimeResourceSnafu)?,
             );
 
-            let grpc_server = GrpcServer::new(instance.clone(), instance.clone(), grpc_runtime);
+            let grpc_server = GrpcServer::new(
+                process_manager.clone(),
+                instance.clone(),
+                grpc_runtime,
+                opts.tls.clone(),
+                user_provider.clone(),
+            );
 
             Some((Box::new(grpc_server) as _, grpc_addr))
         } else {
@@ -75,7 +93,7 @@ impl Services {
             );
 
             let mysql_server = MysqlServer::create_server(
-                instance.clone(),
+                process_manager.clone(),
                 mysql_io_runtime,
                 opts.tls.clone(),
                 user_provider.clone(),
@@ -98,7 +116,7 @@ impl Services {
             );
 
             let pg_server = Box::new(PostgresServer::new(
-                instance.clone(),
+                sql_handler.clone(),
                 opts.check_pwd,
                 opts.tls.clone(),
                 pg_io_runtime,
@@ -131,7 +149,7 @@ impl Services {
         let http_server_and_addr = if let Some(http_options) = &opts.http_options {
             let http_addr = parse_addr(&http_options.addr)?;
 
-            let mut http_server = HttpServer::new(instance.clone(), http_options.clone());
+            let mut http_server = HttpServer::new(sql_handler, http_options.clone());
             if opentsdb_server_and_addr.is_some() {
                 http_server.set_opentsdb_handler(instance.clone());
             }
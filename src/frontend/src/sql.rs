@@ -14,8 +14,10 @@
 
 use catalog::SchemaProviderRef;
 use common_error::snafu::ensure;
+use common_time::TimeZone;
 use datatypes::prelude::ConcreteDataType;
 use datatypes::vectors::VectorBuilder;
+use session::context::QueryContext;
 use snafu::{OptionExt, ResultExt};
 use sql::ast::Value as SqlValue;
 use sql::statements;
@@ -29,11 +31,15 @@ use crate::error::{self, Result};
 pub(crate) fn insert_to_request(
     schema_provider: &SchemaProviderRef,
     stmt: Insert,
+    query_ctx: &QueryContext,
 ) -> Result<InsertRequest> {
     let columns = stmt.columns();
     let values = stmt.values().context(error::ParseSqlSnafu)?;
-    let (catalog_name, schema_name, table_name) =
-        stmt.full_table_name().context(error::ParseSqlSnafu)?;
+    let (catalog_name, schema_name, table_name) = stmt
+        .full_table_name(query_ctx)
+        .context(error::ParseSqlSnafu)?;
+    let time_zone = query_ctx.time_zone();
+    let time_zone = time_zone.as_ref();
 
     let table = schema_provider
         .table(&table_name)
@@ -90,7 +96,7 @@ pub(crate) fn insert_to_request(
         for (sql_val, (column_name, data_type, builder)) in
             row.iter().zip(columns_builders.iter_mut())
         {
-            add_row_to_vector(column_name, data_type, sql_val, builder)?;
+            add_row_to_vector(column_name, data_type, sql_val, time_zone, builder)?;
         }
     }
 
@@ -109,10 +115,12 @@ fn add_row_to_vector(
     column_name: &str,
     data_type: &ConcreteDataType,
     sql_val: &SqlValue,
+    time_zone: Option<&TimeZone>,
     builder: &mut VectorBuilder,
 ) -> Result<()> {
-    let value = statements::sql_value_to_value(column_name, data_type, sql_val)
-        .context(error::ParseSqlSnafu)?;
+    let value =
+        statements::sql_value_to_value_with_time_zone(column_name, data_type, sql_val, time_zone)
+            .context(error::ParseSqlSnafu)?;
     builder.push(&value);
 
     Ok(())
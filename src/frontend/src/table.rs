@@ -524,6 +524,7 @@ mod test {
     use meta_client::client::MetaClient;
     use meta_client::rpc::router::RegionRoute;
     use meta_client::rpc::{Region, Table, TableRoute};
+    use session::context::QueryContext;
     use sql::parser::ParserContext;
     use sql::statements::statement::Statement;
     use sqlparser::dialect::GenericDialect;
@@ -933,7 +934,7 @@ mod test {
         };
 
         let mut expr = DefaultCreateExprFactory
-            .create_expr_by_stmt(&create_table)
+            .create_expr_by_stmt(&create_table, &QueryContext::new())
             .await
             .unwrap();
         let _result = dist_instance
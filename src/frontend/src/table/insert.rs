@@ -106,17 +106,17 @@ pub fn insert_request_to_insert_batch(insert: &InsertRequest) -> Result<(Vec<Col
                 .context(error::ColumnDataTypeSnafu)?;
 
             // TODO(hl): need refactor
-            let semantic_type =
-                if vector.data_type() == ConcreteDataType::timestamp_millis_datatype() {
-                    SemanticType::Timestamp
-                } else {
-                    SemanticType::Field
-                };
+            let semantic_type = if matches!(vector.data_type(), ConcreteDataType::Timestamp(_)) {
+                SemanticType::Timestamp
+            } else {
+                SemanticType::Field
+            };
 
             let mut column = Column {
                 column_name: column_name.clone(),
                 semantic_type: semantic_type.into(),
                 datatype: datatype.datatype() as i32,
+                datatype_extension: datatype.datatype_extension(),
                 ..Default::default()
             };
 
@@ -134,6 +134,7 @@ fn to_insert_expr(region_number: RegionNumber, insert: InsertRequest) -> Result<
     let table_name = insert.table_name.clone();
     let (columns, row_count) = insert_request_to_insert_batch(&insert)?;
     Ok(InsertExpr {
+        catalog_name: insert.catalog_name,
         schema_name: insert.schema_name,
         table_name,
         region_number,
@@ -188,6 +189,7 @@ mod tests {
     }
 
     fn verify_insert_expr(insert_expr: InsertExpr) {
+        assert_eq!(DEFAULT_CATALOG_NAME, insert_expr.catalog_name);
         let table_name = insert_expr.table_name;
         assert_eq!("demo", table_name);
 
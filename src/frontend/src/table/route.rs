@@ -12,19 +12,46 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
 
+use catalog::helper::{
+    build_all_table_global_prefix, build_all_table_route_prefix, TableGlobalKey, TABLE_ROUTE_PREFIX,
+};
+use catalog::remote::{spawn_watch, WatchHandler};
+use common_telemetry::debug;
 use meta_client::client::MetaClient;
-use meta_client::rpc::{RouteRequest, TableName, TableRoute};
+use meta_client::rpc::{EventType, RenameRequest, RouteRequest, TableName, TableRoute};
 use moka::future::{Cache, CacheBuilder};
 use snafu::{ensure, ResultExt};
+use tokio::sync::Mutex;
 
 use crate::error::{self, Result};
 
+/// Versions of the cached routes, which are bumped on invalidation. A route fetched from
+/// `metasrv` is only cached if the version is unchanged during the fetch, otherwise the route
+/// may be fetched before the change and is stale.
+#[derive(Default)]
+struct RouteVersions {
+    /// Bumped when all the routes are invalidated.
+    epoch: u64,
+    tables: HashMap<TableName, u64>,
+}
+
+impl RouteVersions {
+    fn version(&self, table_name: &TableName) -> (u64, u64) {
+        (
+            self.epoch,
+            self.tables.get(table_name).copied().unwrap_or_default(),
+        )
+    }
+}
+
 pub(crate) struct TableRoutes {
     meta_client: Arc<MetaClient>,
     cache: Cache<TableName, Arc<TableRoute>>,
+    versions: Mutex<RouteVersions>,
 }
 
 impl TableRoutes {
@@ -35,19 +62,25 @@ impl TableRoutes {
                 .time_to_live(Duration::from_secs(30 * 60))
                 .time_to_idle(Duration::from_secs(5 * 60))
                 .build(),
+            versions: Mutex::new(RouteVersions::default()),
         }
     }
 
     pub(crate) async fn get_route(&self, table_name: &TableName) -> Result<Arc<TableRoute>> {
-        self.cache
-            .try_get_with_by_ref(table_name, self.get_from_meta(table_name))
-            .await
-            .map_err(|e| {
-                error::GetCacheSnafu {
-                    err_msg: format!("{:?}", e),
-                }
-                .build()
-            })
+        if let Some(route) = self.cache.get(table_name) {
+            return Ok(route);
+        }
+
+        let version = self.versions.lock().await.version(table_name);
+        let route = self.get_from_meta(table_name).await?;
+        // Holds the lock while inserting, so no invalidation happens after the check.
+        let versions = self.versions.lock().await;
+        if versions.version(table_name) == version {
+            self.cache.insert(table_name.clone(), route.clone()).await;
+        } else {
+            debug!("Route of table {} is changed while fetching", table_name);
+        }
+        Ok(route)
     }
 
     async fn get_from_meta(&self, table_name: &TableName) -> Result<Arc<TableRoute>> {
@@ -68,6 +101,61 @@ impl TableRoutes {
         Ok(Arc::new(route))
     }
 
+    pub(crate) async fn invalidate_table_route(&self, table_name: &TableName) {
+        let mut versions = self.versions.lock().await;
+        *versions.tables.entry(table_name.clone()).or_default() += 1;
+        self.cache.invalidate(table_name).await
+    }
+
+    async fn invalidate_all(&self) {
+        let mut versions = self.versions.lock().await;
+        versions.epoch += 1;
+        versions.tables.clear();
+        self.cache.invalidate_all();
+    }
+
+    /// Renames the table in `metasrv`, the cached routes of both the old and the new table
+    /// names are evicted.
+    pub(crate) async fn rename_route(
+        &self,
+        table_name: &TableName,
+        new_table_name: &str,
+    ) -> Result<()> {
+        let req = RenameRequest::new(table_name.clone(), new_table_name);
+        let _ = self
+            .meta_client
+            .rename_route(req)
+            .await
+            .context(error::RequestMetaSnafu)?;
+
+        self.invalidate_table_route(table_name).await;
+        let new_table_name = TableName::new(
+            &table_name.catalog_name,
+            &table_name.schema_name,
+            new_table_name,
+        );
+        self.invalidate_table_route(&new_table_name).await;
+        Ok(())
+    }
+
+    /// Starts watching the changes of table routes and table metadata in `metasrv`, the
+    /// cached route of a changed table is evicted as soon as the change is received.
+    pub(crate) fn start_watching(self: &Arc<Self>) {
+        for prefix in [
+            build_all_table_global_prefix(),
+            build_all_table_route_prefix(),
+        ] {
+            spawn_watch(self.meta_client.clone(), prefix, self.clone());
+        }
+    }
+
+    async fn invalidate_by_key(&self, key: &[u8]) {
+        if let Some(table_name) = parse_table_name(key) {
+            debug!("Invalidate cached route of table {}", table_name);
+            self.invalidate_table_route(&table_name).await;
+        }
+    }
+
     #[cfg(test)]
     pub(crate) async fn insert_table_route(
         &self,
@@ -77,3 +165,106 @@ impl TableRoutes {
         self.cache.insert(table_name, table_route).await
     }
 }
+
+#[async_trait::async_trait]
+impl WatchHandler for TableRoutes {
+    async fn handle(&self, _event_type: EventType, key: &[u8], _value: &[u8]) {
+        self.invalidate_by_key(key).await;
+    }
+
+    async fn reset(&self) {
+        self.invalidate_all().await;
+    }
+}
+
+/// Parses the table name from a table global key or a table route key.
+fn parse_table_name(key: &[u8]) -> Option<TableName> {
+    let key = std::str::from_utf8(key).ok()?;
+
+    if let Some(route_key) = key.strip_prefix(TABLE_ROUTE_PREFIX) {
+        // route key is in the form of "{prefix}-{catalog}-{schema}-{table}-{table_id}"
+        let parts = route_key.split('-').collect::<Vec<_>>();
+        return match parts[..] {
+            ["", catalog_name, schema_name, table_name, _] => {
+                Some(TableName::new(catalog_name, schema_name, table_name))
+            }
+            _ => None,
+        };
+    }
+
+    TableGlobalKey::parse(key).ok().map(|k| TableName {
+        catalog_name: k.catalog_name,
+        schema_name: k.schema_name,
+        table_name: k.table_name,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use meta_client::rpc::Table;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_table_name() {
+        let expected = Some(TableName::new("greptime", "public", "demo"));
+        assert_eq!(
+            expected,
+            parse_table_name(b"__meta_table_route-greptime-public-demo-1024")
+        );
+        assert_eq!(expected, parse_table_name(b"__tg-greptime-public-demo"));
+
+        assert!(parse_table_name(b"__meta_table_route-greptime-public").is_none());
+        assert!(parse_table_name(b"__tr-greptime-public-demo-1").is_none());
+        assert!(parse_table_name(b"__meta_seq-table_id").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_route_versions() {
+        let table_routes = TableRoutes::new(Arc::new(MetaClient::default()));
+        let table_name = TableName::new("greptime", "public", "demo");
+
+        let version = table_routes.versions.lock().await.version(&table_name);
+        table_routes.invalidate_table_route(&table_name).await;
+        let changed = table_routes.versions.lock().await.version(&table_name);
+        assert_ne!(version, changed);
+
+        table_routes.invalidate_all().await;
+        let reset = table_routes.versions.lock().await.version(&table_name);
+        assert_ne!(changed, reset);
+        // Other tables are not affected by the invalidation of a table.
+        let other = TableName::new("greptime", "public", "other");
+        let version = table_routes.versions.lock().await.version(&other);
+        table_routes.invalidate_table_route(&table_name).await;
+        assert_eq!(version, table_routes.versions.lock().await.version(&other));
+    }
+
+    #[tokio::test]
+    async fn test_invalidate_by_key() {
+        let table_routes = TableRoutes::new(Arc::new(MetaClient::default()));
+        let table_name = TableName::new("greptime", "public", "demo");
+        let table_route = Arc::new(TableRoute {
+            table: Table {
+                id: 1024,
+                table_name: table_name.clone(),
+                table_schema: vec![],
+            },
+            region_routes: vec![],
+        });
+
+        table_routes
+            .insert_table_route(table_name.clone(), table_route.clone())
+            .await;
+        assert!(table_routes.cache.get(&table_name).is_some());
+
+        table_routes
+            .invalidate_by_key(b"__tg-greptime-public-other")
+            .await;
+        assert!(table_routes.cache.get(&table_name).is_some());
+
+        table_routes
+            .invalidate_by_key(b"__meta_table_route-greptime-public-demo-1024")
+            .await;
+        assert!(table_routes.cache.get(&table_name).is_none());
+    }
+}
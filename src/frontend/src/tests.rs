@@ -18,6 +18,7 @@ use std::time::Duration;
 
 use catalog::remote::MetaKvBackend;
 use client::Client;
+use common_catalog::privilege::SUPERUSER;
 use common_grpc::channel_manager::ChannelManager;
 use common_runtime::Builder as RuntimeBuilder;
 use datanode::datanode::{DatanodeOptions, ObjectStoreConfig};
@@ -29,6 +30,7 @@ use meta_srv::mocks::MockInfo;
 use meta_srv::service::store::kv::KvStoreRef;
 use meta_srv::service::store::memory::MemStore;
 use servers::grpc::GrpcServer;
+use session::context::{QueryContext, QueryContextRef};
 use tempdir::TempDir;
 use tonic::transport::Server;
 use tower::service_fn;
@@ -47,12 +49,23 @@ async fn create_datanode_instance() -> Arc<DatanodeInstance> {
     instance
 }
 
+/// Creates a session of the superuser, which has all the privileges.
+pub(crate) fn superuser_query_ctx() -> QueryContextRef {
+    let query_ctx = QueryContext::arc();
+    query_ctx.set_current_user(SUPERUSER);
+    query_ctx
+}
+
 pub(crate) async fn create_frontend_instance() -> Arc<Instance> {
     let datanode_instance: Arc<DatanodeInstance> = create_datanode_instance().await;
     let dn_catalog_manager = datanode_instance.catalog_manager().clone();
+    let dn_privilege_manager = datanode_instance.privilege_manager();
 
     let mut frontend_instance = Instance::new_standalone(datanode_instance);
     frontend_instance.set_catalog_manager(dn_catalog_manager);
+    if let Some(privilege_manager) = dn_privilege_manager {
+        frontend_instance.set_privilege_manager(privilege_manager);
+    }
     Arc::new(frontend_instance)
 }
 
@@ -71,8 +84,14 @@ pub(crate) async fn create_datanode_client(
 
     // create a mock datanode grpc service, see example here:
     // https://github.com/hyperium/tonic/blob/master/examples/src/mock/mock.rs
-    let datanode_service =
-        GrpcServer::new(datanode_instance.clone(), datanode_instance, runtime).create_service();
+    let datanode_service = GrpcServer::new(
+        datanode_instance.clone(),
+        datanode_instance,
+        runtime,
+        Default::default(),
+        None,
+    )
+    .create_service();
     tokio::spawn(async move {
         Server::builder()
             .add_service(datanode_service)
@@ -25,13 +25,14 @@ use snafu::OptionExt;
 use store::Client as StoreClient;
 
 pub use self::heartbeat::{HeartbeatSender, HeartbeatStream};
+pub use self::store::WatchStream;
 use crate::error;
 use crate::error::Result;
-use crate::rpc::router::DeleteRequest;
+use crate::rpc::router::{DeleteRequest, RenameRequest};
 use crate::rpc::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse, CreateRequest,
     DeleteRangeRequest, DeleteRangeResponse, MoveValueRequest, MoveValueResponse, PutRequest,
-    PutResponse, RangeRequest, RangeResponse, RouteRequest, RouteResponse,
+    PutResponse, RangeRequest, RangeResponse, RouteRequest, RouteResponse, WatchRequest,
 };
 
 pub type Id = (u64, u64);
@@ -214,6 +215,12 @@ impl MetaClient {
         self.router_client()?.delete(req.into()).await?.try_into()
     }
 
+    /// Atomically moves the global value and the routing information of the
+    /// table to the new table name, returns the renamed routing information.
+    pub async fn rename_route(&self, req: RenameRequest) -> Result<RouteResponse> {
+        self.router_client()?.rename(req.into()).await?.try_into()
+    }
+
     /// Range gets the keys in the range from the key-value store.
     pub async fn range(&self, req: RangeRequest) -> Result<RangeResponse> {
         self.store_client()?.range(req.into()).await?.try_into()
@@ -257,6 +264,15 @@ impl MetaClient {
             .try_into()
     }
 
+    /// Watch watches the changes of the keys in the given range, the changes
+    /// are streamed back as events in the order they happened.
+    ///
+    /// The stream may end at any time (e.g. `metasrv` restarts), so callers
+    /// that cache the watched data should drop the cache and watch again then.
+    pub async fn watch(&self, req: WatchRequest) -> Result<WatchStream> {
+        self.store_client()?.watch(req.into()).await
+    }
+
     #[inline]
     pub fn heartbeat_client(&self) -> Result<HeartbeatClient> {
         self.heartbeat.clone().context(error::NotStartedSnafu {
@@ -300,7 +316,7 @@ mod tests {
 
     use super::*;
     use crate::mocks;
-    use crate::rpc::{Partition, TableName};
+    use crate::rpc::{EventType, Partition, TableName};
 
     const TEST_KEY_PREFIX: &str = "__unit_test__meta__";
 
@@ -516,6 +532,11 @@ mod tests {
         // empty table_routes since no TableGlobalValue is stored by datanode
         assert!(res.table_routes.is_empty());
 
+        let req = RenameRequest::new(table_name.clone(), "test_table_renamed");
+        let res = client.rename_route(req).await;
+        // the table is not found since no TableGlobalValue is stored by datanode
+        assert!(res.is_err());
+
         let req = DeleteRequest::new(table_name.clone());
         let res = client.delete_route(req).await;
         // empty table_routes since no TableGlobalValue is stored by datanode
@@ -789,4 +810,38 @@ mod tests {
         assert_eq!(from_key, kv.take_key());
         assert_eq!(b"value2".to_vec(), kv.take_value());
     }
+
+    #[tokio::test]
+    async fn test_watch() {
+        let tc = new_client("test_watch").await;
+
+        let req = WatchRequest::new().with_prefix(tc.key("watch"));
+        let mut stream = tc.client.watch(req).await.unwrap();
+
+        let req = PutRequest::new()
+            .with_key(tc.key("watch-key"))
+            .with_value(b"value".to_vec());
+        let _ = tc.client.put(req).await;
+        let req = PutRequest::new()
+            .with_key(tc.key("other-key"))
+            .with_value(b"value".to_vec());
+        let _ = tc.client.put(req).await;
+        let req = DeleteRangeRequest::new().with_key(tc.key("watch-key"));
+        let _ = tc.client.delete_range(req).await;
+
+        let mut res = stream.message().await.unwrap().unwrap();
+        let mut events = res.take_events();
+        assert_eq!(1, events.len());
+        assert_eq!(EventType::Put, events[0].event_type());
+        let mut kv = events[0].take_kv().unwrap();
+        assert_eq!(tc.key("watch-key"), kv.take_key());
+        assert_eq!(b"value".to_vec(), kv.take_value());
+
+        let mut res = stream.message().await.unwrap().unwrap();
+        let mut events = res.take_events();
+        assert_eq!(1, events.len());
+        assert_eq!(EventType::Delete, events[0].event_type());
+        let mut kv = events[0].take_kv().unwrap();
+        assert_eq!(tc.key("watch-key"), kv.take_key());
+    }
 }
@@ -16,7 +16,7 @@ use std::collections::HashSet;
 use std::sync::Arc;
 
 use api::v1::meta::router_client::RouterClient;
-use api::v1::meta::{CreateRequest, DeleteRequest, RouteRequest, RouteResponse};
+use api::v1::meta::{CreateRequest, DeleteRequest, RenameRequest, RouteRequest, RouteResponse};
 use common_grpc::channel_manager::ChannelManager;
 use snafu::{ensure, OptionExt, ResultExt};
 use tokio::sync::RwLock;
@@ -70,6 +70,11 @@ impl Client {
         let inner = self.inner.read().await;
         inner.delete(req).await
     }
+
+    pub async fn rename(&self, req: RenameRequest) -> Result<RouteResponse> {
+        let inner = self.inner.read().await;
+        inner.rename(req).await
+    }
 }
 
 #[derive(Debug)]
@@ -127,6 +132,14 @@ impl Inner {
         Ok(res.into_inner())
     }
 
+    async fn rename(&self, mut req: RenameRequest) -> Result<RouteResponse> {
+        let mut client = self.random_client()?;
+        req.set_header(self.id);
+        let res = client.rename(req).await.context(error::TonicStatusSnafu)?;
+
+        Ok(res.into_inner())
+    }
+
     fn random_client(&self) -> Result<RouterClient<Channel>> {
         let len = self.peers.len();
         let peer = lb::random_get(len, |i| Some(&self.peers[i])).context(
@@ -19,16 +19,41 @@ use api::v1::meta::store_client::StoreClient;
 use api::v1::meta::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
     DeleteRangeRequest, DeleteRangeResponse, MoveValueRequest, MoveValueResponse, PutRequest,
-    PutResponse, RangeRequest, RangeResponse,
+    PutResponse, RangeRequest, RangeResponse, WatchRequest, WatchResponse,
 };
 use common_grpc::channel_manager::ChannelManager;
 use snafu::{ensure, OptionExt, ResultExt};
 use tokio::sync::RwLock;
 use tonic::transport::Channel;
+use tonic::Streaming;
 
 use crate::client::{load_balance as lb, Id};
-use crate::error;
 use crate::error::Result;
+use crate::{error, rpc};
+
+#[derive(Debug)]
+pub struct WatchStream {
+    stream: Streaming<WatchResponse>,
+}
+
+impl WatchStream {
+    #[inline]
+    fn new(stream: Streaming<WatchResponse>) -> Self {
+        Self { stream }
+    }
+
+    /// Fetch the next message from this stream, `None` means the stream is
+    /// closed by `metasrv` and some changes may have been missed.
+    #[inline]
+    pub async fn message(&mut self) -> Result<Option<rpc::WatchResponse>> {
+        let res = self
+            .stream
+            .message()
+            .await
+            .context(error::TonicStatusSnafu)?;
+        res.map(TryInto::try_into).transpose()
+    }
+}
 
 #[derive(Clone, Debug)]
 pub struct Client {
@@ -92,6 +117,11 @@ impl Client {
         let inner = self.inner.read().await;
         inner.move_value(req).await
     }
+
+    pub async fn watch(&self, req: WatchRequest) -> Result<WatchStream> {
+        let inner = self.inner.read().await;
+        inner.watch(req).await
+    }
 }
 
 #[derive(Debug)]
@@ -188,6 +218,14 @@ impl Inner {
         Ok(res.into_inner())
     }
 
+    async fn watch(&self, mut req: WatchRequest) -> Result<WatchStream> {
+        let mut client = self.random_client()?;
+        req.set_header(self.id);
+        let res = client.watch(req).await.context(error::TonicStatusSnafu)?;
+
+        Ok(WatchStream::new(res.into_inner()))
+    }
+
     fn random_client(&self) -> Result<StoreClient<Channel>> {
         let len = self.peers.len();
         let peer = lb::random_get(len, |i| Some(&self.peers[i])).context(
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_grpc::channel_manager::ClientTlsOption;
 use serde::{Deserialize, Serialize};
 
 pub mod client;
@@ -27,6 +28,9 @@ pub struct MetaClientOpts {
     pub timeout_millis: u64,
     pub connect_timeout_millis: u64,
     pub tcp_nodelay: bool,
+    /// TLS options to connect to meta servers, `None` for plaintext connections.
+    #[serde(default)]
+    pub tls: Option<ClientTlsOption>,
 }
 
 impl Default for MetaClientOpts {
@@ -36,6 +40,7 @@ impl Default for MetaClientOpts {
             timeout_millis: 3_000u64,
             connect_timeout_millis: 5_000u64,
             tcp_nodelay: true,
+            tls: None,
         }
     }
 }
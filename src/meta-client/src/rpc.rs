@@ -23,13 +23,13 @@ use api::v1::meta::{
     TableName as PbTableName,
 };
 pub use router::{
-    CreateRequest, Partition, Region, RouteRequest, RouteResponse, Table, TableRoute,
+    CreateRequest, Partition, Region, RenameRequest, RouteRequest, RouteResponse, Table, TableRoute,
 };
 use serde::{Deserialize, Serialize};
 pub use store::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
-    DeleteRangeRequest, DeleteRangeResponse, MoveValueRequest, MoveValueResponse, PutRequest,
-    PutResponse, RangeRequest, RangeResponse,
+    DeleteRangeRequest, DeleteRangeResponse, EventType, MoveValueRequest, MoveValueResponse,
+    PutRequest, PutResponse, RangeRequest, RangeResponse, WatchEvent, WatchRequest, WatchResponse,
 };
 
 #[derive(Debug, Clone)]
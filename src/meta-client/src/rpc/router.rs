@@ -16,8 +16,8 @@ use std::collections::HashMap;
 
 use api::v1::meta::{
     CreateRequest as PbCreateRequest, DeleteRequest as PbDeleteRequest, Partition as PbPartition,
-    Region as PbRegion, RouteRequest as PbRouteRequest, RouteResponse as PbRouteResponse,
-    Table as PbTable,
+    Region as PbRegion, RenameRequest as PbRenameRequest, RouteRequest as PbRouteRequest,
+    RouteResponse as PbRouteResponse, Table as PbTable,
 };
 use serde::{Deserialize, Serialize, Serializer};
 use snafu::OptionExt;
@@ -108,6 +108,32 @@ impl DeleteRequest {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct RenameRequest {
+    pub table_name: TableName,
+    pub new_table_name: String,
+}
+
+impl From<RenameRequest> for PbRenameRequest {
+    fn from(req: RenameRequest) -> Self {
+        Self {
+            header: None,
+            table_name: Some(req.table_name.into()),
+            new_table_name: req.new_table_name,
+        }
+    }
+}
+
+impl RenameRequest {
+    #[inline]
+    pub fn new(table_name: TableName, new_table_name: impl Into<String>) -> Self {
+        Self {
+            table_name,
+            new_table_name: new_table_name.into(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct RouteResponse {
     pub table_routes: Vec<TableRoute>,
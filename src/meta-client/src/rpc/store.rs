@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use api::v1::meta::watch_event::EventType as PbEventType;
 use api::v1::meta::{
     BatchPutRequest as PbBatchPutRequest, BatchPutResponse as PbBatchPutResponse,
     CompareAndPutRequest as PbCompareAndPutRequest,
@@ -19,7 +20,8 @@ use api::v1::meta::{
     DeleteRangeResponse as PbDeleteRangeResponse, KeyValue as PbKeyValue,
     MoveValueRequest as PbMoveValueRequest, MoveValueResponse as PbMoveValueResponse,
     PutRequest as PbPutRequest, PutResponse as PbPutResponse, RangeRequest as PbRangeRequest,
-    RangeResponse as PbRangeResponse,
+    RangeResponse as PbRangeResponse, WatchEvent as PbWatchEvent, WatchRequest as PbWatchRequest,
+    WatchResponse as PbWatchResponse,
 };
 
 use crate::error;
@@ -245,6 +247,8 @@ pub struct BatchPutRequest {
     /// If prev_kv is set, gets the previous key-value pairs before changing it.
     /// The previous key-value pairs will be returned in the batch put response.
     pub prev_kv: bool,
+    /// Keys deleted in the same transaction with the puts.
+    pub delete_keys: Vec<Vec<u8>>,
 }
 
 impl From<BatchPutRequest> for PbBatchPutRequest {
@@ -253,6 +257,7 @@ impl From<BatchPutRequest> for PbBatchPutRequest {
             header: None,
             kvs: req.kvs,
             prev_kv: req.prev_kv,
+            delete_keys: req.delete_keys,
         }
     }
 }
@@ -263,9 +268,16 @@ impl BatchPutRequest {
         Self {
             kvs: vec![],
             prev_kv: false,
+            delete_keys: vec![],
         }
     }
 
+    #[inline]
+    pub fn add_delete_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.delete_keys.push(key.into());
+        self
+    }
+
     #[inline]
     pub fn add_kv(mut self, key: impl Into<Vec<u8>>, value: impl Into<Vec<u8>>) -> Self {
         self.kvs.push(PbKeyValue {
@@ -582,6 +594,122 @@ impl MoveValueResponse {
     }
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct WatchRequest {
+    /// key is the first key for the range, If range_end is not given, the
+    /// request only watches key.
+    pub key: Vec<u8>,
+    /// range_end is the upper bound on the requested range [key, range_end).
+    /// If range_end is '\0', the range is all keys >= key.
+    /// If range_end is key plus one (e.g., "aa"+1 == "ab", "a\xff"+1 == "b"),
+    /// then the request watches all keys prefixed with key.
+    pub range_end: Vec<u8>,
+}
+
+impl From<WatchRequest> for PbWatchRequest {
+    fn from(req: WatchRequest) -> Self {
+        Self {
+            header: None,
+            key: req.key,
+            range_end: req.range_end,
+        }
+    }
+}
+
+impl WatchRequest {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            key: vec![],
+            range_end: vec![],
+        }
+    }
+
+    /// key is the first key for the range, If range_end is not given, the
+    /// request only watches key.
+    #[inline]
+    pub fn with_key(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.key = key.into();
+        self
+    }
+
+    /// range_end is the upper bound on the requested range [key, range_end).
+    #[inline]
+    pub fn with_range(mut self, key: impl Into<Vec<u8>>, range_end: impl Into<Vec<u8>>) -> Self {
+        self.key = key.into();
+        self.range_end = range_end.into();
+        self
+    }
+
+    /// Watches all keys prefixed with key.
+    /// range_end is the key plus one (e.g., "aa"+1 == "ab", "a\xff"+1 == "b"),
+    #[inline]
+    pub fn with_prefix(mut self, key: impl Into<Vec<u8>>) -> Self {
+        self.key = key.into();
+        self.range_end = util::get_prefix_end_key(&self.key);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchResponse(PbWatchResponse);
+
+impl TryFrom<PbWatchResponse> for WatchResponse {
+    type Error = error::Error;
+
+    fn try_from(pb: PbWatchResponse) -> Result<Self> {
+        util::check_response_header(pb.header.as_ref())?;
+
+        Ok(Self::new(pb))
+    }
+}
+
+impl WatchResponse {
+    #[inline]
+    pub fn new(res: PbWatchResponse) -> Self {
+        Self(res)
+    }
+
+    #[inline]
+    pub fn take_header(&mut self) -> Option<ResponseHeader> {
+        self.0.header.take().map(ResponseHeader::new)
+    }
+
+    #[inline]
+    pub fn take_events(&mut self) -> Vec<WatchEvent> {
+        self.0.events.drain(..).map(WatchEvent::new).collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EventType {
+    Put,
+    Delete,
+}
+
+#[derive(Debug, Clone)]
+pub struct WatchEvent(PbWatchEvent);
+
+impl WatchEvent {
+    #[inline]
+    pub fn new(event: PbWatchEvent) -> Self {
+        Self(event)
+    }
+
+    #[inline]
+    pub fn event_type(&self) -> EventType {
+        match self.0.event_type() {
+            PbEventType::Put => EventType::Put,
+            PbEventType::Delete => EventType::Delete,
+        }
+    }
+
+    #[inline]
+    pub fn take_kv(&mut self) -> Option<KeyValue> {
+        self.0.kv.take().map(KeyValue::new)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use api::v1::meta::{
@@ -592,7 +720,8 @@ mod tests {
         KeyValue as PbKeyValue, MoveValueRequest as PbMoveValueRequest,
         MoveValueResponse as PbMoveValueResponse, PutRequest as PbPutRequest,
         PutResponse as PbPutResponse, RangeRequest as PbRangeRequest,
-        RangeResponse as PbRangeResponse,
+        RangeResponse as PbRangeResponse, WatchEvent as PbWatchEvent,
+        WatchRequest as PbWatchRequest, WatchResponse as PbWatchResponse,
     };
 
     use super::*;
@@ -705,6 +834,7 @@ mod tests {
             .add_kv(b"test_key1".to_vec(), b"test_value1".to_vec())
             .add_kv(b"test_key2".to_vec(), b"test_value2".to_vec())
             .add_kv(b"test_key3".to_vec(), b"test_value3".to_vec())
+            .add_delete_key(b"test_key4".to_vec())
             .with_prev_kv();
 
         let into_req: PbBatchPutRequest = req.into();
@@ -715,6 +845,7 @@ mod tests {
         assert_eq!(b"test_value1".to_vec(), into_req.kvs.get(0).unwrap().value);
         assert_eq!(b"test_value2".to_vec(), into_req.kvs.get(1).unwrap().value);
         assert_eq!(b"test_value3".to_vec(), into_req.kvs.get(2).unwrap().value);
+        assert_eq!(vec![b"test_key4".to_vec()], into_req.delete_keys);
         assert!(into_req.prev_kv);
     }
 
@@ -869,4 +1000,59 @@ mod tests {
         assert_eq!(b"v1".to_vec(), kv.value().to_vec());
         assert_eq!(b"v1".to_vec(), kv.take_value());
     }
+
+    #[test]
+    fn test_watch_request_trans() {
+        let (key, range_end) = (b"test_key1".to_vec(), b"test_range_end1".to_vec());
+
+        let req = WatchRequest::new().with_range(key.clone(), range_end.clone());
+
+        let into_req: PbWatchRequest = req.into();
+        assert!(into_req.header.is_none());
+        assert_eq!(key, into_req.key);
+        assert_eq!(range_end, into_req.range_end);
+
+        let req = WatchRequest::new().with_prefix(b"test_key");
+        assert_eq!(b"test_kez".to_vec(), req.range_end);
+    }
+
+    #[test]
+    fn test_watch_response_trans() {
+        let pb_res = PbWatchResponse {
+            header: None,
+            events: vec![
+                PbWatchEvent {
+                    event_type: PbEventType::Put as i32,
+                    kv: Some(PbKeyValue {
+                        key: b"k1".to_vec(),
+                        value: b"v1".to_vec(),
+                    }),
+                },
+                PbWatchEvent {
+                    event_type: PbEventType::Delete as i32,
+                    kv: Some(PbKeyValue {
+                        key: b"k2".to_vec(),
+                        value: vec![],
+                    }),
+                },
+            ],
+        };
+
+        let mut res: WatchResponse = pb_res.try_into().unwrap();
+        assert!(res.take_header().is_none());
+        let mut events = res.take_events();
+        assert_eq!(2, events.len());
+
+        let event0 = events.get_mut(0).unwrap();
+        assert_eq!(EventType::Put, event0.event_type());
+        let mut kv0 = event0.take_kv().unwrap();
+        assert_eq!(b"k1".to_vec(), kv0.take_key());
+        assert_eq!(b"v1".to_vec(), kv0.take_value());
+
+        let event1 = events.get_mut(1).unwrap();
+        assert_eq!(EventType::Delete, event1.event_type());
+        let kv1 = event1.take_kv().unwrap();
+        assert_eq!(b"k2".to_vec(), kv1.key().to_vec());
+        assert!(kv1.value().is_empty());
+    }
 }
@@ -49,6 +49,12 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display("Failed to setup TLS, source: {}", source))]
+    SetupTls {
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Empty table name"))]
     EmptyTableName { backtrace: Backtrace },
 
@@ -127,6 +133,9 @@ pub enum Error {
     #[snafu(display("Table {} not found", name))]
     TableNotFound { name: String, backtrace: Backtrace },
 
+    #[snafu(display("Table {} already exists", name))]
+    TableAlreadyExists { name: String, backtrace: Backtrace },
+
     #[snafu(display(
         "Failed to move the value of {} because other clients caused a race condition",
         key
@@ -162,6 +171,7 @@ impl ErrorExt for Error {
             | Error::DecodeTableRoute { .. }
             | Error::NoLeader { .. }
             | Error::StartGrpc { .. } => StatusCode::Internal,
+            Error::SetupTls { .. } => StatusCode::InvalidArguments,
             Error::EmptyKey { .. }
             | Error::EmptyTableName { .. }
             | Error::InvalidLeaseKey { .. }
@@ -174,6 +184,7 @@ impl ErrorExt for Error {
             | Error::MoveValue { .. }
             | Error::InvalidTxnResult { .. } => StatusCode::Unexpected,
             Error::TableNotFound { .. } => StatusCode::TableNotFound,
+            Error::TableAlreadyExists { .. } => StatusCode::TableAlreadyExists,
             Error::InvalidCatalogValue { source, .. } => source.status_code(),
         }
     }
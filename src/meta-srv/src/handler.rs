@@ -14,6 +14,7 @@
 
 pub(crate) mod check_leader;
 pub(crate) mod datanode_lease;
+pub(crate) mod region_stats;
 pub(crate) mod response_header;
 
 use std::collections::BTreeMap;
@@ -0,0 +1,146 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use api::v1::meta::{HeartbeatRequest, PutRequest};
+use catalog::helper::{RegionStatEntry, RegionStatsKey, RegionStatsValue};
+use common_time::util as time_util;
+use snafu::ResultExt;
+
+use crate::error::{self, Result};
+use crate::handler::{HeartbeatAccumulator, HeartbeatHandler};
+use crate::metasrv::Context;
+
+/// Stores the region stats reported by datanodes, they are shown in the
+/// `information_schema.regions` table.
+pub struct RegionStatsHandler;
+
+#[async_trait::async_trait]
+impl HeartbeatHandler for RegionStatsHandler {
+    async fn handle(
+        &self,
+        req: &HeartbeatRequest,
+        ctx: &Context,
+        _acc: &mut HeartbeatAccumulator,
+    ) -> Result<()> {
+        if ctx.is_skip_all() {
+            return Ok(());
+        }
+
+        let HeartbeatRequest {
+            peer, region_stats, ..
+        } = req;
+        if let Some(peer) = &peer {
+            let stats = region_stats
+                .iter()
+                .filter_map(|stat| {
+                    let table_name = stat.table_name.as_ref()?;
+                    Some(RegionStatEntry {
+                        region_id: stat.region_id,
+                        // Region ids are encoded as `table id << 32 | region number`.
+                        region_number: stat.region_id as u32,
+                        catalog_name: table_name.catalog_name.clone(),
+                        schema_name: table_name.schema_name.clone(),
+                        table_name: table_name.table_name.clone(),
+                        approximate_rows: stat.approximate_rows,
+                        approximate_bytes: stat.approximate_size,
+                    })
+                })
+                .collect();
+            let value = RegionStatsValue {
+                node_id: peer.id,
+                timestamp_millis: time_util::current_time_millis(),
+                stats,
+            };
+
+            let put = PutRequest {
+                key: RegionStatsKey { node_id: peer.id }.to_string().into_bytes(),
+                value: value.as_bytes().context(error::InvalidCatalogValueSnafu)?,
+                ..Default::default()
+            };
+            ctx.kv_store.put(put).await?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    use api::v1::meta::{Peer, RangeRequest, RegionStat, RequestHeader, TableName};
+
+    use super::*;
+    use crate::service::store::memory::MemStore;
+
+    #[tokio::test]
+    async fn test_handle_region_stats() {
+        let kv_store = Arc::new(MemStore::new());
+        let ctx = Context {
+            datanode_lease_secs: 30,
+            server_addr: "127.0.0.1:0000".to_string(),
+            kv_store,
+            election: None,
+            skip_all: Arc::new(AtomicBool::new(false)),
+        };
+
+        let req = HeartbeatRequest {
+            header: Some(RequestHeader::new((1, 2))),
+            peer: Some(Peer {
+                id: 3,
+                addr: "127.0.0.1:1111".to_string(),
+            }),
+            region_stats: vec![RegionStat {
+                region_id: (1024 << 32) | 1,
+                table_name: Some(TableName {
+                    catalog_name: "greptime".to_string(),
+                    schema_name: "public".to_string(),
+                    table_name: "demo".to_string(),
+                }),
+                approximate_size: 100,
+                approximate_rows: 10,
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let mut acc = HeartbeatAccumulator::default();
+        RegionStatsHandler
+            .handle(&req, &ctx, &mut acc)
+            .await
+            .unwrap();
+
+        let req = RangeRequest {
+            key: RegionStatsKey { node_id: 3 }.to_string().into_bytes(),
+            ..Default::default()
+        };
+        let res = ctx.kv_store.range(req).await.unwrap();
+        assert_eq!(1, res.kvs.len());
+
+        let value = RegionStatsValue::from_bytes(&res.kvs[0].value).unwrap();
+        assert_eq!(3, value.node_id);
+        assert_eq!(
+            vec![RegionStatEntry {
+                region_id: (1024 << 32) | 1,
+                region_number: 1,
+                catalog_name: "greptime".to_string(),
+                schema_name: "public".to_string(),
+                table_name: "demo".to_string(),
+                approximate_rows: 10,
+                approximate_bytes: 100,
+            }],
+            value.stats
+        );
+    }
+}
@@ -15,7 +15,7 @@
 use std::str::FromStr;
 
 use api::v1::meta::TableName;
-use catalog::helper::TableGlobalKey;
+use catalog::helper::{TableGlobalKey, TABLE_ROUTE_PREFIX};
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -27,7 +27,6 @@ use crate::error::Result;
 pub(crate) const REMOVED_PREFIX: &str = "__removed";
 pub(crate) const DN_LEASE_PREFIX: &str = "__meta_dnlease";
 pub(crate) const SEQ_PREFIX: &str = "__meta_seq";
-pub(crate) const TABLE_ROUTE_PREFIX: &str = "__meta_table_route";
 
 lazy_static! {
     static ref DATANODE_KEY_PATTERN: Regex =
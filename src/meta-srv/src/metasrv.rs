@@ -16,12 +16,14 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use api::v1::meta::Peer;
+use common_grpc::tls::TlsOption;
 use common_telemetry::{info, warn};
 use serde::{Deserialize, Serialize};
 
 use crate::election::Election;
 use crate::handler::check_leader::CheckLeaderHandler;
 use crate::handler::datanode_lease::DatanodeLeaseHandler;
+use crate::handler::region_stats::RegionStatsHandler;
 use crate::handler::response_header::ResponseHeaderHandler;
 use crate::handler::HeartbeatHandlerGroup;
 use crate::selector::lease_based::LeaseBasedSelector;
@@ -37,6 +39,8 @@ pub struct MetaSrvOptions {
     pub server_addr: String,
     pub store_addr: String,
     pub datanode_lease_secs: i64,
+    #[serde(default)]
+    pub tls: TlsOption,
 }
 
 impl Default for MetaSrvOptions {
@@ -46,6 +50,7 @@ impl Default for MetaSrvOptions {
             server_addr: "127.0.0.1:3002".to_string(),
             store_addr: "127.0.0.1:2379".to_string(),
             datanode_lease_secs: 15,
+            tls: TlsOption::default(),
         }
     }
 }
@@ -99,6 +104,7 @@ impl MetaSrv {
         handler_group.add_handler(ResponseHeaderHandler).await;
         handler_group.add_handler(CheckLeaderHandler).await;
         handler_group.add_handler(DatanodeLeaseHandler).await;
+        handler_group.add_handler(RegionStatsHandler).await;
 
         Self {
             started,
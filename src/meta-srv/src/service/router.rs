@@ -13,13 +13,14 @@
 // limitations under the License.
 
 use api::v1::meta::{
-    router_server, CreateRequest, DeleteRequest, Error, MoveValueRequest, Peer, PeerDict,
-    PutRequest, RangeRequest, Region, RegionRoute, ResponseHeader, RouteRequest, RouteResponse,
-    Table, TableRoute, TableRouteValue,
+    router_server, BatchPutRequest, CompareAndPutRequest, CreateRequest, DeleteRangeRequest,
+    DeleteRequest, Error, KeyValue, MoveValueRequest, Peer, PeerDict, PutRequest, RangeRequest,
+    Region, RegionRoute, RenameRequest, ResponseHeader, RouteRequest, RouteResponse, Table,
+    TableRoute, TableRouteValue,
 };
 use catalog::helper::{TableGlobalKey, TableGlobalValue};
 use common_telemetry::warn;
-use snafu::{OptionExt, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use tonic::{Request, Response};
 
 use crate::error;
@@ -57,6 +58,14 @@ impl router_server::Router for MetaSrv {
 
         Ok(Response::new(res))
     }
+
+    async fn rename(&self, req: Request<RenameRequest>) -> GrpcResult<RouteResponse> {
+        let req = req.into_inner();
+        let ctx = self.new_ctx();
+        let res = handle_rename(req, ctx).await?;
+
+        Ok(Response::new(res))
+    }
 }
 
 async fn handle_create(
@@ -178,6 +187,93 @@ async fn handle_delete(req: DeleteRequest, ctx: Context) -> Result<RouteResponse
     })
 }
 
+async fn handle_rename(req: RenameRequest, ctx: Context) -> Result<RouteResponse> {
+    let RenameRequest {
+        header,
+        table_name,
+        new_table_name,
+    } = req;
+    let cluster_id = header.as_ref().map_or(0, |h| h.cluster_id);
+    let tgk = table_name
+        .map(|t| TableGlobalKey {
+            catalog_name: t.catalog_name,
+            schema_name: t.schema_name,
+            table_name: t.table_name,
+        })
+        .context(error::EmptyTableNameSnafu)?;
+    let new_tgk = TableGlobalKey {
+        catalog_name: tgk.catalog_name.clone(),
+        schema_name: tgk.schema_name.clone(),
+        table_name: new_table_name,
+    };
+
+    let mut tgv = get_table_global_value(&ctx.kv_store, &tgk)
+        .await?
+        .with_context(|| error::TableNotFoundSnafu {
+            name: format!("{}", tgk),
+        })?;
+    let table_id = tgv.table_id() as u64;
+    let trk = TableRouteKey::with_table_global_key(table_id, &tgk);
+    let mut trv = get_table_route_value(&ctx.kv_store, &trk).await?;
+
+    tgv.table_info.name = new_tgk.table_name.clone();
+    if let Some(table_name) = trv
+        .table_route
+        .as_mut()
+        .and_then(|r| r.table.as_mut())
+        .and_then(|t| t.table_name.as_mut())
+    {
+        table_name.table_name = new_tgk.table_name.clone();
+    }
+    let new_tgk_bytes = format!("{}", new_tgk).into_bytes();
+    let new_tgv_bytes = tgv.as_bytes().context(error::InvalidCatalogValueSnafu)?;
+    let new_trk = TableRouteKey::with_table_global_key(table_id, &new_tgk);
+
+    // Claims the new table name first, so a concurrent create or rename to the same name fails.
+    let req = CompareAndPutRequest {
+        key: new_tgk_bytes.clone(),
+        expect: vec![],
+        value: new_tgv_bytes,
+        ..Default::default()
+    };
+    let res = ctx.kv_store.compare_and_put(req).await?;
+    ensure!(
+        res.success,
+        error::TableAlreadyExistsSnafu {
+            name: format!("{}", new_tgk),
+        }
+    );
+
+    // Then moves the route and removes the old table name in a single transaction.
+    let req = BatchPutRequest {
+        kvs: vec![KeyValue {
+            key: new_trk.key().into_bytes(),
+            value: trv.clone().into(),
+        }],
+        delete_keys: vec![format!("{}", tgk).into_bytes(), trk.key().into_bytes()],
+        ..Default::default()
+    };
+    if let Err(e) = ctx.kv_store.batch_put(req).await {
+        let req = DeleteRangeRequest {
+            key: new_tgk_bytes,
+            ..Default::default()
+        };
+        if let Err(e) = ctx.kv_store.delete_range(req).await {
+            warn!("Failed to release table name {}, error: {}", new_tgk, e);
+        }
+        return Err(e);
+    }
+
+    let (peers, table_routes) = fill_table_routes(vec![(tgv, trv)])?;
+
+    let header = Some(ResponseHeader::success(cluster_id));
+    Ok(RouteResponse {
+        header,
+        peers,
+        table_routes,
+    })
+}
+
 fn fill_table_routes(
     tables: Vec<(TableGlobalValue, TableRouteValue)>,
 ) -> Result<(Vec<Peer>, Vec<TableRoute>)> {
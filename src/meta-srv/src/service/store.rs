@@ -16,12 +16,15 @@ pub mod etcd;
 pub mod kv;
 pub mod memory;
 
+use std::pin::Pin;
+
 use api::v1::meta::{
     store_server, BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
     DeleteRangeRequest, DeleteRangeResponse, MoveValueRequest, MoveValueResponse, PutRequest,
-    PutResponse, RangeRequest, RangeResponse,
+    PutResponse, RangeRequest, RangeResponse, WatchRequest, WatchResponse,
 };
-use tonic::{Request, Response};
+use futures::{Stream, StreamExt};
+use tonic::{Request, Response, Status};
 
 use crate::metasrv::MetaSrv;
 use crate::service::GrpcResult;
@@ -75,6 +78,16 @@ impl store_server::Store for MetaSrv {
 
         Ok(Response::new(res))
     }
+
+    type WatchStream = Pin<Box<dyn Stream<Item = Result<WatchResponse, Status>> + Send + 'static>>;
+
+    async fn watch(&self, req: Request<WatchRequest>) -> GrpcResult<Self::WatchStream> {
+        let req = req.into_inner();
+        let stream = self.kv_store().watch(req).await?;
+        let stream = stream.map(|res| res.map_err(Into::into));
+
+        Ok(Response::new(Box::pin(stream)))
+    }
 }
 
 #[cfg(test)]
@@ -139,6 +152,52 @@ mod tests {
         assert!(res.is_ok());
     }
 
+    #[tokio::test]
+    async fn test_watch() {
+        let kv_store = Arc::new(MemStore::new());
+        let meta_srv = MetaSrv::new(MetaSrvOptions::default(), kv_store, None, None).await;
+        let req = WatchRequest {
+            key: b"watch_key".to_vec(),
+            range_end: b"watch_kez".to_vec(),
+            ..Default::default()
+        };
+        let mut stream = meta_srv
+            .watch(req.into_request())
+            .await
+            .unwrap()
+            .into_inner();
+
+        for key in ["watch_key1", "other_key", "watch_key2"] {
+            let req = PutRequest {
+                key: key.as_bytes().to_vec(),
+                value: b"value".to_vec(),
+                ..Default::default()
+            };
+            meta_srv.put(req.into_request()).await.unwrap();
+        }
+        let req = DeleteRangeRequest {
+            key: b"watch_key1".to_vec(),
+            ..Default::default()
+        };
+        meta_srv.delete_range(req.into_request()).await.unwrap();
+
+        let mut events = vec![];
+        for _ in 0..3 {
+            let res = stream.next().await.unwrap().unwrap();
+            for event in res.events {
+                events.push((event.event_type(), event.kv.unwrap().key));
+            }
+        }
+        assert_eq!(
+            vec![
+                (watch_event::EventType::Put, b"watch_key1".to_vec()),
+                (watch_event::EventType::Put, b"watch_key2".to_vec()),
+                (watch_event::EventType::Delete, b"watch_key1".to_vec()),
+            ],
+            events
+        );
+    }
+
     #[tokio::test]
     async fn test_move_value() {
         let kv_store = Arc::new(MemStore::new());
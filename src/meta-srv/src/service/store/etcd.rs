@@ -14,20 +14,24 @@
 
 use std::sync::Arc;
 
+use api::v1::meta::watch_event::EventType;
 use api::v1::meta::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
     DeleteRangeRequest, DeleteRangeResponse, KeyValue, MoveValueRequest, MoveValueResponse,
-    PutRequest, PutResponse, RangeRequest, RangeResponse, ResponseHeader,
+    PutRequest, PutResponse, RangeRequest, RangeResponse, ResponseHeader, WatchEvent, WatchRequest,
+    WatchResponse,
 };
 use common_error::prelude::*;
 use common_telemetry::warn;
 use etcd_client::{
     Client, Compare, CompareOp, DeleteOptions, GetOptions, PutOptions, Txn, TxnOp, TxnOpResponse,
+    WatchOptions,
 };
+use futures::StreamExt;
 
 use crate::error;
 use crate::error::Result;
-use crate::service::store::kv::{KvStore, KvStoreRef};
+use crate::service::store::kv::{KvStore, KvStoreRef, WatchStream};
 
 #[derive(Clone)]
 pub struct EtcdStore {
@@ -100,13 +104,18 @@ impl KvStore for EtcdStore {
             cluster_id,
             kvs,
             options,
+            delete_keys,
         } = req.try_into()?;
 
-        let put_ops = kvs
+        let ops = delete_keys
             .into_iter()
-            .map(|kv| (TxnOp::put(kv.key, kv.value, options.clone())))
+            .map(|key| TxnOp::delete(key, None))
+            .chain(
+                kvs.into_iter()
+                    .map(|kv| (TxnOp::put(kv.key, kv.value, options.clone()))),
+            )
             .collect::<Vec<_>>();
-        let txn = Txn::new().and_then(put_ops);
+        let txn = Txn::new().and_then(ops);
 
         let txn_res = self
             .client
@@ -123,6 +132,7 @@ impl KvStore for EtcdStore {
                         prev_kvs.push(KvPair::to_kv(prev_kv));
                     }
                 }
+                TxnOpResponse::Delete(_) => {}
                 _ => unreachable!(), // never get here
             }
         }
@@ -284,6 +294,47 @@ impl KvStore for EtcdStore {
         }
         .fail()
     }
+
+    async fn watch(&self, req: WatchRequest) -> Result<WatchStream> {
+        let Watch {
+            cluster_id,
+            key,
+            options,
+        } = req.try_into()?;
+
+        let (watcher, stream) = self
+            .client
+            .watch_client()
+            .watch(key, options)
+            .await
+            .context(error::EtcdFailedSnafu)?;
+
+        let stream = stream.map(move |res| {
+            // The watcher cancels the watch once dropped, so it has to live as long as the stream.
+            let _ = &watcher;
+
+            let res = res.context(error::EtcdFailedSnafu)?;
+            let events = res
+                .events()
+                .iter()
+                .map(|event| {
+                    let event_type = match event.event_type() {
+                        etcd_client::EventType::Put => EventType::Put,
+                        etcd_client::EventType::Delete => EventType::Delete,
+                    };
+                    WatchEvent {
+                        event_type: event_type as i32,
+                        kv: event.kv().map(KvPair::to_kv),
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            let header = Some(ResponseHeader::success(cluster_id));
+            Ok(WatchResponse { header, events })
+        });
+
+        Ok(Box::pin(stream))
+    }
 }
 
 struct Get {
@@ -361,6 +412,7 @@ struct BatchPut {
     cluster_id: u64,
     kvs: Vec<KeyValue>,
     options: Option<PutOptions>,
+    delete_keys: Vec<Vec<u8>>,
 }
 
 impl TryFrom<BatchPutRequest> for BatchPut {
@@ -371,6 +423,7 @@ impl TryFrom<BatchPutRequest> for BatchPut {
             header,
             kvs,
             prev_kv,
+            delete_keys,
         } = req;
 
         let mut options = PutOptions::default();
@@ -382,6 +435,7 @@ impl TryFrom<BatchPutRequest> for BatchPut {
             cluster_id: header.map_or(0, |h| h.cluster_id),
             kvs,
             options: Some(options),
+            delete_keys,
         })
     }
 }
@@ -476,6 +530,37 @@ impl TryFrom<MoveValueRequest> for MoveValue {
     }
 }
 
+struct Watch {
+    cluster_id: u64,
+    key: Vec<u8>,
+    options: Option<WatchOptions>,
+}
+
+impl TryFrom<WatchRequest> for Watch {
+    type Error = error::Error;
+
+    fn try_from(req: WatchRequest) -> Result<Self> {
+        let WatchRequest {
+            header,
+            key,
+            range_end,
+        } = req;
+
+        ensure!(!key.is_empty(), error::EmptyKeySnafu);
+
+        let mut options = WatchOptions::new();
+        if !range_end.is_empty() {
+            options = options.with_range(range_end);
+        }
+
+        Ok(Watch {
+            cluster_id: header.map_or(0, |h| h.cluster_id),
+            key,
+            options: Some(options),
+        })
+    }
+}
+
 struct KvPair<'a>(&'a etcd_client::KeyValue);
 
 impl<'a> KvPair<'a> {
@@ -544,6 +629,7 @@ mod tests {
                 value: b"test_value".to_vec(),
             }],
             prev_kv: true,
+            delete_keys: vec![b"test_delete_key".to_vec()],
             ..Default::default()
         };
 
@@ -552,6 +638,7 @@ mod tests {
         assert_eq!(b"test_key".to_vec(), batch_put.kvs.get(0).unwrap().key);
         assert_eq!(b"test_value".to_vec(), batch_put.kvs.get(0).unwrap().value);
         assert!(batch_put.options.is_some());
+        assert_eq!(vec![b"test_delete_key".to_vec()], batch_put.delete_keys);
     }
 
     #[test]
@@ -600,4 +687,22 @@ mod tests {
         assert_eq!(b"test_to_key".to_vec(), move_value.to_key);
         assert!(move_value.delete_options.is_some());
     }
+
+    #[test]
+    fn test_parse_watch() {
+        let req = WatchRequest {
+            key: b"test_key".to_vec(),
+            range_end: b"test_range_end".to_vec(),
+            ..Default::default()
+        };
+
+        let watch: Watch = req.try_into().unwrap();
+
+        assert_eq!(b"test_key".to_vec(), watch.key);
+        assert!(watch.options.is_some());
+
+        let req = WatchRequest::default();
+        let res: Result<Watch> = req.try_into();
+        assert!(res.is_err());
+    }
 }
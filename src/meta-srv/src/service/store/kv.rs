@@ -12,17 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::pin::Pin;
 use std::sync::Arc;
 
 use api::v1::meta::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
     DeleteRangeRequest, DeleteRangeResponse, MoveValueRequest, MoveValueResponse, PutRequest,
-    PutResponse, RangeRequest, RangeResponse,
+    PutResponse, RangeRequest, RangeResponse, WatchRequest, WatchResponse,
 };
+use futures::Stream;
 
 use crate::error::Result;
 
 pub type KvStoreRef = Arc<dyn KvStore>;
+pub type WatchStream = Pin<Box<dyn Stream<Item = Result<WatchResponse>> + Send + 'static>>;
 
 #[async_trait::async_trait]
 pub trait KvStore: Send + Sync {
@@ -37,4 +40,9 @@ pub trait KvStore: Send + Sync {
     async fn delete_range(&self, req: DeleteRangeRequest) -> Result<DeleteRangeResponse>;
 
     async fn move_value(&self, req: MoveValueRequest) -> Result<MoveValueResponse>;
+
+    /// Watches the changes of the keys in the requested range. The returned stream
+    /// ends when the underlying store stops delivering events, the watcher should
+    /// assume it may have missed some changes then.
+    async fn watch(&self, req: WatchRequest) -> Result<WatchStream>;
 }
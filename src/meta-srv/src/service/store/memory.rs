@@ -17,20 +17,28 @@ use std::collections::BTreeMap;
 use std::ops::Range;
 use std::sync::Arc;
 
+use api::v1::meta::watch_event::EventType;
 use api::v1::meta::{
     BatchPutRequest, BatchPutResponse, CompareAndPutRequest, CompareAndPutResponse,
     DeleteRangeRequest, DeleteRangeResponse, KeyValue, MoveValueRequest, MoveValueResponse,
-    PutRequest, PutResponse, RangeRequest, RangeResponse, ResponseHeader,
+    PutRequest, PutResponse, RangeRequest, RangeResponse, ResponseHeader, WatchEvent, WatchRequest,
+    WatchResponse,
 };
+use common_telemetry::warn;
 use parking_lot::RwLock;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
 
 use crate::error::Result;
-use crate::service::store::kv::KvStore;
+use crate::service::store::kv::{KvStore, WatchStream};
+
+const WATCH_CHANNEL_CAPACITY: usize = 1024;
 
 /// Only for mock test
 #[derive(Clone)]
 pub struct MemStore {
     inner: Arc<RwLock<BTreeMap<Vec<u8>, Vec<u8>>>>,
+    events: broadcast::Sender<Vec<WatchEvent>>,
 }
 
 impl Default for MemStore {
@@ -41,12 +49,45 @@ impl Default for MemStore {
 
 impl MemStore {
     pub fn new() -> Self {
+        let (events, _) = broadcast::channel(WATCH_CHANNEL_CAPACITY);
         Self {
             inner: Arc::new(RwLock::new(Default::default())),
+            events,
+        }
+    }
+
+    fn notify(&self, events: Vec<WatchEvent>) {
+        if !events.is_empty() {
+            // An error only means there are no watchers at the moment.
+            let _ = self.events.send(events);
         }
     }
 }
 
+fn put_event(key: Vec<u8>, value: Vec<u8>) -> WatchEvent {
+    WatchEvent {
+        event_type: EventType::Put as i32,
+        kv: Some(KeyValue { key, value }),
+    }
+}
+
+fn delete_event(key: Vec<u8>) -> WatchEvent {
+    WatchEvent {
+        event_type: EventType::Delete as i32,
+        kv: Some(KeyValue { key, value: vec![] }),
+    }
+}
+
+fn in_watch_range(key: &[u8], start: &[u8], end: &[u8]) -> bool {
+    if end.is_empty() {
+        key == start
+    } else if end == [0] {
+        key >= start
+    } else {
+        key >= start && key < end
+    }
+}
+
 #[async_trait::async_trait]
 impl KvStore for MemStore {
     async fn range(&self, req: RangeRequest) -> Result<RangeResponse> {
@@ -102,7 +143,8 @@ impl KvStore for MemStore {
         } = req;
 
         let mut memory = self.inner.write();
-        let prev_value = memory.insert(key.clone(), value);
+        let prev_value = memory.insert(key.clone(), value.clone());
+        self.notify(vec![put_event(key.clone(), value)]);
         let prev_kv = if prev_kv {
             prev_value.map(|value| KeyValue { key, value })
         } else {
@@ -119,9 +161,19 @@ impl KvStore for MemStore {
             header,
             kvs,
             prev_kv,
+            delete_keys,
         } = req;
 
         let mut memory = self.inner.write();
+        let mut events = delete_keys
+            .into_iter()
+            .filter(|key| memory.remove(key).is_some())
+            .map(delete_event)
+            .collect::<Vec<_>>();
+        events.extend(
+            kvs.iter()
+                .map(|kv| put_event(kv.key.clone(), kv.value.clone())),
+        );
         let prev_kvs = if prev_kv {
             kvs.into_iter()
                 .map(|kv| (kv.key.clone(), memory.insert(kv.key, kv.value)))
@@ -137,6 +189,7 @@ impl KvStore for MemStore {
             }
             vec![]
         };
+        self.notify(events);
 
         let cluster_id = header.map_or(0, |h| h.cluster_id);
         let header = Some(ResponseHeader::success(cluster_id));
@@ -153,6 +206,7 @@ impl KvStore for MemStore {
 
         let mut memory = self.inner.write();
 
+        let event = put_event(key.clone(), value.clone());
         let (success, prev_kv) = match memory.entry(key) {
             Entry::Vacant(e) => {
                 let success = expect.is_empty();
@@ -172,6 +226,10 @@ impl KvStore for MemStore {
             }
         };
 
+        if success {
+            self.notify(vec![event]);
+        }
+
         let prev_kv = prev_kv.map(|(key, value)| KeyValue { key, value });
 
         let cluster_id = header.map_or(0, |h| h.cluster_id);
@@ -206,6 +264,12 @@ impl KvStore for MemStore {
                 .map(|(key, value)| KeyValue { key, value })
                 .collect::<Vec<_>>()
         };
+        self.notify(
+            prev_kvs
+                .iter()
+                .map(|kv| delete_event(kv.key.clone()))
+                .collect(),
+        );
 
         let cluster_id = header.map_or(0, |h| h.cluster_id);
         let header = Some(ResponseHeader::success(cluster_id));
@@ -231,7 +295,11 @@ impl KvStore for MemStore {
 
         let kv = match memory.remove(&from_key) {
             Some(v) => {
-                memory.insert(to_key, v.clone());
+                memory.insert(to_key.clone(), v.clone());
+                self.notify(vec![
+                    delete_event(from_key.clone()),
+                    put_event(to_key, v.clone()),
+                ]);
                 Some((from_key, v))
             }
             None => memory.get(&to_key).map(|v| (to_key, v.clone())),
@@ -243,4 +311,51 @@ impl KvStore for MemStore {
         let header = Some(ResponseHeader::success(cluster_id));
         Ok(MoveValueResponse { header, kv })
     }
+
+    async fn watch(&self, req: WatchRequest) -> Result<WatchStream> {
+        let WatchRequest {
+            header,
+            key,
+            range_end,
+        } = req;
+
+        let cluster_id = header.map_or(0, |h| h.cluster_id);
+        let receiver = self.events.subscribe();
+        let stream = futures::stream::unfold(receiver, move |mut receiver| {
+            let key = key.clone();
+            let range_end = range_end.clone();
+            async move {
+                loop {
+                    match receiver.recv().await {
+                        Ok(events) => {
+                            let events = events
+                                .into_iter()
+                                .filter(|e| {
+                                    e.kv.as_ref().map_or(false, |kv| {
+                                        in_watch_range(&kv.key, &key, &range_end)
+                                    })
+                                })
+                                .collect::<Vec<_>>();
+                            if events.is_empty() {
+                                continue;
+                            }
+                            let header = Some(ResponseHeader::success(cluster_id));
+                            return Some((Ok(WatchResponse { header, events }), receiver));
+                        }
+                        Err(RecvError::Lagged(n)) => {
+                            // Ends the stream so that the watcher knows some events are lost.
+                            warn!(
+                                "Watcher lagged behind {} events, closing the watch stream",
+                                n
+                            );
+                            return None;
+                        }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            }
+        });
+
+        Ok(Box::pin(stream))
+    }
 }
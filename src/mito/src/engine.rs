@@ -29,7 +29,9 @@ use store_api::storage::{
 };
 use table::engine::{EngineContext, TableEngine, TableReference};
 use table::metadata::{TableId, TableInfoBuilder, TableMetaBuilder, TableType, TableVersion};
-use table::requests::{AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest};
+use table::requests::{
+    AlterKind, AlterTableRequest, CreateTableRequest, DropTableRequest, OpenTableRequest,
+};
 use table::table::TableRef;
 use table::{Result as TableResult, Table};
 use tokio::sync::Mutex;
@@ -482,6 +484,33 @@ impl<S: StorageEngine> MitoEngineInner<S> {
             .context(error::TableNotFoundSnafu { table_name })?;
 
         logging::info!("start altering table {} with request {:?}", table_name, req);
+        if let AlterKind::RenameTable { new_table_name } = &req.alter_kind {
+            let new_table_name = new_table_name.clone();
+            let new_table_ref = TableReference {
+                catalog: catalog_name,
+                schema: schema_name,
+                table: &new_table_name,
+            };
+            // Renaming the table also changes the key in `tables`, so we need to hold the mutex.
+            let _lock = self.table_mutex.lock().await;
+            ensure!(
+                self.get_table(&new_table_ref).is_none(),
+                TableExistsSnafu {
+                    table_name: new_table_ref.to_string(),
+                }
+            );
+
+            table
+                .alter(req)
+                .await
+                .context(error::AlterTableSnafu { table_name })?;
+
+            let mut tables = self.tables.write().unwrap();
+            tables.remove(&table_ref.to_string());
+            tables.insert(new_table_ref.to_string(), table.clone());
+            return Ok(table);
+        }
+
         table
             .alter(req)
             .await
@@ -519,8 +548,10 @@ impl<S: StorageEngine> MitoEngineInner<S> {
 
 #[cfg(test)]
 mod tests {
+    use common_query::logical_plan::Expr;
     use common_query::physical_plan::RuntimeEnv;
     use common_recordbatch::util;
+    use datafusion::prelude::{col, lit};
     use datafusion_common::field_util::{FieldExt, SchemaExt};
     use datatypes::prelude::{ConcreteDataType, ScalarVector};
     use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, SchemaBuilder};
@@ -530,8 +561,8 @@ mod tests {
     use storage::config::EngineConfig as StorageEngineConfig;
     use storage::EngineImpl;
     use store_api::manifest::Manifest;
-    use store_api::storage::ReadContext;
-    use table::requests::{AddColumnRequest, AlterKind};
+    use store_api::storage::{ReadContext, Region, RegionMeta};
+    use table::requests::{AddColumnRequest, AlterKind, ModifyColumnRequest};
     use tempdir::TempDir;
 
     use super::*;
@@ -737,6 +768,12 @@ mod tests {
         let insert_req = new_insert_request("demo".to_string(), columns_values);
         assert_eq!(2, table.insert(insert_req).await.unwrap());
 
+        let region_stats = table.region_stats().unwrap();
+        assert_eq!(1, region_stats.len());
+        assert_eq!(0, region_stats[0].region_number);
+        assert_eq!(2, region_stats[0].approximate_rows);
+        assert!(region_stats[0].approximate_bytes > 0);
+
         let stream = table.scan(&None, &[], None).await.unwrap();
         let stream = stream.execute(0, Arc::new(RuntimeEnv::default())).unwrap();
         let batches = util::collect(stream).await.unwrap();
@@ -831,6 +868,55 @@ mod tests {
         assert_eq!(test_batch_size, total);
     }
 
+    #[tokio::test]
+    async fn test_scan_by_row_key() {
+        let (_engine, table, _schema, _dir) = test_util::setup_test_engine_and_table().await;
+
+        let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(4);
+        let hosts = StringVector::from(vec!["host1", "host2", "host1"]);
+        let cpus = Float64Vector::from_vec(vec![55.5, 66.6, 77.7]);
+        let memories = Float64Vector::from_vec(vec![1024f64, 4096f64, 2048f64]);
+        let tss = TimestampVector::from_vec(vec![1, 1, 2]);
+
+        columns_values.insert("host".to_string(), Arc::new(hosts));
+        columns_values.insert("cpu".to_string(), Arc::new(cpus));
+        columns_values.insert("memory".to_string(), Arc::new(memories));
+        columns_values.insert("ts".to_string(), Arc::new(tss));
+
+        let insert_req = new_insert_request("demo".to_string(), columns_values);
+        assert_eq!(3, table.insert(insert_req).await.unwrap());
+
+        // Filters pin the full row key: host = 'host2' AND ts = 1.
+        let filters: Vec<Expr> = vec![col("host")
+            .eq(lit("host2"))
+            .and(col("ts").eq(lit(1i64)))
+            .into()];
+        let stream = table.scan(&Some(vec![1, 3]), &filters, None).await.unwrap();
+        let stream = stream.execute(0, Arc::new(RuntimeEnv::default())).unwrap();
+        let batches = util::collect(stream).await.unwrap();
+        assert_eq!(1, batches.len());
+        let columns = batches[0].df_recordbatch.columns();
+        assert_eq!(2, columns.len());
+        assert_eq!(
+            Float64Vector::from_vec(vec![66.6]).to_arrow_array(),
+            columns[0]
+        );
+        assert_eq!(
+            TimestampVector::from_vec(vec![1]).to_arrow_array(),
+            columns[1]
+        );
+
+        // Row not found.
+        let filters: Vec<Expr> = vec![
+            col("ts").eq(lit(3i64)).into(),
+            col("host").eq(lit("host1")).into(),
+        ];
+        let stream = table.scan(&None, &filters, None).await.unwrap();
+        let stream = stream.execute(0, Arc::new(RuntimeEnv::default())).unwrap();
+        let batches = util::collect(stream).await.unwrap();
+        assert!(batches.iter().all(|batch| batch.num_rows() == 0));
+    }
+
     #[tokio::test]
     async fn test_create_if_not_exists() {
         common_telemetry::init_default_ut_logging();
@@ -1045,6 +1131,146 @@ mod tests {
         assert_eq!(new_schema.version(), old_schema.version() + 1);
     }
 
+    #[tokio::test]
+    async fn test_alter_table_modify_column() {
+        let (_engine, table_engine, _table, _object_store, _dir) =
+            test_util::setup_mock_engine_and_table().await;
+
+        // Add an int32 field to the table first.
+        let req = AlterTableRequest {
+            catalog_name: None,
+            schema_name: None,
+            table_name: TABLE_NAME.to_string(),
+            alter_kind: AlterKind::AddColumns {
+                columns: vec![AddColumnRequest {
+                    column_schema: ColumnSchema::new(
+                        "my_field",
+                        ConcreteDataType::int32_datatype(),
+                        true,
+                    ),
+                    is_key: false,
+                }],
+            },
+        };
+        let table = table_engine
+            .alter_table(&EngineContext::default(), req)
+            .await
+            .unwrap();
+        let old_schema = table.schema();
+
+        let req = AlterTableRequest {
+            catalog_name: None,
+            schema_name: None,
+            table_name: TABLE_NAME.to_string(),
+            alter_kind: AlterKind::ModifyColumns {
+                columns: vec![ModifyColumnRequest {
+                    column_name: String::from("my_field"),
+                    data_type: ConcreteDataType::int64_datatype(),
+                }],
+            },
+        };
+        let table = table_engine
+            .alter_table(&EngineContext::default(), req)
+            .await
+            .unwrap();
+
+        let new_schema = table.schema();
+        assert_eq!(
+            ConcreteDataType::int64_datatype(),
+            new_schema
+                .column_schema_by_name("my_field")
+                .unwrap()
+                .data_type
+        );
+        assert_eq!(new_schema.version(), old_schema.version() + 1);
+
+        let table = table
+            .as_any()
+            .downcast_ref::<MitoTable<MockRegion>>()
+            .unwrap();
+        let region_schema = table.region().in_memory_metadata().schema().clone();
+        assert_eq!(
+            ConcreteDataType::int64_datatype(),
+            region_schema
+                .column_schema_by_name("my_field")
+                .unwrap()
+                .data_type
+        );
+    }
+
+    #[tokio::test]
+    async fn test_alter_table_rename() {
+        let (_engine, table_engine, table, _object_store, _dir) =
+            test_util::setup_mock_engine_and_table().await;
+        let ctx = EngineContext::default();
+        let old_info = table.table_info();
+
+        let new_table_name = "new_demo";
+        let req = AlterTableRequest {
+            catalog_name: None,
+            schema_name: None,
+            table_name: TABLE_NAME.to_string(),
+            alter_kind: AlterKind::RenameTable {
+                new_table_name: new_table_name.to_string(),
+            },
+        };
+        let table = table_engine.alter_table(&ctx, req).await.unwrap();
+
+        let new_info = table.table_info();
+        assert_eq!(new_table_name, new_info.name);
+        assert_eq!(old_info.ident.table_id, new_info.ident.table_id);
+        assert_eq!(old_info.ident.version + 1, new_info.ident.version);
+        assert_eq!(old_info.meta.schema, new_info.meta.schema);
+
+        let old_table_ref = TableReference {
+            catalog: DEFAULT_CATALOG_NAME,
+            schema: DEFAULT_SCHEMA_NAME,
+            table: TABLE_NAME,
+        };
+        let new_table_ref = TableReference {
+            catalog: DEFAULT_CATALOG_NAME,
+            schema: DEFAULT_SCHEMA_NAME,
+            table: new_table_name,
+        };
+        assert!(!table_engine.table_exists(&ctx, &old_table_ref));
+        assert!(table_engine.table_exists(&ctx, &new_table_ref));
+
+        // Rename to an existing table.
+        let req = AlterTableRequest {
+            catalog_name: None,
+            schema_name: None,
+            table_name: new_table_name.to_string(),
+            alter_kind: AlterKind::RenameTable {
+                new_table_name: new_table_name.to_string(),
+            },
+        };
+        assert!(table_engine.alter_table(&ctx, req).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_alter_table_set_options() {
+        let (_engine, table_engine, table, _object_store, _dir) =
+            test_util::setup_mock_engine_and_table().await;
+        let old_info = table.table_info();
+
+        let req = AlterTableRequest {
+            catalog_name: None,
+            schema_name: None,
+            table_name: TABLE_NAME.to_string(),
+            alter_kind: AlterKind::SetTableOptions {
+                options: HashMap::from([("ttl".to_string(), "7d".to_string())]),
+            },
+        };
+        let table = table_engine
+            .alter_table(&EngineContext::default(), req)
+            .await
+            .unwrap();
+
+        let new_info = table.table_info();
+        assert_eq!("7d", new_info.meta.options["ttl"]);
+        assert_eq!(old_info.meta.schema, new_info.meta.schema);
+    }
+
     #[tokio::test]
     async fn test_drop_table() {
         common_telemetry::init_default_ut_logging();
@@ -16,6 +16,7 @@
 pub mod test_util;
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
 
@@ -24,9 +25,13 @@ use async_trait::async_trait;
 use common_query::logical_plan::Expr;
 use common_query::physical_plan::PhysicalPlanRef;
 use common_recordbatch::error::{Error as RecordBatchError, Result as RecordBatchResult};
-use common_recordbatch::{RecordBatch, RecordBatchStream};
+use common_recordbatch::{RecordBatch, RecordBatchStream, RecordBatches};
 use common_telemetry::logging;
-use datatypes::schema::ColumnSchema;
+use common_time::timestamp::Timestamp;
+use datafusion::logical_plan::{Expr as DfExpr, Operator};
+use datafusion_common::ScalarValue;
+use datatypes::prelude::{ConcreteDataType, Value, VectorBuilder};
+use datatypes::schema::{ColumnSchema, Schema};
 use datatypes::vectors::VectorRef;
 use futures::task::{Context, Poll};
 use futures::Stream;
@@ -34,8 +39,8 @@ use object_store::ObjectStore;
 use snafu::{ensure, OptionExt, ResultExt};
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AddColumn, AlterOperation, AlterRequest, ChunkReader, PutOperation, ReadContext, Region,
-    RegionMeta, ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
+    AddColumn, AlterOperation, AlterRequest, ChunkReader, GetRequest, ModifyColumn, PutOperation,
+    ReadContext, Region, RegionMeta, ScanRequest, SchemaRef, Snapshot, WriteContext, WriteRequest,
 };
 use table::error::{Error as TableError, MissingColumnSnafu, Result as TableResult};
 use table::metadata::{
@@ -43,7 +48,7 @@ use table::metadata::{
 };
 use table::requests::{AddColumnRequest, AlterKind, AlterTableRequest, InsertRequest};
 use table::table::scan::SimpleTableScan;
-use table::table::Table;
+use table::table::{RegionStat, Table};
 use tokio::sync::Mutex;
 
 use crate::error::{
@@ -173,6 +178,12 @@ impl<R: Region> Table for MitoTable<R> {
         let snapshot = self.region.snapshot(&read_ctx).map_err(TableError::new)?;
 
         let projection = self.transform_projection(&self.region, projection.clone())?;
+        if let Some(key) = self.row_key_from_filters(filters) {
+            return self
+                .get_by_row_key(&snapshot, &read_ctx, projection, key)
+                .await;
+        }
+
         let filters = filters.into();
         let scan_request = ScanRequest {
             projection,
@@ -216,6 +227,9 @@ impl<R: Region> Table for MitoTable<R> {
         // Increase version of the table.
         new_info.ident.version = table_info.ident.version + 1;
         new_info.meta = new_meta;
+        if let AlterKind::RenameTable { new_table_name } = &req.alter_kind {
+            new_info.name = new_table_name.clone();
+        }
 
         // Persist the alteration to the manifest.
         logging::debug!(
@@ -234,22 +248,25 @@ impl<R: Region> Table for MitoTable<R> {
                 table_name: &self.table_info().name,
             })?;
 
-        // TODO(yingwen): Error handling. Maybe the region need to provide a method to
-        // validate the request first.
-        let region = self.region();
-        let region_meta = region.in_memory_metadata();
-        let alter_req = AlterRequest {
-            operation: alter_op,
-            version: region_meta.version(),
-        };
-        // Alter the region.
-        logging::debug!(
-            "start altering region {} of table {}, with request {:?}",
-            region.name(),
-            table_name,
-            alter_req,
-        );
-        region.alter(alter_req).await.map_err(TableError::new)?;
+        // Renaming the table or changing table options doesn't need to alter the region.
+        if let Some(alter_op) = alter_op {
+            // TODO(yingwen): Error handling. Maybe the region need to provide a method to
+            // validate the request first.
+            let region = self.region();
+            let region_meta = region.in_memory_metadata();
+            let alter_req = AlterRequest {
+                operation: alter_op,
+                version: region_meta.version(),
+            };
+            // Alter the region.
+            logging::debug!(
+                "start altering region {} of table {}, with request {:?}",
+                region.name(),
+                table_name,
+                alter_req,
+            );
+            region.alter(alter_req).await.map_err(TableError::new)?;
+        }
 
         // Update in memory metadata of the table.
         self.set_table_info(new_info);
@@ -260,6 +277,18 @@ impl<R: Region> Table for MitoTable<R> {
     fn supports_filter_pushdown(&self, _filter: &Expr) -> table::error::Result<FilterPushDownType> {
         Ok(FilterPushDownType::Inexact)
     }
+
+    fn region_stats(&self) -> TableResult<Vec<RegionStat>> {
+        let region_id = self.region.id();
+        let stat = self.region.stats();
+        Ok(vec![RegionStat {
+            region_id,
+            // The lower 32 bits of region id is the region number.
+            region_number: region_id as u32,
+            approximate_rows: stat.approximate_rows,
+            approximate_bytes: stat.approximate_bytes,
+        }])
+    }
 }
 
 struct ChunkStream {
@@ -286,6 +315,45 @@ fn column_qualified_name(table_name: &str, region_name: &str, column_name: &str)
     format!("{}.{}.{}", table_name, region_name, column_name)
 }
 
+/// Collects `column = literal` equalities in conjunctions of `expr`.
+fn collect_equalities(expr: &DfExpr, equalities: &mut HashMap<String, ScalarValue>) {
+    if let DfExpr::BinaryExpr { left, op, right } = expr {
+        match op {
+            Operator::And => {
+                collect_equalities(left, equalities);
+                collect_equalities(right, equalities);
+            }
+            Operator::Eq => match (left.as_ref(), right.as_ref()) {
+                (DfExpr::Column(column), DfExpr::Literal(value))
+                | (DfExpr::Literal(value), DfExpr::Column(column)) => {
+                    equalities.insert(column.name.clone(), value.clone());
+                }
+                _ => (),
+            },
+            _ => (),
+        }
+    }
+}
+
+/// Converts the literal to a value of the key column, returns `None` if the literal
+/// is null or can't be converted.
+fn literal_to_key_value(literal: &ScalarValue, data_type: &ConcreteDataType) -> Option<Value> {
+    let value = Value::try_from(literal.clone()).ok()?;
+    if value.is_null() {
+        return None;
+    }
+    if value.data_type() == *data_type {
+        return Some(value);
+    }
+
+    match (value, data_type) {
+        (Value::Int64(v), ConcreteDataType::Timestamp(t)) => {
+            Some(Value::Timestamp(Timestamp::new(v, t.unit)))
+        }
+        _ => None,
+    }
+}
+
 impl<R: Region> MitoTable<R> {
     fn new(table_info: TableInfo, region: R, manifest: TableManifest) -> Self {
         Self {
@@ -296,6 +364,90 @@ impl<R: Region> MitoTable<R> {
         }
     }
 
+    /// Returns values of row key columns of the region if `filters` pin all of them to
+    /// literals, e.g. `host = 'host1' AND ts = 1000`.
+    fn row_key_from_filters(&self, filters: &[Expr]) -> Option<Vec<Value>> {
+        if filters.is_empty() {
+            return None;
+        }
+
+        let mut equalities = HashMap::new();
+        for filter in filters {
+            collect_equalities(filter.df_expr(), &mut equalities);
+        }
+
+        let table_info = self.table_info();
+        let table_schema = &table_info.meta.schema;
+        let timestamp_index = table_schema.timestamp_index()?;
+        let column_schemas = table_schema.column_schemas();
+        // Row key of the region consists of primary key columns and the timestamp column.
+        table_info
+            .meta
+            .primary_key_indices
+            .iter()
+            .copied()
+            .filter(|idx| *idx != timestamp_index)
+            .chain(std::iter::once(timestamp_index))
+            .map(|idx| {
+                let column_schema = &column_schemas[idx];
+                let literal = equalities.get(&column_schema.name)?;
+                literal_to_key_value(literal, &column_schema.data_type)
+            })
+            .collect()
+    }
+
+    /// Reads the row with given row key by point lookup instead of scanning the region.
+    async fn get_by_row_key(
+        &self,
+        snapshot: &R::Snapshot,
+        read_ctx: &ReadContext,
+        projection: Option<Vec<usize>>,
+        key: Vec<Value>,
+    ) -> TableResult<PhysicalPlanRef> {
+        let region_meta = self.region.in_memory_metadata();
+        let region_schema = region_meta.schema();
+        let column_schemas: Vec<_> = match &projection {
+            Some(indices) => indices
+                .iter()
+                .map(|idx| region_schema.column_schemas()[*idx].clone())
+                .collect(),
+            None => region_schema.column_schemas().to_vec(),
+        };
+
+        let request = GetRequest {
+            key,
+            projection,
+            ..Default::default()
+        };
+        let row = snapshot
+            .get(read_ctx, request)
+            .await
+            .map_err(TableError::new)?
+            .row;
+
+        let schema = Arc::new(Schema::new(column_schemas));
+        let batches =
+            match row {
+                Some(row) => {
+                    let columns = schema.column_schemas().iter().zip(row.iter()).map(
+                        |(column_schema, value)| {
+                            let mut builder =
+                                VectorBuilder::with_capacity(column_schema.data_type.clone(), 1);
+                            builder.push(value);
+                            builder.finish()
+                        },
+                    );
+                    let batch =
+                        RecordBatch::new(schema.clone(), columns).map_err(TableError::new)?;
+                    vec![batch]
+                }
+                None => Vec::new(),
+            };
+        let batches = RecordBatches::try_new(schema, batches).map_err(TableError::new)?;
+
+        Ok(Arc::new(SimpleTableScan::new(batches.as_stream())))
+    }
+
     /// Transform projection which is based on table schema
     /// into projection based on region schema.
     fn transform_projection(
@@ -471,19 +623,30 @@ impl<R: Region> MitoTable<R> {
     }
 }
 
-/// Create [`AlterOperation`] according to given `alter_kind`.
+/// Create [`AlterOperation`] according to given `alter_kind`, returns `None` if
+/// the region is not affected by the `alter_kind`.
 fn create_alter_operation(
     table_name: &str,
     alter_kind: &AlterKind,
     table_meta: &mut TableMeta,
-) -> TableResult<AlterOperation> {
+) -> TableResult<Option<AlterOperation>> {
     match alter_kind {
         AlterKind::AddColumns { columns } => {
-            create_add_columns_operation(table_name, columns, table_meta)
+            create_add_columns_operation(table_name, columns, table_meta).map(Some)
         }
-        AlterKind::DropColumns { names } => Ok(AlterOperation::DropColumns {
+        AlterKind::DropColumns { names } => Ok(Some(AlterOperation::DropColumns {
             names: names.to_vec(),
-        }),
+        })),
+        AlterKind::ModifyColumns { columns } => Ok(Some(AlterOperation::ModifyColumns {
+            columns: columns
+                .iter()
+                .map(|request| ModifyColumn {
+                    name: request.column_name.clone(),
+                    data_type: request.data_type.clone(),
+                })
+                .collect(),
+        })),
+        AlterKind::RenameTable { .. } | AlterKind::SetTableOptions { .. } => Ok(None),
     }
 }
 
@@ -27,8 +27,8 @@ use storage::metadata::{RegionMetaImpl, RegionMetadata};
 use storage::write_batch::{Mutation, WriteBatch};
 use store_api::storage::{
     AlterRequest, Chunk, ChunkReader, CreateOptions, EngineContext, GetRequest, GetResponse,
-    OpenOptions, ReadContext, Region, RegionDescriptor, RegionId, RegionMeta, ScanRequest,
-    ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext, WriteResponse,
+    OpenOptions, ReadContext, Region, RegionDescriptor, RegionId, RegionMeta, RegionStat,
+    ScanRequest, ScanResponse, SchemaRef, Snapshot, StorageEngine, WriteContext, WriteResponse,
 };
 
 pub type Result<T> = std::result::Result<T, MockError>;
@@ -121,7 +121,7 @@ impl Snapshot for MockSnapshot {
     }
 
     async fn get(&self, _ctx: &ReadContext, _request: GetRequest) -> Result<GetResponse> {
-        Ok(GetResponse {})
+        Ok(GetResponse { row: None })
     }
 }
 
@@ -185,6 +185,14 @@ impl Region for MockRegion {
 
         Ok(())
     }
+
+    fn stats(&self) -> RegionStat {
+        let memtable = self.inner.memtable.read().unwrap();
+        RegionStat {
+            approximate_rows: memtable.values().next().map(|v| v.len()).unwrap_or(0) as u64,
+            approximate_bytes: 0,
+        }
+    }
 }
 
 impl MockRegionInner {
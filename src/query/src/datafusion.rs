@@ -16,10 +16,14 @@
 
 mod catalog_adapter;
 mod error;
+mod fill;
 mod planner;
+mod query_planner;
+mod range;
 
 use std::sync::Arc;
 
+use catalog::privilege::PrivilegeManagerRef;
 use catalog::CatalogListRef;
 use common_function::scalars::aggregate::AggregateFunctionMetaRef;
 use common_function::scalars::udf::create_udf;
@@ -30,6 +34,8 @@ use common_query::Output;
 use common_recordbatch::adapter::RecordBatchStreamAdapter;
 use common_recordbatch::{EmptyRecordBatchStream, SendableRecordBatchStream};
 use common_telemetry::timer;
+use datafusion::execution::context::ExecutionProps;
+use datafusion::optimizer::optimizer::OptimizerRule;
 use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
 use datafusion::physical_plan::ExecutionPlan;
 use session::context::QueryContextRef;
@@ -40,9 +46,11 @@ use sql::statements::statement::Statement;
 
 pub use crate::datafusion::catalog_adapter::DfCatalogListAdapter;
 use crate::datafusion::planner::{DfContextProviderAdapter, DfPlanner};
+pub(crate) use crate::datafusion::query_planner::DfQueryPlanner;
 use crate::error::Result;
 use crate::executor::QueryExecutor;
 use crate::logical_optimizer::LogicalOptimizer;
+use crate::optimizer::TypeConversionRule;
 use crate::physical_optimizer::PhysicalOptimizer;
 use crate::physical_planner::PhysicalPlanner;
 use crate::plan::LogicalPlan;
@@ -55,9 +63,12 @@ pub(crate) struct DatafusionQueryEngine {
 }
 
 impl DatafusionQueryEngine {
-    pub fn new(catalog_list: CatalogListRef) -> Self {
+    pub fn new(
+        catalog_list: CatalogListRef,
+        privilege_manager: Option<PrivilegeManagerRef>,
+    ) -> Self {
         Self {
-            state: QueryEngineState::new(catalog_list.clone()),
+            state: QueryEngineState::new(catalog_list, privilege_manager),
         }
     }
 }
@@ -82,10 +93,27 @@ impl QueryEngine for DatafusionQueryEngine {
         stmt: Statement,
         query_ctx: QueryContextRef,
     ) -> Result<LogicalPlan> {
+        let time_zone = query_ctx.time_zone();
         let context_provider = DfContextProviderAdapter::new(self.state.clone(), query_ctx);
         let planner = DfPlanner::new(&context_provider);
 
-        planner.statement_to_plan(stmt)
+        let plan = planner.statement_to_plan(stmt);
+        // Tables denied are missing in planning, which fails for the denial rather than them.
+        context_provider.ensure_permitted()?;
+        let plan = plan?;
+        // Timestamp strings in the plan have to be parsed in the session time zone, so
+        // convert them now rather than leaving them to the global `TypeConversionRule`.
+        match (time_zone, plan) {
+            (Some(time_zone), LogicalPlan::DfPlan(df_plan)) => {
+                let df_plan = TypeConversionRule::with_time_zone(Some(time_zone))
+                    .optimize(&df_plan, &ExecutionProps::new())
+                    .context(error::DatafusionSnafu {
+                        msg: "Fail to convert types in logical plan",
+                    })?;
+                Ok(LogicalPlan::DfPlan(df_plan))
+            }
+            (None, plan) => Ok(plan),
+        }
     }
 
     fn sql_to_plan(&self, sql: &str, query_ctx: QueryContextRef) -> Result<LogicalPlan> {
@@ -114,6 +142,10 @@ impl QueryEngine for DatafusionQueryEngine {
         self.state.register_udf(udf);
     }
 
+    fn deregister_udf(&self, name: &str) {
+        self.state.deregister_udf(name);
+    }
+
     /// Note in SQL queries, aggregate names are looked up using
     /// lowercase unless the query uses quotes. For example,
     ///
@@ -73,6 +73,31 @@ pub enum InnerError {
         #[snafu(backtrace)]
         source: common_query::error::Error,
     },
+
+    #[snafu(display("Failed to plan FILL, reason: {}", reason))]
+    PlanFill {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to fill time buckets, reason: {}", reason))]
+    FillBuckets {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to plan range query, reason: {}", reason))]
+    PlanRange {
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Permission denied for user {}: {}", user, action))]
+    PermissionDenied {
+        user: String,
+        action: String,
+        backtrace: Backtrace,
+    },
 }
 
 impl ErrorExt for InnerError {
@@ -90,6 +115,8 @@ impl ErrorExt for InnerError {
             PlanSql { .. } => StatusCode::PlanQuery,
             ConvertDfRecordBatchStream { source } => source.status_code(),
             ExecutePhysicalPlan { source } => source.status_code(),
+            PlanFill { .. } | FillBuckets { .. } | PlanRange { .. } => StatusCode::InvalidArguments,
+            PermissionDenied { .. } => StatusCode::AccessDenied,
         }
     }
 
@@ -0,0 +1,576 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Gap filling for `GROUP BY time_bucket(..) FILL(..)` queries.
+//!
+//! The planner inserts a [FillNode] above the aggregate, which is planned to a [FillExec] that
+//! adds a row for every bucket missing from a series, so all series have evenly spaced points.
+//!
+//! The fill is below the `HAVING` filter, so filled rows are filtered like the aggregated rows,
+//! e.g. `HAVING max(cpu) > 0 FILL(0)` drops the filled rows, while `PREVIOUS` and `LINEAR`
+//! still fill from the buckets the filter drops.
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use common_query::physical_plan::RuntimeEnv;
+use common_time::duration::parse_duration_nanos;
+use common_time::timestamp::Timestamp;
+use datafusion::error::{DataFusionError, Result as DfResult};
+use datafusion::execution::context::ExecutionContextState;
+use datafusion::logical_plan::plan::{Aggregate, Extension};
+use datafusion::logical_plan::{
+    DFSchemaRef, Expr, LogicalPlan as DfLogicalPlan, UserDefinedLogicalNode,
+};
+use datafusion::optimizer::utils::from_plan;
+use datafusion::physical_plan::expressions::PhysicalSortExpr;
+use datafusion::physical_plan::memory::MemoryStream;
+use datafusion::physical_plan::planner::ExtensionPlanner;
+use datafusion::physical_plan::{
+    collect, DisplayFormatType, ExecutionPlan, Partitioning, PhysicalPlanner,
+    SendableRecordBatchStream, Statistics,
+};
+use datafusion_common::record_batch::RecordBatch as DfRecordBatch;
+use datafusion_common::ScalarValue;
+use datatypes::arrow::array::ArrayRef;
+use datatypes::arrow::compute::concatenate::concatenate;
+use datatypes::arrow::datatypes::SchemaRef as ArrowSchemaRef;
+use datatypes::data_type::{ConcreteDataType, DataType};
+use datatypes::value::Value;
+use datatypes::vectors::{Helper, VectorRef};
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::statements::query::Fill;
+
+use crate::datafusion::error;
+use crate::error::{Result, VectorComputationSnafu};
+
+/// Name of the function whose buckets are filled.
+const TIME_BUCKET: &str = "time_bucket";
+
+/// Maximum number of rows a fill may produce, guards against a tiny interval over a long range.
+const MAX_FILL_ROWS: usize = 1_000_000;
+
+/// Inserts a [FillNode] above the aggregate of `plan` that groups by `time_bucket(..)`.
+///
+/// Only projections, filters, sorts and limits are allowed between the root of the plan and
+/// the aggregate. The fill is directly above the aggregate, so a `HAVING` filter applies to
+/// the filled rows as well.
+pub(crate) fn plan_fill(plan: &DfLogicalPlan, fill: &Fill) -> Result<DfLogicalPlan> {
+    match plan {
+        DfLogicalPlan::Aggregate(Aggregate { group_expr, .. }) => {
+            let (bucket, interval) = group_expr
+                .iter()
+                .enumerate()
+                .find_map(|(i, expr)| time_bucket_interval(expr).map(|interval| (i, interval)))
+                .context(error::PlanFillSnafu {
+                    reason: "FILL requires GROUP BY time_bucket(<interval literal>, ..)",
+                })?;
+            let interval_nanos =
+                parse_duration_nanos(interval)
+                    .ok()
+                    .with_context(|| error::PlanFillSnafu {
+                        reason: format!("invalid time_bucket interval {}", interval),
+                    })?;
+            let keys = (0..group_expr.len()).filter(|i| *i != bucket).collect();
+
+            Ok(DfLogicalPlan::Extension(Extension {
+                node: Arc::new(FillNode {
+                    input: plan.clone(),
+                    bucket,
+                    keys,
+                    interval_nanos,
+                    fill: fill.clone(),
+                }),
+            }))
+        }
+        DfLogicalPlan::Projection { .. }
+        | DfLogicalPlan::Filter { .. }
+        | DfLogicalPlan::Sort { .. }
+        | DfLogicalPlan::Limit { .. } => {
+            let input = plan_fill(plan.inputs()[0], fill)?;
+            let plan =
+                from_plan(plan, &plan.expressions(), &[input]).context(error::DatafusionSnafu {
+                    msg: "Fail to plan FILL",
+                })?;
+            Ok(plan)
+        }
+        _ => error::PlanFillSnafu {
+            reason: "FILL requires GROUP BY time_bucket(<interval literal>, ..)",
+        }
+        .fail()
+        .map_err(Into::into),
+    }
+}
+
+/// Returns the interval if `expr` is `time_bucket(<interval literal>, ..)`.
+fn time_bucket_interval(expr: &Expr) -> Option<&str> {
+    match expr {
+        Expr::Alias(expr, _) => time_bucket_interval(expr),
+        Expr::ScalarUDF { fun, args } if fun.name == TIME_BUCKET => match args.first() {
+            Some(Expr::Literal(ScalarValue::Utf8(Some(interval)))) => Some(interval),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Logical node that fills the missing buckets of the aggregate below it.
+///
+/// The output schema is the same as the schema of the aggregate.
+#[derive(Debug)]
+pub(crate) struct FillNode {
+    input: DfLogicalPlan,
+    /// Index of the `time_bucket(..)` column.
+    bucket: usize,
+    /// Indices of the other group columns, which identify a series.
+    keys: Vec<usize>,
+    interval_nanos: i64,
+    fill: Fill,
+}
+
+impl UserDefinedLogicalNode for FillNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.input.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        // Filled rows need every column, so none of them may be pruned from the input.
+        self.input
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| Expr::Column(field.qualified_column()))
+            .collect()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Fill: bucket={}, interval={}ns, fill={}",
+            self.input.schema().field(self.bucket).qualified_name(),
+            self.interval_nanos,
+            self.fill
+        )
+    }
+
+    fn from_template(
+        &self,
+        _exprs: &[Expr],
+        inputs: &[DfLogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode + Send + Sync> {
+        Arc::new(FillNode {
+            input: inputs[0].clone(),
+            bucket: self.bucket,
+            keys: self.keys.clone(),
+            interval_nanos: self.interval_nanos,
+            fill: self.fill.clone(),
+        })
+    }
+}
+
+/// Plans [FillNode] to [FillExec].
+pub(crate) struct FillPlanner;
+
+impl ExtensionPlanner for FillPlanner {
+    fn plan_extension(
+        &self,
+        _planner: &dyn PhysicalPlanner,
+        node: &dyn UserDefinedLogicalNode,
+        _logical_inputs: &[&DfLogicalPlan],
+        physical_inputs: &[Arc<dyn ExecutionPlan>],
+        _ctx_state: &ExecutionContextState,
+    ) -> DfResult<Option<Arc<dyn ExecutionPlan>>> {
+        Ok(node.as_any().downcast_ref::<FillNode>().map(|node| {
+            Arc::new(FillExec {
+                input: physical_inputs[0].clone(),
+                bucket: node.bucket,
+                keys: node.keys.clone(),
+                interval_nanos: node.interval_nanos,
+                fill: node.fill.clone(),
+            }) as _
+        }))
+    }
+}
+
+/// Fills the missing buckets of each series of its input.
+///
+/// All partitions of the input are collected, since rows of a series may be in any of them.
+#[derive(Debug)]
+pub(crate) struct FillExec {
+    input: Arc<dyn ExecutionPlan>,
+    bucket: usize,
+    keys: Vec<usize>,
+    interval_nanos: i64,
+    fill: Fill,
+}
+
+#[async_trait]
+impl ExecutionPlan for FillExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.input.schema()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(FillExec {
+            input: children[0].clone(),
+            bucket: self.bucket,
+            keys: self.keys.clone(),
+            interval_nanos: self.interval_nanos,
+            fill: self.fill.clone(),
+        }))
+    }
+
+    async fn execute(
+        &self,
+        _partition: usize,
+        runtime: Arc<RuntimeEnv>,
+    ) -> DfResult<SendableRecordBatchStream> {
+        let schema = self.schema();
+        let batches = collect(self.input.clone(), runtime).await?;
+        if batches.is_empty() {
+            return Ok(Box::pin(MemoryStream::try_new(vec![], schema, None)?));
+        }
+
+        let arrays = (0..schema.fields().len())
+            .map(|i| {
+                let arrays = batches
+                    .iter()
+                    .map(|batch| batch.column(i).as_ref())
+                    .collect::<Vec<_>>();
+                concatenate(&arrays).map(ArrayRef::from)
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let columns = Helper::try_into_vectors(&arrays)
+            .context(VectorComputationSnafu)
+            .map_err(crate::error::Error::from)?;
+
+        let columns = fill_columns(
+            &columns,
+            self.bucket,
+            &self.keys,
+            self.interval_nanos,
+            &self.fill,
+        )?;
+        let batch = DfRecordBatch::try_new(
+            schema.clone(),
+            columns.iter().map(|c| c.to_arrow_array()).collect(),
+        )?;
+        Ok(Box::pin(MemoryStream::try_new(vec![batch], schema, None)?))
+    }
+
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "FillExec: interval={}ns, fill={}",
+            self.interval_nanos, self.fill
+        )
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// Fills the missing buckets of every series in `columns`, where a series is identified by the
+/// values of the `keys` columns.
+///
+/// Each series gets a row for every bucket between the first and the last bucket of all series.
+/// Rows with a null bucket are kept at the end of the output.
+fn fill_columns(
+    columns: &[VectorRef],
+    bucket: usize,
+    keys: &[usize],
+    interval_nanos: i64,
+    fill: &Fill,
+) -> Result<Vec<VectorRef>> {
+    let unit = match columns[bucket].data_type() {
+        ConcreteDataType::Timestamp(t) => t.unit,
+        other => {
+            return error::FillBucketsSnafu {
+                reason: format!("time bucket should be a timestamp, found {:?}", other),
+            }
+            .fail()
+            .map_err(Into::into)
+        }
+    };
+    let interval = interval_nanos / unit.factor();
+    ensure!(
+        interval > 0,
+        error::FillBucketsSnafu {
+            reason: format!("interval {}ns is finer than {:?}", interval_nanos, unit),
+        }
+    );
+
+    // Row indices of each series, keyed by bucket.
+    let mut series: BTreeMap<Vec<Value>, BTreeMap<i64, usize>> = BTreeMap::new();
+    let mut null_bucket_rows = Vec::new();
+    for row in 0..columns[bucket].len() {
+        match columns[bucket].get(row) {
+            Value::Timestamp(ts) => {
+                let key = keys.iter().map(|k| columns[*k].get(row)).collect();
+                series
+                    .entry(key)
+                    .or_default()
+                    .insert(ts.convert_to(unit), row);
+            }
+            _ => null_bucket_rows.push(row),
+        }
+    }
+
+    let first = series.values().filter_map(|b| b.keys().next()).min();
+    let last = series.values().filter_map(|b| b.keys().next_back()).max();
+    let (first, last) = match (first, last) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return Ok(columns.to_vec()),
+    };
+    let num_buckets = ((last as i128 - first as i128) / interval as i128 + 1) as usize;
+    let num_rows = num_buckets.saturating_mul(series.len());
+    ensure!(
+        num_rows <= MAX_FILL_ROWS,
+        error::FillBucketsSnafu {
+            reason: format!(
+                "it would produce {} rows, more than the limit {}, try a larger interval",
+                num_rows, MAX_FILL_ROWS
+            ),
+        }
+    );
+
+    let mut builders = columns
+        .iter()
+        .map(|c| c.data_type().create_mutable_vector(num_rows))
+        .collect::<Vec<_>>();
+    let rows = series.iter().flat_map(|(key, buckets)| {
+        (0..num_buckets).map(move |i| (key, buckets, first + i as i64 * interval))
+    });
+    for (key, buckets, ts) in rows {
+        if let Some(row) = buckets.get(&ts) {
+            for (builder, column) in builders.iter_mut().zip(columns) {
+                builder
+                    .push_value_ref(column.get_ref(*row))
+                    .context(VectorComputationSnafu)?;
+            }
+            continue;
+        }
+
+        for (i, builder) in builders.iter_mut().enumerate() {
+            let value = if i == bucket {
+                Value::Timestamp(Timestamp::new(ts, unit))
+            } else if let Some(k) = keys.iter().position(|k| *k == i) {
+                key[k].clone()
+            } else {
+                fill_value(&columns[i], buckets, ts, fill)
+            };
+            builder
+                .push_value_ref(value.as_value_ref())
+                .context(VectorComputationSnafu)?;
+        }
+    }
+    for row in null_bucket_rows {
+        for (builder, column) in builders.iter_mut().zip(columns) {
+            builder
+                .push_value_ref(column.get_ref(row))
+                .context(VectorComputationSnafu)?;
+        }
+    }
+
+    Ok(builders.iter_mut().map(|b| b.to_vector()).collect())
+}
+
+/// Returns the value of `column` for the missing bucket `ts` of a series whose existing rows
+/// are `buckets`.
+fn fill_value(column: &VectorRef, buckets: &BTreeMap<i64, usize>, ts: i64, fill: &Fill) -> Value {
+    match fill {
+        Fill::Null => Value::Null,
+        Fill::Value(v) => numeric_value(&column.data_type(), v.0),
+        Fill::Previous => buckets
+            .range(..ts)
+            .next_back()
+            .map_or(Value::Null, |(_, row)| column.get(*row)),
+        Fill::Linear => {
+            let prev = buckets.range(..ts).next_back();
+            let next = buckets.range(ts..).next();
+            let (t0, v0, t1, v1) = match (prev, next) {
+                (Some((t0, r0)), Some((t1, r1))) => {
+                    match (as_f64(&column.get(*r0)), as_f64(&column.get(*r1))) {
+                        (Some(v0), Some(v1)) => (*t0, v0, *t1, v1),
+                        _ => return Value::Null,
+                    }
+                }
+                _ => return Value::Null,
+            };
+            let v = v0 + (v1 - v0) * (ts - t0) as f64 / (t1 - t0) as f64;
+            numeric_value(&column.data_type(), v)
+        }
+    }
+}
+
+fn as_f64(value: &Value) -> Option<f64> {
+    match value {
+        Value::Int8(v) => Some(*v as f64),
+        Value::Int16(v) => Some(*v as f64),
+        Value::Int32(v) => Some(*v as f64),
+        Value::Int64(v) => Some(*v as f64),
+        Value::UInt8(v) => Some(*v as f64),
+        Value::UInt16(v) => Some(*v as f64),
+        Value::UInt32(v) => Some(*v as f64),
+        Value::UInt64(v) => Some(*v as f64),
+        Value::Float32(v) => Some(v.0 as f64),
+        Value::Float64(v) => Some(v.0),
+        _ => None,
+    }
+}
+
+/// Converts `v` to a value of `data_type`, integers are rounded and non-numeric types get null.
+fn numeric_value(data_type: &ConcreteDataType, v: f64) -> Value {
+    match data_type {
+        ConcreteDataType::Int8(_) => Value::from(v.round() as i8),
+        ConcreteDataType::Int16(_) => Value::from(v.round() as i16),
+        ConcreteDataType::Int32(_) => Value::from(v.round() as i32),
+        ConcreteDataType::Int64(_) => Value::from(v.round() as i64),
+        ConcreteDataType::UInt8(_) => Value::from(v.round() as u8),
+        ConcreteDataType::UInt16(_) => Value::from(v.round() as u16),
+        ConcreteDataType::UInt32(_) => Value::from(v.round() as u32),
+        ConcreteDataType::UInt64(_) => Value::from(v.round() as u64),
+        ConcreteDataType::Float32(_) => Value::from(v as f32),
+        ConcreteDataType::Float64(_) => Value::from(v),
+        _ => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_time::timestamp::TimeUnit;
+    use datatypes::value::OrderedF64;
+    use datatypes::vectors::{Float64Vector, Int64Vector, StringVector, TimestampVector};
+
+    use super::*;
+
+    const SECOND: i64 = 1_000_000_000;
+
+    /// Two series, `a` misses the bucket at 2s and `b` only has the bucket at 1s.
+    fn columns() -> Vec<VectorRef> {
+        vec![
+            Arc::new(TimestampVector::from_values_with_unit(
+                vec![0, 3, 1],
+                TimeUnit::Second,
+            )),
+            Arc::new(StringVector::from(vec!["a", "a", "b"])),
+            Arc::new(Float64Vector::from_slice(&[1.0, 4.0, 10.0])),
+            Arc::new(Int64Vector::from_slice(&[1, 2, 3])),
+        ]
+    }
+
+    fn rows(columns: &[VectorRef]) -> Vec<Vec<Value>> {
+        (0..columns[0].len())
+            .map(|row| columns.iter().map(|c| c.get(row)).collect())
+            .collect()
+    }
+
+    fn row(ts: i64, key: &str, float: Option<f64>, int: Option<i64>) -> Vec<Value> {
+        vec![
+            Value::Timestamp(Timestamp::new(ts, TimeUnit::Second)),
+            Value::from(key),
+            float.map_or(Value::Null, Value::from),
+            int.map_or(Value::Null, Value::from),
+        ]
+    }
+
+    #[test]
+    fn test_fill_null() {
+        let filled = fill_columns(&columns(), 0, &[1], SECOND, &Fill::Null).unwrap();
+        assert_eq!(
+            vec![
+                row(0, "a", Some(1.0), Some(1)),
+                row(1, "a", None, None),
+                row(2, "a", None, None),
+                row(3, "a", Some(4.0), Some(2)),
+                row(0, "b", None, None),
+                row(1, "b", Some(10.0), Some(3)),
+                row(2, "b", None, None),
+                row(3, "b", None, None),
+            ],
+            rows(&filled)
+        );
+    }
+
+    #[test]
+    fn test_fill_previous_and_value() {
+        let filled = fill_columns(&columns(), 0, &[1], SECOND, &Fill::Previous).unwrap();
+        let filled = rows(&filled);
+        assert_eq!(row(1, "a", Some(1.0), Some(1)), filled[1]);
+        assert_eq!(row(2, "a", Some(1.0), Some(1)), filled[2]);
+        // There is no previous bucket.
+        assert_eq!(row(0, "b", None, None), filled[4]);
+        assert_eq!(row(3, "b", Some(10.0), Some(3)), filled[7]);
+
+        let fill = Fill::Value(OrderedF64::from(-1.5));
+        let filled = fill_columns(&columns(), 0, &[1], SECOND, &fill).unwrap();
+        let filled = rows(&filled);
+        assert_eq!(row(1, "a", Some(-1.5), Some(-2)), filled[1]);
+        assert_eq!(row(0, "b", Some(-1.5), Some(-2)), filled[4]);
+    }
+
+    #[test]
+    fn test_fill_linear() {
+        let filled = fill_columns(&columns(), 0, &[1], SECOND, &Fill::Linear).unwrap();
+        let filled = rows(&filled);
+        assert_eq!(row(1, "a", Some(2.0), Some(1)), filled[1]);
+        assert_eq!(row(2, "a", Some(3.0), Some(2)), filled[2]);
+        // Values can't be interpolated at the edges of a series.
+        assert_eq!(row(0, "b", None, None), filled[4]);
+        assert_eq!(row(2, "b", None, None), filled[6]);
+    }
+
+    #[test]
+    fn test_fill_invalid() {
+        // Interval finer than the unit of the bucket.
+        assert!(fill_columns(&columns(), 0, &[1], SECOND / 2, &Fill::Null).is_err());
+        // Too many rows.
+        let columns = vec![Arc::new(TimestampVector::from_values_with_unit(
+            vec![0, i64::MAX / 2],
+            TimeUnit::Nanosecond,
+        )) as VectorRef];
+        assert!(fill_columns(&columns, 0, &[], 1, &Fill::Null).is_err());
+    }
+}
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_catalog::privilege::Privilege;
 use common_query::logical_plan::create_aggregate_function;
+use common_telemetry::warn;
 use datafusion::catalog::TableReference;
 use datafusion::datasource::TableProvider;
 use datafusion::physical_plan::udaf::AggregateUDF;
@@ -27,7 +30,7 @@ use sql::statements::explain::Explain;
 use sql::statements::query::Query;
 use sql::statements::statement::Statement;
 
-use crate::datafusion::error;
+use crate::datafusion::{error, fill, range};
 use crate::error::Result;
 use crate::plan::LogicalPlan;
 use crate::planner::Planner;
@@ -35,23 +38,41 @@ use crate::query_engine::QueryEngineState;
 
 pub struct DfPlanner<'a, S: ContextProvider> {
     sql_to_rel: SqlToRel<'a, S>,
+    schema_provider: &'a S,
 }
 
 impl<'a, S: ContextProvider + Send + Sync> DfPlanner<'a, S> {
     /// Creates a DataFusion planner instance
     pub fn new(schema_provider: &'a S) -> Self {
         let rel = SqlToRel::new(schema_provider);
-        Self { sql_to_rel: rel }
+        Self {
+            sql_to_rel: rel,
+            schema_provider,
+        }
     }
 
     /// Converts QUERY statement to logical plan.
     pub fn query_to_plan(&self, query: Box<Query>) -> Result<LogicalPlan> {
+        let Query {
+            mut inner,
+            fill,
+            range_select,
+        } = *query;
+        if let Some(range_select) = &range_select {
+            range::rewrite_query(&mut inner, range_select, self.schema_provider)?;
+        }
         // todo(hl): original SQL should be provided as an argument
-        let sql = query.inner.to_string();
-        let result = self
+        let sql = inner.to_string();
+        let mut result = self
             .sql_to_rel
-            .query_to_plan(query.inner)
+            .query_to_plan(inner)
             .context(error::PlanSqlSnafu { sql })?;
+        if let Some(range_select) = &range_select {
+            result = range::plan_range_select(&result, range_select)?;
+        }
+        if let Some(fill) = &fill {
+            result = fill::plan_fill(&result, fill)?;
+        }
 
         Ok(LogicalPlan::DfPlan(result))
     }
@@ -81,13 +102,22 @@ where
             Statement::ShowTables(_)
             | Statement::ShowDatabases(_)
             | Statement::ShowCreateTable(_)
+            | Statement::ShowProcesslist(_)
+            | Statement::Kill(_)
             | Statement::DescribeTable(_)
             | Statement::CreateTable(_)
             | Statement::CreateDatabase(_)
+            | Statement::CreateCatalog(_)
+            | Statement::DropCatalog(_)
             | Statement::Alter(_)
             | Statement::Insert(_)
             | Statement::DropTable(_)
-            | Statement::Use(_) => unreachable!(),
+            | Statement::Use(_)
+            | Statement::SetVariables(_)
+            | Statement::CreateUser(_)
+            | Statement::CreateRole(_)
+            | Statement::Grant(_)
+            | Statement::Revoke(_) => unreachable!(),
         }
     }
 }
@@ -95,11 +125,56 @@ where
 pub(crate) struct DfContextProviderAdapter {
     state: QueryEngineState,
     query_ctx: QueryContextRef,
+    /// The user and the action denied while planning.
+    denied: Mutex<Option<(String, String)>>,
 }
 
 impl DfContextProviderAdapter {
     pub(crate) fn new(state: QueryEngineState, query_ctx: QueryContextRef) -> Self {
-        Self { state, query_ctx }
+        Self {
+            state,
+            query_ctx,
+            denied: Mutex::new(None),
+        }
+    }
+
+    /// Fails if the session isn't allowed to query some of the tables of the planned statement.
+    pub(crate) fn ensure_permitted(&self) -> Result<()> {
+        match self.denied.lock().unwrap().take() {
+            Some((user, action)) => {
+                Err(error::PermissionDeniedSnafu { user, action }.build().into())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Returns whether the session can query the table, the denial is recorded, as tables
+    /// without providers are reported as not found by DataFusion.
+    fn check_select(&self, schema: &str, table: &str) -> bool {
+        let manager = match self.state.privilege_manager() {
+            Some(manager) => manager,
+            None => return true,
+        };
+        let tenant = self.query_ctx.tenant();
+        // Sessions of users always come from servers, only internal ones, like the sessions
+        // of scripts, are trusted without users.
+        let user = match self.query_ctx.current_user() {
+            Some(user)
+                if manager.check(tenant.as_deref(), &user, Privilege::Select, schema, table) =>
+            {
+                return true
+            }
+            Some(user) => user,
+            None if self.query_ctx.is_internal() => return true,
+            None => "anonymous".to_string(),
+        };
+        warn!(
+            "User {} is denied to query table {}.{}",
+            user, schema, table
+        );
+        let action = format!("{} on {}.{}", Privilege::Select, schema, table);
+        *self.denied.lock().unwrap() = Some((user, action));
+        false
     }
 }
 
@@ -107,18 +182,39 @@ impl DfContextProviderAdapter {
 ///                           manage UDFs, UDAFs, variables by ourself in future.
 impl ContextProvider for DfContextProviderAdapter {
     fn get_table_provider(&self, name: TableReference) -> Option<Arc<dyn TableProvider>> {
-        let schema = self.query_ctx.current_schema();
-        let execution_ctx = self.state.df_context().state.lock();
-        match name {
-            TableReference::Bare { table } if schema.is_some() => {
-                execution_ctx.get_table_provider(TableReference::Partial {
-                    // unwrap safety: checked in this match's arm
-                    schema: &schema.unwrap(),
-                    table,
-                })
-            }
-            _ => execution_ctx.get_table_provider(name),
+        let current_catalog = self.query_ctx.current_catalog();
+        let current_schema = self.query_ctx.current_schema();
+        let current_catalog = current_catalog.as_deref().unwrap_or(DEFAULT_CATALOG_NAME);
+        let (catalog, schema, table) = match name {
+            TableReference::Full {
+                catalog,
+                schema,
+                table,
+            } => (catalog, schema, table),
+            TableReference::Partial { schema, table } => (current_catalog, schema, table),
+            TableReference::Bare { table } => (
+                current_catalog,
+                current_schema.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME),
+                table,
+            ),
+        };
+        // Tables in catalogs of other tenants are treated as not found.
+        if !self.query_ctx.is_catalog_accessible(catalog) {
+            warn!("Access denied to catalog: {}", catalog);
+            return None;
+        }
+        if !self.check_select(schema, table) {
+            return None;
         }
+        self.state
+            .df_context()
+            .state
+            .lock()
+            .get_table_provider(TableReference::Full {
+                catalog,
+                schema,
+                table,
+            })
     }
 
     fn get_function_meta(&self, name: &str) -> Option<Arc<ScalarUDF>> {
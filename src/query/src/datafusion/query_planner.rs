@@ -0,0 +1,45 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use datafusion::error::Result as DfResult;
+use datafusion::execution::context::{ExecutionContextState, QueryPlanner};
+use datafusion::logical_plan::LogicalPlan as DfLogicalPlan;
+use datafusion::physical_plan::planner::DefaultPhysicalPlanner;
+use datafusion::physical_plan::{ExecutionPlan, PhysicalPlanner};
+
+use crate::datafusion::fill::FillPlanner;
+use crate::datafusion::range::RangePlanner;
+
+/// DataFusion query planner that also plans the extension nodes of our logical plans.
+#[derive(Default)]
+pub(crate) struct DfQueryPlanner;
+
+#[async_trait]
+impl QueryPlanner for DfQueryPlanner {
+    async fn create_physical_plan(
+        &self,
+        logical_plan: &DfLogicalPlan,
+        ctx_state: &ExecutionContextState,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        DefaultPhysicalPlanner::with_extension_planners(vec![
+            Arc::new(FillPlanner),
+            Arc::new(RangePlanner),
+        ])
+        .create_physical_plan(logical_plan, ctx_state)
+        .await
+    }
+}
@@ -0,0 +1,810 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Range queries, which evaluate aggregates over sliding windows of each series.
+//!
+//! `SELECT ts, host, avg(cpu) RANGE '5m' FROM monitor ALIGN '1m' BY (host)` is planned by
+//! DataFusion as an aggregate grouped by the time index and `host`, then the aggregate is
+//! replaced by a [RangeNode]. It runs on a [RangeExec], which streams its input sorted by series
+//! and time, and outputs a row every `ALIGN` interval with the aggregates over the look-back
+//! window `(ts - RANGE, ts]`.
+
+use std::any::Any;
+use std::fmt;
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use common_query::physical_plan::RuntimeEnv;
+use common_time::timestamp::{TimeUnit, Timestamp};
+use datafusion::error::{DataFusionError, Result as DfResult};
+use datafusion::execution::context::ExecutionContextState;
+use datafusion::logical_plan::plan::{Aggregate, Extension};
+use datafusion::logical_plan::{
+    DFSchema, DFSchemaRef, Expr, LogicalPlan as DfLogicalPlan, UserDefinedLogicalNode,
+};
+use datafusion::optimizer::utils::from_plan;
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::expressions::PhysicalSortExpr;
+use datafusion::physical_plan::planner::{create_aggregate_expr, ExtensionPlanner};
+use datafusion::physical_plan::sorts::sort::SortExec;
+use datafusion::physical_plan::{
+    DisplayFormatType, ExecutionPlan, Partitioning, PhysicalPlanner, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use datafusion::sql::planner::ContextProvider;
+use datafusion_common::record_batch::RecordBatch as DfRecordBatch;
+use datafusion_physical_expr::{AggregateExpr, PhysicalExpr};
+use datatypes::arrow::array::ArrayRef;
+use datatypes::arrow::compute::concatenate::concatenate;
+use datatypes::arrow::compute::sort::SortOptions;
+use datatypes::arrow::datatypes::{Schema as ArrowSchema, SchemaRef as ArrowSchemaRef};
+use datatypes::arrow::error::{ArrowError, Result as ArrowResult};
+use datatypes::data_type::{ConcreteDataType, DataType};
+use datatypes::schema::Schema;
+use datatypes::value::Value;
+use datatypes::vectors::{Helper, MutableVector, VectorRef};
+use futures::{ready, Stream, StreamExt};
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::ast::{
+    Expr as SqlExpr, Ident, Query as SpQuery, SelectItem, SetExpr, TableFactor, TableWithJoins,
+};
+use sql::statements::query::RangeSelect;
+
+use crate::datafusion::error;
+use crate::error::{Result, TableNotFoundSnafu, VectorComputationSnafu};
+
+/// Groups the range query `query` by the time index of its table and the `ALIGN .. BY`
+/// expressions, so DataFusion plans the aggregate that [plan_range_select] replaces.
+///
+/// A range query must select from a single table, without `GROUP BY` or wildcards.
+pub(crate) fn rewrite_query<S: ContextProvider>(
+    query: &mut SpQuery,
+    range_select: &RangeSelect,
+    schema_provider: &S,
+) -> Result<()> {
+    let select = match &mut query.body {
+        SetExpr::Select(select) => select,
+        _ => {
+            return error::PlanRangeSnafu {
+                reason: "range query must be a SELECT",
+            }
+            .fail()
+            .map_err(Into::into)
+        }
+    };
+    ensure!(
+        select.group_by.is_empty(),
+        error::PlanRangeSnafu {
+            reason: "range query can't have GROUP BY, use ALIGN .. BY instead",
+        }
+    );
+    ensure!(
+        select.projection.iter().all(|item| matches!(
+            item,
+            SelectItem::UnnamedExpr(_) | SelectItem::ExprWithAlias { .. }
+        )),
+        error::PlanRangeSnafu {
+            reason: "range query can't select wildcards",
+        }
+    );
+    let table_name = match &select.from[..] {
+        [TableWithJoins {
+            relation: TableFactor::Table { name, .. },
+            joins,
+        }] if joins.is_empty() => name.to_string(),
+        _ => {
+            return error::PlanRangeSnafu {
+                reason: "range query must select from a single table",
+            }
+            .fail()
+            .map_err(Into::into)
+        }
+    };
+
+    let table = schema_provider
+        .get_table_provider(table_name.as_str().into())
+        .context(TableNotFoundSnafu { table: &table_name })?;
+    let schema = Schema::try_from(table.schema()).context(error::ConvertSchemaSnafu)?;
+    let time_index = schema
+        .timestamp_column()
+        .with_context(|| error::PlanRangeSnafu {
+            reason: format!("table {} has no time index", table_name),
+        })?;
+
+    select.group_by = std::iter::once(SqlExpr::Identifier(Ident::new(&time_index.name)))
+        .chain(range_select.by.iter().cloned())
+        .collect();
+    Ok(())
+}
+
+/// Replaces the aggregate of the range query `plan` with a [RangeNode].
+///
+/// Only sorts and limits are allowed above the projection of the query, and only a filter
+/// between the projection and the aggregate.
+pub(crate) fn plan_range_select(
+    plan: &DfLogicalPlan,
+    range_select: &RangeSelect,
+) -> Result<DfLogicalPlan> {
+    let input = match plan {
+        DfLogicalPlan::Projection { .. } => {
+            let exprs = plan.expressions();
+            let ranges = range_select
+                .ranges
+                .iter()
+                .map(|(i, range)| {
+                    exprs
+                        .get(*i)
+                        .map(|expr| (expr, *range))
+                        .context(error::PlanRangeSnafu {
+                            reason: "RANGE is out of the projection",
+                        })
+                })
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            replace_aggregate(plan.inputs()[0], &ranges, range_select.align)?
+        }
+        DfLogicalPlan::Sort { .. } | DfLogicalPlan::Limit { .. } => {
+            plan_range_select(plan.inputs()[0], range_select)?
+        }
+        _ => {
+            return error::PlanRangeSnafu {
+                reason: "range query must aggregate its rows",
+            }
+            .fail()
+            .map_err(Into::into)
+        }
+    };
+    let plan = from_plan(plan, &plan.expressions(), &[input]).context(error::DatafusionSnafu {
+        msg: "Fail to plan range query",
+    })?;
+    Ok(plan)
+}
+
+fn replace_aggregate(
+    plan: &DfLogicalPlan,
+    ranges: &[(&Expr, i64)],
+    align: i64,
+) -> Result<DfLogicalPlan> {
+    match plan {
+        DfLogicalPlan::Aggregate(Aggregate {
+            input,
+            group_expr,
+            aggr_expr,
+            schema,
+        }) => {
+            let mut aggr_ranges = vec![None; aggr_expr.len()];
+            for (expr, range) in ranges {
+                let i = aggregate_index(expr, schema, group_expr.len()).with_context(|| {
+                    error::PlanRangeSnafu {
+                        reason: format!(
+                            "RANGE must follow an aggregate function, found {:?}",
+                            expr
+                        ),
+                    }
+                })?;
+                ensure!(
+                    aggr_ranges[i].map_or(true, |r| r == *range),
+                    error::PlanRangeSnafu {
+                        reason: format!("aggregate {:?} has different ranges", aggr_expr[i]),
+                    }
+                );
+                aggr_ranges[i] = Some(*range);
+            }
+
+            Ok(DfLogicalPlan::Extension(Extension {
+                node: Arc::new(RangeNode {
+                    input: input.as_ref().clone(),
+                    group_expr: group_expr.clone(),
+                    aggr_expr: aggr_expr.clone(),
+                    ranges: aggr_ranges
+                        .into_iter()
+                        .map(|r| r.unwrap_or(align))
+                        .collect(),
+                    align,
+                    schema: schema.clone(),
+                }),
+            }))
+        }
+        DfLogicalPlan::Filter { .. } => {
+            let input = replace_aggregate(plan.inputs()[0], ranges, align)?;
+            let plan =
+                from_plan(plan, &plan.expressions(), &[input]).context(error::DatafusionSnafu {
+                    msg: "Fail to plan range query",
+                })?;
+            Ok(plan)
+        }
+        _ => error::PlanRangeSnafu {
+            reason: "range query must aggregate its rows",
+        }
+        .fail()
+        .map_err(Into::into),
+    }
+}
+
+/// Returns the index of the aggregate that `expr` projects from the output of an aggregate with
+/// `schema` and `num_groups` group expressions.
+fn aggregate_index(expr: &Expr, schema: &DFSchema, num_groups: usize) -> Option<usize> {
+    match expr {
+        Expr::Alias(expr, _) => aggregate_index(expr, schema, num_groups),
+        Expr::Column(column) => schema.index_of_column(column).ok()?.checked_sub(num_groups),
+        _ => None,
+    }
+}
+
+/// Logical node that evaluates aggregates over sliding windows of each series.
+///
+/// The output schema is the same as the schema of the aggregate it replaces.
+#[derive(Debug)]
+pub(crate) struct RangeNode {
+    input: DfLogicalPlan,
+    /// The time index followed by the expressions that identify a series.
+    group_expr: Vec<Expr>,
+    aggr_expr: Vec<Expr>,
+    /// Window length of each aggregate in nanoseconds.
+    ranges: Vec<i64>,
+    /// Interval between output rows in nanoseconds.
+    align: i64,
+    schema: DFSchemaRef,
+}
+
+impl UserDefinedLogicalNode for RangeNode {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn inputs(&self) -> Vec<&DfLogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.schema
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        self.group_expr
+            .iter()
+            .chain(&self.aggr_expr)
+            .cloned()
+            .collect()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RangeSelect: align={}ns, ranges={:?}, groupBy={:?}, aggr={:?}",
+            self.align, self.ranges, self.group_expr, self.aggr_expr
+        )
+    }
+
+    fn from_template(
+        &self,
+        exprs: &[Expr],
+        inputs: &[DfLogicalPlan],
+    ) -> Arc<dyn UserDefinedLogicalNode + Send + Sync> {
+        let (group_expr, aggr_expr) = exprs.split_at(self.group_expr.len());
+        Arc::new(RangeNode {
+            input: inputs[0].clone(),
+            group_expr: group_expr.to_vec(),
+            aggr_expr: aggr_expr.to_vec(),
+            ranges: self.ranges.clone(),
+            align: self.align,
+            schema: self.schema.clone(),
+        })
+    }
+}
+
+/// Plans [RangeNode] to [RangeExec].
+pub(crate) struct RangePlanner;
+
+impl ExtensionPlanner for RangePlanner {
+    fn plan_extension(
+        &self,
+        planner: &dyn PhysicalPlanner,
+        node: &dyn UserDefinedLogicalNode,
+        logical_inputs: &[&DfLogicalPlan],
+        physical_inputs: &[Arc<dyn ExecutionPlan>],
+        ctx_state: &ExecutionContextState,
+    ) -> DfResult<Option<Arc<dyn ExecutionPlan>>> {
+        let node = match node.as_any().downcast_ref::<RangeNode>() {
+            Some(node) => node,
+            None => return Ok(None),
+        };
+        let input_dfschema = logical_inputs[0].schema();
+        let input_schema = physical_inputs[0].schema();
+        let group_expr = node
+            .group_expr
+            .iter()
+            .map(|e| planner.create_physical_expr(e, input_dfschema, &input_schema, ctx_state))
+            .collect::<DfResult<Vec<_>>>()?;
+        let aggr_expr = node
+            .aggr_expr
+            .iter()
+            .map(|e| {
+                create_aggregate_expr(e, input_dfschema, &input_schema, &ctx_state.execution_props)
+            })
+            .collect::<DfResult<Vec<_>>>()?;
+
+        let schema: ArrowSchema = node.schema.as_ref().into();
+        let unit = match ConcreteDataType::from_arrow_type(schema.fields()[0].data_type()) {
+            ConcreteDataType::Timestamp(t) => t.unit,
+            other => {
+                return Err(DataFusionError::Plan(format!(
+                    "time index of range query should be a timestamp, found {:?}",
+                    other
+                )))
+            }
+        };
+        let to_unit = |nanos: i64| -> Result<i64> {
+            let interval = nanos / unit.factor();
+            ensure!(
+                interval > 0,
+                error::PlanRangeSnafu {
+                    reason: format!("interval {}ns is finer than {:?}", nanos, unit),
+                }
+            );
+            ensure!(
+                nanos % unit.factor() == 0,
+                error::PlanRangeSnafu {
+                    reason: format!("interval {}ns is not a multiple of {:?}", nanos, unit),
+                }
+            );
+            Ok(interval)
+        };
+        let ranges = node
+            .ranges
+            .iter()
+            .map(|r| to_unit(*r))
+            .collect::<Result<Vec<_>>>()?;
+        let align = to_unit(node.align)?;
+
+        Ok(Some(Arc::new(RangeExec {
+            input: physical_inputs[0].clone(),
+            group_expr,
+            aggr_expr,
+            ranges,
+            align,
+            unit,
+            schema: Arc::new(schema),
+        })))
+    }
+}
+
+/// Evaluates aggregates over sliding windows of each series of its input.
+///
+/// All partitions of the input are merged and sorted by series and time, so the rows of a
+/// series can be aggregated as they are read.
+#[derive(Debug)]
+pub(crate) struct RangeExec {
+    input: Arc<dyn ExecutionPlan>,
+    /// The time index followed by the expressions that identify a series.
+    group_expr: Vec<Arc<dyn PhysicalExpr>>,
+    aggr_expr: Vec<Arc<dyn AggregateExpr>>,
+    /// Window length of each aggregate in the unit of the time index.
+    ranges: Vec<i64>,
+    /// Interval between output rows in the unit of the time index.
+    align: i64,
+    unit: TimeUnit,
+    schema: ArrowSchemaRef,
+}
+
+#[async_trait]
+impl ExecutionPlan for RangeExec {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> ArrowSchemaRef {
+        self.schema.clone()
+    }
+
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn output_ordering(&self) -> Option<&[PhysicalSortExpr]> {
+        None
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> DfResult<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(RangeExec {
+            input: children[0].clone(),
+            group_expr: self.group_expr.clone(),
+            aggr_expr: self.aggr_expr.clone(),
+            ranges: self.ranges.clone(),
+            align: self.align,
+            unit: self.unit,
+            schema: self.schema.clone(),
+        }))
+    }
+
+    async fn execute(
+        &self,
+        _partition: usize,
+        runtime: Arc<RuntimeEnv>,
+    ) -> DfResult<SendableRecordBatchStream> {
+        // Sorts by the keys and then by the time index, so the rows of a series are adjacent.
+        let sort_expr = self.group_expr[1..]
+            .iter()
+            .chain(&self.group_expr[..1])
+            .map(|expr| PhysicalSortExpr {
+                expr: expr.clone(),
+                options: SortOptions::default(),
+            })
+            .collect();
+        let input = SortExec::try_new(
+            sort_expr,
+            Arc::new(CoalescePartitionsExec::new(self.input.clone())),
+        )?;
+        let input = input.execute(0, runtime).await?;
+
+        Ok(Box::pin(RangeStream {
+            input,
+            schema: self.schema.clone(),
+            group_expr: self.group_expr.clone(),
+            aggr_expr: self.aggr_expr.clone(),
+            ranges: self.ranges.clone(),
+            align: self.align,
+            unit: self.unit,
+            series: None,
+            finished: false,
+        }))
+    }
+
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "RangeExec: align={}, ranges={:?}, unit={:?}",
+            self.align, self.ranges, self.unit
+        )
+    }
+
+    fn statistics(&self) -> Statistics {
+        Statistics::default()
+    }
+}
+
+/// Buffered rows of a series.
+struct Series {
+    key: Vec<Value>,
+    timestamps: Vec<i64>,
+    /// Arguments of each aggregate, for every chunk of rows read.
+    args: Vec<Vec<Vec<ArrayRef>>>,
+}
+
+impl Series {
+    fn new(key: Vec<Value>) -> Self {
+        Self {
+            key,
+            timestamps: Vec::new(),
+            args: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, timestamps: &VectorRef, args: &[Vec<ArrayRef>], rows: Range<usize>) {
+        // Rows with a null timestamp are sorted first and aren't in any window.
+        let start = rows
+            .clone()
+            .find(|row| !timestamps.is_null(*row))
+            .unwrap_or(rows.end);
+        if start == rows.end {
+            return;
+        }
+
+        for row in start..rows.end {
+            if let Value::Timestamp(ts) = timestamps.get(row) {
+                self.timestamps.push(ts.value());
+            }
+        }
+        self.args.push(
+            args.iter()
+                .map(|arrays| {
+                    arrays
+                        .iter()
+                        .map(|array| ArrayRef::from(array.slice(start, rows.end - start)))
+                        .collect()
+                })
+                .collect(),
+        );
+    }
+}
+
+struct RangeStream {
+    input: SendableRecordBatchStream,
+    schema: ArrowSchemaRef,
+    group_expr: Vec<Arc<dyn PhysicalExpr>>,
+    aggr_expr: Vec<Arc<dyn AggregateExpr>>,
+    ranges: Vec<i64>,
+    align: i64,
+    unit: TimeUnit,
+    /// The series being read.
+    series: Option<Series>,
+    finished: bool,
+}
+
+impl RangeStream {
+    /// Reads `batch` and returns the output rows of the series it completes.
+    fn process_batch(&mut self, batch: &DfRecordBatch) -> Result<Option<DfRecordBatch>> {
+        let num_rows = batch.num_rows();
+        let evaluate = |expr: &Arc<dyn PhysicalExpr>| {
+            expr.evaluate(batch).map(|value| value.into_array(num_rows))
+        };
+        let group_arrays = self
+            .group_expr
+            .iter()
+            .map(evaluate)
+            .collect::<DfResult<Vec<_>>>()
+            .context(error::DatafusionSnafu {
+                msg: "Fail to evaluate range query keys",
+            })?;
+        let args = self
+            .aggr_expr
+            .iter()
+            .map(|aggr| {
+                aggr.expressions()
+                    .iter()
+                    .map(evaluate)
+                    .collect::<DfResult<Vec<_>>>()
+            })
+            .collect::<DfResult<Vec<_>>>()
+            .context(error::DatafusionSnafu {
+                msg: "Fail to evaluate range query arguments",
+            })?;
+        let columns = Helper::try_into_vectors(&group_arrays).context(VectorComputationSnafu)?;
+        let (timestamps, keys) = (&columns[0], &columns[1..]);
+
+        let mut builders = self.create_builders();
+        let mut start = 0;
+        while start < num_rows {
+            let end = (start + 1..num_rows)
+                .find(|row| keys.iter().any(|k| k.get_ref(*row) != k.get_ref(start)))
+                .unwrap_or(num_rows);
+            let key = keys.iter().map(|k| k.get(start)).collect::<Vec<_>>();
+            if self.series.as_ref().map_or(true, |s| s.key != key) {
+                if let Some(series) = self.series.replace(Series::new(key)) {
+                    self.evaluate_series(&series, &mut builders)?;
+                }
+            }
+            if let Some(series) = &mut self.series {
+                series.push(timestamps, &args, start..end);
+            }
+            start = end;
+        }
+        self.build_batch(builders)
+    }
+
+    /// Returns the output rows of the last series.
+    fn finish(&mut self) -> Result<Option<DfRecordBatch>> {
+        let mut builders = self.create_builders();
+        if let Some(series) = self.series.take() {
+            self.evaluate_series(&series, &mut builders)?;
+        }
+        self.build_batch(builders)
+    }
+
+    fn evaluate_series(
+        &self,
+        series: &Series,
+        builders: &mut [Box<dyn MutableVector>],
+    ) -> Result<()> {
+        if series.timestamps.is_empty() {
+            return Ok(());
+        }
+
+        let args = self
+            .aggr_expr
+            .iter()
+            .enumerate()
+            .map(|(i, aggr)| {
+                (0..aggr.expressions().len())
+                    .map(|j| {
+                        let arrays = series
+                            .args
+                            .iter()
+                            .map(|chunk| chunk[i][j].as_ref())
+                            .collect::<Vec<_>>();
+                        concatenate(&arrays).map(ArrayRef::from)
+                    })
+                    .collect::<ArrowResult<Vec<_>>>()
+            })
+            .collect::<ArrowResult<Vec<_>>>()
+            .map_err(DataFusionError::from)
+            .context(error::DatafusionSnafu {
+                msg: "Fail to concatenate range query arguments",
+            })?;
+
+        for (ts, windows) in align_windows(&series.timestamps, self.align, &self.ranges) {
+            let mut values = vec![Value::Timestamp(Timestamp::new(ts, self.unit))];
+            values.extend(series.key.iter().cloned());
+            for ((aggr, args), rows) in self.aggr_expr.iter().zip(&args).zip(windows) {
+                let value =
+                    evaluate_window(aggr.as_ref(), args, rows).context(error::DatafusionSnafu {
+                        msg: "Fail to evaluate range query aggregate",
+                    })?;
+                values.push(Value::try_from(value).context(VectorComputationSnafu)?);
+            }
+
+            for (builder, value) in builders.iter_mut().zip(values) {
+                builder
+                    .push_value_ref(value.as_value_ref())
+                    .context(VectorComputationSnafu)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn create_builders(&self) -> Vec<Box<dyn MutableVector>> {
+        self.schema
+            .fields()
+            .iter()
+            .map(|field| {
+                ConcreteDataType::from_arrow_type(field.data_type()).create_mutable_vector(0)
+            })
+            .collect()
+    }
+
+    fn build_batch(
+        &self,
+        mut builders: Vec<Box<dyn MutableVector>>,
+    ) -> Result<Option<DfRecordBatch>> {
+        if builders.first().map_or(true, |b| b.len() == 0) {
+            return Ok(None);
+        }
+
+        let batch = DfRecordBatch::try_new(
+            self.schema.clone(),
+            builders
+                .iter_mut()
+                .map(|b| b.to_vector().to_arrow_array())
+                .collect(),
+        )
+        .map_err(DataFusionError::from)
+        .context(error::DatafusionSnafu {
+            msg: "Fail to build range query output",
+        })?;
+        Ok(Some(batch))
+    }
+}
+
+fn evaluate_window(
+    aggr: &dyn AggregateExpr,
+    args: &[ArrayRef],
+    rows: Range<usize>,
+) -> DfResult<datafusion_common::ScalarValue> {
+    let mut accumulator = aggr.create_accumulator()?;
+    if !rows.is_empty() {
+        let args = args
+            .iter()
+            .map(|array| ArrayRef::from(array.slice(rows.start, rows.len())))
+            .collect::<Vec<_>>();
+        accumulator.update_batch(&args)?;
+    }
+    accumulator.evaluate()
+}
+
+impl RecordBatchStream for RangeStream {
+    fn schema(&self) -> ArrowSchemaRef {
+        self.schema.clone()
+    }
+}
+
+impl Stream for RangeStream {
+    type Item = ArrowResult<DfRecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if self.finished {
+                return Poll::Ready(None);
+            }
+            let output = match ready!(self.input.poll_next_unpin(cx)) {
+                Some(Ok(batch)) => self.process_batch(&batch),
+                Some(Err(e)) => return Poll::Ready(Some(Err(e))),
+                None => {
+                    self.finished = true;
+                    self.finish()
+                }
+            };
+            match output {
+                Ok(Some(batch)) => return Poll::Ready(Some(Ok(batch))),
+                Ok(None) => continue,
+                Err(e) => {
+                    return Poll::Ready(Some(Err(ArrowError::External("".to_owned(), Box::new(e)))))
+                }
+            }
+        }
+    }
+}
+
+/// Returns the timestamps of the output rows of a series whose sorted timestamps are
+/// `timestamps`, with the rows in the look-back window `(ts - range, ts]` of each output
+/// timestamp for every range in `ranges`.
+///
+/// Output timestamps are multiples of `align`, whose longest window has at least one row.
+fn align_windows(timestamps: &[i64], align: i64, ranges: &[i64]) -> Vec<(i64, Vec<Range<usize>>)> {
+    let (first, last) = match (timestamps.first(), timestamps.last()) {
+        (Some(first), Some(last)) => (*first, *last),
+        _ => return Vec::new(),
+    };
+    let max_range = ranges.iter().copied().max().unwrap_or(align);
+    // The first aligned timestamp whose windows contain `ts`.
+    let first_window = |ts: i64| {
+        let floor = ts.div_euclid(align) * align;
+        if floor < ts {
+            floor.saturating_add(align)
+        } else {
+            floor
+        }
+    };
+
+    let mut windows = Vec::new();
+    let mut ts = first_window(first);
+    while ts.saturating_sub(max_range) < last {
+        let end = timestamps.partition_point(|v| *v <= ts);
+        let rows = |range: i64| timestamps.partition_point(|v| *v <= ts.saturating_sub(range))..end;
+        if rows(max_range).is_empty() {
+            // Skips the gap until the next row.
+            ts = first_window(timestamps[end]);
+            continue;
+        }
+
+        windows.push((ts, ranges.iter().map(|range| rows(*range)).collect()));
+        ts = match ts.checked_add(align) {
+            Some(ts) => ts,
+            None => break,
+        };
+    }
+    windows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_align_windows() {
+        let timestamps = [0, 10, 20, 30, 100];
+        assert_eq!(
+            vec![
+                (0, vec![0..1, 0..1]),
+                (10, vec![0..2, 1..2]),
+                (20, vec![1..3, 2..3]),
+                (30, vec![2..4, 3..4]),
+                (40, vec![3..4, 4..4]),
+                // Skips the gap between 30 and 100.
+                (100, vec![4..5, 4..5]),
+                (110, vec![4..5, 5..5]),
+            ],
+            align_windows(&timestamps, 10, &[20, 10])
+        );
+
+        // Windows shorter than the align interval.
+        assert_eq!(
+            vec![(0, vec![0..1]), (20, vec![2..3]), (100, vec![4..5])],
+            align_windows(&timestamps, 20, &[5])
+        );
+        assert_eq!(
+            vec![(-10, vec![0..1]), (0, vec![0..1]), (10, vec![0..1])],
+            align_windows(&[-15], 10, &[30])
+        );
+        assert!(align_windows(&[], 10, &[10]).is_empty());
+    }
+}
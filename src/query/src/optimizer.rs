@@ -16,6 +16,7 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use common_time::timestamp::{TimeUnit, Timestamp};
+use common_time::TimeZone;
 use datafusion::execution::context::ExecutionProps;
 use datafusion::logical_plan::plan::Filter;
 use datafusion::logical_plan::{
@@ -26,14 +27,28 @@ use datafusion::optimizer::utils;
 use datafusion_common::{DFSchemaRef, DataFusionError, Result, ScalarValue};
 use datatypes::arrow::compute;
 use datatypes::arrow::compute::cast::CastOptions;
-use datatypes::arrow::datatypes::DataType;
+use datatypes::arrow::datatypes::{DataType, TimeUnit as ArrowTimeUnit};
 
 /// TypeConversionRule converts some literal values in logical plan to other types according
 /// to data type of corresponding columns.
 /// Specifically:
-/// - string literal of timestamp is converted to `Expr::Literal(ScalarValue::TimestampMillis)`
+/// - string and timestamp literals compared with a timestamp column are converted to timestamp
+///   literals in the time unit of the column. A literal finer than the unit is rounded so the
+///   comparison keeps its result, and `=` (`!=`) with such a literal never (always) holds
 /// - string literal of boolean is converted to `Expr::Literal(ScalarValue::Boolean)`
-pub struct TypeConversionRule;
+///
+/// Timestamp strings without an explicit offset are parsed in `time_zone`, or in the
+/// local time zone of the server if it's `None`.
+#[derive(Default)]
+pub struct TypeConversionRule {
+    time_zone: Option<TimeZone>,
+}
+
+impl TypeConversionRule {
+    pub fn with_time_zone(time_zone: Option<TimeZone>) -> Self {
+        Self { time_zone }
+    }
+}
 
 impl OptimizerRule for TypeConversionRule {
     fn optimize(
@@ -43,6 +58,7 @@ impl OptimizerRule for TypeConversionRule {
     ) -> Result<LogicalPlan> {
         let mut converter = TypeConverter {
             schemas: plan.all_schemas(),
+            time_zone: self.time_zone.as_ref(),
         };
 
         match plan {
@@ -114,6 +130,7 @@ impl OptimizerRule for TypeConversionRule {
 
 struct TypeConverter<'a> {
     schemas: Vec<&'a DFSchemaRef>,
+    time_zone: Option<&'a TimeZone>,
 }
 
 impl<'a> TypeConverter<'a> {
@@ -128,13 +145,28 @@ impl<'a> TypeConverter<'a> {
         None
     }
 
-    fn cast_scalar_value(value: &ScalarValue, target_type: &DataType) -> Result<ScalarValue> {
+    /// Casts the literal to `target_type`, returns `None` if it's a timestamp that can't be
+    /// represented in the target unit and `rounding` is [Rounding::Exact].
+    fn cast_scalar_value(
+        &self,
+        value: &ScalarValue,
+        target_type: &DataType,
+        rounding: Rounding,
+    ) -> Result<Option<ScalarValue>> {
+        if let DataType::Timestamp(unit, _) = target_type {
+            if let ScalarValue::Utf8(Some(v)) = value {
+                return string_to_timestamp(v, unit, self.time_zone, rounding);
+            }
+            if let Some(timestamp) = timestamp_of(value) {
+                return Ok(timestamp_to_scalar(timestamp, unit, rounding));
+            }
+        }
+
         match (target_type, value) {
-            (DataType::Timestamp(_, _), ScalarValue::Utf8(Some(v))) => string_to_timestamp_ms(v),
             (DataType::Boolean, ScalarValue::Utf8(Some(v))) => match v.to_lowercase().as_str() {
-                "true" => Ok(ScalarValue::Boolean(Some(true))),
-                "false" => Ok(ScalarValue::Boolean(Some(false))),
-                _ => Ok(ScalarValue::Boolean(None)),
+                "true" => Ok(Some(ScalarValue::Boolean(Some(true)))),
+                "false" => Ok(Some(ScalarValue::Boolean(Some(false)))),
+                _ => Ok(Some(ScalarValue::Boolean(None))),
             },
             (target_type, value) => {
                 let value_arr = value.to_array();
@@ -146,11 +178,21 @@ impl<'a> TypeConverter<'a> {
                     &Arc::from(arr), // index: Converts a value in `array` at `index` into a ScalarValue
                     0,
                 )
+                .map(Some)
             }
         }
     }
 
-    fn convert_type<'b>(&self, mut left: &'b Expr, mut right: &'b Expr) -> Result<(Expr, Expr)> {
+    /// Converts the literal in `left op right` to the type of the column compared with it.
+    ///
+    /// Returns `None` if `op` is `=` or `!=` and the timestamp literal can't be represented in
+    /// the unit of the column.
+    fn convert_type<'b>(
+        &self,
+        mut left: &'b Expr,
+        op: Operator,
+        mut right: &'b Expr,
+    ) -> Result<Option<(Expr, Expr)>> {
         let left_type = self.column_type(left);
         let right_type = self.column_type(right);
 
@@ -162,12 +204,20 @@ impl<'a> TypeConverter<'a> {
                 std::mem::swap(&mut left, &mut right);
                 v
             }
-            _ => return Ok((left.clone(), right.clone())),
+            _ => return Ok(Some((left.clone(), right.clone()))),
+        };
+        let rounding = if reverse {
+            Rounding::of(swap_operator(op))
+        } else {
+            Rounding::of(op)
         };
 
         match (left, right) {
             (Expr::Column(col), Expr::Literal(value)) => {
-                let casted_right = Self::cast_scalar_value(value, left_type)?;
+                let casted_right = match self.cast_scalar_value(value, left_type, rounding)? {
+                    Some(v) => v,
+                    None => return Ok(None),
+                };
                 if casted_right.is_null() {
                     return Err(DataFusionError::Plan(format!(
                         "column:{:?} value:{:?} is invalid",
@@ -175,12 +225,12 @@ impl<'a> TypeConverter<'a> {
                     )));
                 }
                 if reverse {
-                    Ok((Expr::Literal(casted_right), left.clone()))
+                    Ok(Some((Expr::Literal(casted_right), left.clone())))
                 } else {
-                    Ok((left.clone(), Expr::Literal(casted_right)))
+                    Ok(Some((left.clone(), Expr::Literal(casted_right))))
                 }
             }
-            _ => Ok((left.clone(), right.clone())),
+            _ => Ok(Some((left.clone(), right.clone()))),
         }
     }
 }
@@ -194,14 +244,24 @@ impl<'a> ExprRewriter for TypeConverter<'a> {
                 | Operator::Lt
                 | Operator::LtEq
                 | Operator::Gt
-                | Operator::GtEq => {
-                    let (left, right) = self.convert_type(&left, &right)?;
-                    Expr::BinaryExpr {
+                | Operator::GtEq => match self.convert_type(&left, op, &right)? {
+                    Some((left, right)) => Expr::BinaryExpr {
                         left: Box::new(left),
                         op,
                         right: Box::new(right),
+                    },
+                    // Only `=` and `!=` reach here, the column never equals the literal so `!=`
+                    // holds for all non-null values.
+                    None if op == Operator::Eq => Expr::Literal(ScalarValue::Boolean(Some(false))),
+                    None => {
+                        let column = if matches!(*left, Expr::Column(_)) {
+                            left
+                        } else {
+                            right
+                        };
+                        Expr::IsNotNull(column)
                     }
-                }
+                },
                 _ => Expr::BinaryExpr { left, op, right },
             },
             Expr::Between {
@@ -210,8 +270,15 @@ impl<'a> ExprRewriter for TypeConverter<'a> {
                 low,
                 high,
             } => {
-                let (expr, low) = self.convert_type(&expr, &low)?;
-                let (expr, high) = self.convert_type(&expr, &high)?;
+                // `expr BETWEEN low AND high` is `expr >= low AND expr <= high`, and its negation
+                // is `expr < low OR expr > high`, which rounds the bounds the same way. Ordering
+                // comparisons always have a converted literal.
+                let (expr, low) = self
+                    .convert_type(&expr, Operator::GtEq, &low)?
+                    .unwrap_or_else(|| (expr.as_ref().clone(), low.as_ref().clone()));
+                let (expr, high) = self
+                    .convert_type(&expr, Operator::LtEq, &high)?
+                    .unwrap_or_else(|| (expr.clone(), high.as_ref().clone()));
                 Expr::Between {
                     expr: Box::new(expr),
                     negated,
@@ -225,58 +292,123 @@ impl<'a> ExprRewriter for TypeConverter<'a> {
                 negated,
             } => {
                 let mut list_expr = Vec::with_capacity(list.len());
-                for e in list {
-                    let (_, expr_conversion) = self.convert_type(&expr, &e)?;
-                    list_expr.push(expr_conversion);
+                for e in &list {
+                    // Values never equal to `expr` are dropped from the list.
+                    if let Some((_, expr_conversion)) = self.convert_type(&expr, Operator::Eq, e)? {
+                        list_expr.push(expr_conversion);
+                    }
                 }
-                Expr::InList {
-                    expr,
-                    list: list_expr,
-                    negated,
+                match (list_expr.is_empty() && !list.is_empty(), negated) {
+                    (true, false) => Expr::Literal(ScalarValue::Boolean(Some(false))),
+                    (true, true) => Expr::IsNotNull(expr),
+                    (false, _) => Expr::InList {
+                        expr,
+                        list: list_expr,
+                        negated,
+                    },
                 }
             }
-            Expr::Literal(value) => match value {
-                ScalarValue::TimestampSecond(Some(i), _) => {
-                    timestamp_to_timestamp_ms_expr(i, TimeUnit::Second)
-                }
-                ScalarValue::TimestampMillisecond(Some(i), _) => {
-                    timestamp_to_timestamp_ms_expr(i, TimeUnit::Millisecond)
-                }
-
-                ScalarValue::TimestampMicrosecond(Some(i), _) => {
-                    timestamp_to_timestamp_ms_expr(i, TimeUnit::Microsecond)
-                }
-                ScalarValue::TimestampNanosecond(Some(i), _) => {
-                    timestamp_to_timestamp_ms_expr(i, TimeUnit::Nanosecond)
-                }
-                _ => Expr::Literal(value),
-            },
             expr => expr,
         };
         Ok(new_expr)
     }
 }
 
-fn timestamp_to_timestamp_ms_expr(val: i64, unit: TimeUnit) -> Expr {
-    let timestamp = match unit {
-        TimeUnit::Second => val * 1_000,
-        TimeUnit::Millisecond => val,
-        TimeUnit::Microsecond => val / 1_000,
-        TimeUnit::Nanosecond => val / 1_000 / 1_000,
+/// How to round a timestamp literal that's finer than the time unit of the column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Rounding {
+    /// Rounds towards negative infinity.
+    Floor,
+    /// Rounds towards positive infinity.
+    Ceil,
+    /// Only keeps literals that are exactly representable.
+    Exact,
+}
+
+impl Rounding {
+    /// Returns the rounding of the literal in `column op literal` that keeps the result of the
+    /// comparison, e.g. `ts >= 1.5s` is `ts >= 2s` and `ts > 1.5s` is `ts > 1s` for a column
+    /// in seconds.
+    fn of(op: Operator) -> Self {
+        match op {
+            Operator::GtEq | Operator::Lt => Rounding::Ceil,
+            Operator::Gt | Operator::LtEq => Rounding::Floor,
+            _ => Rounding::Exact,
+        }
+    }
+}
+
+/// Returns the operator of the comparison with operands swapped, e.g. `a < b` is `b > a`.
+fn swap_operator(op: Operator) -> Operator {
+    match op {
+        Operator::Lt => Operator::Gt,
+        Operator::LtEq => Operator::GtEq,
+        Operator::Gt => Operator::Lt,
+        Operator::GtEq => Operator::LtEq,
+        op => op,
+    }
+}
+
+/// Returns the timestamp of a timestamp literal.
+fn timestamp_of(value: &ScalarValue) -> Option<Timestamp> {
+    match value {
+        ScalarValue::TimestampSecond(Some(v), _) => Some(Timestamp::new(*v, TimeUnit::Second)),
+        ScalarValue::TimestampMillisecond(Some(v), _) => {
+            Some(Timestamp::new(*v, TimeUnit::Millisecond))
+        }
+        ScalarValue::TimestampMicrosecond(Some(v), _) => {
+            Some(Timestamp::new(*v, TimeUnit::Microsecond))
+        }
+        ScalarValue::TimestampNanosecond(Some(v), _) => {
+            Some(Timestamp::new(*v, TimeUnit::Nanosecond))
+        }
+        _ => None,
+    }
+}
+
+/// Converts the timestamp to a literal in the time unit of the column it's compared with,
+/// returns `None` if it's not representable in the unit and `rounding` is [Rounding::Exact].
+fn timestamp_to_scalar(
+    timestamp: Timestamp,
+    unit: &ArrowTimeUnit,
+    rounding: Rounding,
+) -> Option<ScalarValue> {
+    let time_unit = match unit {
+        ArrowTimeUnit::Second => TimeUnit::Second,
+        ArrowTimeUnit::Millisecond => TimeUnit::Millisecond,
+        ArrowTimeUnit::Microsecond => TimeUnit::Microsecond,
+        ArrowTimeUnit::Nanosecond => TimeUnit::Nanosecond,
+    };
+    // Rounded towards negative infinity.
+    let floor = timestamp.convert_to(time_unit);
+    let value = if Timestamp::new(floor, time_unit) == timestamp {
+        floor
+    } else {
+        match rounding {
+            Rounding::Floor => floor,
+            Rounding::Ceil => floor + 1,
+            Rounding::Exact => return None,
+        }
     };
 
-    Expr::Literal(ScalarValue::TimestampMillisecond(Some(timestamp), None))
+    let value = match unit {
+        ArrowTimeUnit::Second => ScalarValue::TimestampSecond(Some(value), None),
+        ArrowTimeUnit::Millisecond => ScalarValue::TimestampMillisecond(Some(value), None),
+        ArrowTimeUnit::Microsecond => ScalarValue::TimestampMicrosecond(Some(value), None),
+        ArrowTimeUnit::Nanosecond => ScalarValue::TimestampNanosecond(Some(value), None),
+    };
+    Some(value)
 }
 
-fn string_to_timestamp_ms(string: &str) -> Result<ScalarValue> {
-    Ok(ScalarValue::TimestampMillisecond(
-        Some(
-            Timestamp::from_str(string)
-                .map(|t| t.value() / 1_000_000)
-                .map_err(|e| DataFusionError::External(Box::new(e)))?,
-        ),
-        None,
-    ))
+fn string_to_timestamp(
+    string: &str,
+    unit: &ArrowTimeUnit,
+    time_zone: Option<&TimeZone>,
+    rounding: Rounding,
+) -> Result<Option<ScalarValue>> {
+    let timestamp = Timestamp::from_str_with_time_zone(string, time_zone)
+        .map_err(|e| DataFusionError::External(Box::new(e)))?;
+    Ok(timestamp_to_scalar(timestamp, unit, rounding))
 }
 
 #[cfg(test)]
@@ -288,58 +420,101 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_string_to_timestamp_ms() {
-        assert!(matches!(
-            string_to_timestamp_ms("2022-02-02 19:00:00+08:00").unwrap(),
-            ScalarValue::TimestampMillisecond(Some(1643799600000), None)
-        ));
-        assert!(matches!(
-            string_to_timestamp_ms("2009-02-13 23:31:30Z").unwrap(),
-            ScalarValue::TimestampMillisecond(Some(1234567890000), None)
-        ));
+    fn test_string_to_timestamp() {
+        let ms = ArrowTimeUnit::Millisecond;
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(1643799600000), None)),
+            string_to_timestamp("2022-02-02 19:00:00+08:00", &ms, None, Rounding::Exact).unwrap()
+        );
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(1234567890000), None)),
+            string_to_timestamp("2009-02-13 23:31:30Z", &ms, None, Rounding::Exact).unwrap()
+        );
+
+        let time_zone = TimeZone::from_tz_string("+08:00").unwrap();
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(1643799600000), None)),
+            string_to_timestamp(
+                "2022-02-02 19:00:00",
+                &ms,
+                time_zone.as_ref(),
+                Rounding::Exact
+            )
+            .unwrap()
+        );
+
+        let ns = ArrowTimeUnit::Nanosecond;
+        assert_eq!(
+            Some(ScalarValue::TimestampNanosecond(
+                Some(1234567890000000001),
+                None
+            )),
+            string_to_timestamp("2009-02-13 23:31:30.000000001Z", &ns, None, Rounding::Exact)
+                .unwrap()
+        );
+
+        let s = ArrowTimeUnit::Second;
+        let half = "2009-02-13 23:31:30.5Z";
+        assert_eq!(
+            Some(ScalarValue::TimestampSecond(Some(1234567890), None)),
+            string_to_timestamp(half, &s, None, Rounding::Floor).unwrap()
+        );
+        assert_eq!(
+            Some(ScalarValue::TimestampSecond(Some(1234567891), None)),
+            string_to_timestamp(half, &s, None, Rounding::Ceil).unwrap()
+        );
+        assert_eq!(
+            None,
+            string_to_timestamp(half, &s, None, Rounding::Exact).unwrap()
+        );
     }
 
     #[test]
-    fn test_timestamp_to_timestamp_ms_expr() {
-        assert!(matches!(
-            timestamp_to_timestamp_ms_expr(123, TimeUnit::Second),
-            Expr::Literal(ScalarValue::TimestampMillisecond(Some(123000), None))
-        ));
-
-        assert!(matches!(
-            timestamp_to_timestamp_ms_expr(123, TimeUnit::Millisecond),
-            Expr::Literal(ScalarValue::TimestampMillisecond(Some(123), None))
-        ));
-
-        assert!(matches!(
-            timestamp_to_timestamp_ms_expr(123, TimeUnit::Microsecond),
-            Expr::Literal(ScalarValue::TimestampMillisecond(Some(0), None))
-        ));
-
-        assert!(matches!(
-            timestamp_to_timestamp_ms_expr(1230, TimeUnit::Microsecond),
-            Expr::Literal(ScalarValue::TimestampMillisecond(Some(1), None))
-        ));
-
-        assert!(matches!(
-            timestamp_to_timestamp_ms_expr(123000, TimeUnit::Microsecond),
-            Expr::Literal(ScalarValue::TimestampMillisecond(Some(123), None))
-        ));
-
-        assert!(matches!(
-            timestamp_to_timestamp_ms_expr(1230, TimeUnit::Nanosecond),
-            Expr::Literal(ScalarValue::TimestampMillisecond(Some(0), None))
-        ));
-        assert!(matches!(
-            timestamp_to_timestamp_ms_expr(123_000_000, TimeUnit::Nanosecond),
-            Expr::Literal(ScalarValue::TimestampMillisecond(Some(123), None))
-        ));
+    fn test_timestamp_to_scalar() {
+        let ms = ArrowTimeUnit::Millisecond;
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(123000), None)),
+            timestamp_to_scalar(Timestamp::new(123, TimeUnit::Second), &ms, Rounding::Exact)
+        );
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(123), None)),
+            timestamp_to_scalar(
+                Timestamp::new(123, TimeUnit::Millisecond),
+                &ms,
+                Rounding::Exact
+            )
+        );
+        let us = Timestamp::new(1230, TimeUnit::Microsecond);
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(1), None)),
+            timestamp_to_scalar(us, &ms, Rounding::Floor)
+        );
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(2), None)),
+            timestamp_to_scalar(us, &ms, Rounding::Ceil)
+        );
+        assert_eq!(None, timestamp_to_scalar(us, &ms, Rounding::Exact));
+        let ns = Timestamp::new(-1, TimeUnit::Nanosecond);
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(-1), None)),
+            timestamp_to_scalar(ns, &ms, Rounding::Floor)
+        );
+        assert_eq!(
+            Some(ScalarValue::TimestampMillisecond(Some(0), None)),
+            timestamp_to_scalar(ns, &ms, Rounding::Ceil)
+        );
+        assert_eq!(
+            Some(ScalarValue::TimestampNanosecond(Some(123_000_000), None)),
+            timestamp_to_scalar(
+                Timestamp::new(123, TimeUnit::Millisecond),
+                &ArrowTimeUnit::Nanosecond,
+                Rounding::Exact
+            )
+        );
     }
 
     #[test]
     fn test_convert_timestamp_str() {
-        use datatypes::arrow::datatypes::TimeUnit as ArrowTimeUnit;
-
         let schema_ref = Arc::new(
             DFSchema::new_with_metadata(
                 vec![DFField::new(
@@ -354,6 +529,7 @@ mod tests {
         );
         let mut converter = TypeConverter {
             schemas: vec![&schema_ref],
+            time_zone: None,
         };
 
         assert_eq!(
@@ -370,6 +546,146 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_convert_timestamp_literal() {
+        let schema_ref = Arc::new(
+            DFSchema::new_with_metadata(
+                vec![DFField::new(
+                    None,
+                    "ts",
+                    DataType::Timestamp(ArrowTimeUnit::Nanosecond, None),
+                    true,
+                )],
+                HashMap::new(),
+            )
+            .unwrap(),
+        );
+        let mut converter = TypeConverter {
+            schemas: vec![&schema_ref],
+            time_zone: None,
+        };
+
+        // Literals are converted to the unit of the column without losing precision.
+        assert_eq!(
+            Expr::Literal(ScalarValue::TimestampNanosecond(Some(1_000_000_001), None))
+                .lt(Expr::Column(Column::from_name("ts"))),
+            converter
+                .mutate(
+                    Expr::Literal(ScalarValue::TimestampNanosecond(Some(1_000_000_001), None))
+                        .lt(Expr::Column(Column::from_name("ts")))
+                )
+                .unwrap()
+        );
+        assert_eq!(
+            Expr::Column(Column::from_name("ts")).gt_eq(Expr::Literal(
+                ScalarValue::TimestampNanosecond(Some(2_000_000_000), None)
+            )),
+            converter
+                .mutate(
+                    Expr::Column(Column::from_name("ts"))
+                        .gt_eq(Expr::Literal(ScalarValue::TimestampSecond(Some(2), None)))
+                )
+                .unwrap()
+        );
+        assert_eq!(
+            Expr::Column(Column::from_name("ts")).lt(Expr::Literal(
+                ScalarValue::TimestampNanosecond(Some(1599514949000000000), None)
+            )),
+            converter
+                .mutate(
+                    Expr::Column(Column::from_name("ts")).lt(Expr::Literal(ScalarValue::Utf8(
+                        Some("2020-09-08T05:42:29+08:00".to_string()),
+                    )))
+                )
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_convert_rounded_timestamp_literal() {
+        let schema_ref = Arc::new(
+            DFSchema::new_with_metadata(
+                vec![DFField::new(
+                    None,
+                    "ts",
+                    DataType::Timestamp(ArrowTimeUnit::Second, None),
+                    true,
+                )],
+                HashMap::new(),
+            )
+            .unwrap(),
+        );
+        let mut converter = TypeConverter {
+            schemas: vec![&schema_ref],
+            time_zone: None,
+        };
+        let ts = || Expr::Column(Column::from_name("ts"));
+        let ms = |v| Expr::Literal(ScalarValue::TimestampMillisecond(Some(v), None));
+        let s = |v| Expr::Literal(ScalarValue::TimestampSecond(Some(v), None));
+        let between = |low, high| Expr::Between {
+            expr: Box::new(ts()),
+            negated: false,
+            low: Box::new(low),
+            high: Box::new(high),
+        };
+
+        assert_eq!(
+            ts().gt_eq(s(2)),
+            converter.mutate(ts().gt_eq(ms(1500))).unwrap()
+        );
+        assert_eq!(ts().lt(s(2)), converter.mutate(ts().lt(ms(1500))).unwrap());
+        assert_eq!(ts().gt(s(1)), converter.mutate(ts().gt(ms(1500))).unwrap());
+        assert_eq!(
+            ts().lt_eq(s(1)),
+            converter.mutate(ts().lt_eq(ms(1500))).unwrap()
+        );
+        // The literal on the left side rounds as the swapped comparison.
+        assert_eq!(s(1).lt(ts()), converter.mutate(ms(1500).lt(ts())).unwrap());
+        assert_eq!(s(2).gt(ts()), converter.mutate(ms(1500).gt(ts())).unwrap());
+        // Negative literals round towards the same direction.
+        assert_eq!(
+            ts().gt_eq(s(-1)),
+            converter.mutate(ts().gt_eq(ms(-1500))).unwrap()
+        );
+        assert_eq!(
+            ts().gt(s(-2)),
+            converter.mutate(ts().gt(ms(-1500))).unwrap()
+        );
+
+        assert_eq!(
+            Expr::Literal(ScalarValue::Boolean(Some(false))),
+            converter.mutate(ts().eq(ms(1500))).unwrap()
+        );
+        assert_eq!(
+            ts().is_not_null(),
+            converter.mutate(ts().not_eq(ms(1500))).unwrap()
+        );
+        assert_eq!(ts().eq(s(2)), converter.mutate(ts().eq(ms(2000))).unwrap());
+
+        assert_eq!(
+            between(s(2), s(2)),
+            converter.mutate(between(ms(1500), ms(2500))).unwrap()
+        );
+        assert_eq!(
+            ts().in_list(vec![s(2)], false),
+            converter
+                .mutate(ts().in_list(vec![ms(1500), ms(2000)], false))
+                .unwrap()
+        );
+        assert_eq!(
+            Expr::Literal(ScalarValue::Boolean(Some(false))),
+            converter
+                .mutate(ts().in_list(vec![ms(1500)], false))
+                .unwrap()
+        );
+        assert_eq!(
+            ts().is_not_null(),
+            converter
+                .mutate(ts().in_list(vec![ms(1500)], true))
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_convert_bool() {
         let col_name = "is_valid";
@@ -382,6 +698,7 @@ mod tests {
         );
         let mut converter = TypeConverter {
             schemas: vec![&schema_ref],
+            time_zone: None,
         };
 
         assert_eq!(
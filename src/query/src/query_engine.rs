@@ -17,6 +17,7 @@ mod state;
 
 use std::sync::Arc;
 
+use catalog::privilege::PrivilegeManagerRef;
 use catalog::CatalogListRef;
 use common_function::scalars::aggregate::AggregateFunctionMetaRef;
 use common_function::scalars::{FunctionRef, FUNCTION_REGISTRY};
@@ -49,6 +50,9 @@ pub trait QueryEngine: Send + Sync {
 
     fn register_udf(&self, udf: ScalarUdf);
 
+    /// Deregisters the udf of the name, does nothing if it's not registered.
+    fn deregister_udf(&self, name: &str);
+
     fn register_aggregate_function(&self, func: AggregateFunctionMetaRef);
 
     fn register_function(&self, func: FunctionRef);
@@ -60,7 +64,16 @@ pub struct QueryEngineFactory {
 
 impl QueryEngineFactory {
     pub fn new(catalog_list: CatalogListRef) -> Self {
-        let query_engine = Arc::new(DatafusionQueryEngine::new(catalog_list));
+        Self::with_privilege_manager(catalog_list, None)
+    }
+
+    /// Creates the factory of a query engine which checks the privileges of sessions on the
+    /// tables they query.
+    pub fn with_privilege_manager(
+        catalog_list: CatalogListRef,
+        privilege_manager: Option<PrivilegeManagerRef>,
+    ) -> Self {
+        let query_engine = Arc::new(DatafusionQueryEngine::new(catalog_list, privilege_manager));
 
         for func in FUNCTION_REGISTRY.functions() {
             query_engine.register_function(func);
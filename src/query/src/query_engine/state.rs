@@ -16,6 +16,7 @@ use std::collections::HashMap;
 use std::fmt;
 use std::sync::{Arc, RwLock};
 
+use catalog::privilege::PrivilegeManagerRef;
 use catalog::CatalogListRef;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_function::scalars::aggregate::AggregateFunctionMetaRef;
@@ -30,7 +31,7 @@ use datafusion::optimizer::single_distinct_to_groupby::SingleDistinctToGroupBy;
 use datafusion::optimizer::to_approx_perc::ToApproxPerc;
 use datafusion::prelude::{ExecutionConfig, ExecutionContext};
 
-use crate::datafusion::DfCatalogListAdapter;
+use crate::datafusion::{DfCatalogListAdapter, DfQueryPlanner};
 use crate::optimizer::TypeConversionRule;
 
 /// Query engine global state
@@ -42,6 +43,8 @@ pub struct QueryEngineState {
     df_context: ExecutionContext,
     catalog_list: CatalogListRef,
     aggregate_functions: Arc<RwLock<HashMap<String, AggregateFunctionMetaRef>>>,
+    /// Checks the privileges on the tables queried by sessions, not checked if it's None.
+    privilege_manager: Option<PrivilegeManagerRef>,
 }
 
 impl fmt::Debug for QueryEngineState {
@@ -52,12 +55,15 @@ impl fmt::Debug for QueryEngineState {
 }
 
 impl QueryEngineState {
-    pub(crate) fn new(catalog_list: CatalogListRef) -> Self {
+    pub(crate) fn new(
+        catalog_list: CatalogListRef,
+        privilege_manager: Option<PrivilegeManagerRef>,
+    ) -> Self {
         let config = ExecutionConfig::new()
             .with_default_catalog_and_schema(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME)
             .with_optimizer_rules(vec![
                 // TODO(hl): SimplifyExpressions is not exported.
-                Arc::new(TypeConversionRule {}),
+                Arc::new(TypeConversionRule::default()),
                 // These are the default optimizer in datafusion
                 Arc::new(CommonSubexprEliminate::new()),
                 Arc::new(EliminateLimit::new()),
@@ -66,7 +72,8 @@ impl QueryEngineState {
                 Arc::new(LimitPushDown::new()),
                 Arc::new(SingleDistinctToGroupBy::new()),
                 Arc::new(ToApproxPerc::new()),
-            ]);
+            ])
+            .with_query_planner(Arc::new(DfQueryPlanner::default()));
 
         let df_context = ExecutionContext::with_config(config);
 
@@ -77,6 +84,7 @@ impl QueryEngineState {
             df_context,
             catalog_list,
             aggregate_functions: Arc::new(RwLock::new(HashMap::new())),
+            privilege_manager,
         }
     }
 
@@ -90,6 +98,11 @@ impl QueryEngineState {
             .insert(udf.name.clone(), Arc::new(udf.into_df_udf()));
     }
 
+    /// Deregister the udf function of the name
+    pub fn deregister_udf(&self, name: &str) {
+        let _ = self.df_context.state.lock().scalar_functions.remove(name);
+    }
+
     pub fn aggregate_function(&self, function_name: &str) -> Option<AggregateFunctionMetaRef> {
         self.aggregate_functions
             .read()
@@ -112,6 +125,11 @@ impl QueryEngineState {
         &self.catalog_list
     }
 
+    #[inline]
+    pub(crate) fn privilege_manager(&self) -> Option<&PrivilegeManagerRef> {
+        self.privilege_manager.as_ref()
+    }
+
     #[inline]
     pub(crate) fn df_context(&self) -> &ExecutionContext {
         &self.df_context
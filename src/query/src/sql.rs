@@ -77,7 +77,11 @@ static DESCRIBE_TABLE_OUTPUT_SCHEMA: Lazy<Arc<Schema>> = Lazy::new(|| {
     ]))
 });
 
-pub fn show_databases(stmt: ShowDatabases, catalog_manager: CatalogManagerRef) -> Result<Output> {
+pub fn show_databases(
+    stmt: ShowDatabases,
+    catalog_manager: CatalogManagerRef,
+    query_ctx: QueryContextRef,
+) -> Result<Output> {
     // TODO(LFC): supports WHERE
     ensure!(
         matches!(stmt.kind, ShowKind::All | ShowKind::Like(_)),
@@ -86,11 +90,14 @@ pub fn show_databases(stmt: ShowDatabases, catalog_manager: CatalogManagerRef) -
         }
     );
 
+    let catalog_name = query_ctx
+        .current_catalog()
+        .unwrap_or_else(|| DEFAULT_CATALOG_NAME.to_string());
     let catalog = catalog_manager
-        .catalog(DEFAULT_CATALOG_NAME)
+        .catalog(&catalog_name)
         .context(error::CatalogSnafu)?
         .context(error::CatalogNotFoundSnafu {
-            catalog: DEFAULT_CATALOG_NAME,
+            catalog: &catalog_name,
         })?;
     let databases = catalog.schema_names().context(error::CatalogSnafu)?;
 
@@ -130,8 +137,11 @@ pub fn show_tables(
             .current_schema()
             .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string())
     };
+    let catalog = query_ctx
+        .current_catalog()
+        .unwrap_or_else(|| DEFAULT_CATALOG_NAME.to_string());
     let schema = catalog_manager
-        .schema(DEFAULT_CATALOG_NAME, &schema)
+        .schema(&catalog, &schema)
         .context(error::CatalogSnafu)?
         .context(error::SchemaNotFoundSnafu { schema })?;
     let tables = schema.table_names().context(error::CatalogSnafu)?;
@@ -0,0 +1,68 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use catalog::local::{MemoryCatalogProvider, MemorySchemaProvider};
+use catalog::{CatalogList, CatalogProvider, SchemaProvider};
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_query::Output;
+use common_recordbatch::{util, RecordBatch};
+use datatypes::prelude::*;
+use datatypes::vectors::Helper;
+use query::error::Result;
+use query::query_engine::QueryEngineFactory;
+use query::QueryEngine;
+use session::context::QueryContext;
+use table::test_util::MemTable;
+
+/// Creates a query engine with a memory table `table_name` holding `recordbatch` in the
+/// default schema.
+pub fn create_query_engine(table_name: &str, recordbatch: RecordBatch) -> Arc<dyn QueryEngine> {
+    let table = Arc::new(MemTable::new(table_name, recordbatch));
+
+    let schema_provider = Arc::new(MemorySchemaProvider::new());
+    schema_provider
+        .register_table(table_name.to_string(), table)
+        .unwrap();
+    let catalog_provider = Arc::new(MemoryCatalogProvider::new());
+    catalog_provider
+        .register_schema(DEFAULT_SCHEMA_NAME.to_string(), schema_provider)
+        .unwrap();
+    let catalog_list = catalog::local::new_memory_catalog_list().unwrap();
+    catalog_list
+        .register_catalog(DEFAULT_CATALOG_NAME.to_string(), catalog_provider)
+        .unwrap();
+
+    QueryEngineFactory::new(catalog_list).query_engine()
+}
+
+/// Executes the query and returns the values of the result rows.
+pub async fn execute(engine: &Arc<dyn QueryEngine>, sql: &str) -> Result<Vec<Vec<Value>>> {
+    let plan = engine.sql_to_plan(sql, Arc::new(QueryContext::new()))?;
+    let recordbatch = match engine.execute(&plan).await? {
+        Output::Stream(recordbatch) => recordbatch,
+        _ => unreachable!(),
+    };
+    let batches = util::collect(recordbatch).await.unwrap();
+
+    let mut rows = Vec::new();
+    for batch in batches {
+        let columns = Helper::try_into_vectors(batch.df_recordbatch.columns()).unwrap();
+        for row in 0..batch.df_recordbatch.num_rows() {
+            rows.push(columns.iter().map(|c| c.get(row)).collect());
+        }
+    }
+    Ok(rows)
+}
@@ -0,0 +1,141 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_recordbatch::RecordBatch;
+use common_time::timestamp::{TimeUnit, Timestamp};
+use datatypes::prelude::*;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::{Float64Vector, StringVector, TimestampVector};
+use query::error::Result;
+use query::QueryEngine;
+use query_util::execute;
+use session::context::QueryContext;
+
+mod query_util;
+
+fn create_query_engine() -> Arc<dyn QueryEngine> {
+    let column_schemas = vec![
+        ColumnSchema::new(
+            "ts",
+            ConcreteDataType::timestamp_datatype(TimeUnit::Millisecond),
+            false,
+        )
+        .with_time_index(true),
+        ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+        ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+    ];
+    let schema = Arc::new(Schema::new(column_schemas));
+    // Rows are out of order, host "b" has no samples between 0s and 3s.
+    let columns: Vec<VectorRef> = vec![
+        Arc::new(TimestampVector::from_values_with_unit(
+            vec![3000, 0, 1000, 0, 4000, 2000],
+            TimeUnit::Millisecond,
+        )),
+        Arc::new(StringVector::from(vec!["b", "a", "a", "b", "a", "a"])),
+        Arc::new(Float64Vector::from_slice(&[30.0, 1.0, 2.0, 10.0, 4.0, 3.0])),
+    ];
+    let recordbatch = RecordBatch::new(schema, columns).unwrap();
+    query_util::create_query_engine("monitor", recordbatch)
+}
+
+fn row(ts: i64, host: &str, avg: Option<f64>, max: Option<f64>) -> Vec<Value> {
+    vec![
+        Value::Timestamp(Timestamp::new(ts, TimeUnit::Millisecond)),
+        Value::from(host),
+        avg.map_or(Value::Null, Value::from),
+        max.map_or(Value::Null, Value::from),
+    ]
+}
+
+#[tokio::test]
+async fn test_range_select() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let engine = create_query_engine();
+
+    // max(cpu) has no RANGE, so its window is the align interval.
+    let rows = execute(
+        &engine,
+        "SELECT ts, host, avg(cpu) RANGE '2s', max(cpu) FROM monitor \
+         ALIGN '1s' BY (host) ORDER BY host, ts",
+    )
+    .await?;
+    assert_eq!(
+        vec![
+            row(0, "a", Some(1.0), Some(1.0)),
+            row(1000, "a", Some(1.5), Some(2.0)),
+            row(2000, "a", Some(2.5), Some(3.0)),
+            row(3000, "a", Some(3.0), None),
+            row(4000, "a", Some(4.0), Some(4.0)),
+            row(5000, "a", Some(4.0), None),
+            row(0, "b", Some(10.0), Some(10.0)),
+            row(1000, "b", Some(10.0), None),
+            // Both windows ending at 2s are empty.
+            row(3000, "b", Some(30.0), Some(30.0)),
+            row(4000, "b", Some(30.0), None),
+        ],
+        rows
+    );
+
+    let rows = execute(
+        &engine,
+        "SELECT ts, host, avg(cpu) RANGE '3s' AS a, max(cpu) RANGE '3s' FROM monitor \
+         WHERE host = 'a' ALIGN '2s' BY (host) ORDER BY ts DESC LIMIT 2",
+    )
+    .await?;
+    assert_eq!(
+        vec![
+            row(6000, "a", Some(4.0), Some(4.0)),
+            row(4000, "a", Some(3.5), Some(4.0)),
+        ],
+        rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_range_select_invalid() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let engine = create_query_engine();
+
+    let plan = |sql: &str| engine.sql_to_plan(sql, Arc::new(QueryContext::new()));
+    // RANGE must follow an aggregate.
+    assert!(plan("SELECT ts, host RANGE '1s' FROM monitor ALIGN '1s' BY (host)").is_err());
+    // The same aggregate with different ranges.
+    assert!(
+        plan("SELECT ts, max(cpu) RANGE '1s', max(cpu) RANGE '2s' FROM monitor ALIGN '1s'")
+            .is_err()
+    );
+    // Series are grouped by ALIGN .. BY instead of GROUP BY.
+    assert!(plan("SELECT ts, max(cpu) FROM monitor GROUP BY host ALIGN '1s'").is_err());
+    assert!(plan("SELECT * FROM monitor ALIGN '1s'").is_err());
+    assert!(plan("SELECT ts, max(cpu) FROM not_exist ALIGN '1s'").is_err());
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_range_select_interval_not_multiple_of_unit() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let engine = create_query_engine();
+
+    // The time index is in milliseconds.
+    for sql in [
+        "SELECT ts, max(cpu) RANGE '1500us' FROM monitor ALIGN '1s'",
+        "SELECT ts, max(cpu) FROM monitor ALIGN '1500us'",
+    ] {
+        assert!(execute(&engine, sql).await.is_err(), "{}", sql);
+    }
+    Ok(())
+}
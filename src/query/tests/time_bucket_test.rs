@@ -0,0 +1,155 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_recordbatch::RecordBatch;
+use common_time::timestamp::{TimeUnit, Timestamp};
+use datatypes::prelude::*;
+use datatypes::schema::{ColumnSchema, Schema};
+use datatypes::vectors::{Float64Vector, StringVector, TimestampVector};
+use query::error::Result;
+use query::QueryEngine;
+use query_util::execute;
+use session::context::QueryContext;
+
+mod query_util;
+
+fn create_query_engine() -> Arc<dyn QueryEngine> {
+    let column_schemas = vec![
+        ColumnSchema::new(
+            "ts",
+            ConcreteDataType::timestamp_datatype(TimeUnit::Millisecond),
+            false,
+        ),
+        ColumnSchema::new("host", ConcreteDataType::string_datatype(), false),
+        ColumnSchema::new("cpu", ConcreteDataType::float64_datatype(), true),
+    ];
+    let schema = Arc::new(Schema::new(column_schemas));
+    // Host "a" misses the samples in the third second.
+    let columns: Vec<VectorRef> = vec![
+        Arc::new(TimestampVector::from_values_with_unit(
+            vec![0, 500, 1000, 3000, 3500, 0, 2000],
+            TimeUnit::Millisecond,
+        )),
+        Arc::new(StringVector::from(vec!["a", "a", "a", "a", "a", "b", "b"])),
+        Arc::new(Float64Vector::from_slice(&[
+            1.0, 3.0, 4.0, 10.0, 6.0, 20.0, 40.0,
+        ])),
+    ];
+    let recordbatch = RecordBatch::new(schema, columns).unwrap();
+    query_util::create_query_engine("monitor", recordbatch)
+}
+
+fn row(ts: i64, host: &str, cpu: Option<f64>) -> Vec<Value> {
+    vec![
+        Value::Timestamp(Timestamp::new(ts, TimeUnit::Millisecond)),
+        Value::from(host),
+        cpu.map_or(Value::Null, Value::from),
+    ]
+}
+
+#[tokio::test]
+async fn test_time_bucket() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let engine = create_query_engine();
+
+    let rows = execute(
+        &engine,
+        "SELECT time_bucket('1s', ts) AS t, host, max(cpu) FROM monitor \
+         WHERE host = 'a' GROUP BY time_bucket('1s', ts), host ORDER BY t",
+    )
+    .await?;
+    assert_eq!(
+        vec![
+            row(0, "a", Some(3.0)),
+            row(1000, "a", Some(4.0)),
+            row(3000, "a", Some(10.0)),
+        ],
+        rows
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_time_bucket_fill() -> Result<()> {
+    common_telemetry::init_default_ut_logging();
+    let engine = create_query_engine();
+
+    let sql = |fill: &str| {
+        format!(
+            "SELECT time_bucket('1s', ts) AS t, host, max(cpu) FROM monitor \
+             GROUP BY time_bucket('1s', ts), host FILL({}) ORDER BY host, t",
+            fill
+        )
+    };
+
+    let rows = execute(&engine, &sql("NULL")).await?;
+    assert_eq!(
+        vec![
+            row(0, "a", Some(3.0)),
+            row(1000, "a", Some(4.0)),
+            row(2000, "a", None),
+            row(3000, "a", Some(10.0)),
+            row(0, "b", Some(20.0)),
+            row(1000, "b", None),
+            row(2000, "b", Some(40.0)),
+            row(3000, "b", None),
+        ],
+        rows
+    );
+
+    let rows = execute(&engine, &sql("PREVIOUS")).await?;
+    assert_eq!(row(2000, "a", Some(4.0)), rows[2]);
+    assert_eq!(row(3000, "b", Some(40.0)), rows[7]);
+
+    let rows = execute(&engine, &sql("LINEAR")).await?;
+    assert_eq!(row(2000, "a", Some(7.0)), rows[2]);
+    assert_eq!(row(1000, "b", Some(30.0)), rows[5]);
+    assert_eq!(row(3000, "b", None), rows[7]);
+
+    let rows = execute(&engine, &sql("0")).await?;
+    assert_eq!(row(2000, "a", Some(0.0)), rows[2]);
+
+    // HAVING filters the filled rows too.
+    let sql = |fill: &str| {
+        format!(
+            "SELECT time_bucket('1s', ts) AS t, host, max(cpu) FROM monitor \
+             GROUP BY time_bucket('1s', ts), host HAVING max(cpu) > 3 FILL({}) \
+             ORDER BY host, t",
+            fill
+        )
+    };
+    let rows = execute(&engine, &sql("0")).await?;
+    assert_eq!(
+        vec![
+            row(1000, "a", Some(4.0)),
+            row(3000, "a", Some(10.0)),
+            row(0, "b", Some(20.0)),
+            row(2000, "b", Some(40.0)),
+        ],
+        rows
+    );
+    let rows = execute(&engine, &sql("LINEAR")).await?;
+    assert_eq!(row(2000, "a", Some(7.0)), rows[1]);
+    assert_eq!(row(1000, "b", Some(30.0)), rows[4]);
+
+    // FILL needs a time bucket to fill.
+    let result = engine.sql_to_plan(
+        "SELECT host, max(cpu) FROM monitor GROUP BY host FILL(NULL)",
+        Arc::new(QueryContext::new()),
+    );
+    assert!(result.is_err());
+    Ok(())
+}
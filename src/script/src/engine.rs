@@ -15,10 +15,12 @@
 //! Script engine
 
 use std::any::Any;
+use std::collections::HashMap;
 
 use async_trait::async_trait;
 use common_error::ext::ErrorExt;
 use common_query::Output;
+use session::context::QueryContextRef;
 
 #[async_trait]
 pub trait Script {
@@ -52,8 +54,13 @@ pub trait ScriptEngine {
 }
 
 /// Evaluate script context
-#[derive(Debug, Default)]
-pub struct EvalContext {}
+#[derive(Default)]
+pub struct EvalContext {
+    /// Runtime parameters of the script, which are interpolated into the coprocessor's sql.
+    pub params: HashMap<String, String>,
+    /// Context of the caller, the coprocessor's sql is executed in it.
+    pub query_ctx: QueryContextRef,
+}
 
 /// Compile script context
 #[derive(Debug, Default)]
@@ -64,6 +64,13 @@ pub enum Error {
     #[snafu(display("Script not found, name: {}", name))]
     ScriptNotFound { backtrace: Backtrace, name: String },
 
+    #[snafu(display("Script version not found, name: {}, version: {}", name, version))]
+    ScriptVersionNotFound {
+        backtrace: Backtrace,
+        name: String,
+        version: i64,
+    },
+
     #[snafu(display("Failed to find script by name: {}", name))]
     FindScript {
         name: String,
@@ -79,6 +86,74 @@ pub enum Error {
 
     #[snafu(display("Failed to cast type, msg: {}", msg))]
     CastType { msg: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to register script schedules table, source: {}", source))]
+    RegisterSchedulesTable {
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
+    #[snafu(display("Failed to find table: {}, source: {}", table, source))]
+    FindTable {
+        table: String,
+        #[snafu(backtrace)]
+        source: catalog::error::Error,
+    },
+
+    #[snafu(display("Table not found: {}", table))]
+    TableNotFound { table: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to insert into table: {}, source: {}", table, source))]
+    InsertTable {
+        table: String,
+        #[snafu(backtrace)]
+        source: table::error::Error,
+    },
+
+    #[snafu(display("Failed to convert arrow array to vector, source: {}", source))]
+    ConvertVector {
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
+    #[snafu(display("Invalid schedule of script {}, reason: {}", name, reason))]
+    InvalidSchedule {
+        name: String,
+        reason: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Script is not scheduled, name: {}", name))]
+    ScriptNotScheduled { name: String, backtrace: Backtrace },
+
+    #[snafu(display("Failed to find schedule of script: {}, source: {}", name, source))]
+    FindSchedule {
+        name: String,
+        #[snafu(backtrace)]
+        source: query::error::Error,
+    },
+
+    #[snafu(display(
+        "Failed to encode params of script schedule: {}, source: {}",
+        name,
+        source
+    ))]
+    EncodeScheduleParams {
+        name: String,
+        source: serde_json::Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Failed to decode params of script schedule: {}, source: {}",
+        name,
+        source
+    ))]
+    DecodeScheduleParams {
+        name: String,
+        source: serde_json::Error,
+        backtrace: Backtrace,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -87,14 +162,25 @@ impl ErrorExt for Error {
     fn status_code(&self) -> StatusCode {
         use Error::*;
         match self {
-            CastType { .. } => StatusCode::Unexpected,
+            CastType { .. } | EncodeScheduleParams { .. } | DecodeScheduleParams { .. } => {
+                StatusCode::Unexpected
+            }
             ScriptsTableNotFound { .. } => StatusCode::TableNotFound,
-            RegisterScriptsTable { source } | FindScriptsTable { source } => source.status_code(),
+            RegisterScriptsTable { source }
+            | FindScriptsTable { source }
+            | RegisterSchedulesTable { source }
+            | FindTable { source, .. } => source.status_code(),
+            TableNotFound { .. } => StatusCode::TableNotFound,
+            InsertTable { source, .. } => source.status_code(),
+            ConvertVector { source } => source.status_code(),
             InsertScript { source, .. } => source.status_code(),
             CompilePython { source, .. } | ExecutePython { source, .. } => source.status_code(),
-            FindScript { source, .. } => source.status_code(),
+            FindScript { source, .. } | FindSchedule { source, .. } => source.status_code(),
             CollectRecords { source } => source.status_code(),
-            ScriptNotFound { .. } => StatusCode::InvalidArguments,
+            ScriptNotFound { .. }
+            | ScriptVersionNotFound { .. }
+            | InvalidSchedule { .. }
+            | ScriptNotScheduled { .. } => StatusCode::InvalidArguments,
         }
     }
 
@@ -19,4 +19,8 @@ pub mod error;
 pub mod manager;
 #[cfg(feature = "python")]
 pub mod python;
+#[cfg(feature = "python")]
+pub mod scheduler;
 mod table;
+
+pub use table::ScriptRecord;
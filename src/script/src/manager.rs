@@ -14,91 +14,348 @@
 
 //! Scripts manager
 use std::collections::HashMap;
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, Mutex, RwLock};
 
 use catalog::CatalogManagerRef;
 use common_query::Output;
 use common_telemetry::logging;
 use query::QueryEngineRef;
-use snafu::{OptionExt, ResultExt};
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{ensure, OptionExt, ResultExt};
+use tokio::task::JoinHandle;
 
 use crate::engine::{CompileContext, EvalContext, Script, ScriptEngine};
-use crate::error::{CompilePythonSnafu, ExecutePythonSnafu, Result, ScriptNotFoundSnafu};
+use crate::error::{
+    CompilePythonSnafu, Error, ExecutePythonSnafu, InvalidScheduleSnafu, Result,
+    ScriptNotFoundSnafu, ScriptNotScheduledSnafu,
+};
 use crate::python::{PyEngine, PyScript};
-use crate::table::ScriptsTable;
+use crate::scheduler::{self, ScheduleDefinitionsTable, ScheduleRequest, ScheduleStatusTable};
+use crate::table::{ScriptRecord, ScriptsTable};
 
 pub struct ScriptManager {
-    compiled: RwLock<HashMap<String, Arc<PyScript>>>,
+    /// Compiled scripts with their versions.
+    compiled: RwLock<HashMap<String, (i64, Arc<PyScript>)>>,
     py_engine: PyEngine,
     table: ScriptsTable,
+    catalog_manager: CatalogManagerRef,
+    schedules: ScheduleStatusTable,
+    schedule_definitions: ScheduleDefinitionsTable,
+    /// Id of this node, which owns the schedules created on it.
+    node_id: u64,
+    /// Running scheduled jobs by script name.
+    jobs: Mutex<HashMap<String, JoinHandle<()>>>,
+    /// Scripts registered as SQL functions by script name.
+    udfs: Mutex<HashMap<String, Arc<PyScript>>>,
 }
 
 impl ScriptManager {
     pub async fn new(
         catalog_manager: CatalogManagerRef,
         query_engine: QueryEngineRef,
+        node_id: u64,
     ) -> Result<Self> {
         Ok(Self {
             compiled: RwLock::new(HashMap::default()),
             py_engine: PyEngine::new(query_engine.clone()),
-            table: ScriptsTable::new(catalog_manager, query_engine).await?,
+            table: ScriptsTable::new(catalog_manager.clone(), query_engine.clone()).await?,
+            schedules: ScheduleStatusTable::new(catalog_manager.clone()).await?,
+            schedule_definitions: ScheduleDefinitionsTable::new(
+                catalog_manager.clone(),
+                query_engine,
+            )
+            .await?,
+            node_id,
+            catalog_manager,
+            jobs: Mutex::new(HashMap::default()),
+            udfs: Mutex::new(HashMap::default()),
         })
     }
 
+    /// Registers the stored scripts as SQL functions and restores the schedules owned by this
+    /// node, must be called after the catalog manager is started.
+    pub async fn start(self: &Arc<Self>) -> Result<()> {
+        self.register_udfs().await?;
+
+        for definition in self.schedule_definitions.list().await? {
+            if definition.scheduled && definition.owner == self.node_id {
+                self.spawn_schedule(definition.request);
+            }
+        }
+        Ok(())
+    }
+
+    pub fn node_id(&self) -> u64 {
+        self.node_id
+    }
+
+    /// Registers the scripts of all latest versions as SQL functions, the scripts that fail to
+    /// compile are skipped.
+    async fn register_udfs(&self) -> Result<()> {
+        for record in self.table.list_latest().await? {
+            let name = &record.name;
+            match self.compile(name, &record.script).await {
+                Ok(script) => self.cache(name, record.version, script),
+                Err(e) => logging::error!(e; "Failed to compile script: {}", name),
+            }
+        }
+        Ok(())
+    }
+
     async fn compile(&self, name: &str, script: &str) -> Result<Arc<PyScript>> {
-        let script = Arc::new(
+        Ok(Arc::new(
             self.py_engine
                 .compile(script, CompileContext::default())
                 .await
                 .context(CompilePythonSnafu { name })?,
-        );
+        ))
+    }
+
+    fn cache(&self, name: &str, version: i64, script: Arc<PyScript>) {
+        // Coprocessors returning exactly one vector could also be called as SQL functions.
+        let registered = match script.register_udf() {
+            Ok(()) => Some(script.clone()),
+            Err(e) => {
+                logging::debug!("Script {} is not registered as a SQL function: {}", name, e);
+                None
+            }
+        };
+        let is_registered = registered.is_some();
+        let prior = {
+            let mut udfs = self.udfs.lock().unwrap();
+            match registered {
+                Some(script) => udfs.insert(name.to_string(), script),
+                None => udfs.remove(name),
+            }
+        };
+        // The function of the prior version has been replaced if they have the same name.
+        if let Some(prior) = prior {
+            if !is_registered || prior.name() != script.name() {
+                prior.deregister_udf();
+            }
+        }
 
         let mut compiled = self.compiled.write().unwrap();
-        compiled.insert(name.to_string(), script.clone());
+        compiled.insert(name.to_string(), (version, script));
 
-        logging::info!("Compiled and cached script: {}", name);
+        logging::info!("Compiled and cached script: {}, version: {}", name, version);
+    }
 
-        Ok(script)
+    /// Removes the compiled script and deregisters its SQL function.
+    fn uncache(&self, name: &str) {
+        let _ = self.compiled.write().unwrap().remove(name);
+        if let Some(script) = self.udfs.lock().unwrap().remove(name) {
+            script.deregister_udf();
+        }
     }
 
     pub async fn insert_and_compile(&self, name: &str, script: &str) -> Result<Arc<PyScript>> {
         let compiled_script = self.compile(name, script).await?;
-        self.table.insert(name, script).await?;
+        let version = self.table.insert(name, script).await?;
+        self.cache(name, version, compiled_script.clone());
         Ok(compiled_script)
     }
 
-    pub async fn execute(&self, name: &str) -> Result<Output> {
-        let script = {
-            let s = self.compiled.read().unwrap().get(name).cloned();
-
-            if s.is_some() {
-                s
-            } else {
-                self.try_find_script_and_compile(name).await?
-            }
-        };
+    pub async fn execute(
+        &self,
+        name: &str,
+        params: HashMap<String, String>,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let script = self.try_find_script_and_compile(name).await?;
 
         let script = script.context(ScriptNotFoundSnafu { name })?;
 
         script
-            .execute(EvalContext::default())
+            .execute(EvalContext { params, query_ctx })
             .await
             .context(ExecutePythonSnafu { name })
     }
 
+    /// Finds the latest version of the script, compiles it if the cached one is stale.
+    ///
+    /// The scripts table is shared by all frontends, so checking the version here makes
+    /// the scripts updated or deleted by other instances visible.
     async fn try_find_script_and_compile(&self, name: &str) -> Result<Option<Arc<PyScript>>> {
-        let script = self.table.find_script_by_name(name).await?;
+        let record = match self.table.find_latest(name).await {
+            Ok(record) => record,
+            Err(Error::ScriptNotFound { .. }) => {
+                self.uncache(name);
+                return Ok(None);
+            }
+            Err(e) => return Err(e),
+        };
+
+        if let Some((version, script)) = self.compiled.read().unwrap().get(name) {
+            if *version == record.version {
+                return Ok(Some(script.clone()));
+            }
+        }
 
-        Ok(Some(self.compile(name, &script).await?))
+        let script = self.compile(name, &record.script).await?;
+        self.cache(name, record.version, script.clone());
+        Ok(Some(script))
+    }
+
+    /// Deletes the script and its schedule, its prior versions are kept in the scripts table.
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        self.table.delete(name).await?;
+        self.uncache(name);
+        let _ = self.unschedule_definition(name).await?;
+        if let Some(job) = self.jobs.lock().unwrap().remove(name) {
+            job.abort();
+        }
+        Ok(())
+    }
+
+    /// Lists the latest versions of all scripts.
+    pub async fn list(&self) -> Result<Vec<ScriptRecord>> {
+        self.table.list_latest().await
+    }
+
+    /// Gets the script of the given version, or the latest one if `version` is `None`.
+    pub async fn get(&self, name: &str, version: Option<i64>) -> Result<ScriptRecord> {
+        match version {
+            Some(version) => self.table.find_version(name, version).await,
+            None => self.table.find_latest(name).await,
+        }
+    }
+
+    /// Lists all versions of the script.
+    pub async fn versions(&self, name: &str) -> Result<Vec<ScriptRecord>> {
+        self.table.list_versions(name).await
+    }
+
+    /// Schedules the script to run every interval and write its output into the target table,
+    /// replaces the prior schedule of the script if any.
+    pub async fn schedule(self: &Arc<Self>, request: ScheduleRequest) -> Result<()> {
+        let name = request.name.clone();
+        ensure!(
+            request.interval.as_millis() > 0,
+            InvalidScheduleSnafu {
+                name: &name,
+                reason: "interval must be positive",
+            }
+        );
+        let _ = self
+            .try_find_script_and_compile(&name)
+            .await?
+            .context(ScriptNotFoundSnafu { name: &name })?;
+        let _ = scheduler::find_table(&self.catalog_manager, &request.target_table)?;
+
+        // The schedule is taken over by this node if it's owned by another one.
+        self.schedule_definitions
+            .insert(&request, self.node_id, true)
+            .await?;
+        self.spawn_schedule(request);
+        Ok(())
+    }
+
+    fn spawn_schedule(self: &Arc<Self>, request: ScheduleRequest) {
+        logging::info!(
+            "Scheduled script: {}, interval: {:?}, target table: {}",
+            request.name,
+            request.interval,
+            request.target_table
+        );
+
+        let name = request.name.clone();
+        let job = tokio::spawn(scheduler::run_schedule(Arc::downgrade(self), request));
+        if let Some(prior) = self.jobs.lock().unwrap().insert(name, job) {
+            prior.abort();
+        }
+    }
+
+    /// Unschedules the script, the schedule is stopped on its owner even if it's another node.
+    pub async fn unschedule(&self, name: &str) -> Result<()> {
+        let scheduled = self.unschedule_definition(name).await?;
+        let job = self.jobs.lock().unwrap().remove(name);
+        ensure!(scheduled || job.is_some(), ScriptNotScheduledSnafu { name });
+        if let Some(job) = job {
+            job.abort();
+        }
+
+        logging::info!("Unscheduled script: {}", name);
+
+        Ok(())
+    }
+
+    /// Marks the schedule of the script as unscheduled, returns false if it isn't scheduled.
+    async fn unschedule_definition(&self, name: &str) -> Result<bool> {
+        match self.schedule_definitions.find(name).await? {
+            Some(definition) if definition.scheduled => {
+                self.schedule_definitions
+                    .insert(&definition.request, definition.owner, false)
+                    .await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    /// Returns the owner of the schedule of the script, `None` if it isn't scheduled.
+    pub(crate) async fn schedule_owner(&self, name: &str) -> Result<Option<u64>> {
+        Ok(self
+            .schedule_definitions
+            .find(name)
+            .await?
+            .filter(|definition| definition.scheduled)
+            .map(|definition| definition.owner))
+    }
+
+    /// Runs the scheduled script once over the window ends at `window_end`, and records the
+    /// status of the run.
+    pub(crate) async fn run_scheduled(&self, request: &ScheduleRequest, window_end: i64) {
+        let result = self.execute_and_write_back(request, window_end).await;
+        if let Err(e) = &result {
+            logging::error!(e; "Failed to run scheduled script: {}", request.name);
+        }
+
+        if let Err(e) = self.schedules.insert(request, window_end, &result).await {
+            logging::error!(e; "Failed to record status of scheduled script: {}", request.name);
+        }
+    }
+
+    async fn execute_and_write_back(
+        &self,
+        request: &ScheduleRequest,
+        window_end: i64,
+    ) -> Result<usize> {
+        let table = scheduler::find_table(&self.catalog_manager, &request.target_table)?;
+        let output = self
+            .execute(
+                &request.name,
+                request.params(window_end),
+                QueryContext::internal_arc(),
+            )
+            .await?;
+        scheduler::write_back(&table, &request.target_table, output).await
+    }
+}
+
+impl Drop for ScriptManager {
+    fn drop(&mut self) {
+        for (_, job) in self.jobs.lock().unwrap().drain() {
+            job.abort();
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use catalog::CatalogManager;
+    use std::time::Duration;
+
+    use catalog::{CatalogManager, RegisterTableRequest};
+    use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+    use common_recordbatch::util as record_util;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema, SchemaBuilder};
     use mito::config::EngineConfig as TableEngineConfig;
     use mito::table::test_util::new_test_object_store;
     use query::QueryEngineFactory;
+    use session::context::QueryContext;
+    use table::engine::{EngineContext, TableEngine};
+    use table::requests::CreateTableRequest;
 
     use super::*;
     type DefaultEngine = MitoEngine<EngineImpl<LocalFileLogStore>>;
@@ -109,13 +366,20 @@ mod tests {
     use storage::EngineImpl;
     use tempdir::TempDir;
 
-    #[tokio::test]
-    async fn test_insert_find_compile_script() {
-        let wal_dir = TempDir::new("test_insert_find_compile_script_wal").unwrap();
+    async fn setup_script_manager(
+        prefix: &str,
+    ) -> (
+        (TempDir, TempDir),
+        Arc<DefaultEngine>,
+        CatalogManagerRef,
+        QueryEngineRef,
+        ScriptManager,
+    ) {
+        let wal_dir = TempDir::new(&format!("{}_wal", prefix)).unwrap();
         let wal_dir_str = wal_dir.path().to_string_lossy();
 
         common_telemetry::init_default_ut_logging();
-        let (_dir, object_store) = new_test_object_store("test_insert_find_compile_script").await;
+        let (dir, object_store) = new_test_object_store(prefix).await;
         let log_config = LogConfig {
             log_file_dir: wal_dir_str.to_string(),
             ..Default::default()
@@ -141,11 +405,24 @@ mod tests {
 
         let factory = QueryEngineFactory::new(catalog_manager.clone());
         let query_engine = factory.query_engine();
-        let mgr = ScriptManager::new(catalog_manager.clone(), query_engine)
+        let mgr = ScriptManager::new(catalog_manager.clone(), query_engine.clone(), 0)
             .await
             .unwrap();
         catalog_manager.start().await.unwrap();
 
+        (
+            (dir, wal_dir),
+            mock_engine,
+            catalog_manager,
+            query_engine,
+            mgr,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_insert_find_compile_script() {
+        let (_dirs, _, _, _, mgr) = setup_script_manager("test_insert_find_compile_script").await;
+
         let name = "test";
         mgr.table
             .insert(
@@ -172,5 +449,236 @@ def test(n):
             let cached = mgr.compiled.read().unwrap();
             assert!(cached.get(name).is_some());
         }
+
+        // a new version replaces the cached one
+        let version = mgr
+            .table
+            .insert(
+                name,
+                r#"
+@copr(sql='select number from numbers limit 10', args=['number'], returns=['n'])
+def test(n):
+    return n + 2;
+"#,
+            )
+            .await
+            .unwrap();
+        let _ = mgr
+            .try_find_script_and_compile(name)
+            .await
+            .unwrap()
+            .unwrap();
+        {
+            let cached = mgr.compiled.read().unwrap();
+            assert_eq!(version, cached.get(name).unwrap().0);
+        }
+
+        assert_eq!(2, version);
+        let versions = mgr.versions(name).await.unwrap();
+        assert_eq!(2, versions.len());
+        assert_eq!(1, versions[0].version);
+        assert!(versions[1].script.contains("n + 2"));
+        assert_eq!(versions[0].gmt_created, versions[1].gmt_created);
+        let first = mgr.get(name, Some(versions[0].version)).await.unwrap();
+        assert!(first.script.contains("n + 1"));
+        assert_eq!(1, mgr.list().await.unwrap().len());
+
+        // delete the script
+        mgr.delete(name).await.unwrap();
+        assert!(mgr.compiled.read().unwrap().get(name).is_none());
+        assert!(mgr
+            .try_find_script_and_compile(name)
+            .await
+            .unwrap()
+            .is_none());
+        assert!(mgr.get(name, None).await.is_err());
+        assert!(mgr.list().await.unwrap().is_empty());
+        assert!(mgr.delete(name).await.is_err());
+
+        // versions keep increasing after the deletion
+        let version = mgr.table.insert(name, "").await.unwrap();
+        assert_eq!(4, version);
+    }
+
+    #[tokio::test]
+    async fn test_script_udfs() {
+        let (_dirs, _, _, query_engine, mgr) = setup_script_manager("test_script_udfs").await;
+
+        let name = "add_one";
+        let script = r#"
+@copr(args=['n'], returns=['r'])
+def add_one(n) -> vector[f64]:
+    return n + 1
+"#;
+        let _ = mgr.insert_and_compile(name, script).await.unwrap();
+        let sql = "select add_one(number) as r from numbers limit 1";
+        assert!(query_engine
+            .sql_to_plan(sql, Arc::new(QueryContext::new()))
+            .is_ok());
+
+        // stored scripts are registered again after restarting
+        mgr.compiled.write().unwrap().clear();
+        mgr.udfs.lock().unwrap().clear();
+        query_engine.deregister_udf("add_one");
+        assert!(query_engine
+            .sql_to_plan(sql, Arc::new(QueryContext::new()))
+            .is_err());
+        mgr.register_udfs().await.unwrap();
+        assert!(mgr.compiled.read().unwrap().contains_key(name));
+        assert!(query_engine
+            .sql_to_plan(sql, Arc::new(QueryContext::new()))
+            .is_ok());
+
+        mgr.delete(name).await.unwrap();
+        assert!(query_engine
+            .sql_to_plan(sql, Arc::new(QueryContext::new()))
+            .is_err());
+    }
+
+    async fn query(query_engine: &QueryEngineRef, sql: &str) -> String {
+        let plan = query_engine
+            .sql_to_plan(sql, Arc::new(QueryContext::new()))
+            .unwrap();
+        let batches = match query_engine.execute(&plan).await.unwrap() {
+            Output::Stream(stream) => record_util::collect(stream).await.unwrap(),
+            _ => unreachable!(),
+        };
+        common_recordbatch::RecordBatches::try_new(batches[0].schema.clone(), batches)
+            .unwrap()
+            .pretty_print()
+    }
+
+    #[tokio::test]
+    async fn test_schedule_script() {
+        let (_dirs, engine, catalog_manager, query_engine, mgr) =
+            setup_script_manager("test_schedule_script").await;
+        let mgr = Arc::new(mgr);
+
+        let table_name = "downsampled";
+        let schema = SchemaBuilder::try_from(vec![
+            ColumnSchema::new("value", ConcreteDataType::float64_datatype(), true),
+            ColumnSchema::new("ts", ConcreteDataType::timestamp_millis_datatype(), false)
+                .with_time_index(true)
+                .with_default_constraint(Some(ColumnDefaultConstraint::Function(
+                    "current_timestamp()".to_string(),
+                )))
+                .unwrap(),
+        ])
+        .unwrap()
+        .build()
+        .unwrap();
+        let table = engine
+            .create_table(
+                &EngineContext::default(),
+                CreateTableRequest {
+                    id: 1024,
+                    catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                    schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                    table_name: table_name.to_string(),
+                    desc: None,
+                    schema: Arc::new(schema),
+                    region_numbers: vec![0],
+                    primary_key_indices: vec![],
+                    create_if_not_exists: true,
+                    table_options: HashMap::default(),
+                },
+            )
+            .await
+            .unwrap();
+        let _ = catalog_manager
+            .register_table(RegisterTableRequest {
+                catalog: DEFAULT_CATALOG_NAME.to_string(),
+                schema: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: table_name.to_string(),
+                table_id: 1024,
+                table,
+            })
+            .await
+            .unwrap();
+
+        let name = "downsample";
+        let _ = mgr
+            .insert_and_compile(
+                name,
+                r#"
+@copr(sql='select number from numbers where number < {window_end} / 100000 limit 10', args=['number'], returns=['value'])
+def downsample(number) -> vector[f64]:
+    return number * 1.5
+"#,
+            )
+            .await
+            .unwrap();
+
+        let mut request = ScheduleRequest {
+            name: name.to_string(),
+            interval: Duration::from_secs(300),
+            target_table: "not_exists".to_string(),
+            params: HashMap::new(),
+        };
+        assert!(mgr.schedule(request.clone()).await.is_err());
+        request.target_table = table_name.to_string();
+        mgr.schedule(request.clone()).await.unwrap();
+        assert!(mgr.jobs.lock().unwrap().contains_key(name));
+        assert_eq!(Some(0), mgr.schedule_owner(name).await.unwrap());
+
+        // the schedules owned by this node are restored after restarting
+        mgr.jobs
+            .lock()
+            .unwrap()
+            .drain()
+            .for_each(|(_, job)| job.abort());
+        mgr.start().await.unwrap();
+        assert!(mgr.jobs.lock().unwrap().contains_key(name));
+
+        // the schedules owned by other nodes are not
+        mgr.jobs
+            .lock()
+            .unwrap()
+            .drain()
+            .for_each(|(_, job)| job.abort());
+        mgr.schedule_definitions
+            .insert(&request, 1, true)
+            .await
+            .unwrap();
+        mgr.start().await.unwrap();
+        assert!(!mgr.jobs.lock().unwrap().contains_key(name));
+        assert_eq!(Some(1), mgr.schedule_owner(name).await.unwrap());
+
+        // schedules owned by other nodes could be unscheduled too
+        mgr.unschedule(name).await.unwrap();
+        assert_eq!(None, mgr.schedule_owner(name).await.unwrap());
+        assert!(mgr.unschedule(name).await.is_err());
+        mgr.start().await.unwrap();
+        assert!(mgr.jobs.lock().unwrap().is_empty());
+
+        // runs over the window [0, 300000)
+        mgr.run_scheduled(&request, 300_000).await;
+        let output = query(
+            &query_engine,
+            "select value from downsampled order by value",
+        )
+        .await;
+        let expected = "\
++-------+
+| value |
++-------+
+| 0     |
+| 1.5   |
+| 3     |
++-------+";
+        assert_eq!(expected, output);
+
+        let output = query(
+            &query_engine,
+            "select name, target_table, status, last_error, affected_rows from script_schedules",
+        )
+        .await;
+        let expected = "\
++------------+--------------+---------+------------+---------------+
+| name       | target_table | status  | last_error | affected_rows |
++------------+--------------+---------+------------+---------------+
+| downsample | downsampled  | success |            | 3             |
++------------+--------------+---------+------------+---------------+";
+        assert_eq!(expected, output);
     }
 }
@@ -468,6 +468,66 @@ pub(crate) fn exec_parsed(copr: &Coprocessor, rb: &DfRecordBatch) -> Result<Reco
     exec_with_cached_vm(copr, rb, args, &interpreter)
 }
 
+/// execute the coprocessor as a scalar function, `args` are bound to the coprocessor's `args` by
+/// position and the only returned vector is cast to `return_type`
+pub(crate) fn exec_udf(
+    copr: &Coprocessor,
+    args: &[VectorRef],
+    return_type: &DataType,
+) -> Result<VectorRef> {
+    ensure!(
+        args.len() == copr.deco_args.arg_names.len(),
+        OtherSnafu {
+            reason: format!(
+                "Coprocessor {} expects {} arguments, found {}",
+                copr.name,
+                copr.deco_args.arg_names.len(),
+                args.len()
+            )
+        }
+    );
+    let columns: Vec<ArrayRef> = args.iter().map(|arg| arg.to_arrow_array()).collect();
+    let fields = copr
+        .deco_args
+        .arg_names
+        .iter()
+        .zip(&columns)
+        .zip(&copr.arg_types)
+        .map(|((name, column), anno)| {
+            // use the annotated nullability so the check of argument types could pass
+            let is_nullable = anno.as_ref().map(|anno| anno.is_nullable).unwrap_or(true);
+            Field::new(name, column.data_type().to_owned(), is_nullable)
+        })
+        .collect::<Vec<Field>>();
+    let rb =
+        DfRecordBatch::try_new(Arc::new(ArrowSchema::from(fields)), columns).context(ArrowSnafu)?;
+
+    let ret = exec_parsed(copr, &rb)?;
+    ensure!(
+        ret.df_recordbatch.num_columns() == 1,
+        OtherSnafu {
+            reason: format!(
+                "Coprocessor {} must return exactly one vector to be called as a function",
+                copr.name
+            )
+        }
+    );
+    let mut col = ret.df_recordbatch.column(0).clone();
+    if col.data_type() != return_type {
+        col = arrow::compute::cast::cast(
+            col.as_ref(),
+            return_type,
+            CastOptions {
+                wrapped: true,
+                partial: true,
+            },
+        )
+        .context(ArrowSnafu)?
+        .into();
+    }
+    Helper::try_into_vector(col).context(TypeCastSnafu)
+}
+
 /// execute script just like [`exec_coprocessor`] do,
 /// but instead of return a internal [`Error`] type,
 /// return a friendly String format of error
@@ -14,24 +14,29 @@
 
 //! Python script engine
 use std::any::Any;
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
 
 use async_trait::async_trait;
 use common_error::prelude::BoxedError;
+use common_query::prelude::{
+    make_scalar_function, ReturnTypeFunction, ScalarUdf, Signature, Volatility,
+};
 use common_query::Output;
 use common_recordbatch::error::{ExternalSnafu, Result as RecordBatchResult};
 use common_recordbatch::{RecordBatch, RecordBatchStream, SendableRecordBatchStream};
+use datatypes::data_type::{ConcreteDataType, DataType};
 use datatypes::schema::SchemaRef;
+use datatypes::vectors::VectorRef;
 use futures::Stream;
 use query::QueryEngineRef;
-use session::context::QueryContext;
-use snafu::{ensure, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use sql::statements::statement::Statement;
 
 use crate::engine::{CompileContext, EvalContext, Script, ScriptEngine};
-use crate::python::coprocessor::{exec_parsed, parse, CoprocessorRef};
+use crate::python::coprocessor::{exec_parsed, exec_udf, parse, CoprocessorRef};
 use crate::python::error::{self, Result};
 
 const PY_ENGINE: &str = "python";
@@ -87,16 +92,15 @@ impl Script for PyScript {
         self
     }
 
-    async fn execute(&self, _ctx: EvalContext) -> Result<Output> {
+    async fn execute(&self, ctx: EvalContext) -> Result<Output> {
         if let Some(sql) = &self.copr.deco_args.sql {
-            let stmt = self.query_engine.sql_to_statement(sql)?;
+            let sql = interpolate_sql(sql, &ctx.params)?;
+            let stmt = self.query_engine.sql_to_statement(&sql)?;
             ensure!(
                 matches!(stmt, Statement::Query { .. }),
                 error::UnsupportedSqlSnafu { sql }
             );
-            let plan = self
-                .query_engine
-                .statement_to_plan(stmt, Arc::new(QueryContext::new()))?;
+            let plan = self.query_engine.statement_to_plan(stmt, ctx.query_ctx)?;
             let res = self.query_engine.execute(&plan).await?;
             let copr = self.copr.clone();
             match res {
@@ -110,6 +114,132 @@ impl Script for PyScript {
     }
 }
 
+impl PyScript {
+    /// Returns the name of the coprocessor, which is also the name of its SQL function.
+    pub fn name(&self) -> &str {
+        &self.copr.name
+    }
+
+    /// Registers the coprocessor as a SQL scalar function named after the coprocessor, so it
+    /// could be called like `SELECT my_copr(col) FROM t`. The arguments of the function are
+    /// bound to the coprocessor's `args` by position, and the coprocessor must return exactly
+    /// one vector, whose type is the annotated return type or `f64` if not annotated.
+    pub fn register_udf(&self) -> Result<()> {
+        let copr = self.copr.clone();
+        ensure!(
+            copr.deco_args.ret_names.len() == 1,
+            error::OtherSnafu {
+                reason: format!(
+                    "Coprocessor {} must return exactly one vector to be registered as a function",
+                    copr.name
+                )
+            }
+        );
+        let return_type = copr
+            .return_types
+            .first()
+            .cloned()
+            .flatten()
+            .and_then(|anno| anno.datatype)
+            .map(|datatype| ConcreteDataType::from_arrow_type(&datatype))
+            .unwrap_or_else(ConcreteDataType::float64_datatype);
+
+        let arrow_type = return_type.as_arrow_type();
+        let udf_copr = copr.clone();
+        let fun = make_scalar_function(move |args: &[VectorRef]| {
+            Ok(exec_udf(&udf_copr, args, &arrow_type).map_err(BoxedError::new)?)
+        });
+        let return_type = Arc::new(return_type);
+        let return_type: ReturnTypeFunction = Arc::new(move |_| Ok(return_type.clone()));
+        let signature = Signature::any(copr.deco_args.arg_names.len(), Volatility::Immutable);
+
+        self.query_engine
+            .register_udf(ScalarUdf::new(&copr.name, &signature, &return_type, &fun));
+        Ok(())
+    }
+
+    /// Deregisters the SQL function registered by [PyScript::register_udf].
+    pub fn deregister_udf(&self) {
+        self.query_engine.deregister_udf(&self.copr.name);
+    }
+}
+
+/// Replaces the `{name}` placeholders in `sql` with the parameter values as SQL literals, `{{`
+/// and `}}` are escapes of `{` and `}`.
+///
+/// The type of a placeholder could be declared like `{name:int}`, the type is one of `int`,
+/// `float`, `bool` and `string`, and values not of the type are rejected. Values of untyped
+/// placeholders are numbers or booleans if they look like ones, otherwise strings. Strings are
+/// always quoted, so placeholders must not be quoted in the sql.
+fn interpolate_sql(sql: &str, params: &HashMap<String, String>) -> Result<String> {
+    let mut result = String::with_capacity(sql.len());
+    let mut rest = sql;
+    while let Some(start) = rest.find(|c: char| c == '{' || c == '}') {
+        result.push_str(&rest[..start]);
+        let brace = &rest[start..start + 1];
+        rest = &rest[start + 1..];
+        if rest.starts_with(brace) {
+            // escaped brace
+            result.push_str(brace);
+            rest = &rest[1..];
+            continue;
+        }
+
+        let placeholder = rest
+            .find('}')
+            .map(|end| &rest[..end])
+            .filter(|placeholder| {
+                brace == "{"
+                    && !placeholder.is_empty()
+                    && placeholder
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '_' || c == ':')
+            });
+        match placeholder {
+            Some(placeholder) => {
+                let (name, param_type) = match placeholder.split_once(':') {
+                    Some((name, param_type)) => (name, Some(param_type)),
+                    None => (placeholder, None),
+                };
+                let value = params
+                    .get(name)
+                    .context(error::MissingSqlParamSnafu { name })?;
+                result.push_str(&param_to_literal(name, param_type, value)?);
+                rest = &rest[placeholder.len() + 1..];
+            }
+            // not a placeholder, keeps it as is
+            None => result.push_str(brace),
+        }
+    }
+    result.push_str(rest);
+    Ok(result)
+}
+
+/// Converts the parameter value to a SQL literal of `param_type`, or the type inferred from the
+/// value if not declared.
+fn param_to_literal(name: &str, param_type: Option<&str>, value: &str) -> Result<String> {
+    let invalid_param = |reason: String| error::InvalidSqlParamSnafu { name, reason }.fail();
+    let is_int = value.parse::<i64>().is_ok();
+    let is_float = value.parse::<f64>().map_or(false, |v| v.is_finite());
+    let is_bool = value.eq_ignore_ascii_case("true") || value.eq_ignore_ascii_case("false");
+    let quoted = || format!("'{}'", value.replace('\'', "''"));
+
+    match param_type {
+        None if is_int => Ok(value.to_string()),
+        // Floats are formatted again, as the SQL parser doesn't accept exponents.
+        None if is_float => Ok(value.parse::<f64>().unwrap().to_string()),
+        None if is_bool => Ok(value.to_lowercase()),
+        None | Some("string") => Ok(quoted()),
+        Some("int") if is_int => Ok(value.to_string()),
+        Some("float") if is_float => Ok(value.parse::<f64>().unwrap().to_string()),
+        Some("bool") if is_bool => Ok(value.to_lowercase()),
+        Some(param_type @ ("int" | "float" | "bool")) => {
+            invalid_param(format!("value {} is not {}", value, param_type))
+        }
+        Some(param_type) => invalid_param(format!("unknown type {}", param_type)),
+    }
+}
+
 pub struct PyEngine {
     query_engine: QueryEngineRef,
 }
@@ -150,14 +280,14 @@ mod tests {
     use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
     use common_recordbatch::util;
     use datafusion_common::field_util::{FieldExt, SchemaExt};
-    use datatypes::arrow::array::{Float64Array, Int64Array};
+    use datatypes::arrow::array::{Float64Array, Int64Array, UInt32Array};
     use query::QueryEngineFactory;
+    use session::context::QueryContext;
     use table::table::numbers::NumbersTable;
 
     use super::*;
 
-    #[tokio::test]
-    async fn test_compile_execute() {
+    fn sample_query_engine() -> QueryEngineRef {
         let catalog_list = catalog::local::new_memory_catalog_list().unwrap();
 
         let default_schema = Arc::new(MemorySchemaProvider::new());
@@ -173,9 +303,12 @@ mod tests {
             .unwrap();
 
         let factory = QueryEngineFactory::new(catalog_list);
-        let query_engine = factory.query_engine();
+        factory.query_engine()
+    }
 
-        let script_engine = PyEngine::new(query_engine.clone());
+    #[tokio::test]
+    async fn test_compile_execute() {
+        let script_engine = PyEngine::new(sample_query_engine());
 
         let script = r#"
 import greptime as g
@@ -242,4 +375,123 @@ def test(a):
             _ => unreachable!(),
         }
     }
+
+    #[tokio::test]
+    async fn test_execute_with_params() {
+        let script_engine = PyEngine::new(sample_query_engine());
+
+        let script = r#"
+@copr(args=["number"], returns = ["r"], sql="select number from numbers where number >= {min:int} limit {limit}")
+def test(n):
+    return n
+"#;
+        let script = script_engine
+            .compile(script, CompileContext::default())
+            .await
+            .unwrap();
+
+        let result = script.execute(EvalContext::default()).await;
+        assert!(matches!(result, Err(error::Error::MissingSqlParam { .. })));
+
+        let ctx = EvalContext {
+            params: HashMap::from([
+                ("min".to_string(), "10".to_string()),
+                ("limit".to_string(), "5".to_string()),
+            ]),
+            query_ctx: Arc::new(QueryContext::new()),
+        };
+        match script.execute(ctx).await.unwrap() {
+            Output::Stream(stream) => {
+                let numbers = util::collect(stream).await.unwrap();
+                let columns = numbers[0].df_recordbatch.columns();
+                assert_eq!(5, columns[0].len());
+                let rows = columns[0].as_any().downcast_ref::<UInt32Array>().unwrap();
+                assert_eq!(10, rows.value(0));
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_register_udf() {
+        let query_engine = sample_query_engine();
+        let script_engine = PyEngine::new(query_engine.clone());
+
+        let script = r#"
+@copr(args=["a", "b"], returns = ["r"])
+def add_twice(a, b) -> vector[f64]:
+    return a + b * 2
+"#;
+        let script = script_engine
+            .compile(script, CompileContext::default())
+            .await
+            .unwrap();
+        script.register_udf().unwrap();
+
+        let plan = query_engine
+            .sql_to_plan(
+                "select add_twice(number, number) as r from numbers limit 5",
+                Arc::new(QueryContext::new()),
+            )
+            .unwrap();
+        match query_engine.execute(&plan).await.unwrap() {
+            Output::Stream(stream) => {
+                let numbers = util::collect(stream).await.unwrap();
+                let columns = numbers[0].df_recordbatch.columns();
+                let rows = columns[0].as_any().downcast_ref::<Float64Array>().unwrap();
+                assert_eq!(5, rows.len());
+                assert_eq!(12.0, rows.value(4));
+            }
+            _ => unreachable!(),
+        }
+
+        // coprocessors returning more than one vector can't be functions
+        let script = r#"
+@copr(args=["a"], returns = ["r", "s"])
+def test(a):
+    return a, a
+"#;
+        let script = script_engine
+            .compile(script, CompileContext::default())
+            .await
+            .unwrap();
+        assert!(script.register_udf().is_err());
+    }
+
+    #[test]
+    fn test_interpolate_sql() {
+        let params = HashMap::from([
+            ("limit".to_string(), "10".to_string()),
+            ("host".to_string(), "host1".to_string()),
+            ("ratio".to_string(), "1e-1".to_string()),
+            ("flag".to_string(), "TRUE".to_string()),
+            ("injection".to_string(), "a' OR '1' = '1".to_string()),
+        ]);
+        assert_eq!(
+            "select * from t where host = 'host1' and v > 0.1 and f = true limit 10",
+            interpolate_sql(
+                "select * from t where host = {host} and v > {ratio} and f = {flag} limit {limit}",
+                &params
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "select * from t where host = 'a'' OR ''1'' = ''1' or host = '10'",
+            interpolate_sql(
+                "select * from t where host = {injection} or host = {limit:string}",
+                &params
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            "select '{}', '{host}', '{a b}' from t",
+            interpolate_sql("select '{}', '{{host}}', '{a b}' from t", &params).unwrap()
+        );
+        assert!(interpolate_sql("select * from t limit {unknown}", &params).is_err());
+        assert!(matches!(
+            interpolate_sql("select * from t limit {host:int}", &params),
+            Err(error::Error::InvalidSqlParam { .. })
+        ));
+        assert!(interpolate_sql("select * from t limit {limit:decimal}", &params).is_err());
+    }
 }
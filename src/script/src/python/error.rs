@@ -100,6 +100,20 @@ pub enum Error {
     #[snafu(display("Missing sql in coprocessor"))]
     MissingSql { backtrace: Backtrace },
 
+    #[snafu(display("Missing parameter `{}` for sql in coprocessor", name))]
+    MissingSqlParam { name: String, backtrace: Backtrace },
+
+    #[snafu(display(
+        "Invalid parameter `{}` for sql in coprocessor, reason: {}",
+        name,
+        reason
+    ))]
+    InvalidSqlParam {
+        name: String,
+        reason: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Failed to retrieve record batches, source: {}", source))]
     RecordBatch {
         #[snafu(backtrace)]
@@ -129,7 +143,9 @@ impl ErrorExt for Error {
             | Error::PyCompile { .. }
             | Error::CoprParse { .. }
             | Error::UnsupportedSql { .. }
-            | Error::MissingSql { .. } => StatusCode::InvalidArguments,
+            | Error::MissingSql { .. }
+            | Error::MissingSqlParam { .. }
+            | Error::InvalidSqlParam { .. } => StatusCode::InvalidArguments,
         }
     }
     fn backtrace_opt(&self) -> Option<&common_error::snafu::Backtrace> {
@@ -26,6 +26,7 @@ use datatypes::arrow::compute::{arithmetics, comparison};
 use datatypes::arrow::datatypes::DataType;
 use datatypes::arrow::scalar::{PrimitiveScalar, Scalar};
 use datatypes::data_type::ConcreteDataType;
+use datatypes::decimal::Decimal128;
 use datatypes::prelude::Value;
 use datatypes::value::OrderedFloat;
 use datatypes::vectors::{Helper, NullVector, VectorBuilder, VectorRef};
@@ -855,6 +856,27 @@ pub fn pyobj_try_to_typed_val(
                     None
                 }
             }
+            ConcreteDataType::Decimal128(t) => {
+                let s = if is_instance::<PyStr>(&obj, vm) {
+                    obj.try_into_value::<String>(vm).ok()
+                } else if is_instance::<PyInt>(&obj, vm) || is_instance::<PyFloat>(&obj, vm) {
+                    obj.try_into_value::<f64>(vm).ok().map(|v| v.to_string())
+                } else {
+                    None
+                };
+                s.and_then(|s| Decimal128::from_str_with(&s, t.precision, t.scale).ok())
+                    .map(value::Value::Decimal128)
+            }
+            ConcreteDataType::Json(_) => {
+                if is_instance::<PyStr>(&obj, vm) {
+                    obj.try_into_value::<String>(vm)
+                        .ok()
+                        .and_then(|v| v.parse().ok())
+                        .map(value::Value::Json)
+                } else {
+                    None
+                }
+            }
             ConcreteDataType::List(_) => unreachable!(),
             ConcreteDataType::Date(_)
             | ConcreteDataType::DateTime(_)
@@ -941,6 +963,10 @@ pub fn val_to_pyobj(val: value::Value, vm: &VirtualMachine) -> PyObjectRef {
         value::Value::DateTime(v) => vm.ctx.new_int(v.val()).into(),
         // FIXME(dennis): lose the timestamp unit here
         Value::Timestamp(v) => vm.ctx.new_int(v.value()).into(),
+        // Python float is the closest builtin type, precision may be lost.
+        value::Value::Decimal128(v) => vm.ctx.new_float(v.to_f64()).into(),
+        // Json is passed to python in text format, scripts can decode it with `json.loads`.
+        value::Value::Json(v) => vm.ctx.new_str(v.to_string()).into(),
         value::Value::List(list) => {
             let list = list.items().as_ref();
             match list {
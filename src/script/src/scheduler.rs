@@ -0,0 +1,610 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Scheduled execution of scripts, the outputs are written back into tables.
+//!
+//! Schedules are stored in a system table, and each schedule is owned by the node that
+//! created it. The owner restores its schedules at startup, and the job of a schedule stops
+//! once it's unscheduled or taken over by another node.
+use std::collections::HashMap;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use catalog::{CatalogManagerRef, RegisterSystemTableRequest};
+use common_catalog::consts::{
+    DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, SCRIPT_SCHEDULES_TABLE_ID,
+    SCRIPT_SCHEDULE_DEFINITIONS_TABLE_ID,
+};
+use common_query::Output;
+use common_recordbatch::util as record_util;
+use common_telemetry::logging;
+use common_time::timestamp::Timestamp;
+use common_time::util;
+use datatypes::arrow::array::{PrimitiveArray, Utf8Array};
+use datatypes::prelude::{ConcreteDataType, ScalarVector};
+use datatypes::schema::{ColumnSchema, Schema, SchemaBuilder};
+use datatypes::vectors::{
+    Helper, Int64Vector, StringVector, TimestampVector, UInt64Vector, VectorRef,
+};
+use query::QueryEngineRef;
+use session::context::QueryContext;
+use snafu::{OptionExt, ResultExt};
+use table::requests::{CreateTableRequest, InsertRequest};
+use table::TableRef;
+use tokio::time::MissedTickBehavior;
+
+use crate::error::{
+    CollectRecordsSnafu, ConvertVectorSnafu, DecodeScheduleParamsSnafu, EncodeScheduleParamsSnafu,
+    FindScheduleSnafu, FindTableSnafu, InsertTableSnafu, RegisterSchedulesTableSnafu, Result,
+    TableNotFoundSnafu,
+};
+use crate::manager::ScriptManager;
+use crate::table::downcast_column;
+
+pub const SCRIPT_SCHEDULES_TABLE_NAME: &str = "script_schedules";
+pub const SCRIPT_SCHEDULE_DEFINITIONS_TABLE_NAME: &str = "script_schedule_definitions";
+const SCHEDULED_STATUS: &str = "scheduled";
+const UNSCHEDULED_STATUS: &str = "unscheduled";
+/// Runtime parameter of the window start passed to scheduled scripts, in milliseconds.
+pub const WINDOW_START_PARAM: &str = "window_start";
+/// Runtime parameter of the window end (exclusive) passed to scheduled scripts, in milliseconds.
+pub const WINDOW_END_PARAM: &str = "window_end";
+
+#[derive(Debug, Clone)]
+pub struct ScheduleRequest {
+    /// Name of the script.
+    pub name: String,
+    pub interval: Duration,
+    /// Table in the default catalog and schema that the returned columns are written into.
+    pub target_table: String,
+    /// Runtime parameters passed to the script besides the window.
+    pub params: HashMap<String, String>,
+}
+
+impl ScheduleRequest {
+    fn interval_millis(&self) -> i64 {
+        self.interval.as_millis() as i64
+    }
+
+    /// Returns the window ends at `window_end`.
+    pub(crate) fn window(&self, window_end: i64) -> (i64, i64) {
+        (window_end - self.interval_millis(), window_end)
+    }
+
+    pub(crate) fn params(&self, window_end: i64) -> HashMap<String, String> {
+        let (start, end) = self.window(window_end);
+        let mut params = self.params.clone();
+        let _ = params.insert(WINDOW_START_PARAM.to_string(), start.to_string());
+        let _ = params.insert(WINDOW_END_PARAM.to_string(), end.to_string());
+        params
+    }
+}
+
+/// Runs the scheduled script every interval until the job is aborted, the manager is dropped
+/// or the schedule isn't owned by the node anymore. The first run is one interval later.
+pub(crate) async fn run_schedule(manager: Weak<ScriptManager>, request: ScheduleRequest) {
+    let interval_millis = request.interval_millis();
+    let mut ticker = tokio::time::interval_at(
+        tokio::time::Instant::now() + request.interval,
+        request.interval,
+    );
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Skip);
+
+    loop {
+        let _ = ticker.tick().await;
+        let manager = match manager.upgrade() {
+            Some(manager) => manager,
+            None => return,
+        };
+
+        match manager.schedule_owner(&request.name).await {
+            Ok(Some(owner)) if owner == manager.node_id() => {}
+            Ok(_) => {
+                logging::info!(
+                    "Stopped scheduled script: {}, it's unscheduled or owned by another node",
+                    request.name
+                );
+                return;
+            }
+            Err(e) => {
+                logging::error!(e; "Failed to find schedule of script: {}", request.name);
+                continue;
+            }
+        }
+
+        // Aligns windows to the interval, so that consecutive runs cover adjacent windows.
+        let now = util::current_time_millis();
+        manager
+            .run_scheduled(&request, now - now % interval_millis)
+            .await;
+    }
+}
+
+pub(crate) fn find_table(catalog_manager: &CatalogManagerRef, table: &str) -> Result<TableRef> {
+    catalog_manager
+        .table(DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME, table)
+        .context(FindTableSnafu { table })?
+        .context(TableNotFoundSnafu { table })
+}
+
+/// Writes the output of the script into the table, returns the affected rows.
+pub(crate) async fn write_back(
+    table: &TableRef,
+    table_name: &str,
+    output: Output,
+) -> Result<usize> {
+    let batches = match output {
+        Output::AffectedRows(_) => return Ok(0),
+        Output::RecordBatches(batches) => batches.take(),
+        Output::Stream(stream) => record_util::collect(stream)
+            .await
+            .context(CollectRecordsSnafu)?,
+    };
+
+    let mut affected_rows = 0;
+    for batch in batches {
+        let columns_values = batch
+            .schema
+            .column_schemas()
+            .iter()
+            .zip(batch.df_recordbatch.columns())
+            .map(|(column_schema, array)| {
+                Helper::try_into_vector(array.clone())
+                    .map(|vector| (column_schema.name.clone(), vector))
+                    .context(ConvertVectorSnafu)
+            })
+            .collect::<Result<HashMap<_, _>>>()?;
+
+        affected_rows += table
+            .insert(InsertRequest {
+                catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: table_name.to_string(),
+                columns_values,
+            })
+            .await
+            .context(InsertTableSnafu { table: table_name })?;
+    }
+    Ok(affected_rows)
+}
+
+/// A schedule stored in the schedule definitions table.
+#[derive(Debug, Clone)]
+pub struct ScheduleDefinition {
+    pub request: ScheduleRequest,
+    /// Node id of the owner, only the owner runs the schedule.
+    pub owner: u64,
+    /// Whether the schedule is active, `false` if it's unscheduled.
+    pub scheduled: bool,
+}
+
+/// System table of the schedule definitions, every change of a schedule is a new row, and the
+/// latest row of a script is its current schedule.
+pub struct ScheduleDefinitionsTable {
+    catalog_manager: CatalogManagerRef,
+    query_engine: QueryEngineRef,
+}
+
+impl ScheduleDefinitionsTable {
+    pub async fn new(
+        catalog_manager: CatalogManagerRef,
+        query_engine: QueryEngineRef,
+    ) -> Result<Self> {
+        let request = CreateTableRequest {
+            id: SCRIPT_SCHEDULE_DEFINITIONS_TABLE_ID,
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: SCRIPT_SCHEDULE_DEFINITIONS_TABLE_NAME.to_string(),
+            desc: Some("Script schedule definitions table".to_string()),
+            schema: Arc::new(build_definitions_schema()),
+            region_numbers: vec![0],
+            // name as primary key
+            primary_key_indices: vec![0],
+            create_if_not_exists: true,
+            table_options: HashMap::default(),
+        };
+
+        catalog_manager
+            .register_system_table(RegisterSystemTableRequest {
+                create_table_request: request,
+                open_hook: None,
+            })
+            .await
+            .context(RegisterSchedulesTableSnafu)?;
+
+        Ok(Self {
+            catalog_manager,
+            query_engine,
+        })
+    }
+
+    /// Stores the schedule owned by the node `owner`, it replaces the prior one of the script.
+    pub async fn insert(
+        &self,
+        request: &ScheduleRequest,
+        owner: u64,
+        scheduled: bool,
+    ) -> Result<()> {
+        let name = &request.name;
+        let params =
+            serde_json::to_string(&request.params).context(EncodeScheduleParamsSnafu { name })?;
+        let status = if scheduled {
+            SCHEDULED_STATUS
+        } else {
+            UNSCHEDULED_STATUS
+        };
+
+        let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(7);
+        columns_values.insert(
+            "name".to_string(),
+            Arc::new(StringVector::from(vec![name.as_str()])) as _,
+        );
+        columns_values.insert(
+            "target_table".to_string(),
+            Arc::new(StringVector::from(vec![request.target_table.as_str()])) as _,
+        );
+        columns_values.insert(
+            "interval_millis".to_string(),
+            Arc::new(Int64Vector::from_slice(&[request.interval_millis()])) as _,
+        );
+        columns_values.insert(
+            "params".to_string(),
+            Arc::new(StringVector::from(vec![params.as_str()])) as _,
+        );
+        columns_values.insert(
+            "owner".to_string(),
+            Arc::new(UInt64Vector::from_slice(&[owner])) as _,
+        );
+        columns_values.insert(
+            "status".to_string(),
+            Arc::new(StringVector::from(vec![status])) as _,
+        );
+        // Timestamp in key part is the time of the change
+        columns_values.insert(
+            "timestamp".to_string(),
+            Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
+                util::current_time_millis(),
+            )])) as _,
+        );
+
+        let table = find_table(
+            &self.catalog_manager,
+            SCRIPT_SCHEDULE_DEFINITIONS_TABLE_NAME,
+        )?;
+        let _ = table
+            .insert(InsertRequest {
+                catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: SCRIPT_SCHEDULE_DEFINITIONS_TABLE_NAME.to_string(),
+                columns_values,
+            })
+            .await
+            .context(InsertTableSnafu {
+                table: SCRIPT_SCHEDULE_DEFINITIONS_TABLE_NAME,
+            })?;
+
+        logging::info!(
+            "Stored schedule of script: name={}, owner={}, status={}",
+            name,
+            owner,
+            status
+        );
+
+        Ok(())
+    }
+
+    /// Finds the current schedule of the script.
+    pub async fn find(&self, name: &str) -> Result<Option<ScheduleDefinition>> {
+        Ok(self.find_definitions(Some(name)).await?.pop())
+    }
+
+    /// Lists the current schedules of all scripts, including the unscheduled ones.
+    pub async fn list(&self) -> Result<Vec<ScheduleDefinition>> {
+        self.find_definitions(None).await
+    }
+
+    /// Finds the current schedules of the script `name` or all scripts, ordered by name.
+    async fn find_definitions(&self, name: Option<&str>) -> Result<Vec<ScheduleDefinition>> {
+        let condition = name
+            .map(|name| format!(" where name = '{}'", name.replace('\'', "''")))
+            .unwrap_or_default();
+        let sql = format!(
+            "select name, target_table, interval_millis, params, owner, status from {}{} \
+             order by name, timestamp",
+            catalog::format_full_table_name(
+                DEFAULT_CATALOG_NAME,
+                DEFAULT_SCHEMA_NAME,
+                SCRIPT_SCHEDULE_DEFINITIONS_TABLE_NAME,
+            ),
+            condition
+        );
+        let name = name.unwrap_or_default();
+
+        let plan = self
+            .query_engine
+            .sql_to_plan(&sql, QueryContext::internal_arc())
+            .context(FindScheduleSnafu { name })?;
+        let stream = match self
+            .query_engine
+            .execute(&plan)
+            .await
+            .context(FindScheduleSnafu { name })?
+        {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        let records = record_util::collect(stream)
+            .await
+            .context(CollectRecordsSnafu)?;
+
+        let mut definitions: Vec<ScheduleDefinition> = Vec::new();
+        for record in records {
+            let record = &record.df_recordbatch;
+            let names = downcast_column::<Utf8Array<i32>>(record, 0)?;
+            let target_tables = downcast_column::<Utf8Array<i32>>(record, 1)?;
+            let intervals = downcast_column::<PrimitiveArray<i64>>(record, 2)?;
+            let params = downcast_column::<Utf8Array<i32>>(record, 3)?;
+            let owners = downcast_column::<PrimitiveArray<u64>>(record, 4)?;
+            let statuses = downcast_column::<Utf8Array<i32>>(record, 5)?;
+
+            for i in 0..record.num_rows() {
+                let name = names.value(i);
+                let definition = ScheduleDefinition {
+                    request: ScheduleRequest {
+                        name: name.to_string(),
+                        interval: Duration::from_millis(intervals.value(i) as u64),
+                        target_table: target_tables.value(i).to_string(),
+                        params: serde_json::from_str(params.value(i))
+                            .context(DecodeScheduleParamsSnafu { name })?,
+                    },
+                    owner: owners.value(i),
+                    scheduled: statuses.value(i) == SCHEDULED_STATUS,
+                };
+                match definitions.last_mut() {
+                    // definitions are ordered by name and time
+                    Some(last) if last.request.name == name => *last = definition,
+                    _ => definitions.push(definition),
+                }
+            }
+        }
+        Ok(definitions)
+    }
+}
+
+/// System table recording the status of every run of scheduled scripts, the latest row of
+/// a script is its current status.
+pub struct ScheduleStatusTable {
+    catalog_manager: CatalogManagerRef,
+}
+
+impl ScheduleStatusTable {
+    pub async fn new(catalog_manager: CatalogManagerRef) -> Result<Self> {
+        let request = CreateTableRequest {
+            id: SCRIPT_SCHEDULES_TABLE_ID,
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+            schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+            table_name: SCRIPT_SCHEDULES_TABLE_NAME.to_string(),
+            desc: Some("Script schedules table".to_string()),
+            schema: Arc::new(build_schedules_schema()),
+            region_numbers: vec![0],
+            // name as primary key
+            primary_key_indices: vec![0],
+            create_if_not_exists: true,
+            table_options: HashMap::default(),
+        };
+
+        catalog_manager
+            .register_system_table(RegisterSystemTableRequest {
+                create_table_request: request,
+                open_hook: None,
+            })
+            .await
+            .context(RegisterSchedulesTableSnafu)?;
+
+        Ok(Self { catalog_manager })
+    }
+
+    /// Records the result of the run over the window ends at `window_end`.
+    pub async fn insert(
+        &self,
+        request: &ScheduleRequest,
+        window_end: i64,
+        result: &Result<usize>,
+    ) -> Result<()> {
+        let (window_start, window_end) = request.window(window_end);
+        let (status, last_error, affected_rows) = match result {
+            Ok(rows) => ("success", None, *rows as u64),
+            Err(e) => ("failed", Some(e.to_string()), 0),
+        };
+
+        let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(8);
+        columns_values.insert(
+            "name".to_string(),
+            Arc::new(StringVector::from(vec![request.name.as_str()])) as _,
+        );
+        columns_values.insert(
+            "target_table".to_string(),
+            Arc::new(StringVector::from(vec![request.target_table.as_str()])) as _,
+        );
+        columns_values.insert(
+            "status".to_string(),
+            Arc::new(StringVector::from(vec![status])) as _,
+        );
+        columns_values.insert(
+            "last_error".to_string(),
+            Arc::new(StringVector::from(vec![last_error.as_deref()])) as _,
+        );
+        columns_values.insert(
+            "affected_rows".to_string(),
+            Arc::new(UInt64Vector::from_slice(&[affected_rows])) as _,
+        );
+        columns_values.insert(
+            "window_start".to_string(),
+            Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
+                window_start,
+            )])) as _,
+        );
+        columns_values.insert(
+            "window_end".to_string(),
+            Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
+                window_end,
+            )])) as _,
+        );
+        // Timestamp in key part is the time of the run
+        columns_values.insert(
+            "timestamp".to_string(),
+            Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
+                util::current_time_millis(),
+            )])) as _,
+        );
+
+        let table = find_table(&self.catalog_manager, SCRIPT_SCHEDULES_TABLE_NAME)?;
+        let _ = table
+            .insert(InsertRequest {
+                catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: SCRIPT_SCHEDULES_TABLE_NAME.to_string(),
+                columns_values,
+            })
+            .await
+            .context(InsertTableSnafu {
+                table: SCRIPT_SCHEDULES_TABLE_NAME,
+            })?;
+
+        logging::debug!(
+            "Recorded run of scheduled script: name={}, status={}",
+            request.name,
+            status
+        );
+
+        Ok(())
+    }
+}
+
+/// Build script schedule definitions table
+fn build_definitions_schema() -> Schema {
+    let cols = vec![
+        ColumnSchema::new(
+            "name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "target_table".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "interval_millis".to_string(),
+            ConcreteDataType::int64_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "params".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "owner".to_string(),
+            ConcreteDataType::uint64_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "status".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "timestamp".to_string(),
+            ConcreteDataType::timestamp_millis_datatype(),
+            false,
+        )
+        .with_time_index(true),
+    ];
+
+    // Schema is always valid here
+    SchemaBuilder::try_from(cols).unwrap().build().unwrap()
+}
+
+/// Build script schedules table
+fn build_schedules_schema() -> Schema {
+    let cols = vec![
+        ColumnSchema::new(
+            "name".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "target_table".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "status".to_string(),
+            ConcreteDataType::string_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "last_error".to_string(),
+            ConcreteDataType::string_datatype(),
+            true,
+        ),
+        ColumnSchema::new(
+            "affected_rows".to_string(),
+            ConcreteDataType::uint64_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "window_start".to_string(),
+            ConcreteDataType::timestamp_millis_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "window_end".to_string(),
+            ConcreteDataType::timestamp_millis_datatype(),
+            false,
+        ),
+        ColumnSchema::new(
+            "timestamp".to_string(),
+            ConcreteDataType::timestamp_millis_datatype(),
+            false,
+        )
+        .with_time_index(true),
+    ];
+
+    // Schema is always valid here
+    SchemaBuilder::try_from(cols).unwrap().build().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_schedule_params() {
+        let request = ScheduleRequest {
+            name: "test".to_string(),
+            interval: Duration::from_secs(300),
+            target_table: "downsampled".to_string(),
+            params: HashMap::from([("host".to_string(), "host1".to_string())]),
+        };
+
+        assert_eq!((300_000, 600_000), request.window(600_000));
+        let params = request.params(600_000);
+        assert_eq!(3, params.len());
+        assert_eq!("host1", params["host"]);
+        assert_eq!("300000", params[WINDOW_START_PARAM]);
+        assert_eq!("600000", params[WINDOW_END_PARAM]);
+    }
+}
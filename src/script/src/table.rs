@@ -23,7 +23,8 @@ use common_recordbatch::util as record_util;
 use common_telemetry::logging;
 use common_time::timestamp::Timestamp;
 use common_time::util;
-use datatypes::arrow::array::Utf8Array;
+use datafusion_common::record_batch::RecordBatch as DfRecordBatch;
+use datatypes::arrow::array::{PrimitiveArray, Utf8Array};
 use datatypes::prelude::{ConcreteDataType, ScalarVector};
 use datatypes::schema::{ColumnSchema, Schema, SchemaBuilder};
 use datatypes::vectors::{StringVector, TimestampVector, VectorRef};
@@ -34,15 +35,38 @@ use table::requests::{CreateTableRequest, InsertRequest};
 
 use crate::error::{
     CastTypeSnafu, CollectRecordsSnafu, FindScriptSnafu, FindScriptsTableSnafu, InsertScriptSnafu,
-    RegisterScriptsTableSnafu, Result, ScriptNotFoundSnafu, ScriptsTableNotFoundSnafu,
+    RegisterScriptsTableSnafu, Result, ScriptNotFoundSnafu, ScriptVersionNotFoundSnafu,
+    ScriptsTableNotFoundSnafu,
 };
 
 pub const SCRIPTS_TABLE_NAME: &str = "scripts";
+/// Engine of the tombstone version inserted when deleting a script.
+const DELETED_SCRIPT_ENGINE: &str = "deleted";
+
+/// A version of a script in the scripts table, times are in milliseconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptRecord {
+    pub name: String,
+    pub script: String,
+    pub engine: String,
+    /// The version starts from 1 and increases by one on every update or deletion of the script.
+    pub version: i64,
+    pub gmt_created: i64,
+    pub gmt_modified: i64,
+}
+
+impl ScriptRecord {
+    fn is_deleted(&self) -> bool {
+        self.engine == DELETED_SCRIPT_ENGINE
+    }
+}
 
 pub struct ScriptsTable {
     catalog_manager: CatalogManagerRef,
     query_engine: QueryEngineRef,
     name: String,
+    /// Serializes the writes, so that no two versions of a script get the same version number.
+    write_lock: tokio::sync::Mutex<()>,
 }
 
 impl ScriptsTable {
@@ -83,10 +107,66 @@ impl ScriptsTable {
                 DEFAULT_SCHEMA_NAME,
                 SCRIPTS_TABLE_NAME,
             ),
+            write_lock: tokio::sync::Mutex::new(()),
         })
     }
 
-    pub async fn insert(&self, name: &str, script: &str) -> Result<()> {
+    /// Inserts a new version of the script, returns the version.
+    pub async fn insert(&self, name: &str, script: &str) -> Result<i64> {
+        let _guard = self.write_lock.lock().await;
+        let now = util::current_time_millis();
+        let last = self.find_last_record(name).await?;
+        let version = last.as_ref().map(|record| record.version + 1).unwrap_or(1);
+        // The creation time of a script is kept across its versions.
+        let gmt_created = last
+            .filter(|record| !record.is_deleted())
+            .map(|record| record.gmt_created)
+            .unwrap_or(now);
+        // TODO(dennis): we only supports python right now.
+        self.insert_version(name, script, "python", version, gmt_created, now)
+            .await?;
+
+        logging::info!(
+            "Inserted script: name={}, version={} into scripts table.",
+            name,
+            version
+        );
+
+        Ok(version)
+    }
+
+    /// Deletes the script by inserting a tombstone version, prior versions are kept.
+    pub async fn delete(&self, name: &str) -> Result<()> {
+        let _guard = self.write_lock.lock().await;
+        let latest = self
+            .find_last_record(name)
+            .await?
+            .filter(|record| !record.is_deleted())
+            .context(ScriptNotFoundSnafu { name })?;
+        self.insert_version(
+            name,
+            "",
+            DELETED_SCRIPT_ENGINE,
+            latest.version + 1,
+            latest.gmt_created,
+            util::current_time_millis(),
+        )
+        .await?;
+
+        logging::info!("Deleted script: name={} from scripts table.", name);
+
+        Ok(())
+    }
+
+    async fn insert_version(
+        &self,
+        name: &str,
+        script: &str,
+        engine: &str,
+        version: i64,
+        gmt_created: i64,
+        gmt_modified: i64,
+    ) -> Result<()> {
         let mut columns_values: HashMap<String, VectorRef> = HashMap::with_capacity(7);
         columns_values.insert(
             "name".to_string(),
@@ -96,26 +176,27 @@ impl ScriptsTable {
             "script".to_string(),
             Arc::new(StringVector::from(vec![script])) as _,
         );
-        // TODO(dennis): we only supports python right now.
         columns_values.insert(
             "engine".to_string(),
-            Arc::new(StringVector::from(vec!["python"])) as _,
+            Arc::new(StringVector::from(vec![engine])) as _,
         );
-        // Timestamp in key part is intentionally left to 0
+        // Timestamp in key part is the version of the script
         columns_values.insert(
             "timestamp".to_string(),
-            Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(0)])) as _,
+            Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
+                version,
+            )])) as _,
         );
         columns_values.insert(
             "gmt_created".to_string(),
             Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
-                util::current_time_millis(),
+                gmt_created,
             )])) as _,
         );
         columns_values.insert(
             "gmt_modified".to_string(),
             Arc::new(TimestampVector::from_slice(&[Timestamp::from_millis(
-                util::current_time_millis(),
+                gmt_modified,
             )])) as _,
         );
 
@@ -138,21 +219,90 @@ impl ScriptsTable {
             })
             .await
             .context(InsertScriptSnafu { name })?;
-
-        logging::info!("Inserted script: name={} into scripts table.", name);
-
         Ok(())
     }
 
     pub async fn find_script_by_name(&self, name: &str) -> Result<String> {
-        // FIXME(dennis): SQL injection
+        Ok(self.find_latest(name).await?.script)
+    }
+
+    /// Finds the latest version of the script, returns `ScriptNotFound` error if the script
+    /// doesn't exist or is deleted.
+    pub async fn find_latest(&self, name: &str) -> Result<ScriptRecord> {
+        self.find_last_record(name)
+            .await?
+            .filter(|record| !record.is_deleted())
+            .context(ScriptNotFoundSnafu { name })
+    }
+
+    /// Finds the record of the latest version of the script, including the tombstone.
+    async fn find_last_record(&self, name: &str) -> Result<Option<ScriptRecord>> {
+        Ok(self.find_records(Some(name), true).await?.pop())
+    }
+
+    /// Finds the specific version of the script.
+    pub async fn find_version(&self, name: &str, version: i64) -> Result<ScriptRecord> {
+        self.find_records(Some(name), false)
+            .await?
+            .into_iter()
+            .find(|record| record.version == version && !record.is_deleted())
+            .context(ScriptVersionNotFoundSnafu { name, version })
+    }
+
+    /// Lists all versions of the script ordered by version, deletions are excluded.
+    pub async fn list_versions(&self, name: &str) -> Result<Vec<ScriptRecord>> {
+        let records = self
+            .find_records(Some(name), false)
+            .await?
+            .into_iter()
+            .filter(|record| !record.is_deleted())
+            .collect::<Vec<_>>();
+        ensure!(!records.is_empty(), ScriptNotFoundSnafu { name });
+        Ok(records)
+    }
+
+    /// Lists the latest versions of all scripts not deleted, ordered by name.
+    pub async fn list_latest(&self) -> Result<Vec<ScriptRecord>> {
+        let mut latest: Vec<ScriptRecord> = Vec::new();
+        for record in self.find_records(None, false).await? {
+            match latest.last_mut() {
+                // records are ordered by name and version
+                Some(last) if last.name == record.name => *last = record,
+                _ => latest.push(record),
+            }
+        }
+        latest.retain(|record| !record.is_deleted());
+        Ok(latest)
+    }
+
+    /// Finds records of the script `name` or all scripts, ordered by name and version. Only the
+    /// record of the latest version is returned if `latest_only` is true.
+    async fn find_records(
+        &self,
+        name: Option<&str>,
+        latest_only: bool,
+    ) -> Result<Vec<ScriptRecord>> {
         // TODO(dennis): we use sql to find the script, the better way is use a function
         //               such as `find_record_by_primary_key` in table_engine.
-        let sql = format!("select script from {} where name='{}'", self.name(), name);
+        let condition = name
+            .map(|name| format!(" where name = '{}'", name.replace('\'', "''")))
+            .unwrap_or_default();
+        let order_by = if latest_only {
+            "order by timestamp desc limit 1"
+        } else {
+            "order by name, timestamp"
+        };
+        let sql = format!(
+            "select name, script, engine, timestamp, gmt_created, gmt_modified from {}{} {}",
+            self.name(),
+            condition,
+            order_by
+        );
+        let name = name.unwrap_or_default();
 
         let plan = self
             .query_engine
-            .sql_to_plan(&sql, Arc::new(QueryContext::new()))
+            .sql_to_plan(&sql, QueryContext::internal_arc())
             .context(FindScriptSnafu { name })?;
 
         let stream = match self
@@ -168,26 +318,28 @@ impl ScriptsTable {
             .await
             .context(CollectRecordsSnafu)?;
 
-        ensure!(!records.is_empty(), ScriptNotFoundSnafu { name });
-
-        assert_eq!(records.len(), 1);
-        assert_eq!(records[0].df_recordbatch.num_columns(), 1);
+        let mut scripts = Vec::new();
+        for record in records {
+            let record = &record.df_recordbatch;
+            let names = downcast_column::<Utf8Array<i32>>(record, 0)?;
+            let sources = downcast_column::<Utf8Array<i32>>(record, 1)?;
+            let engines = downcast_column::<Utf8Array<i32>>(record, 2)?;
+            let versions = downcast_column::<PrimitiveArray<i64>>(record, 3)?;
+            let gmt_created = downcast_column::<PrimitiveArray<i64>>(record, 4)?;
+            let gmt_modified = downcast_column::<PrimitiveArray<i64>>(record, 5)?;
 
-        let record = &records[0].df_recordbatch;
-
-        let script_column = record
-            .column(0)
-            .as_any()
-            .downcast_ref::<Utf8Array<i32>>()
-            .context(CastTypeSnafu {
-                msg: format!(
-                    "can't downcast {:?} array into utf8 array",
-                    record.column(0).data_type()
-                ),
-            })?;
-
-        assert_eq!(script_column.len(), 1);
-        Ok(script_column.value(0).to_string())
+            for i in 0..record.num_rows() {
+                scripts.push(ScriptRecord {
+                    name: names.value(i).to_string(),
+                    script: sources.value(i).to_string(),
+                    engine: engines.value(i).to_string(),
+                    version: versions.value(i),
+                    gmt_created: gmt_created.value(i),
+                    gmt_modified: gmt_modified.value(i),
+                });
+            }
+        }
+        Ok(scripts)
     }
 
     #[inline]
@@ -196,6 +348,20 @@ impl ScriptsTable {
     }
 }
 
+pub(crate) fn downcast_column<'a, T: 'static>(
+    record: &'a DfRecordBatch,
+    index: usize,
+) -> Result<&'a T> {
+    let column = record.column(index);
+    column.as_any().downcast_ref::<T>().context(CastTypeSnafu {
+        msg: format!(
+            "can't downcast {:?} array at column {}",
+            column.data_type(),
+            index
+        ),
+    })
+}
+
 /// Build scripts table
 fn build_scripts_schema() -> Schema {
     let cols = vec![
@@ -12,13 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-pub const DEFAULT_USERNAME: &str = "greptime";
-
 use std::sync::Arc;
 
+use common_catalog::privilege::SUPERUSER;
 use common_error::prelude::ErrorExt;
 use common_error::status_code::StatusCode;
-use snafu::{Backtrace, ErrorCompat, Snafu};
+use session::context::QueryContext;
+use snafu::{ensure, Backtrace, ErrorCompat, Snafu};
+
+pub const DEFAULT_USERNAME: &str = SUPERUSER;
 
 #[async_trait::async_trait]
 pub trait UserProvider: Send + Sync {
@@ -50,29 +52,75 @@ pub enum Password<'a> {
 #[derive(Clone, Debug)]
 pub struct UserInfo {
     username: String,
+    /// Catalog of the tenant the user belongs to, `None` if the user can access all catalogs.
+    catalog: Option<String>,
 }
 
 impl Default for UserInfo {
     fn default() -> Self {
         Self {
             username: DEFAULT_USERNAME.to_string(),
+            catalog: None,
         }
     }
 }
 
 impl UserInfo {
+    pub fn new(username: impl Into<String>) -> Self {
+        Self {
+            username: username.into(),
+            catalog: None,
+        }
+    }
+
+    /// Binds the user to the catalog of a tenant.
+    pub fn with_catalog(mut self, catalog: impl Into<String>) -> Self {
+        self.catalog = Some(catalog.into());
+        self
+    }
+
     pub fn user_name(&self) -> &str {
         &self.username
     }
 
-    #[cfg(test)]
-    pub fn new(username: impl Into<String>) -> Self {
-        Self {
-            username: username.into(),
+    pub fn catalog(&self) -> Option<&str> {
+        self.catalog.as_deref()
+    }
+
+    /// Binds the user to the session, a session of a user that belongs to a tenant can only
+    /// access the catalog of the tenant.
+    pub fn bind_to(&self, query_ctx: &QueryContext) {
+        query_ctx.set_current_user(&self.username);
+        if let Some(catalog) = &self.catalog {
+            query_ctx.set_tenant(catalog);
         }
     }
 }
 
+/// Splits the login name `<username>@<tenant>` sent by clients into the username and the
+/// tenant, the tenant is `None` if the login name has no `@`.
+pub fn parse_login_name(login_name: &str) -> (&str, Option<&str>) {
+    match login_name.rsplit_once('@') {
+        Some((username, tenant)) if !username.is_empty() && !tenant.is_empty() => {
+            (username, Some(tenant))
+        }
+        _ => (login_name, None),
+    }
+}
+
+/// Binds `user_info` to the tenant the user logs in to. Users already bound to a tenant by the
+/// user provider are not allowed to log in to another tenant.
+pub fn login_to_tenant(user_info: UserInfo, tenant: Option<&str>) -> Result<UserInfo, Error> {
+    match (user_info.catalog(), tenant) {
+        (Some(catalog), Some(tenant)) => {
+            ensure!(catalog == tenant, AccessDeniedSnafu { catalog: tenant });
+            Ok(user_info)
+        }
+        (None, Some(tenant)) => Ok(user_info.with_catalog(tenant)),
+        (_, None) => Ok(user_info),
+    }
+}
+
 #[derive(Debug, Snafu)]
 #[snafu(visibility(pub))]
 pub enum Error {
@@ -87,6 +135,12 @@ pub enum Error {
 
     #[snafu(display("Username and password does not match"))]
     UserPasswordMismatch { backtrace: Backtrace },
+
+    #[snafu(display("Access denied to catalog: {}", catalog))]
+    AccessDenied {
+        catalog: String,
+        backtrace: Backtrace,
+    },
 }
 
 impl ErrorExt for Error {
@@ -95,6 +149,7 @@ impl ErrorExt for Error {
             Error::UserNotFound { .. } => StatusCode::UserNotFound,
             Error::UnsupportedPasswordType { .. } => StatusCode::UnsupportedPasswordType,
             Error::UserPasswordMismatch { .. } => StatusCode::UserPasswordMismatch,
+            Error::AccessDenied { .. } => StatusCode::AccessDenied,
         }
     }
 
@@ -129,9 +184,13 @@ pub mod test {
                     Password::PlainText(password) => {
                         if username == "greptime" {
                             if password == "greptime" {
-                                return Ok(UserInfo {
-                                    username: "greptime".to_string(),
-                                });
+                                return Ok(UserInfo::new("greptime"));
+                            } else {
+                                return super::UserPasswordMismatchSnafu {}.fail();
+                            }
+                        } else if username == "tenant_user" {
+                            if password == "tenant_user" {
+                                return Ok(UserInfo::new("tenant_user").with_catalog("team_a"));
                             } else {
                                 return super::UserPasswordMismatchSnafu {}.fail();
                             }
@@ -151,10 +210,59 @@ pub mod test {
 
 #[cfg(test)]
 mod tests {
+    use session::context::QueryContext;
+
     use super::test::MockUserProvider;
-    use super::{Identity, Password, UserProvider};
+    use super::{login_to_tenant, parse_login_name, Identity, Password, UserInfo, UserProvider};
     use crate::auth;
 
+    #[test]
+    fn test_parse_login_name() {
+        assert_eq!(("greptime", None), parse_login_name("greptime"));
+        assert_eq!(
+            ("greptime", Some("team_a")),
+            parse_login_name("greptime@team_a")
+        );
+        assert_eq!(("a@b", Some("team_a")), parse_login_name("a@b@team_a"));
+        assert_eq!(("greptime@", None), parse_login_name("greptime@"));
+        assert_eq!(("@team_a", None), parse_login_name("@team_a"));
+    }
+
+    #[test]
+    fn test_login_to_tenant() {
+        let user_info = login_to_tenant(UserInfo::new("greptime"), None).unwrap();
+        assert!(user_info.catalog().is_none());
+
+        let user_info = login_to_tenant(UserInfo::new("greptime"), Some("team_a")).unwrap();
+        assert_eq!(Some("team_a"), user_info.catalog());
+
+        let tenant_user = UserInfo::new("tenant_user").with_catalog("team_a");
+        let user_info = login_to_tenant(tenant_user.clone(), Some("team_a")).unwrap();
+        assert_eq!(Some("team_a"), user_info.catalog());
+        let user_info = login_to_tenant(tenant_user.clone(), None).unwrap();
+        assert_eq!(Some("team_a"), user_info.catalog());
+        assert!(matches!(
+            login_to_tenant(tenant_user, Some("team_b")),
+            Err(auth::Error::AccessDenied { .. })
+        ));
+    }
+
+    #[test]
+    fn test_bind_user_info() {
+        let query_ctx = QueryContext::new();
+        UserInfo::new("greptime").bind_to(&query_ctx);
+        assert_eq!(Some("greptime".to_string()), query_ctx.current_user());
+        assert!(query_ctx.tenant().is_none());
+
+        let query_ctx = QueryContext::new();
+        UserInfo::new("tenant_user")
+            .with_catalog("team_a")
+            .bind_to(&query_ctx);
+        assert_eq!(Some("tenant_user".to_string()), query_ctx.current_user());
+        assert_eq!(Some("team_a".to_string()), query_ctx.tenant());
+        assert!(!query_ctx.is_catalog_accessible("greptime"));
+    }
+
     #[tokio::test]
     async fn test_auth_by_plain_text() {
         let user_provider = MockUserProvider {};
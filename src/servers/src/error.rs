@@ -53,6 +53,34 @@ pub enum Error {
         source: common_recordbatch::error::Error,
     },
 
+    #[snafu(display("Failed to encode recordbatch in Arrow IPC format, source: {}", source))]
+    EncodeArrow {
+        source: datatypes::arrow::error::ArrowError,
+    },
+
+    #[snafu(display("Failed to encode recordbatch in JSON format, source: {}", source))]
+    EncodeJson { source: serde_json::Error },
+
+    #[snafu(display("Query {} is cancelled", id))]
+    QueryCancelled { id: u64, backtrace: Backtrace },
+
+    #[snafu(display("Query {} exceeds the timeout {:?}", id, timeout))]
+    QueryTimeout {
+        id: u64,
+        timeout: std::time::Duration,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Unknown process id: {}", id))]
+    ProcessNotFound { id: u64, backtrace: Backtrace },
+
+    #[snafu(display("User {} is not allowed to kill process {}", user, id))]
+    KillDenied {
+        id: u64,
+        user: String,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Failed to start HTTP server, source: {}", source))]
     StartHttp { source: hyper::Error },
 
@@ -106,6 +134,33 @@ pub enum Error {
         source: BoxedError,
     },
 
+    #[snafu(display("Failed to list scripts, source: {}", source))]
+    ListScripts {
+        #[snafu(backtrace)]
+        source: BoxedError,
+    },
+
+    #[snafu(display("Failed to find script by name: {}, source: {}", name, source))]
+    FindScript {
+        name: String,
+        #[snafu(backtrace)]
+        source: BoxedError,
+    },
+
+    #[snafu(display("Failed to delete script by name: {}, source: {}", name, source))]
+    DeleteScript {
+        name: String,
+        #[snafu(backtrace)]
+        source: BoxedError,
+    },
+
+    #[snafu(display("Failed to schedule script by name: {}, source: {}", name, source))]
+    ScheduleScript {
+        name: String,
+        #[snafu(backtrace)]
+        source: BoxedError,
+    },
+
     #[snafu(display("Not supported: {}", feat))]
     NotSupported { feat: String },
 
@@ -127,6 +182,24 @@ pub enum Error {
         source: common_grpc::error::Error,
     },
 
+    #[snafu(display("Failed to decompress InfluxDB gzip request body, source: {}", source))]
+    DecompressInfluxdbRequest {
+        source: std::io::Error,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "InfluxDB request body is too large after decompression, limit: {} bytes",
+        limit
+    ))]
+    InfluxdbRequestTooLarge { limit: usize, backtrace: Backtrace },
+
+    #[snafu(display("Invalid InfluxDB request body, source: {}", source))]
+    InvalidInfluxdbRequest {
+        source: FromUtf8Error,
+        backtrace: Backtrace,
+    },
+
     #[snafu(display("Failed to convert time precision, name: {}", name))]
     TimePrecision { name: String, backtrace: Backtrace },
 
@@ -148,6 +221,12 @@ pub enum Error {
         backtrace: Backtrace,
     },
 
+    #[snafu(display("Failed to write OpenTSDB data points, source: {}", source))]
+    OpentsdbLinesWrite {
+        #[snafu(backtrace)]
+        source: common_grpc::error::Error,
+    },
+
     #[snafu(display(
         "Failed to put OpenTSDB data point: {:?}, source: {}",
         data_point,
@@ -246,6 +325,8 @@ impl ErrorExt for Error {
             | TokioIo { .. }
             | VectorConversion { .. }
             | CollectRecordbatch { .. }
+            | EncodeArrow { .. }
+            | EncodeJson { .. }
             | StartHttp { .. }
             | StartGrpc { .. }
             | AlreadyStarted { .. }
@@ -256,6 +337,10 @@ impl ErrorExt for Error {
 
             InsertScript { source, .. }
             | ExecuteScript { source, .. }
+            | ListScripts { source, .. }
+            | FindScript { source, .. }
+            | DeleteScript { source, .. }
+            | ScheduleScript { source, .. }
             | ExecuteQuery { source, .. }
             | ExecuteInsert { source, .. }
             | ExecuteAlter { source, .. }
@@ -264,6 +349,9 @@ impl ErrorExt for Error {
             NotSupported { .. }
             | InvalidQuery { .. }
             | InfluxdbLineProtocol { .. }
+            | DecompressInfluxdbRequest { .. }
+            | InfluxdbRequestTooLarge { .. }
+            | InvalidInfluxdbRequest { .. }
             | ConnResetByPeer { .. }
             | InvalidOpentsdbLine { .. }
             | InvalidOpentsdbJsonRequest { .. }
@@ -271,9 +359,16 @@ impl ErrorExt for Error {
             | DecompressPromRemoteRequest { .. }
             | InvalidPromRemoteRequest { .. }
             | DecodeRegionNumber { .. }
+            | ProcessNotFound { .. }
             | TimePrecision { .. } => StatusCode::InvalidArguments,
 
-            InfluxdbLinesWrite { source, .. } => source.status_code(),
+            KillDenied { .. } => StatusCode::AccessDenied,
+            QueryCancelled { .. } => StatusCode::Cancelled,
+            QueryTimeout { .. } => StatusCode::DeadlineExceeded,
+
+            InfluxdbLinesWrite { source, .. } | OpentsdbLinesWrite { source, .. } => {
+                source.status_code()
+            }
             Hyper { .. } => StatusCode::Unknown,
             TlsRequired { .. } => StatusCode::Unknown,
             StartFrontend { source, .. } => source.status_code(),
@@ -314,8 +409,12 @@ impl IntoResponse for Error {
         let (status, error_message) = match self {
             Error::InfluxdbLineProtocol { .. }
             | Error::InfluxdbLinesWrite { .. }
+            | Error::DecompressInfluxdbRequest { .. }
+            | Error::InfluxdbRequestTooLarge { .. }
+            | Error::InvalidInfluxdbRequest { .. }
             | Error::InvalidOpentsdbLine { .. }
             | Error::InvalidOpentsdbJsonRequest { .. }
+            | Error::OpentsdbLinesWrite { .. }
             | Error::DecodePromRemoteRequest { .. }
             | Error::DecompressPromRemoteRequest { .. }
             | Error::InvalidPromRemoteRequest { .. }
@@ -19,9 +19,11 @@ use std::sync::Arc;
 
 use api::v1::{greptime_server, BatchRequest, BatchResponse};
 use async_trait::async_trait;
+use common_grpc::tls::TlsOption;
 use common_runtime::Runtime;
-use common_telemetry::logging::info;
+use common_telemetry::logging::{error, info};
 use futures::FutureExt;
+use session::context::QueryContext;
 use snafu::{ensure, ResultExt};
 use tokio::net::TcpListener;
 use tokio::sync::oneshot::{self, Sender};
@@ -29,16 +31,25 @@ use tokio::sync::Mutex;
 use tokio_stream::wrappers::TcpListenerStream;
 use tonic::{Request, Response, Status};
 
+use crate::auth::{
+    login_to_tenant, parse_login_name, Identity, Password, UserInfo, UserProviderRef,
+};
 use crate::error::{self, AlreadyStartedSnafu, Result, StartGrpcSnafu, TcpBindSnafu};
 use crate::grpc::handler::BatchHandler;
+use crate::http::authorize::decode_auth_header;
 use crate::query_handler::{GrpcAdminHandlerRef, GrpcQueryHandlerRef};
 use crate::server::Server;
 
+/// Key of the gRPC metadata carrying the credential of the request.
+pub const AUTHORIZATION_METADATA: &str = "authorization";
+
 pub struct GrpcServer {
     query_handler: GrpcQueryHandlerRef,
     admin_handler: GrpcAdminHandlerRef,
     shutdown_tx: Mutex<Option<Sender<()>>>,
     runtime: Arc<Runtime>,
+    tls: Arc<TlsOption>,
+    user_provider: Option<UserProviderRef>,
 }
 
 impl GrpcServer {
@@ -46,12 +57,16 @@ impl GrpcServer {
         query_handler: GrpcQueryHandlerRef,
         admin_handler: GrpcAdminHandlerRef,
         runtime: Arc<Runtime>,
+        tls: Arc<TlsOption>,
+        user_provider: Option<UserProviderRef>,
     ) -> Self {
         Self {
             query_handler,
             admin_handler,
             shutdown_tx: Mutex::new(None),
             runtime,
+            tls,
+            user_provider,
         }
     }
 
@@ -62,6 +77,7 @@ impl GrpcServer {
                 self.admin_handler.clone(),
                 self.runtime.clone(),
             ),
+            user_provider: self.user_provider.clone(),
         };
         greptime_server::GreptimeServer::new(service)
     }
@@ -69,6 +85,7 @@ impl GrpcServer {
 
 pub struct GrpcService {
     handler: BatchHandler,
+    user_provider: Option<UserProviderRef>,
 }
 
 #[tonic::async_trait]
@@ -77,12 +94,52 @@ impl greptime_server::Greptime for GrpcService {
         &self,
         req: Request<BatchRequest>,
     ) -> std::result::Result<Response<BatchResponse>, Status> {
+        let query_ctx = QueryContext::arc();
+        authenticate(self.user_provider.as_ref(), &req)
+            .await?
+            .bind_to(&query_ctx);
         let req = req.into_inner();
-        let res = self.handler.batch(req).await?;
+        let res = self.handler.batch(req, query_ctx).await?;
         Ok(Response::new(res))
     }
 }
 
+/// Authenticates the request by its `authorization` metadata, which carries the credential
+/// like the `Authorization` header of HTTP, e.g. `Basic <base64 of username:password>`.
+async fn authenticate<T>(
+    user_provider: Option<&UserProviderRef>,
+    req: &Request<T>,
+) -> std::result::Result<UserInfo, Status> {
+    let user_provider = match user_provider {
+        Some(user_provider) => user_provider,
+        None => return Ok(UserInfo::default()),
+    };
+
+    let auth_header = req
+        .metadata()
+        .get(AUTHORIZATION_METADATA)
+        .ok_or_else(|| Status::unauthenticated("authorization metadata not found"))?
+        .to_str()
+        .map_err(|e| Status::unauthenticated(e.to_string()))?;
+    let (username, password) = decode_auth_header(auth_header).map_err(|e| {
+        error!("failed to decode gRPC authorization, err: {:?}", e);
+        Status::unauthenticated(e.to_string())
+    })?;
+    // The tenant can be chosen by logging in as `<username>@<tenant>`.
+    let (username, tenant) = parse_login_name(&username);
+    user_provider
+        .auth(
+            Identity::UserId(username, None),
+            Password::PlainText(&password),
+        )
+        .await
+        .and_then(|user_info| login_to_tenant(user_info, tenant))
+        .map_err(|e| {
+            error!("failed to auth gRPC request, err: {:?}", e);
+            Status::unauthenticated(e.to_string())
+        })
+}
+
 #[async_trait]
 impl Server for GrpcServer {
     async fn shutdown(&self) -> Result<()> {
@@ -98,6 +155,11 @@ impl Server for GrpcServer {
     }
 
     async fn start(&self, addr: SocketAddr) -> Result<SocketAddr> {
+        let mut builder = tonic::transport::Server::builder();
+        if let Some(tls_config) = self.tls.setup_grpc()? {
+            builder = builder.tls_config(tls_config).context(StartGrpcSnafu)?;
+        }
+
         let (tx, rx) = oneshot::channel();
         let (listener, addr) = {
             let mut shutdown_tx = self.shutdown_tx.lock().await;
@@ -124,7 +186,7 @@ impl Server for GrpcServer {
             .context(error::GrpcReflectionServiceSnafu)?;
 
         // Would block to serve requests.
-        tonic::transport::Server::builder()
+        builder
             .add_service(self.create_service())
             .add_service(reflection_service)
             .serve_with_incoming_shutdown(TcpListenerStream::new(listener), rx.map(drop))
@@ -134,3 +196,57 @@ impl Server for GrpcServer {
         Ok(addr)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::test::MockUserProvider;
+    use crate::auth::DEFAULT_USERNAME;
+
+    fn request_with_auth(auth_header: Option<&str>) -> Request<()> {
+        let mut req = Request::new(());
+        if let Some(auth_header) = auth_header {
+            req.metadata_mut()
+                .insert(AUTHORIZATION_METADATA, auth_header.parse().unwrap());
+        }
+        req
+    }
+
+    #[tokio::test]
+    async fn test_authenticate() {
+        let user_info = authenticate(None, &request_with_auth(None)).await.unwrap();
+        assert_eq!(DEFAULT_USERNAME, user_info.user_name());
+
+        let user_provider: UserProviderRef = Arc::new(MockUserProvider {});
+        let user_provider = Some(&user_provider);
+        // base64encode("greptime:greptime") == "Z3JlcHRpbWU6Z3JlcHRpbWU="
+        let user_info = authenticate(
+            user_provider,
+            &request_with_auth(Some("Basic Z3JlcHRpbWU6Z3JlcHRpbWU=")),
+        )
+        .await
+        .unwrap();
+        assert_eq!("greptime", user_info.user_name());
+        assert!(user_info.catalog().is_none());
+
+        let user_info = authenticate(
+            user_provider,
+            &request_with_auth(Some("Token greptime@team_a:greptime")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(Some("team_a"), user_info.catalog());
+
+        for auth_header in [
+            None,
+            Some("Basic invalid"),
+            Some("Token greptime:wrong_password"),
+            Some("Token tenant_user@team_b:tenant_user"),
+        ] {
+            let status = authenticate(user_provider, &request_with_auth(auth_header))
+                .await
+                .unwrap_err();
+            assert_eq!(tonic::Code::Unauthenticated, status.code());
+        }
+    }
+}
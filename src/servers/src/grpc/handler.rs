@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use api::v1::{AdminResponse, BatchRequest, BatchResponse, DatabaseResponse};
 use common_runtime::Runtime;
+use session::context::QueryContextRef;
 use tokio::sync::oneshot;
 
 use crate::error::Result;
@@ -41,7 +42,11 @@ impl BatchHandler {
         }
     }
 
-    pub async fn batch(&self, batch_req: BatchRequest) -> Result<BatchResponse> {
+    pub async fn batch(
+        &self,
+        batch_req: BatchRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<BatchResponse> {
         let (tx, rx) = oneshot::channel();
         let query_handler = self.query_handler.clone();
         let admin_handler = self.admin_handler.clone();
@@ -65,7 +70,7 @@ impl BatchHandler {
                 db_resp.results.reserve(db_req.exprs.len());
 
                 for obj_expr in db_req.exprs {
-                    let object_resp = query_handler.do_query(obj_expr).await?;
+                    let object_resp = query_handler.do_query(obj_expr, query_ctx.clone()).await?;
 
                     db_resp.results.push(object_resp);
                 }
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod authorize;
+pub(crate) mod authorize;
+pub mod format;
 pub mod handler;
 pub mod influxdb;
 pub mod opentsdb;
@@ -32,26 +33,36 @@ use axum::response::{Html, Json};
 use axum::{routing, BoxError, Extension, Router};
 use common_error::prelude::ErrorExt;
 use common_error::status_code::StatusCode;
+use common_grpc::tls::TlsOption;
 use common_query::Output;
 use common_recordbatch::{util, RecordBatch};
-use common_telemetry::logging::info;
+use common_telemetry::logging::{error, info};
+use common_time::TimeZone;
 use datatypes::data_type::DataType;
-use futures::FutureExt;
+use futures::{FutureExt, Stream, StreamExt};
+use hyper::server::accept;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use snafu::{ensure, ResultExt};
+use tokio::net::{TcpListener, TcpStream};
 use tokio::sync::oneshot::{self, Sender};
 use tokio::sync::Mutex;
+use tokio_rustls::server::TlsStream;
+use tokio_rustls::TlsAcceptor;
+use tokio_stream::wrappers::TcpListenerStream;
 use tower::timeout::TimeoutLayer;
 use tower::ServiceBuilder;
 use tower_http::auth::AsyncRequireAuthorizationLayer;
 use tower_http::trace::TraceLayer;
 
 use self::authorize::HttpAuth;
-use self::influxdb::influxdb_write;
+use self::influxdb::{
+    influxdb_health, influxdb_ping, influxdb_query, influxdb_write, influxdb_write_v2,
+};
 use crate::auth::UserProviderRef;
-use crate::error::{AlreadyStartedSnafu, Result, StartHttpSnafu};
+use crate::error::{AlreadyStartedSnafu, Result, StartHttpSnafu, TcpBindSnafu};
+use crate::http::script::ScriptInfo;
 use crate::query_handler::{
     InfluxdbLineProtocolHandlerRef, OpentsdbProtocolHandlerRef, PrometheusProtocolHandlerRef,
     ScriptHandlerRef, SqlQueryHandlerRef,
@@ -59,6 +70,8 @@ use crate::query_handler::{
 use crate::server::Server;
 
 const HTTP_API_VERSION: &str = "v1";
+const TLS_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+const TLS_HANDSHAKE_CONCURRENCY: usize = 64;
 
 pub struct HttpServer {
     sql_handler: SqlQueryHandlerRef,
@@ -76,6 +89,8 @@ pub struct HttpOptions {
     pub addr: String,
     #[serde(with = "humantime_serde")]
     pub timeout: Duration,
+    #[serde(default = "Default::default")]
+    pub tls: Arc<TlsOption>,
 }
 
 impl Default for HttpOptions {
@@ -83,6 +98,7 @@ impl Default for HttpOptions {
         Self {
             addr: "127.0.0.1:4000".to_string(),
             timeout: Duration::from_secs(30),
+            tls: Arc::new(TlsOption::default()),
         }
     }
 }
@@ -142,10 +158,20 @@ impl HttpRecordsOutput {
 impl TryFrom<Vec<RecordBatch>> for HttpRecordsOutput {
     type Error = String;
 
-    // TODO(sunng87): use schema from recordstreams when #366 fixed
     fn try_from(
         recordbatches: Vec<RecordBatch>,
     ) -> std::result::Result<HttpRecordsOutput, Self::Error> {
+        HttpRecordsOutput::try_new(recordbatches, None)
+    }
+}
+
+impl HttpRecordsOutput {
+    /// Converts the recordbatches to JSON rows, timestamps are rendered in `time_zone`.
+    // TODO(sunng87): use schema from recordstreams when #366 fixed
+    fn try_new(
+        recordbatches: Vec<RecordBatch>,
+        time_zone: Option<&TimeZone>,
+    ) -> std::result::Result<HttpRecordsOutput, String> {
         if recordbatches.is_empty() {
             Ok(HttpRecordsOutput {
                 schema: None,
@@ -174,7 +200,7 @@ impl TryFrom<Vec<RecordBatch>> for HttpRecordsOutput {
                     let row = row.map_err(|e| e.to_string())?;
                     let value_row = row
                         .into_iter()
-                        .map(|f| Value::try_from(f).map_err(|err| err.to_string()))
+                        .map(|f| format::value_to_json(f, time_zone).map_err(|err| err.to_string()))
                         .collect::<std::result::Result<Vec<Value>, _>>()?;
 
                     rows.push(value_row);
@@ -194,6 +220,7 @@ impl TryFrom<Vec<RecordBatch>> for HttpRecordsOutput {
 pub enum JsonOutput {
     AffectedRows(usize),
     Records(HttpRecordsOutput),
+    Scripts(Vec<ScriptInfo>),
 }
 
 #[derive(Serialize, Deserialize, Debug, JsonSchema)]
@@ -231,21 +258,21 @@ impl JsonResponse {
         self
     }
 
-    /// Create a json response from query result
-    async fn from_output(output: Result<Output>) -> Self {
+    /// Create a json response from query result, timestamps are rendered in `time_zone`.
+    async fn from_output(output: Result<Output>, time_zone: Option<&TimeZone>) -> Self {
         match output {
             Ok(Output::AffectedRows(rows)) => {
                 Self::with_output(Some(vec![JsonOutput::AffectedRows(rows)]))
             }
             Ok(Output::Stream(stream)) => match util::collect(stream).await {
-                Ok(rows) => match HttpRecordsOutput::try_from(rows) {
+                Ok(rows) => match HttpRecordsOutput::try_new(rows, time_zone) {
                     Ok(rows) => Self::with_output(Some(vec![JsonOutput::Records(rows)])),
                     Err(err) => Self::with_error(err, StatusCode::Internal),
                 },
                 Err(e) => Self::with_error(format!("Recordbatch error: {}", e), e.status_code()),
             },
             Ok(Output::RecordBatches(recordbatches)) => {
-                match HttpRecordsOutput::try_from(recordbatches.take()) {
+                match HttpRecordsOutput::try_new(recordbatches.take(), time_zone) {
                     Ok(rows) => Self::with_output(Some(vec![JsonOutput::Records(rows)])),
                     Err(err) => Self::with_error(err, StatusCode::Internal),
                 }
@@ -419,7 +446,16 @@ impl HttpServer {
                 apirouting::get_with(handler::sql, handler::sql_docs)
                     .post_with(handler::sql, handler::sql_docs),
             )
-            .api_route("/scripts", apirouting::post(script::scripts))
+            .api_route(
+                "/scripts",
+                apirouting::post(script::scripts)
+                    .get(script::get_scripts)
+                    .delete(script::delete_script),
+            )
+            .api_route(
+                "/scripts/versions",
+                apirouting::get(script::script_versions),
+            )
             .api_route("/run-script", apirouting::post(script::run_script))
             .route("/private/api.json", apirouting::get(serve_api))
             .route("/private/docs", apirouting::get(serve_docs))
@@ -436,12 +472,18 @@ impl HttpServer {
     fn route_influxdb<S>(&self, influxdb_handler: InfluxdbLineProtocolHandlerRef) -> Router<S> {
         Router::new()
             .route("/write", routing::post(influxdb_write))
+            .route("/api/v2/write", routing::post(influxdb_write_v2))
+            .route("/query", routing::get(influxdb_query).post(influxdb_query))
+            .route("/ping", routing::get(influxdb_ping).head(influxdb_ping))
+            .route("/health", routing::get(influxdb_health))
             .with_state(influxdb_handler)
     }
 
     fn route_opentsdb<S>(&self, opentsdb_handler: OpentsdbProtocolHandlerRef) -> Router<S> {
         Router::new()
             .route("/api/put", routing::post(opentsdb::put))
+            .route("/api/query", routing::post(opentsdb::query))
+            .route("/api/suggest", routing::get(opentsdb::suggest))
             .with_state(opentsdb_handler)
     }
 }
@@ -461,31 +503,84 @@ impl Server for HttpServer {
     }
 
     async fn start(&self, listening: SocketAddr) -> Result<SocketAddr> {
+        let tls_acceptor = self
+            .options
+            .tls
+            .setup()?
+            .map(|server_conf| TlsAcceptor::from(Arc::new(server_conf)));
+
         let (tx, rx) = oneshot::channel();
-        let server = {
+        let listener = {
             let mut shutdown_tx = self.shutdown_tx.lock().await;
             ensure!(
                 shutdown_tx.is_none(),
                 AlreadyStartedSnafu { server: "HTTP" }
             );
 
-            let app = self.make_app();
-            let server = axum::Server::bind(&listening).serve(app.into_make_service());
+            let listener = TcpListener::bind(listening)
+                .await
+                .context(TcpBindSnafu { addr: listening })?;
 
             *shutdown_tx = Some(tx);
 
-            server
+            listener
         };
-        let listening = server.local_addr();
+        let listening = listener
+            .local_addr()
+            .context(TcpBindSnafu { addr: listening })?;
         info!("HTTP server is bound to {}", listening);
 
-        let graceful = server.with_graceful_shutdown(rx.map(drop));
-        graceful.await.context(StartHttpSnafu)?;
+        let app = self.make_app().into_make_service();
+        let shutdown = rx.map(drop);
+        match tls_acceptor {
+            Some(tls_acceptor) => {
+                let incoming = accept::from_stream(tls_incoming(listener, tls_acceptor));
+                axum::Server::builder(incoming)
+                    .serve(app)
+                    .with_graceful_shutdown(shutdown)
+                    .await
+            }
+            None => {
+                axum::Server::from_tcp(listener.into_std()?)
+                    .context(StartHttpSnafu)?
+                    .serve(app)
+                    .with_graceful_shutdown(shutdown)
+                    .await
+            }
+        }
+        .context(StartHttpSnafu)?;
 
         Ok(listening)
     }
 }
 
+/// Accepts TLS connections, connections failed in handshake are dropped.
+fn tls_incoming(
+    listener: TcpListener,
+    tls_acceptor: TlsAcceptor,
+) -> impl Stream<Item = std::io::Result<TlsStream<TcpStream>>> {
+    TcpListenerStream::new(listener)
+        .map(move |stream| {
+            let tls_acceptor = tls_acceptor.clone();
+            async move {
+                let handshake = tls_acceptor.accept(stream?);
+                tokio::time::timeout(TLS_HANDSHAKE_TIMEOUT, handshake)
+                    .await
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::TimedOut, e))?
+            }
+        })
+        .buffer_unordered(TLS_HANDSHAKE_CONCURRENCY)
+        .filter_map(|stream| async move {
+            match stream {
+                Ok(stream) => Some(Ok(stream)),
+                Err(e) => {
+                    error!("Failed to accept TLS connection: {}", e);
+                    None
+                }
+            }
+        })
+}
+
 /// handle error middleware
 async fn handle_error(err: BoxError) -> Json<JsonResponse> {
     Json(JsonResponse::with_error(
@@ -582,7 +677,8 @@ mod test {
         let recordbatch = RecordBatch::new(schema.clone(), columns).unwrap();
         let recordbatches = RecordBatches::try_new(schema.clone(), vec![recordbatch]).unwrap();
 
-        let json_resp = JsonResponse::from_output(Ok(Output::RecordBatches(recordbatches))).await;
+        let json_resp =
+            JsonResponse::from_output(Ok(Output::RecordBatches(recordbatches)), None).await;
 
         let json_output = &json_resp.output.unwrap()[0];
         if let JsonOutput::Records(r) = json_output {
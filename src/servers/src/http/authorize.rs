@@ -22,7 +22,7 @@ use http_body::Body;
 use snafu::{OptionExt, ResultExt};
 use tower_http::auth::AsyncAuthorizeRequest;
 
-use crate::auth::{Identity, UserInfo, UserProviderRef};
+use crate::auth::{login_to_tenant, parse_login_name, Identity, UserInfo, UserProviderRef};
 use crate::error::{self, Result};
 
 pub struct HttpAuth<RespBody> {
@@ -75,31 +75,34 @@ where
                 }
             };
 
-            match scheme {
-                AuthScheme::Basic => {
-                    let (username, password) = match decode_basic(credential) {
-                        Ok(basic_auth) => basic_auth,
-                        Err(e) => {
-                            error!("failed to decode basic authorize, err: {:?}", e);
-                            return Err(unauthorized_resp());
-                        }
-                    };
-                    match user_provider
-                        .auth(
-                            Identity::UserId(&username, None),
-                            crate::auth::Password::PlainText(&password),
-                        )
-                        .await
-                    {
-                        Ok(user_info) => {
-                            request.extensions_mut().insert(user_info);
-                            Ok(request)
-                        }
-                        Err(e) => {
-                            error!("failed to auth, err: {:?}", e);
-                            Err(unauthorized_resp())
-                        }
-                    }
+            let credential = match scheme {
+                AuthScheme::Basic => decode_basic(credential),
+                AuthScheme::Token => decode_token(credential),
+            };
+            let (username, password) = match credential {
+                Ok(credential) => credential,
+                Err(e) => {
+                    error!("failed to decode {:?} authorize, err: {:?}", scheme, e);
+                    return Err(unauthorized_resp());
+                }
+            };
+            // The tenant can be chosen by logging in as `<username>@<tenant>`.
+            let (username, tenant) = parse_login_name(&username);
+            match user_provider
+                .auth(
+                    Identity::UserId(username, None),
+                    crate::auth::Password::PlainText(&password),
+                )
+                .await
+                .and_then(|user_info| login_to_tenant(user_info, tenant))
+            {
+                Ok(user_info) => {
+                    request.extensions_mut().insert(user_info);
+                    Ok(request)
+                }
+                Err(e) => {
+                    error!("failed to auth, err: {:?}", e);
+                    Err(unauthorized_resp())
                 }
             }
         })
@@ -118,6 +121,8 @@ where
 #[derive(Debug)]
 pub enum AuthScheme {
     Basic,
+    /// Token scheme used by InfluxDB v2 clients, the token is `username:password`.
+    Token,
 }
 
 impl TryFrom<&str> for AuthScheme {
@@ -126,6 +131,7 @@ impl TryFrom<&str> for AuthScheme {
     fn try_from(value: &str) -> Result<Self> {
         match value.to_lowercase().as_str() {
             "basic" => Ok(AuthScheme::Basic),
+            "token" => Ok(AuthScheme::Token),
             other => error::UnsupportedAuthSchemeSnafu { name: other }.fail(),
         }
     }
@@ -140,7 +146,10 @@ fn auth_header<B>(req: &Request<B>) -> Result<(AuthScheme, Credential)> {
         .context(error::NotFoundAuthHeaderSnafu)?
         .to_str()
         .context(error::InvisibleASCIISnafu)?;
+    parse_auth_header(auth_header)
+}
 
+fn parse_auth_header(auth_header: &str) -> Result<(AuthScheme, Credential)> {
     let (auth_scheme, encoded_credentials) = auth_header
         .split_once(' ')
         .context(error::InvalidAuthorizationHeaderSnafu)?;
@@ -155,6 +164,16 @@ fn auth_header<B>(req: &Request<B>) -> Result<(AuthScheme, Credential)> {
 type Username = String;
 type Password = String;
 
+/// Decodes the username and password from the value of an authorization header, like
+/// `Basic <base64 of username:password>`.
+pub(crate) fn decode_auth_header(auth_header: &str) -> Result<(Username, Password)> {
+    let (scheme, credential) = parse_auth_header(auth_header)?;
+    match scheme {
+        AuthScheme::Basic => decode_basic(credential),
+        AuthScheme::Token => decode_token(credential),
+    }
+}
+
 fn decode_basic(credential: Credential) -> Result<(Username, Password)> {
     let decoded = base64::decode(credential).context(error::InvalidBase64ValueSnafu)?;
     let as_utf8 = String::from_utf8(decoded).context(error::InvalidUtf8ValueSnafu)?;
@@ -166,6 +185,13 @@ fn decode_basic(credential: Credential) -> Result<(Username, Password)> {
     error::InvalidAuthorizationHeaderSnafu {}.fail()
 }
 
+fn decode_token(credential: Credential) -> Result<(Username, Password)> {
+    credential
+        .split_once(':')
+        .map(|(user_id, password)| (user_id.to_string(), password.to_string()))
+        .context(error::InvalidAuthorizationHeaderSnafu)
+}
+
 #[cfg(test)]
 mod tests {
     use std::marker::PhantomData;
@@ -176,7 +202,7 @@ mod tests {
     use hyper::Request;
     use tower_http::auth::AsyncAuthorizeRequest;
 
-    use super::{auth_header, decode_basic, AuthScheme, HttpAuth};
+    use super::{auth_header, decode_basic, decode_token, AuthScheme, HttpAuth};
     use crate::auth::test::MockUserProvider;
     use crate::auth::{UserInfo, UserProvider};
     use crate::error;
@@ -218,6 +244,12 @@ mod tests {
         let wrong_req = mock_http_request("Basic dXNlcm5hbWU6cGFzc3dvcmQ=").unwrap();
         let auth_res = http_auth.authorize(wrong_req).await;
         assert!(auth_res.is_err());
+
+        let req = mock_http_request("Token greptime:greptime").unwrap();
+        assert!(http_auth.authorize(req).await.is_ok());
+
+        let wrong_req = mock_http_request("Token username:password").unwrap();
+        assert!(http_auth.authorize(wrong_req).await.is_err());
     }
 
     #[test]
@@ -233,6 +265,19 @@ mod tests {
         matches!(result.err(), Some(error::Error::InvalidBase64Value { .. }));
     }
 
+    #[test]
+    fn test_decode_token() {
+        let (username, pwd) = decode_token("username:password").unwrap();
+        assert_eq!("username", username);
+        assert_eq!("password", pwd);
+
+        let result = decode_token("password");
+        matches!(
+            result.err(),
+            Some(error::Error::InvalidAuthorizationHeader { .. })
+        );
+    }
+
     #[test]
     fn test_try_into_auth_scheme() {
         let auth_scheme_str = "basic";
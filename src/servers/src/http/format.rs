@@ -0,0 +1,417 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Output formats of the HTTP SQL API besides the default JSON response. Records in these
+//! formats are streamed to the client batch by batch.
+
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use axum::body::StreamBody;
+use axum::http::header;
+use axum::response::{IntoResponse, Response};
+use bytes::Bytes;
+use common_recordbatch::{RecordBatch, SendableRecordBatchStream};
+use common_time::TimeZone;
+use datatypes::arrow::chunk::Chunk;
+use datatypes::arrow::io::ipc::write::{StreamWriter, WriteOptions};
+use datatypes::data_type::DataType;
+use datatypes::schema::SchemaRef;
+use datatypes::value::Value;
+use datatypes::vectors::Helper;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
+use serde_json::json;
+use snafu::ResultExt;
+
+use crate::error::{
+    CollectRecordbatchSnafu, EncodeArrowSnafu, EncodeJsonSnafu, Result, VectorConversionSnafu,
+};
+
+pub const CSV_CONTENT_TYPE: &str = "text/csv";
+pub const TSV_CONTENT_TYPE: &str = "text/tab-separated-values";
+pub const ARROW_CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+pub const COLUMNAR_CONTENT_TYPE: &str = "application/x-ndjson";
+pub const JSON_CONTENT_TYPE: &str = "application/json";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResponseFormat {
+    /// The JSON response with all records.
+    #[default]
+    Json,
+    Csv,
+    Tsv,
+    /// Arrow IPC streaming format.
+    Arrow,
+    /// Newline delimited JSON, each line holds the columns of a record batch.
+    Columnar,
+}
+
+impl ResponseFormat {
+    pub fn parse(format: &str) -> Option<Self> {
+        match format.to_ascii_lowercase().as_str() {
+            "json" => Some(ResponseFormat::Json),
+            "csv" => Some(ResponseFormat::Csv),
+            "tsv" => Some(ResponseFormat::Tsv),
+            "arrow" => Some(ResponseFormat::Arrow),
+            "columnar" => Some(ResponseFormat::Columnar),
+            _ => None,
+        }
+    }
+
+    /// Returns the first format in the `Accept` header that is supported.
+    pub fn from_accept(accept: &str) -> Option<Self> {
+        accept.split(',').find_map(|media_type| {
+            let media_type = media_type.split(';').next().unwrap_or_default().trim();
+            match media_type {
+                JSON_CONTENT_TYPE => Some(ResponseFormat::Json),
+                CSV_CONTENT_TYPE => Some(ResponseFormat::Csv),
+                TSV_CONTENT_TYPE => Some(ResponseFormat::Tsv),
+                ARROW_CONTENT_TYPE => Some(ResponseFormat::Arrow),
+                COLUMNAR_CONTENT_TYPE => Some(ResponseFormat::Columnar),
+                _ => None,
+            }
+        })
+    }
+
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ResponseFormat::Json => JSON_CONTENT_TYPE,
+            ResponseFormat::Csv => CSV_CONTENT_TYPE,
+            ResponseFormat::Tsv => TSV_CONTENT_TYPE,
+            ResponseFormat::Arrow => ARROW_CONTENT_TYPE,
+            ResponseFormat::Columnar => COLUMNAR_CONTENT_TYPE,
+        }
+    }
+}
+
+/// Streams the records in the format, which must not be [ResponseFormat::Json]. Timestamps
+/// in textual formats are rendered in `time_zone`, or in UTC if it's `None`.
+pub fn stream_response(
+    format: ResponseFormat,
+    stream: SendableRecordBatchStream,
+    time_zone: Option<TimeZone>,
+) -> Response {
+    let schema = stream.schema();
+    let body = match format {
+        ResponseFormat::Csv | ResponseFormat::Tsv => {
+            let header_line = stream::once(futures::future::ready(Ok(encode_delimited_header(
+                &schema, format,
+            ))));
+            header_line
+                .chain(stream.map(move |batch| {
+                    encode_delimited(
+                        &batch.context(CollectRecordbatchSnafu)?,
+                        format,
+                        time_zone.as_ref(),
+                    )
+                }))
+                .boxed()
+        }
+        ResponseFormat::Arrow => arrow_stream(schema, stream),
+        ResponseFormat::Columnar => stream
+            .map(move |batch| {
+                encode_columnar(&batch.context(CollectRecordbatchSnafu)?, time_zone.as_ref())
+            })
+            .boxed(),
+        ResponseFormat::Json => unreachable!("JSON response is not streamed"),
+    };
+
+    (
+        [(header::CONTENT_TYPE, format.content_type())],
+        StreamBody::new(body),
+    )
+        .into_response()
+}
+
+fn delimiter(format: ResponseFormat) -> char {
+    if format == ResponseFormat::Tsv {
+        '\t'
+    } else {
+        ','
+    }
+}
+
+/// Escapes the field, CSV fields are quoted while special chars in TSV fields are escaped
+/// by backslash.
+fn escape_field(field: &str, format: ResponseFormat) -> String {
+    if format == ResponseFormat::Tsv {
+        field
+            .replace('\\', "\\\\")
+            .replace('\t', "\\t")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r")
+    } else if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn encode_line<I: Iterator<Item = String>>(fields: I, format: ResponseFormat, buf: &mut String) {
+    let delimiter = delimiter(format);
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            buf.push(delimiter);
+        }
+        buf.push_str(&escape_field(&field, format));
+    }
+    buf.push('\n');
+}
+
+fn encode_delimited_header(schema: &SchemaRef, format: ResponseFormat) -> Bytes {
+    let mut buf = String::new();
+    encode_line(
+        schema.column_schemas().iter().map(|c| c.name.clone()),
+        format,
+        &mut buf,
+    );
+    Bytes::from(buf)
+}
+
+/// Converts the value to JSON, timestamps are rendered in `time_zone`.
+pub(crate) fn value_to_json(
+    value: Value,
+    time_zone: Option<&TimeZone>,
+) -> serde_json::Result<serde_json::Value> {
+    match value {
+        Value::Timestamp(ts) => Ok(serde_json::Value::String(
+            ts.to_timezone_aware_string(time_zone),
+        )),
+        value => serde_json::Value::try_from(value),
+    }
+}
+
+fn encode_delimited(
+    batch: &RecordBatch,
+    format: ResponseFormat,
+    time_zone: Option<&TimeZone>,
+) -> Result<Bytes> {
+    let mut buf = String::new();
+    for row in batch.rows() {
+        let row = row.context(CollectRecordbatchSnafu)?;
+        encode_line(
+            row.into_iter().map(|value| match value {
+                Value::Null => String::new(),
+                Value::Timestamp(ts) => ts.to_timezone_aware_string(time_zone),
+                value => value.to_string(),
+            }),
+            format,
+            &mut buf,
+        );
+    }
+    Ok(Bytes::from(buf))
+}
+
+fn encode_columnar(batch: &RecordBatch, time_zone: Option<&TimeZone>) -> Result<Bytes> {
+    let column_schemas = batch.schema.column_schemas();
+    let values = batch
+        .df_recordbatch
+        .columns()
+        .iter()
+        .map(|array| {
+            let vector = Helper::try_into_vector(array.clone()).context(VectorConversionSnafu)?;
+            (0..vector.len())
+                .map(|i| value_to_json(vector.get(i), time_zone).context(EncodeJsonSnafu))
+                .collect::<Result<Vec<_>>>()
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let line = json!({
+        "columns": column_schemas.iter().map(|c| &c.name).collect::<Vec<_>>(),
+        "types": column_schemas.iter().map(|c| c.data_type.name()).collect::<Vec<_>>(),
+        "values": values,
+    });
+    let mut buf = serde_json::to_vec(&line).context(EncodeJsonSnafu)?;
+    buf.push(b'\n');
+    Ok(Bytes::from(buf))
+}
+
+/// Buffer shared with the [StreamWriter], taken after each message is written.
+#[derive(Clone, Default)]
+struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl SharedBuffer {
+    fn take(&self) -> Bytes {
+        Bytes::from(std::mem::take(&mut *self.0.lock().unwrap()))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.0.lock().unwrap().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn arrow_stream(
+    schema: SchemaRef,
+    stream: SendableRecordBatchStream,
+) -> BoxStream<'static, Result<Bytes>> {
+    let buffer = SharedBuffer::default();
+    let writer = Arc::new(Mutex::new(StreamWriter::new(
+        buffer.clone(),
+        WriteOptions { compression: None },
+    )));
+
+    let start = writer
+        .lock()
+        .unwrap()
+        .start(schema.arrow_schema(), None)
+        .context(EncodeArrowSnafu)
+        .map(|_| buffer.take());
+
+    let batches = {
+        let writer = writer.clone();
+        let buffer = buffer.clone();
+        stream.map(move |batch| {
+            let batch = batch.context(CollectRecordbatchSnafu)?;
+            let chunk = Chunk::try_new(batch.df_recordbatch.columns().to_vec())
+                .context(EncodeArrowSnafu)?;
+            writer
+                .lock()
+                .unwrap()
+                .write(&chunk, None)
+                .context(EncodeArrowSnafu)?;
+            Ok(buffer.take())
+        })
+    };
+
+    let finish = stream::once(async move {
+        writer.lock().unwrap().finish().context(EncodeArrowSnafu)?;
+        Ok(buffer.take())
+    });
+
+    stream::once(futures::future::ready(start))
+        .chain(batches)
+        .chain(finish)
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use common_recordbatch::RecordBatches;
+    use datatypes::prelude::ConcreteDataType;
+    use datatypes::schema::{ColumnSchema, Schema};
+    use datatypes::vectors::{StringVector, TimestampVector, UInt32Vector};
+
+    use super::*;
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(Some(ResponseFormat::Csv), ResponseFormat::parse("CSV"));
+        assert_eq!(Some(ResponseFormat::Arrow), ResponseFormat::parse("arrow"));
+        assert_eq!(None, ResponseFormat::parse("xml"));
+
+        assert_eq!(
+            Some(ResponseFormat::Tsv),
+            ResponseFormat::from_accept("text/xml, text/tab-separated-values; charset=utf-8")
+        );
+        assert_eq!(None, ResponseFormat::from_accept("*/*"));
+    }
+
+    fn new_batch() -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("host", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("cpu", ConcreteDataType::uint32_datatype(), true),
+        ]));
+        RecordBatch::new(
+            schema,
+            vec![
+                Arc::new(StringVector::from(vec![Some("a,b"), None])) as _,
+                Arc::new(UInt32Vector::from(vec![Some(1), Some(2)])) as _,
+            ],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_encode_delimited() {
+        let batch = new_batch();
+        assert_eq!(
+            "host,cpu\n",
+            encode_delimited_header(&batch.schema, ResponseFormat::Csv)
+        );
+        assert_eq!(
+            "\"a,b\",1\n,2\n",
+            encode_delimited(&batch, ResponseFormat::Csv, None).unwrap()
+        );
+        assert_eq!(
+            "a,b\t1\n\t2\n",
+            encode_delimited(&batch, ResponseFormat::Tsv, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_encode_columnar() {
+        let batch = new_batch();
+        let line = encode_columnar(&batch, None).unwrap();
+        let expected = r#"{"columns":["host","cpu"],"types":["String","UInt32"],"values":[["a,b",null],[1,2]]}"#;
+        assert_eq!(format!("{}\n", expected), line);
+    }
+
+    #[test]
+    fn test_encode_timestamp_in_time_zone() {
+        let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+            "ts",
+            ConcreteDataType::timestamp_millis_datatype(),
+            true,
+        )]));
+        let batch = RecordBatch::new(
+            schema,
+            vec![Arc::new(TimestampVector::from_values(vec![1668070237000])) as _],
+        )
+        .unwrap();
+        let time_zone = TimeZone::from_tz_string("+08:00").unwrap();
+
+        assert_eq!(
+            "2022-11-10 08:50:37+0000\n",
+            encode_delimited(&batch, ResponseFormat::Csv, None).unwrap()
+        );
+        assert_eq!(
+            "2022-11-10 16:50:37+0800\n",
+            encode_delimited(&batch, ResponseFormat::Csv, time_zone.as_ref()).unwrap()
+        );
+        let line = encode_columnar(&batch, time_zone.as_ref()).unwrap();
+        let expected =
+            r#"{"columns":["ts"],"types":["Timestamp"],"values":[["2022-11-10 16:50:37+0800"]]}"#;
+        assert_eq!(format!("{}\n", expected), line);
+    }
+
+    #[tokio::test]
+    async fn test_arrow_stream() {
+        let batch = new_batch();
+        let batches = RecordBatches::try_new(batch.schema.clone(), vec![batch]).unwrap();
+        let mut buf = Vec::new();
+        let mut stream = arrow_stream(batches.schema(), batches.as_stream());
+        while let Some(bytes) = stream.next().await {
+            buf.extend_from_slice(&bytes.unwrap());
+        }
+
+        let mut reader = std::io::Cursor::new(buf);
+        let metadata = datatypes::arrow::io::ipc::read::read_stream_metadata(&mut reader).unwrap();
+        assert_eq!(2, metadata.schema.fields.len());
+        let reader = datatypes::arrow::io::ipc::read::StreamReader::new(reader, metadata);
+        let mut rows = 0;
+        for state in reader {
+            if let datatypes::arrow::io::ipc::read::StreamState::Some(chunk) = state.unwrap() {
+                rows += chunk.len();
+            }
+        }
+        assert_eq!(2, rows);
+    }
+}
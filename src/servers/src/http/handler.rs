@@ -17,21 +17,72 @@ use std::sync::Arc;
 use std::time::Instant;
 
 use aide::transform::TransformOperation;
+use aide::OperationOutput;
 use axum::extract::{Json, Query, State};
+use axum::http::{header, HeaderMap};
+use axum::response::{IntoResponse, Response};
 use axum::Extension;
+use common_catalog::parse_catalog_and_schema_from_client_database_name;
 use common_error::status_code::StatusCode;
+use common_query::Output;
 use common_telemetry::metric;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use session::context::QueryContext;
+use session::context::{QueryContext, TIME_ZONE_VARIABLE};
 
 use crate::auth::UserInfo;
+use crate::http::format::{self, ResponseFormat};
 use crate::http::{ApiState, JsonResponse};
 
 #[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
 pub struct SqlQuery {
     pub database: Option<String>,
     pub sql: Option<String>,
+    /// Output format of the records: `json`, `csv`, `tsv`, `arrow` or `columnar`, the
+    /// `Accept` header is used if absent.
+    pub format: Option<String>,
+    /// Time zone to parse and render timestamps in, like `+08:00` or `Asia/Shanghai`.
+    pub time_zone: Option<String>,
+}
+
+/// Response of the sql handler, records are streamed unless they are requested in JSON.
+pub enum SqlResponse {
+    Json(JsonResponse),
+    Stream(Response),
+}
+
+impl SqlResponse {
+    pub fn into_json(self) -> Option<JsonResponse> {
+        match self {
+            SqlResponse::Json(resp) => Some(resp),
+            SqlResponse::Stream(_) => None,
+        }
+    }
+}
+
+impl IntoResponse for SqlResponse {
+    fn into_response(self) -> Response {
+        match self {
+            SqlResponse::Json(resp) => Json(resp).into_response(),
+            SqlResponse::Stream(resp) => resp,
+        }
+    }
+}
+
+impl OperationOutput for SqlResponse {
+    type Inner = JsonResponse;
+}
+
+fn response_format(params: &SqlQuery, headers: &HeaderMap) -> Result<ResponseFormat, String> {
+    match &params.format {
+        Some(format) => ResponseFormat::parse(format)
+            .ok_or_else(|| format!("Unsupported output format: {}", format)),
+        None => Ok(headers
+            .get(header::ACCEPT)
+            .and_then(|accept| accept.to_str().ok())
+            .and_then(ResponseFormat::from_accept)
+            .unwrap_or_default()),
+    }
 }
 
 /// Handler to execute sql
@@ -39,15 +90,62 @@ pub struct SqlQuery {
 pub async fn sql(
     State(state): State<ApiState>,
     Query(params): Query<SqlQuery>,
-    // TODO(fys): pass _user_info into query context
-    _user_info: Extension<UserInfo>,
-) -> Json<JsonResponse> {
+    headers: HeaderMap,
+    Extension(user_info): Extension<UserInfo>,
+) -> SqlResponse {
     let sql_handler = &state.sql_handler;
     let start = Instant::now();
+    let format = match response_format(&params, &headers) {
+        Ok(format) => format,
+        Err(e) => {
+            return SqlResponse::Json(JsonResponse::with_error(e, StatusCode::InvalidArguments))
+        }
+    };
+
     let resp = if let Some(sql) = &params.sql {
         // TODO(LFC): Sessions in http server.
         let query_ctx = Arc::new(QueryContext::new());
-        JsonResponse::from_output(sql_handler.do_query(sql, query_ctx).await).await
+        user_info.bind_to(&query_ctx);
+        if let Some(database) = &params.database {
+            let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(database);
+            if let Some(catalog) = catalog {
+                if !query_ctx.is_catalog_accessible(catalog) {
+                    return SqlResponse::Json(JsonResponse::with_error(
+                        format!("Access denied to catalog: {}", catalog),
+                        StatusCode::AccessDenied,
+                    ));
+                }
+                query_ctx.set_current_catalog(catalog);
+            }
+            query_ctx.set_current_schema(schema);
+        }
+        if let Some(time_zone) = &params.time_zone {
+            if let Err(e) = query_ctx.set_variable(TIME_ZONE_VARIABLE, time_zone) {
+                return SqlResponse::Json(JsonResponse::with_error(
+                    e.to_string(),
+                    StatusCode::InvalidArguments,
+                ));
+            }
+        }
+        let output = sql_handler.do_query(sql, query_ctx.clone()).await;
+        let time_zone = query_ctx.time_zone();
+        match (format, output) {
+            (ResponseFormat::Json, output) => {
+                JsonResponse::from_output(output, time_zone.as_ref()).await
+            }
+            (format, Ok(Output::Stream(stream))) => {
+                return SqlResponse::Stream(format::stream_response(format, stream, time_zone))
+            }
+            (format, Ok(Output::RecordBatches(batches))) => {
+                return SqlResponse::Stream(format::stream_response(
+                    format,
+                    batches.as_stream(),
+                    time_zone,
+                ))
+            }
+            // Affected rows and errors are always in JSON.
+            (_, output) => JsonResponse::from_output(output, time_zone.as_ref()).await,
+        }
     } else {
         JsonResponse::with_error(
             "sql parameter is required.".to_string(),
@@ -55,7 +153,7 @@ pub async fn sql(
         )
     };
 
-    Json(resp.with_execution_time(start.elapsed().as_millis()))
+    SqlResponse::Json(resp.with_execution_time(start.elapsed().as_millis()))
 }
 
 pub(crate) fn sql_docs(op: TransformOperation) -> TransformOperation {
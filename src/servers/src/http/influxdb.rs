@@ -13,43 +13,188 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::io::Read;
 
 use axum::extract::{Query, State};
-use axum::http::StatusCode;
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::response::IntoResponse;
+use axum::{Extension, Json};
+use bytes::Bytes;
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use common_grpc::writer::Precision;
+use flate2::read::GzDecoder;
+use serde::Serialize;
+use session::context::QueryContext;
+use snafu::{ensure, ResultExt};
 
-use crate::error::{Result, TimePrecisionSnafu};
+use crate::auth::UserInfo;
+use crate::error::{self, Result, TimePrecisionSnafu};
+use crate::influxdb::query::{InfluxqlRequest, InfluxqlResponse};
 use crate::influxdb::InfluxdbRequest;
 use crate::query_handler::InfluxdbLineProtocolHandlerRef;
 
+const INFLUXDB_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Max size of a gzip encoded request body after decompression.
+const MAX_DECOMPRESSED_BODY_SIZE: usize = 64 * 1024 * 1024;
+
 #[axum_macros::debug_handler]
 pub async fn influxdb_write(
     State(handler): State<InfluxdbLineProtocolHandlerRef>,
     Query(mut params): Query<HashMap<String, String>>,
-    lines: String,
+    headers: HeaderMap,
+    Extension(user_info): Extension<UserInfo>,
+    body: Bytes,
 ) -> Result<(StatusCode, ())> {
     let db = params
         .remove("db")
         .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string());
+    write(handler, db, &params, &headers, &user_info, body).await
+}
 
+/// Handler of the InfluxDB v2 write API, the bucket is used as the database and the
+/// organization is ignored.
+#[axum_macros::debug_handler]
+pub async fn influxdb_write_v2(
+    State(handler): State<InfluxdbLineProtocolHandlerRef>,
+    Query(mut params): Query<HashMap<String, String>>,
+    headers: HeaderMap,
+    Extension(user_info): Extension<UserInfo>,
+    body: Bytes,
+) -> Result<(StatusCode, ())> {
+    let db = params
+        .remove("bucket")
+        .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string());
+    write(handler, db, &params, &headers, &user_info, body).await
+}
+
+async fn write(
+    handler: InfluxdbLineProtocolHandlerRef,
+    db: String,
+    params: &HashMap<String, String>,
+    headers: &HeaderMap,
+    user_info: &UserInfo,
+    body: Bytes,
+) -> Result<(StatusCode, ())> {
     let precision = params
         .get("precision")
         .map(|val| parse_time_precision(val))
         .transpose()?;
+    let lines = decode_body(headers, body)?;
     let request = InfluxdbRequest {
         precision,
         lines,
         db,
     };
-    handler.exec(&request).await?;
+    let query_ctx = QueryContext::arc();
+    user_info.bind_to(&query_ctx);
+    handler.exec(&request, query_ctx).await?;
     Ok((StatusCode::NO_CONTENT, ()))
 }
 
+/// Decodes the request body to string, decompresses it first if it is gzip encoded.
+fn decode_body(headers: &HeaderMap, body: Bytes) -> Result<String> {
+    decode_body_with_limit(headers, body, MAX_DECOMPRESSED_BODY_SIZE)
+}
+
+/// Decodes the request body like [decode_body], fails if the decompressed body is larger than
+/// `limit` bytes.
+fn decode_body_with_limit(headers: &HeaderMap, body: Bytes, limit: usize) -> Result<String> {
+    let gzipped = headers
+        .get(header::CONTENT_ENCODING)
+        .map(|encoding| encoding.as_bytes().eq_ignore_ascii_case(b"gzip"))
+        .unwrap_or(false);
+    let body = if gzipped {
+        let mut decompressed = Vec::new();
+        // Reads one more byte than the limit to tell whether the body exceeds it.
+        GzDecoder::new(&body[..])
+            .take(limit as u64 + 1)
+            .read_to_end(&mut decompressed)
+            .context(error::DecompressInfluxdbRequestSnafu)?;
+        ensure!(
+            decompressed.len() <= limit,
+            error::InfluxdbRequestTooLargeSnafu { limit }
+        );
+        decompressed
+    } else {
+        body.to_vec()
+    };
+    String::from_utf8(body).context(error::InvalidInfluxdbRequestSnafu)
+}
+
+#[axum_macros::debug_handler]
+pub async fn influxdb_query(
+    State(handler): State<InfluxdbLineProtocolHandlerRef>,
+    Query(mut params): Query<HashMap<String, String>>,
+    Extension(user_info): Extension<UserInfo>,
+    body: Bytes,
+) -> Result<Json<InfluxqlResponse>> {
+    // Parameters could also be sent in the url-encoded body of POST requests.
+    if !body.is_empty() {
+        let form: HashMap<String, String> = serde_urlencoded::from_bytes(&body).map_err(|e| {
+            error::InvalidQuerySnafu {
+                reason: format!("invalid form body: {}", e),
+            }
+            .build()
+        })?;
+        params.extend(form);
+    }
+
+    let query = params.remove("q").ok_or_else(|| {
+        error::InvalidQuerySnafu {
+            reason: "missing required parameter \"q\"",
+        }
+        .build()
+    })?;
+    let db = params
+        .remove("db")
+        .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string());
+    let epoch = params
+        .get("epoch")
+        .map(|val| parse_time_precision(val))
+        .transpose()?;
+    let request = InfluxqlRequest { db, query, epoch };
+    let query_ctx = QueryContext::arc();
+    user_info.bind_to(&query_ctx);
+    Ok(Json(handler.query(&request, query_ctx).await?))
+}
+
+/// Responds the InfluxDB ping requests, which are used by clients to check whether the
+/// server is up.
+#[axum_macros::debug_handler]
+pub async fn influxdb_ping() -> impl IntoResponse {
+    (
+        StatusCode::NO_CONTENT,
+        [
+            ("X-Influxdb-Build", "GreptimeDB"),
+            ("X-Influxdb-Version", INFLUXDB_VERSION),
+        ],
+    )
+}
+
+#[derive(Debug, Serialize)]
+pub struct InfluxdbHealthResponse {
+    name: &'static str,
+    message: &'static str,
+    status: &'static str,
+    checks: Vec<String>,
+    version: &'static str,
+}
+
+#[axum_macros::debug_handler]
+pub async fn influxdb_health() -> Json<InfluxdbHealthResponse> {
+    Json(InfluxdbHealthResponse {
+        name: "influxdb",
+        message: "ready for queries and writes",
+        status: "pass",
+        checks: vec![],
+        version: INFLUXDB_VERSION,
+    })
+}
+
 fn parse_time_precision(value: &str) -> Result<Precision> {
     match value {
-        "n" => Ok(Precision::NANOSECOND),
-        "u" => Ok(Precision::MICROSECOND),
+        "n" | "ns" => Ok(Precision::NANOSECOND),
+        "u" | "us" | "µ" => Ok(Precision::MICROSECOND),
         "ms" => Ok(Precision::MILLISECOND),
         "s" => Ok(Precision::SECOND),
         "m" => Ok(Precision::MINUTE),
@@ -63,18 +208,45 @@ fn parse_time_precision(value: &str) -> Result<Precision> {
 
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
+
+    use axum::http::{header, HeaderMap, HeaderValue};
+    use bytes::Bytes;
     use common_grpc::writer::Precision;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
 
-    use crate::http::influxdb::parse_time_precision;
+    use crate::http::influxdb::{decode_body_with_limit, parse_time_precision};
 
     #[test]
     fn test_parse_time_precision() {
         assert_eq!(Precision::NANOSECOND, parse_time_precision("n").unwrap());
+        assert_eq!(Precision::NANOSECOND, parse_time_precision("ns").unwrap());
         assert_eq!(Precision::MICROSECOND, parse_time_precision("u").unwrap());
+        assert_eq!(Precision::MICROSECOND, parse_time_precision("us").unwrap());
         assert_eq!(Precision::MILLISECOND, parse_time_precision("ms").unwrap());
         assert_eq!(Precision::SECOND, parse_time_precision("s").unwrap());
         assert_eq!(Precision::MINUTE, parse_time_precision("m").unwrap());
         assert_eq!(Precision::HOUR, parse_time_precision("h").unwrap());
         assert!(parse_time_precision("unknown").is_err());
     }
+
+    #[test]
+    fn test_decode_body_with_limit() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("gzip"));
+        let lines = "monitor,host=host1 cpu=1.2 1664370459457010101";
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(lines.as_bytes()).unwrap();
+        let body = Bytes::from(encoder.finish().unwrap());
+
+        let decoded = decode_body_with_limit(&headers, body.clone(), lines.len()).unwrap();
+        assert_eq!(lines, decoded);
+        assert!(decode_body_with_limit(&headers, body, lines.len() - 1).is_err());
+
+        // the limit only applies to compressed bodies
+        let decoded =
+            decode_body_with_limit(&HeaderMap::new(), Bytes::from(lines), lines.len() - 1).unwrap();
+        assert_eq!(lines, decoded);
+    }
 }
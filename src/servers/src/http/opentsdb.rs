@@ -16,13 +16,16 @@ use std::collections::HashMap;
 
 use axum::extract::{Query, RawBody, State};
 use axum::http::StatusCode as HttpStatusCode;
-use axum::Json;
+use axum::{Extension, Json};
 use hyper::Body;
 use serde::{Deserialize, Serialize};
+use session::context::QueryContext;
 use snafu::ResultExt;
 
-use crate::error::{self, Error, Result};
+use crate::auth::UserInfo;
+use crate::error::{self, Result};
 use crate::opentsdb::codec::DataPoint;
+use crate::opentsdb::query::{QueryRequest, QueryResult, SuggestRequest};
 use crate::query_handler::OpentsdbProtocolHandlerRef;
 
 #[derive(Serialize, Deserialize)]
@@ -76,22 +79,27 @@ pub enum OpentsdbPutResponse {
 pub async fn put(
     State(opentsdb_handler): State<OpentsdbProtocolHandlerRef>,
     Query(params): Query<HashMap<String, String>>,
+    Extension(user_info): Extension<UserInfo>,
     RawBody(body): RawBody,
 ) -> Result<(HttpStatusCode, Json<OpentsdbPutResponse>)> {
+    let query_ctx = QueryContext::arc();
+    user_info.bind_to(&query_ctx);
     let summary = params.contains_key("summary");
     let details = params.contains_key("details");
 
     let data_points = parse_data_points(body).await?;
 
     let response = if !summary && !details {
-        for data_point in data_points.into_iter() {
-            if let Err(e) = opentsdb_handler.exec(&data_point.into()).await {
-                // Not debugging purpose, failed fast.
-                return error::InternalSnafu {
-                    err_msg: e.to_string(),
-                }
-                .fail();
+        let data_points = data_points
+            .into_iter()
+            .map(DataPoint::from)
+            .collect::<Vec<_>>();
+        if let Err(e) = opentsdb_handler.exec(&data_points, query_ctx).await {
+            // Not debugging purpose, failed fast.
+            return error::InternalSnafu {
+                err_msg: e.to_string(),
             }
+            .fail();
         }
         (HttpStatusCode::NO_CONTENT, Json(OpentsdbPutResponse::Empty))
     } else {
@@ -105,12 +113,21 @@ pub async fn put(
             },
         };
 
-        for data_point in data_points.into_iter() {
-            let result = opentsdb_handler.exec(&data_point.clone().into()).await;
-            match result {
-                Ok(()) => response.on_success(),
+        // Data points of the same metric are inserted in one batch, so they succeed or fail
+        // together.
+        for data_points in group_by_metric(data_points) {
+            let batch = data_points
+                .iter()
+                .cloned()
+                .map(DataPoint::from)
+                .collect::<Vec<_>>();
+            match opentsdb_handler.exec(&batch, query_ctx.clone()).await {
+                Ok(()) => response.on_success(data_points.len()),
                 Err(e) => {
-                    response.on_failed(data_point, e);
+                    let error = e.to_string();
+                    for data_point in data_points {
+                        response.on_failed(data_point, &error);
+                    }
                 }
             }
         }
@@ -122,6 +139,50 @@ pub async fn put(
     Ok(response)
 }
 
+// Please refer to the OpenTSDB documents of ["api/query"](http://opentsdb.net/docs/build/html/api_http/query/index.html)
+// for more details.
+#[axum_macros::debug_handler]
+pub async fn query(
+    State(opentsdb_handler): State<OpentsdbProtocolHandlerRef>,
+    Extension(user_info): Extension<UserInfo>,
+    Json(request): Json<QueryRequest>,
+) -> Result<Json<Vec<QueryResult>>> {
+    let query_ctx = QueryContext::arc();
+    user_info.bind_to(&query_ctx);
+    let results = opentsdb_handler.query(&request, query_ctx).await?;
+    Ok(Json(results))
+}
+
+// Please refer to the OpenTSDB documents of ["api/suggest"](http://opentsdb.net/docs/build/html/api_http/suggest.html)
+// for more details.
+#[axum_macros::debug_handler]
+pub async fn suggest(
+    State(opentsdb_handler): State<OpentsdbProtocolHandlerRef>,
+    Query(request): Query<SuggestRequest>,
+    Extension(user_info): Extension<UserInfo>,
+) -> Result<Json<Vec<String>>> {
+    let query_ctx = QueryContext::arc();
+    user_info.bind_to(&query_ctx);
+    let suggestions = opentsdb_handler.suggest(&request, query_ctx).await?;
+    Ok(Json(suggestions))
+}
+
+/// Groups data points by metric, in the order of the first appearance of metrics.
+fn group_by_metric(data_points: Vec<DataPointRequest>) -> Vec<Vec<DataPointRequest>> {
+    let mut metric_index: HashMap<String, usize> = HashMap::new();
+    let mut groups: Vec<Vec<DataPointRequest>> = Vec::new();
+    for data_point in data_points {
+        let index = *metric_index
+            .entry(data_point.metric.clone())
+            .or_insert_with(|| {
+                groups.push(Vec::new());
+                groups.len() - 1
+            });
+        groups[index].push(data_point);
+    }
+    groups
+}
+
 async fn parse_data_points(body: Body) -> Result<Vec<DataPointRequest>> {
     let body = hyper::body::to_bytes(body)
         .await
@@ -146,11 +207,11 @@ pub struct OpentsdbDebuggingResponse {
 }
 
 impl OpentsdbDebuggingResponse {
-    fn on_success(&mut self) {
-        self.success += 1;
+    fn on_success(&mut self, count: usize) {
+        self.success += count as i32;
     }
 
-    fn on_failed(&mut self, datapoint: DataPointRequest, error: Error) {
+    fn on_failed(&mut self, datapoint: DataPointRequest, error: &str) {
         self.failed += 1;
 
         if let Some(details) = self.errors.as_mut() {
@@ -236,4 +297,26 @@ mod test {
             "Invalid OpenTSDB Json request, source: expected value at line 1 column 1"
         );
     }
+
+    #[test]
+    fn test_group_by_metric() {
+        let data_point = |metric: &str, timestamp: i64| DataPointRequest {
+            metric: metric.to_string(),
+            timestamp,
+            value: 1.0,
+            tags: HashMap::new(),
+        };
+        let groups = group_by_metric(vec![
+            data_point("m2", 1),
+            data_point("m1", 2),
+            data_point("m2", 3),
+        ]);
+        assert_eq!(
+            groups,
+            vec![
+                vec![data_point("m2", 1), data_point("m2", 3)],
+                vec![data_point("m1", 2)],
+            ]
+        );
+    }
 }
@@ -16,13 +16,16 @@ use api::prometheus::remote::{ReadRequest, WriteRequest};
 use axum::extract::{Query, RawBody, State};
 use axum::http::{header, StatusCode};
 use axum::response::IntoResponse;
+use axum::Extension;
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
 use hyper::Body;
 use prost::Message;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use session::context::QueryContext;
 use snafu::prelude::*;
 
+use crate::auth::UserInfo;
 use crate::error::{self, Result};
 use crate::prometheus::snappy_decompress;
 use crate::query_handler::{PrometheusProtocolHandlerRef, PrometheusResponse};
@@ -44,12 +47,19 @@ impl Default for DatabaseQuery {
 pub async fn remote_write(
     State(handler): State<PrometheusProtocolHandlerRef>,
     Query(params): Query<DatabaseQuery>,
+    Extension(user_info): Extension<UserInfo>,
     RawBody(body): RawBody,
 ) -> Result<(StatusCode, ())> {
     let request = decode_remote_write_request(body).await?;
+    let query_ctx = QueryContext::arc();
+    user_info.bind_to(&query_ctx);
 
     handler
-        .write(params.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME), request)
+        .write(
+            params.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME),
+            request,
+            query_ctx,
+        )
         .await?;
 
     Ok((StatusCode::NO_CONTENT, ()))
@@ -72,12 +82,19 @@ impl IntoResponse for PrometheusResponse {
 pub async fn remote_read(
     State(handler): State<PrometheusProtocolHandlerRef>,
     Query(params): Query<DatabaseQuery>,
+    Extension(user_info): Extension<UserInfo>,
     RawBody(body): RawBody,
 ) -> Result<PrometheusResponse> {
     let request = decode_remote_read_request(body).await?;
+    let query_ctx = QueryContext::arc();
+    user_info.bind_to(&query_ctx);
 
     handler
-        .read(params.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME), request)
+        .read(
+            params.db.as_deref().unwrap_or(DEFAULT_SCHEMA_NAME),
+            request,
+            query_ctx,
+        )
         .await
 }
 
@@ -12,14 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::time::Instant;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use axum::extract::{Json, Query, RawBody, State};
+use axum::Extension;
 use common_error::ext::ErrorExt;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use session::context::QueryContext;
 
-use crate::http::{ApiState, JsonResponse};
+use crate::auth::UserInfo;
+use crate::http::{ApiState, JsonOutput, JsonResponse};
 
 macro_rules! json_err {
     ($e: expr) => {{
@@ -74,6 +79,9 @@ pub async fn scripts(
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
 pub struct ScriptQuery {
     pub name: Option<String>,
+    /// Other parameters are passed to the script as its runtime parameters.
+    #[serde(flatten)]
+    pub params: HashMap<String, String>,
 }
 
 /// Handler to execute script
@@ -81,6 +89,7 @@ pub struct ScriptQuery {
 pub async fn run_script(
     State(state): State<ApiState>,
     Query(params): Query<ScriptQuery>,
+    Extension(user_info): Extension<UserInfo>,
 ) -> Json<JsonResponse> {
     if let Some(script_handler) = &state.script_handler {
         let start = Instant::now();
@@ -90,11 +99,184 @@ pub async fn run_script(
             json_err!("invalid name");
         }
 
-        let output = script_handler.execute_script(name.unwrap()).await;
-        let resp = JsonResponse::from_output(output).await;
+        let query_ctx = Arc::new(QueryContext::new());
+        user_info.bind_to(&query_ctx);
+        let output = script_handler
+            .execute_script(name.unwrap(), params.params, query_ctx)
+            .await;
+        let resp = JsonResponse::from_output(output, None).await;
 
         Json(resp.with_execution_time(start.elapsed().as_millis()))
     } else {
         json_err!("Script execution not supported, missing script handler");
     }
 }
+
+/// A version of a script, times are in milliseconds.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Eq, PartialEq)]
+pub struct ScriptInfo {
+    pub name: String,
+    pub engine: String,
+    pub version: i64,
+    pub gmt_created: i64,
+    pub gmt_modified: i64,
+    /// Source of the script, only present when fetching a single script.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ScriptInfoQuery {
+    pub name: Option<String>,
+    pub version: Option<i64>,
+}
+
+/// Handler to list scripts, or fetch the script by name and optional version
+#[axum_macros::debug_handler]
+pub async fn get_scripts(
+    State(state): State<ApiState>,
+    Query(params): Query<ScriptInfoQuery>,
+) -> Json<JsonResponse> {
+    if let Some(script_handler) = &state.script_handler {
+        let result = match &params.name {
+            Some(name) if !name.is_empty() => script_handler
+                .get_script(name, params.version)
+                .await
+                .map(|script| vec![script]),
+            _ => script_handler.list_scripts().await,
+        };
+
+        let body = match result {
+            Ok(scripts) => JsonResponse::with_output(Some(vec![JsonOutput::Scripts(scripts)])),
+            Err(e) => json_err!(format!("Get scripts error: {}", e), e.status_code()),
+        };
+
+        Json(body)
+    } else {
+        json_err!("Script execution not supported, missing script handler");
+    }
+}
+
+/// Handler to list all versions of the script
+#[axum_macros::debug_handler]
+pub async fn script_versions(
+    State(state): State<ApiState>,
+    Query(params): Query<ScriptInfoQuery>,
+) -> Json<JsonResponse> {
+    if let Some(script_handler) = &state.script_handler {
+        let name = params.name.as_ref();
+
+        if name.is_none() || name.unwrap().is_empty() {
+            json_err!("invalid name");
+        }
+
+        let body = match script_handler.list_script_versions(name.unwrap()).await {
+            Ok(scripts) => JsonResponse::with_output(Some(vec![JsonOutput::Scripts(scripts)])),
+            Err(e) => json_err!(
+                format!("List script versions error: {}", e),
+                e.status_code()
+            ),
+        };
+
+        Json(body)
+    } else {
+        json_err!("Script execution not supported, missing script handler");
+    }
+}
+
+/// Handler to delete script, its prior versions are kept
+#[axum_macros::debug_handler]
+pub async fn delete_script(
+    State(state): State<ApiState>,
+    Query(params): Query<ScriptInfoQuery>,
+) -> Json<JsonResponse> {
+    if let Some(script_handler) = &state.script_handler {
+        let name = params.name.as_ref();
+
+        if name.is_none() || name.unwrap().is_empty() {
+            json_err!("invalid name");
+        }
+
+        let body = match script_handler.delete_script(name.unwrap()).await {
+            Ok(()) => JsonResponse::with_output(None),
+            Err(e) => json_err!(format!("Delete script error: {}", e), e.status_code()),
+        };
+
+        Json(body)
+    } else {
+        json_err!("Script execution not supported, missing script handler");
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduleQuery {
+    pub name: Option<String>,
+    /// Interval of the runs, such as `5m`.
+    #[serde(default, with = "humantime_serde")]
+    #[schemars(with = "Option<String>")]
+    pub interval: Option<Duration>,
+    /// Table that the output of the script is written into.
+    pub table: Option<String>,
+    /// Other parameters are passed to the script as its runtime parameters.
+    #[serde(flatten)]
+    pub params: HashMap<String, String>,
+}
+
+/// Handler to schedule script
+#[axum_macros::debug_handler]
+pub async fn schedule_script(
+    State(state): State<ApiState>,
+    Query(params): Query<ScheduleQuery>,
+) -> Json<JsonResponse> {
+    if let Some(script_handler) = &state.script_handler {
+        let name = params.name.as_ref();
+
+        if name.is_none() || name.unwrap().is_empty() {
+            json_err!("invalid name");
+        }
+        let interval = match params.interval {
+            Some(interval) if !interval.is_zero() => interval,
+            _ => json_err!("invalid interval"),
+        };
+        let table = match &params.table {
+            Some(table) if !table.is_empty() => table,
+            _ => json_err!("invalid table"),
+        };
+
+        let body = match script_handler
+            .schedule_script(name.unwrap(), interval, table, params.params)
+            .await
+        {
+            Ok(()) => JsonResponse::with_output(None),
+            Err(e) => json_err!(format!("Schedule script error: {}", e), e.status_code()),
+        };
+
+        Json(body)
+    } else {
+        json_err!("Script execution not supported, missing script handler");
+    }
+}
+
+/// Handler to cancel the schedule of script
+#[axum_macros::debug_handler]
+pub async fn unschedule_script(
+    State(state): State<ApiState>,
+    Query(params): Query<ScriptInfoQuery>,
+) -> Json<JsonResponse> {
+    if let Some(script_handler) = &state.script_handler {
+        let name = params.name.as_ref();
+
+        if name.is_none() || name.unwrap().is_empty() {
+            json_err!("invalid name");
+        }
+
+        let body = match script_handler.unschedule_script(name.unwrap()).await {
+            Ok(()) => JsonResponse::with_output(None),
+            Err(e) => json_err!(format!("Unschedule script error: {}", e), e.status_code()),
+        };
+
+        Json(body)
+    } else {
+        json_err!("Script execution not supported, missing script handler");
+    }
+}
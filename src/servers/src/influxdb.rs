@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 
 use api::v1::InsertExpr;
+use common_catalog::consts::DEFAULT_CATALOG_NAME;
 use common_grpc::writer::{LinesWriter, Precision};
 use influxdb_line_protocol::{parse_lines, FieldValue};
 use snafu::ResultExt;
@@ -23,6 +24,9 @@ use table::requests::InsertRequest;
 use crate::error::{Error, InfluxdbLineProtocolSnafu, InfluxdbLinesWriteSnafu};
 use crate::line_writer::LineWriter;
 
+mod condition;
+pub mod query;
+
 pub const INFLUXDB_TIMESTAMP_COLUMN_NAME: &str = "ts";
 pub const DEFAULT_TIME_PRECISION: Precision = Precision::NANOSECOND;
 
@@ -167,6 +171,7 @@ impl TryFrom<&InfluxdbRequest> for Vec<InsertExpr> {
             .map(|(table_name, writer)| {
                 let (columns, row_count) = writer.finish();
                 InsertExpr {
+                    catalog_name: DEFAULT_CATALOG_NAME.to_string(),
                     schema_name: schema_name.clone(),
                     table_name,
                     region_number: 0,
@@ -268,12 +273,12 @@ monitor2,host=host4 cpu=66.3,memory=1029 1663840496400340003";
         let ts = columns.get("ts").unwrap();
         let expected: Vec<Value> = vec![
             datatypes::prelude::Value::Timestamp(Timestamp::new(
-                1663840496100,
-                TimeUnit::Millisecond,
+                1663840496100023100,
+                TimeUnit::Nanosecond,
             )),
             datatypes::prelude::Value::Timestamp(Timestamp::new(
-                1663840496400,
-                TimeUnit::Millisecond,
+                1663840496400340001,
+                TimeUnit::Nanosecond,
             )),
         ];
         assert_vector(&expected, ts);
@@ -300,12 +305,12 @@ monitor2,host=host4 cpu=66.3,memory=1029 1663840496400340003";
         let ts = columns.get("ts").unwrap();
         let expected: Vec<Value> = vec![
             datatypes::prelude::Value::Timestamp(Timestamp::new(
-                1663840496100,
-                TimeUnit::Millisecond,
+                1663840496100023102,
+                TimeUnit::Nanosecond,
             )),
             datatypes::prelude::Value::Timestamp(Timestamp::new(
-                1663840496400,
-                TimeUnit::Millisecond,
+                1663840496400340003,
+                TimeUnit::Nanosecond,
             )),
         ];
         assert_vector(&expected, ts);
@@ -359,11 +364,11 @@ monitor2,host=host4 cpu=66.3,memory=1029 1663840496400340003";
         verify_column(
             &columns[3],
             "ts",
-            ColumnDataType::Timestamp,
+            ColumnDataType::TimestampNanosecond,
             SemanticType::Timestamp,
             Vec::new(),
             Values {
-                ts_millis_values: vec![1663840496100, 1663840496400],
+                ts_nanosecond_values: vec![1663840496100023100, 1663840496400340001],
                 ..Default::default()
             },
         );
@@ -398,11 +403,11 @@ monitor2,host=host4 cpu=66.3,memory=1029 1663840496400340003";
         verify_column(
             &columns[2],
             "ts",
-            ColumnDataType::Timestamp,
+            ColumnDataType::TimestampNanosecond,
             SemanticType::Timestamp,
             Vec::new(),
             Values {
-                ts_millis_values: vec![1663840496100, 1663840496400],
+                ts_nanosecond_values: vec![1663840496100023102, 1663840496400340003],
                 ..Default::default()
             },
         );
@@ -0,0 +1,282 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parser of the non-time conditions in the `WHERE` clause of InfluxQL statements.
+//!
+//! A condition is made up of comparisons between a tag or field and a literal, combined by
+//! `AND`, `OR` and parentheses. It's parsed and then translated to SQL with all identifiers
+//! and literals quoted, anything else is rejected, so no SQL could be injected by a query.
+
+use crate::error::{self, Result};
+use crate::query_util::{quote_ident, quote_literal};
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Ident(String),
+    Str(String),
+    Number(String),
+    Bool(bool),
+    Regex(String),
+    Op(&'static str),
+    LParen,
+    RParen,
+    And,
+    Or,
+}
+
+/// Comparison operators, the longer ones go first so that they are matched first.
+const OPERATORS: [&str; 9] = ["=~", "!~", "!=", "<>", "<=", ">=", "=", "<", ">"];
+
+/// Translates an InfluxQL condition like `host = 'a' AND usage > 10` to SQL.
+pub(crate) fn translate_condition(condition: &str) -> Result<String> {
+    let mut parser = Parser {
+        tokens: tokenize(condition)?,
+        pos: 0,
+    };
+    let sql = parser.parse_or()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(sql),
+        Some(token) => invalid_condition(format!("unexpected {:?}", token)),
+    }
+}
+
+fn invalid_condition<T>(reason: impl Into<String>) -> Result<T> {
+    error::InvalidQuerySnafu {
+        reason: format!("invalid condition, {}", reason.into()),
+    }
+    .fail()
+}
+
+fn tokenize(condition: &str) -> Result<Vec<Token>> {
+    let chars = condition.chars().collect::<Vec<_>>();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        let rest = chars[i..chars.len().min(i + 2)].iter().collect::<String>();
+        if let Some(op) = OPERATORS.iter().find(|op| rest.starts_with(*op)) {
+            tokens.push(Token::Op(*op));
+            i += op.len();
+            continue;
+        }
+        let (token, next) = match c {
+            '(' => (Token::LParen, i + 1),
+            ')' => (Token::RParen, i + 1),
+            '\'' => {
+                let (s, next) = read_quoted(&chars, i, true)?;
+                (Token::Str(s), next)
+            }
+            '"' => {
+                let (s, next) = read_quoted(&chars, i, true)?;
+                (Token::Ident(s), next)
+            }
+            '/' if matches!(tokens.last(), Some(Token::Op("=~" | "!~"))) => {
+                // Escapes other than `\/` are kept for the regex engine.
+                let (s, next) = read_quoted(&chars, i, false)?;
+                (Token::Regex(s), next)
+            }
+            c if c.is_ascii_digit() || ((c == '-' || c == '.') && i + 1 < chars.len()) => {
+                let end = (i + 1..chars.len())
+                    .find(|j| !(chars[*j].is_ascii_alphanumeric() || chars[*j] == '.'))
+                    .unwrap_or(chars.len());
+                let number = chars[i..end].iter().collect::<String>();
+                // Floats are formatted again, as the SQL parser doesn't accept exponents.
+                let number = match (number.parse::<i64>(), number.parse::<f64>()) {
+                    (Ok(_), _) => number,
+                    (_, Ok(n)) if n.is_finite() => n.to_string(),
+                    _ => return invalid_condition(format!("invalid number {}", number)),
+                };
+                (Token::Number(number), end)
+            }
+            c if c.is_alphabetic() || c == '_' => {
+                let end = (i + 1..chars.len())
+                    .find(|j| !(chars[*j].is_alphanumeric() || chars[*j] == '_'))
+                    .unwrap_or(chars.len());
+                let word = chars[i..end].iter().collect::<String>();
+                let token = match word.to_lowercase().as_str() {
+                    "and" => Token::And,
+                    "or" => Token::Or,
+                    "true" => Token::Bool(true),
+                    "false" => Token::Bool(false),
+                    _ => Token::Ident(word),
+                };
+                (token, end)
+            }
+            c => return invalid_condition(format!("unexpected character {}", c)),
+        };
+        tokens.push(token);
+        i = next;
+    }
+    Ok(tokens)
+}
+
+/// Reads the string quoted by `chars[start]`, returns the unquoted string and the position after
+/// the closing quote. Backslash escaped characters are unescaped if `unescape` is true,
+/// otherwise only the escaped quotes are.
+fn read_quoted(chars: &[char], start: usize, unescape: bool) -> Result<(String, usize)> {
+    let quote = chars[start];
+    let mut s = String::new();
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' if i + 1 < chars.len() => {
+                if !unescape && chars[i + 1] != quote {
+                    s.push('\\');
+                }
+                s.push(chars[i + 1]);
+                i += 2;
+            }
+            c if c == quote => return Ok((s, i + 1)),
+            c => {
+                s.push(c);
+                i += 1;
+            }
+        }
+    }
+    invalid_condition(format!("unterminated {}", quote))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn next_if(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<String> {
+        let mut exprs = vec![self.parse_and()?];
+        while self.next_if(&Token::Or) {
+            exprs.push(self.parse_and()?);
+        }
+        Ok(join(exprs, " OR "))
+    }
+
+    fn parse_and(&mut self) -> Result<String> {
+        let mut exprs = vec![self.parse_primary()?];
+        while self.next_if(&Token::And) {
+            exprs.push(self.parse_primary()?);
+        }
+        Ok(join(exprs, " AND "))
+    }
+
+    fn parse_primary(&mut self) -> Result<String> {
+        match self.next() {
+            Some(Token::LParen) => {
+                let expr = self.parse_or()?;
+                if !self.next_if(&Token::RParen) {
+                    return invalid_condition("missing )");
+                }
+                Ok(expr)
+            }
+            Some(Token::Ident(name)) => self.parse_comparison(name),
+            Some(token) => invalid_condition(format!("expect tag or field, found {:?}", token)),
+            None => invalid_condition("unexpected end"),
+        }
+    }
+
+    fn parse_comparison(&mut self, name: String) -> Result<String> {
+        if name.eq_ignore_ascii_case("time") {
+            return invalid_condition("time conditions must be combined by top level AND");
+        }
+        let op = match self.next() {
+            Some(Token::Op(op)) => op,
+            other => return invalid_condition(format!("expect operator, found {:?}", other)),
+        };
+        let is_regex_op = op == "=~" || op == "!~";
+        let value = match self.next() {
+            Some(Token::Regex(regex)) if is_regex_op => quote_literal(&regex),
+            Some(Token::Str(s)) if !is_regex_op => quote_literal(&s),
+            Some(Token::Number(number)) if !is_regex_op => number,
+            Some(Token::Bool(b)) if !is_regex_op => b.to_string(),
+            other => {
+                return invalid_condition(format!("invalid value {:?} for operator {}", other, op))
+            }
+        };
+        let op = match op {
+            "=~" => "~",
+            "<>" => "!=",
+            op => op,
+        };
+        Ok(format!("{} {} {}", quote_ident(&name), op, value))
+    }
+}
+
+/// Joins the expressions by the operator, multiple expressions are put in parentheses.
+fn join(mut exprs: Vec<String>, op: &str) -> String {
+    if exprs.len() == 1 {
+        exprs.pop().unwrap()
+    } else {
+        format!("({})", exprs.join(op))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translate_condition() {
+        assert_eq!(
+            r#""host" = 'a'"#,
+            translate_condition("host = 'a'").unwrap()
+        );
+        assert_eq!(
+            r#"("host" ~ '^web\d+/' AND ("usage" > -1.5 OR "up" = true))"#,
+            translate_condition(r#""host" =~ /^web\d+\// and (usage > -1.5 OR up = TRUE)"#)
+                .unwrap()
+        );
+        assert_eq!(
+            r#"("region" != 'it''s' AND "region" !~ 'us')"#,
+            translate_condition(r#"region <> 'it\'s' AND region !~ /us/"#).unwrap()
+        );
+        assert_eq!(
+            r#""my ""tag""" = 1000"#,
+            translate_condition(r#""my \"tag\"" = 1e3"#).unwrap()
+        );
+
+        for condition in [
+            "host = 'a'; DROP TABLE cpu",
+            "host = 'a' OR 1 = 1",
+            "host = 'a' OR time > 0",
+            "host = upper('a')",
+            "host = 'a",
+            "(host = 'a'",
+            "host =~ 'a'",
+            "host = /a/",
+            "host = 1x",
+            "host",
+        ] {
+            assert!(translate_condition(condition).is_err(), "{}", condition);
+        }
+    }
+}
@@ -0,0 +1,1227 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Supports the common subset of InfluxQL used by the InfluxDB v1 `/query` endpoint:
+//!
+//! ```text
+//! SELECT <field>[, <function>(<field>) [AS <alias>]...] FROM <measurement>
+//! [WHERE <condition>] [GROUP BY time(<interval>)[, <tag>...]] [fill(null|none)]
+//! [ORDER BY time ASC|DESC] [LIMIT <n>]
+//! ```
+//!
+//! A statement is translated into a SQL, the time range filtering, `GROUP BY` and aggregate
+//! functions are all computed by the query engine, the rows of the result are then grouped into
+//! series and limited here. Empty time intervals are filled here too unless `fill(none)`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::str::FromStr;
+
+use api::v1::codec::SelectResult;
+use api::v1::{Column, ColumnDataType};
+use chrono::{SecondsFormat, TimeZone, Utc};
+use common_grpc::writer::Precision;
+use common_time::timestamp::TimeUnit;
+use common_time::Timestamp;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use snafu::OptionExt;
+
+use crate::error::{self, Result};
+use crate::influxdb::condition::translate_condition;
+use crate::influxdb::INFLUXDB_TIMESTAMP_COLUMN_NAME;
+use crate::query_util::{expand_column, quote_ident};
+
+const TIME_COLUMN: &str = "time";
+/// Max number of intervals a `GROUP BY time()` query could produce for each series.
+const MAX_GROUP_BY_INTERVALS: i64 = 100_000;
+
+static SELECT_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?is)^SELECT\s+(?P<fields>.+?)\s+FROM\s+(?P<from>\S+?)",
+        r"(?:\s+WHERE\s+(?P<where>.+?))?",
+        r"(?:\s+GROUP\s+BY\s+(?P<group_by>.+?))?",
+        r"(?:\s+fill\(\s*(?P<fill>\w+)\s*\))?",
+        r"(?:\s+ORDER\s+BY\s+time\s+(?P<order>ASC|DESC))?",
+        r"(?:\s+LIMIT\s+(?P<limit>\d+))?$",
+    ))
+    .unwrap()
+});
+static FIELD_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?is)^(?:(?P<function>\w+)\(\s*(?P<arg>[^()]+?)\s*\)|(?P<name>\S+))",
+        r"(?:\s+AS\s+(?P<alias>\S+))?$",
+    ))
+    .unwrap()
+});
+static TIME_CONDITION_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?is)^"?time"?\s*(?P<op>>=|<=|>|<|=)\s*(?P<expr>.+)$"#).unwrap());
+static NOW_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^now\(\)(?:\s*(?P<sign>[+-])\s*(?P<duration>\w+))?$").unwrap());
+static GROUP_BY_TIME_PATTERN: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)^time\(\s*(?P<interval>\w+)\s*\)$").unwrap());
+
+/// Request of the InfluxDB v1 `/query` endpoint.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InfluxqlRequest {
+    pub db: String,
+    pub query: String,
+    /// Returns timestamps as epoch in this precision instead of RFC3339 strings.
+    pub epoch: Option<Precision>,
+}
+
+impl InfluxqlRequest {
+    /// Splits the query into statements separated by semicolons.
+    pub fn statements(&self) -> Vec<&str> {
+        split_top_level(&self.query, |rest| rest.starts_with(';').then_some(1))
+            .into_iter()
+            .filter(|s| !s.is_empty())
+            .collect()
+    }
+}
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+pub struct InfluxqlResponse {
+    pub results: Vec<StatementResult>,
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct StatementResult {
+    pub statement_id: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub series: Vec<Series>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl StatementResult {
+    pub fn new(statement_id: usize, result: Result<Vec<Series>>) -> Self {
+        match result {
+            Ok(series) => Self {
+                statement_id,
+                series,
+                error: None,
+            },
+            Err(e) => Self {
+                statement_id,
+                series: vec![],
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Series {
+    pub name: String,
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub tags: BTreeMap<String, String>,
+    pub columns: Vec<String>,
+    pub values: Vec<Vec<JsonValue>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Function {
+    Count,
+    Sum,
+    Mean,
+    Median,
+    Min,
+    Max,
+    First,
+    Last,
+    Spread,
+}
+
+impl Function {
+    fn parse(name: &str) -> Result<Self> {
+        let function = match name.to_lowercase().as_str() {
+            "count" => Function::Count,
+            "sum" => Function::Sum,
+            "mean" => Function::Mean,
+            "median" => Function::Median,
+            "min" => Function::Min,
+            "max" => Function::Max,
+            "first" => Function::First,
+            "last" => Function::Last,
+            "spread" => Function::Spread,
+            _ => return invalid_query(format!("unsupported function: {}", name)),
+        };
+        Ok(function)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Function::Count => "count",
+            Function::Sum => "sum",
+            Function::Mean => "mean",
+            Function::Median => "median",
+            Function::Min => "min",
+            Function::Max => "max",
+            Function::First => "first",
+            Function::Last => "last",
+            Function::Spread => "spread",
+        }
+    }
+
+    /// Returns the SQL aggregating `column`, `first` and `last` aggregate the column `picked`,
+    /// the value picked by the `first_value` window function.
+    fn to_sql(&self, column: &str, picked: &str) -> String {
+        match self {
+            Function::Count => format!("count({})", column),
+            Function::Sum => format!("sum({})", column),
+            Function::Mean => format!("avg({})", column),
+            Function::Median => format!("median({})", column),
+            Function::Min => format!("min({})", column),
+            Function::Max => format!("max({})", column),
+            // All rows of a group have the same picked value.
+            Function::First | Function::Last => format!("max({})", picked),
+            Function::Spread => format!("max({}) - min({})", column, column),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Field {
+    function: Option<Function>,
+    name: String,
+    alias: String,
+}
+
+/// A parsed InfluxQL `SELECT` statement, timestamps are in nanoseconds.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SelectStatement {
+    /// Empty means `SELECT *`.
+    fields: Vec<Field>,
+    measurement: String,
+    /// SQL condition translated from the non-time conditions.
+    condition: Option<String>,
+    /// Inclusive time range.
+    start: Option<i64>,
+    end: Option<i64>,
+    interval: Option<i64>,
+    group_by_tags: Vec<String>,
+    group_by_all_tags: bool,
+    fill_none: bool,
+    descending: bool,
+    limit: Option<usize>,
+}
+
+impl SelectStatement {
+    /// Parses the statement, relative times like `now() - 1h` are resolved against `now_nanos`.
+    pub fn parse(statement: &str, now_nanos: i64) -> Result<Self> {
+        let statement = statement.trim();
+        let captures =
+            SELECT_PATTERN
+                .captures(statement)
+                .with_context(|| error::InvalidQuerySnafu {
+                    reason: format!("unsupported statement: {}", statement),
+                })?;
+
+        let fields = parse_fields(&captures["fields"])?;
+        let measurement = captures["from"]
+            .rsplit('.')
+            .next()
+            .map(unquote)
+            .unwrap_or_default();
+
+        let mut select = Self {
+            fields,
+            measurement,
+            condition: None,
+            start: None,
+            end: None,
+            interval: None,
+            group_by_tags: vec![],
+            group_by_all_tags: false,
+            fill_none: false,
+            descending: false,
+            limit: None,
+        };
+        if let Some(condition) = captures.name("where") {
+            select.parse_condition(condition.as_str(), now_nanos)?;
+        }
+        if let Some(group_by) = captures.name("group_by") {
+            select.parse_group_by(group_by.as_str())?;
+        }
+        if let Some(fill) = captures.name("fill") {
+            select.fill_none = match fill.as_str().to_lowercase().as_str() {
+                "none" => true,
+                "null" => false,
+                other => return invalid_query(format!("unsupported fill option: {}", other)),
+            };
+        }
+        select.descending = captures
+            .name("order")
+            .map(|o| o.as_str().eq_ignore_ascii_case("DESC"))
+            .unwrap_or(false);
+        select.limit = captures.name("limit").and_then(|l| l.as_str().parse().ok());
+
+        if select.interval.is_some() && !select.is_aggregate() {
+            return invalid_query("GROUP BY requires at least one aggregate function");
+        }
+        if select.is_aggregate() && select.fields.iter().any(|f| f.function.is_none()) {
+            return invalid_query("mixing aggregate and non-aggregate queries is not supported");
+        }
+        if select.is_aggregate() && select.interval.is_some() && select.start.is_none() {
+            return invalid_query(
+                "aggregate functions with GROUP BY time require a WHERE time clause",
+            );
+        }
+        // Grouping by time intervals without upper bound ends at now, like InfluxDB does.
+        if select.interval.is_some() && select.end.is_none() {
+            select.end = Some(now_nanos);
+        }
+        if let (Some(interval), Some(start), Some(end)) =
+            (select.interval, select.start, select.end)
+        {
+            if !select.fill_none && (end - start) / interval >= MAX_GROUP_BY_INTERVALS {
+                return invalid_query(format!(
+                    "too many intervals in GROUP BY time, max: {}",
+                    MAX_GROUP_BY_INTERVALS
+                ));
+            }
+        }
+        Ok(select)
+    }
+
+    pub fn measurement(&self) -> &str {
+        &self.measurement
+    }
+
+    fn is_aggregate(&self) -> bool {
+        self.fields.iter().any(|f| f.function.is_some())
+    }
+
+    fn parse_condition(&mut self, condition: &str, now_nanos: i64) -> Result<()> {
+        let has_top_level_or = split_top_level(condition, |rest| keyword_len(rest, "OR")).len() > 1;
+        if has_top_level_or {
+            self.condition = Some(translate_condition(condition)?);
+            return Ok(());
+        }
+
+        let mut conditions = Vec::new();
+        for term in split_top_level(condition, |rest| keyword_len(rest, "AND")) {
+            let captures = match TIME_CONDITION_PATTERN.captures(term) {
+                Some(captures) => captures,
+                None => {
+                    conditions.push(translate_condition(term)?);
+                    continue;
+                }
+            };
+            let ts = parse_time_expr(captures["expr"].trim(), now_nanos)?;
+            match &captures["op"] {
+                ">" => self.start = Some(self.start.map_or(ts + 1, |s| s.max(ts + 1))),
+                ">=" => self.start = Some(self.start.map_or(ts, |s| s.max(ts))),
+                "<" => self.end = Some(self.end.map_or(ts - 1, |e| e.min(ts - 1))),
+                "<=" => self.end = Some(self.end.map_or(ts, |e| e.min(ts))),
+                _ => {
+                    self.start = Some(self.start.map_or(ts, |s| s.max(ts)));
+                    self.end = Some(self.end.map_or(ts, |e| e.min(ts)));
+                }
+            }
+        }
+        if !conditions.is_empty() {
+            self.condition = Some(conditions.join(" AND "));
+        }
+        Ok(())
+    }
+
+    fn parse_group_by(&mut self, group_by: &str) -> Result<()> {
+        for dimension in split_top_level(group_by, |rest| rest.starts_with(',').then_some(1)) {
+            if let Some(captures) = GROUP_BY_TIME_PATTERN.captures(dimension) {
+                let interval = parse_duration_nanos(&captures["interval"])?;
+                if interval <= 0 {
+                    return invalid_query(format!("invalid interval: {}", dimension));
+                }
+                self.interval = Some(interval);
+            } else if dimension == "*" {
+                self.group_by_all_tags = true;
+            } else {
+                self.group_by_tags.push(unquote(dimension));
+            }
+        }
+        Ok(())
+    }
+
+    fn group_by_tags(&self, tag_names: &[String]) -> Vec<String> {
+        if self.group_by_all_tags {
+            tag_names.to_vec()
+        } else {
+            self.group_by_tags.clone()
+        }
+    }
+
+    /// Generates a SQL querying the measurement in `db`, `tag_names` are all the tags of the
+    /// measurement.
+    ///
+    /// Raw queries select all the columns of rows in time order. Aggregate queries select the
+    /// group by tags, the time interval as the timestamp column if grouped by time, and the
+    /// aggregated fields as columns `f0`, `f1` and so on.
+    pub fn to_sql(&self, db: &str, tag_names: &[String]) -> String {
+        let ts = INFLUXDB_TIMESTAMP_COLUMN_NAME;
+        let mut conditions = Vec::new();
+        if let Some(start) = self.start {
+            conditions.push(format!("{}>={}", ts, time_literal(start)));
+        }
+        if let Some(end) = self.end {
+            conditions.push(format!("{}<={}", ts, time_literal(end)));
+        }
+        if let Some(condition) = &self.condition {
+            conditions.push(format!("({})", condition));
+        }
+        let mut source = format!("{}.{}", quote_ident(db), quote_ident(&self.measurement));
+        if !conditions.is_empty() {
+            source = format!("{} WHERE {}", source, conditions.join(" AND "));
+        }
+        if !self.is_aggregate() {
+            return format!("SELECT * FROM {} ORDER BY {}", source, ts);
+        }
+
+        // Pairs of the grouping expression and its output name.
+        let mut keys = self
+            .group_by_tags(tag_names)
+            .iter()
+            .map(|tag| (quote_ident(tag), quote_ident(tag)))
+            .collect::<Vec<_>>();
+        if let Some(interval) = self.interval {
+            keys.push((
+                format!("time_bucket('{}ns', {})", interval, ts),
+                ts.to_string(),
+            ));
+        }
+        let group_exprs = keys
+            .iter()
+            .map(|(expr, _)| expr.as_str())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        // Values of `first` and `last` are picked by window functions ignoring nulls first.
+        let mut picks = Vec::new();
+        let mut projection = keys
+            .iter()
+            .map(|(expr, name)| {
+                if expr == name {
+                    expr.clone()
+                } else {
+                    format!("{} AS {}", expr, name)
+                }
+            })
+            .collect::<Vec<_>>();
+        for (i, field) in self.fields.iter().enumerate() {
+            // Fields are checked to be all aggregated, see `SelectStatement::parse`.
+            let function = field.function.unwrap();
+            let column = quote_ident(&field.name);
+            let picked = format!("\"__pick{}\"", i);
+            let direction = match function {
+                Function::First => "ASC",
+                Function::Last => "DESC",
+                _ => "",
+            };
+            if !direction.is_empty() {
+                let partition = if group_exprs.is_empty() {
+                    String::new()
+                } else {
+                    format!("PARTITION BY {} ", group_exprs)
+                };
+                picks.push(format!(
+                    "first_value({}) OVER ({}ORDER BY {} IS NULL, {} {}) AS {}",
+                    column, partition, column, ts, direction, picked
+                ));
+            }
+            projection.push(format!(
+                "{} AS \"f{}\"",
+                function.to_sql(&column, &picked),
+                i
+            ));
+        }
+        let from = if picks.is_empty() {
+            source
+        } else {
+            format!("(SELECT *, {} FROM {}) AS t", picks.join(", "), source)
+        };
+
+        let mut sql = format!("SELECT {} FROM {}", projection.join(", "), from);
+        if group_exprs.is_empty() {
+            // Aggregating no rows yields no series, like InfluxDB does.
+            sql.push_str(" HAVING count(*) > 0");
+        } else {
+            sql.push_str(&format!(" GROUP BY {}", group_exprs));
+        }
+        if self.interval.is_some() {
+            sql.push_str(&format!(" ORDER BY {}", ts));
+        }
+        sql
+    }
+
+    /// Converts the result of the SQL generated by [SelectStatement::to_sql] to InfluxDB series.
+    pub fn to_series(
+        &self,
+        select_result: SelectResult,
+        tag_names: &[String],
+        epoch: Option<Precision>,
+    ) -> Result<Vec<Series>> {
+        let row_count = select_result.row_count as usize;
+        let timestamps = match select_result
+            .columns
+            .iter()
+            .find(|c| c.column_name == INFLUXDB_TIMESTAMP_COLUMN_NAME)
+        {
+            Some(ts_column) => timestamp_column_nanos(ts_column, row_count),
+            // Aggregates without GROUP BY time are at the start of the time range.
+            None if self.is_aggregate() && self.interval.is_none() => {
+                vec![Some(self.start.unwrap_or(0)); row_count]
+            }
+            None => {
+                return error::InternalSnafu {
+                    err_msg: "missing ts column in query result",
+                }
+                .fail()
+            }
+        };
+
+        let columns = select_result
+            .columns
+            .iter()
+            .filter(|c| c.column_name != INFLUXDB_TIMESTAMP_COLUMN_NAME)
+            .map(|c| (c.column_name.as_str(), column_json_values(c, row_count)))
+            .collect::<HashMap<_, _>>();
+        let group_by_tags = self.group_by_tags(tag_names);
+
+        let fields = if self.fields.is_empty() {
+            // Selects all fields and tags not grouped by, in alphabetical order like InfluxDB.
+            let mut names = columns
+                .keys()
+                .map(|name| name.to_string())
+                .filter(|name| !group_by_tags.contains(name))
+                .collect::<Vec<_>>();
+            names.sort();
+            names
+                .into_iter()
+                .map(|name| Field {
+                    function: None,
+                    alias: name.clone(),
+                    name,
+                })
+                .collect()
+        } else {
+            self.fields.clone()
+        };
+        let field_values = fields
+            .iter()
+            .enumerate()
+            .map(|(i, f)| {
+                let name = if f.function.is_some() {
+                    format!("f{}", i)
+                } else {
+                    f.name.clone()
+                };
+                columns.get(name.as_str())
+            })
+            .collect::<Vec<_>>();
+
+        // Groups rows by the values of group by tags, rows are ordered by time.
+        let mut groups: BTreeMap<BTreeMap<String, String>, Vec<(i64, Vec<JsonValue>)>> =
+            BTreeMap::new();
+        for (row, ts) in timestamps.iter().enumerate() {
+            let ts = match ts {
+                Some(ts) => *ts,
+                None => continue,
+            };
+            let tags = group_by_tags
+                .iter()
+                .map(|tag| {
+                    let value = columns
+                        .get(tag.as_str())
+                        .and_then(|values| values[row].as_str())
+                        .unwrap_or_default();
+                    (tag.clone(), value.to_string())
+                })
+                .collect();
+            let values = field_values
+                .iter()
+                .map(|values| values.map_or(JsonValue::Null, |v| v[row].clone()))
+                .collect();
+            groups.entry(tags).or_default().push((ts, values));
+        }
+
+        let mut column_names = vec![TIME_COLUMN.to_string()];
+        column_names.extend(fields.iter().map(|f| f.alias.clone()));
+
+        let mut series = Vec::with_capacity(groups.len());
+        for (tags, rows) in groups {
+            let mut rows = if self.is_aggregate() {
+                self.fill(&fields, rows)
+            } else {
+                rows.into_iter()
+                    .filter(|(_, values)| values.iter().any(|v| !v.is_null()))
+                    .collect()
+            };
+            if self.descending {
+                rows.reverse();
+            }
+            if let Some(limit) = self.limit {
+                rows.truncate(limit);
+            }
+            if rows.is_empty() {
+                continue;
+            }
+
+            let values = rows
+                .into_iter()
+                .map(|(ts, values)| {
+                    let mut row = Vec::with_capacity(values.len() + 1);
+                    row.push(format_time(ts, epoch));
+                    row.extend(values);
+                    row
+                })
+                .collect();
+            series.push(Series {
+                name: self.measurement.clone(),
+                tags,
+                columns: column_names.clone(),
+                values,
+            });
+        }
+        Ok(series)
+    }
+
+    /// Fills the time intervals without rows in the time range, unless `fill(none)`. Counts of
+    /// the filled intervals are zero, and other aggregated values are null.
+    fn fill(
+        &self,
+        fields: &[Field],
+        rows: Vec<(i64, Vec<JsonValue>)>,
+    ) -> Vec<(i64, Vec<JsonValue>)> {
+        let interval = match self.interval {
+            Some(interval) if !self.fill_none => interval,
+            _ => return rows,
+        };
+
+        let empty_values = fields
+            .iter()
+            .map(|f| match f.function {
+                Some(Function::Count) => JsonValue::from(0),
+                _ => JsonValue::Null,
+            })
+            .collect::<Vec<_>>();
+        // Both bounds are set when grouping by time, see `SelectStatement::parse`.
+        let first = self.start.unwrap_or(0).div_euclid(interval) * interval;
+        let last = self.end.unwrap_or(0).div_euclid(interval) * interval;
+        let mut buckets = BTreeMap::new();
+        let mut bucket = first;
+        while bucket <= last {
+            buckets.insert(bucket, empty_values.clone());
+            bucket += interval;
+        }
+        buckets.extend(rows);
+        buckets.into_iter().collect()
+    }
+}
+
+/// Formats the timestamp in nanoseconds as a SQL literal in RFC3339.
+fn time_literal(ts: i64) -> String {
+    format!(
+        "'{}'",
+        Utc.timestamp_nanos(ts)
+            .to_rfc3339_opts(SecondsFormat::Nanos, true)
+    )
+}
+
+fn invalid_query<T>(reason: impl Into<String>) -> Result<T> {
+    error::InvalidQuerySnafu {
+        reason: reason.into(),
+    }
+    .fail()
+}
+
+fn parse_fields(fields: &str) -> Result<Vec<Field>> {
+    if fields.trim() == "*" {
+        return Ok(vec![]);
+    }
+
+    split_top_level(fields, |rest| rest.starts_with(',').then_some(1))
+        .into_iter()
+        .map(|field| {
+            let captures =
+                FIELD_PATTERN
+                    .captures(field)
+                    .with_context(|| error::InvalidQuerySnafu {
+                        reason: format!("invalid field: {}", field),
+                    })?;
+            let (function, name) = match captures.name("function") {
+                Some(function) => (
+                    Some(Function::parse(function.as_str())?),
+                    unquote(&captures["arg"]),
+                ),
+                None => (None, unquote(&captures["name"])),
+            };
+            let alias = match (captures.name("alias"), function) {
+                (Some(alias), _) => unquote(alias.as_str()),
+                (None, Some(function)) => function.name().to_string(),
+                (None, None) => name.clone(),
+            };
+            Ok(Field {
+                function,
+                name,
+                alias,
+            })
+        })
+        .collect()
+}
+
+/// Splits `s` by the separators outside of quotes and parentheses, `separator` returns the
+/// length of the separator at the start of the rest string if matches.
+fn split_top_level(s: &str, separator: impl Fn(&str) -> Option<usize>) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut depth = 0;
+    let mut part_start = 0;
+    let mut chars = s.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match (quote, c) {
+            (Some(q), _) if c == q => quote = None,
+            (Some(_), '\\') => {
+                chars.next();
+            }
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') => depth -= 1,
+            (None, _) if depth == 0 => {
+                if let Some(len) = separator(&s[i..]) {
+                    parts.push(s[part_start..i].trim());
+                    part_start = i + len;
+                    while chars.peek().map_or(false, |(j, _)| *j < part_start) {
+                        chars.next();
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    parts.push(s[part_start..].trim());
+    parts
+}
+
+/// Returns the length of `keyword` and the whitespace around it if `rest` starts with it.
+fn keyword_len(rest: &str, keyword: &str) -> Option<usize> {
+    let trimmed = rest.trim_start();
+    let leading = rest.len() - trimmed.len();
+    if leading == 0 || trimmed.len() <= keyword.len() {
+        return None;
+    }
+    let (word, after) = trimmed.split_at(keyword.len());
+    let followed_by_space = after.starts_with(char::is_whitespace) || after.starts_with('(');
+    if word.eq_ignore_ascii_case(keyword) && followed_by_space {
+        Some(leading + keyword.len())
+    } else {
+        None
+    }
+}
+
+fn unquote(ident: &str) -> String {
+    let ident = ident.trim();
+    ident
+        .strip_prefix('"')
+        .and_then(|s| s.strip_suffix('"'))
+        .map(|s| s.replace("\\\"", "\""))
+        .unwrap_or_else(|| ident.to_string())
+}
+
+/// Parses time expressions like `now() - 1h`, `'2022-09-22T09:54:56Z'`, `1663840496000000000`
+/// and `1663840496s` into nanoseconds.
+fn parse_time_expr(expr: &str, now_nanos: i64) -> Result<i64> {
+    if let Some(captures) = NOW_PATTERN.captures(expr) {
+        let offset = match captures.name("duration") {
+            Some(duration) => parse_duration_nanos(duration.as_str())?,
+            None => 0,
+        };
+        return Ok(match captures.name("sign").map(|s| s.as_str()) {
+            Some("-") => now_nanos - offset,
+            _ => now_nanos + offset,
+        });
+    }
+    if let Some(text) = expr.strip_prefix('\'').and_then(|s| s.strip_suffix('\'')) {
+        return Timestamp::from_str(text)
+            .map(|ts| ts.convert_to(TimeUnit::Nanosecond))
+            .ok()
+            .with_context(|| error::InvalidQuerySnafu {
+                reason: format!("invalid time: {}", expr),
+            });
+    }
+    if let Ok(ts) = expr.parse::<i64>() {
+        return Ok(ts);
+    }
+    parse_duration_nanos(expr)
+}
+
+/// Parses InfluxQL durations like `10s` and `1h` into nanoseconds.
+fn parse_duration_nanos(duration: &str) -> Result<i64> {
+    let invalid_duration = || error::InvalidQuerySnafu {
+        reason: format!("invalid duration: {}", duration),
+    };
+
+    let unit_start = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .with_context(invalid_duration)?;
+    let (value, unit) = duration.split_at(unit_start);
+    let value = value.parse::<i64>().ok().with_context(invalid_duration)?;
+    let unit_nanos = match unit {
+        "ns" => 1,
+        "u" | "µ" => 1_000,
+        "ms" => 1_000_000,
+        "s" => 1_000_000_000,
+        "m" => 60 * 1_000_000_000,
+        "h" => 60 * 60 * 1_000_000_000,
+        "d" => 24 * 60 * 60 * 1_000_000_000,
+        "w" => 7 * 24 * 60 * 60 * 1_000_000_000,
+        _ => return invalid_duration().fail(),
+    };
+    Ok(value * unit_nanos)
+}
+
+fn format_time(ts: i64, epoch: Option<Precision>) -> JsonValue {
+    let divisor = match epoch {
+        None => {
+            return Utc
+                .timestamp_nanos(ts)
+                .to_rfc3339_opts(SecondsFormat::AutoSi, true)
+                .into()
+        }
+        Some(Precision::NANOSECOND) => 1,
+        Some(Precision::MICROSECOND) => 1_000,
+        Some(Precision::MILLISECOND) => 1_000_000,
+        Some(Precision::SECOND) => 1_000_000_000,
+        Some(Precision::MINUTE) => 60 * 1_000_000_000,
+        Some(Precision::HOUR) => 60 * 60 * 1_000_000_000,
+    };
+    JsonValue::from(ts.div_euclid(divisor))
+}
+
+/// Returns timestamps of the column in nanoseconds, with `None` for nulls.
+fn timestamp_column_nanos(column: &Column, row_count: usize) -> Vec<Option<i64>> {
+    let values = match column.values.as_ref() {
+        Some(values) => values,
+        None => return vec![None; row_count],
+    };
+    let (values, factor) = match ColumnDataType::from_i32(column.datatype) {
+        Some(ColumnDataType::TimestampSecond) => (&values.ts_second_values, 1_000_000_000),
+        Some(ColumnDataType::TimestampMicrosecond) => (&values.ts_microsecond_values, 1_000),
+        Some(ColumnDataType::TimestampNanosecond) => (&values.ts_nanosecond_values, 1),
+        Some(ColumnDataType::Int64) => (&values.i64_values, 1_000_000),
+        _ => (&values.ts_millis_values, 1_000_000),
+    };
+    expand_column(column, values, row_count)
+        .into_iter()
+        .map(|ts| ts.map(|ts| ts * factor))
+        .collect()
+}
+
+/// Returns values of the field or tag column as json values, with null for nulls.
+fn column_json_values(column: &Column, row_count: usize) -> Vec<JsonValue> {
+    let values = match column.values.as_ref() {
+        Some(values) => values,
+        None => return vec![JsonValue::Null; row_count],
+    };
+    let values = match ColumnDataType::from_i32(column.datatype) {
+        Some(ColumnDataType::Float64) => expand_column(column, &values.f64_values, row_count)
+            .into_iter()
+            .map(JsonValue::from)
+            .collect(),
+        Some(ColumnDataType::Float32) => expand_column(column, &values.f32_values, row_count)
+            .into_iter()
+            .map(JsonValue::from)
+            .collect(),
+        Some(ColumnDataType::Int64) => expand_column(column, &values.i64_values, row_count)
+            .into_iter()
+            .map(JsonValue::from)
+            .collect(),
+        Some(ColumnDataType::Uint64) => expand_column(column, &values.u64_values, row_count)
+            .into_iter()
+            .map(JsonValue::from)
+            .collect(),
+        Some(ColumnDataType::Boolean) => expand_column(column, &values.bool_values, row_count)
+            .into_iter()
+            .map(JsonValue::from)
+            .collect(),
+        Some(ColumnDataType::String) => expand_column(column, &values.string_values, row_count)
+            .into_iter()
+            .map(JsonValue::from)
+            .collect(),
+        _ => vec![JsonValue::Null; row_count],
+    };
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::column::{SemanticType, Values};
+    use common_base::BitVec;
+
+    use super::*;
+
+    const NOW: i64 = 1663840800000000000;
+
+    #[test]
+    fn test_split_statements() {
+        let request = InfluxqlRequest {
+            db: "public".to_string(),
+            query: "SELECT * FROM cpu WHERE host = 'a;b'; SELECT * FROM mem;".to_string(),
+            epoch: None,
+        };
+        assert_eq!(
+            vec!["SELECT * FROM cpu WHERE host = 'a;b'", "SELECT * FROM mem"],
+            request.statements()
+        );
+    }
+
+    #[test]
+    fn test_parse_select() {
+        let select = SelectStatement::parse(
+            r#"SELECT mean("usage") AS avg_usage, max(usage) FROM "telegraf"."autogen"."cpu" WHERE time > now() - 1h AND "host" =~ /^web/ AND region = 'us and eu' GROUP BY time(1m), "host" fill(none) ORDER BY time DESC LIMIT 10"#,
+            NOW,
+        )
+        .unwrap();
+        assert_eq!(
+            vec![
+                Field {
+                    function: Some(Function::Mean),
+                    name: "usage".to_string(),
+                    alias: "avg_usage".to_string(),
+                },
+                Field {
+                    function: Some(Function::Max),
+                    name: "usage".to_string(),
+                    alias: "max".to_string(),
+                }
+            ],
+            select.fields
+        );
+        assert_eq!("cpu", select.measurement);
+        assert_eq!(Some(NOW - 3_600_000_000_000 + 1), select.start);
+        assert_eq!(Some(NOW), select.end);
+        assert_eq!(
+            Some(r#""host" ~ '^web' AND "region" = 'us and eu'"#.to_string()),
+            select.condition
+        );
+        assert_eq!(Some(60_000_000_000), select.interval);
+        assert_eq!(vec!["host".to_string()], select.group_by_tags);
+        assert!(select.fill_none);
+        assert!(select.descending);
+        assert_eq!(Some(10), select.limit);
+
+        let select = SelectStatement::parse(
+            "select * from cpu where time >= '2022-09-22T09:00:00Z' and time < 1663840800000000000",
+            NOW,
+        )
+        .unwrap();
+        assert!(select.fields.is_empty());
+        assert_eq!(Some(1663837200000000000), select.start);
+        assert_eq!(Some(NOW - 1), select.end);
+        assert_eq!(None, select.condition);
+
+        let select =
+            SelectStatement::parse("SELECT usage FROM cpu WHERE host = 'a' OR host = 'b'", NOW)
+                .unwrap();
+        assert_eq!(
+            Some(r#"("host" = 'a' OR "host" = 'b')"#.to_string()),
+            select.condition
+        );
+        assert_eq!(None, select.start);
+        assert!(
+            SelectStatement::parse("SELECT usage FROM cpu WHERE host = 'a' OR time > 0", NOW)
+                .is_err()
+        );
+        assert!(
+            SelectStatement::parse("SELECT usage FROM cpu WHERE host = 'a') OR (1 = 1", NOW)
+                .is_err()
+        );
+        assert!(SelectStatement::parse("SELECT mean(usage), usage FROM cpu", NOW).is_err());
+        assert!(SelectStatement::parse(
+            "SELECT mean(usage) FROM cpu WHERE time > 0 GROUP BY time(1s)",
+            NOW
+        )
+        .is_err());
+
+        assert!(SelectStatement::parse("SHOW DATABASES", NOW).is_err());
+        assert!(SelectStatement::parse("SELECT stddev(usage) FROM cpu", NOW).is_err());
+        assert!(SelectStatement::parse("SELECT usage FROM cpu GROUP BY time(1m)", NOW).is_err());
+        assert!(
+            SelectStatement::parse("SELECT mean(usage) FROM cpu GROUP BY time(1m)", NOW).is_err()
+        );
+    }
+
+    #[test]
+    fn test_to_sql() {
+        let tag_names = vec!["host".to_string(), "dc".to_string()];
+        let select = SelectStatement::parse(
+            "SELECT usage FROM cpu WHERE time >= 1663837200000000001 AND host = 'a'",
+            NOW,
+        )
+        .unwrap();
+        assert_eq!(
+            r#"SELECT * FROM "public"."cpu" WHERE ts>='2022-09-22T09:00:00.000000001Z' AND ("host" = 'a') ORDER BY ts"#,
+            select.to_sql("public", &tag_names)
+        );
+
+        let select = SelectStatement::parse(
+            "SELECT mean(usage), spread(usage) FROM cpu WHERE time >= 1663837200000000000 GROUP BY time(1m), * fill(none)",
+            NOW,
+        )
+        .unwrap();
+        assert_eq!(
+            r#"SELECT "host", "dc", time_bucket('60000000000ns', ts) AS ts, avg("usage") AS "f0", max("usage") - min("usage") AS "f1" FROM "public"."cpu" WHERE ts>='2022-09-22T09:00:00.000000000Z' AND ts<='2022-09-22T10:00:00.000000000Z' GROUP BY "host", "dc", time_bucket('60000000000ns', ts) ORDER BY ts"#,
+            select.to_sql("public", &tag_names)
+        );
+
+        let select = SelectStatement::parse(
+            "SELECT first(usage) AS f, count(usage) FROM cpu GROUP BY host",
+            NOW,
+        )
+        .unwrap();
+        assert_eq!(
+            r#"SELECT "host", max("__pick0") AS "f0", count("usage") AS "f1" FROM (SELECT *, first_value("usage") OVER (PARTITION BY "host" ORDER BY "usage" IS NULL, ts ASC) AS "__pick0" FROM "public"."cpu") AS t GROUP BY "host""#,
+            select.to_sql("public", &tag_names)
+        );
+
+        let select = SelectStatement::parse("SELECT last(usage) FROM cpu", NOW).unwrap();
+        assert_eq!(
+            r#"SELECT max("__pick0") AS "f0" FROM (SELECT *, first_value("usage") OVER (ORDER BY "usage" IS NULL, ts DESC) AS "__pick0" FROM "public"."cpu") AS t HAVING count(*) > 0"#,
+            select.to_sql("public", &tag_names)
+        );
+    }
+
+    fn mock_select_result() -> SelectResult {
+        let ts = Column {
+            column_name: INFLUXDB_TIMESTAMP_COLUMN_NAME.to_string(),
+            semantic_type: SemanticType::Timestamp as i32,
+            values: Some(Values {
+                ts_nanosecond_values: vec![
+                    NOW - 90_000_000_000,
+                    NOW - 80_000_000_000,
+                    NOW - 70_000_000_000,
+                    NOW - 10_000_000_000,
+                ],
+                ..Default::default()
+            }),
+            datatype: ColumnDataType::TimestampNanosecond as i32,
+            ..Default::default()
+        };
+        let host = Column {
+            column_name: "host".to_string(),
+            semantic_type: SemanticType::Tag as i32,
+            values: Some(Values {
+                string_values: vec![
+                    "a".to_string(),
+                    "b".to_string(),
+                    "a".to_string(),
+                    "a".to_string(),
+                ],
+                ..Default::default()
+            }),
+            datatype: ColumnDataType::String as i32,
+            ..Default::default()
+        };
+        let mut null_mask = BitVec::repeat(false, 4);
+        null_mask.set(3, true);
+        let usage = Column {
+            column_name: "usage".to_string(),
+            semantic_type: SemanticType::Field as i32,
+            values: Some(Values {
+                f64_values: vec![1.0, 2.0, 3.0],
+                ..Default::default()
+            }),
+            null_mask: null_mask.into_vec(),
+            datatype: ColumnDataType::Float64 as i32,
+            datatype_extension: None,
+        };
+        SelectResult {
+            columns: vec![ts, host, usage],
+            row_count: 4,
+        }
+    }
+
+    #[test]
+    fn test_raw_series() {
+        let tag_names = vec!["host".to_string()];
+        let select = SelectStatement::parse("SELECT * FROM cpu", NOW).unwrap();
+        let series = select
+            .to_series(mock_select_result(), &tag_names, Some(Precision::SECOND))
+            .unwrap();
+        assert_eq!(
+            vec![Series {
+                name: "cpu".to_string(),
+                tags: BTreeMap::new(),
+                columns: vec!["time".to_string(), "host".to_string(), "usage".to_string()],
+                values: vec![
+                    vec![1663840710.into(), "a".into(), 1.0.into()],
+                    vec![1663840720.into(), "b".into(), 2.0.into()],
+                    vec![1663840730.into(), "a".into(), 3.0.into()],
+                    vec![1663840790.into(), "a".into(), JsonValue::Null],
+                ],
+            }],
+            series
+        );
+
+        let select =
+            SelectStatement::parse("SELECT usage FROM cpu GROUP BY host LIMIT 1", NOW).unwrap();
+        let series = select
+            .to_series(mock_select_result(), &tag_names, None)
+            .unwrap();
+        assert_eq!(2, series.len());
+        assert_eq!(
+            BTreeMap::from([("host".to_string(), "a".to_string())]),
+            series[0].tags
+        );
+        assert_eq!(
+            vec![vec![
+                JsonValue::from("2022-09-22T09:58:30Z"),
+                JsonValue::from(1.0)
+            ]],
+            series[0].values
+        );
+
+        let select = SelectStatement::parse("SELECT * FROM cpu GROUP BY *", NOW).unwrap();
+        let series = select
+            .to_series(mock_select_result(), &tag_names, None)
+            .unwrap();
+        assert_eq!(2, series.len());
+        assert_eq!(
+            vec!["time".to_string(), "usage".to_string()],
+            series[1].columns
+        );
+        assert_eq!(
+            BTreeMap::from([("host".to_string(), "b".to_string())]),
+            series[1].tags
+        );
+    }
+
+    fn mock_aggregate_result(
+        timestamps: Option<Vec<i64>>,
+        f0: Vec<f64>,
+        f1: Vec<i64>,
+    ) -> SelectResult {
+        let row_count = f0.len() as u32;
+        let mut columns = vec![
+            Column {
+                column_name: "f0".to_string(),
+                values: Some(Values {
+                    f64_values: f0,
+                    ..Default::default()
+                }),
+                datatype: ColumnDataType::Float64 as i32,
+                ..Default::default()
+            },
+            Column {
+                column_name: "f1".to_string(),
+                values: Some(Values {
+                    i64_values: f1,
+                    ..Default::default()
+                }),
+                datatype: ColumnDataType::Int64 as i32,
+                ..Default::default()
+            },
+        ];
+        if let Some(timestamps) = timestamps {
+            columns.push(Column {
+                column_name: INFLUXDB_TIMESTAMP_COLUMN_NAME.to_string(),
+                values: Some(Values {
+                    ts_nanosecond_values: timestamps,
+                    ..Default::default()
+                }),
+                datatype: ColumnDataType::TimestampNanosecond as i32,
+                ..Default::default()
+            });
+        }
+        SelectResult { columns, row_count }
+    }
+
+    #[test]
+    fn test_aggregate_series() {
+        let select = SelectStatement::parse(
+            "SELECT mean(usage), count(usage) FROM cpu WHERE time > now() - 2m GROUP BY time(1m)",
+            NOW,
+        )
+        .unwrap();
+        let result = mock_aggregate_result(Some(vec![NOW - 120_000_000_000]), vec![2.0], vec![3]);
+        let series = select
+            .to_series(result, &[], Some(Precision::SECOND))
+            .unwrap();
+        assert_eq!(1, series.len());
+        assert_eq!(
+            vec!["time".to_string(), "mean".to_string(), "count".to_string()],
+            series[0].columns
+        );
+        assert_eq!(
+            vec![
+                vec![1663840680.into(), 2.0.into(), 3.into()],
+                vec![1663840740.into(), JsonValue::Null, 0.into()],
+                vec![1663840800.into(), JsonValue::Null, 0.into()],
+            ],
+            series[0].values
+        );
+
+        // not filled
+        let select = SelectStatement::parse(
+            "SELECT mean(usage), count(usage) FROM cpu WHERE time > now() - 2m GROUP BY time(1m) fill(none) ORDER BY time DESC",
+            NOW,
+        )
+        .unwrap();
+        let result = mock_aggregate_result(
+            Some(vec![NOW - 120_000_000_000, NOW]),
+            vec![2.0, 1.0],
+            vec![3, 1],
+        );
+        let series = select
+            .to_series(result, &[], Some(Precision::SECOND))
+            .unwrap();
+        assert_eq!(
+            vec![
+                vec![1663840800.into(), 1.0.into(), 1.into()],
+                vec![1663840680.into(), 2.0.into(), 3.into()],
+            ],
+            series[0].values
+        );
+
+        let select = SelectStatement::parse(
+            "SELECT spread(usage), count(usage) FROM cpu WHERE time >= 1663840710000000000",
+            NOW,
+        )
+        .unwrap();
+        let result = mock_aggregate_result(None, vec![2.0], vec![3]);
+        let series = select
+            .to_series(result, &[], Some(Precision::NANOSECOND))
+            .unwrap();
+        assert_eq!(
+            vec![vec![1663840710000000000i64.into(), 2.0.into(), 3.into()]],
+            series[0].values
+        );
+    }
+
+    #[test]
+    fn test_statement_result_json() {
+        let result = InfluxqlResponse {
+            results: vec![
+                StatementResult::new(0, Ok(vec![])),
+                StatementResult::new(1, invalid_query("unsupported")),
+            ],
+        };
+        assert_eq!(
+            r#"{"results":[{"statement_id":0},{"statement_id":1,"error":"Invalid query: unsupported"}]}"#,
+            serde_json::to_string(&result).unwrap()
+        );
+    }
+}
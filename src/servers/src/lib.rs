@@ -26,10 +26,11 @@ pub mod line_writer;
 pub mod mysql;
 pub mod opentsdb;
 pub mod postgres;
+pub mod process;
 pub mod prometheus;
 pub mod query_handler;
+pub mod query_util;
 pub mod server;
-pub mod tls;
 
 mod shutdown;
 
@@ -15,8 +15,8 @@
 use std::collections::HashMap;
 
 use common_catalog::consts::DEFAULT_CATALOG_NAME;
-use common_grpc::writer::{to_ms_ts, Precision};
-use common_time::timestamp::TimeUnit::Millisecond;
+use common_grpc::writer::{to_precise_ts, Precision};
+use common_time::timestamp::TimeUnit;
 use common_time::Timestamp;
 use datatypes::prelude::ConcreteDataType;
 use datatypes::types::TimestampType;
@@ -48,8 +48,9 @@ impl LineWriter {
 
     pub fn write_ts(&mut self, column_name: &str, value: (i64, Precision)) {
         let (val, precision) = value;
-        let datatype = ConcreteDataType::Timestamp(TimestampType { unit: Millisecond });
-        let ts_val = Value::Timestamp(Timestamp::new(to_ms_ts(precision, val), Millisecond));
+        let unit = precision_to_time_unit(precision);
+        let datatype = ConcreteDataType::Timestamp(TimestampType { unit });
+        let ts_val = Value::Timestamp(Timestamp::new(to_precise_ts(precision, val), unit));
         self.write(column_name, datatype, ts_val);
     }
 
@@ -144,6 +145,15 @@ impl LineWriter {
     }
 }
 
+fn precision_to_time_unit(precision: Precision) -> TimeUnit {
+    match precision {
+        Precision::NANOSECOND => TimeUnit::Nanosecond,
+        Precision::MICROSECOND => TimeUnit::Microsecond,
+        Precision::MILLISECOND => TimeUnit::Millisecond,
+        Precision::SECOND | Precision::MINUTE | Precision::HOUR => TimeUnit::Second,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -15,7 +15,7 @@
 //! Use regex to filter out some MySQL federated components' emitted statements.
 //! Inspired by Databend's "[mysql_federated.rs](https://github.com/datafuselabs/databend/blob/ac706bf65845e6895141c96c0a10bad6fdc2d367/src/query/service/src/servers/mysql/mysql_federated.rs)".
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 use common_query::Output;
@@ -38,8 +38,9 @@ static SHOW_LOWER_CASE_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new("(?i)^(SHOW VARIABLES LIKE 'lower_case_table_names'(.*))").unwrap());
 static SHOW_COLLATION_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new("(?i)^(show collation where(.*))").unwrap());
-static SHOW_VARIABLES_PATTERN: Lazy<Regex> =
-    Lazy::new(|| Regex::new("(?i)^(SHOW VARIABLES(.*))").unwrap());
+static SHOW_VARIABLES_PATTERN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^SHOW\s+(?:SESSION\s+|GLOBAL\s+)?VARIABLES(?:\s+LIKE\s+'([^']*)')?").unwrap()
+});
 
 static SELECT_VERSION_PATTERN: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"(?i)^(SELECT VERSION\(\s*\))").unwrap());
@@ -70,13 +71,12 @@ static OTHER_NOT_SUPPORTED_STMT: Lazy<RegexSet> = Lazy::new(|| {
         "(?i)^(SET SQL_LOG_BIN(.*))",
         "(?i)^(SET sql_mode(.*))",
         "(?i)^(SET SQL_SELECT_LIMIT(.*))",
-        "(?i)^(SET @@(.*))",
+        "(?i)^(SET (SESSION |GLOBAL )?TRANSACTION(.*))",
 
         "(?i)^(SHOW COLLATION)",
         "(?i)^(SHOW CHARSET)",
 
         // mysqldump.
-        "(?i)^(SET SESSION(.*))",
         "(?i)^(SET SQL_QUOTE_SHOW_CREATE(.*))",
         "(?i)^(LOCK TABLES(.*))",
         "(?i)^(UNLOCK TABLES(.*))",
@@ -155,20 +155,33 @@ fn select_function(name: &str, value: &str) -> RecordBatches {
 // | Variable_name | Value |
 // | xx            | yy    |
 fn show_variables(name: &str, value: &str) -> RecordBatches {
+    show_variables_list(vec![(name.to_string(), value.to_string())])
+}
+
+fn show_variables_list(variables: Vec<(String, String)>) -> RecordBatches {
     let schema = Arc::new(Schema::new(vec![
         ColumnSchema::new("Variable_name", ConcreteDataType::string_datatype(), true),
         ColumnSchema::new("Value", ConcreteDataType::string_datatype(), true),
     ]));
+    let (names, values): (Vec<_>, Vec<_>) = variables.into_iter().unzip();
     let columns = vec![
-        Arc::new(StringVector::from(vec![name])) as _,
-        Arc::new(StringVector::from(vec![value])) as _,
+        Arc::new(StringVector::from(names)) as _,
+        Arc::new(StringVector::from(values)) as _,
     ];
     RecordBatches::try_from_columns(schema, columns)
         // unwrap is safe because the schema and data are definitely able to form a recordbatch, they are all string type
         .unwrap()
 }
 
-fn select_variable(query: &str) -> Option<Output> {
+// Looks up a variable in the session first, then in the faked global variables.
+fn variable_value(name: &str, query_ctx: &QueryContextRef) -> String {
+    query_ctx
+        .variable(name.trim_start_matches("session."))
+        .or_else(|| VAR_VALUES.get(name).map(|v| v.to_string()))
+        .unwrap_or_else(|| "0".to_string())
+}
+
+fn select_variable(query: &str, query_ctx: &QueryContextRef) -> Option<Output> {
     let mut fields = vec![];
     let mut values = vec![];
 
@@ -194,8 +207,8 @@ fn select_variable(query: &str) -> Option<Output> {
         match var_as.len() {
             1 => {
                 // @@aa
-                let value = VAR_VALUES.get(var_as[0]).unwrap_or(&"0");
-                values.push(Arc::new(StringVector::from(vec![*value])) as _);
+                let value = variable_value(var_as[0], query_ctx);
+                values.push(Arc::new(StringVector::from(vec![value])) as _);
 
                 // field is '@@aa'
                 fields.push(ColumnSchema::new(
@@ -207,8 +220,8 @@ fn select_variable(query: &str) -> Option<Output> {
             2 => {
                 // @@bb as cc:
                 // var is 'bb'.
-                let value = VAR_VALUES.get(var_as[0]).unwrap_or(&"0");
-                values.push(Arc::new(StringVector::from(vec![*value])) as _);
+                let value = variable_value(var_as[0], query_ctx);
+                values.push(Arc::new(StringVector::from(vec![value])) as _);
 
                 // field is 'cc'.
                 fields.push(ColumnSchema::new(
@@ -227,24 +240,48 @@ fn select_variable(query: &str) -> Option<Output> {
     Some(Output::RecordBatches(batches))
 }
 
-fn check_select_variable(query: &str) -> Option<Output> {
+fn check_select_variable(query: &str, query_ctx: &QueryContextRef) -> Option<Output> {
     if vec![&SELECT_VAR_PATTERN, &MYSQL_CONN_JAVA_PATTERN]
         .iter()
         .any(|r| r.is_match(query))
     {
-        select_variable(query)
+        select_variable(query, query_ctx)
     } else {
         None
     }
 }
 
-fn check_show_variables(query: &str) -> Option<Output> {
+// Lists the faked global variables overridden by the session ones, filtered by
+// the pattern of "LIKE" if any.
+fn list_variables(like: Option<&str>, query_ctx: &QueryContextRef) -> Vec<(String, String)> {
+    let like = like.map(|pattern| {
+        let pattern = regex::escape(pattern).replace('%', ".*").replace('_', ".");
+        // unwrap is safe because the special characters are all escaped
+        Regex::new(&format!("(?i)^{}$", pattern)).unwrap()
+    });
+
+    let mut variables: BTreeMap<String, String> = VAR_VALUES
+        .iter()
+        .filter(|(name, _)| !name.starts_with("session."))
+        .map(|(name, value)| (name.to_string(), value.to_string()))
+        .collect();
+    variables.extend(query_ctx.variables());
+    variables
+        .into_iter()
+        .filter(|(name, _)| like.as_ref().map_or(true, |like| like.is_match(name)))
+        .collect()
+}
+
+fn check_show_variables(query: &str, query_ctx: &QueryContextRef) -> Option<Output> {
     let recordbatches = if SHOW_SQL_MODE_PATTERN.is_match(query) {
         Some(show_variables("sql_mode", "ONLY_FULL_GROUP_BY STRICT_TRANS_TABLES NO_ZERO_IN_DATE NO_ZERO_DATE ERROR_FOR_DIVISION_BY_ZERO NO_ENGINE_SUBSTITUTION"))
     } else if SHOW_LOWER_CASE_PATTERN.is_match(query) {
         Some(show_variables("lower_case_table_names", "0"))
-    } else if SHOW_COLLATION_PATTERN.is_match(query) || SHOW_VARIABLES_PATTERN.is_match(query) {
+    } else if SHOW_COLLATION_PATTERN.is_match(query) {
         Some(show_variables("", ""))
+    } else if let Some(captures) = SHOW_VARIABLES_PATTERN.captures(query) {
+        let like = captures.get(1).map(|m| m.as_str());
+        Some(show_variables_list(list_variables(like, query_ctx)))
     } else {
         None
     };
@@ -279,13 +316,13 @@ fn check_others(query: &str, query_ctx: QueryContextRef) -> Option<Output> {
 // and return some faked results if there are any.
 pub(crate) fn check(query: &str, query_ctx: QueryContextRef) -> Option<Output> {
     // First to check the query is like "select @@variables".
-    let output = check_select_variable(query);
+    let output = check_select_variable(query, &query_ctx);
     if output.is_some() {
         return output;
     }
 
     // Then to check "show variables like ...".
-    let output = check_show_variables(query);
+    let output = check_show_variables(query, &query_ctx);
     if output.is_some() {
         return output;
     }
@@ -362,13 +399,15 @@ mod test {
         ];
         test(query, expected);
 
-        let query = "show variables";
+        let query = "show variables like '%timeout'";
         let expected = vec![
-            "+---------------+-------+",
-            "| Variable_name | Value |",
-            "+---------------+-------+",
-            "|               |       |",
-            "+---------------+-------+",
+            "+---------------------+----------+",
+            "| Variable_name       | Value    |",
+            "+---------------------+----------+",
+            "| interactive_timeout | 31536000 |",
+            "| net_write_timeout   | 31536000 |",
+            "| wait_timeout        | 31536000 |",
+            "+---------------------+----------+",
         ];
         test(query, expected);
 
@@ -396,4 +435,47 @@ mod test {
         ];
         test(query, expected);
     }
+
+    #[test]
+    fn test_session_variables() {
+        let query_ctx = Arc::new(QueryContext::new());
+        query_ctx.set_variable("time_zone", "+08:00").unwrap();
+        query_ctx.set_variable("foo", "bar").unwrap();
+
+        fn test(query: &str, query_ctx: QueryContextRef, expected: Vec<&str>) {
+            let output = check(query, query_ctx);
+            match output.unwrap() {
+                Output::RecordBatches(r) => {
+                    assert_eq!(r.pretty_print().lines().collect::<Vec<_>>(), expected)
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        let query = "select @@time_zone, @@session.time_zone as tz, @@foo";
+        let expected = vec![
+            "+-------------+--------+-------+",
+            "| @@time_zone | tz     | @@foo |",
+            "+-------------+--------+-------+",
+            "| +08:00      | +08:00 | bar   |",
+            "+-------------+--------+-------+",
+        ];
+        test(query, query_ctx.clone(), expected);
+
+        let query = "SHOW SESSION VARIABLES LIKE '%time_zone'";
+        let expected = vec![
+            "+------------------+--------+",
+            "| Variable_name    | Value  |",
+            "+------------------+--------+",
+            "| system_time_zone | UTC    |",
+            "| time_zone        | +08:00 |",
+            "+------------------+--------+",
+        ];
+        test(query, query_ctx.clone(), expected);
+
+        // "SET" statements are left to the query engine.
+        assert!(check("SET @@session.time_zone = 'UTC'", query_ctx.clone()).is_none());
+        assert!(check("SET SESSION time_zone = 'UTC'", query_ctx.clone()).is_none());
+        assert!(check("SET SESSION TRANSACTION READ ONLY", query_ctx).is_some());
+    }
 }
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
@@ -22,20 +23,26 @@ use opensrv_mysql::{
     AsyncMysqlShim, ErrorKind, InitWriter, ParamParser, QueryResultWriter, StatementMetaWriter,
 };
 use rand::RngCore;
+use session::context::QueryContextRef;
 use session::Session;
 use tokio::io::AsyncWrite;
 use tokio::sync::RwLock;
 
-use crate::auth::{Identity, Password, UserProviderRef};
+use crate::auth::{login_to_tenant, parse_login_name, Identity, Password, UserProviderRef};
 use crate::context::Channel::Mysql;
 use crate::context::{Context, CtxBuilder};
 use crate::error::{self, Result};
 use crate::mysql::writer::MysqlResultWriter;
 use crate::query_handler::SqlQueryHandlerRef;
+use crate::query_util::quote_ident;
+
+/// Ids of MySQL connections, which are sent to clients in the handshake and used by `KILL`.
+static NEXT_CONNECTION_ID: AtomicU32 = AtomicU32::new(1);
 
 // An intermediate shim for executing MySQL queries.
 pub struct MysqlInstanceShim {
     query_handler: SqlQueryHandlerRef,
+    connection_id: u32,
     salt: [u8; 20],
     client_addr: String,
     // TODO(LFC): Break `Context` struct into different fields in `Session`, each with its own purpose.
@@ -63,16 +70,34 @@ impl MysqlInstanceShim {
             }
         }
 
+        // Skips 0 on wrapping around, which means no connection in `QueryContext`.
+        let connection_id = match NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed) {
+            0 => NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed),
+            id => id,
+        };
+        let session = Arc::new(Session::new());
+        session.context().set_connection_id(connection_id);
+
         MysqlInstanceShim {
             query_handler,
+            connection_id,
             salt: scramble,
             client_addr,
             ctx: Arc::new(RwLock::new(None)),
-            session: Arc::new(Session::new()),
+            session,
             user_provider,
         }
     }
 
+    pub fn connection_id(&self) -> u32 {
+        self.connection_id
+    }
+
+    /// Context of the session, which is bound to the user once authenticated.
+    pub fn query_ctx(&self) -> QueryContextRef {
+        self.session.context()
+    }
+
     async fn do_query(&self, query: &str) -> Result<Output> {
         debug!("Start executing query: '{}'", query);
         let start = Instant::now();
@@ -102,6 +127,10 @@ impl MysqlInstanceShim {
 impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShim {
     type Error = error::Error;
 
+    fn connect_id(&self) -> u32 {
+        self.connection_id
+    }
+
     fn salt(&self) -> [u8; 20] {
         self.salt
     }
@@ -114,12 +143,14 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShi
         auth_data: &[u8],
     ) -> bool {
         // if not specified then **greptime** will be used
-        let username = String::from_utf8_lossy(username);
+        let login_name = String::from_utf8_lossy(username);
+        // The tenant can be chosen by logging in as `<username>@<tenant>`.
+        let (username, tenant) = parse_login_name(&login_name);
         let client_addr = self.client_addr.clone();
 
         let mut user_info = None;
         if let Some(user_provider) = &self.user_provider {
-            let user_id = Identity::UserId(&username, Some(&client_addr));
+            let user_id = Identity::UserId(username, Some(&client_addr));
 
             let password = match auth_plugin {
                 "mysql_native_password" => Password::MysqlNativePassword(auth_data, salt),
@@ -138,7 +169,14 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShi
                 }
             };
         }
-        let user_info = user_info.unwrap_or_default();
+        let user_info = match login_to_tenant(user_info.unwrap_or_default(), tenant) {
+            Ok(user_info) => user_info,
+            Err(e) => {
+                error!("Failed to auth, err: {:?}", e);
+                return false;
+            }
+        };
+        user_info.bind_to(&self.session.context());
 
         return match CtxBuilder::new()
             .client_addr(client_addr)
@@ -194,12 +232,13 @@ impl<W: AsyncWrite + Send + Sync + Unpin> AsyncMysqlShim<W> for MysqlInstanceShi
         writer: QueryResultWriter<'a, W>,
     ) -> Result<()> {
         let output = self.do_query(query).await;
-        let mut writer = MysqlResultWriter::new(writer);
+        let mut writer = MysqlResultWriter::new(writer, self.session.context());
         writer.write(query, output).await
     }
 
     async fn on_init<'a>(&'a mut self, database: &'a str, w: InitWriter<'a, W>) -> Result<()> {
-        let query = format!("USE {}", database.trim());
+        // Quotes the database name, which could be `<catalog>-<schema>` or contain quotes.
+        let query = format!("USE {}", quote_ident(database.trim()));
         let output = self.do_query(&query).await;
         if let Err(e) = output {
             w.error(ErrorKind::ER_UNKNOWN_ERROR, e.to_string().as_bytes())
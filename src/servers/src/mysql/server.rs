@@ -17,6 +17,7 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use common_grpc::tls::TlsOption;
 use common_runtime::Runtime;
 use common_telemetry::logging::{error, info};
 use futures::StreamExt;
@@ -31,30 +32,29 @@ use tokio_rustls::rustls::ServerConfig;
 use crate::auth::UserProviderRef;
 use crate::error::{Error, Result};
 use crate::mysql::handler::MysqlInstanceShim;
-use crate::query_handler::SqlQueryHandlerRef;
+use crate::process::ProcessManagerRef;
 use crate::server::{AbortableStream, BaseTcpServer, Server};
-use crate::tls::TlsOption;
 
 // Default size of ResultSet write buffer: 100KB
 const DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE: usize = 100 * 1024;
 
 pub struct MysqlServer {
     base_server: BaseTcpServer,
-    query_handler: SqlQueryHandlerRef,
+    process_manager: ProcessManagerRef,
     tls: Arc<TlsOption>,
     user_provider: Option<UserProviderRef>,
 }
 
 impl MysqlServer {
     pub fn create_server(
-        query_handler: SqlQueryHandlerRef,
+        process_manager: ProcessManagerRef,
         io_runtime: Arc<Runtime>,
         tls: Arc<TlsOption>,
         user_provider: Option<UserProviderRef>,
     ) -> Box<dyn Server> {
         Box::new(MysqlServer {
             base_server: BaseTcpServer::create_server("MySQL", io_runtime),
-            query_handler,
+            process_manager,
             tls,
             user_provider,
         })
@@ -66,14 +66,14 @@ impl MysqlServer {
         stream: AbortableStream,
         tls_conf: Option<Arc<ServerConfig>>,
     ) -> impl Future<Output = ()> {
-        let query_handler = self.query_handler.clone();
+        let process_manager = self.process_manager.clone();
         let user_provider = self.user_provider.clone();
 
         let force_tls = self.tls.should_force_tls();
 
         stream.for_each(move |tcp_stream| {
             let io_runtime = io_runtime.clone();
-            let query_handler = query_handler.clone();
+            let process_manager = process_manager.clone();
             let user_provider = user_provider.clone();
             let tls_conf = tls_conf.clone();
 
@@ -84,7 +84,7 @@ impl MysqlServer {
                         if let Err(error) = Self::handle(
                             io_stream,
                             io_runtime,
-                            query_handler,
+                            process_manager,
                             tls_conf,
                             force_tls,
                             user_provider,
@@ -102,7 +102,7 @@ impl MysqlServer {
     async fn handle(
         stream: TcpStream,
         io_runtime: Arc<Runtime>,
-        query_handler: SqlQueryHandlerRef,
+        process_manager: ProcessManagerRef,
         tls_conf: Option<Arc<ServerConfig>>,
         force_tls: bool,
         user_provider: Option<UserProviderRef>,
@@ -110,7 +110,7 @@ impl MysqlServer {
         info!("MySQL connection coming from: {}", stream.peer_addr()?);
         io_runtime .spawn(async move {
             // TODO(LFC): Use `output_stream` to write large MySQL ResultSet to client.
-            if let Err(e)  = Self::do_handle(stream, query_handler, tls_conf, force_tls, user_provider).await {
+            if let Err(e)  = Self::do_handle(stream, process_manager, tls_conf, force_tls, user_provider).await {
                 // TODO(LFC): Write this error to client as well, in MySQL text protocol.
                 // Looks like we have to expose opensrv-mysql's `PacketWriter`?
                 error!(e; "Internal error occurred during query exec, server actively close the channel to let client try next time.")
@@ -122,16 +122,19 @@ impl MysqlServer {
 
     async fn do_handle(
         stream: TcpStream,
-        query_handler: SqlQueryHandlerRef,
+        process_manager: ProcessManagerRef,
         tls_conf: Option<Arc<ServerConfig>>,
         force_tls: bool,
         user_provider: Option<UserProviderRef>,
     ) -> Result<()> {
         let mut shim = MysqlInstanceShim::create(
-            query_handler,
+            process_manager.clone(),
             stream.peer_addr()?.to_string(),
             user_provider,
         );
+        let connection_id = shim.connection_id();
+        let mut connection = process_manager.register_connection(shim.query_ctx());
+
         let (mut r, w) = stream.into_split();
         let mut w = BufWriter::with_capacity(DEFAULT_RESULT_SET_WRITE_BUFFER_SIZE, w);
         let ops = IntermediaryOptions::default();
@@ -145,11 +148,21 @@ impl MysqlServer {
             });
         }
 
-        match tls_conf {
-            Some(tls_conf) if client_tls => {
-                secure_run_with_options(shim, w, ops, tls_conf, init_params).await
+        let run = async move {
+            match tls_conf {
+                Some(tls_conf) if client_tls => {
+                    secure_run_with_options(shim, w, ops, tls_conf, init_params).await
+                }
+                _ => plain_run_with_options(shim, w, ops, init_params).await,
+            }
+        };
+        // Dropping the running future closes the connection.
+        tokio::select! {
+            result = run => result,
+            _ = connection.closed() => {
+                info!("MySQL connection {} is killed", connection_id);
+                Ok(())
             }
-            _ => plain_run_with_options(shim, w, ops, init_params).await,
         }
     }
 }
@@ -17,13 +17,13 @@ use std::ops::Deref;
 use common_query::Output;
 use common_recordbatch::{util, RecordBatch};
 use common_telemetry::error;
-use common_time::datetime::DateTime;
-use common_time::timestamp::TimeUnit;
+use common_time::TimeZone;
 use datatypes::prelude::{ConcreteDataType, Value};
 use datatypes::schema::{ColumnSchema, SchemaRef};
 use opensrv_mysql::{
     Column, ColumnFlags, ColumnType, ErrorKind, OkResponse, QueryResultWriter, RowWriter,
 };
+use session::context::QueryContextRef;
 use snafu::prelude::*;
 use tokio::io::AsyncWrite;
 
@@ -38,17 +38,26 @@ pub struct MysqlResultWriter<'a, W: AsyncWrite + Unpin> {
     // `QueryResultWriter` will be consumed when the write completed (see
     // QueryResultWriter::completed), thus we use an option to wrap it.
     inner: Option<QueryResultWriter<'a, W>>,
+    // Timestamps are rendered in the time zone of the session.
+    query_ctx: QueryContextRef,
 }
 
 impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
-    pub fn new(inner: QueryResultWriter<'a, W>) -> MysqlResultWriter<'a, W> {
-        MysqlResultWriter::<'a, W> { inner: Some(inner) }
+    pub fn new(
+        inner: QueryResultWriter<'a, W>,
+        query_ctx: QueryContextRef,
+    ) -> MysqlResultWriter<'a, W> {
+        MysqlResultWriter::<'a, W> {
+            inner: Some(inner),
+            query_ctx,
+        }
     }
 
     pub async fn write(&mut self, query: &str, output: Result<Output>) -> Result<()> {
         let writer = self.inner.take().context(error::InternalSnafu {
             err_msg: "inner MySQL writer is consumed",
         })?;
+        let time_zone = self.query_ctx.time_zone();
         match output {
             Ok(output) => match output {
                 Output::Stream(stream) => {
@@ -60,14 +69,16 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
                         recordbatches,
                         schema,
                     };
-                    Self::write_query_result(query, query_result, writer).await?
+                    Self::write_query_result(query, query_result, writer, time_zone.as_ref())
+                        .await?
                 }
                 Output::RecordBatches(recordbatches) => {
                     let query_result = QueryResult {
                         schema: recordbatches.schema(),
                         recordbatches: recordbatches.take(),
                     };
-                    Self::write_query_result(query, query_result, writer).await?
+                    Self::write_query_result(query, query_result, writer, time_zone.as_ref())
+                        .await?
                 }
                 Output::AffectedRows(rows) => Self::write_affected_rows(writer, rows).await?,
             },
@@ -89,12 +100,13 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
         query: &str,
         query_result: QueryResult,
         writer: QueryResultWriter<'a, W>,
+        time_zone: Option<&TimeZone>,
     ) -> Result<()> {
         match create_mysql_column_def(&query_result.schema) {
             Ok(column_def) => {
                 let mut row_writer = writer.start(&column_def).await?;
                 for recordbatch in &query_result.recordbatches {
-                    Self::write_recordbatch(&mut row_writer, recordbatch).await?;
+                    Self::write_recordbatch(&mut row_writer, recordbatch, time_zone).await?;
                 }
                 row_writer.finish().await?;
                 Ok(())
@@ -106,6 +118,7 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
     async fn write_recordbatch(
         row_writer: &mut RowWriter<'_, W>,
         recordbatch: &RecordBatch,
+        time_zone: Option<&TimeZone>,
     ) -> Result<()> {
         for row in recordbatch.rows() {
             let row = row.context(error::CollectRecordbatchSnafu)?;
@@ -128,7 +141,9 @@ impl<'a, W: AsyncWrite + Unpin> MysqlResultWriter<'a, W> {
                     Value::Date(v) => row_writer.write_col(v.val())?,
                     Value::DateTime(v) => row_writer.write_col(v.val())?,
                     Value::Timestamp(v) => row_writer
-                        .write_col(DateTime::new(v.convert_to(TimeUnit::Second)).to_string())?,
+                        .write_col(v.as_formatted_string("%Y-%m-%d %H:%M:%S", time_zone))?,
+                    Value::Decimal128(v) => row_writer.write_col(v.to_string())?,
+                    Value::Json(v) => row_writer.write_col(v.to_string())?,
                     Value::List(_) => {
                         return Err(Error::Internal {
                             err_msg: format!(
@@ -177,6 +192,8 @@ fn create_mysql_column(column_schema: &ColumnSchema) -> Result<Column> {
             Ok(ColumnType::MYSQL_TYPE_VARCHAR)
         }
         ConcreteDataType::Timestamp(_) => Ok(ColumnType::MYSQL_TYPE_DATETIME),
+        ConcreteDataType::Decimal128(_) => Ok(ColumnType::MYSQL_TYPE_NEWDECIMAL),
+        ConcreteDataType::Json(_) => Ok(ColumnType::MYSQL_TYPE_JSON),
         _ => error::InternalSnafu {
             err_msg: format!(
                 "not implemented for column datatype {:?}",
@@ -15,6 +15,7 @@
 pub mod codec;
 pub mod connection;
 mod handler;
+pub mod query;
 
 use std::future::Future;
 use std::net::SocketAddr;
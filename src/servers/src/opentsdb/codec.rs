@@ -12,10 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+
 use api::v1::column::SemanticType;
 use api::v1::{column, Column, ColumnDataType, InsertExpr};
-use common_catalog::consts::DEFAULT_SCHEMA_NAME;
-use common_grpc::writer::Precision;
+use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
+use common_grpc::writer::{LinesWriter, Precision};
+use snafu::ResultExt;
 use table::requests::InsertRequest;
 
 use crate::error::{self, Result};
@@ -187,6 +190,7 @@ impl DataPoint {
         }
 
         InsertExpr {
+            catalog_name: DEFAULT_CATALOG_NAME.to_string(),
             schema_name,
             table_name: self.metric.clone(),
             region_number: 0,
@@ -208,6 +212,56 @@ impl DataPoint {
     }
 }
 
+/// Groups data points by metric and converts each group into one `InsertExpr`, so that data
+/// points of the same metric are written to their table in a single insertion. Groups are
+/// ordered by the first appearance of their metrics.
+pub fn data_points_to_grpc_inserts(data_points: &[DataPoint]) -> Result<Vec<InsertExpr>> {
+    let mut metric_index: HashMap<&str, usize> = HashMap::new();
+    let mut writers: Vec<(&str, LinesWriter)> = Vec::new();
+
+    for data_point in data_points {
+        let index = *metric_index.entry(data_point.metric()).or_insert_with(|| {
+            writers.push((
+                data_point.metric(),
+                LinesWriter::with_lines(data_points.len()),
+            ));
+            writers.len() - 1
+        });
+        let writer = &mut writers[index].1;
+
+        writer
+            .write_ts(
+                OPENTSDB_TIMESTAMP_COLUMN_NAME,
+                (data_point.ts_millis(), Precision::MILLISECOND),
+            )
+            .context(error::OpentsdbLinesWriteSnafu)?;
+        writer
+            .write_f64(OPENTSDB_VALUE_COLUMN_NAME, data_point.value())
+            .context(error::OpentsdbLinesWriteSnafu)?;
+        for (tagk, tagv) in data_point.tags() {
+            writer
+                .write_tag(tagk, tagv)
+                .context(error::OpentsdbLinesWriteSnafu)?;
+        }
+        writer.commit();
+    }
+
+    Ok(writers
+        .into_iter()
+        .map(|(metric, writer)| {
+            let (columns, row_count) = writer.finish();
+            InsertExpr {
+                catalog_name: DEFAULT_CATALOG_NAME.to_string(),
+                schema_name: DEFAULT_SCHEMA_NAME.to_string(),
+                table_name: metric.to_string(),
+                region_number: 0,
+                columns,
+                row_count,
+            }
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod test {
     use std::sync::Arc;
@@ -355,4 +409,65 @@ mod test {
             vec!["tagv2"]
         );
     }
+
+    #[test]
+    fn test_data_points_to_grpc_inserts() {
+        let data_points = vec![
+            DataPoint::new(
+                "my_metric_1".to_string(),
+                1000,
+                1.0,
+                vec![("tagk1".to_string(), "tagv1".to_string())],
+            ),
+            DataPoint::new("my_metric_2".to_string(), 2000, 2.0, vec![]),
+            DataPoint::new(
+                "my_metric_1".to_string(),
+                3000,
+                3.0,
+                vec![("tagk2".to_string(), "tagv2".to_string())],
+            ),
+        ];
+
+        let inserts = data_points_to_grpc_inserts(&data_points).unwrap();
+        assert_eq!(2, inserts.len());
+
+        let insert = &inserts[0];
+        assert_eq!("my_metric_1", insert.table_name);
+        assert_eq!(2, insert.row_count);
+        let columns = &insert.columns;
+        assert_eq!(4, columns.len());
+        assert_eq!(columns[0].column_name, OPENTSDB_TIMESTAMP_COLUMN_NAME);
+        assert_eq!(
+            columns[0].values.as_ref().unwrap().ts_millis_values,
+            vec![1000, 3000]
+        );
+        assert_eq!(columns[1].column_name, OPENTSDB_VALUE_COLUMN_NAME);
+        assert_eq!(
+            columns[1].values.as_ref().unwrap().f64_values,
+            vec![1.0, 3.0]
+        );
+        assert_eq!(columns[2].column_name, "tagk1");
+        assert_eq!(
+            columns[2].values.as_ref().unwrap().string_values,
+            vec!["tagv1"]
+        );
+        // the second row has no "tagk1"
+        assert_eq!(columns[2].null_mask, vec![0b10]);
+        assert_eq!(columns[3].column_name, "tagk2");
+        assert_eq!(
+            columns[3].values.as_ref().unwrap().string_values,
+            vec!["tagv2"]
+        );
+        // the first row has no "tagk2"
+        assert_eq!(columns[3].null_mask, vec![0b01]);
+
+        let insert = &inserts[1];
+        assert_eq!("my_metric_2", insert.table_name);
+        assert_eq!(1, insert.row_count);
+        assert_eq!(2, insert.columns.len());
+        assert_eq!(
+            insert.columns[0].values.as_ref().unwrap().ts_millis_values,
+            vec![2000]
+        );
+    }
 }
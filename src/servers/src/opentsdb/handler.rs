@@ -14,14 +14,25 @@
 
 //! Modified from Tokio's mini-redis example.
 
+use std::collections::HashMap;
+use std::time::Duration;
+
+use session::context::QueryContext;
 use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::time::Instant;
 
+use crate::auth::UserInfo;
 use crate::error::Result;
 use crate::opentsdb::codec::DataPoint;
 use crate::opentsdb::connection::Connection;
 use crate::query_handler::OpentsdbProtocolHandlerRef;
 use crate::shutdown::Shutdown;
 
+/// Max number of data points buffered in one connection before they are flushed.
+const DEFAULT_MAX_BATCH_SIZE: usize = 1024;
+/// Max time a buffered data point waits before it is flushed.
+const DEFAULT_FLUSH_INTERVAL: Duration = Duration::from_millis(100);
+
 /// Per-connection handler. Reads requests from `connection` and applies the OpenTSDB metric to
 /// [OpentsdbLineProtocolHandler].
 pub(crate) struct Handler<S: AsyncWrite + AsyncRead + Unpin> {
@@ -43,6 +54,14 @@ pub(crate) struct Handler<S: AsyncWrite + AsyncRead + Unpin> {
     /// any in-flight work being processed for the peer is continued until it reaches a safe state,
     /// at which point the connection is terminated. (Graceful shutdown.)
     shutdown: Shutdown,
+
+    /// Data points received but not yet flushed to `query_handler`.
+    ///
+    /// Data points are flushed in batch when there are `max_batch_size` of them, when the
+    /// oldest one has been buffered for `flush_interval`, or when the connection is closing.
+    data_points: Vec<DataPoint>,
+    max_batch_size: usize,
+    flush_interval: Duration,
 }
 
 impl<S: AsyncWrite + AsyncRead + Unpin> Handler<S> {
@@ -55,18 +74,34 @@ impl<S: AsyncWrite + AsyncRead + Unpin> Handler<S> {
             query_handler,
             connection,
             shutdown,
+            data_points: Vec::with_capacity(DEFAULT_MAX_BATCH_SIZE),
+            max_batch_size: DEFAULT_MAX_BATCH_SIZE,
+            flush_interval: DEFAULT_FLUSH_INTERVAL,
         }
     }
 
+    #[cfg(test)]
+    fn with_batch_options(mut self, max_batch_size: usize, flush_interval: Duration) -> Self {
+        self.max_batch_size = max_batch_size;
+        self.flush_interval = flush_interval;
+        self
+    }
+
     pub(crate) async fn run(&mut self) -> Result<()> {
+        // The deadline to flush buffered data points, only meaningful when the buffer is not empty.
+        let mut flush_deadline = Instant::now();
         while !self.shutdown.is_shutdown() {
-            // While reading a request, also listen for the shutdown signal.
+            // While reading a request, also listen for the shutdown signal and flush deadline.
             let maybe_line = tokio::select! {
                 line = self.connection.read_line() => line?,
+                _ = tokio::time::sleep_until(flush_deadline), if !self.data_points.is_empty() => {
+                    self.flush().await?;
+                    continue;
+                }
                 _ = self.shutdown.recv() => {
-                    // If a shutdown signal is received, return from `run`.
-                    // This will result in the task terminating.
-                    return Ok(());
+                    // If a shutdown signal is received, flush buffered data points and return
+                    // from `run`. This will result in the task terminating.
+                    return self.flush().await;
                 }
             };
 
@@ -74,7 +109,7 @@ impl<S: AsyncWrite + AsyncRead + Unpin> Handler<S> {
             // no further work to do and the task can be terminated.
             let line = match maybe_line {
                 Some(line) => line,
-                None => return Ok(()),
+                None => return self.flush().await,
             };
 
             // Close connection upon receiving "quit" line. With actual OpenTSDB, telnet just won't
@@ -83,14 +118,17 @@ impl<S: AsyncWrite + AsyncRead + Unpin> Handler<S> {
             // so I added "quit" command to the line protocol, to make telnet client able to quit
             // gracefully.
             if line.trim().eq_ignore_ascii_case("quit") {
-                return Ok(());
+                return self.flush().await;
             }
 
             match DataPoint::try_create(&line) {
                 Ok(data_point) => {
-                    let result = self.query_handler.exec(&data_point).await;
-                    if let Err(e) = result {
-                        self.connection.write_line(e.to_string()).await?;
+                    if self.data_points.is_empty() {
+                        flush_deadline = Instant::now() + self.flush_interval;
+                    }
+                    self.data_points.push(data_point);
+                    if self.data_points.len() >= self.max_batch_size {
+                        self.flush().await?;
                     }
                 }
                 Err(e) => {
@@ -98,40 +136,100 @@ impl<S: AsyncWrite + AsyncRead + Unpin> Handler<S> {
                 }
             }
         }
+        self.flush().await
+    }
+
+    /// Flushes buffered data points to `query_handler`, grouped by metric, so a failing metric
+    /// doesn't drop the data points of others. Errors are written back to the peer.
+    async fn flush(&mut self) -> Result<()> {
+        if self.data_points.is_empty() {
+            return Ok(());
+        }
+        let data_points = std::mem::replace(
+            &mut self.data_points,
+            Vec::with_capacity(self.max_batch_size),
+        );
+        // The telnet protocol has no authentication, data points are written as the default
+        // user, like servers without user providers do.
+        let query_ctx = QueryContext::arc();
+        UserInfo::default().bind_to(&query_ctx);
+        for data_points in group_by_metric(data_points) {
+            if let Err(e) = self
+                .query_handler
+                .exec(&data_points, query_ctx.clone())
+                .await
+            {
+                self.connection.write_line(e.to_string()).await?;
+            }
+        }
         Ok(())
     }
 }
 
+/// Groups data points by metric, in the order each metric first appears.
+fn group_by_metric(data_points: Vec<DataPoint>) -> Vec<Vec<DataPoint>> {
+    let mut groups: Vec<Vec<DataPoint>> = Vec::new();
+    let mut group_indexes: HashMap<String, usize> = HashMap::new();
+    for data_point in data_points {
+        match group_indexes.get(data_point.metric()) {
+            Some(i) => groups[*i].push(data_point),
+            None => {
+                group_indexes.insert(data_point.metric().to_string(), groups.len());
+                groups.push(vec![data_point]);
+            }
+        }
+    }
+    groups
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::SocketAddr;
     use std::sync::Arc;
 
     use async_trait::async_trait;
+    use session::context::QueryContextRef;
     use tokio::net::{TcpListener, TcpStream};
     use tokio::sync::{broadcast, mpsc};
 
     use super::*;
     use crate::error;
+    use crate::opentsdb::query::{QueryRequest, QueryResult, SuggestRequest};
     use crate::query_handler::OpentsdbProtocolHandler;
 
     struct DummyQueryHandler {
-        tx: mpsc::Sender<String>,
+        tx: mpsc::Sender<Vec<String>>,
     }
 
     #[async_trait]
     impl OpentsdbProtocolHandler for DummyQueryHandler {
-        async fn exec(&self, data_point: &DataPoint) -> Result<()> {
-            let metric = data_point.metric();
-            if metric == "should_failed" {
+        async fn exec(&self, data_points: &[DataPoint], _query_ctx: QueryContextRef) -> Result<()> {
+            if data_points.iter().any(|p| p.metric() == "should_failed") {
                 return error::InternalSnafu {
                     err_msg: "expected",
                 }
                 .fail();
             }
-            self.tx.send(metric.to_string()).await.unwrap();
+            let metrics = data_points.iter().map(|p| p.metric().to_string()).collect();
+            self.tx.send(metrics).await.unwrap();
             Ok(())
         }
+
+        async fn query(
+            &self,
+            _request: &QueryRequest,
+            _query_ctx: QueryContextRef,
+        ) -> Result<Vec<QueryResult>> {
+            unimplemented!()
+        }
+
+        async fn suggest(
+            &self,
+            _request: &SuggestRequest,
+            _query_ctx: QueryContextRef,
+        ) -> Result<Vec<String>> {
+            unimplemented!()
+        }
     }
 
     #[tokio::test]
@@ -140,7 +238,13 @@ mod tests {
 
         let query_handler = Arc::new(DummyQueryHandler { tx });
         let (notify_shutdown, _) = broadcast::channel(1);
-        let addr = start_server(query_handler, notify_shutdown).await;
+        let addr = start_server(
+            query_handler,
+            notify_shutdown,
+            DEFAULT_MAX_BATCH_SIZE,
+            DEFAULT_FLUSH_INTERVAL,
+        )
+        .await;
 
         let stream = TcpStream::connect(addr).await.unwrap();
         let mut client = Connection::new(stream);
@@ -149,13 +253,13 @@ mod tests {
             .write_line("put my_metric_1 1000 1.0 host=web01".to_string())
             .await
             .unwrap();
-        assert_eq!(rx.recv().await.unwrap(), "my_metric_1");
+        assert_eq!(rx.recv().await.unwrap(), vec!["my_metric_1"]);
 
         client
             .write_line("put my_metric_2 1000 1.0 host=web01".to_string())
             .await
             .unwrap();
-        assert_eq!(rx.recv().await.unwrap(), "my_metric_2");
+        assert_eq!(rx.recv().await.unwrap(), vec!["my_metric_2"]);
 
         client
             .write_line("put should_failed 1000 1.0 host=web01".to_string())
@@ -172,9 +276,60 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_run_in_batch() {
+        let (tx, mut rx) = mpsc::channel(100);
+
+        let query_handler = Arc::new(DummyQueryHandler { tx });
+        let (notify_shutdown, _) = broadcast::channel(1);
+        // Never flushes by time, only by batch size or on quit.
+        let addr = start_server(query_handler, notify_shutdown, 2, Duration::from_secs(3600)).await;
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut client = Connection::new(stream);
+
+        for metric in ["my_metric_1", "my_metric_2", "my_metric_3"] {
+            client
+                .write_line(format!("put {} 1000 1.0 host=web01", metric))
+                .await
+                .unwrap();
+        }
+        assert_eq!(rx.recv().await.unwrap(), vec!["my_metric_1", "my_metric_2"]);
+
+        client.write_line("quit".to_string()).await.unwrap();
+        assert_eq!(rx.recv().await.unwrap(), vec!["my_metric_3"]);
+        assert_eq!(client.read_line().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_run_in_batch_with_failed_metric() {
+        let (tx, mut rx) = mpsc::channel(100);
+
+        let query_handler = Arc::new(DummyQueryHandler { tx });
+        let (notify_shutdown, _) = broadcast::channel(1);
+        let addr = start_server(query_handler, notify_shutdown, 4, Duration::from_secs(3600)).await;
+
+        let stream = TcpStream::connect(addr).await.unwrap();
+        let mut client = Connection::new(stream);
+
+        for metric in ["my_metric_1", "should_failed", "my_metric_2", "my_metric_1"] {
+            client
+                .write_line(format!("put {} 1000 1.0 host=web01", metric))
+                .await
+                .unwrap();
+        }
+        // Data points of other metrics in the batch are still written.
+        assert_eq!(rx.recv().await.unwrap(), vec!["my_metric_1", "my_metric_1"]);
+        assert_eq!(rx.recv().await.unwrap(), vec!["my_metric_2"]);
+        let resp = client.read_line().await.unwrap();
+        assert_eq!(resp, Some("Internal error: expected".to_string()));
+    }
+
     async fn start_server(
         query_handler: OpentsdbProtocolHandlerRef,
         notify_shutdown: broadcast::Sender<()>,
+        max_batch_size: usize,
+        flush_interval: Duration,
     ) -> SocketAddr {
         let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
         let addr = listener.local_addr().unwrap();
@@ -188,6 +343,7 @@ mod tests {
                 let shutdown = Shutdown::new(notify_shutdown.subscribe());
                 tokio::spawn(async move {
                     Handler::new(query_handler, connection, shutdown)
+                        .with_batch_options(max_batch_size, flush_interval)
                         .run()
                         .await
                 });
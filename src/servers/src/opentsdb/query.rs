@@ -0,0 +1,1212 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! OpenTSDB query supportings, please refer to the OpenTSDB documents of
+//! ["api/query"](http://opentsdb.net/docs/build/html/api_http/query/index.html) and
+//! ["api/suggest"](http://opentsdb.net/docs/build/html/api_http/suggest.html) for more details.
+//!
+//! A sub query is translated into a SQL doing the downsampling, rate conversion and aggregation
+//! of the series by the query engine, and a SQL listing the aggregated series to find out the
+//! common tags of each group.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use api::v1::codec::SelectResult;
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+
+use crate::error::{self, Result};
+use crate::opentsdb::codec::{
+    DataPoint, OPENTSDB_TIMESTAMP_COLUMN_NAME, OPENTSDB_VALUE_COLUMN_NAME,
+};
+use crate::query_util::{expand_column, quote_ident, quote_literal};
+
+const DEFAULT_SUGGEST_MAX: usize = 25;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryRequest {
+    pub start: TimeSpec,
+    #[serde(default)]
+    pub end: Option<TimeSpec>,
+    pub queries: Vec<SubQuery>,
+    /// Returns data points timestamps in milliseconds instead of seconds.
+    #[serde(default)]
+    pub ms_resolution: bool,
+}
+
+impl QueryRequest {
+    /// Returns the queried time range `[start, end]` in milliseconds, relative times like
+    /// `1h-ago` are resolved against `now_millis`.
+    pub fn time_range(&self, now_millis: i64) -> Result<(i64, i64)> {
+        let start = self.start.to_millis(now_millis)?;
+        let end = match &self.end {
+            Some(end) => end.to_millis(now_millis)?,
+            None => now_millis,
+        };
+        if start > end {
+            return error::InvalidQuerySnafu {
+                reason: format!("start time {} is after end time {}", start, end),
+            }
+            .fail();
+        }
+        Ok((start, end))
+    }
+}
+
+/// Absolute timestamp in seconds or milliseconds, or relative time like `1h-ago`.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum TimeSpec {
+    Timestamp(i64),
+    Text(String),
+}
+
+impl TimeSpec {
+    fn to_millis(&self, now_millis: i64) -> Result<i64> {
+        match self {
+            TimeSpec::Timestamp(t) => Ok(DataPoint::timestamp_to_millis(*t)),
+            TimeSpec::Text(text) => {
+                if let Ok(t) = text.parse::<i64>() {
+                    Ok(DataPoint::timestamp_to_millis(t))
+                } else if let Some(duration) = text.strip_suffix("-ago") {
+                    Ok(now_millis - parse_duration_millis(duration)?)
+                } else {
+                    error::InvalidQuerySnafu {
+                        reason: format!("invalid time: {}", text),
+                    }
+                    .fail()
+                }
+            }
+        }
+    }
+}
+
+/// Parses OpenTSDB durations like `10s` and `1h` into milliseconds.
+fn parse_duration_millis(duration: &str) -> Result<i64> {
+    let invalid_duration = || error::InvalidQuerySnafu {
+        reason: format!("invalid duration: {}", duration),
+    };
+
+    let unit_start = duration
+        .find(|c: char| !c.is_ascii_digit())
+        .with_context(invalid_duration)?;
+    let (value, unit) = duration.split_at(unit_start);
+    let value = value.parse::<i64>().ok().with_context(invalid_duration)?;
+    let unit_millis = match unit {
+        "ms" => 1,
+        "s" => 1000,
+        "m" => 60 * 1000,
+        "h" => 60 * 60 * 1000,
+        "d" => 24 * 60 * 60 * 1000,
+        "w" => 7 * 24 * 60 * 60 * 1000,
+        "n" => 30 * 24 * 60 * 60 * 1000,
+        "y" => 365 * 24 * 60 * 60 * 1000,
+        _ => return invalid_duration().fail(),
+    };
+    Ok(value * unit_millis)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubQuery {
+    pub aggregator: String,
+    pub metric: String,
+    /// Downsample specification like `1m-avg`.
+    #[serde(default)]
+    pub downsample: Option<String>,
+    #[serde(default)]
+    pub rate: bool,
+    #[serde(default)]
+    pub rate_options: Option<RateOptions>,
+    /// Tags to filter and group by, value could be a literal, literals separated by `|` or `*`.
+    #[serde(default)]
+    pub tags: HashMap<String, String>,
+    #[serde(default)]
+    pub filters: Vec<Filter>,
+}
+
+impl SubQuery {
+    /// Returns all the tag filters of this sub query, including the ones converted from `tags`.
+    fn tag_filters(&self) -> Vec<Filter> {
+        let mut filters = self.filters.clone();
+        for (tagk, tagv) in &self.tags {
+            let filter_type = if tagv.contains('*') {
+                "wildcard"
+            } else {
+                "literal_or"
+            };
+            filters.push(Filter {
+                filter_type: filter_type.to_string(),
+                tagk: tagk.clone(),
+                filter: tagv.clone(),
+                group_by: true,
+            });
+        }
+        filters.sort_by(|a, b| a.tagk.cmp(&b.tagk));
+        filters
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RateOptions {
+    #[serde(default)]
+    pub counter: bool,
+    #[serde(default)]
+    pub counter_max: Option<f64>,
+    #[serde(default)]
+    pub reset_value: Option<f64>,
+    #[serde(default)]
+    pub drop_resets: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+    #[serde(rename = "type")]
+    pub filter_type: String,
+    pub tagk: String,
+    pub filter: String,
+    #[serde(default)]
+    pub group_by: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct QueryResult {
+    pub metric: String,
+    /// Tags that have the same value in all the aggregated series.
+    pub tags: BTreeMap<String, String>,
+    /// Tags that have different values in the aggregated series.
+    pub aggregate_tags: Vec<String>,
+    pub dps: BTreeMap<i64, f64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SuggestType {
+    Metrics,
+    Tagk,
+    Tagv,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SuggestRequest {
+    #[serde(rename = "type")]
+    pub suggest_type: SuggestType,
+    /// Prefix of the suggested names, all names are suggested if it's empty.
+    #[serde(default)]
+    pub q: String,
+    #[serde(default = "default_suggest_max")]
+    pub max: usize,
+}
+
+fn default_suggest_max() -> usize {
+    DEFAULT_SUGGEST_MAX
+}
+
+impl SuggestRequest {
+    /// Sorts and deduplicates `candidates`, returns at most `max` ones matching the prefix `q`.
+    pub fn suggest(&self, candidates: impl IntoIterator<Item = String>) -> Vec<String> {
+        candidates
+            .into_iter()
+            .filter(|c| c.starts_with(&self.q))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .take(self.max)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Aggregator {
+    Sum,
+    Min,
+    Max,
+    Avg,
+    Count,
+    Dev,
+    First,
+    Last,
+    /// Only valid for sub query aggregator, means no aggregation across series.
+    None,
+}
+
+impl Aggregator {
+    fn parse(name: &str) -> Result<Self> {
+        let aggregator = match name {
+            "sum" | "zimsum" => Aggregator::Sum,
+            "min" | "mimmin" => Aggregator::Min,
+            "max" | "mimmax" => Aggregator::Max,
+            "avg" => Aggregator::Avg,
+            "count" => Aggregator::Count,
+            "dev" => Aggregator::Dev,
+            "first" => Aggregator::First,
+            "last" => Aggregator::Last,
+            "none" => Aggregator::None,
+            _ => {
+                return error::InvalidQuerySnafu {
+                    reason: format!("unsupported aggregator: {}", name),
+                }
+                .fail()
+            }
+        };
+        Ok(aggregator)
+    }
+
+    /// Returns the SQL aggregate function of this aggregator, or `None` for the aggregators
+    /// picking a data point, which are computed by the `first_value` window function instead.
+    fn sql_function(&self) -> Option<&'static str> {
+        match self {
+            Aggregator::Sum => Some("sum"),
+            Aggregator::Min => Some("min"),
+            Aggregator::Max => Some("max"),
+            Aggregator::Avg => Some("avg"),
+            Aggregator::Count => Some("count"),
+            Aggregator::Dev => Some("stddev_pop"),
+            Aggregator::First | Aggregator::Last | Aggregator::None => None,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Downsample {
+    interval_millis: i64,
+    aggregator: Aggregator,
+}
+
+impl Downsample {
+    /// Parses downsample specification like `1m-avg`, the optional fill policy must be `none`.
+    fn parse(spec: &str) -> Result<Self> {
+        let parts = spec.split('-').collect::<Vec<_>>();
+        let valid_fill_policy = parts.len() == 2 || (parts.len() == 3 && parts[2] == "none");
+        if !valid_fill_policy {
+            return error::InvalidQuerySnafu {
+                reason: format!("invalid downsample: {}", spec),
+            }
+            .fail();
+        }
+
+        let interval_millis = parse_duration_millis(parts[0])?;
+        let aggregator = Aggregator::parse(parts[1])?;
+        if interval_millis <= 0 || aggregator == Aggregator::None {
+            return error::InvalidQuerySnafu {
+                reason: format!("invalid downsample: {}", spec),
+            }
+            .fail();
+        }
+        Ok(Self {
+            interval_millis,
+            aggregator,
+        })
+    }
+}
+
+/// Converts OpenTSDB wildcard like `web*` to an anchored regex.
+fn wildcard_to_regex(wildcard: &str) -> String {
+    let parts = wildcard.split('*').map(regex::escape).collect::<Vec<_>>();
+    format!("^{}$", parts.join(".*"))
+}
+
+fn filter_to_condition(filter: &Filter) -> Result<String> {
+    let tagk = quote_ident(&filter.tagk);
+    let literals = || {
+        filter
+            .filter
+            .split('|')
+            .map(quote_literal)
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    let condition = match filter.filter_type.as_str() {
+        "literal_or" => format!("{} IN ({})", tagk, literals()),
+        "not_literal_or" => format!("{} NOT IN ({})", tagk, literals()),
+        "wildcard" | "iwildcard" if filter.filter == "*" => format!("{} IS NOT NULL", tagk),
+        "wildcard" => format!(
+            "{}~{}",
+            tagk,
+            quote_literal(&wildcard_to_regex(&filter.filter))
+        ),
+        "iwildcard" => format!(
+            "{}~{}",
+            tagk,
+            quote_literal(&format!("(?i){}", wildcard_to_regex(&filter.filter)))
+        ),
+        "regexp" => format!("{}~{}", tagk, quote_literal(&filter.filter)),
+        _ => {
+            return error::InvalidQuerySnafu {
+                reason: format!("unsupported filter type: {}", filter.filter_type),
+            }
+            .fail()
+        }
+    };
+    Ok(condition)
+}
+
+/// Formats `value` as a SQL float literal, which always has a decimal point.
+fn float_literal(value: f64) -> String {
+    let literal = value.to_string();
+    if literal.contains('.') {
+        literal
+    } else {
+        format!("{}.0", literal)
+    }
+}
+
+/// Returns the table and the conditions selecting the data points of the sub query in time
+/// range `[start, end]`, series without any of the group by tags are not selected.
+fn sub_query_source(db: &str, sub_query: &SubQuery, start: i64, end: i64) -> Result<String> {
+    let mut conditions = vec![
+        format!(
+            "{}>={} AND {}<={}",
+            OPENTSDB_TIMESTAMP_COLUMN_NAME, start, OPENTSDB_TIMESTAMP_COLUMN_NAME, end,
+        ),
+        format!("{} IS NOT NULL", OPENTSDB_VALUE_COLUMN_NAME),
+    ];
+    for filter in sub_query.tag_filters() {
+        conditions.push(filter_to_condition(&filter)?);
+        if filter.group_by {
+            conditions.push(format!("{} IS NOT NULL", quote_ident(&filter.tagk)));
+        }
+    }
+
+    Ok(format!(
+        "{}.{} WHERE {}",
+        quote_ident(db),
+        quote_ident(&sub_query.metric),
+        conditions.join(" AND ")
+    ))
+}
+
+/// Generates a SQL aggregating the values of the rows `from` in the groups of `keys`, a key is
+/// a pair of the grouping expression and its output name. The `first` and `last` aggregators
+/// pick the first and last value of a group in the order of `order_by`.
+fn aggregate_sql(
+    aggregator: Aggregator,
+    keys: &[(String, String)],
+    order_by: &[String],
+    from: &str,
+) -> String {
+    let value = OPENTSDB_VALUE_COLUMN_NAME;
+    let projection = keys
+        .iter()
+        .map(|(expr, name)| {
+            if expr == name {
+                expr.clone()
+            } else {
+                format!("{} AS {}", expr, name)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+    let group_exprs = keys
+        .iter()
+        .map(|(expr, _)| expr.as_str())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    match aggregator.sql_function() {
+        Some(function) => format!(
+            "SELECT {}, CAST({}({}) AS DOUBLE) AS {} FROM {} GROUP BY {}",
+            projection, function, value, value, from, group_exprs
+        ),
+        None => {
+            let direction = if aggregator == Aggregator::First {
+                "ASC"
+            } else {
+                "DESC"
+            };
+            let order_by = order_by
+                .iter()
+                .map(|expr| format!("{} {}", expr, direction))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "SELECT DISTINCT {}, CAST(first_value({}) OVER (PARTITION BY {} ORDER BY {}) \
+                 AS DOUBLE) AS {} FROM {}",
+                projection, value, group_exprs, order_by, value, from
+            )
+        }
+    }
+}
+
+/// Generates a SQL converting the values of each series of `series_keys` in the rows `from` to
+/// the rate of change per second, the first data point of each series is dropped.
+fn rate_sql(series_keys: &[String], from: &str, options: &RateOptions) -> String {
+    let ts = OPENTSDB_TIMESTAMP_COLUMN_NAME;
+    let value = OPENTSDB_VALUE_COLUMN_NAME;
+    let window = if series_keys.is_empty() {
+        format!("ORDER BY {}", ts)
+    } else {
+        format!("PARTITION BY {} ORDER BY {}", series_keys.join(", "), ts)
+    };
+    let columns = series_keys
+        .iter()
+        .map(String::as_str)
+        .chain([ts])
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let delta = if options.counter {
+        let counter_max = options.counter_max.unwrap_or(u64::MAX as f64);
+        format!(
+            "CASE WHEN {v} < prev_value THEN {v} - prev_value + {max} ELSE {v} - prev_value END",
+            v = value,
+            max = float_literal(counter_max)
+        )
+    } else {
+        format!("{} - prev_value", value)
+    };
+    let mut rate = format!(
+        "({}) / ((CAST({} AS BIGINT) - prev_ts) / 1000.0)",
+        delta, ts
+    );
+    match options.reset_value {
+        Some(reset_value) if options.counter && reset_value > 0.0 => {
+            rate = format!(
+                "CASE WHEN {r} > {reset} THEN 0.0 ELSE {r} END",
+                r = rate,
+                reset = float_literal(reset_value)
+            );
+        }
+        _ => {}
+    }
+
+    let mut conditions = vec!["prev_value IS NOT NULL".to_string()];
+    if options.counter && options.drop_resets {
+        conditions.push(format!("{} >= prev_value", value));
+    }
+    format!(
+        "SELECT {columns}, {rate} AS {value} FROM (SELECT {columns}, {value}, \
+         lag({value}) OVER ({window}) AS prev_value, \
+         lag(CAST({ts} AS BIGINT)) OVER ({window}) AS prev_ts FROM {from}) AS r \
+         WHERE {conditions}",
+        columns = columns,
+        rate = rate,
+        value = value,
+        window = window,
+        ts = ts,
+        from = from,
+        conditions = conditions.join(" AND "),
+    )
+}
+
+/// Generates a SQL computing the data points of the sub query in time range `[start, end]`,
+/// `tagks` are all the tag names of the metric.
+///
+/// The downsampling, rate conversion and aggregation of the sub query are applied in order by
+/// the query engine, the result has the timestamp and value columns, as well as the group by
+/// tags, or all the tags if the sub query doesn't aggregate series.
+pub fn sub_query_to_sql(
+    db: &str,
+    sub_query: &SubQuery,
+    tagks: &[String],
+    start: i64,
+    end: i64,
+) -> Result<String> {
+    let aggregator = Aggregator::parse(&sub_query.aggregator)?;
+    let downsample = sub_query
+        .downsample
+        .as_deref()
+        .map(Downsample::parse)
+        .transpose()?;
+    let ts = OPENTSDB_TIMESTAMP_COLUMN_NAME.to_string();
+    let series_keys = tagks.iter().map(|k| quote_ident(k)).collect::<Vec<_>>();
+    let with_ts = |keys: Vec<String>| {
+        keys.into_iter()
+            .chain([ts.clone()])
+            .map(|k| (k.clone(), k))
+            .collect::<Vec<_>>()
+    };
+
+    let mut from = sub_query_source(db, sub_query, start, end)?;
+    let mut sql = None;
+    if let Some(downsample) = downsample {
+        let mut keys = with_ts(series_keys.clone());
+        let bucket = format!(
+            "time_bucket('{}ms', {})",
+            downsample.interval_millis, OPENTSDB_TIMESTAMP_COLUMN_NAME
+        );
+        keys.last_mut().unwrap().0 = bucket;
+        let downsampled = aggregate_sql(downsample.aggregator, &keys, &[ts.clone()], &from);
+        from = format!("({}) AS t", downsampled);
+        sql = Some(downsampled);
+    }
+    if sub_query.rate {
+        let rate_options = sub_query.rate_options.clone().unwrap_or_default();
+        let rates = rate_sql(&series_keys, &from, &rate_options);
+        from = format!("({}) AS t", rates);
+        sql = Some(rates);
+    }
+    if aggregator != Aggregator::None {
+        let group_by_tags = sub_query
+            .tag_filters()
+            .into_iter()
+            .filter(|f| f.group_by)
+            .map(|f| quote_ident(&f.tagk))
+            .collect::<BTreeSet<_>>();
+        let keys = with_ts(group_by_tags.into_iter().collect());
+        // Series are ordered by their tags, like the series of a group in the result.
+        let order_by = if series_keys.is_empty() {
+            vec![ts.clone()]
+        } else {
+            series_keys.clone()
+        };
+        sql = Some(aggregate_sql(aggregator, &keys, &order_by, &from));
+    }
+
+    let sql = sql.unwrap_or_else(|| {
+        let columns = with_ts(series_keys)
+            .into_iter()
+            .map(|(k, _)| k)
+            .chain([OPENTSDB_VALUE_COLUMN_NAME.to_string()])
+            .collect::<Vec<_>>();
+        format!("SELECT {} FROM {}", columns.join(", "), from)
+    });
+    Ok(format!("{} ORDER BY {}", sql, ts))
+}
+
+/// Generates a SQL selecting the distinct tags of the series matching the sub query in time
+/// range `[start, end]`, returns `None` if the metric has no tags.
+pub fn sub_query_to_series_sql(
+    db: &str,
+    sub_query: &SubQuery,
+    tagks: &[String],
+    start: i64,
+    end: i64,
+) -> Result<Option<String>> {
+    if tagks.is_empty() {
+        return Ok(None);
+    }
+    let columns = tagks.iter().map(|k| quote_ident(k)).collect::<Vec<_>>();
+    Ok(Some(format!(
+        "SELECT DISTINCT {} FROM {}",
+        columns.join(", "),
+        sub_query_source(db, sub_query, start, end)?
+    )))
+}
+
+/// Name of the column in the result of [tagv_suggest_sql].
+pub const TAGV_COLUMN_NAME: &str = "tagv";
+
+/// Generates a SQL selecting the distinct values of all the tags in `metrics` matching the
+/// suggest request, `metrics` are pairs of the metric and its tag names. Returns `None` if
+/// there are no tags at all.
+pub fn tagv_suggest_sql(
+    db: &str,
+    metrics: &[(String, Vec<String>)],
+    request: &SuggestRequest,
+) -> Option<String> {
+    let selects = metrics
+        .iter()
+        .flat_map(|(metric, tagks)| {
+            tagks.iter().map(move |tagk| {
+                format!(
+                    "SELECT {} AS {} FROM {}.{}",
+                    quote_ident(tagk),
+                    TAGV_COLUMN_NAME,
+                    quote_ident(db),
+                    quote_ident(metric)
+                )
+            })
+        })
+        .collect::<Vec<_>>();
+    if selects.is_empty() {
+        return None;
+    }
+
+    let mut sql = format!(
+        "SELECT DISTINCT {} FROM ({}) AS t WHERE {} IS NOT NULL",
+        TAGV_COLUMN_NAME,
+        selects.join(" UNION ALL "),
+        TAGV_COLUMN_NAME
+    );
+    if !request.q.is_empty() {
+        sql.push_str(&format!(
+            " AND starts_with({}, {})",
+            TAGV_COLUMN_NAME,
+            quote_literal(&request.q)
+        ));
+    }
+    sql.push_str(&format!(
+        " ORDER BY {} LIMIT {}",
+        TAGV_COLUMN_NAME, request.max
+    ));
+    Some(sql)
+}
+
+/// Returns values of the string column `name` in `select_result`.
+pub fn string_column_values(
+    select_result: &SelectResult,
+    name: &str,
+) -> Result<Vec<Option<String>>> {
+    let column = select_result
+        .columns
+        .iter()
+        .find(|c| c.column_name == name)
+        .with_context(|| error::InternalSnafu {
+            err_msg: format!("missing column {} in query result", name),
+        })?;
+    let values = column
+        .values
+        .as_ref()
+        .map(|v| v.string_values.as_slice())
+        .unwrap_or_default();
+    Ok(expand_column(
+        column,
+        values,
+        select_result.row_count as usize,
+    ))
+}
+
+type Tags = BTreeMap<String, String>;
+
+/// Rows of the query result, the timestamps and values are `None` if the columns are absent.
+struct Rows {
+    tags: Vec<Tags>,
+    timestamps: Option<Vec<Option<i64>>>,
+    values: Option<Vec<Option<f64>>>,
+}
+
+impl Rows {
+    fn new(select_result: &SelectResult) -> Self {
+        let row_count = select_result.row_count as usize;
+        let mut timestamps = None;
+        let mut values = None;
+        let mut tags = vec![Tags::new(); row_count];
+        for column in &select_result.columns {
+            let column_values = column.values.as_ref();
+            if column.column_name == OPENTSDB_TIMESTAMP_COLUMN_NAME {
+                let ts = column_values
+                    .map(|v| v.ts_millis_values.as_slice())
+                    .unwrap_or_default();
+                timestamps = Some(expand_column(column, ts, row_count));
+            } else if column.column_name == OPENTSDB_VALUE_COLUMN_NAME {
+                let vs = column_values
+                    .map(|v| v.f64_values.as_slice())
+                    .unwrap_or_default();
+                values = Some(expand_column(column, vs, row_count));
+            } else {
+                let vs = column_values
+                    .map(|v| v.string_values.as_slice())
+                    .unwrap_or_default();
+                for (row, value) in expand_column(column, vs, row_count).into_iter().enumerate() {
+                    if let Some(value) = value {
+                        tags[row].insert(column.column_name.clone(), value);
+                    }
+                }
+            }
+        }
+        Self {
+            tags,
+            timestamps,
+            values,
+        }
+    }
+}
+
+/// Converts the results of the SQLs generated by [sub_query_to_sql] and
+/// [sub_query_to_series_sql] to OpenTSDB query results, `series` is `None` if the metric has no
+/// tags.
+pub fn select_result_to_query_results(
+    sub_query: &SubQuery,
+    series: Option<SelectResult>,
+    select_result: SelectResult,
+    ms_resolution: bool,
+) -> Result<Vec<QueryResult>> {
+    let series = match series {
+        Some(series) => Rows::new(&series).tags,
+        None => vec![Tags::new()],
+    };
+
+    let rows = Rows::new(&select_result);
+    let timestamps = rows.timestamps.context(error::InternalSnafu {
+        err_msg: "missing greptime_timestamp column in query result",
+    })?;
+    let values = rows.values.context(error::InternalSnafu {
+        err_msg: "missing greptime_value column in query result",
+    })?;
+
+    // Rows are grouped by the group by tags, or all the tags if series are not aggregated.
+    let mut groups: BTreeMap<Tags, BTreeMap<i64, f64>> = BTreeMap::new();
+    for (row, group_key) in rows.tags.into_iter().enumerate() {
+        if let (Some(ts), Some(value)) = (timestamps[row], values[row]) {
+            let ts = if ms_resolution { ts } else { ts / 1000 };
+            groups.entry(group_key).or_default().insert(ts, value);
+        }
+    }
+
+    Ok(groups
+        .into_iter()
+        .map(|(group_key, dps)| {
+            let group_series = series
+                .iter()
+                .filter(|tags| group_key.iter().all(|(k, v)| tags.get(k) == Some(v)))
+                .collect::<Vec<_>>();
+            query_result(&sub_query.metric, group_key, &group_series, dps)
+        })
+        .collect())
+}
+
+fn query_result(
+    metric: &str,
+    group_key: Tags,
+    group_series: &[&Tags],
+    dps: BTreeMap<i64, f64>,
+) -> QueryResult {
+    // Tags with the same value in all series are kept, others become aggregated tags.
+    let mut tags = group_series
+        .first()
+        .map_or(group_key, |series_tags| (*series_tags).clone());
+    let mut all_tagks = BTreeSet::new();
+    for series_tags in group_series {
+        all_tagks.extend(series_tags.keys().cloned());
+        tags.retain(|k, v| series_tags.get(k) == Some(v));
+    }
+    let aggregate_tags = all_tagks
+        .into_iter()
+        .filter(|k| !tags.contains_key(k))
+        .collect();
+
+    QueryResult {
+        metric: metric.to_string(),
+        tags,
+        aggregate_tags,
+        dps,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use api::v1::column::Values;
+    use api::v1::Column;
+    use common_base::BitVec;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_query_request() {
+        let request = r#"{
+            "start": "1h-ago",
+            "end": 1346850000,
+            "queries": [{
+                "aggregator": "sum",
+                "metric": "sys.cpu.nice",
+                "downsample": "1m-avg",
+                "rate": true,
+                "tags": {"host": "web01|web02"},
+                "filters": [{"type": "wildcard", "tagk": "dc", "filter": "lga*", "groupBy": false}]
+            }]
+        }"#;
+        let request = serde_json::from_str::<QueryRequest>(request).unwrap();
+        assert_eq!(TimeSpec::Text("1h-ago".to_string()), request.start);
+        assert_eq!(Some(TimeSpec::Timestamp(1346850000)), request.end);
+        assert!(!request.ms_resolution);
+        let sub_query = &request.queries[0];
+        assert_eq!("sys.cpu.nice", sub_query.metric);
+        assert_eq!(Some("1m-avg".to_string()), sub_query.downsample);
+        assert!(sub_query.rate);
+        assert_eq!(2, sub_query.tag_filters().len());
+
+        let now = 1346850000000 + 3600 * 1000;
+        assert_eq!(
+            (1346850000000, 1346850000000),
+            request.time_range(now).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_time_spec() {
+        let now = 1_000_000_000_000;
+        assert_eq!(
+            1346846400000,
+            TimeSpec::Timestamp(1346846400).to_millis(now).unwrap()
+        );
+        assert_eq!(
+            1346846400123,
+            TimeSpec::Text("1346846400123".to_string())
+                .to_millis(now)
+                .unwrap()
+        );
+        assert_eq!(
+            now - 2 * 3600 * 1000,
+            TimeSpec::Text("2h-ago".to_string()).to_millis(now).unwrap()
+        );
+        assert_eq!(
+            now - 30 * 1000,
+            TimeSpec::Text("30s-ago".to_string())
+                .to_millis(now)
+                .unwrap()
+        );
+        assert!(TimeSpec::Text("2x-ago".to_string()).to_millis(now).is_err());
+        assert!(TimeSpec::Text("h-ago".to_string()).to_millis(now).is_err());
+        assert!(TimeSpec::Text("yesterday".to_string())
+            .to_millis(now)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_downsample() {
+        assert_eq!(
+            Downsample {
+                interval_millis: 60 * 1000,
+                aggregator: Aggregator::Avg
+            },
+            Downsample::parse("1m-avg").unwrap()
+        );
+        assert_eq!(
+            Downsample {
+                interval_millis: 10 * 1000,
+                aggregator: Aggregator::Sum
+            },
+            Downsample::parse("10s-sum-none").unwrap()
+        );
+        assert!(Downsample::parse("1m").is_err());
+        assert!(Downsample::parse("1m-none").is_err());
+        assert!(Downsample::parse("1m-avg-zero").is_err());
+        assert!(Downsample::parse("0s-avg").is_err());
+        assert!(Downsample::parse("1m-median").is_err());
+    }
+
+    #[test]
+    fn test_sub_query_to_sql() {
+        let sub_query = SubQuery {
+            aggregator: "sum".to_string(),
+            metric: "sys.cpu.nice".to_string(),
+            downsample: None,
+            rate: false,
+            rate_options: None,
+            tags: HashMap::from([("host".to_string(), "web01|web'02".to_string())]),
+            filters: vec![
+                Filter {
+                    filter_type: "wildcard".to_string(),
+                    tagk: "dc".to_string(),
+                    filter: "lga.*".to_string(),
+                    group_by: false,
+                },
+                Filter {
+                    filter_type: "not_literal_or".to_string(),
+                    tagk: "env".to_string(),
+                    filter: "test".to_string(),
+                    group_by: false,
+                },
+                Filter {
+                    filter_type: "regexp".to_string(),
+                    tagk: "rack".to_string(),
+                    filter: "r[0-9]+".to_string(),
+                    group_by: true,
+                },
+            ],
+        };
+        let tagks = ["dc", "env", "host", "rack"].map(String::from);
+        let source = "\"public\".\"sys.cpu.nice\" WHERE greptime_timestamp>=1000 AND greptime_timestamp<=2000 AND greptime_value IS NOT NULL AND \"dc\"~'^lga\\..*$' AND \"env\" NOT IN ('test') AND \"host\" IN ('web01','web''02') AND \"host\" IS NOT NULL AND \"rack\"~'r[0-9]+' AND \"rack\" IS NOT NULL";
+        let sql = sub_query_to_sql("public", &sub_query, &tagks, 1000, 2000).unwrap();
+        assert_eq!(
+            format!("SELECT \"host\", \"rack\", greptime_timestamp, CAST(sum(greptime_value) AS DOUBLE) AS greptime_value FROM {} GROUP BY \"host\", \"rack\", greptime_timestamp ORDER BY greptime_timestamp", source),
+            sql
+        );
+        let sql = sub_query_to_series_sql("public", &sub_query, &tagks, 1000, 2000).unwrap();
+        assert_eq!(
+            Some(format!(
+                "SELECT DISTINCT \"dc\", \"env\", \"host\", \"rack\" FROM {}",
+                source
+            )),
+            sql
+        );
+        assert_eq!(
+            None,
+            sub_query_to_series_sql("public", &sub_query, &[], 1000, 2000).unwrap()
+        );
+
+        let mut sub_query = sub_query;
+        sub_query.filters = vec![Filter {
+            filter_type: "unknown".to_string(),
+            tagk: "dc".to_string(),
+            filter: "lga".to_string(),
+            group_by: false,
+        }];
+        assert!(sub_query_to_sql("public", &sub_query, &tagks, 1000, 2000).is_err());
+        assert!(sub_query_to_series_sql("public", &sub_query, &tagks, 1000, 2000).is_err());
+
+        sub_query.filters.clear();
+        sub_query.aggregator = "median".to_string();
+        assert!(sub_query_to_sql("public", &sub_query, &tagks, 1000, 2000).is_err());
+    }
+
+    #[test]
+    fn test_sub_query_to_sql_stages() {
+        let mut sub_query = mock_sub_query("none", None);
+        let tagks = vec!["host".to_string()];
+        let sql = sub_query_to_sql("public", &sub_query, &tagks, 1000, 2000).unwrap();
+        assert_eq!(
+            "SELECT \"host\", greptime_timestamp, greptime_value FROM \"public\".\"sys.cpu.nice\" WHERE greptime_timestamp>=1000 AND greptime_timestamp<=2000 AND greptime_value IS NOT NULL ORDER BY greptime_timestamp",
+            sql
+        );
+
+        // downsamples each series, then converts them to rates
+        sub_query.downsample = Some("1m-avg".to_string());
+        sub_query.rate = true;
+        let sql = sub_query_to_sql("public", &sub_query, &tagks, 1000, 2000).unwrap();
+        assert!(sql
+            .starts_with("SELECT \"host\", greptime_timestamp, (greptime_value - prev_value) / "));
+        assert!(sql.contains("FROM (SELECT \"host\", time_bucket('60000ms', greptime_timestamp) AS greptime_timestamp, CAST(avg(greptime_value) AS DOUBLE) AS greptime_value FROM \"public\".\"sys.cpu.nice\" WHERE greptime_timestamp>=1000 AND greptime_timestamp<=2000 AND greptime_value IS NOT NULL GROUP BY \"host\", time_bucket('60000ms', greptime_timestamp)) AS t)"));
+        assert!(sql.ends_with(") AS r WHERE prev_value IS NOT NULL ORDER BY greptime_timestamp"));
+
+        // aggregates the rates of all series
+        sub_query.aggregator = "max".to_string();
+        let sql = sub_query_to_sql("public", &sub_query, &tagks, 1000, 2000).unwrap();
+        assert!(sql.starts_with(
+            "SELECT greptime_timestamp, CAST(max(greptime_value) AS DOUBLE) AS greptime_value FROM (SELECT \"host\", greptime_timestamp, (greptime_value - prev_value) / "
+        ));
+        assert!(sql.ends_with(
+            ") AS r WHERE prev_value IS NOT NULL) AS t GROUP BY greptime_timestamp ORDER BY greptime_timestamp"
+        ));
+    }
+
+    #[test]
+    fn test_aggregate_sql() {
+        let keys = vec![
+            ("\"host\"".to_string(), "\"host\"".to_string()),
+            (
+                "time_bucket('60000ms', greptime_timestamp)".to_string(),
+                "greptime_timestamp".to_string(),
+            ),
+        ];
+        let order_by = vec!["greptime_timestamp".to_string()];
+        assert_eq!(
+            "SELECT \"host\", time_bucket('60000ms', greptime_timestamp) AS greptime_timestamp, CAST(stddev_pop(greptime_value) AS DOUBLE) AS greptime_value FROM t0 GROUP BY \"host\", time_bucket('60000ms', greptime_timestamp)",
+            aggregate_sql(Aggregator::Dev, &keys, &order_by, "t0")
+        );
+        assert_eq!(
+            "SELECT DISTINCT \"host\", time_bucket('60000ms', greptime_timestamp) AS greptime_timestamp, CAST(first_value(greptime_value) OVER (PARTITION BY \"host\", time_bucket('60000ms', greptime_timestamp) ORDER BY greptime_timestamp DESC) AS DOUBLE) AS greptime_value FROM t0",
+            aggregate_sql(Aggregator::Last, &keys, &order_by, "t0")
+        );
+        assert!(aggregate_sql(Aggregator::First, &keys, &order_by, "t0")
+            .contains("ORDER BY greptime_timestamp ASC"));
+    }
+
+    #[test]
+    fn test_rate_sql() {
+        let series_keys = vec!["\"host\"".to_string()];
+        assert_eq!(
+            "SELECT \"host\", greptime_timestamp, (greptime_value - prev_value) / ((CAST(greptime_timestamp AS BIGINT) - prev_ts) / 1000.0) AS greptime_value FROM (SELECT \"host\", greptime_timestamp, greptime_value, lag(greptime_value) OVER (PARTITION BY \"host\" ORDER BY greptime_timestamp) AS prev_value, lag(CAST(greptime_timestamp AS BIGINT)) OVER (PARTITION BY \"host\" ORDER BY greptime_timestamp) AS prev_ts FROM t0) AS r WHERE prev_value IS NOT NULL",
+            rate_sql(&series_keys, "t0", &RateOptions::default())
+        );
+
+        let options = RateOptions {
+            counter: true,
+            counter_max: Some(100.0),
+            reset_value: Some(50.0),
+            drop_resets: true,
+        };
+        let sql = rate_sql(&[], "t0", &options);
+        assert!(sql.starts_with("SELECT greptime_timestamp, CASE WHEN (CASE WHEN greptime_value < prev_value THEN greptime_value - prev_value + 100.0 ELSE greptime_value - prev_value END) / ((CAST(greptime_timestamp AS BIGINT) - prev_ts) / 1000.0) > 50.0 THEN 0.0 ELSE "));
+        assert!(sql.contains("OVER (ORDER BY greptime_timestamp) AS prev_value"));
+        assert!(sql.ends_with("WHERE prev_value IS NOT NULL AND greptime_value >= prev_value"));
+
+        assert_eq!("1.0", float_literal(1.0));
+        assert_eq!("0.5", float_literal(0.5));
+        assert_eq!("18446744073709552000.0", float_literal(u64::MAX as f64));
+    }
+
+    fn string_column(name: &str, values: &[Option<&str>]) -> Column {
+        let null_mask = BitVec::from_iter(values.iter().map(|v| v.is_none()));
+        Column {
+            column_name: name.to_string(),
+            values: Some(Values {
+                string_values: values.iter().flatten().map(|v| v.to_string()).collect(),
+                ..Default::default()
+            }),
+            null_mask: null_mask.into_vec(),
+            ..Default::default()
+        }
+    }
+
+    fn mock_select_result(tag_columns: Vec<Column>, dps: &[(i64, f64)]) -> SelectResult {
+        let mut columns = tag_columns;
+        columns.push(Column {
+            column_name: OPENTSDB_TIMESTAMP_COLUMN_NAME.to_string(),
+            values: Some(Values {
+                ts_millis_values: dps.iter().map(|p| p.0).collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        columns.push(Column {
+            column_name: OPENTSDB_VALUE_COLUMN_NAME.to_string(),
+            values: Some(Values {
+                f64_values: dps.iter().map(|p| p.1).collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        });
+        SelectResult {
+            columns,
+            row_count: dps.len() as u32,
+        }
+    }
+
+    fn mock_series() -> SelectResult {
+        // the last series has no "dc"
+        SelectResult {
+            columns: vec![
+                string_column("host", &[Some("web01"), Some("web02"), Some("web03")]),
+                string_column("dc", &[Some("lga"), Some("lga"), None]),
+            ],
+            row_count: 3,
+        }
+    }
+
+    fn mock_sub_query(aggregator: &str, downsample: Option<&str>) -> SubQuery {
+        SubQuery {
+            aggregator: aggregator.to_string(),
+            metric: "sys.cpu.nice".to_string(),
+            downsample: downsample.map(|s| s.to_string()),
+            rate: false,
+            rate_options: None,
+            tags: HashMap::new(),
+            filters: vec![],
+        }
+    }
+
+    #[test]
+    fn test_select_result_to_query_results() {
+        // aggregates all series
+        let sub_query = mock_sub_query("sum", None);
+        let data = mock_select_result(vec![], &[(1000, 3.0), (61000, 17.0)]);
+        let results =
+            select_result_to_query_results(&sub_query, Some(mock_series()), data, true).unwrap();
+        assert_eq!(
+            vec![QueryResult {
+                metric: "sys.cpu.nice".to_string(),
+                tags: BTreeMap::new(),
+                aggregate_tags: vec!["dc".to_string(), "host".to_string()],
+                dps: BTreeMap::from([(1000, 3.0), (61000, 17.0)]),
+            }],
+            results
+        );
+
+        // in seconds resolution
+        let data = mock_select_result(vec![], &[(1000, 3.0), (61000, 17.0)]);
+        let results =
+            select_result_to_query_results(&sub_query, Some(mock_series()), data, false).unwrap();
+        assert_eq!(BTreeMap::from([(1, 3.0), (61, 17.0)]), results[0].dps);
+
+        // groups by "dc"
+        let mut sub_query = mock_sub_query("sum", None);
+        sub_query.tags = HashMap::from([("dc".to_string(), "*".to_string())]);
+        let data = mock_select_result(
+            vec![string_column("dc", &[Some("lga"), Some("lga")])],
+            &[(1000, 3.0), (61000, 7.0)],
+        );
+        let results =
+            select_result_to_query_results(&sub_query, Some(mock_series()), data, true).unwrap();
+        assert_eq!(1, results.len());
+        assert_eq!(
+            BTreeMap::from([("dc".to_string(), "lga".to_string())]),
+            results[0].tags
+        );
+        assert_eq!(vec!["host".to_string()], results[0].aggregate_tags);
+        assert_eq!(BTreeMap::from([(1000, 3.0), (61000, 7.0)]), results[0].dps);
+
+        // no aggregation
+        let sub_query = mock_sub_query("none", None);
+        let data = mock_select_result(
+            vec![
+                string_column("host", &[Some("web01"), Some("web01"), Some("web03")]),
+                string_column("dc", &[Some("lga"), Some("lga"), None]),
+            ],
+            &[(1000, 1.0), (61000, 3.0), (61000, 10.0)],
+        );
+        let results =
+            select_result_to_query_results(&sub_query, Some(mock_series()), data, true).unwrap();
+        assert_eq!(2, results.len());
+        assert_eq!(
+            BTreeMap::from([
+                ("dc".to_string(), "lga".to_string()),
+                ("host".to_string(), "web01".to_string())
+            ]),
+            results[0].tags
+        );
+        assert!(results[0].aggregate_tags.is_empty());
+        assert_eq!(BTreeMap::from([(1000, 1.0), (61000, 3.0)]), results[0].dps);
+        assert_eq!(
+            BTreeMap::from([("host".to_string(), "web03".to_string())]),
+            results[1].tags
+        );
+
+        // metric without tags
+        let data = mock_select_result(vec![], &[(1000, 1.0)]);
+        let results = select_result_to_query_results(&sub_query, None, data, true).unwrap();
+        assert!(results[0].tags.is_empty());
+        assert!(results[0].aggregate_tags.is_empty());
+
+        let data = SelectResult {
+            columns: vec![],
+            row_count: 0,
+        };
+        assert!(select_result_to_query_results(&sub_query, None, data, true).is_err());
+    }
+
+    #[test]
+    fn test_string_column_values() {
+        let values = string_column_values(&mock_series(), "dc").unwrap();
+        assert_eq!(
+            vec![Some("lga".to_string()), Some("lga".to_string()), None],
+            values
+        );
+        assert!(string_column_values(&mock_series(), "foo").is_err());
+    }
+
+    #[test]
+    fn test_tagv_suggest_sql() {
+        let request =
+            serde_json::from_str::<SuggestRequest>(r#"{"type": "tagv", "q": "web"}"#).unwrap();
+        let metrics = vec![
+            ("m1".to_string(), vec!["host".to_string(), "dc".to_string()]),
+            ("m2".to_string(), vec![]),
+        ];
+        assert_eq!(
+            Some("SELECT DISTINCT tagv FROM (SELECT \"host\" AS tagv FROM \"public\".\"m1\" UNION ALL SELECT \"dc\" AS tagv FROM \"public\".\"m1\") AS t WHERE tagv IS NOT NULL AND starts_with(tagv, 'web') ORDER BY tagv LIMIT 25".to_string()),
+            tagv_suggest_sql("public", &metrics, &request)
+        );
+        assert_eq!(None, tagv_suggest_sql("public", &metrics[1..], &request));
+    }
+
+    #[test]
+    fn test_suggest() {
+        let request =
+            serde_json::from_str::<SuggestRequest>(r#"{"type": "metrics", "q": "sys.", "max": 2}"#)
+                .unwrap();
+        assert_eq!(SuggestType::Metrics, request.suggest_type);
+        let suggestions = request.suggest(
+            [
+                "sys.mem",
+                "sys.cpu.user",
+                "app.latency",
+                "sys.cpu.nice",
+                "sys.cpu.user",
+            ]
+            .into_iter()
+            .map(|s| s.to_string()),
+        );
+        assert_eq!(vec!["sys.cpu.nice", "sys.cpu.user"], suggestions);
+
+        let request = serde_json::from_str::<SuggestRequest>(r#"{"type": "tagk"}"#).unwrap();
+        assert_eq!(SuggestType::Tagk, request.suggest_type);
+        assert_eq!("", request.q);
+        assert_eq!(DEFAULT_SUGGEST_MAX, request.max);
+    }
+}
@@ -25,7 +25,9 @@ use pgwire::messages::startup::Authentication;
 use pgwire::messages::{PgWireBackendMessage, PgWireFrontendMessage};
 use snafu::ResultExt;
 
-use crate::auth::{Identity, Password, UserProviderRef};
+use crate::auth::{
+    login_to_tenant, parse_login_name, Identity, Password, UserInfo, UserProviderRef,
+};
 use crate::error;
 use crate::error::Result;
 
@@ -57,23 +59,29 @@ impl LoginInfo {
 }
 
 impl PgPwdVerifier {
-    async fn verify_pwd(&self, password: &str, login: LoginInfo) -> Result<bool> {
-        if let Some(user_provider) = &self.user_provider {
-            let user_name = match login.user {
-                Some(name) => name,
-                None => return Ok(false),
-            };
-
-            // TODO(fys): pass user_info to context
-            let _user_info = user_provider
+    /// Verifies the password, returns the user on success.
+    async fn verify_pwd(&self, password: &str, login: LoginInfo) -> Result<Option<UserInfo>> {
+        let login_name = match login.user {
+            Some(name) => name,
+            None if self.user_provider.is_some() => return Ok(None),
+            None => return Ok(Some(UserInfo::default())),
+        };
+        // The tenant can be chosen by logging in as `<username>@<tenant>`.
+        let (user_name, tenant) = parse_login_name(&login_name);
+
+        let user_info = if let Some(user_provider) = &self.user_provider {
+            user_provider
                 .auth(
-                    Identity::UserId(&user_name, None),
+                    Identity::UserId(user_name, None),
                     Password::PlainText(password),
                 )
                 .await
-                .context(error::AuthSnafu)?;
-        }
-        Ok(true)
+                .context(error::AuthSnafu)?
+        } else {
+            UserInfo::new(user_name)
+        };
+        let user_info = login_to_tenant(user_info, tenant).context(error::AuthSnafu)?;
+        Ok(Some(user_info))
     }
 }
 
@@ -164,7 +172,21 @@ impl StartupHandler for PgAuthStartupHandler {
             }
             PgWireFrontendMessage::Password(ref pwd) => {
                 let login_info = LoginInfo::from_client_info(client);
-                if let Ok(true) = self.verifier.verify_pwd(pwd.password(), login_info).await {
+                if let Ok(Some(user_info)) =
+                    self.verifier.verify_pwd(pwd.password(), login_info).await
+                {
+                    // Saves the authenticated user, it's bound to the session on the first query.
+                    let metadata = client.metadata_mut();
+                    metadata.insert(
+                        super::METADATA_USER.to_owned(),
+                        user_info.user_name().to_owned(),
+                    );
+                    match user_info.catalog() {
+                        Some(catalog) => {
+                            metadata.insert(super::METADATA_TENANT.to_owned(), catalog.to_owned())
+                        }
+                        None => metadata.remove(super::METADATA_TENANT),
+                    };
                     auth::finish_authentication(client, &self.param_provider).await
                 } else {
                     let error_info = ErrorInfo::new(
@@ -16,29 +16,41 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use common_catalog::parse_catalog_and_schema_from_client_database_name;
 use common_query::Output;
 use common_recordbatch::error::Result as RecordBatchResult;
 use common_recordbatch::RecordBatch;
+use common_telemetry::warn;
+use common_time::TimeZone;
 use datatypes::prelude::{ConcreteDataType, Value};
 use datatypes::schema::SchemaRef;
 use futures::{future, stream, Stream, StreamExt};
+use once_cell::sync::OnceCell;
 use pgwire::api::portal::Portal;
 use pgwire::api::query::{ExtendedQueryHandler, SimpleQueryHandler};
 use pgwire::api::results::{text_query_response, FieldInfo, Response, Tag, TextDataRowEncoder};
 use pgwire::api::{ClientInfo, Type};
 use pgwire::error::{PgWireError, PgWireResult};
-use session::context::QueryContext;
+use session::context::{QueryContext, QueryContextRef};
 
+use crate::auth::parse_login_name;
 use crate::error::{self, Error, Result};
 use crate::query_handler::SqlQueryHandlerRef;
 
+/// Handler of a single Postgres connection.
 pub struct PostgresServerHandler {
     query_handler: SqlQueryHandlerRef,
+    // Session of the connection, initialized from the startup parameters of the client
+    // on the first query.
+    query_ctx: OnceCell<QueryContextRef>,
 }
 
 impl PostgresServerHandler {
     pub fn new(query_handler: SqlQueryHandlerRef) -> Self {
-        PostgresServerHandler { query_handler }
+        PostgresServerHandler {
+            query_handler,
+            query_ctx: OnceCell::new(),
+        }
     }
 }
 
@@ -47,8 +59,36 @@ where
     C: ClientInfo,
 {
     let query_context = QueryContext::new();
-    if let Some(current_schema) = client.metadata().get(super::METADATA_DATABASE) {
-        query_context.set_current_schema(current_schema);
+    if let Some(login_name) = client.metadata().get(super::METADATA_USER) {
+        // Users authenticated by password have been resolved to the tenant they log in to,
+        // otherwise the tenant is taken from the login name `<username>@<tenant>`.
+        let (user, tenant) = parse_login_name(login_name);
+        query_context.set_current_user(user);
+        if let Some(tenant) = client.metadata().get(super::METADATA_TENANT) {
+            query_context.set_tenant(tenant);
+        } else if let Some(tenant) = tenant {
+            query_context.set_tenant(tenant);
+        }
+    }
+    if let Some(database) = client.metadata().get(super::METADATA_DATABASE) {
+        let (catalog, schema) = parse_catalog_and_schema_from_client_database_name(database);
+        if let Some(catalog) = catalog {
+            if query_context.is_catalog_accessible(catalog) {
+                query_context.set_current_catalog(catalog);
+            } else {
+                warn!(
+                    "Ignore inaccessible catalog {} in database {}",
+                    catalog, database
+                );
+            }
+        }
+        query_context.set_current_schema(schema);
+    }
+    if let Some(time_zone) = client.metadata().get(super::METADATA_TIME_ZONE) {
+        if let Err(e) = query_context.set_variable(session::context::TIME_ZONE_VARIABLE, time_zone)
+        {
+            warn!("Ignore invalid TimeZone parameter {}: {}", time_zone, e);
+        }
     }
 
     Arc::new(query_context)
@@ -60,7 +100,11 @@ impl SimpleQueryHandler for PostgresServerHandler {
     where
         C: ClientInfo + Unpin + Send + Sync,
     {
-        let query_ctx = query_context_from_client_info(client);
+        let query_ctx = self
+            .query_ctx
+            .get_or_init(|| query_context_from_client_info(client))
+            .clone();
+        let time_zone = query_ctx.time_zone();
         let output = self
             .query_handler
             .do_query(query, query_ctx)
@@ -74,13 +118,14 @@ impl SimpleQueryHandler for PostgresServerHandler {
             ))]),
             Output::Stream(record_stream) => {
                 let schema = record_stream.schema();
-                recordbatches_to_query_response(record_stream, schema)
+                recordbatches_to_query_response(record_stream, schema, time_zone)
             }
             Output::RecordBatches(recordbatches) => {
                 let schema = recordbatches.schema();
                 recordbatches_to_query_response(
                     stream::iter(recordbatches.take().into_iter().map(Ok)),
                     schema,
+                    time_zone,
                 )
             }
         }
@@ -90,6 +135,7 @@ impl SimpleQueryHandler for PostgresServerHandler {
 fn recordbatches_to_query_response<S>(
     recordbatches_stream: S,
     schema: SchemaRef,
+    time_zone: Option<TimeZone>,
 ) -> PgWireResult<Vec<Response>>
 where
     S: Stream<Item = RecordBatchResult<RecordBatch>> + Send + Unpin + 'static,
@@ -115,7 +161,7 @@ where
             row.and_then(|row| {
                 let mut encoder = TextDataRowEncoder::new(ncols);
                 for value in row.into_iter() {
-                    encode_value(&value, &mut encoder)?;
+                    encode_value(&value, time_zone.as_ref(), &mut encoder)?;
                 }
                 encoder.finish()
             })
@@ -142,7 +188,11 @@ fn schema_to_pg(origin: SchemaRef) -> Result<Vec<FieldInfo>> {
         .collect::<Result<Vec<FieldInfo>>>()
 }
 
-fn encode_value(value: &Value, builder: &mut TextDataRowEncoder) -> PgWireResult<()> {
+fn encode_value(
+    value: &Value,
+    time_zone: Option<&TimeZone>,
+    builder: &mut TextDataRowEncoder,
+) -> PgWireResult<()> {
     match value {
         Value::Null => builder.append_field(None::<&i8>),
         Value::Boolean(v) => builder.append_field(Some(v)),
@@ -160,7 +210,9 @@ fn encode_value(value: &Value, builder: &mut TextDataRowEncoder) -> PgWireResult
         Value::Binary(v) => builder.append_field(Some(&hex::encode(v.deref()))),
         Value::Date(v) => builder.append_field(Some(&v.to_string())),
         Value::DateTime(v) => builder.append_field(Some(&v.to_string())),
-        Value::Timestamp(v) => builder.append_field(Some(&v.to_iso8601_string())),
+        Value::Timestamp(v) => builder.append_field(Some(&v.to_timezone_aware_string(time_zone))),
+        Value::Decimal128(v) => builder.append_field(Some(&v.to_string())),
+        Value::Json(v) => builder.append_field(Some(&v.to_string())),
         Value::List(_) => Err(PgWireError::ApiError(Box::new(Error::Internal {
             err_msg: format!(
                 "cannot write value {:?} in postgres protocol: unimplemented",
@@ -185,6 +237,8 @@ fn type_translate(origin: &ConcreteDataType) -> Result<Type> {
         &ConcreteDataType::Date(_) => Ok(Type::DATE),
         &ConcreteDataType::DateTime(_) => Ok(Type::TIMESTAMP),
         &ConcreteDataType::Timestamp(_) => Ok(Type::TIMESTAMP),
+        &ConcreteDataType::Decimal128(_) => Ok(Type::NUMERIC),
+        &ConcreteDataType::Json(_) => Ok(Type::JSON),
         &ConcreteDataType::List(_) => error::InternalSnafu {
             err_msg: format!("not implemented for column datatype {:?}", origin),
         }
@@ -211,6 +265,7 @@ impl ExtendedQueryHandler for PostgresServerHandler {
 mod test {
     use std::sync::Arc;
 
+    use datatypes::decimal::Decimal128;
     use datatypes::schema::{ColumnSchema, Schema};
     use datatypes::value::ListValue;
     use pgwire::api::results::FieldInfo;
@@ -241,6 +296,12 @@ mod test {
                 true,
             ),
             ColumnSchema::new("dates", ConcreteDataType::date_datatype(), true),
+            ColumnSchema::new(
+                "decimals",
+                ConcreteDataType::decimal128_datatype(10, 2),
+                true,
+            ),
+            ColumnSchema::new("jsons", ConcreteDataType::json_datatype(), true),
         ];
         let pg_field_info = vec![
             FieldInfo::new("nulls".into(), None, None, Type::UNKNOWN),
@@ -259,6 +320,8 @@ mod test {
             FieldInfo::new("strings".into(), None, None, Type::VARCHAR),
             FieldInfo::new("timestamps".into(), None, None, Type::TIMESTAMP),
             FieldInfo::new("dates".into(), None, None, Type::DATE),
+            FieldInfo::new("decimals".into(), None, None, Type::NUMERIC),
+            FieldInfo::new("jsons".into(), None, None, Type::JSON),
         ];
         let schema = Arc::new(Schema::new(column_schemas));
         let fs = schema_to_pg(schema).unwrap();
@@ -293,6 +356,8 @@ mod test {
             FieldInfo::new("dates".into(), None, None, Type::DATE),
             FieldInfo::new("datetimes".into(), None, None, Type::TIMESTAMP),
             FieldInfo::new("timestamps".into(), None, None, Type::TIMESTAMP),
+            FieldInfo::new("decimals".into(), None, None, Type::NUMERIC),
+            FieldInfo::new("jsons".into(), None, None, Type::JSON),
         ];
 
         let values = vec![
@@ -321,10 +386,12 @@ mod test {
             Value::Date(1001i32.into()),
             Value::DateTime(1000001i64.into()),
             Value::Timestamp(1000001i64.into()),
+            Value::Decimal128(Decimal128::new(-12345, 10, 2)),
+            Value::Json(r#"{"a": [1]}"#.parse().unwrap()),
         ];
         let mut builder = TextDataRowEncoder::new(schema.len());
         for i in values {
-            assert!(encode_value(&i, &mut builder).is_ok());
+            assert!(encode_value(&i, None, &mut builder).is_ok());
         }
 
         let err = encode_value(
@@ -332,6 +399,7 @@ mod test {
                 Some(Box::new(vec![])),
                 ConcreteDataType::int8_datatype(),
             )),
+            None,
             &mut builder,
         )
         .unwrap_err();
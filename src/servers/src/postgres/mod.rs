@@ -18,5 +18,8 @@ mod server;
 
 pub(crate) const METADATA_USER: &str = "user";
 pub(crate) const METADATA_DATABASE: &str = "database";
+pub(crate) const METADATA_TIME_ZONE: &str = "TimeZone";
+/// Catalog of the tenant the authenticated user belongs to.
+pub(crate) const METADATA_TENANT: &str = "greptime_tenant";
 
 pub use server::PostgresServer;
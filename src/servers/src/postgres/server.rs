@@ -17,6 +17,7 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use common_grpc::tls::TlsOption;
 use common_runtime::Runtime;
 use common_telemetry::logging::error;
 use futures::StreamExt;
@@ -30,12 +31,11 @@ use crate::postgres::auth_handler::PgAuthStartupHandler;
 use crate::postgres::handler::PostgresServerHandler;
 use crate::query_handler::SqlQueryHandlerRef;
 use crate::server::{AbortableStream, BaseTcpServer, Server};
-use crate::tls::TlsOption;
 
 pub struct PostgresServer {
     base_server: BaseTcpServer,
     auth_handler: Arc<PgAuthStartupHandler>,
-    query_handler: Arc<PostgresServerHandler>,
+    query_handler: SqlQueryHandlerRef,
     tls: Arc<TlsOption>,
 }
 
@@ -48,7 +48,6 @@ impl PostgresServer {
         io_runtime: Arc<Runtime>,
         user_provider: Option<UserProviderRef>,
     ) -> PostgresServer {
-        let postgres_handler = Arc::new(PostgresServerHandler::new(query_handler));
         let startup_handler = Arc::new(PgAuthStartupHandler::new(
             check_pwd,
             user_provider,
@@ -57,7 +56,7 @@ impl PostgresServer {
         PostgresServer {
             base_server: BaseTcpServer::create_server("Postgres", io_runtime),
             auth_handler: startup_handler,
-            query_handler: postgres_handler,
+            query_handler,
             tls,
         }
     }
@@ -81,6 +80,9 @@ impl PostgresServer {
                 match tcp_stream {
                     Err(error) => error!("Broken pipe: {}", error), // IoError doesn't impl ErrorExt.
                     Ok(io_stream) => {
+                        // Each connection has its own handler to keep its session state.
+                        let query_handler =
+                            Arc::new(PostgresServerHandler::new(query_handler.clone()));
                         io_runtime.spawn(process_socket(
                             io_stream,
                             tls_acceptor.clone(),
@@ -0,0 +1,830 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tracks the running queries, so that they can be listed by `SHOW PROCESSLIST`, cancelled by
+//! `KILL` and interrupted after the timeout.
+//!
+//! Queries of MySQL connections are identified by the connection ids, so `KILL QUERY <id>` works
+//! as in MySQL, other queries get ids above the range of connection ids. `KILL [CONNECTION] <id>`
+//! closes the MySQL connection as well. A process can only be killed by the user running it, or
+//! by the default user that isn't bound to any tenant.
+//!
+//! Interrupting a query drops its record batch stream, which stops the execution of the plan on
+//! this node. Queries sent to datanodes by gRPC are tracked by the datanodes on their own.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use api::v1::{object_expr, select_expr, ObjectExpr, ObjectResult};
+use async_trait::async_trait;
+use common_error::ext::BoxedError;
+use common_query::Output;
+use common_recordbatch::error::{ExternalSnafu, Result as RecordBatchResult};
+use common_recordbatch::{
+    RecordBatch, RecordBatchStream, RecordBatches, SendableRecordBatchStream,
+};
+use datatypes::prelude::ConcreteDataType;
+use datatypes::schema::{ColumnSchema, Schema, SchemaRef};
+use datatypes::vectors::{StringVector, UInt64Vector, VectorRef};
+use futures::future::BoxFuture;
+use futures::{FutureExt, Stream};
+use serde::{Deserialize, Serialize};
+use session::context::{QueryContext, QueryContextRef};
+use snafu::{ensure, OptionExt, ResultExt};
+use sql::dialect::GenericDialect;
+use sql::parser::ParserContext;
+use sql::statements::kill::{Kill, KillKind};
+use sql::statements::statement::Statement;
+use tokio::sync::watch;
+
+use crate::auth::DEFAULT_USERNAME;
+use crate::error::{
+    KillDeniedSnafu, NotSupportedSnafu, ProcessNotFoundSnafu, QueryCancelledSnafu,
+    QueryTimeoutSnafu, Result,
+};
+use crate::query_handler::{
+    GrpcQueryHandler, GrpcQueryHandlerRef, SqlQueryHandler, SqlQueryHandlerRef,
+};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct QueryOptions {
+    /// Queries running longer than the timeout are interrupted, no timeout if absent.
+    #[serde(default, with = "humantime_serde")]
+    pub timeout: Option<Duration>,
+}
+
+/// Ids of queries not from MySQL connections start from here, above all the connection ids.
+const FIRST_PROCESS_ID: u64 = u32::MAX as u64 + 1;
+
+/// Queries are truncated to this many characters by `SHOW PROCESSLIST` without `FULL`, as MySQL.
+const PROCESSLIST_QUERY_LEN: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct ProcessInfo {
+    /// Id of the MySQL connection running the query, or an id allocated by the manager.
+    pub id: u64,
+    pub user: Option<String>,
+    pub tenant: Option<String>,
+    pub schema: Option<String>,
+    pub query: String,
+    pub start: Instant,
+}
+
+struct Process {
+    info: ProcessInfo,
+    cancel: watch::Sender<bool>,
+}
+
+impl ProcessInfo {
+    fn killable_by(&self, query_ctx: &QueryContext) -> bool {
+        killable_by(self.user.clone(), self.tenant.clone(), query_ctx)
+    }
+}
+
+/// Returns whether the processes of the user can be killed by the session. `KILL` is served by
+/// the manager before the statement reaches the checks of the inner handler, so this is its only
+/// check.
+fn killable_by(user: Option<String>, tenant: Option<String>, query_ctx: &QueryContext) -> bool {
+    let killer_tenant = query_ctx.tenant();
+    match query_ctx.current_user() {
+        Some(killer) if killer == DEFAULT_USERNAME && killer_tenant.is_none() => true,
+        Some(killer) => user == Some(killer) && tenant == killer_tenant,
+        None => false,
+    }
+}
+
+/// Processes keyed by unique keys, as a connection id may still be held by the finishing query
+/// while the next query of the connection starts.
+type ProcessMap = Arc<RwLock<HashMap<u64, Process>>>;
+
+/// Removes the process from the list once the query is done.
+struct Ticket {
+    key: u64,
+    id: u64,
+    processes: ProcessMap,
+}
+
+impl Drop for Ticket {
+    fn drop(&mut self) {
+        let _ = self.processes.write().unwrap().remove(&self.key);
+    }
+}
+
+struct Connection {
+    /// Context of the session, whose user is set once the connection is authenticated.
+    query_ctx: QueryContextRef,
+    close: watch::Sender<bool>,
+}
+
+/// Open MySQL connections keyed by the connection ids.
+type ConnectionMap = Arc<RwLock<HashMap<u64, Connection>>>;
+
+/// Handle of a connection registered to the manager, which unregisters the connection once
+/// dropped.
+pub struct ConnectionHandle {
+    id: u64,
+    closed: watch::Receiver<bool>,
+    connections: ConnectionMap,
+}
+
+impl ConnectionHandle {
+    /// Resolves once the connection is killed, the server should close the connection then.
+    pub async fn closed(&mut self) {
+        while !*self.closed.borrow() {
+            if self.closed.changed().await.is_err() {
+                // The sender is only dropped with the handle itself.
+                futures::future::pending::<()>().await;
+            }
+        }
+    }
+}
+
+impl Drop for ConnectionHandle {
+    fn drop(&mut self) {
+        let _ = self.connections.write().unwrap().remove(&self.id);
+    }
+}
+
+/// A [SqlQueryHandler] and [GrpcQueryHandler] that records the queries executed by the inner
+/// handlers.
+pub struct ProcessManager {
+    handler: SqlQueryHandlerRef,
+    grpc_handler: Option<GrpcQueryHandlerRef>,
+    options: QueryOptions,
+    next_key: AtomicU64,
+    processes: ProcessMap,
+    connections: ConnectionMap,
+}
+
+pub type ProcessManagerRef = Arc<ProcessManager>;
+
+impl ProcessManager {
+    pub fn new(handler: SqlQueryHandlerRef, options: QueryOptions) -> Self {
+        Self {
+            handler,
+            grpc_handler: None,
+            options,
+            next_key: AtomicU64::new(FIRST_PROCESS_ID),
+            processes: Arc::new(RwLock::new(HashMap::new())),
+            connections: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Tracks the gRPC queries executed by `grpc_handler` as well.
+    pub fn with_grpc_handler(mut self, grpc_handler: GrpcQueryHandlerRef) -> Self {
+        self.grpc_handler = Some(grpc_handler);
+        self
+    }
+
+    /// Registers the MySQL connection of the session, so that it can be closed by `KILL`.
+    ///
+    /// # Panics
+    /// Panics if the session has no connection id.
+    pub fn register_connection(&self, query_ctx: QueryContextRef) -> ConnectionHandle {
+        let id = u64::from(query_ctx.connection_id().unwrap());
+        let (close, closed) = watch::channel(false);
+        let _ = self
+            .connections
+            .write()
+            .unwrap()
+            .insert(id, Connection { query_ctx, close });
+        ConnectionHandle {
+            id,
+            closed,
+            connections: self.connections.clone(),
+        }
+    }
+
+    /// Returns the running queries ordered by their ids.
+    pub fn processes(&self) -> Vec<ProcessInfo> {
+        let mut processes = self
+            .processes
+            .read()
+            .unwrap()
+            .values()
+            .map(|process| process.info.clone())
+            .collect::<Vec<_>>();
+        processes.sort_by_key(|process| process.id);
+        processes
+    }
+
+    /// Cancels the query with the `id` on behalf of the session, returns false if there is no
+    /// such query.
+    pub fn kill_query(&self, id: u64, query_ctx: &QueryContext) -> Result<bool> {
+        let processes = self.processes.read().unwrap();
+        let mut found = false;
+        for process in processes.values().filter(|process| process.info.id == id) {
+            ensure!(
+                process.info.killable_by(query_ctx),
+                KillDeniedSnafu {
+                    id,
+                    user: query_ctx.current_user().unwrap_or_default(),
+                }
+            );
+            let _ = process.cancel.send(true);
+            found = true;
+        }
+        Ok(found)
+    }
+
+    /// Closes the connection with the `id` and cancels its query on behalf of the session,
+    /// returns false if there is neither such connection nor such query.
+    pub fn kill_connection(&self, id: u64, query_ctx: &QueryContext) -> Result<bool> {
+        let connections = self.connections.read().unwrap();
+        let connection = connections.get(&id);
+        if let Some(connection) = connection {
+            let owner = &connection.query_ctx;
+            ensure!(
+                killable_by(owner.current_user(), owner.tenant(), query_ctx),
+                KillDeniedSnafu {
+                    id,
+                    user: query_ctx.current_user().unwrap_or_default(),
+                }
+            );
+        }
+
+        let killed = self.kill_query(id, query_ctx)?;
+        if let Some(connection) = connection {
+            let _ = connection.close.send(true);
+        }
+        Ok(killed || connection.is_some())
+    }
+
+    fn kill(&self, kill: &Kill, query_ctx: &QueryContext) -> Result<()> {
+        let killed = match kill.kind {
+            KillKind::Query => self.kill_query(kill.id, query_ctx)?,
+            KillKind::Connection => self.kill_connection(kill.id, query_ctx)?,
+        };
+        ensure!(killed, ProcessNotFoundSnafu { id: kill.id });
+        common_telemetry::info!("Process {} is killed, kind: {:?}", kill.id, kill.kind);
+        Ok(())
+    }
+
+    fn register(&self, query: &str, query_ctx: &QueryContext) -> (Ticket, watch::Receiver<bool>) {
+        let key = self.next_key.fetch_add(1, Ordering::Relaxed);
+        let id = query_ctx.connection_id().map(u64::from).unwrap_or(key);
+        let (cancel, cancelled) = watch::channel(false);
+        let process = Process {
+            info: ProcessInfo {
+                id,
+                user: query_ctx.current_user(),
+                tenant: query_ctx.tenant(),
+                schema: query_ctx.current_schema(),
+                query: query.to_string(),
+                start: Instant::now(),
+            },
+            cancel,
+        };
+        let _ = self.processes.write().unwrap().insert(key, process);
+
+        let ticket = Ticket {
+            key,
+            id,
+            processes: self.processes.clone(),
+        };
+        (ticket, cancelled)
+    }
+
+    /// Lists the queries the session is allowed to kill.
+    fn show_processlist(&self, full: bool, query_ctx: &QueryContext) -> Output {
+        let processes = self
+            .processes()
+            .into_iter()
+            .filter(|process| process.killable_by(query_ctx))
+            .collect::<Vec<_>>();
+        let schema = Arc::new(Schema::new(vec![
+            ColumnSchema::new("Id", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("User", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("Db", ConcreteDataType::string_datatype(), true),
+            ColumnSchema::new("Elapsed", ConcreteDataType::uint64_datatype(), false),
+            ColumnSchema::new("Query", ConcreteDataType::string_datatype(), false),
+        ]));
+        let columns: Vec<VectorRef> = vec![
+            Arc::new(UInt64Vector::from_vec(
+                processes.iter().map(|p| p.id).collect(),
+            )),
+            Arc::new(StringVector::from(
+                processes.iter().map(|p| p.user.clone()).collect::<Vec<_>>(),
+            )),
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|p| p.schema.clone())
+                    .collect::<Vec<_>>(),
+            )),
+            // Elapsed time in milliseconds.
+            Arc::new(UInt64Vector::from_vec(
+                processes
+                    .iter()
+                    .map(|p| p.start.elapsed().as_millis() as u64)
+                    .collect(),
+            )),
+            Arc::new(StringVector::from(
+                processes
+                    .iter()
+                    .map(|p| {
+                        if full {
+                            p.query.clone()
+                        } else {
+                            p.query.chars().take(PROCESSLIST_QUERY_LEN).collect()
+                        }
+                    })
+                    .collect::<Vec<String>>(),
+            )),
+        ];
+        // unwrap is safe because the columns always match the schema
+        Output::RecordBatches(RecordBatches::try_from_columns(schema, columns).unwrap())
+    }
+}
+
+/// Parses the query if it's `KILL` or `SHOW PROCESSLIST`, which are served by the manager
+/// itself. Other queries, including the malformed ones, are left to the inner handler.
+fn parse_process_statement(query: &str) -> Option<Statement> {
+    // Avoids parsing the other queries twice.
+    let keyword = query.trim_start().get(..4)?;
+    if !keyword.eq_ignore_ascii_case("KILL") && !keyword.eq_ignore_ascii_case("SHOW") {
+        return None;
+    }
+
+    let mut stmts = ParserContext::create_with_dialect(query, &GenericDialect {}).ok()?;
+    match stmts.as_slice() {
+        [Statement::Kill(_)] | [Statement::ShowProcesslist(_)] => stmts.pop(),
+        _ => None,
+    }
+}
+
+/// Resolves to the error once the query is cancelled or timed out.
+fn interrupted(
+    id: u64,
+    mut receiver: watch::Receiver<bool>,
+    timeout: Option<Duration>,
+) -> BoxFuture<'static, crate::error::Error> {
+    let deadline = timeout.map(|timeout| (tokio::time::Instant::now() + timeout, timeout));
+    async move {
+        let cancelled = async move {
+            loop {
+                let is_cancelled = *receiver.borrow();
+                if is_cancelled {
+                    break;
+                }
+                if receiver.changed().await.is_err() {
+                    // The process is removed, the query can't be cancelled any more.
+                    futures::future::pending::<()>().await;
+                }
+            }
+        };
+
+        match deadline {
+            Some((deadline, timeout)) => tokio::select! {
+                _ = cancelled => QueryCancelledSnafu { id }.build(),
+                _ = tokio::time::sleep_until(deadline) => QueryTimeoutSnafu { id, timeout }.build(),
+            },
+            None => {
+                cancelled.await;
+                QueryCancelledSnafu { id }.build()
+            }
+        }
+    }
+    .boxed()
+}
+
+#[async_trait]
+impl SqlQueryHandler for ProcessManager {
+    async fn do_query(&self, query: &str, query_ctx: QueryContextRef) -> Result<Output> {
+        match parse_process_statement(query) {
+            Some(Statement::ShowProcesslist(show)) => {
+                return Ok(self.show_processlist(show.full, &query_ctx));
+            }
+            Some(Statement::Kill(kill)) => {
+                self.kill(&kill, &query_ctx)?;
+                return Ok(Output::AffectedRows(0));
+            }
+            _ => {}
+        }
+
+        let (ticket, cancelled) = self.register(query, &query_ctx);
+        let mut interrupted = interrupted(ticket.id, cancelled, self.options.timeout);
+        let output = tokio::select! {
+            output = self.handler.do_query(query, query_ctx) => output?,
+            e = &mut interrupted => return Err(e),
+        };
+
+        Ok(match output {
+            Output::Stream(stream) => Output::Stream(Box::pin(ProcessStream {
+                schema: stream.schema(),
+                stream: Some(stream),
+                interrupted,
+                _ticket: ticket,
+            })),
+            output => output,
+        })
+    }
+}
+
+#[async_trait]
+impl GrpcQueryHandler for ProcessManager {
+    async fn do_query(
+        &self,
+        query: ObjectExpr,
+        query_ctx: QueryContextRef,
+    ) -> Result<ObjectResult> {
+        let handler = self
+            .grpc_handler
+            .as_ref()
+            .context(NotSupportedSnafu { feat: "gRPC query" })?;
+        let (ticket, cancelled) = self.register(&describe_object_expr(&query), &query_ctx);
+        let interrupted = interrupted(ticket.id, cancelled, self.options.timeout);
+        tokio::select! {
+            result = handler.do_query(query, query_ctx) => result,
+            e = interrupted => Err(e),
+        }
+    }
+}
+
+/// Describes the gRPC query in the process list.
+fn describe_object_expr(query: &ObjectExpr) -> String {
+    match &query.expr {
+        Some(object_expr::Expr::Select(select)) => match &select.expr {
+            Some(select_expr::Expr::Sql(sql)) => sql.clone(),
+            Some(select_expr::Expr::LogicalPlan(_)) => "SELECT <logical plan>".to_string(),
+            None => "SELECT".to_string(),
+        },
+        Some(object_expr::Expr::Insert(insert)) => {
+            format!("INSERT INTO {}.{}", insert.schema_name, insert.table_name)
+        }
+        Some(object_expr::Expr::Script(script)) => format!("SCRIPT {}", script.name),
+        Some(object_expr::Expr::Update(_)) => "UPDATE".to_string(),
+        Some(object_expr::Expr::Delete(_)) => "DELETE".to_string(),
+        None => String::new(),
+    }
+}
+
+/// Stream of the running query, which ends with an error once the query is interrupted.
+struct ProcessStream {
+    schema: SchemaRef,
+    stream: Option<SendableRecordBatchStream>,
+    interrupted: BoxFuture<'static, crate::error::Error>,
+    _ticket: Ticket,
+}
+
+impl Stream for ProcessStream {
+    type Item = RecordBatchResult<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let stream = match this.stream.as_mut() {
+            Some(stream) => stream,
+            None => return Poll::Ready(None),
+        };
+
+        if let Poll::Ready(e) = this.interrupted.poll_unpin(cx) {
+            // Drops the stream to stop the execution.
+            this.stream = None;
+            return Poll::Ready(Some(Err(BoxedError::new(e)).context(ExternalSnafu)));
+        }
+
+        stream.as_mut().poll_next(cx)
+    }
+}
+
+impl RecordBatchStream for ProcessStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_recordbatch::util;
+    use datatypes::vectors::UInt32Vector;
+    use futures::StreamExt;
+    use session::context::QueryContext;
+
+    use super::*;
+
+    /// Handler returns a stream never ends.
+    struct PendingHandler;
+
+    struct PendingStream(SchemaRef);
+
+    impl Stream for PendingStream {
+        type Item = RecordBatchResult<RecordBatch>;
+
+        fn poll_next(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            Poll::Pending
+        }
+    }
+
+    impl RecordBatchStream for PendingStream {
+        fn schema(&self) -> SchemaRef {
+            self.0.clone()
+        }
+    }
+
+    #[async_trait]
+    impl SqlQueryHandler for PendingHandler {
+        async fn do_query(&self, query: &str, _query_ctx: QueryContextRef) -> Result<Output> {
+            if query == "select 1" {
+                let schema = Arc::new(Schema::new(vec![ColumnSchema::new(
+                    "n",
+                    ConcreteDataType::uint32_datatype(),
+                    false,
+                )]));
+                let batches = RecordBatches::try_from_columns(
+                    schema,
+                    vec![Arc::new(UInt32Vector::from_slice(&[1])) as VectorRef],
+                )
+                .unwrap();
+                return Ok(Output::Stream(batches.as_stream()));
+            }
+            let schema = Arc::new(Schema::new(vec![]));
+            Ok(Output::Stream(Box::pin(PendingStream(schema))))
+        }
+    }
+
+    fn query_ctx() -> QueryContextRef {
+        user_ctx("greptime", None)
+    }
+
+    fn user_ctx(user: &str, tenant: Option<&str>) -> QueryContextRef {
+        let query_ctx = QueryContext::arc();
+        query_ctx.set_current_user(user);
+        if let Some(tenant) = tenant {
+            query_ctx.set_tenant(tenant);
+        }
+        query_ctx
+    }
+
+    async fn pending_stream(
+        manager: &ProcessManager,
+        query_ctx: QueryContextRef,
+    ) -> SendableRecordBatchStream {
+        match SqlQueryHandler::do_query(manager, "select pending", query_ctx)
+            .await
+            .unwrap()
+        {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_processlist_and_kill() {
+        let manager = ProcessManager::new(Arc::new(PendingHandler), QueryOptions::default());
+
+        let output = SqlQueryHandler::do_query(&manager, "select 1", query_ctx())
+            .await
+            .unwrap();
+        let stream = match output {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        assert_eq!(1, manager.processes().len());
+        let batches = util::collect(stream).await.unwrap();
+        assert_eq!(1, batches[0].num_rows());
+        assert!(manager.processes().is_empty());
+
+        let output = SqlQueryHandler::do_query(&manager, "select pending", query_ctx())
+            .await
+            .unwrap();
+        let mut stream = match output {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        let processes = manager.processes();
+        assert_eq!(1, processes.len());
+        assert_eq!(Some("greptime".to_string()), processes[0].user);
+        assert_eq!("select pending", processes[0].query);
+
+        match SqlQueryHandler::do_query(&manager, "SHOW PROCESSLIST", query_ctx())
+            .await
+            .unwrap()
+        {
+            Output::RecordBatches(batches) => assert_eq!(1, batches.take()[0].num_rows()),
+            _ => unreachable!(),
+        }
+
+        let kill = format!("KILL QUERY {}", processes[0].id);
+        let output = SqlQueryHandler::do_query(&manager, &kill, query_ctx())
+            .await
+            .unwrap();
+        assert!(matches!(output, Output::AffectedRows(0)));
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("cancelled"), "{}", err);
+        assert!(stream.next().await.is_none());
+        drop(stream);
+        assert!(manager.processes().is_empty());
+
+        let err = SqlQueryHandler::do_query(&manager, "KILL 1024", query_ctx())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::error::Error::ProcessNotFound { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_kill_by_connection_id() {
+        let manager = ProcessManager::new(Arc::new(PendingHandler), QueryOptions::default());
+
+        let query_ctx = user_ctx("alice", Some("team_a"));
+        query_ctx.set_connection_id(7);
+        let mut stream = pending_stream(&manager, query_ctx).await;
+        let other = pending_stream(&manager, user_ctx("bob", None)).await;
+        let processes = manager.processes();
+        assert_eq!(7, processes[0].id);
+        assert!(processes[1].id >= FIRST_PROCESS_ID);
+
+        // Users only see and kill their own queries.
+        match SqlQueryHandler::do_query(&manager, "SHOW PROCESSLIST", user_ctx("bob", None))
+            .await
+            .unwrap()
+        {
+            Output::RecordBatches(batches) => assert_eq!(1, batches.take()[0].num_rows()),
+            _ => unreachable!(),
+        }
+        // Sessions without users can't kill any query.
+        for ctx in [
+            user_ctx("bob", None),
+            user_ctx("alice", Some("team_b")),
+            QueryContext::arc(),
+        ] {
+            let err = SqlQueryHandler::do_query(&manager, "KILL QUERY 7", ctx)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(err, crate::error::Error::KillDenied { .. }),
+                "{}",
+                err
+            );
+        }
+
+        let output =
+            SqlQueryHandler::do_query(&manager, "KILL QUERY 7", user_ctx("alice", Some("team_a")))
+                .await
+                .unwrap();
+        assert!(matches!(output, Output::AffectedRows(0)));
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("cancelled"), "{}", err);
+        drop(stream);
+
+        // The default user can kill all queries.
+        let kill = format!("KILL {}", manager.processes()[0].id);
+        let _ = SqlQueryHandler::do_query(&manager, &kill, query_ctx())
+            .await
+            .unwrap();
+        drop(other);
+        assert!(manager.processes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_kill_connection() {
+        let manager = ProcessManager::new(Arc::new(PendingHandler), QueryOptions::default());
+
+        let query_ctx = user_ctx("alice", None);
+        query_ctx.set_connection_id(9);
+        let mut connection = manager.register_connection(query_ctx.clone());
+        let mut stream = pending_stream(&manager, query_ctx).await;
+
+        let err = SqlQueryHandler::do_query(&manager, "KILL 9", user_ctx("bob", None))
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, crate::error::Error::KillDenied { .. }),
+            "{}",
+            err
+        );
+        assert!(connection.closed().now_or_never().is_none());
+
+        // `KILL QUERY` leaves the connection open.
+        let _ = SqlQueryHandler::do_query(&manager, "KILL QUERY 9", user_ctx("alice", None))
+            .await
+            .unwrap();
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("cancelled"), "{}", err);
+        assert!(connection.closed().now_or_never().is_none());
+
+        // The idle connection can still be killed.
+        let output =
+            SqlQueryHandler::do_query(&manager, "KILL CONNECTION 9", user_ctx("alice", None))
+                .await
+                .unwrap();
+        assert!(matches!(output, Output::AffectedRows(0)));
+        connection.closed().await;
+
+        drop(connection);
+        let err = SqlQueryHandler::do_query(&manager, "KILL CONNECTION 9", query_ctx())
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::error::Error::ProcessNotFound { .. }));
+    }
+
+    #[test]
+    fn test_parse_process_statement() {
+        assert!(matches!(
+            parse_process_statement("  show full processlist;"),
+            Some(Statement::ShowProcesslist(_))
+        ));
+        assert!(matches!(
+            parse_process_statement("KILL QUERY 3"),
+            Some(Statement::Kill(Kill {
+                kind: KillKind::Query,
+                id: 3,
+            }))
+        ));
+        assert!(parse_process_statement("SHOW TABLES").is_none());
+        assert!(parse_process_statement("KILL abc").is_none());
+        assert!(parse_process_statement("select 'KILL 1'").is_none());
+        assert!(parse_process_statement("KILL 1; KILL 2").is_none());
+    }
+
+    struct PendingGrpcHandler;
+
+    #[async_trait]
+    impl GrpcQueryHandler for PendingGrpcHandler {
+        async fn do_query(
+            &self,
+            _query: ObjectExpr,
+            _query_ctx: QueryContextRef,
+        ) -> Result<ObjectResult> {
+            futures::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_grpc_query() {
+        let manager = Arc::new(
+            ProcessManager::new(
+                Arc::new(PendingHandler),
+                QueryOptions {
+                    timeout: Some(Duration::from_secs(60)),
+                },
+            )
+            .with_grpc_handler(Arc::new(PendingGrpcHandler)),
+        );
+
+        let query = ObjectExpr {
+            header: None,
+            expr: Some(object_expr::Expr::Select(api::v1::SelectExpr {
+                expr: Some(select_expr::Expr::Sql("select grpc".to_string())),
+            })),
+        };
+        let handle = {
+            let manager = manager.clone();
+            let query_ctx = user_ctx("alice", None);
+            tokio::spawn(
+                async move { GrpcQueryHandler::do_query(&*manager, query, query_ctx).await },
+            )
+        };
+        while manager.processes().is_empty() {
+            tokio::task::yield_now().await;
+        }
+        let process = manager.processes().remove(0);
+        assert_eq!("select grpc", process.query);
+        assert_eq!(Some("alice".to_string()), process.user);
+
+        // gRPC queries are owned by the users of their contexts.
+        assert!(manager
+            .kill_query(process.id, &user_ctx("bob", None))
+            .is_err());
+        assert!(manager
+            .kill_query(process.id, &user_ctx("alice", None))
+            .unwrap());
+        let err = handle.await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("cancelled"), "{}", err);
+        assert!(manager.processes().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_timeout() {
+        let manager = ProcessManager::new(
+            Arc::new(PendingHandler),
+            QueryOptions {
+                timeout: Some(Duration::from_millis(10)),
+            },
+        );
+
+        let output = SqlQueryHandler::do_query(&manager, "select pending", query_ctx())
+            .await
+            .unwrap();
+        let mut stream = match output {
+            Output::Stream(stream) => stream,
+            _ => unreachable!(),
+        };
+        let err = stream.next().await.unwrap().unwrap_err();
+        assert!(err.to_string().contains("timeout"), "{}", err);
+    }
+}
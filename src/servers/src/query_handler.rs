@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use api::prometheus::remote::{ReadRequest, WriteRequest};
 use api::v1::{AdminExpr, AdminResult, ObjectExpr, ObjectResult};
@@ -21,8 +23,11 @@ use common_query::Output;
 use session::context::QueryContextRef;
 
 use crate::error::Result;
+use crate::http::script::ScriptInfo;
+use crate::influxdb::query::{InfluxqlRequest, InfluxqlResponse};
 use crate::influxdb::InfluxdbRequest;
 use crate::opentsdb::codec::DataPoint;
+use crate::opentsdb::query::{QueryRequest, QueryResult, SuggestRequest};
 use crate::prometheus::Metrics;
 
 /// All query handler traits for various request protocols, like SQL or GRPC.
@@ -51,12 +56,37 @@ pub trait SqlQueryHandler {
 #[async_trait]
 pub trait ScriptHandler {
     async fn insert_script(&self, name: &str, script: &str) -> Result<()>;
-    async fn execute_script(&self, name: &str) -> Result<Output>;
+    /// Executes the script with runtime parameters, which are interpolated into the sql of the
+    /// coprocessor.
+    async fn execute_script(
+        &self,
+        name: &str,
+        params: HashMap<String, String>,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output>;
+    /// Lists the latest versions of all scripts, without their sources.
+    async fn list_scripts(&self) -> Result<Vec<ScriptInfo>>;
+    /// Gets the script of the given version, or the latest one if `version` is `None`.
+    async fn get_script(&self, name: &str, version: Option<i64>) -> Result<ScriptInfo>;
+    /// Lists all versions of the script, without their sources.
+    async fn list_script_versions(&self, name: &str) -> Result<Vec<ScriptInfo>>;
+    async fn delete_script(&self, name: &str) -> Result<()>;
+    /// Schedules the script to run every `interval` and write its output into `target_table`,
+    /// the window of each run is passed to the script as runtime parameters.
+    async fn schedule_script(
+        &self,
+        name: &str,
+        interval: Duration,
+        target_table: &str,
+        params: HashMap<String, String>,
+    ) -> Result<()>;
+    async fn unschedule_script(&self, name: &str) -> Result<()>;
 }
 
 #[async_trait]
 pub trait GrpcQueryHandler {
-    async fn do_query(&self, query: ObjectExpr) -> Result<ObjectResult>;
+    async fn do_query(&self, query: ObjectExpr, query_ctx: QueryContextRef)
+        -> Result<ObjectResult>;
 }
 
 #[async_trait]
@@ -68,14 +98,36 @@ pub trait GrpcAdminHandler {
 pub trait InfluxdbLineProtocolHandler {
     /// A successful request will not return a response.
     /// Only on error will the socket return a line of data.
-    async fn exec(&self, request: &InfluxdbRequest) -> Result<()>;
+    async fn exec(&self, request: &InfluxdbRequest, query_ctx: QueryContextRef) -> Result<()>;
+
+    /// Executes the InfluxQL statements of the request, errors of each statement are
+    /// returned in its result.
+    async fn query(
+        &self,
+        request: &InfluxqlRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<InfluxqlResponse>;
 }
 
 #[async_trait]
 pub trait OpentsdbProtocolHandler {
+    /// Inserts a batch of data points, data points of the same metric are inserted into their
+    /// table at once.
     /// A successful request will not return a response.
     /// Only on error will the socket return a line of data.
-    async fn exec(&self, data_point: &DataPoint) -> Result<()>;
+    async fn exec(&self, data_points: &[DataPoint], query_ctx: QueryContextRef) -> Result<()>;
+    /// Handling OpenTSDB `/api/query` requests
+    async fn query(
+        &self,
+        request: &QueryRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<Vec<QueryResult>>;
+    /// Handling OpenTSDB `/api/suggest` requests
+    async fn suggest(
+        &self,
+        request: &SuggestRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<Vec<String>>;
 }
 
 pub struct PrometheusResponse {
@@ -87,9 +139,19 @@ pub struct PrometheusResponse {
 #[async_trait]
 pub trait PrometheusProtocolHandler {
     /// Handling prometheus remote write requests
-    async fn write(&self, database: &str, request: WriteRequest) -> Result<()>;
+    async fn write(
+        &self,
+        database: &str,
+        request: WriteRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<()>;
     /// Handling prometheus remote read requests
-    async fn read(&self, database: &str, request: ReadRequest) -> Result<PrometheusResponse>;
+    async fn read(
+        &self,
+        database: &str,
+        request: ReadRequest,
+        query_ctx: QueryContextRef,
+    ) -> Result<PrometheusResponse>;
     /// Handling push gateway requests
     async fn ingest_metrics(&self, metrics: Metrics) -> Result<()>;
 }
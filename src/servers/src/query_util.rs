@@ -0,0 +1,62 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers for protocols whose queries are translated to SQL and answered from select results.
+
+use api::v1::Column;
+use common_base::BitVec;
+
+/// Quotes a SQL identifier, so that names with dots or uppercase letters are kept as they are.
+pub fn quote_ident(ident: &str) -> String {
+    format!("\"{}\"", ident.replace('"', "\"\""))
+}
+
+/// Quotes a SQL string literal.
+pub fn quote_literal(literal: &str) -> String {
+    format!("'{}'", literal.replace('\'', "''"))
+}
+
+/// Expands the values of `column` to all rows, with `None` for nulls.
+pub(crate) fn expand_column<T: Clone>(
+    column: &Column,
+    values: &[T],
+    row_count: usize,
+) -> Vec<Option<T>> {
+    if column.null_mask.is_empty() {
+        return values.iter().cloned().map(Some).collect();
+    }
+    let null_mask = BitVec::from_slice(&column.null_mask);
+    let mut values = values.iter();
+    (0..row_count)
+        .map(|row| {
+            if null_mask.get(row).map(|b| *b).unwrap_or(false) {
+                None
+            } else {
+                values.next().cloned()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(r#""sys.cpu""#, quote_ident("sys.cpu"));
+        assert_eq!(r#""a""b""#, quote_ident(r#"a"b"#));
+        assert_eq!("'it''s'", quote_literal("it's"));
+    }
+}
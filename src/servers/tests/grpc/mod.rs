@@ -0,0 +1,149 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::net::{SocketAddr, TcpListener};
+use std::sync::Arc;
+use std::time::Duration;
+
+use api::result::ObjectResultBuilder;
+use api::v1::greptime_client::GreptimeClient;
+use api::v1::{
+    object_expr, select_expr, AdminExpr, AdminResult, BatchRequest, DatabaseRequest, ObjectExpr,
+    ObjectResult, SelectExpr,
+};
+use async_trait::async_trait;
+use common_grpc::channel_manager::{ChannelConfig, ChannelManager, ClientTlsOption};
+use common_grpc::tls::{TlsMode, TlsOption};
+use common_runtime::Builder as RuntimeBuilder;
+use servers::error::{NotSupportedSnafu, Result};
+use servers::grpc::GrpcServer;
+use servers::query_handler::{GrpcAdminHandler, GrpcQueryHandler};
+use servers::server::Server;
+use session::context::QueryContextRef;
+
+struct DummyHandler;
+
+#[async_trait]
+impl GrpcQueryHandler for DummyHandler {
+    async fn do_query(
+        &self,
+        _query: ObjectExpr,
+        _query_ctx: QueryContextRef,
+    ) -> Result<ObjectResult> {
+        Ok(ObjectResultBuilder::default().mutate_result(1, 0).build())
+    }
+}
+
+#[async_trait]
+impl GrpcAdminHandler for DummyHandler {
+    async fn exec_admin_request(&self, _expr: AdminExpr) -> Result<AdminResult> {
+        NotSupportedSnafu {
+            feat: "admin request",
+        }
+        .fail()
+    }
+}
+
+/// Starts a gRPC server in background, returns the address it listens on.
+async fn start_grpc_server(tls: TlsOption) -> SocketAddr {
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let runtime = Arc::new(
+        RuntimeBuilder::default()
+            .worker_threads(2)
+            .thread_name("grpc-tls-test")
+            .build()
+            .unwrap(),
+    );
+    let handler = Arc::new(DummyHandler);
+    let server = GrpcServer::new(handler.clone(), handler, runtime, Arc::new(tls), None);
+    let _ = tokio::spawn(async move { server.start(addr).await });
+    // Waits for the server to listen.
+    tokio::time::sleep(Duration::from_millis(300)).await;
+    addr
+}
+
+/// Sends a select request to the server, returns whether the request succeeds.
+async fn request(addr: SocketAddr, client_tls: Option<ClientTlsOption>) -> bool {
+    let config = ChannelConfig::new()
+        .timeout(Duration::from_secs(3))
+        .client_tls_config(client_tls);
+    let channel = ChannelManager::with_config(config)
+        .get(addr.to_string())
+        .unwrap();
+    let request = BatchRequest {
+        databases: vec![DatabaseRequest {
+            name: "greptime".to_string(),
+            exprs: vec![ObjectExpr {
+                header: None,
+                expr: Some(object_expr::Expr::Select(SelectExpr {
+                    expr: Some(select_expr::Expr::Sql("select 1".to_string())),
+                })),
+            }],
+        }],
+        ..Default::default()
+    };
+    match GreptimeClient::new(channel).batch(request).await {
+        Ok(response) => {
+            let results = &response.into_inner().databases[0].results;
+            assert_eq!(1, results.len());
+            true
+        }
+        Err(_) => false,
+    }
+}
+
+fn server_tls(ca_cert_path: &str) -> TlsOption {
+    TlsOption {
+        mode: TlsMode::Require,
+        cert_path: "tests/ssl/localhost.crt".to_string(),
+        key_path: "tests/ssl/localhost.key".to_string(),
+        ca_cert_path: ca_cert_path.to_string(),
+    }
+}
+
+fn client_tls(with_cert: bool) -> ClientTlsOption {
+    let (client_cert_path, client_key_path) = if with_cert {
+        ("tests/ssl/client.crt", "tests/ssl/client.key")
+    } else {
+        ("", "")
+    };
+    ClientTlsOption {
+        server_ca_cert_path: "tests/ssl/ca.crt".to_string(),
+        client_cert_path: client_cert_path.to_string(),
+        client_key_path: client_key_path.to_string(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_grpc_over_tls() {
+    common_telemetry::init_default_ut_logging();
+    let addr = start_grpc_server(server_tls("")).await;
+
+    assert!(request(addr, Some(client_tls(false))).await);
+    // Plaintext requests can't be served by a TLS server.
+    assert!(!request(addr, None).await);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+async fn test_grpc_over_mutual_tls() {
+    common_telemetry::init_default_ut_logging();
+    let addr = start_grpc_server(server_tls("tests/ssl/ca.crt")).await;
+
+    assert!(request(addr, Some(client_tls(true))).await);
+    // The server requires the certificate of the client.
+    assert!(!request(addr, Some(client_tls(false))).await);
+}
@@ -13,9 +13,12 @@
 // limitations under the License.
 
 use std::collections::HashMap;
+use std::time::Duration;
 
 use axum::body::Body;
 use axum::extract::{Json, Query, RawBody, State};
+use axum::http::{header, HeaderMap, HeaderValue};
+use axum::response::IntoResponse;
 use common_telemetry::metric;
 use metrics::counter;
 use servers::auth::UserInfo;
@@ -27,15 +30,18 @@ use crate::{create_testing_script_handler, create_testing_sql_query_handler};
 #[tokio::test]
 async fn test_sql_not_provided() {
     let sql_handler = create_testing_sql_query_handler(MemTable::default_numbers_table());
-    let Json(json) = http_handler::sql(
+    let json = http_handler::sql(
         State(ApiState {
             sql_handler,
             script_handler: None,
         }),
         Query(http_handler::SqlQuery::default()),
+        HeaderMap::new(),
         axum::Extension(UserInfo::default()),
     )
-    .await;
+    .await
+    .into_json()
+    .unwrap();
     assert!(!json.success());
     assert_eq!(
         Some(&"sql parameter is required.".to_string()),
@@ -51,15 +57,18 @@ async fn test_sql_output_rows() {
     let query = create_query();
     let sql_handler = create_testing_sql_query_handler(MemTable::default_numbers_table());
 
-    let Json(json) = http_handler::sql(
+    let json = http_handler::sql(
         State(ApiState {
             sql_handler,
             script_handler: None,
         }),
         query,
+        HeaderMap::new(),
         axum::Extension(UserInfo::default()),
     )
-    .await;
+    .await
+    .into_json()
+    .unwrap();
     assert!(json.success(), "{:?}", json);
     assert!(json.error().is_none());
     match &json.output().expect("assertion failed")[0] {
@@ -70,6 +79,123 @@ async fn test_sql_output_rows() {
     }
 }
 
+#[tokio::test]
+async fn test_sql_access_denied() {
+    let sql_handler = create_testing_sql_query_handler(MemTable::default_numbers_table());
+    let query = Query(http_handler::SqlQuery {
+        sql: Some("select 1".to_string()),
+        database: Some("greptime-public".to_string()),
+        ..Default::default()
+    });
+    let json = http_handler::sql(
+        State(ApiState {
+            sql_handler,
+            script_handler: None,
+        }),
+        query,
+        HeaderMap::new(),
+        axum::Extension(UserInfo::new("tenant_user").with_catalog("team_a")),
+    )
+    .await
+    .into_json()
+    .unwrap();
+    assert!(!json.success());
+    assert_eq!(
+        Some(&"Access denied to catalog: greptime".to_string()),
+        json.error()
+    );
+}
+
+#[tokio::test]
+async fn test_sql_output_formats() {
+    let sql_handler = create_testing_sql_query_handler(MemTable::default_numbers_table());
+    let state = ApiState {
+        sql_handler,
+        script_handler: None,
+    };
+
+    let query = Query(http_handler::SqlQuery {
+        sql: Some("select sum(uint32s) from numbers limit 20".to_string()),
+        database: None,
+        format: Some("csv".to_string()),
+        ..Default::default()
+    });
+    let resp = http_handler::sql(
+        State(state.clone()),
+        query,
+        HeaderMap::new(),
+        axum::Extension(UserInfo::default()),
+    )
+    .await
+    .into_response();
+    assert_eq!(
+        "text/csv",
+        resp.headers().get(header::CONTENT_TYPE).unwrap()
+    );
+    let body = hyper::body::to_bytes(resp.into_body()).await.unwrap();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    assert_eq!(2, body.lines().count(), "{}", body);
+
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        header::ACCEPT,
+        HeaderValue::from_static("application/vnd.apache.arrow.stream"),
+    );
+    let resp = http_handler::sql(
+        State(state.clone()),
+        create_query(),
+        headers,
+        axum::Extension(UserInfo::default()),
+    )
+    .await
+    .into_response();
+    assert_eq!(
+        "application/vnd.apache.arrow.stream",
+        resp.headers().get(header::CONTENT_TYPE).unwrap()
+    );
+
+    let query = Query(http_handler::SqlQuery {
+        sql: Some("select 1".to_string()),
+        database: None,
+        format: Some("xml".to_string()),
+        ..Default::default()
+    });
+    let json = http_handler::sql(
+        State(state.clone()),
+        query,
+        HeaderMap::new(),
+        axum::Extension(UserInfo::default()),
+    )
+    .await
+    .into_json()
+    .unwrap();
+    assert!(!json.success());
+    assert_eq!(
+        Some(&"Unsupported output format: xml".to_string()),
+        json.error()
+    );
+
+    let query = Query(http_handler::SqlQuery {
+        sql: Some("select 1".to_string()),
+        time_zone: Some("Mars/Olympus_Mons".to_string()),
+        ..Default::default()
+    });
+    let json = http_handler::sql(
+        State(state),
+        query,
+        HeaderMap::new(),
+        axum::Extension(UserInfo::default()),
+    )
+    .await
+    .into_json()
+    .unwrap();
+    assert!(!json.success());
+    assert_eq!(
+        Some(&"Failed to parse a string into time zone, raw string: Mars/Olympus_Mons".to_string()),
+        json.error()
+    );
+}
+
 #[tokio::test]
 async fn test_metrics() {
     metric::init_default_metrics_recorder();
@@ -122,20 +248,148 @@ def test(n):
     assert!(json.output().is_none());
 }
 
+#[tokio::test]
+async fn test_script_lifecycle() {
+    common_telemetry::init_default_ut_logging();
+
+    let script = r#"
+@copr(sql='select uint32s as number from numbers', args=['number'], returns=['n'])
+def test(n):
+    return n;
+"#
+    .to_string();
+    let state = ApiState {
+        sql_handler: create_testing_sql_query_handler(MemTable::default_numbers_table()),
+        script_handler: Some(create_testing_script_handler(
+            MemTable::default_numbers_table(),
+        )),
+    };
+    let Json(json) = script_handler::scripts(
+        State(state.clone()),
+        create_script_query(),
+        RawBody(Body::from(script.clone())),
+    )
+    .await;
+    assert!(json.success(), "{:?}", json);
+
+    let Json(json) = script_handler::get_scripts(
+        State(state.clone()),
+        Query(script_handler::ScriptInfoQuery::default()),
+    )
+    .await;
+    assert!(json.success(), "{:?}", json);
+    match &json.output().unwrap()[0] {
+        JsonOutput::Scripts(scripts) => {
+            assert_eq!(1, scripts.len());
+            assert_eq!("test", scripts[0].name);
+            assert!(scripts[0].script.is_none());
+        }
+        _ => unreachable!(),
+    }
+
+    let Json(json) =
+        script_handler::get_scripts(State(state.clone()), create_script_info_query()).await;
+    assert!(json.success(), "{:?}", json);
+    match &json.output().unwrap()[0] {
+        JsonOutput::Scripts(scripts) => {
+            assert_eq!(Some(&script), scripts[0].script.as_ref());
+        }
+        _ => unreachable!(),
+    }
+
+    let Json(json) = script_handler::script_versions(
+        State(state.clone()),
+        Query(script_handler::ScriptInfoQuery::default()),
+    )
+    .await;
+    assert!(!json.success(), "{:?}", json);
+    assert_eq!(json.error().unwrap(), "Invalid argument: invalid name");
+
+    let Json(json) =
+        script_handler::delete_script(State(state.clone()), create_script_info_query()).await;
+    assert!(json.success(), "{:?}", json);
+    assert!(json.output().is_none());
+
+    let Json(json) = script_handler::get_scripts(State(state), create_script_info_query()).await;
+    assert!(!json.success(), "{:?}", json);
+}
+
+#[tokio::test]
+async fn test_schedule_script() {
+    let state = ApiState {
+        sql_handler: create_testing_sql_query_handler(MemTable::default_numbers_table()),
+        script_handler: Some(create_testing_script_handler(
+            MemTable::default_numbers_table(),
+        )),
+    };
+
+    let Json(json) = script_handler::schedule_script(
+        State(state.clone()),
+        Query(script_handler::ScheduleQuery {
+            name: Some("test".to_string()),
+            interval: None,
+            table: Some("downsampled".to_string()),
+            params: HashMap::new(),
+        }),
+    )
+    .await;
+    assert!(!json.success(), "{:?}", json);
+    assert_eq!(json.error().unwrap(), "Invalid argument: invalid interval");
+
+    let Json(json) = script_handler::schedule_script(
+        State(state),
+        Query(script_handler::ScheduleQuery {
+            name: Some("test".to_string()),
+            interval: Some(Duration::from_secs(300)),
+            table: None,
+            params: HashMap::new(),
+        }),
+    )
+    .await;
+    assert!(!json.success(), "{:?}", json);
+    assert_eq!(json.error().unwrap(), "Invalid argument: invalid table");
+}
+
+#[test]
+fn test_schedule_query() {
+    let query: script_handler::ScheduleQuery =
+        serde_urlencoded::from_str("name=test&interval=5m&table=downsampled&host=host1").unwrap();
+    assert_eq!(Some("test".to_string()), query.name);
+    assert_eq!(Some(Duration::from_secs(300)), query.interval);
+    assert_eq!(Some("downsampled".to_string()), query.table);
+    assert_eq!(
+        HashMap::from([("host".to_string(), "host1".to_string())]),
+        query.params
+    );
+}
+
+fn create_script_info_query() -> Query<script_handler::ScriptInfoQuery> {
+    Query(script_handler::ScriptInfoQuery {
+        name: Some("test".to_string()),
+        version: None,
+    })
+}
+
 fn create_script_query() -> Query<script_handler::ScriptQuery> {
     Query(script_handler::ScriptQuery {
         name: Some("test".to_string()),
+        params: HashMap::new(),
     })
 }
 
 fn create_invalid_script_query() -> Query<script_handler::ScriptQuery> {
-    Query(script_handler::ScriptQuery { name: None })
+    Query(script_handler::ScriptQuery {
+        name: None,
+        params: HashMap::new(),
+    })
 }
 
 fn create_query() -> Query<http_handler::SqlQuery> {
     Query(http_handler::SqlQuery {
         sql: Some("select sum(uint32s) from numbers limit 20".to_string()),
         database: None,
+        format: None,
+        ..Default::default()
     })
 }
 
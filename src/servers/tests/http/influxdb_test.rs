@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::io::Write;
 use std::sync::Arc;
 
 use api::v1::InsertExpr;
@@ -19,8 +20,11 @@ use async_trait::async_trait;
 use axum::Router;
 use axum_test_helper::TestClient;
 use common_query::Output;
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use servers::error::Result;
 use servers::http::{HttpOptions, HttpServer};
+use servers::influxdb::query::{InfluxqlRequest, InfluxqlResponse, StatementResult};
 use servers::influxdb::InfluxdbRequest;
 use servers::query_handler::{InfluxdbLineProtocolHandler, SqlQueryHandler};
 use session::context::QueryContextRef;
@@ -32,7 +36,7 @@ struct DummyInstance {
 
 #[async_trait]
 impl InfluxdbLineProtocolHandler for DummyInstance {
-    async fn exec(&self, request: &InfluxdbRequest) -> Result<()> {
+    async fn exec(&self, request: &InfluxdbRequest, _: QueryContextRef) -> Result<()> {
         let exprs: Vec<InsertExpr> = request.try_into()?;
 
         for expr in exprs {
@@ -41,6 +45,22 @@ impl InfluxdbLineProtocolHandler for DummyInstance {
 
         Ok(())
     }
+
+    async fn query(
+        &self,
+        request: &InfluxqlRequest,
+        _: QueryContextRef,
+    ) -> Result<InfluxqlResponse> {
+        let mut results = Vec::new();
+        for (i, statement) in request.statements().into_iter().enumerate() {
+            let _ = self
+                .tx
+                .send((request.db.clone(), statement.to_string()))
+                .await;
+            results.push(StatementResult::new(i, Ok(vec![])));
+        }
+        Ok(InfluxqlResponse { results })
+    }
 }
 
 #[async_trait]
@@ -102,3 +122,117 @@ async fn test_influxdb_write() {
         ]
     );
 }
+
+#[tokio::test]
+async fn test_influxdb_write_v2() {
+    let (tx, mut rx) = mpsc::channel(100);
+
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let result = client
+        .post("/v1/influxdb/api/v2/write?org=greptime&bucket=influxdb&precision=ns")
+        .body("monitor,host=host1 cpu=1.2 1664370459457010101")
+        .send()
+        .await;
+    assert_eq!(result.status(), 204);
+    assert!(result.text().await.is_empty());
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder
+        .write_all(b"monitor,host=host1 cpu=1.2 1664370459457010101")
+        .unwrap();
+    let result = client
+        .post("/v1/influxdb/api/v2/write?bucket=public")
+        .header("Content-Encoding", "gzip")
+        .body(encoder.finish().unwrap())
+        .send()
+        .await;
+    assert_eq!(result.status(), 204);
+
+    // body is not gzip encoded
+    let result = client
+        .post("/v1/influxdb/api/v2/write")
+        .header("Content-Encoding", "gzip")
+        .body("monitor,host=host1 cpu=1.2 1664370459457010101")
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+
+    let mut metrics = vec![];
+    while let Ok(s) = rx.try_recv() {
+        metrics.push(s);
+    }
+    assert_eq!(
+        metrics,
+        vec![
+            ("influxdb".to_string(), "monitor".to_string()),
+            ("public".to_string(), "monitor".to_string())
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_influxdb_ping_and_health() {
+    let (tx, _rx) = mpsc::channel(100);
+
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let result = client.get("/v1/influxdb/ping").send().await;
+    assert_eq!(result.status(), 204);
+    assert!(result.headers().contains_key("X-Influxdb-Version"));
+
+    let result = client.get("/v1/influxdb/health").send().await;
+    assert_eq!(result.status(), 200);
+    let body: serde_json::Value = serde_json::from_str(&result.text().await).unwrap();
+    assert_eq!("pass", body["status"]);
+}
+
+#[tokio::test]
+async fn test_influxdb_query() {
+    let (tx, mut rx) = mpsc::channel(100);
+
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let result = client
+        .get("/v1/influxdb/query?db=influxdb&q=SELECT%20*%20FROM%20cpu%3BSELECT%20*%20FROM%20mem")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(
+        r#"{"results":[{"statement_id":0},{"statement_id":1}]}"#,
+        result.text().await
+    );
+
+    let result = client
+        .post("/v1/influxdb/query")
+        .header("Content-Type", "application/x-www-form-urlencoded")
+        .body("q=SELECT+*+FROM+cpu&epoch=ms")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+
+    let result = client.get("/v1/influxdb/query?db=influxdb").send().await;
+    assert_eq!(result.status(), 400);
+
+    let result = client
+        .get("/v1/influxdb/query?q=SELECT%20*%20FROM%20cpu&epoch=unknown")
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+
+    let mut queries = vec![];
+    while let Ok(s) = rx.try_recv() {
+        queries.push(s);
+    }
+    assert_eq!(
+        queries,
+        vec![
+            ("influxdb".to_string(), "SELECT * FROM cpu".to_string()),
+            ("influxdb".to_string(), "SELECT * FROM mem".to_string()),
+            ("public".to_string(), "SELECT * FROM cpu".to_string()),
+        ]
+    );
+}
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
@@ -21,6 +22,7 @@ use common_query::Output;
 use servers::error::{self, Result};
 use servers::http::{HttpOptions, HttpServer};
 use servers::opentsdb::codec::DataPoint;
+use servers::opentsdb::query::{QueryRequest, QueryResult, SuggestRequest};
 use servers::query_handler::{OpentsdbProtocolHandler, SqlQueryHandler};
 use session::context::QueryContextRef;
 use tokio::sync::mpsc;
@@ -31,16 +33,39 @@ struct DummyInstance {
 
 #[async_trait]
 impl OpentsdbProtocolHandler for DummyInstance {
-    async fn exec(&self, data_point: &DataPoint) -> Result<()> {
-        if data_point.metric() == "should_failed" {
+    async fn exec(&self, data_points: &[DataPoint], _: QueryContextRef) -> Result<()> {
+        if data_points.iter().any(|p| p.metric() == "should_failed") {
             return error::InternalSnafu {
                 err_msg: "expected",
             }
             .fail();
         }
-        let _ = self.tx.send(data_point.metric().to_string()).await;
+        for data_point in data_points {
+            let _ = self.tx.send(data_point.metric().to_string()).await;
+        }
         Ok(())
     }
+
+    async fn query(&self, request: &QueryRequest, _: QueryContextRef) -> Result<Vec<QueryResult>> {
+        Ok(request
+            .queries
+            .iter()
+            .map(|q| QueryResult {
+                metric: q.metric.clone(),
+                tags: BTreeMap::from([("host".to_string(), "web01".to_string())]),
+                aggregate_tags: vec!["dc".to_string()],
+                dps: BTreeMap::from([(1346846400, 18.0), (1346846460, 9.0)]),
+            })
+            .collect())
+    }
+
+    async fn suggest(&self, request: &SuggestRequest, _: QueryContextRef) -> Result<Vec<String>> {
+        Ok(request.suggest(
+            ["sys.cpu.nice", "sys.cpu.user", "app.latency"]
+                .into_iter()
+                .map(|s| s.to_string()),
+        ))
+    }
 }
 
 #[async_trait]
@@ -187,6 +212,57 @@ async fn test_opentsdb_debug_put() {
     );
 }
 
+#[tokio::test]
+async fn test_opentsdb_query() {
+    let (tx, _rx) = mpsc::channel(100);
+
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let result = client
+        .post("/v1/opentsdb/api/query")
+        .body(
+            r#"{
+                "start": "1h-ago",
+                "queries": [{
+                    "aggregator": "sum",
+                    "metric": "sys.cpu.nice",
+                    "downsample": "1m-avg",
+                    "tags": {"dc": "*"}
+                }]
+            }"#,
+        )
+        .header("Content-Type", "application/json")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(
+        result.text().await,
+        "[{\"metric\":\"sys.cpu.nice\",\"tags\":{\"host\":\"web01\"},\"aggregateTags\":[\"dc\"],\"dps\":{\"1346846400\":18.0,\"1346846460\":9.0}}]"
+    );
+}
+
+#[tokio::test]
+async fn test_opentsdb_suggest() {
+    let (tx, _rx) = mpsc::channel(100);
+
+    let app = make_test_app(tx);
+    let client = TestClient::new(app);
+
+    let result = client
+        .get("/v1/opentsdb/api/suggest?type=metrics&q=sys&max=10")
+        .send()
+        .await;
+    assert_eq!(result.status(), 200);
+    assert_eq!(result.text().await, "[\"sys.cpu.nice\",\"sys.cpu.user\"]");
+
+    let result = client
+        .get("/v1/opentsdb/api/suggest?type=unknown")
+        .send()
+        .await;
+    assert_eq!(result.status(), 400);
+}
+
 fn create_data_point(metric: &str) -> String {
     format!(
         r#"{{
@@ -36,7 +36,7 @@ struct DummyInstance {
 
 #[async_trait]
 impl PrometheusProtocolHandler for DummyInstance {
-    async fn write(&self, db: &str, request: WriteRequest) -> Result<()> {
+    async fn write(&self, db: &str, request: WriteRequest, _: QueryContextRef) -> Result<()> {
         let _ = self
             .tx
             .send((db.to_string(), request.encode_to_vec()))
@@ -44,7 +44,12 @@ impl PrometheusProtocolHandler for DummyInstance {
 
         Ok(())
     }
-    async fn read(&self, db: &str, request: ReadRequest) -> Result<PrometheusResponse> {
+    async fn read(
+        &self,
+        db: &str,
+        request: ReadRequest,
+        _: QueryContextRef,
+    ) -> Result<PrometheusResponse> {
         let _ = self
             .tx
             .send((db.to_string(), request.encode_to_vec()))
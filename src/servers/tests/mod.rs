@@ -14,6 +14,7 @@
 
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
+use std::time::Duration;
 
 use async_trait::async_trait;
 use catalog::local::{MemoryCatalogManager, MemoryCatalogProvider, MemorySchemaProvider};
@@ -21,12 +22,15 @@ use catalog::{CatalogList, CatalogProvider, SchemaProvider};
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
 use common_query::Output;
 use query::{QueryEngineFactory, QueryEngineRef};
-use servers::error::Result;
+use servers::error::{NotSupportedSnafu, Result};
+use servers::http::script::ScriptInfo;
 use servers::query_handler::{
     ScriptHandler, ScriptHandlerRef, SqlQueryHandler, SqlQueryHandlerRef,
 };
+use snafu::OptionExt;
 use table::test_util::MemTable;
 
+mod grpc;
 mod http;
 mod mysql;
 use script::engine::{CompileContext, EvalContext, Script, ScriptEngine};
@@ -39,7 +43,8 @@ mod postgres;
 struct DummyInstance {
     query_engine: QueryEngineRef,
     py_engine: Arc<PyEngine>,
-    scripts: RwLock<HashMap<String, Arc<PyScript>>>,
+    /// Compiled scripts with their sources.
+    scripts: RwLock<HashMap<String, (String, Arc<PyScript>)>>,
 }
 
 impl DummyInstance {
@@ -63,7 +68,7 @@ impl SqlQueryHandler for DummyInstance {
 #[async_trait]
 impl ScriptHandler for DummyInstance {
     async fn insert_script(&self, name: &str, script: &str) -> Result<()> {
-        let script = self
+        let compiled = self
             .py_engine
             .compile(script, CompileContext::default())
             .await
@@ -71,15 +76,79 @@ impl ScriptHandler for DummyInstance {
         self.scripts
             .write()
             .unwrap()
-            .insert(name.to_string(), Arc::new(script));
+            .insert(name.to_string(), (script.to_string(), Arc::new(compiled)));
 
         Ok(())
     }
 
-    async fn execute_script(&self, name: &str) -> Result<Output> {
-        let py_script = self.scripts.read().unwrap().get(name).unwrap().clone();
+    async fn execute_script(
+        &self,
+        name: &str,
+        params: HashMap<String, String>,
+        query_ctx: QueryContextRef,
+    ) -> Result<Output> {
+        let py_script = self.scripts.read().unwrap().get(name).unwrap().1.clone();
 
-        Ok(py_script.execute(EvalContext::default()).await.unwrap())
+        Ok(py_script
+            .execute(EvalContext { params, query_ctx })
+            .await
+            .unwrap())
+    }
+
+    async fn list_scripts(&self) -> Result<Vec<ScriptInfo>> {
+        let scripts = self.scripts.read().unwrap();
+        Ok(scripts
+            .keys()
+            .map(|name| dummy_script_info(name, None))
+            .collect())
+    }
+
+    async fn get_script(&self, name: &str, _version: Option<i64>) -> Result<ScriptInfo> {
+        let scripts = self.scripts.read().unwrap();
+        let (script, _) = scripts.get(name).context(NotSupportedSnafu {
+            feat: "unknown script",
+        })?;
+        Ok(dummy_script_info(name, Some(script.clone())))
+    }
+
+    async fn list_script_versions(&self, name: &str) -> Result<Vec<ScriptInfo>> {
+        Ok(vec![dummy_script_info(name, None)])
+    }
+
+    async fn delete_script(&self, name: &str) -> Result<()> {
+        let _ = self.scripts.write().unwrap().remove(name);
+        Ok(())
+    }
+
+    async fn schedule_script(
+        &self,
+        _name: &str,
+        _interval: Duration,
+        _target_table: &str,
+        _params: HashMap<String, String>,
+    ) -> Result<()> {
+        NotSupportedSnafu {
+            feat: "schedule script",
+        }
+        .fail()
+    }
+
+    async fn unschedule_script(&self, _name: &str) -> Result<()> {
+        NotSupportedSnafu {
+            feat: "schedule script",
+        }
+        .fail()
+    }
+}
+
+fn dummy_script_info(name: &str, script: Option<String>) -> ScriptInfo {
+    ScriptInfo {
+        name: name.to_string(),
+        engine: "python".to_string(),
+        version: 0,
+        gmt_created: 0,
+        gmt_modified: 0,
+        script,
     }
 }
 
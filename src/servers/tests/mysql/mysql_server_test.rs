@@ -16,6 +16,7 @@ use std::net::SocketAddr;
 use std::sync::Arc;
 use std::time::Duration;
 
+use common_grpc::tls::TlsOption;
 use common_recordbatch::RecordBatch;
 use common_runtime::Builder as RuntimeBuilder;
 use datatypes::schema::Schema;
@@ -25,8 +26,8 @@ use rand::rngs::StdRng;
 use rand::Rng;
 use servers::error::Result;
 use servers::mysql::server::MysqlServer;
+use servers::process::{ProcessManager, QueryOptions};
 use servers::server::Server;
-use servers::tls::TlsOption;
 use table::test_util::MemTable;
 
 use crate::create_testing_sql_query_handler;
@@ -43,7 +44,7 @@ fn create_mysql_server(table: MemTable, tls: Arc<TlsOption>) -> Result<Box<dyn S
     );
 
     Ok(MysqlServer::create_server(
-        query_handler,
+        Arc::new(ProcessManager::new(query_handler, QueryOptions::default())),
         io_runtime,
         tls,
         None,
@@ -132,9 +133,10 @@ async fn test_query_all_datatypes() -> Result<()> {
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_server_prefer_secure_client_plain() -> Result<()> {
     let server_tls = Arc::new(TlsOption {
-        mode: servers::tls::TlsMode::Prefer,
+        mode: common_grpc::tls::TlsMode::Prefer,
         cert_path: "tests/ssl/server.crt".to_owned(),
         key_path: "tests/ssl/server.key".to_owned(),
+        ..Default::default()
     });
 
     let client_tls = false;
@@ -145,9 +147,10 @@ async fn test_server_prefer_secure_client_plain() -> Result<()> {
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_server_prefer_secure_client_secure() -> Result<()> {
     let server_tls = Arc::new(TlsOption {
-        mode: servers::tls::TlsMode::Prefer,
+        mode: common_grpc::tls::TlsMode::Prefer,
         cert_path: "tests/ssl/server.crt".to_owned(),
         key_path: "tests/ssl/server.key".to_owned(),
+        ..Default::default()
     });
 
     let client_tls = true;
@@ -158,9 +161,10 @@ async fn test_server_prefer_secure_client_secure() -> Result<()> {
 #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
 async fn test_server_require_secure_client_secure() -> Result<()> {
     let server_tls = Arc::new(TlsOption {
-        mode: servers::tls::TlsMode::Require,
+        mode: common_grpc::tls::TlsMode::Require,
         cert_path: "tests/ssl/server.crt".to_owned(),
         key_path: "tests/ssl/server.key".to_owned(),
+        ..Default::default()
     });
 
     let client_tls = true;
@@ -171,9 +175,10 @@ async fn test_server_require_secure_client_secure() -> Result<()> {
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_server_required_secure_client_plain() -> Result<()> {
     let server_tls = Arc::new(TlsOption {
-        mode: servers::tls::TlsMode::Require,
+        mode: common_grpc::tls::TlsMode::Require,
         cert_path: "tests/ssl/server.crt".to_owned(),
         key_path: "tests/ssl/server.key".to_owned(),
+        ..Default::default()
     });
 
     let client_tls = false;
@@ -295,6 +300,39 @@ async fn test_query_concurrently() -> Result<()> {
     Ok(())
 }
 
+#[tokio::test]
+async fn test_kill_connection() -> Result<()> {
+    let table = MemTable::default_numbers_table();
+
+    let mysql_server = create_mysql_server(table, Default::default())?;
+    let listening = "127.0.0.1:0".parse::<SocketAddr>().unwrap();
+    let server_port = mysql_server.start(listening).await.unwrap().port();
+
+    let mut victim = create_connection(server_port, false, false).await.unwrap();
+    let mut killer = create_connection(server_port, false, false).await.unwrap();
+
+    killer
+        .query_drop(format!("KILL QUERY {}", victim.id()))
+        .await
+        .unwrap_err();
+    let result: u32 = victim
+        .query_first("SELECT uint32s FROM numbers LIMIT 1")
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(0, result);
+
+    killer
+        .query_drop(format!("KILL CONNECTION {}", victim.id()))
+        .await
+        .unwrap();
+    assert!(victim
+        .query_first::<u32, _>("SELECT uint32s FROM numbers LIMIT 1")
+        .await
+        .is_err());
+    Ok(())
+}
+
 async fn create_connection(
     port: u16,
     with_pwd: bool,
@@ -24,9 +24,11 @@ use rand::Rng;
 use servers::error::{self as server_error, Error, Result};
 use servers::opentsdb::codec::DataPoint;
 use servers::opentsdb::connection::Connection;
+use servers::opentsdb::query::{QueryRequest, QueryResult, SuggestRequest};
 use servers::opentsdb::OpentsdbServer;
 use servers::query_handler::OpentsdbProtocolHandler;
 use servers::server::Server;
+use session::context::QueryContextRef;
 use tokio::net::TcpStream;
 use tokio::sync::{mpsc, Notify};
 
@@ -36,18 +38,27 @@ struct DummyOpentsdbInstance {
 
 #[async_trait]
 impl OpentsdbProtocolHandler for DummyOpentsdbInstance {
-    async fn exec(&self, data_point: &DataPoint) -> Result<()> {
-        let metric = data_point.metric();
-        if metric == "should_failed" {
+    async fn exec(&self, data_points: &[DataPoint], _: QueryContextRef) -> Result<()> {
+        if data_points.iter().any(|p| p.metric() == "should_failed") {
             return server_error::InternalSnafu {
                 err_msg: "expected",
             }
             .fail();
         }
-        let i = metric.parse::<i32>().unwrap();
-        let _ = self.tx.send(i * i).await;
+        for data_point in data_points {
+            let i = data_point.metric().parse::<i32>().unwrap();
+            let _ = self.tx.send(i * i).await;
+        }
         Ok(())
     }
+
+    async fn query(&self, _request: &QueryRequest, _: QueryContextRef) -> Result<Vec<QueryResult>> {
+        unimplemented!()
+    }
+
+    async fn suggest(&self, _request: &SuggestRequest, _: QueryContextRef) -> Result<Vec<String>> {
+        unimplemented!()
+    }
 }
 
 fn create_opentsdb_server(tx: mpsc::Sender<i32>) -> Result<Box<dyn Server>> {
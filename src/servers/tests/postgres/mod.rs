@@ -17,6 +17,7 @@ use std::sync::Arc;
 use std::time::{Duration, SystemTime};
 
 use common_catalog::consts::DEFAULT_SCHEMA_NAME;
+use common_grpc::tls::TlsOption;
 use common_runtime::Builder as RuntimeBuilder;
 use rand::rngs::StdRng;
 use rand::Rng;
@@ -25,7 +26,6 @@ use rustls::{Certificate, Error, ServerName};
 use servers::error::Result;
 use servers::postgres::PostgresServer;
 use servers::server::Server;
-use servers::tls::TlsOption;
 use table::test_util::MemTable;
 use tokio_postgres::{Client, Error as PgError, NoTls, SimpleQueryMessage};
 
@@ -186,9 +186,10 @@ async fn test_server_secure_prefer_client_plain() -> Result<()> {
     common_telemetry::init_default_ut_logging();
 
     let server_tls = Arc::new(TlsOption {
-        mode: servers::tls::TlsMode::Prefer,
+        mode: common_grpc::tls::TlsMode::Prefer,
         cert_path: "tests/ssl/server.crt".to_owned(),
         key_path: "tests/ssl/server.key".to_owned(),
+        ..Default::default()
     });
 
     let client_tls = false;
@@ -201,9 +202,10 @@ async fn test_server_secure_require_client_plain() -> Result<()> {
     common_telemetry::init_default_ut_logging();
 
     let server_tls = Arc::new(TlsOption {
-        mode: servers::tls::TlsMode::Require,
+        mode: common_grpc::tls::TlsMode::Require,
         cert_path: "tests/ssl/server.crt".to_owned(),
         key_path: "tests/ssl/server.key".to_owned(),
+        ..Default::default()
     });
     let server_port = start_test_server(server_tls).await?;
     let r = create_plain_connection(server_port, false).await;
@@ -216,9 +218,10 @@ async fn test_server_secure_require_client_secure() -> Result<()> {
     common_telemetry::init_default_ut_logging();
 
     let server_tls = Arc::new(TlsOption {
-        mode: servers::tls::TlsMode::Require,
+        mode: common_grpc::tls::TlsMode::Require,
         cert_path: "tests/ssl/server.crt".to_owned(),
         key_path: "tests/ssl/server.key".to_owned(),
+        ..Default::default()
     });
 
     let client_tls = true;
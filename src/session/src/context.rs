@@ -12,15 +12,34 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, RwLock};
 
 use arc_swap::ArcSwapOption;
 use common_telemetry::info;
+use common_time::TimeZone;
 
 pub type QueryContextRef = Arc<QueryContext>;
 
+/// Name of the session variable holding the time zone of the session.
+pub const TIME_ZONE_VARIABLE: &str = "time_zone";
+
 pub struct QueryContext {
+    current_catalog: ArcSwapOption<String>,
     current_schema: ArcSwapOption<String>,
+    current_user: ArcSwapOption<String>,
+    /// Catalog of the tenant the current user belongs to, a user bound to a tenant can only
+    /// access the catalog of its tenant.
+    tenant: ArcSwapOption<String>,
+    time_zone: ArcSwapOption<TimeZone>,
+    /// Id of the MySQL connection of the session, 0 if the session isn't a MySQL connection.
+    connection_id: AtomicU32,
+    /// Variables set by `SET name = value`, keyed by lowercase name.
+    variables: RwLock<BTreeMap<String, String>>,
+    /// Whether the session is created by the server itself, like the sessions of scripts.
+    /// Internal sessions have no users and are trusted by privilege checks.
+    internal: bool,
 }
 
 impl Default for QueryContext {
@@ -34,16 +53,40 @@ impl QueryContext {
         Arc::new(QueryContext::new())
     }
 
+    /// Creates the context of an internal session, see [QueryContext::is_internal].
+    pub fn internal_arc() -> QueryContextRef {
+        Arc::new(QueryContext {
+            internal: true,
+            ..QueryContext::new()
+        })
+    }
+
     pub fn new() -> Self {
         Self {
+            current_catalog: ArcSwapOption::new(None),
             current_schema: ArcSwapOption::new(None),
+            current_user: ArcSwapOption::new(None),
+            tenant: ArcSwapOption::new(None),
+            time_zone: ArcSwapOption::new(None),
+            connection_id: AtomicU32::new(0),
+            variables: RwLock::new(BTreeMap::new()),
+            internal: false,
         }
     }
 
     pub fn with_current_schema(schema: String) -> Self {
-        Self {
-            current_schema: ArcSwapOption::new(Some(Arc::new(schema))),
-        }
+        let ctx = Self::new();
+        ctx.current_schema.store(Some(Arc::new(schema)));
+        ctx
+    }
+
+    pub fn current_catalog(&self) -> Option<String> {
+        self.current_catalog.load().as_deref().cloned()
+    }
+
+    pub fn set_current_catalog(&self, catalog: &str) {
+        self.current_catalog
+            .store(Some(Arc::new(catalog.to_string())));
     }
 
     pub fn current_schema(&self) -> Option<String> {
@@ -57,4 +100,134 @@ impl QueryContext {
             schema, last
         )
     }
+
+    pub fn current_user(&self) -> Option<String> {
+        self.current_user.load().as_deref().cloned()
+    }
+
+    pub fn set_current_user(&self, user: &str) {
+        self.current_user.store(Some(Arc::new(user.to_string())));
+    }
+
+    pub fn tenant(&self) -> Option<String> {
+        self.tenant.load().as_deref().cloned()
+    }
+
+    /// Binds the session to the catalog of a tenant, which also becomes the current catalog.
+    pub fn set_tenant(&self, catalog: &str) {
+        self.tenant.store(Some(Arc::new(catalog.to_string())));
+        self.set_current_catalog(catalog);
+    }
+
+    /// Returns whether the session is allowed to access `catalog`, sessions not bound to any
+    /// tenant can access all catalogs.
+    pub fn is_catalog_accessible(&self, catalog: &str) -> bool {
+        match self.tenant.load().as_deref() {
+            Some(tenant) => tenant == catalog,
+            None => true,
+        }
+    }
+
+    pub fn is_internal(&self) -> bool {
+        self.internal
+    }
+
+    pub fn connection_id(&self) -> Option<u32> {
+        match self.connection_id.load(Ordering::Relaxed) {
+            0 => None,
+            id => Some(id),
+        }
+    }
+
+    pub fn set_connection_id(&self, id: u32) {
+        self.connection_id.store(id, Ordering::Relaxed);
+    }
+
+    /// Time zone of the session, `None` means the local time zone of the server.
+    pub fn time_zone(&self) -> Option<TimeZone> {
+        self.time_zone.load().as_deref().cloned()
+    }
+
+    pub fn set_time_zone(&self, time_zone: Option<TimeZone>) {
+        self.time_zone.store(time_zone.map(Arc::new));
+    }
+
+    /// Sets a session variable. Setting `time_zone` also changes the time zone
+    /// used to parse and render timestamps in this session.
+    pub fn set_variable(&self, name: &str, value: &str) -> common_time::error::Result<()> {
+        let name = name.to_lowercase();
+        if name == TIME_ZONE_VARIABLE {
+            self.set_time_zone(TimeZone::from_tz_string(value)?);
+        }
+        self.variables
+            .write()
+            .unwrap()
+            .insert(name, value.to_string());
+        Ok(())
+    }
+
+    pub fn variable(&self, name: &str) -> Option<String> {
+        self.variables
+            .read()
+            .unwrap()
+            .get(&name.to_lowercase())
+            .cloned()
+    }
+
+    /// All variables set in this session, sorted by name.
+    pub fn variables(&self) -> Vec<(String, String)> {
+        self.variables
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_variable() {
+        let ctx = QueryContext::new();
+        assert!(ctx.time_zone().is_none());
+        assert!(ctx.variable("foo").is_none());
+
+        ctx.set_variable("FOO", "bar").unwrap();
+        assert_eq!(Some("bar".to_string()), ctx.variable("foo"));
+
+        ctx.set_variable("time_zone", "+08:00").unwrap();
+        assert_eq!("+08:00", ctx.time_zone().unwrap().to_string());
+        assert_eq!(Some("+08:00".to_string()), ctx.variable("Time_Zone"));
+
+        assert!(ctx.set_variable("time_zone", "invalid").is_err());
+        assert_eq!("+08:00", ctx.time_zone().unwrap().to_string());
+
+        ctx.set_variable("time_zone", "SYSTEM").unwrap();
+        assert!(ctx.time_zone().is_none());
+
+        assert_eq!(
+            vec![
+                ("foo".to_string(), "bar".to_string()),
+                ("time_zone".to_string(), "SYSTEM".to_string())
+            ],
+            ctx.variables()
+        );
+    }
+
+    #[test]
+    fn test_tenant() {
+        let ctx = QueryContext::new();
+        assert!(ctx.tenant().is_none());
+        assert!(ctx.is_catalog_accessible("greptime"));
+        assert!(ctx.is_catalog_accessible("team_a"));
+
+        ctx.set_tenant("team_a");
+        assert_eq!(Some("team_a".to_string()), ctx.tenant());
+        assert_eq!(Some("team_a".to_string()), ctx.current_catalog());
+        assert!(ctx.is_catalog_accessible("team_a"));
+        assert!(!ctx.is_catalog_accessible("greptime"));
+    }
 }
@@ -14,5 +14,6 @@
 
 pub use sqlparser::ast::{
     ColumnDef, ColumnOption, ColumnOptionDef, DataType, Expr, Function, FunctionArg,
-    FunctionArgExpr, Ident, ObjectName, SqlOption, TableConstraint, Value,
+    FunctionArgExpr, Ident, ObjectName, Query, SelectItem, SetExpr, SqlOption, TableConstraint,
+    TableFactor, TableWithJoins, Value,
 };
@@ -73,6 +73,12 @@ pub enum Error {
     #[snafu(display("Invalid SQL, error: {}", msg))]
     InvalidSql { msg: String, backtrace: Backtrace },
 
+    #[snafu(display("Invalid interval: {}, source: {}", interval, source))]
+    InvalidInterval {
+        interval: String,
+        source: common_time::error::Error,
+    },
+
     #[snafu(display("SQL data type not supported yet: {:?}", t))]
     SqlTypeNotSupported {
         t: crate::ast::DataType,
@@ -116,6 +122,12 @@ pub enum Error {
         source: datatypes::error::Error,
     },
 
+    #[snafu(display("Invalid DECIMAL type, source: {}", source))]
+    InvalidDecimalType {
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
+
     #[snafu(display(
         "Failed to convert data type to gRPC data type defined in proto, source: {}",
         source
@@ -137,8 +149,10 @@ impl ErrorExt for Error {
             | InvalidTimeIndex { .. }
             | Tokenizer { .. }
             | InvalidSql { .. }
+            | InvalidInterval { .. }
             | ParseSqlValue { .. }
             | SqlTypeNotSupported { .. }
+            | InvalidDecimalType { .. }
             | InvalidDefault { .. } => StatusCode::InvalidSyntax,
 
             InvalidDatabaseName { .. } | ColumnTypeMismatch { .. } | InvalidTableName { .. } => {
@@ -22,9 +22,11 @@ use crate::error::{
     self, InvalidDatabaseNameSnafu, InvalidTableNameSnafu, Result, SyntaxSnafu, TokenizerSnafu,
 };
 use crate::statements::describe::DescribeTable;
-use crate::statements::drop::DropTable;
+use crate::statements::drop::{DropCatalog, DropTable};
 use crate::statements::explain::Explain;
-use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowKind, ShowTables};
+use crate::statements::show::{
+    ShowCreateTable, ShowDatabases, ShowKind, ShowProcesslist, ShowTables,
+};
 use crate::statements::statement::Statement;
 use crate::statements::table_idents_to_full_name;
 
@@ -32,6 +34,7 @@ use crate::statements::table_idents_to_full_name;
 pub struct ParserContext<'a> {
     pub(crate) parser: Parser<'a>,
     pub(crate) sql: &'a str,
+    pub(crate) dialect: &'a dyn Dialect,
 }
 
 impl<'a> ParserContext<'a> {
@@ -45,6 +48,7 @@ impl<'a> ParserContext<'a> {
         let mut parser_ctx = ParserContext {
             sql,
             parser: Parser::new(tokens, dialect),
+            dialect,
         };
 
         let mut expecting_statement_delimiter = false;
@@ -102,6 +106,14 @@ impl<'a> ParserContext<'a> {
 
                     Keyword::DROP => self.parse_drop(),
 
+                    Keyword::SET => self.parse_set_variables(),
+
+                    _ if w.value.eq_ignore_ascii_case("GRANT") => self.parse_grant(),
+
+                    _ if w.value.eq_ignore_ascii_case("REVOKE") => self.parse_revoke(),
+
+                    _ if w.value.eq_ignore_ascii_case("KILL") => self.parse_kill(),
+
                     // TODO(LFC): Use "Keyword::USE" when we can upgrade to newer version of crate sqlparser.
                     Keyword::NoKeyword if w.value.to_lowercase() == "use" => {
                         self.parser.next_token();
@@ -149,6 +161,14 @@ impl<'a> ParserContext<'a> {
             } else {
                 self.unsupported(self.peek_token_as_string())
             }
+        } else if self.consume_token("PROCESSLIST") {
+            Ok(Statement::ShowProcesslist(ShowProcesslist { full: false }))
+        } else if self.consume_token("FULL") {
+            if self.consume_token("PROCESSLIST") {
+                Ok(Statement::ShowProcesslist(ShowProcesslist { full: true }))
+            } else {
+                self.unsupported(self.peek_token_as_string())
+            }
         } else {
             self.unsupported(self.peek_token_as_string())
         }
@@ -291,6 +311,10 @@ impl<'a> ParserContext<'a> {
 
     fn parse_drop(&mut self) -> Result<Statement> {
         self.parser.next_token();
+        // "CATALOG" is not a keyword of sqlparser.
+        if self.consume_token("CATALOG") {
+            return self.parse_drop_catalog();
+        }
         if !self.matches_keyword(Keyword::TABLE) {
             return self.unsupported(self.peek_token_as_string());
         }
@@ -319,6 +343,20 @@ impl<'a> ParserContext<'a> {
         }))
     }
 
+    fn parse_drop_catalog(&mut self) -> Result<Statement> {
+        let catalog_name =
+            self.parser
+                .parse_identifier()
+                .with_context(|_| error::UnexpectedSnafu {
+                    sql: self.sql,
+                    expected: "a catalog name",
+                    actual: self.peek_token_as_string(),
+                })?;
+        Ok(Statement::DropCatalog(DropCatalog {
+            name: catalog_name.value,
+        }))
+    }
+
     // Report unexpected token
     pub(crate) fn expected<T>(&self, expected: &str, found: Token) -> Result<T> {
         Err(ParserError::ParserError(format!(
@@ -532,6 +570,28 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn test_show_processlist() {
+        let stmts =
+            ParserContext::create_with_dialect("SHOW PROCESSLIST", &GenericDialect {}).unwrap();
+        assert_eq!(1, stmts.len());
+        assert_matches!(
+            &stmts[0],
+            Statement::ShowProcesslist(ShowProcesslist { full: false })
+        );
+
+        let stmts =
+            ParserContext::create_with_dialect("show full processlist;", &GenericDialect {})
+                .unwrap();
+        assert_eq!(1, stmts.len());
+        assert_matches!(
+            &stmts[0],
+            Statement::ShowProcesslist(ShowProcesslist { full: true })
+        );
+
+        assert!(ParserContext::create_with_dialect("SHOW FULL", &GenericDialect {}).is_err());
+    }
+
     #[test]
     pub fn test_explain() {
         let sql = "EXPLAIN select * from foo";
@@ -620,4 +680,21 @@ mod tests {
             })
         )
     }
+
+    #[test]
+    pub fn test_drop_catalog() {
+        let sql = "DROP CATALOG team_a";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        let mut stmts = result.unwrap();
+        assert_eq!(
+            stmts.pop().unwrap(),
+            Statement::DropCatalog(DropCatalog {
+                name: "team_a".to_string()
+            })
+        );
+
+        let sql = "DROP CATALOG";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        assert!(result.is_err());
+    }
 }
@@ -15,4 +15,7 @@
 mod alter_parser;
 pub(crate) mod create_parser;
 pub(crate) mod insert_parser;
+mod kill_parser;
+mod privilege_parser;
 pub(crate) mod query_parser;
+mod set_variables_parser;
@@ -15,6 +15,7 @@
 use snafu::ResultExt;
 use sqlparser::keywords::Keyword;
 use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
@@ -43,7 +44,7 @@ impl<'a> ParserContext<'a> {
             }
         } else if parser.parse_keyword(Keyword::DROP) {
             if parser.parse_keyword(Keyword::COLUMN) {
-                let name = self.parser.parse_identifier()?;
+                let name = parser.parse_identifier()?;
                 AlterTableOperation::DropColumn { name }
             } else {
                 return Err(ParserError::ParserError(format!(
@@ -51,9 +52,26 @@ impl<'a> ParserContext<'a> {
                     parser.peek_token()
                 )));
             }
+        } else if parser.parse_keyword(Keyword::RENAME) {
+            parser.expect_keyword(Keyword::TO)?;
+            let new_table_name = parser.parse_identifier()?;
+            AlterTableOperation::RenameTable { new_table_name }
+        } else if matches!(parser.peek_token(), Token::Word(w) if w.value.eq_ignore_ascii_case("MODIFY"))
+        {
+            let _ = parser.next_token();
+            let _ = parser.parse_keyword(Keyword::COLUMN);
+            let column_name = parser.parse_identifier()?;
+            let data_type = parser.parse_data_type()?;
+            AlterTableOperation::ModifyColumn {
+                column_name,
+                data_type,
+            }
+        } else if matches!(parser.peek_token(), Token::Word(w) if w.keyword == Keyword::SET) {
+            let options = parser.parse_options(Keyword::SET)?;
+            AlterTableOperation::SetTableOptions { options }
         } else {
             return Err(ParserError::ParserError(format!(
-                "expect keyword ADD or DROP after ALTER TABLE, found {}",
+                "expect keyword ADD, DROP, RENAME, MODIFY or SET after ALTER TABLE, found {}",
                 parser.peek_token()
             )));
         };
@@ -130,4 +148,77 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_parse_alter_rename_table() {
+        let sql = "ALTER TABLE my_metric_1 RENAME TO my_metric_2";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        match statement {
+            Statement::Alter(alter_table) => {
+                assert_eq!("my_metric_1", alter_table.table_name().0[0].value);
+                match alter_table.alter_operation() {
+                    AlterTableOperation::RenameTable { new_table_name } => {
+                        assert_eq!("my_metric_2", new_table_name.value);
+                    }
+                    _ => unreachable!(),
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 RENAME my_metric_2";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+    }
+
+    #[test]
+    fn test_parse_alter_modify_column() {
+        for sql in [
+            "ALTER TABLE my_metric_1 MODIFY COLUMN a BIGINT",
+            "ALTER TABLE my_metric_1 modify a BIGINT",
+        ] {
+            let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+            assert_eq!(1, result.len());
+
+            let statement = result.remove(0);
+            match statement {
+                Statement::Alter(alter_table) => match alter_table.alter_operation() {
+                    AlterTableOperation::ModifyColumn {
+                        column_name,
+                        data_type,
+                    } => {
+                        assert_eq!("a", column_name.value);
+                        assert_eq!(DataType::BigInt(None), *data_type);
+                    }
+                    _ => unreachable!(),
+                },
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_alter_set_table_options() {
+        let sql = "ALTER TABLE my_metric_1 SET (ttl = '7d', write_buffer_size = 1024)";
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+
+        let statement = result.remove(0);
+        match statement {
+            Statement::Alter(alter_table) => match alter_table.alter_operation() {
+                AlterTableOperation::SetTableOptions { options } => {
+                    assert_eq!(2, options.len());
+                    assert_eq!("ttl", options[0].name.value);
+                    assert_eq!("write_buffer_size", options[1].name.value);
+                }
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        }
+
+        let sql = "ALTER TABLE my_metric_1 SET ()";
+        assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+    }
 }
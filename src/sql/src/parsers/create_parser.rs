@@ -14,12 +14,13 @@
 
 use std::cmp::Ordering;
 
+use common_time::timestamp::TimeUnit;
 use itertools::Itertools;
 use mito::engine;
 use once_cell::sync::Lazy;
 use snafu::{ensure, OptionExt, ResultExt};
 use sqlparser::ast::ColumnOption::NotNull;
-use sqlparser::ast::{ColumnOptionDef, DataType, Value};
+use sqlparser::ast::{ColumnOptionDef, DataType, ObjectName, Value};
 use sqlparser::dialect::keywords::Keyword;
 use sqlparser::parser::IsOptional::Mandatory;
 use sqlparser::tokenizer::{Token, Word};
@@ -28,10 +29,12 @@ use crate::ast::{ColumnDef, Ident, TableConstraint, Value as SqlValue};
 use crate::error::{self, InvalidTimeIndexSnafu, Result, SyntaxSnafu};
 use crate::parser::ParserContext;
 use crate::statements::create::{
-    CreateDatabase, CreateTable, PartitionEntry, Partitions, TIME_INDEX,
+    CreateCatalog, CreateDatabase, CreateTable, PartitionEntry, Partitions, TIME_INDEX,
 };
 use crate::statements::statement::Statement;
-use crate::statements::{sql_data_type_to_concrete_data_type, sql_value_to_value};
+use crate::statements::{
+    sql_data_type_to_concrete_data_type, sql_value_to_value, timestamp_type_name, timestamp_unit_of,
+};
 
 const ENGINE: &str = "ENGINE";
 const MAXVALUE: &str = "MAXVALUE";
@@ -48,6 +51,13 @@ impl<'a> ParserContext<'a> {
 
                 Keyword::DATABASE => self.parse_create_database(),
 
+                // "CATALOG" is not a keyword of sqlparser.
+                _ if w.value.eq_ignore_ascii_case("CATALOG") => self.parse_create_catalog(),
+
+                _ if w.value.eq_ignore_ascii_case("USER") => self.parse_create_user(),
+
+                _ if w.value.eq_ignore_ascii_case("ROLE") => self.parse_create_role(),
+
                 _ => self.unsupported(w.to_string()),
             },
             unexpected => self.unsupported(unexpected.to_string()),
@@ -71,6 +81,23 @@ impl<'a> ParserContext<'a> {
         }))
     }
 
+    fn parse_create_catalog(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+
+        let catalog_name = self
+            .parser
+            .parse_object_name()
+            .context(error::UnexpectedSnafu {
+                sql: self.sql,
+                expected: "a catalog name",
+                actual: self.peek_token_as_string(),
+            })?;
+
+        Ok(Statement::CreateCatalog(CreateCatalog {
+            name: catalog_name,
+        }))
+    }
+
     fn parse_create_table(&mut self) -> Result<Statement> {
         self.parser.next_token();
         let if_not_exists =
@@ -248,12 +275,19 @@ impl<'a> ParserContext<'a> {
         columns: &mut Vec<ColumnDef>,
         constraints: &mut Vec<TableConstraint>,
     ) -> Result<()> {
-        let column = self
+        let mut column = self
             .parser
             .parse_column_def()
             .context(SyntaxSnafu { sql: self.sql })?;
 
-        if !matches!(column.data_type, DataType::Timestamp)
+        // sqlparser stops at `(` after `TIMESTAMP`, for supporting `ts TIMESTAMP(ns)` syntax.
+        if matches!(column.data_type, DataType::Timestamp)
+            && self.parser.consume_token(&Token::LParen)
+        {
+            self.parse_timestamp_unit(&mut column)?;
+        }
+
+        if timestamp_unit_of(&column.data_type).is_none()
             || matches!(self.parser.peek_token(), Token::Comma)
         {
             columns.push(column);
@@ -264,6 +298,43 @@ impl<'a> ParserContext<'a> {
         self.parse_time_index(column, columns, constraints)
     }
 
+    /// Parses the time unit of `TIMESTAMP(unit)` and the column options after it, the unit
+    /// is one of `s`, `ms`, `us`, `ns` or the fractional seconds precision `0`, `3`, `6`, `9`.
+    fn parse_timestamp_unit(&mut self, column: &mut ColumnDef) -> Result<()> {
+        let token = self.parser.next_token();
+        let unit = match &token {
+            Token::Word(w) => match w.value.to_lowercase().as_str() {
+                "s" => TimeUnit::Second,
+                "ms" => TimeUnit::Millisecond,
+                "us" => TimeUnit::Microsecond,
+                "ns" => TimeUnit::Nanosecond,
+                _ => return self.expected("time unit s, ms, us or ns", token),
+            },
+            Token::Number(n, _) => match n.as_str() {
+                "0" => TimeUnit::Second,
+                "3" => TimeUnit::Millisecond,
+                "6" => TimeUnit::Microsecond,
+                "9" => TimeUnit::Nanosecond,
+                _ => return self.expected("timestamp precision 0, 3, 6 or 9", token),
+            },
+            _ => return self.expected("time unit", token),
+        };
+        self.parser
+            .expect_token(&Token::RParen)
+            .context(SyntaxSnafu { sql: self.sql })?;
+
+        column.data_type =
+            DataType::Custom(ObjectName(vec![Ident::new(timestamp_type_name(unit))]));
+        while let Some(option) = self
+            .parser
+            .parse_optional_column_option()
+            .context(SyntaxSnafu { sql: self.sql })?
+        {
+            column.options.push(ColumnOptionDef { name: None, option });
+        }
+        Ok(())
+    }
+
     fn parse_time_index(
         &mut self,
         mut column: ColumnDef,
@@ -585,6 +656,24 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_create_catalog() {
+        let sql = "create catalog team_a";
+        let stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+
+        assert_eq!(1, stmts.len());
+        match &stmts[0] {
+            Statement::CreateCatalog(c) => {
+                assert_eq!(c.name.to_string(), "team_a");
+            }
+            _ => unreachable!(),
+        }
+
+        let sql = "CREATE CATALOG";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {});
+        assert!(result.is_err());
+    }
+
     #[test]
     fn test_validate_create() {
         let sql = r"
@@ -840,6 +929,59 @@ ENGINE=mito";
         assert_ne!(result1, result3);
     }
 
+    #[test]
+    fn test_parse_create_table_with_timestamp_unit() {
+        let sql = r"
+CREATE TABLE monitor (
+  host       STRING,
+  ts         TIMESTAMP(ns) TIME INDEX,
+  t_s        TIMESTAMP(s) NOT NULL,
+  t_us       TIMESTAMP(6) DEFAULT 0,
+  t_ms       TimestampMillisecond,
+  PRIMARY KEY (host),
+)
+ENGINE=mito";
+        let result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        let c = match &result[0] {
+            Statement::CreateTable(c) => c,
+            _ => panic!("should be create table statement"),
+        };
+        let units = c
+            .columns
+            .iter()
+            .skip(1)
+            .map(|c| timestamp_unit_of(&c.data_type).unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                TimeUnit::Nanosecond,
+                TimeUnit::Second,
+                TimeUnit::Microsecond,
+                TimeUnit::Millisecond
+            ],
+            units
+        );
+        assert_eq!(c.columns[1].options[0].option, NotNull);
+        assert_eq!(c.columns[2].options[0].option, NotNull);
+        assert!(matches!(
+            c.columns[3].options[0].option,
+            sqlparser::ast::ColumnOption::Default(_)
+        ));
+        assert!(matches!(
+            &c.constraints[0],
+            TableConstraint::Unique { name: Some(name), columns, .. }
+                if name.value == TIME_INDEX && columns[0].value == "ts"
+        ));
+
+        for sql in [
+            "CREATE TABLE t (ts TIMESTAMP(min) TIME INDEX) ENGINE=mito",
+            "CREATE TABLE t (ts TIMESTAMP(2) TIME INDEX) ENGINE=mito",
+            "CREATE TABLE t (ts TIMESTAMP(ns TIME INDEX) ENGINE=mito",
+        ] {
+            assert!(ParserContext::create_with_dialect(sql, &GenericDialect {}).is_err());
+        }
+    }
+
     #[test]
     fn test_parse_create_table_with_timestamp_index_not_null() {
         let sql = r"
@@ -0,0 +1,73 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::ResultExt;
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::kill::{Kill, KillKind};
+use crate::statements::statement::Statement;
+
+impl<'a> ParserContext<'a> {
+    /// Parses `KILL [CONNECTION | QUERY] <id>`.
+    pub(crate) fn parse_kill(&mut self) -> Result<Statement> {
+        let _ = self.parser.next_token();
+        let kind = if self.consume_token("QUERY") {
+            KillKind::Query
+        } else {
+            let _ = self.consume_token("CONNECTION");
+            KillKind::Connection
+        };
+        let id = self
+            .parser
+            .parse_literal_uint()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(Statement::Kill(Kill { kind, id }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    fn parse_kill(sql: &str) -> Result<Kill> {
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {})?;
+        assert_eq!(1, result.len());
+        match result.remove(0) {
+            Statement::Kill(kill) => Ok(kill),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_kill() {
+        let kill = parse_kill("KILL 42").unwrap();
+        assert_eq!(KillKind::Connection, kill.kind);
+        assert_eq!(42, kill.id);
+
+        let kill = parse_kill("kill connection 7;").unwrap();
+        assert_eq!(KillKind::Connection, kill.kind);
+        assert_eq!(7, kill.id);
+
+        let kill = parse_kill("KILL QUERY 4294967296").unwrap();
+        assert_eq!(KillKind::Query, kill.kind);
+        assert_eq!(4294967296, kill.id);
+
+        assert!(parse_kill("KILL").is_err());
+        assert!(parse_kill("KILL QUERY abc").is_err());
+        assert!(parse_kill("KILL 1 2").is_err());
+    }
+}
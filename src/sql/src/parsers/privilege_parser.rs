@@ -0,0 +1,277 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_catalog::privilege::Privilege;
+use snafu::ResultExt;
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::privilege::{
+    CreateRole, CreateUser, Grant, GrantKind, GrantObjectName, Revoke,
+};
+use crate::statements::statement::Statement;
+
+/// Parses GRANT, REVOKE, CREATE USER and CREATE ROLE statements.
+/// "GRANT", "REVOKE", "USER", "ROLE" and "PRIVILEGES" are not keywords of sqlparser, so they are
+/// matched by their values.
+impl<'a> ParserContext<'a> {
+    pub(crate) fn parse_grant(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let (kind, grantee) = self
+            .parse_grant_or_revoke("TO")
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(Statement::Grant(Grant { kind, grantee }))
+    }
+
+    pub(crate) fn parse_revoke(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let (kind, grantee) = self
+            .parse_grant_or_revoke("FROM")
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(Statement::Revoke(Revoke { kind, grantee }))
+    }
+
+    pub(crate) fn parse_create_user(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let name = self
+            .parse_principal()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(Statement::CreateUser(CreateUser { name }))
+    }
+
+    pub(crate) fn parse_create_role(&mut self) -> Result<Statement> {
+        self.parser.next_token();
+        let name = self
+            .parse_principal()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(Statement::CreateRole(CreateRole { name }))
+    }
+
+    /// Parses the rest of `GRANT`/`REVOKE` after the leading keyword, `preposition` is `TO` for
+    /// GRANT and `FROM` for REVOKE.
+    fn parse_grant_or_revoke(
+        &mut self,
+        preposition: &str,
+    ) -> std::result::Result<(GrantKind, String), ParserError> {
+        let kind = if self.is_privilege_list() {
+            let privileges = self.parse_privileges()?;
+            self.parser.expect_keyword(Keyword::ON)?;
+            let object = self.parse_grant_object()?;
+            GrantKind::Privileges { privileges, object }
+        } else {
+            GrantKind::Role(self.parse_principal()?)
+        };
+
+        if !self.consume_token(preposition) {
+            return self.parser.expected(preposition, self.parser.peek_token());
+        }
+        let grantee = self.parse_principal()?;
+        Ok((kind, grantee))
+    }
+
+    fn is_privilege_list(&self) -> bool {
+        match self.parser.peek_token() {
+            Token::Word(w) => {
+                w.quote_style.is_none()
+                    && (w.keyword == Keyword::ALL || Privilege::from_name(&w.value).is_some())
+            }
+            _ => false,
+        }
+    }
+
+    fn parse_privileges(&mut self) -> std::result::Result<Vec<Privilege>, ParserError> {
+        if self.parser.parse_keyword(Keyword::ALL) {
+            let _ = self.consume_token("PRIVILEGES");
+            return Ok(Privilege::ALL.to_vec());
+        }
+
+        let mut privileges = Vec::new();
+        loop {
+            let privilege = match self.parser.next_token() {
+                Token::Word(w) => Privilege::from_name(&w.value),
+                _ => None,
+            };
+            match privilege {
+                Some(privilege) if !privileges.contains(&privilege) => privileges.push(privilege),
+                Some(_) => {}
+                None => {
+                    self.parser.prev_token();
+                    return self.parser.expected(
+                        "SELECT, INSERT, CREATE, DROP or ALTER",
+                        self.parser.peek_token(),
+                    );
+                }
+            }
+            if !self.parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        Ok(privileges)
+    }
+
+    /// Parses `*`, `*.*`, `schema.*`, `schema.table` or `table`.
+    fn parse_grant_object(&mut self) -> std::result::Result<GrantObjectName, ParserError> {
+        let first = self.parse_grant_object_part()?;
+        if self.parser.consume_token(&Token::Period) {
+            let table = self.parse_grant_object_part()?;
+            Ok(GrantObjectName {
+                schema: Some(first),
+                table,
+            })
+        } else {
+            Ok(GrantObjectName {
+                schema: None,
+                table: first,
+            })
+        }
+    }
+
+    fn parse_grant_object_part(&mut self) -> std::result::Result<String, ParserError> {
+        if self.parser.consume_token(&Token::Mul) {
+            Ok(Token::Mul.to_string())
+        } else {
+            Ok(self.parser.parse_identifier()?.value)
+        }
+    }
+
+    /// Parses a user or role name, which may be quoted like MySQL's `'user'`.
+    fn parse_principal(&mut self) -> std::result::Result<String, ParserError> {
+        match self.parser.next_token() {
+            Token::Word(w) => Ok(w.value),
+            Token::SingleQuotedString(s) => Ok(s),
+            unexpected => self.parser.expected("a user or role name", unexpected),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    fn parse(sql: &str) -> Statement {
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+        result.remove(0)
+    }
+
+    #[test]
+    fn test_parse_create_user_and_role() {
+        assert_eq!(
+            Statement::CreateUser(CreateUser {
+                name: "dashboard".to_string()
+            }),
+            parse("CREATE USER dashboard")
+        );
+        assert_eq!(
+            Statement::CreateUser(CreateUser {
+                name: "grafana".to_string()
+            }),
+            parse("create user 'grafana'")
+        );
+        assert_eq!(
+            Statement::CreateRole(CreateRole {
+                name: "reader".to_string()
+            }),
+            parse("CREATE ROLE reader")
+        );
+    }
+
+    #[test]
+    fn test_parse_grant() {
+        assert_eq!(
+            Statement::Grant(Grant {
+                kind: GrantKind::Privileges {
+                    privileges: vec![Privilege::Select, Privilege::Insert],
+                    object: GrantObjectName {
+                        schema: Some("public".to_string()),
+                        table: "cpu".to_string(),
+                    },
+                },
+                grantee: "writer".to_string(),
+            }),
+            parse("GRANT SELECT, insert ON public.cpu TO writer")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                kind: GrantKind::Privileges {
+                    privileges: Privilege::ALL.to_vec(),
+                    object: GrantObjectName {
+                        schema: Some("*".to_string()),
+                        table: "*".to_string(),
+                    },
+                },
+                grantee: "admin".to_string(),
+            }),
+            parse("GRANT ALL PRIVILEGES ON *.* TO admin")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                kind: GrantKind::Privileges {
+                    privileges: vec![Privilege::Select],
+                    object: GrantObjectName {
+                        schema: None,
+                        table: "*".to_string(),
+                    },
+                },
+                grantee: "reader".to_string(),
+            }),
+            parse("GRANT SELECT ON * TO reader")
+        );
+        assert_eq!(
+            Statement::Grant(Grant {
+                kind: GrantKind::Role("reader".to_string()),
+                grantee: "dashboard".to_string(),
+            }),
+            parse("GRANT reader TO dashboard")
+        );
+    }
+
+    #[test]
+    fn test_parse_revoke() {
+        assert_eq!(
+            Statement::Revoke(Revoke {
+                kind: GrantKind::Privileges {
+                    privileges: vec![Privilege::Drop],
+                    object: GrantObjectName {
+                        schema: Some("public".to_string()),
+                        table: "*".to_string(),
+                    },
+                },
+                grantee: "writer".to_string(),
+            }),
+            parse("REVOKE DROP ON public.* FROM writer")
+        );
+        assert_eq!(
+            Statement::Revoke(Revoke {
+                kind: GrantKind::Role("reader".to_string()),
+                grantee: "dashboard".to_string(),
+            }),
+            parse("REVOKE reader FROM dashboard")
+        );
+    }
+
+    #[test]
+    fn test_parse_grant_error() {
+        let parse = |sql| ParserContext::create_with_dialect(sql, &GenericDialect {});
+        assert!(parse("GRANT SELECT TO reader").is_err());
+        assert!(parse("GRANT SELECT, TRUNCATE ON cpu TO reader").is_err());
+        assert!(parse("GRANT SELECT ON cpu reader").is_err());
+        assert!(parse("REVOKE reader TO dashboard").is_err());
+    }
+}
@@ -12,30 +12,220 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use common_time::duration::parse_duration_nanos;
+use datatypes::value::OrderedF64;
 use snafu::prelude::*;
+use sqlparser::ast::Expr;
+use sqlparser::dialect::keywords::Keyword;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Token;
 
 use crate::error::{self, Result};
 use crate::parser::ParserContext;
-use crate::statements::query::Query;
+use crate::statements::query::{Fill, Query, RangeSelect};
 use crate::statements::statement::Statement;
 
 impl<'a> ParserContext<'a> {
     /// Parses select and it's variants.
     pub(crate) fn parse_query(&mut self) -> Result<Statement> {
-        let spquery = self
+        // sqlparser doesn't know the clauses of range queries, so they are taken out of the
+        // tokens of the statement and the rest is parsed by a new parser.
+        let mut tokens = Vec::new();
+        while !matches!(self.parser.peek_token(), Token::EOF | Token::SemiColon) {
+            tokens.push(self.parser.next_token());
+        }
+        let (tokens, range_select) = self.take_range_select(tokens)?;
+
+        let parser = std::mem::replace(&mut self.parser, Parser::new(tokens, self.dialect));
+        let query = self.parse_query_with_fill();
+        let rest = self.parser.peek_token();
+        self.parser = parser;
+
+        let mut query = query?;
+        if rest != Token::EOF {
+            return self.expected("end of statement", rest);
+        }
+        query.range_select = range_select;
+        Ok(Statement::Query(Box::new(query)))
+    }
+
+    fn parse_query_with_fill(&mut self) -> Result<Query> {
+        let mut spquery = self
             .parser
             .parse_query()
             .context(error::SyntaxSnafu { sql: self.sql })?;
 
-        Ok(Statement::Query(Box::new(Query::try_from(spquery)?)))
+        let fill = self.parse_fill()?;
+        if fill.is_some() {
+            // sqlparser stops at FILL, so the ORDER BY and LIMIT after it are parsed here.
+            if spquery.order_by.is_empty()
+                && self.parser.parse_keywords(&[Keyword::ORDER, Keyword::BY])
+            {
+                spquery.order_by = self
+                    .parser
+                    .parse_comma_separated(Parser::parse_order_by_expr)
+                    .context(error::SyntaxSnafu { sql: self.sql })?;
+            }
+            if spquery.limit.is_none() && self.parser.parse_keyword(Keyword::LIMIT) {
+                spquery.limit = self
+                    .parser
+                    .parse_limit()
+                    .context(error::SyntaxSnafu { sql: self.sql })?;
+            }
+        }
+
+        let mut query = Query::try_from(spquery)?;
+        query.fill = fill;
+        Ok(query)
+    }
+
+    /// Takes the clauses of a range query, `RANGE '<interval>'` after projection items and
+    /// `ALIGN '<interval>' [BY (<exprs>)]` after the FROM clause, out of the `tokens` of a query.
+    ///
+    /// Only the clauses of the outermost select are taken.
+    fn take_range_select(&self, tokens: Vec<Token>) -> Result<(Vec<Token>, Option<RangeSelect>)> {
+        let mut rest = Vec::with_capacity(tokens.len());
+        let mut ranges = Vec::new();
+        let mut align = None;
+        let mut by = Vec::new();
+
+        let (mut depth, mut item) = (0usize, 0usize);
+        let (mut seen_select, mut in_projection) = (false, false);
+        let mut tokens = tokens.into_iter().peekable();
+        while let Some(token) = tokens.next() {
+            match &token {
+                Token::LParen => depth += 1,
+                Token::RParen => depth = depth.saturating_sub(1),
+                Token::Comma if depth == 0 && in_projection => item += 1,
+                Token::Word(w) if depth == 0 => match w.keyword {
+                    Keyword::SELECT if !seen_select => {
+                        seen_select = true;
+                        in_projection = true;
+                    }
+                    Keyword::FROM => in_projection = false,
+                    Keyword::RANGE if in_projection => {
+                        if let Some(Token::SingleQuotedString(interval)) = tokens.peek() {
+                            ranges.push((item, parse_interval(interval)?));
+                            tokens.next();
+                            continue;
+                        }
+                    }
+                    _ if seen_select
+                        && !in_projection
+                        && w.value.eq_ignore_ascii_case("ALIGN")
+                        && matches!(tokens.peek(), Some(Token::SingleQuotedString(_))) =>
+                    {
+                        if let Some(Token::SingleQuotedString(interval)) = tokens.next() {
+                            align = Some(parse_interval(&interval)?);
+                        }
+                        if matches!(tokens.peek(), Some(Token::Word(w)) if w.keyword == Keyword::BY)
+                        {
+                            tokens.next();
+                            by = self.parse_align_by(&mut tokens)?;
+                        }
+                        continue;
+                    }
+                    _ => {}
+                },
+                _ => {}
+            }
+            rest.push(token);
+        }
+
+        match align {
+            Some(align) => Ok((rest, Some(RangeSelect { align, by, ranges }))),
+            None if ranges.is_empty() => Ok((rest, None)),
+            None => error::InvalidSqlSnafu {
+                msg: "RANGE requires an ALIGN clause",
+            }
+            .fail(),
+        }
+    }
+
+    /// Parses the parenthesized expressions after `ALIGN '<interval>' BY`.
+    fn parse_align_by(&self, tokens: &mut impl Iterator<Item = Token>) -> Result<Vec<Expr>> {
+        match tokens.next() {
+            Some(Token::LParen) => {}
+            token => return self.expected("( after ALIGN .. BY", token.unwrap_or(Token::EOF)),
+        }
+        let mut depth = 1;
+        let mut by_tokens = Vec::new();
+        loop {
+            let token = tokens.next().unwrap_or(Token::EOF);
+            match &token {
+                Token::EOF => return self.expected(")", token),
+                Token::LParen => depth += 1,
+                Token::RParen => {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                _ => {}
+            }
+            by_tokens.push(token);
+        }
+        if by_tokens.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut parser = Parser::new(by_tokens, self.dialect);
+        let by = parser
+            .parse_comma_separated(Parser::parse_expr)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        match parser.peek_token() {
+            Token::EOF => Ok(by),
+            token => self.expected("',' or ')' in ALIGN .. BY", token),
+        }
+    }
+
+    /// Parses an optional `FILL(NULL | PREVIOUS | LINEAR | <number>)` clause.
+    fn parse_fill(&mut self) -> Result<Option<Fill>> {
+        match self.parser.peek_token() {
+            Token::Word(w) if w.value.eq_ignore_ascii_case("FILL") => {
+                self.parser.next_token();
+            }
+            _ => return Ok(None),
+        }
+        self.parser
+            .expect_token(&Token::LParen)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+
+        let negative = self.parser.consume_token(&Token::Minus);
+        let token = self.parser.next_token();
+        let fill = match &token {
+            Token::Word(w) if !negative && w.keyword == Keyword::NULL => Fill::Null,
+            Token::Word(w) if !negative && w.value.eq_ignore_ascii_case("PREVIOUS") => {
+                Fill::Previous
+            }
+            Token::Word(w) if !negative && w.value.eq_ignore_ascii_case("LINEAR") => Fill::Linear,
+            Token::Number(n, _) => match n.parse::<f64>() {
+                Ok(v) if negative => Fill::Value(OrderedF64::from(-v)),
+                Ok(v) => Fill::Value(OrderedF64::from(v)),
+                Err(_) => return self.expected("a number", token),
+            },
+            _ => return self.expected("NULL, PREVIOUS, LINEAR or a number", token),
+        };
+        self.parser
+            .expect_token(&Token::RParen)
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(Some(fill))
     }
 }
 
+fn parse_interval(interval: &str) -> Result<i64> {
+    parse_duration_nanos(interval).context(error::InvalidIntervalSnafu { interval })
+}
+
 #[cfg(test)]
 mod tests {
+    use datatypes::value::OrderedF64;
+    use sqlparser::ast::{Expr, Ident};
     use sqlparser::dialect::GenericDialect;
 
     use crate::parser::ParserContext;
+    use crate::statements::query::{Fill, Query, RangeSelect};
+    use crate::statements::statement::Statement;
 
     #[test]
     pub fn test_parse_query() {
@@ -57,4 +247,92 @@ mod tests {
             .to_string()
             .contains("Expected an expression"));
     }
+
+    fn parse_query(sql: &str) -> Result<Query, String> {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {})
+            .map_err(|e| e.to_string())?;
+        match stmts.remove(0) {
+            Statement::Query(query) => Ok(*query),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    pub fn test_parse_fill() {
+        let query = parse_query(
+            "SELECT time_bucket('1m', ts) AS t, host, avg(cpu) FROM monitor \
+             GROUP BY t, host FILL(PREVIOUS) ORDER BY t LIMIT 10",
+        )
+        .unwrap();
+        assert_eq!(Some(Fill::Previous), query.fill);
+        assert_eq!(1, query.inner.order_by.len());
+        assert!(query.inner.limit.is_some());
+
+        let query = parse_query("SELECT a FROM t GROUP BY a ORDER BY a FILL(linear)").unwrap();
+        assert_eq!(Some(Fill::Linear), query.fill);
+        assert_eq!(1, query.inner.order_by.len());
+
+        let query = parse_query("SELECT a FROM t GROUP BY a FILL(null)").unwrap();
+        assert_eq!(Some(Fill::Null), query.fill);
+        let query = parse_query("SELECT a FROM t GROUP BY a FILL(-1.5)").unwrap();
+        assert_eq!(Some(Fill::Value(OrderedF64::from(-1.5))), query.fill);
+        let query = parse_query("SELECT a FROM t GROUP BY a").unwrap();
+        assert_eq!(None, query.fill);
+
+        let err = parse_query("SELECT a FROM t GROUP BY a FILL(next)").unwrap_err();
+        assert!(
+            err.contains("Expected NULL, PREVIOUS, LINEAR or a number"),
+            "{}",
+            err
+        );
+        assert!(parse_query("SELECT a FROM t GROUP BY a FILL(-null)").is_err());
+        assert!(parse_query("SELECT a FROM t GROUP BY a FILL(null").is_err());
+    }
+
+    #[test]
+    pub fn test_parse_range_select() {
+        let query = parse_query(
+            "SELECT ts, host, avg(cpu) RANGE '5m' AS c, max(memory) RANGE '10m' FROM monitor \
+             WHERE host != 'x' ALIGN '1m' BY (host) ORDER BY ts LIMIT 5",
+        )
+        .unwrap();
+        let minute = 60_000_000_000;
+        assert_eq!(
+            Some(RangeSelect {
+                align: minute,
+                by: vec![Expr::Identifier(Ident::new("host"))],
+                ranges: vec![(2, 5 * minute), (3, 10 * minute)],
+            }),
+            query.range_select
+        );
+        assert_eq!(
+            "SELECT ts, host, avg(cpu) AS c, max(memory) FROM monitor WHERE host <> 'x' \
+             ORDER BY ts LIMIT 5",
+            query.inner.to_string()
+        );
+
+        // Aggregates without RANGE and ALIGN without BY.
+        let query = parse_query("SELECT ts, count(*) FROM monitor ALIGN '30s'").unwrap();
+        assert_eq!(
+            Some(RangeSelect {
+                align: 30_000_000_000,
+                by: vec![],
+                ranges: vec![],
+            }),
+            query.range_select
+        );
+
+        // RANGE and ALIGN in subqueries are not range query clauses.
+        let query =
+            parse_query("SELECT align FROM (SELECT a AS align FROM t) WHERE align > 1").unwrap();
+        assert_eq!(None, query.range_select);
+
+        let err = parse_query("SELECT avg(cpu) RANGE '5m' FROM monitor").unwrap_err();
+        assert!(err.contains("RANGE requires an ALIGN clause"), "{}", err);
+        let err = parse_query("SELECT avg(cpu) FROM monitor ALIGN '1x'").unwrap_err();
+        assert!(err.contains("Invalid interval: 1x"), "{}", err);
+        assert!(parse_query("SELECT avg(cpu) FROM monitor ALIGN '1m' BY (host").is_err());
+        assert!(parse_query("SELECT avg(cpu) FROM monitor ALIGN '1m' BY (host a)").is_err());
+        assert!(parse_query("SELECT avg(cpu) FROM monitor ALIGN '1m' LIMIT 1 a").is_err());
+    }
 }
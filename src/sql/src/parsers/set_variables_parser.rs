@@ -0,0 +1,136 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use snafu::ResultExt;
+use sqlparser::ast::{Expr, Value};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::ParserError;
+use sqlparser::tokenizer::Token;
+
+use crate::error::{self, Result};
+use crate::parser::ParserContext;
+use crate::statements::set_variables::SetVariables;
+use crate::statements::statement::Statement;
+
+impl<'a> ParserContext<'a> {
+    pub(crate) fn parse_set_variables(&mut self) -> Result<Statement> {
+        let set_variables = self
+            .parse_set()
+            .context(error::SyntaxSnafu { sql: self.sql })?;
+        Ok(Statement::SetVariables(set_variables))
+    }
+
+    fn parse_set(&mut self) -> std::result::Result<SetVariables, ParserError> {
+        let parser = &mut self.parser;
+        parser.expect_keyword(Keyword::SET)?;
+        let _ = parser.parse_one_of_keywords(&[Keyword::SESSION, Keyword::LOCAL]);
+
+        // Postgres style `SET TIME ZONE value`.
+        if parser.parse_keywords(&[Keyword::TIME, Keyword::ZONE]) {
+            let value = expr_to_variable_value(parser.parse_expr()?);
+            return Ok(SetVariables {
+                variables: vec![("time_zone".to_string(), value)],
+            });
+        }
+
+        let mut variables = Vec::new();
+        loop {
+            let name = parser.parse_object_name()?;
+            let name = name
+                .0
+                .last()
+                .map(|ident| normalize_variable_name(&ident.value))
+                .unwrap_or_default();
+            if !parser.consume_token(&Token::Eq) && !parser.parse_keyword(Keyword::TO) {
+                return parser.expected("= or TO", parser.peek_token());
+            }
+            let value = expr_to_variable_value(parser.parse_expr()?);
+            variables.push((name, value));
+
+            if !parser.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        Ok(SetVariables { variables })
+    }
+}
+
+/// Strips MySQL's `@@` prefix and maps the Postgres name `timezone` to `time_zone`.
+fn normalize_variable_name(name: &str) -> String {
+    let name = name.trim_start_matches('@').to_lowercase();
+    if name == "timezone" {
+        "time_zone".to_string()
+    } else {
+        name
+    }
+}
+
+fn expr_to_variable_value(expr: Expr) -> String {
+    match expr {
+        Expr::Value(Value::SingleQuotedString(s))
+        | Expr::Value(Value::DoubleQuotedString(s))
+        | Expr::Value(Value::Number(s, _)) => s,
+        Expr::Identifier(ident) => ident.value,
+        expr => expr.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use super::*;
+
+    fn parse_set(sql: &str) -> Vec<(String, String)> {
+        let mut result = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        assert_eq!(1, result.len());
+        match result.remove(0) {
+            Statement::SetVariables(set) => set.variables,
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_parse_set_variables() {
+        assert_eq!(
+            vec![("time_zone".to_string(), "+08:00".to_string())],
+            parse_set("SET time_zone = '+08:00'")
+        );
+        assert_eq!(
+            vec![("time_zone".to_string(), "UTC".to_string())],
+            parse_set("SET @@session.time_zone = 'UTC'")
+        );
+        assert_eq!(
+            vec![
+                ("autocommit".to_string(), "1".to_string()),
+                ("foo".to_string(), "bar".to_string())
+            ],
+            parse_set("SET SESSION @@autocommit = 1, Foo TO bar")
+        );
+        assert_eq!(
+            vec![("time_zone".to_string(), "Asia/Shanghai".to_string())],
+            parse_set("SET TIME ZONE 'Asia/Shanghai'")
+        );
+        assert_eq!(
+            vec![("time_zone".to_string(), "UTC".to_string())],
+            parse_set("SET timezone TO 'UTC'")
+        );
+    }
+
+    #[test]
+    fn test_parse_set_variables_error() {
+        assert!(ParserContext::create_with_dialect("SET foo", &GenericDialect {}).is_err());
+        assert!(ParserContext::create_with_dialect("SET foo = ", &GenericDialect {}).is_err());
+    }
+}
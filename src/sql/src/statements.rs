@@ -18,23 +18,30 @@ pub mod describe;
 pub mod drop;
 pub mod explain;
 pub mod insert;
+pub mod kill;
+pub mod privilege;
 pub mod query;
+pub mod set_variables;
 pub mod show;
 pub mod statement;
 
+use std::collections::HashMap;
 use std::str::FromStr;
 
 use api::helper::ColumnDataTypeWrapper;
 use common_catalog::consts::{DEFAULT_CATALOG_NAME, DEFAULT_SCHEMA_NAME};
-use common_time::Timestamp;
+use common_time::timestamp::TimeUnit;
+use common_time::{TimeZone, Timestamp};
+use datatypes::decimal::{Decimal128, DECIMAL128_DEFAULT_PRECISION, DECIMAL128_DEFAULT_SCALE};
 use datatypes::prelude::ConcreteDataType;
 use datatypes::schema::{ColumnDefaultConstraint, ColumnSchema};
-use datatypes::types::DateTimeType;
+use datatypes::types::{DateTimeType, Decimal128Type, JsonType};
 use datatypes::value::Value;
+use session::context::QueryContext;
 use snafu::{ensure, ResultExt};
 
 use crate::ast::{
-    ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, Expr, ObjectName,
+    ColumnDef, ColumnOption, ColumnOptionDef, DataType as SqlDataType, Expr, ObjectName, SqlOption,
     Value as SqlValue,
 };
 use crate::error::{
@@ -47,17 +54,29 @@ use crate::error::{
 /// Converts maybe fully-qualified table name (`<catalog>.<schema>.<table>` or `<table>` when
 /// catalog and schema are default) to tuple.
 pub fn table_idents_to_full_name(obj_name: &ObjectName) -> Result<(String, String, String)> {
+    table_idents_to_full_name_with_ctx(obj_name, &QueryContext::new())
+}
+
+/// Converts maybe fully-qualified table name to tuple, the omitted catalog and schema are the
+/// current ones of the session, or the default ones if the session hasn't chosen any.
+pub fn table_idents_to_full_name_with_ctx(
+    obj_name: &ObjectName,
+    query_ctx: &QueryContext,
+) -> Result<(String, String, String)> {
+    let catalog = || {
+        query_ctx
+            .current_catalog()
+            .unwrap_or_else(|| DEFAULT_CATALOG_NAME.to_string())
+    };
     match &obj_name.0[..] {
         [table] => Ok((
-            DEFAULT_CATALOG_NAME.to_string(),
-            DEFAULT_SCHEMA_NAME.to_string(),
-            table.value.clone(),
-        )),
-        [schema, table] => Ok((
-            DEFAULT_CATALOG_NAME.to_string(),
-            schema.value.clone(),
+            catalog(),
+            query_ctx
+                .current_schema()
+                .unwrap_or_else(|| DEFAULT_SCHEMA_NAME.to_string()),
             table.value.clone(),
         )),
+        [schema, table] => Ok((catalog(), schema.value.clone(), table.value.clone())),
         [catalog, schema, table] => Ok((
             catalog.value.clone(),
             schema.value.clone(),
@@ -77,6 +96,7 @@ fn parse_string_to_value(
     column_name: &str,
     s: String,
     data_type: &ConcreteDataType,
+    time_zone: Option<&TimeZone>,
 ) -> Result<Value> {
     ensure!(
         data_type.stringifiable(),
@@ -109,8 +129,15 @@ fn parse_string_to_value(
                 .fail()
             }
         }
+        ConcreteDataType::Decimal128(t) => parse_sql_decimal(&s, t),
+        ConcreteDataType::Json(_) => s.parse().map(Value::Json).map_err(|e| {
+            ParseSqlValueSnafu {
+                msg: format!("Failed to parse {} to Json value, {}", s, e),
+            }
+            .build()
+        }),
         ConcreteDataType::Timestamp(t) => {
-            if let Ok(ts) = Timestamp::from_str(&s) {
+            if let Ok(ts) = Timestamp::from_str_with_time_zone(&s, time_zone) {
                 Ok(Value::Timestamp(Timestamp::new(
                     ts.convert_to(t.unit),
                     t.unit,
@@ -147,6 +174,10 @@ macro_rules! parse_number_to_value {
 
 /// Convert a sql value into datatype's value
 pub fn sql_number_to_value(data_type: &ConcreteDataType, n: &str) -> Result<Value> {
+    if let ConcreteDataType::Decimal128(t) = data_type {
+        return parse_sql_decimal(n, t);
+    }
+
     parse_number_to_value!(
         data_type,
         n,
@@ -178,10 +209,32 @@ where
     }
 }
 
+fn parse_sql_decimal(s: &str, t: &Decimal128Type) -> Result<Value> {
+    Decimal128::from_str_with(s, t.precision, t.scale)
+        .map(Value::Decimal128)
+        .map_err(|e| {
+            ParseSqlValueSnafu {
+                msg: format!("Fail to parse decimal {}, {}", s, e),
+            }
+            .build()
+        })
+}
+
 pub fn sql_value_to_value(
     column_name: &str,
     data_type: &ConcreteDataType,
     sql_val: &SqlValue,
+) -> Result<Value> {
+    sql_value_to_value_with_time_zone(column_name, data_type, sql_val, None)
+}
+
+/// Same as [sql_value_to_value], but timestamp strings without an explicit offset
+/// are regarded as in `time_zone` (the session time zone) instead of the local time zone.
+pub fn sql_value_to_value_with_time_zone(
+    column_name: &str,
+    data_type: &ConcreteDataType,
+    sql_val: &SqlValue,
+    time_zone: Option<&TimeZone>,
 ) -> Result<Value> {
     Ok(match sql_val {
         SqlValue::Number(n, _) => sql_number_to_value(data_type, n)?,
@@ -199,7 +252,7 @@ pub fn sql_value_to_value(
             (*b).into()
         }
         SqlValue::DoubleQuotedString(s) | SqlValue::SingleQuotedString(s) => {
-            parse_string_to_value(column_name, s.to_owned(), data_type)?
+            parse_string_to_value(column_name, s.to_owned(), data_type, time_zone)?
         }
         _ => todo!("Other sql value"),
     })
@@ -260,6 +313,20 @@ pub fn column_def_to_schema(column_def: &ColumnDef, is_time_index: bool) -> Resu
         })
 }
 
+/// Converts sql options like `(k1 = 'v1', k2 = 2)` to a string map, quoted values are unquoted.
+pub fn sql_options_to_map(options: &[SqlOption]) -> HashMap<String, String> {
+    options
+        .iter()
+        .map(|option| {
+            let value = match &option.value {
+                SqlValue::SingleQuotedString(s) | SqlValue::DoubleQuotedString(s) => s.clone(),
+                v => v.to_string(),
+            };
+            (option.name.value.clone(), value)
+        })
+        .collect()
+}
+
 /// Convert `ColumnDef` in sqlparser to `ColumnDef` in gRPC proto.
 pub fn sql_column_def_to_grpc_column_def(col: ColumnDef) -> Result<api::v1::ColumnDef> {
     let name = col.name.value.clone();
@@ -275,18 +342,55 @@ pub fn sql_column_def_to_grpc_column_def(col: ColumnDef) -> Result<api::v1::Colu
         .transpose()
         .context(SerializeColumnDefaultConstraintSnafu)?;
 
-    let data_type = ColumnDataTypeWrapper::try_from(data_type)
-        .context(ConvertToGrpcDataTypeSnafu)?
-        .datatype() as i32;
+    let data_type =
+        ColumnDataTypeWrapper::try_from(data_type).context(ConvertToGrpcDataTypeSnafu)?;
     Ok(api::v1::ColumnDef {
         name,
-        datatype: data_type,
+        datatype: data_type.datatype() as i32,
         is_nullable: nullable,
         default_constraint,
+        datatype_extension: data_type.datatype_extension(),
     })
 }
 
+/// Names of timestamp types with an explicit time unit, `TIMESTAMP(s|ms|us|ns)` in DDL is
+/// parsed into these custom types.
+const TIMESTAMP_TYPE_NAMES: [(&str, TimeUnit); 4] = [
+    ("TimestampSecond", TimeUnit::Second),
+    ("TimestampMillisecond", TimeUnit::Millisecond),
+    ("TimestampMicrosecond", TimeUnit::Microsecond),
+    ("TimestampNanosecond", TimeUnit::Nanosecond),
+];
+
+/// Returns the name of the timestamp type in `unit`.
+pub(crate) fn timestamp_type_name(unit: TimeUnit) -> &'static str {
+    TIMESTAMP_TYPE_NAMES
+        .iter()
+        .find(|(_, u)| *u == unit)
+        .map(|(name, _)| *name)
+        .unwrap()
+}
+
+/// Returns the time unit of the sql data type if it's a timestamp type.
+pub(crate) fn timestamp_unit_of(data_type: &SqlDataType) -> Option<TimeUnit> {
+    match data_type {
+        SqlDataType::Timestamp => Some(TimeUnit::Millisecond),
+        SqlDataType::Custom(obj_name) => match &obj_name.0[..] {
+            [type_name] => TIMESTAMP_TYPE_NAMES
+                .iter()
+                .find(|(name, _)| type_name.value.eq_ignore_ascii_case(name))
+                .map(|(_, unit)| *unit),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
 pub fn sql_data_type_to_concrete_data_type(data_type: &SqlDataType) -> Result<ConcreteDataType> {
+    if let Some(unit) = timestamp_unit_of(data_type) {
+        return Ok(ConcreteDataType::timestamp_datatype(unit));
+    }
+
     match data_type {
         SqlDataType::BigInt(_) => Ok(ConcreteDataType::int64_datatype()),
         SqlDataType::Int(_) => Ok(ConcreteDataType::int32_datatype()),
@@ -303,6 +407,8 @@ pub fn sql_data_type_to_concrete_data_type(data_type: &SqlDataType) -> Result<Co
             [type_name] => {
                 if type_name.value.eq_ignore_ascii_case(DateTimeType::name()) {
                     Ok(ConcreteDataType::datetime_datatype())
+                } else if type_name.value.eq_ignore_ascii_case(JsonType::name()) {
+                    Ok(ConcreteDataType::json_datatype())
                 } else {
                     error::SqlTypeNotSupportedSnafu {
                         t: data_type.clone(),
@@ -315,7 +421,14 @@ pub fn sql_data_type_to_concrete_data_type(data_type: &SqlDataType) -> Result<Co
             }
             .fail(),
         },
-        SqlDataType::Timestamp => Ok(ConcreteDataType::timestamp_millis_datatype()),
+        SqlDataType::Decimal(precision, scale) => {
+            let to_u8 = |v: u64| u8::try_from(v).unwrap_or(u8::MAX);
+            let precision = precision.map(to_u8).unwrap_or(DECIMAL128_DEFAULT_PRECISION);
+            let scale = scale.map(to_u8).unwrap_or(DECIMAL128_DEFAULT_SCALE);
+            Decimal128Type::try_new(precision, scale)
+                .map(ConcreteDataType::Decimal128)
+                .context(error::InvalidDecimalTypeSnafu)
+        }
         _ => error::SqlTypeNotSupportedSnafu {
             t: data_type.clone(),
         }
@@ -372,10 +485,36 @@ mod tests {
             SqlDataType::Custom(ObjectName(vec![Ident::new("datetime")])),
             ConcreteDataType::datetime_datatype(),
         );
+        check_type(
+            SqlDataType::Custom(ObjectName(vec![Ident::new("JSON")])),
+            ConcreteDataType::json_datatype(),
+        );
         check_type(
             SqlDataType::Timestamp,
             ConcreteDataType::timestamp_millis_datatype(),
         );
+        check_type(
+            SqlDataType::Custom(ObjectName(vec![Ident::new("TimestampSecond")])),
+            ConcreteDataType::timestamp_datatype(TimeUnit::Second),
+        );
+        check_type(
+            SqlDataType::Custom(ObjectName(vec![Ident::new("timestampnanosecond")])),
+            ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond),
+        );
+        check_type(
+            SqlDataType::Decimal(None, None),
+            ConcreteDataType::decimal128_datatype(10, 0),
+        );
+        check_type(
+            SqlDataType::Decimal(Some(20), Some(4)),
+            ConcreteDataType::decimal128_datatype(20, 4),
+        );
+        assert!(
+            sql_data_type_to_concrete_data_type(&SqlDataType::Decimal(Some(39), None)).is_err()
+        );
+        assert!(
+            sql_data_type_to_concrete_data_type(&SqlDataType::Decimal(Some(5), Some(6))).is_err()
+        );
     }
 
     #[test]
@@ -388,6 +527,43 @@ mod tests {
 
         let v = sql_number_to_value(&ConcreteDataType::string_datatype(), "999");
         assert!(v.is_err(), "parse value error is: {:?}", v);
+
+        let v =
+            sql_number_to_value(&ConcreteDataType::decimal128_datatype(10, 2), "3.145").unwrap();
+        assert_eq!(Value::Decimal128(Decimal128::new(315, 10, 2)), v);
+
+        let v = sql_number_to_value(&ConcreteDataType::decimal128_datatype(3, 2), "10.5");
+        assert!(v.is_err(), "parse value error is: {:?}", v);
+    }
+
+    #[test]
+    fn test_parse_decimal_literal() {
+        let value = sql_value_to_value(
+            "price",
+            &ConcreteDataType::decimal128_datatype(10, 2),
+            &SqlValue::SingleQuotedString("-12.3".to_string()),
+        )
+        .unwrap();
+        assert_eq!(Value::Decimal128(Decimal128::new(-1230, 10, 2)), value);
+        assert_eq!("-12.30", value.to_string());
+    }
+
+    #[test]
+    fn test_parse_json_literal() {
+        let value = sql_value_to_value(
+            "attrs",
+            &ConcreteDataType::json_datatype(),
+            &SqlValue::SingleQuotedString(r#"{"a": {"b": "c"}}"#.to_string()),
+        )
+        .unwrap();
+        assert_eq!(r#"{"a":{"b":"c"}}"#, value.to_string());
+
+        assert!(sql_value_to_value(
+            "attrs",
+            &ConcreteDataType::json_datatype(),
+            &SqlValue::SingleQuotedString("{".to_string()),
+        )
+        .is_err());
     }
 
     #[test]
@@ -472,6 +648,7 @@ mod tests {
             "timestamp_col",
             "2022-02-22T00:01:01+08:00".to_string(),
             &ConcreteDataType::timestamp_millis_datatype(),
+            None,
         )
         .unwrap()
         {
@@ -488,6 +665,7 @@ mod tests {
             "timestamp_col",
             "2022-02-22T00:01:01+08:00".to_string(),
             &ConcreteDataType::timestamp_datatype(TimeUnit::Second),
+            None,
         )
         .unwrap()
         {
@@ -504,6 +682,7 @@ mod tests {
             "timestamp_col",
             "2022-02-22T00:01:01+08:00".to_string(),
             &ConcreteDataType::timestamp_datatype(TimeUnit::Microsecond),
+            None,
         )
         .unwrap()
         {
@@ -520,6 +699,7 @@ mod tests {
             "timestamp_col",
             "2022-02-22T00:01:01+08:00".to_string(),
             &ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond),
+            None,
         )
         .unwrap()
         {
@@ -536,6 +716,7 @@ mod tests {
             "timestamp_col",
             "2022-02-22T00:01:01+08".to_string(),
             &ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond),
+            None,
         )
         .is_err());
     }
@@ -596,4 +777,22 @@ mod tests {
         let grpc_column_def = sql_column_def_to_grpc_column_def(column_def).unwrap();
         assert!(!grpc_column_def.is_nullable);
     }
+
+    #[test]
+    pub fn test_sql_options_to_map() {
+        let options = vec![
+            SqlOption {
+                name: "ttl".into(),
+                value: SqlValue::SingleQuotedString("7d".to_string()),
+            },
+            SqlOption {
+                name: "write_buffer_size".into(),
+                value: SqlValue::Number("1024".to_string(), false),
+            },
+        ];
+        let map = sql_options_to_map(&options);
+        assert_eq!(2, map.len());
+        assert_eq!("7d", map["ttl"]);
+        assert_eq!("1024", map["write_buffer_size"]);
+    }
 }
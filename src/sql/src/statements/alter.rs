@@ -12,11 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use api::v1::{alter_expr, AddColumn, AlterExpr, DropColumn};
-use sqlparser::ast::{ColumnDef, Ident, ObjectName, TableConstraint};
+use api::helper::ColumnDataTypeWrapper;
+use api::v1::{alter_expr, AddColumn, AlterExpr, DropColumn, ModifyColumn};
+use snafu::ResultExt;
+use sqlparser::ast::{ColumnDef, DataType, Ident, ObjectName, SqlOption, TableConstraint};
 
-use crate::error::UnsupportedAlterTableStatementSnafu;
-use crate::statements::{sql_column_def_to_grpc_column_def, table_idents_to_full_name};
+use crate::error::{ConvertToGrpcDataTypeSnafu, UnsupportedAlterTableStatementSnafu};
+use crate::statements::{
+    sql_column_def_to_grpc_column_def, sql_data_type_to_concrete_data_type, sql_options_to_map,
+    table_idents_to_full_name,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct AlterTable {
@@ -49,6 +54,15 @@ pub enum AlterTableOperation {
     AddColumn { column_def: ColumnDef },
     /// `DROP COLUMN <name>`
     DropColumn { name: Ident },
+    /// `RENAME TO <new_table_name>`
+    RenameTable { new_table_name: Ident },
+    /// `MODIFY [ COLUMN ] <column_name> <data_type>`
+    ModifyColumn {
+        column_name: Ident,
+        data_type: DataType,
+    },
+    /// `SET ( <name> = <value> [, ...] )`
+    SetTableOptions { options: Vec<SqlOption> },
 }
 
 /// Convert `AlterTable` statement to `AlterExpr` for gRPC
@@ -78,6 +92,31 @@ impl TryFrom<AlterTable> for AlterExpr {
                     drop_columns: vec![DropColumn { name: name.value }],
                 })
             }
+            AlterTableOperation::RenameTable { new_table_name } => {
+                alter_expr::Kind::RenameTable(api::v1::RenameTable {
+                    new_table_name: new_table_name.value,
+                })
+            }
+            AlterTableOperation::ModifyColumn {
+                column_name,
+                data_type,
+            } => {
+                let data_type = sql_data_type_to_concrete_data_type(&data_type)?;
+                let datatype = ColumnDataTypeWrapper::try_from(data_type)
+                    .context(ConvertToGrpcDataTypeSnafu)?;
+                alter_expr::Kind::ModifyColumns(api::v1::ModifyColumns {
+                    modify_columns: vec![ModifyColumn {
+                        name: column_name.value,
+                        datatype: datatype.datatype() as i32,
+                        datatype_extension: datatype.datatype_extension(),
+                    }],
+                })
+            }
+            AlterTableOperation::SetTableOptions { options } => {
+                alter_expr::Kind::SetTableOptions(api::v1::SetTableOptions {
+                    table_options: sql_options_to_map(&options),
+                })
+            }
         };
         let expr = AlterExpr {
             catalog_name: Some(catalog),
@@ -48,3 +48,9 @@ pub struct PartitionEntry {
 pub struct CreateDatabase {
     pub name: ObjectName,
 }
+
+/// CREATE CATALOG statement, a catalog holds all schemas of a tenant.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct CreateCatalog {
+    pub name: ObjectName,
+}
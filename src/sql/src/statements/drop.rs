@@ -30,3 +30,9 @@ impl DropTable {
         }
     }
 }
+
+/// DROP CATALOG statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DropCatalog {
+    pub name: String,
+}
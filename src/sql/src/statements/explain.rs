@@ -12,9 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sqlparser::ast::Statement as SpStatement;
+use sqlparser::ast::{ObjectName, Statement as SpStatement};
 
 use crate::error::Error;
+use crate::statements::query::TableNameCollector;
 
 /// Explain statement.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -22,6 +23,15 @@ pub struct Explain {
     pub inner: SpStatement,
 }
 
+impl Explain {
+    /// Returns names of tables read by the explained statement.
+    pub fn table_names(&self) -> Vec<ObjectName> {
+        let mut collector = TableNameCollector::default();
+        collector.visit_statement(&self.inner);
+        collector.tables
+    }
+}
+
 impl TryFrom<SpStatement> for Explain {
     type Error = Error;
 
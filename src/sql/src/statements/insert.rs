@@ -12,12 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use session::context::QueryContext;
 use sqlparser::ast::{ObjectName, SetExpr, Statement, UnaryOperator, Values};
 use sqlparser::parser::ParserError;
 
 use crate::ast::{Expr, Value};
 use crate::error::{self, Result};
-use crate::statements::table_idents_to_full_name;
+use crate::statements::table_idents_to_full_name_with_ctx;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Insert {
@@ -26,9 +27,13 @@ pub struct Insert {
 }
 
 impl Insert {
-    pub fn full_table_name(&self) -> Result<(String, String, String)> {
+    /// Returns the full name of the table to insert, resolved against the catalog and schema of
+    /// the session.
+    pub fn full_table_name(&self, query_ctx: &QueryContext) -> Result<(String, String, String)> {
         match &self.inner {
-            Statement::Insert { table_name, .. } => table_idents_to_full_name(table_name),
+            Statement::Insert { table_name, .. } => {
+                table_idents_to_full_name_with_ctx(table_name, query_ctx)
+            }
             _ => unreachable!(),
         }
     }
@@ -147,4 +152,51 @@ mod tests {
             _ => unreachable!(),
         }
     }
+
+    #[test]
+    fn test_full_table_name() {
+        use crate::statements::statement::Statement;
+
+        let insert = |sql| match ParserContext::create_with_dialect(sql, &GenericDialect {})
+            .unwrap()
+            .remove(0)
+        {
+            Statement::Insert(insert) => insert,
+            _ => unreachable!(),
+        };
+        let query_ctx = QueryContext::new();
+        query_ctx.set_current_catalog("tenant");
+        query_ctx.set_current_schema("my_schema");
+
+        assert_eq!(
+            (
+                "tenant".to_string(),
+                "my_schema".to_string(),
+                "my_table".to_string()
+            ),
+            insert("INSERT INTO my_table VALUES(1)")
+                .full_table_name(&query_ctx)
+                .unwrap()
+        );
+        assert_eq!(
+            (
+                "tenant".to_string(),
+                "public".to_string(),
+                "my_table".to_string()
+            ),
+            insert("INSERT INTO public.my_table VALUES(1)")
+                .full_table_name(&query_ctx)
+                .unwrap()
+        );
+        assert_eq!(
+            (
+                "greptime".to_string(),
+                "public".to_string(),
+                "my_table".to_string()
+            ),
+            insert("INSERT INTO my_table VALUES(1)")
+                .full_table_name(&QueryContext::new())
+                .unwrap()
+        );
+    }
 }
@@ -0,0 +1,69 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_catalog::privilege::{GrantObject, Privilege};
+
+/// CREATE USER statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateUser {
+    pub name: String,
+}
+
+/// CREATE ROLE statement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateRole {
+    pub name: String,
+}
+
+/// Tables in `ON <object>` of GRANT and REVOKE, names are `*` for all schemas or tables.
+/// The schema is `None` if the object is a bare table name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrantObjectName {
+    pub schema: Option<String>,
+    pub table: String,
+}
+
+impl GrantObjectName {
+    /// Resolves the object, a bare table name refers to the table in `current_schema`.
+    pub fn resolve(&self, current_schema: &str) -> GrantObject {
+        let schema = self.schema.as_deref().unwrap_or(current_schema);
+        GrantObject::new(schema, &self.table)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GrantKind {
+    /// `<privileges> ON <object>`, granted to or revoked from a role.
+    Privileges {
+        privileges: Vec<Privilege>,
+        object: GrantObjectName,
+    },
+    /// A role granted to or revoked from a user.
+    Role(String),
+}
+
+/// GRANT statement: `GRANT <privileges> ON <object> TO <role>` or `GRANT <role> TO <user>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grant {
+    pub kind: GrantKind,
+    pub grantee: String,
+}
+
+/// REVOKE statement: `REVOKE <privileges> ON <object> FROM <role>` or
+/// `REVOKE <role> FROM <user>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Revoke {
+    pub kind: GrantKind,
+    pub grantee: String,
+}
@@ -12,7 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use sqlparser::ast::Query as SpQuery;
+use std::collections::HashSet;
+use std::fmt;
+
+use datatypes::value::OrderedF64;
+use sqlparser::ast::{
+    Expr, FunctionArg, FunctionArgExpr, ObjectName, Query as SpQuery, SelectItem, SetExpr,
+    Statement as SpStatement, TableFactor, TableWithJoins,
+};
 
 use crate::error::Error;
 
@@ -20,6 +27,265 @@ use crate::error::Error;
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Query {
     pub inner: SpQuery,
+    /// How to fill missing time buckets, set by a `FILL(..)` clause after `GROUP BY`.
+    pub fill: Option<Fill>,
+    /// Set if this is a range query with an `ALIGN` clause.
+    pub range_select: Option<RangeSelect>,
+}
+
+/// Clauses of a range query like
+/// `SELECT ts, host, avg(cpu) RANGE '5m' FROM monitor ALIGN '1m' BY (host)`, which evaluates
+/// aggregates over sliding windows of each series.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeSelect {
+    /// Interval in nanoseconds between the timestamps of the output rows, from `ALIGN`.
+    pub align: i64,
+    /// Expressions that identify a series, from `ALIGN .. BY (..)`.
+    pub by: Vec<Expr>,
+    /// Window lengths in nanoseconds from `RANGE`, keyed by the index of the projection item.
+    ///
+    /// Aggregates without `RANGE` use the align interval as the window length.
+    pub ranges: Vec<(usize, i64)>,
+}
+
+/// Strategy to fill the buckets of `GROUP BY time_bucket(..)` that have no rows.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fill {
+    /// Fills aggregated values with null.
+    Null,
+    /// Carries the values of the previous bucket forward.
+    Previous,
+    /// Interpolates values linearly between the surrounding buckets.
+    Linear,
+    /// Fills aggregated values with a constant.
+    Value(OrderedF64),
+}
+
+impl fmt::Display for Fill {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fill::Null => write!(f, "NULL"),
+            Fill::Previous => write!(f, "PREVIOUS"),
+            Fill::Linear => write!(f, "LINEAR"),
+            Fill::Value(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+impl Query {
+    /// Returns names of tables read by the query, including tables in joins, derived tables,
+    /// set operations and subqueries. Names of common table expressions are excluded.
+    pub fn table_names(&self) -> Vec<ObjectName> {
+        let mut collector = TableNameCollector::default();
+        collector.visit_query(&self.inner);
+        collector.tables
+    }
+}
+
+/// Collects tables referenced by statements, used to check the privileges before executing.
+#[derive(Default)]
+pub(crate) struct TableNameCollector {
+    pub(crate) tables: Vec<ObjectName>,
+    /// Names of the common table expressions in scope, a set for each enclosing query, so a
+    /// CTE doesn't hide the table of the same name outside its query.
+    cte_scopes: Vec<HashSet<String>>,
+}
+
+impl TableNameCollector {
+    pub(crate) fn visit_statement(&mut self, statement: &SpStatement) {
+        match statement {
+            SpStatement::Query(query) => self.visit_query(query),
+            SpStatement::Explain { statement, .. } => self.visit_statement(statement),
+            SpStatement::Insert { source, .. } => self.visit_query(source),
+            _ => {}
+        }
+    }
+
+    fn visit_query(&mut self, query: &SpQuery) {
+        self.cte_scopes.push(HashSet::new());
+        if let Some(with) = &query.with {
+            for cte in &with.cte_tables {
+                self.visit_query(&cte.query);
+                // Visible to the following CTEs and the body of the query.
+                if let Some(scope) = self.cte_scopes.last_mut() {
+                    scope.insert(cte.alias.name.value.clone());
+                }
+            }
+        }
+        self.visit_set_expr(&query.body);
+        for order_by in &query.order_by {
+            self.visit_expr(&order_by.expr);
+        }
+        if let Some(limit) = &query.limit {
+            self.visit_expr(limit);
+        }
+        if let Some(offset) = &query.offset {
+            self.visit_expr(&offset.value);
+        }
+        self.cte_scopes.pop();
+    }
+
+    fn visit_set_expr(&mut self, set_expr: &SetExpr) {
+        match set_expr {
+            SetExpr::Select(select) => {
+                for table in &select.from {
+                    self.visit_table_with_joins(table);
+                }
+                for item in &select.projection {
+                    if let SelectItem::UnnamedExpr(expr) | SelectItem::ExprWithAlias { expr, .. } =
+                        item
+                    {
+                        self.visit_expr(expr);
+                    }
+                }
+                if let Some(selection) = &select.selection {
+                    self.visit_expr(selection);
+                }
+                for expr in select
+                    .group_by
+                    .iter()
+                    .chain(&select.cluster_by)
+                    .chain(&select.distribute_by)
+                    .chain(&select.sort_by)
+                {
+                    self.visit_expr(expr);
+                }
+                if let Some(having) = &select.having {
+                    self.visit_expr(having);
+                }
+            }
+            SetExpr::Query(query) => self.visit_query(query),
+            SetExpr::SetOperation { left, right, .. } => {
+                self.visit_set_expr(left);
+                self.visit_set_expr(right);
+            }
+            SetExpr::Values(values) => {
+                for expr in values.0.iter().flatten() {
+                    self.visit_expr(expr);
+                }
+            }
+            SetExpr::Insert(statement) => self.visit_statement(statement),
+        }
+    }
+
+    fn visit_table_with_joins(&mut self, table: &TableWithJoins) {
+        self.visit_table_factor(&table.relation);
+        for join in &table.joins {
+            self.visit_table_factor(&join.relation);
+        }
+    }
+
+    fn visit_table_factor(&mut self, table: &TableFactor) {
+        match table {
+            TableFactor::Table { name, args, .. } => {
+                let is_cte = matches!(&name.0[..], [ident] if self
+                    .cte_scopes
+                    .iter()
+                    .any(|scope| scope.contains(&ident.value)));
+                if !is_cte {
+                    self.tables.push(name.clone());
+                }
+                self.visit_function_args(args);
+            }
+            TableFactor::Derived { subquery, .. } => self.visit_query(subquery),
+            TableFactor::NestedJoin(table) => self.visit_table_with_joins(table),
+            TableFactor::TableFunction { expr, .. } => self.visit_expr(expr),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expr) {
+        match expr {
+            Expr::Subquery(query) | Expr::Exists(query) => self.visit_query(query),
+            Expr::InSubquery { expr, subquery, .. } => {
+                self.visit_expr(expr);
+                self.visit_query(subquery);
+            }
+            Expr::BinaryOp { left, right, .. } => {
+                self.visit_expr(left);
+                self.visit_expr(right);
+            }
+            Expr::UnaryOp { expr, .. }
+            | Expr::Nested(expr)
+            | Expr::IsNull(expr)
+            | Expr::IsNotNull(expr)
+            | Expr::Cast { expr, .. }
+            | Expr::TryCast { expr, .. } => self.visit_expr(expr),
+            Expr::InList { expr, list, .. } => {
+                self.visit_expr(expr);
+                for item in list {
+                    self.visit_expr(item);
+                }
+            }
+            Expr::Between {
+                expr, low, high, ..
+            } => {
+                self.visit_expr(expr);
+                self.visit_expr(low);
+                self.visit_expr(high);
+            }
+            Expr::Case {
+                operand,
+                conditions,
+                results,
+                else_result,
+            } => {
+                if let Some(operand) = operand {
+                    self.visit_expr(operand);
+                }
+                for expr in conditions.iter().chain(results) {
+                    self.visit_expr(expr);
+                }
+                if let Some(else_result) = else_result {
+                    self.visit_expr(else_result);
+                }
+            }
+            Expr::Tuple(exprs) => {
+                for expr in exprs {
+                    self.visit_expr(expr);
+                }
+            }
+            Expr::Function(function) => {
+                self.visit_function_args(&function.args);
+                if let Some(over) = &function.over {
+                    for expr in &over.partition_by {
+                        self.visit_expr(expr);
+                    }
+                    for order_by in &over.order_by {
+                        self.visit_expr(&order_by.expr);
+                    }
+                }
+            }
+            Expr::IsDistinctFrom(left, right) | Expr::IsNotDistinctFrom(left, right) => {
+                self.visit_expr(left);
+                self.visit_expr(right);
+            }
+            Expr::Extract { expr, .. } | Expr::Collate { expr, .. } => self.visit_expr(expr),
+            Expr::Substring {
+                expr,
+                substring_from,
+                substring_for,
+            } => {
+                self.visit_expr(expr);
+                for expr in substring_from.iter().chain(substring_for) {
+                    self.visit_expr(expr);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_function_args(&mut self, args: &[FunctionArg]) {
+        for arg in args {
+            if let FunctionArg::Named {
+                arg: FunctionArgExpr::Expr(expr),
+                ..
+            }
+            | FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg
+            {
+                self.visit_expr(expr);
+            }
+        }
+    }
 }
 
 /// Automatically converts from sqlparser Query instance to SqlQuery.
@@ -27,7 +293,11 @@ impl TryFrom<SpQuery> for Query {
     type Error = Error;
 
     fn try_from(q: SpQuery) -> Result<Self, Self::Error> {
-        Ok(Query { inner: q })
+        Ok(Query {
+            inner: q,
+            fill: None,
+            range_select: None,
+        })
     }
 }
 
@@ -38,3 +308,64 @@ impl TryFrom<Query> for SpQuery {
         Ok(value.inner)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use sqlparser::dialect::GenericDialect;
+
+    use crate::parser::ParserContext;
+    use crate::statements::statement::Statement;
+
+    fn table_names(sql: &str) -> Vec<String> {
+        let mut stmts = ParserContext::create_with_dialect(sql, &GenericDialect {}).unwrap();
+        match stmts.remove(0) {
+            Statement::Query(query) => query
+                .table_names()
+                .into_iter()
+                .map(|name| name.to_string())
+                .collect(),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_query_table_names() {
+        assert_eq!(vec!["cpu"], table_names("SELECT * FROM cpu"));
+        assert_eq!(
+            vec!["public.cpu", "memory"],
+            table_names("SELECT * FROM public.cpu JOIN memory ON cpu.host = memory.host")
+        );
+        assert_eq!(
+            vec!["cpu", "memory", "disk"],
+            table_names(
+                "SELECT * FROM (SELECT * FROM cpu) t WHERE host IN (SELECT host FROM memory) \
+                 UNION SELECT * FROM disk"
+            )
+        );
+        assert_eq!(
+            vec!["cpu", "memory"],
+            table_names(
+                "WITH c AS (SELECT * FROM cpu) SELECT * FROM c WHERE EXISTS (SELECT 1 FROM memory)"
+            )
+        );
+        assert_eq!(
+            vec!["memory"],
+            table_names("SELECT (SELECT max(v) FROM memory) AS m")
+        );
+        assert_eq!(
+            vec!["cpu", "memory", "disk", "net"],
+            table_names(
+                "SELECT abs((SELECT max(v) FROM cpu)) FROM memory \
+                 GROUP BY (SELECT 1 FROM disk) ORDER BY (SELECT 1 FROM net)"
+            )
+        );
+        // The CTE is only in scope of its query.
+        assert_eq!(
+            vec!["cpu", "cpu"],
+            table_names(
+                "SELECT * FROM (WITH cpu AS (SELECT * FROM cpu) SELECT * FROM cpu) t \
+                 JOIN cpu ON t.host = cpu.host"
+            )
+        );
+    }
+}
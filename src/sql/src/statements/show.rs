@@ -60,6 +60,13 @@ pub struct ShowCreateTable {
     pub table_name: String,
 }
 
+/// SQL structure for `SHOW [FULL] PROCESSLIST`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShowProcesslist {
+    /// Whether to show the full query text instead of a truncated one.
+    pub full: bool,
+}
+
 #[cfg(test)]
 mod tests {
     use std::assert_matches::assert_matches;
@@ -13,13 +13,16 @@
 // limitations under the License.
 
 use crate::statements::alter::AlterTable;
-use crate::statements::create::{CreateDatabase, CreateTable};
+use crate::statements::create::{CreateCatalog, CreateDatabase, CreateTable};
 use crate::statements::describe::DescribeTable;
-use crate::statements::drop::DropTable;
+use crate::statements::drop::{DropCatalog, DropTable};
 use crate::statements::explain::Explain;
 use crate::statements::insert::Insert;
+use crate::statements::kill::Kill;
+use crate::statements::privilege::{CreateRole, CreateUser, Grant, Revoke};
 use crate::statements::query::Query;
-use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowTables};
+use crate::statements::set_variables::SetVariables;
+use crate::statements::show::{ShowCreateTable, ShowDatabases, ShowProcesslist, ShowTables};
 
 /// Tokens parsed by `DFParser` are converted into these values.
 #[allow(clippy::large_enum_variant)]
@@ -35,6 +38,10 @@ pub enum Statement {
     DropTable(DropTable),
     // CREATE DATABASE
     CreateDatabase(CreateDatabase),
+    // CREATE CATALOG
+    CreateCatalog(CreateCatalog),
+    // DROP CATALOG
+    DropCatalog(DropCatalog),
     /// ALTER TABLE
     Alter(AlterTable),
     // Databases.
@@ -43,11 +50,25 @@ pub enum Statement {
     ShowTables(ShowTables),
     // SHOW CREATE TABLE
     ShowCreateTable(ShowCreateTable),
+    // SHOW [FULL] PROCESSLIST
+    ShowProcesslist(ShowProcesslist),
+    // KILL [CONNECTION | QUERY] id
+    Kill(Kill),
     // DESCRIBE TABLE
     DescribeTable(DescribeTable),
     // EXPLAIN QUERY
     Explain(Explain),
     Use(String),
+    // SET variable = value
+    SetVariables(SetVariables),
+    // CREATE USER
+    CreateUser(CreateUser),
+    // CREATE ROLE
+    CreateRole(CreateRole),
+    // GRANT privileges or role
+    Grant(Grant),
+    // REVOKE privileges or role
+    Revoke(Revoke),
 }
 
 /// Comment hints from SQL.
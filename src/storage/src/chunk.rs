@@ -125,7 +125,15 @@ impl ChunkReaderBuilder {
         Ok(self)
     }
 
-    pub async fn build(mut self) -> Result<ChunkReaderImpl> {
+    pub async fn build(self) -> Result<ChunkReaderImpl> {
+        let (schema, reader) = self.build_batch_reader().await?;
+
+        Ok(ChunkReaderImpl::new(schema, reader))
+    }
+
+    /// Builds a reader that returns deduplicated batches ordered by row key, with the
+    /// projected schema of these batches.
+    pub async fn build_batch_reader(mut self) -> Result<(ProjectedSchemaRef, BoxedBatchReader)> {
         let schema = Arc::new(
             ProjectedSchema::new(self.schema, self.projection)
                 .context(error::InvalidProjectionSnafu)?,
@@ -158,7 +166,7 @@ impl ChunkReaderBuilder {
         let reader = reader_builder.build();
         let reader = DedupReader::new(schema.clone(), reader);
 
-        Ok(ChunkReaderImpl::new(schema, Box::new(reader)))
+        Ok((schema, Box::new(reader)))
     }
 }
 
@@ -14,5 +14,50 @@
 
 //! storage engine config
 
-#[derive(Debug, Default, Clone)]
-pub struct EngineConfig {}
+use std::time::Duration;
+
+/// Default global write buffer size (1G).
+const DEFAULT_GLOBAL_WRITE_BUFFER_SIZE: usize = 1024 * 1024 * 1024;
+/// Default max duration to stall a write (10s).
+const DEFAULT_MAX_WRITE_STALL_DURATION: Duration = Duration::from_secs(10);
+
+/// Type of memtables created by the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemtableType {
+    /// Memtable that stores rows in a btree ordered by row key.
+    BTree,
+    /// Memtable that stores rows of each series in columnar vectors, which is more
+    /// memory efficient for time-series data with many rows per series.
+    Columnar,
+}
+
+impl Default for MemtableType {
+    fn default() -> Self {
+        MemtableType::BTree
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EngineConfig {
+    /// Max bytes of mutable memtables of all regions, the engine flushes the region with
+    /// largest mutable memtable if this limit is exceeded.
+    pub global_write_buffer_size: usize,
+    /// Max bytes of all memtables of all regions, writes are stalled if this limit is
+    /// exceeded.
+    pub global_write_buffer_reject_size: usize,
+    /// Max duration to wait for memtables to be flushed before rejecting a stalled write.
+    pub max_write_stall_duration: Duration,
+    /// Type of memtables.
+    pub memtable_type: MemtableType,
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        Self {
+            global_write_buffer_size: DEFAULT_GLOBAL_WRITE_BUFFER_SIZE,
+            global_write_buffer_reject_size: DEFAULT_GLOBAL_WRITE_BUFFER_SIZE * 2,
+            max_write_stall_duration: DEFAULT_MAX_WRITE_STALL_DURATION,
+            memtable_type: MemtableType::default(),
+        }
+    }
+}
@@ -25,14 +25,15 @@ use store_api::storage::{
 };
 
 use crate::background::JobPoolImpl;
-use crate::config::EngineConfig;
+use crate::config::{EngineConfig, MemtableType};
 use crate::error::{self, Error, Result};
 use crate::flush::{FlushSchedulerImpl, FlushSchedulerRef, FlushStrategyRef, SizeBasedStrategy};
 use crate::manifest::region::RegionManifest;
-use crate::memtable::{DefaultMemtableBuilder, MemtableBuilderRef};
+use crate::memtable::{ColumnarMemtableBuilder, DefaultMemtableBuilder, MemtableBuilderRef};
 use crate::metadata::RegionMetadata;
 use crate::region::{RegionImpl, StoreConfig};
 use crate::sst::FsAccessLayer;
+use crate::write_buffer::{WriteBufferManager, WriteBufferManagerRef};
 
 /// [StorageEngine] implementation.
 pub struct EngineImpl<S: LogStore> {
@@ -210,20 +211,31 @@ struct EngineInner<S: LogStore> {
     memtable_builder: MemtableBuilderRef,
     flush_scheduler: FlushSchedulerRef,
     flush_strategy: FlushStrategyRef,
+    write_buffer_manager: WriteBufferManagerRef,
 }
 
 impl<S: LogStore> EngineInner<S> {
-    pub fn new(_config: EngineConfig, log_store: Arc<S>, object_store: ObjectStore) -> Self {
+    pub fn new(config: EngineConfig, log_store: Arc<S>, object_store: ObjectStore) -> Self {
         let job_pool = Arc::new(JobPoolImpl {});
         let flush_scheduler = Arc::new(FlushSchedulerImpl::new(job_pool));
+        let write_buffer_manager = Arc::new(WriteBufferManager::new(
+            config.global_write_buffer_size,
+            config.global_write_buffer_reject_size,
+            config.max_write_stall_duration,
+        ));
+        let memtable_builder: MemtableBuilderRef = match config.memtable_type {
+            MemtableType::BTree => Arc::new(DefaultMemtableBuilder::default()),
+            MemtableType::Columnar => Arc::new(ColumnarMemtableBuilder::default()),
+        };
 
         Self {
             object_store,
             log_store,
             regions: RwLock::new(Default::default()),
-            memtable_builder: Arc::new(DefaultMemtableBuilder::default()),
+            memtable_builder,
             flush_scheduler,
             flush_strategy: Arc::new(SizeBasedStrategy::default()),
+            write_buffer_manager,
         }
     }
 
@@ -320,6 +332,7 @@ impl<S: LogStore> EngineInner<S> {
             memtable_builder: self.memtable_builder.clone(),
             flush_scheduler: self.flush_scheduler.clone(),
             flush_strategy: self.flush_strategy.clone(),
+            write_buffer_manager: self.write_buffer_manager.clone(),
         }
     }
 }
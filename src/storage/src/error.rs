@@ -15,6 +15,7 @@
 use std::any::Any;
 use std::io::Error as IoError;
 use std::str::Utf8Error;
+use std::sync::Arc;
 
 use common_error::prelude::*;
 use datatypes::arrow;
@@ -350,6 +351,38 @@ pub enum Error {
         #[snafu(backtrace)]
         source: datatypes::error::Error,
     },
+
+    #[snafu(display(
+        "Failed to cast column {} to {:?} type, source: {}",
+        column,
+        data_type,
+        source
+    ))]
+    CastColumn {
+        column: String,
+        data_type: ConcreteDataType,
+        source: ArrowError,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Failed to commit write group, source: {}", source))]
+    WriteGroup { source: Arc<Error> },
+
+    #[snafu(display(
+        "Write to region {} is rejected as memtables use too much memory, memory_usage: {}, limit: {}",
+        region,
+        memory_usage,
+        limit
+    ))]
+    WriteStall {
+        region: String,
+        memory_usage: usize,
+        limit: usize,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display("Invalid key to get, {}", msg))]
+    InvalidGetKey { msg: String, backtrace: Backtrace },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -367,7 +400,8 @@ impl ErrorExt for Error {
             | BuildBatch { .. }
             | NotInSchemaToCompat { .. }
             | WriteToOldVersion { .. }
-            | IllegalTimestampColumnType { .. } => StatusCode::InvalidArguments,
+            | IllegalTimestampColumnType { .. }
+            | InvalidGetKey { .. } => StatusCode::InvalidArguments,
 
             Utf8 { .. }
             | EncodeJson { .. }
@@ -384,6 +418,7 @@ impl ErrorExt for Error {
             | FilterColumn { .. }
             | AlterMetadata { .. }
             | CompatRead { .. }
+            | CastColumn { .. }
             | CreateDefaultToRead { .. }
             | NoDefaultToRead { .. } => StatusCode::Unexpected,
 
@@ -401,7 +436,8 @@ impl ErrorExt for Error {
             | ReadParquet { .. }
             | ReadParquetIo { .. }
             | InvalidRegionState { .. }
-            | ReadWal { .. } => StatusCode::StorageUnavailable,
+            | ReadWal { .. }
+            | WriteStall { .. } => StatusCode::StorageUnavailable,
 
             InvalidAlterRequest { source, .. }
             | InvalidRegionDesc { source, .. }
@@ -409,6 +445,7 @@ impl ErrorExt for Error {
             PushBatch { source, .. } => source.status_code(),
             AddDefault { source, .. } => source.status_code(),
             ConvertChunk { source, .. } => source.status_code(),
+            WriteGroup { source, .. } => source.status_code(),
         }
     }
 
@@ -185,13 +185,16 @@ impl<S: LogStore> FlushJob<S> {
             // TODO(hl): Check if random file name already exists in meta.
             let iter = m.iter(&iter_ctx)?;
             futures.push(async move {
-                self.sst_layer
+                let sst_info = self
+                    .sst_layer
                     .write_sst(&file_name, iter, &WriteOptions::default())
                     .await?;
 
                 Ok(FileMeta {
                     file_name,
                     level: 0,
+                    num_rows: sst_info.num_rows,
+                    file_size: sst_info.file_size,
                 })
             });
         }
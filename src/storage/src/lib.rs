@@ -36,5 +36,6 @@ mod test_util;
 mod version;
 mod wal;
 pub mod write_batch;
+mod write_buffer;
 
 pub use engine::EngineImpl;
@@ -43,6 +43,7 @@ pub fn build_region_edit(
             .map(|f| FileMeta {
                 file_name: f.to_string(),
                 level: 0,
+                ..Default::default()
             })
             .collect(),
         files_to_remove: files_to_remove
@@ -50,6 +51,7 @@ pub fn build_region_edit(
             .map(|f| FileMeta {
                 file_name: f.to_string(),
                 level: 0,
+                ..Default::default()
             })
             .collect(),
     }
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 mod btree;
+mod columnar;
 mod inserter;
 #[cfg(test)]
 pub mod tests;
@@ -26,6 +27,7 @@ use store_api::storage::{consts, OpType, SequenceNumber};
 
 use crate::error::Result;
 use crate::memtable::btree::BTreeMemtable;
+pub use crate::memtable::columnar::ColumnarMemtable;
 pub use crate::memtable::inserter::Inserter;
 pub use crate::memtable::version::MemtableVersion;
 use crate::read::Batch;
@@ -171,3 +173,16 @@ impl MemtableBuilder for DefaultMemtableBuilder {
         Arc::new(BTreeMemtable::new(id, schema))
     }
 }
+
+/// Builder of [ColumnarMemtable].
+#[derive(Debug, Default)]
+pub struct ColumnarMemtableBuilder {
+    memtable_id: AtomicU32,
+}
+
+impl MemtableBuilder for ColumnarMemtableBuilder {
+    fn build(&self, schema: RegionSchemaRef) -> MemtableRef {
+        let id = self.memtable_id.fetch_add(1, Ordering::Relaxed);
+        Arc::new(ColumnarMemtable::new(id, schema))
+    }
+}
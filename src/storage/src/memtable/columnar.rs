@@ -0,0 +1,732 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::{Arc, RwLock};
+use std::{fmt, mem};
+
+use datatypes::prelude::*;
+use datatypes::vectors::{UInt64Vector, UInt8Vector};
+use snafu::ResultExt;
+use store_api::storage::{OpType, SequenceNumber};
+
+use crate::error::{self, Result};
+use crate::memtable::{
+    BatchIterator, BoxedBatchIterator, IterContext, KeyValues, Memtable, MemtableId, RowOrdering,
+};
+use crate::read::Batch;
+use crate::schema::compat::ReadAdapter;
+use crate::schema::{ProjectedSchema, ProjectedSchemaRef, RegionSchemaRef};
+
+/// Max number of chunks in a series, the newest chunks would be merged if exceeded.
+const MAX_CHUNKS_PER_SERIES: usize = 8;
+
+/// Row key columns before the timestamp column, which identify a series.
+type SeriesKey = Vec<Value>;
+
+type SeriesMap = BTreeMap<SeriesKey, Series>;
+
+/// A memtable that groups rows by series and stores timestamps and fields of each
+/// series in columnar vectors.
+///
+/// Row key columns before the timestamp column are the key of a series, so each
+/// series only stores its key once. Rows of a series in a write are sorted into an
+/// immutable chunk, and chunks are merged as they accumulate, so readers only merge
+/// the few sorted chunks of each series and never block on each other.
+pub struct ColumnarMemtable {
+    id: MemtableId,
+    schema: RegionSchemaRef,
+    series: RwLock<SeriesMap>,
+    /// Estimated size of series keys and chunks.
+    estimated_bytes: AtomicUsize,
+    num_rows: AtomicUsize,
+}
+
+impl fmt::Debug for ColumnarMemtable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ColumnarMemtable")
+            .field("id", &self.id)
+            .field("schema", &self.schema)
+            .field("num_rows", &self.num_rows())
+            .finish()
+    }
+}
+
+impl ColumnarMemtable {
+    pub fn new(id: MemtableId, schema: RegionSchemaRef) -> ColumnarMemtable {
+        ColumnarMemtable {
+            id,
+            schema,
+            series: RwLock::new(BTreeMap::new()),
+            estimated_bytes: AtomicUsize::new(0),
+            num_rows: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the chunks of all series, rows written later are not visible to the snapshot.
+    fn snapshot(&self) -> Vec<SeriesSnapshot> {
+        let series_map = self.series.read().unwrap();
+        series_map
+            .iter()
+            .map(|(key, series)| SeriesSnapshot {
+                key: key.clone(),
+                chunks: series.chunks.clone(),
+            })
+            .collect()
+    }
+}
+
+impl Memtable for ColumnarMemtable {
+    fn id(&self) -> MemtableId {
+        self.id
+    }
+
+    fn schema(&self) -> RegionSchemaRef {
+        self.schema.clone()
+    }
+
+    fn write(&self, kvs: &KeyValues) -> Result<()> {
+        let timestamp_index = self.schema.timestamp_key_index();
+        let (series_columns, time_columns) = kvs.keys.split_at(timestamp_index);
+
+        // Positions of rows of each series in the written chunk.
+        let mut series_rows: BTreeMap<SeriesKey, Vec<RowPosition>> = BTreeMap::new();
+        let mut series_key: SeriesKey = Vec::with_capacity(series_columns.len());
+        for row in 0..kvs.len() {
+            series_key.clear();
+            series_key.extend(series_columns.iter().map(|vector| vector.get(row)));
+
+            // Avoid cloning the key if the series already has rows.
+            match series_rows.get_mut(&series_key) {
+                Some(rows) => rows.push((0, row)),
+                None => {
+                    series_rows.insert(series_key.clone(), vec![(0, row)]);
+                }
+            }
+        }
+
+        // Sorts rows of each series before acquiring the lock.
+        let written = [Arc::new(RowChunk {
+            time_keys: time_columns.to_vec(),
+            values: kvs.values.clone(),
+            sequences: vec![kvs.sequence; kvs.len()],
+            op_types: vec![kvs.op_type; kvs.len()],
+            indexes: (kvs.start_index_in_batch..kvs.start_index_in_batch + kvs.len()).collect(),
+        })];
+        let mut series_chunks = Vec::with_capacity(series_rows.len());
+        for (key, mut positions) in series_rows {
+            positions.sort_by(|a, b| written[0].cmp_row(a.1, &written[0], b.1));
+            series_chunks.push((key, build_chunk(&written, &positions)?));
+        }
+
+        let mut allocated = 0;
+        let mut released = 0;
+        let mut series_map = self.series.write().unwrap();
+        for (key, chunk) in series_chunks {
+            if !series_map.contains_key(&key) {
+                allocated += estimated_key_size(&key);
+                series_map.insert(key.clone(), Series::default());
+            }
+            let series = series_map.get_mut(&key).unwrap();
+            released += series.estimated_size();
+            series.push_chunk(chunk)?;
+            allocated += series.estimated_size();
+        }
+        // Adds before subtracting, so the size never underflows.
+        self.estimated_bytes
+            .fetch_add(allocated, AtomicOrdering::Relaxed);
+        self.estimated_bytes
+            .fetch_sub(released, AtomicOrdering::Relaxed);
+        self.num_rows.fetch_add(kvs.len(), AtomicOrdering::Relaxed);
+
+        Ok(())
+    }
+
+    fn iter(&self, ctx: &IterContext) -> Result<BoxedBatchIterator> {
+        assert!(ctx.batch_size > 0);
+
+        let snapshots = self.snapshot();
+        let iter = ColumnarIterator::new(ctx.clone(), self.schema.clone(), snapshots)?;
+
+        Ok(Box::new(iter))
+    }
+
+    fn bytes_allocated(&self) -> usize {
+        self.estimated_bytes.load(AtomicOrdering::Relaxed)
+    }
+
+    fn num_rows(&self) -> usize {
+        self.num_rows.load(AtomicOrdering::Relaxed)
+    }
+}
+
+/// Returns the estimated size of a series key.
+fn estimated_key_size(key: &SeriesKey) -> usize {
+    key.iter()
+        .map(|value| {
+            let data_size = match value.as_value_ref() {
+                ValueRef::String(s) => s.len(),
+                ValueRef::Binary(b) => b.len(),
+                _ => 0,
+            };
+            mem::size_of::<Value>() + data_size
+        })
+        .sum()
+}
+
+/// Immutable sorted rows of a series.
+struct RowChunk {
+    /// Row key columns since the timestamp column.
+    time_keys: Vec<VectorRef>,
+    values: Vec<VectorRef>,
+    sequences: Vec<SequenceNumber>,
+    op_types: Vec<OpType>,
+    /// Index of each row in its write batch.
+    indexes: Vec<usize>,
+}
+
+impl RowChunk {
+    #[inline]
+    fn num_rows(&self) -> usize {
+        self.sequences.len()
+    }
+
+    fn estimated_size(&self) -> usize {
+        let columns_size: usize = self
+            .time_keys
+            .iter()
+            .chain(&self.values)
+            .map(|vector| vector.memory_size())
+            .sum();
+        let row_size =
+            mem::size_of::<SequenceNumber>() + mem::size_of::<OpType>() + mem::size_of::<usize>();
+
+        columns_size + self.num_rows() * row_size
+    }
+
+    /// Compares row `i` of this chunk with row `j` of `other` by (time keys asc, sequence
+    /// desc, index_in_batch desc, op_type desc).
+    fn cmp_row(&self, i: usize, other: &RowChunk, j: usize) -> Ordering {
+        self.cmp_time_keys(i, other, j)
+            .then_with(|| other.sequences[j].cmp(&self.sequences[i]))
+            .then_with(|| other.indexes[j].cmp(&self.indexes[i]))
+            .then_with(|| other.op_types[j].cmp(&self.op_types[i]))
+    }
+
+    fn cmp_time_keys(&self, i: usize, other: &RowChunk, j: usize) -> Ordering {
+        for (left, right) in self.time_keys.iter().zip(other.time_keys.iter()) {
+            let ord = left.get_ref(i).cmp(&right.get_ref(j));
+            if ord != Ordering::Equal {
+                return ord;
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+type RowChunkRef = Arc<RowChunk>;
+
+/// Position of a row in chunks of a series, in (chunk index, row index) format.
+type RowPosition = (usize, usize);
+
+/// Returns positions of all rows in sorted `chunks` in row order, by merging the chunks.
+fn merged_positions(chunks: &[RowChunkRef]) -> Vec<RowPosition> {
+    let num_rows = chunks.iter().map(|chunk| chunk.num_rows()).sum();
+    let mut positions = Vec::with_capacity(num_rows);
+    // Next row to merge in each chunk.
+    let mut next_rows = vec![0; chunks.len()];
+    loop {
+        // A series has a few chunks, so finding the smallest row by a scan is cheap.
+        let smallest = next_rows
+            .iter()
+            .enumerate()
+            .filter(|(chunk_idx, row)| **row < chunks[*chunk_idx].num_rows())
+            .map(|(chunk_idx, row)| (chunk_idx, *row))
+            .min_by(|a, b| chunks[a.0].cmp_row(a.1, &chunks[b.0], b.1));
+        match smallest {
+            Some((chunk_idx, row)) => {
+                next_rows[chunk_idx] += 1;
+                positions.push((chunk_idx, row));
+            }
+            None => return positions,
+        }
+    }
+}
+
+/// Rows of a series.
+#[derive(Default)]
+struct Series {
+    /// Sorted chunks, from the oldest to the newest.
+    chunks: Vec<RowChunkRef>,
+}
+
+impl Series {
+    /// Adds a sorted chunk, then merges the newest two chunks while the older one isn't
+    /// larger, so chunk sizes decrease from the oldest to the newest and a row is only
+    /// merged a logarithmic number of times.
+    fn push_chunk(&mut self, chunk: RowChunk) -> Result<()> {
+        self.chunks.push(Arc::new(chunk));
+
+        while self.chunks.len() > 1 {
+            let num_chunks = self.chunks.len();
+            let (older, newer) = (&self.chunks[num_chunks - 2], &self.chunks[num_chunks - 1]);
+            if older.num_rows() > newer.num_rows() && num_chunks <= MAX_CHUNKS_PER_SERIES {
+                break;
+            }
+
+            let to_merge = &self.chunks[num_chunks - 2..];
+            let merged = build_chunk(to_merge, &merged_positions(to_merge))?;
+            self.chunks.truncate(num_chunks - 2);
+            self.chunks.push(Arc::new(merged));
+        }
+
+        Ok(())
+    }
+
+    fn estimated_size(&self) -> usize {
+        self.chunks.iter().map(|chunk| chunk.estimated_size()).sum()
+    }
+}
+
+/// Builds a chunk from rows at `positions` of `chunks`.
+fn build_chunk(chunks: &[RowChunkRef], positions: &[RowPosition]) -> Result<RowChunk> {
+    let first = &chunks[0];
+    let time_keys = copy_columns(&first.time_keys, chunks, positions, |c| &c.time_keys)?;
+    let values = copy_columns(&first.values, chunks, positions, |c| &c.values)?;
+
+    Ok(RowChunk {
+        time_keys,
+        values,
+        sequences: positions
+            .iter()
+            .map(|(c, r)| chunks[*c].sequences[*r])
+            .collect(),
+        op_types: positions
+            .iter()
+            .map(|(c, r)| chunks[*c].op_types[*r])
+            .collect(),
+        indexes: positions
+            .iter()
+            .map(|(c, r)| chunks[*c].indexes[*r])
+            .collect(),
+    })
+}
+
+fn copy_columns<F>(
+    template: &[VectorRef],
+    chunks: &[RowChunkRef],
+    positions: &[RowPosition],
+    columns_of: F,
+) -> Result<Vec<VectorRef>>
+where
+    F: Fn(&RowChunk) -> &[VectorRef],
+{
+    template
+        .iter()
+        .enumerate()
+        .map(|(col_idx, vector)| {
+            let mut builder = vector.data_type().create_mutable_vector(positions.len());
+            for (chunk_idx, row) in positions {
+                let column = &columns_of(&chunks[*chunk_idx])[col_idx];
+                builder
+                    .push_value_ref(column.get_ref(*row))
+                    .context(error::PushBatchSnafu)?;
+            }
+            Ok(builder.to_vector())
+        })
+        .collect()
+}
+
+/// Chunks of a series at the time the iterator is created.
+struct SeriesSnapshot {
+    key: SeriesKey,
+    chunks: Vec<RowChunkRef>,
+}
+
+/// Rows of a series to read, in row order.
+struct SeriesCursor {
+    series_idx: usize,
+    positions: Vec<RowPosition>,
+    next: usize,
+}
+
+struct ColumnarIterator {
+    ctx: IterContext,
+    /// Schema of this memtable.
+    schema: RegionSchemaRef,
+    /// Projected schema that user expect to read.
+    projected_schema: ProjectedSchemaRef,
+    adapter: ReadAdapter,
+    series: Vec<SeriesSnapshot>,
+    /// Index of next series to read.
+    next_series: usize,
+    cursor: Option<SeriesCursor>,
+}
+
+impl BatchIterator for ColumnarIterator {
+    fn schema(&self) -> ProjectedSchemaRef {
+        self.projected_schema.clone()
+    }
+
+    fn ordering(&self) -> RowOrdering {
+        RowOrdering::Key
+    }
+}
+
+impl Iterator for ColumnarIterator {
+    type Item = Result<Batch>;
+
+    fn next(&mut self) -> Option<Result<Batch>> {
+        self.next_batch().transpose()
+    }
+}
+
+impl ColumnarIterator {
+    fn new(
+        ctx: IterContext,
+        schema: RegionSchemaRef,
+        series: Vec<SeriesSnapshot>,
+    ) -> Result<ColumnarIterator> {
+        let projected_schema = ctx
+            .projected_schema
+            .clone()
+            .unwrap_or_else(|| Arc::new(ProjectedSchema::no_projection(schema.clone())));
+        let adapter = ReadAdapter::new(schema.store_schema().clone(), projected_schema.clone())?;
+
+        Ok(ColumnarIterator {
+            ctx,
+            schema,
+            projected_schema,
+            adapter,
+            series,
+            next_series: 0,
+            cursor: None,
+        })
+    }
+
+    /// Returns positions of rows to read in next series, visibility and duplication
+    /// are handled here.
+    fn next_cursor(&mut self) -> Option<SeriesCursor> {
+        while self.next_series < self.series.len() {
+            let series_idx = self.next_series;
+            self.next_series += 1;
+
+            let chunks = &self.series[series_idx].chunks;
+            let mut positions = merged_positions(chunks);
+            if !self.ctx.for_flush {
+                let visible_sequence = self.ctx.visible_sequence;
+                positions.retain(|(c, r)| chunks[*c].sequences[*r] <= visible_sequence);
+                // Only keep the first row of rows with the same time keys, which is the
+                // latest one.
+                positions.dedup_by(|(c, r), (prev_c, prev_r)| {
+                    chunks[*c].cmp_time_keys(*r, &chunks[*prev_c], *prev_r) == Ordering::Equal
+                });
+            }
+
+            if !positions.is_empty() {
+                return Some(SeriesCursor {
+                    series_idx,
+                    positions,
+                    next: 0,
+                });
+            }
+        }
+
+        None
+    }
+
+    fn next_batch(&mut self) -> Result<Option<Batch>> {
+        let key_needed = self.adapter.source_key_needed().to_vec();
+        let value_needed = self.adapter.source_value_needed().to_vec();
+        let key_types: Vec<_> = self
+            .schema
+            .row_key_columns()
+            .map(|column| column.desc.data_type.clone())
+            .collect();
+        let value_types: Vec<_> = self
+            .schema
+            .value_columns()
+            .map(|column| column.desc.data_type.clone())
+            .collect();
+        let batch_size = self.ctx.batch_size;
+        let timestamp_index = self.schema.timestamp_key_index();
+
+        let mut key_builders = new_builders(&key_types, &key_needed, batch_size);
+        let mut value_builders = new_builders(&value_types, &value_needed, batch_size);
+        let mut sequences = Vec::with_capacity(batch_size);
+        let mut op_types = Vec::with_capacity(batch_size);
+
+        while sequences.len() < batch_size {
+            if self.cursor.is_none() {
+                self.cursor = self.next_cursor();
+            }
+            let cursor = match &mut self.cursor {
+                Some(cursor) => cursor,
+                None => break,
+            };
+
+            let snapshot = &self.series[cursor.series_idx];
+            let num_to_read =
+                (batch_size - sequences.len()).min(cursor.positions.len() - cursor.next);
+            let positions = &cursor.positions[cursor.next..cursor.next + num_to_read];
+            for (chunk_idx, row) in positions {
+                let chunk = &snapshot.chunks[*chunk_idx];
+                for (col_idx, builder) in key_builders.iter_mut().enumerate() {
+                    let builder = match builder {
+                        Some(builder) => builder,
+                        None => continue,
+                    };
+                    if col_idx < timestamp_index {
+                        builder.push(&snapshot.key[col_idx]);
+                    } else {
+                        let vector = &chunk.time_keys[col_idx - timestamp_index];
+                        builder
+                            .try_push_ref(vector.get_ref(*row))
+                            .context(error::PushBatchSnafu)?;
+                    }
+                }
+                for (col_idx, builder) in value_builders.iter_mut().enumerate() {
+                    if let Some(builder) = builder {
+                        builder
+                            .try_push_ref(chunk.values[col_idx].get_ref(*row))
+                            .context(error::PushBatchSnafu)?;
+                    }
+                }
+                sequences.push(chunk.sequences[*row]);
+                op_types.push(chunk.op_types[*row].as_u8());
+            }
+
+            cursor.next += num_to_read;
+            if cursor.next >= cursor.positions.len() {
+                self.cursor = None;
+            }
+        }
+
+        if sequences.is_empty() {
+            return Ok(None);
+        }
+
+        let key_columns = key_builders
+            .iter_mut()
+            .flatten()
+            .map(|builder| builder.finish())
+            .collect();
+        let value_columns = value_builders
+            .iter_mut()
+            .flatten()
+            .map(|builder| builder.finish())
+            .collect();
+
+        let batch = self.adapter.batch_from_parts(
+            key_columns,
+            value_columns,
+            Arc::new(UInt64Vector::from_vec(sequences)),
+            Arc::new(UInt8Vector::from_vec(op_types)),
+        )?;
+
+        Ok(Some(batch))
+    }
+}
+
+/// Creates builders for needed columns, `None` for columns not needed.
+fn new_builders(
+    data_types: &[ConcreteDataType],
+    column_needed: &[bool],
+    capacity: usize,
+) -> Vec<Option<VectorBuilder>> {
+    data_types
+        .iter()
+        .zip(column_needed)
+        .map(|(data_type, needed)| {
+            if *needed {
+                Some(VectorBuilder::with_capacity(data_type.clone(), capacity))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use datatypes::type_id::LogicalTypeId;
+    use datatypes::vectors::{Int64Vector, StringVector, TimestampVector};
+
+    use super::*;
+    use crate::metadata::RegionMetadata;
+    use crate::test_util::descriptor_util::RegionDescBuilder;
+
+    fn schema_with_tag() -> RegionSchemaRef {
+        let desc = RegionDescBuilder::new("test")
+            .push_key_column(("host", LogicalTypeId::String, false))
+            .push_value_column(("v0", LogicalTypeId::Int64, true))
+            .build();
+        let metadata: RegionMetadata = desc.try_into().unwrap();
+
+        metadata.schema().clone()
+    }
+
+    fn write_rows(
+        memtable: &ColumnarMemtable,
+        sequence: SequenceNumber,
+        rows: &[(&str, i64, Option<i64>)],
+    ) -> usize {
+        let hosts = StringVector::from(rows.iter().map(|r| r.0).collect::<Vec<_>>());
+        let timestamps = TimestampVector::from_values(rows.iter().map(|r| r.1));
+        let values = Int64Vector::from(rows.iter().map(|r| r.2).collect::<Vec<_>>());
+
+        let kvs = KeyValues {
+            sequence,
+            op_type: OpType::Put,
+            start_index_in_batch: 0,
+            keys: vec![Arc::new(hosts), Arc::new(timestamps)],
+            values: vec![Arc::new(values)],
+        };
+        memtable.write(&kvs).unwrap();
+
+        kvs.estimated_memory_size()
+    }
+
+    fn read_rows(
+        memtable: &ColumnarMemtable,
+        ctx: &IterContext,
+    ) -> Vec<(String, i64, Option<i64>)> {
+        let mut rows = Vec::new();
+        for batch in memtable.iter(ctx).unwrap() {
+            let batch = batch.unwrap();
+            for i in 0..batch.num_rows() {
+                let host = match batch.column(0).get(i) {
+                    Value::String(s) => s.as_utf8().to_string(),
+                    v => panic!("Unexpected value {:?}", v),
+                };
+                let ts = match batch.column(1).get(i) {
+                    Value::Timestamp(ts) => ts.value(),
+                    v => panic!("Unexpected value {:?}", v),
+                };
+                let value = match batch.column(2).get(i) {
+                    Value::Int64(v) => Some(v),
+                    Value::Null => None,
+                    v => panic!("Unexpected value {:?}", v),
+                };
+                rows.push((host, ts, value));
+            }
+        }
+
+        rows
+    }
+
+    #[test]
+    fn test_columnar_memtable_series() {
+        let memtable = ColumnarMemtable::new(0, schema_with_tag());
+
+        write_rows(
+            &memtable,
+            1,
+            &[
+                ("host2", 1000, Some(1)),
+                ("host1", 2000, Some(2)),
+                ("host1", 1000, Some(3)),
+            ],
+        );
+        write_rows(
+            &memtable,
+            2,
+            &[("host1", 1000, Some(4)), ("host3", 500, None)],
+        );
+        assert_eq!(5, memtable.num_rows());
+
+        for batch_size in [1, 2, 3, 16] {
+            let ctx = IterContext {
+                batch_size,
+                ..Default::default()
+            };
+            let rows = read_rows(&memtable, &ctx);
+            let expect = vec![
+                ("host1".to_string(), 1000, Some(4)),
+                ("host1".to_string(), 2000, Some(2)),
+                ("host2".to_string(), 1000, Some(1)),
+                ("host3".to_string(), 500, None),
+            ];
+            assert_eq!(expect, rows);
+        }
+
+        // Read with old sequence.
+        let ctx = IterContext {
+            visible_sequence: 1,
+            ..Default::default()
+        };
+        let rows = read_rows(&memtable, &ctx);
+        let expect = vec![
+            ("host1".to_string(), 1000, Some(3)),
+            ("host1".to_string(), 2000, Some(2)),
+            ("host2".to_string(), 1000, Some(1)),
+        ];
+        assert_eq!(expect, rows);
+
+        // Read for flush returns all rows.
+        let ctx = IterContext {
+            for_flush: true,
+            ..Default::default()
+        };
+        assert_eq!(5, read_rows(&memtable, &ctx).len());
+    }
+
+    #[test]
+    fn test_columnar_memtable_merge_chunks() {
+        let memtable = ColumnarMemtable::new(0, schema_with_tag());
+        let ctx = IterContext::default();
+
+        let mut expect = Vec::new();
+        for i in 0..(MAX_CHUNKS_PER_SERIES as i64 * 2) {
+            // Write in reverse order.
+            let ts = 10000 - i;
+            write_rows(&memtable, i as SequenceNumber, &[("host", ts, Some(i))]);
+            expect.insert(0, ("host".to_string(), ts, Some(i)));
+
+            assert_eq!(expect, read_rows(&memtable, &ctx));
+
+            // Chunk sizes decrease from the oldest to the newest.
+            let series = memtable.series.read().unwrap();
+            let chunks = &series.values().next().unwrap().chunks;
+            assert!(chunks.len() <= MAX_CHUNKS_PER_SERIES);
+            assert!(chunks.windows(2).all(|w| w[0].num_rows() > w[1].num_rows()));
+        }
+    }
+
+    #[test]
+    fn test_columnar_memtable_bytes_allocated() {
+        let memtable = ColumnarMemtable::new(0, schema_with_tag());
+        assert_eq!(0, memtable.bytes_allocated());
+
+        let host = "a-host-with-a-name-longer-than-the-other-columns-of-a-row";
+        let mut row_oriented_size = 0;
+        for i in 0..16 {
+            let rows: Vec<_> = (0..64).map(|j| (host, i * 64 + j, Some(j))).collect();
+            row_oriented_size += write_rows(&memtable, i as SequenceNumber, &rows);
+
+            let series = memtable.series.read().unwrap();
+            let chunks_size: usize = series.values().map(|s| s.estimated_size()).sum();
+            let key = vec![Value::from(host)];
+            assert_eq!(
+                chunks_size + estimated_key_size(&key),
+                memtable.bytes_allocated()
+            );
+        }
+        // The host is only stored once.
+        assert!(memtable.bytes_allocated() < row_oriented_size);
+    }
+}
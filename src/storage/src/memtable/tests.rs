@@ -168,7 +168,10 @@ impl Default for MemtableTester {
 impl MemtableTester {
     fn new() -> MemtableTester {
         let schema = schema_for_test();
-        let builders = vec![Arc::new(DefaultMemtableBuilder::default()) as _];
+        let builders = vec![
+            Arc::new(DefaultMemtableBuilder::default()) as _,
+            Arc::new(ColumnarMemtableBuilder::default()) as _,
+        ];
 
         MemtableTester { schema, builders }
     }
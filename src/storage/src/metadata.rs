@@ -26,8 +26,8 @@ use store_api::storage::consts::{self, ReservedColumnId};
 use store_api::storage::{
     AddColumn, AlterOperation, AlterRequest, ColumnDescriptor, ColumnDescriptorBuilder,
     ColumnDescriptorBuilderError, ColumnFamilyDescriptor, ColumnFamilyDescriptorBuilder,
-    ColumnFamilyId, ColumnId, RegionDescriptor, RegionDescriptorBuilder, RegionId, RegionMeta,
-    RowKeyDescriptor, RowKeyDescriptorBuilder, Schema, SchemaRef,
+    ColumnFamilyId, ColumnId, ModifyColumn, RegionDescriptor, RegionDescriptorBuilder, RegionId,
+    RegionMeta, RowKeyDescriptor, RowKeyDescriptorBuilder, Schema, SchemaRef,
 };
 
 use crate::manifest::action::{RawColumnFamiliesMetadata, RawColumnsMetadata, RawRegionMetadata};
@@ -83,6 +83,37 @@ pub enum Error {
     #[snafu(display("Failed to drop column {} as it is an internal column", name))]
     DropInternalColumn { name: String },
 
+    #[snafu(display("Failed to modify column as there is no column named {}", name))]
+    ModifyAbsentColumn { name: String },
+
+    #[snafu(display("Failed to modify column {} as it is part of key", name))]
+    ModifyKeyColumn { name: String },
+
+    #[snafu(display("Failed to modify column {} as it is an internal column", name))]
+    ModifyInternalColumn { name: String },
+
+    #[snafu(display(
+        "Failed to modify column {} from {:?} type to incompatible {:?} type",
+        name,
+        from,
+        to
+    ))]
+    ModifyColumnType {
+        name: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+    },
+
+    #[snafu(display(
+        "Failed to modify column {} as its default constraint is invalid for {:?} type",
+        name,
+        data_type
+    ))]
+    ModifyColumnDefault {
+        name: String,
+        data_type: ConcreteDataType,
+    },
+
     // End of variants for validating `AlterRequest`.
     #[snafu(display("Failed to convert to column schema, source: {}", source))]
     ToColumnSchema {
@@ -247,6 +278,11 @@ impl RegionMetadata {
                     self.validate_drop_column(name)?;
                 }
             }
+            AlterOperation::ModifyColumns { columns } => {
+                for col in columns {
+                    self.validate_modify_column(col)?;
+                }
+            }
         }
 
         Ok(())
@@ -312,6 +348,50 @@ impl RegionMetadata {
         Ok(())
     }
 
+    fn validate_modify_column(&self, modify_column: &ModifyColumn) -> Result<()> {
+        let name = &modify_column.name;
+        let store_schema = self.schema.store_schema();
+        ensure!(
+            store_schema.contains_column(name),
+            ModifyAbsentColumnSnafu { name }
+        );
+        ensure!(
+            !store_schema.is_key_column(name),
+            ModifyKeyColumnSnafu { name }
+        );
+        ensure!(
+            store_schema.is_user_column(name),
+            ModifyInternalColumnSnafu { name }
+        );
+
+        // Safety: We have checked the column exists.
+        let column = self.columns.column_metadata_by_name(name).unwrap();
+        let data_type = &modify_column.data_type;
+        // Only lossless conversion is allowed, so data in old files could always be
+        // casted to the new type while reading.
+        ensure!(
+            column.desc.data_type.can_widen_to(data_type),
+            ModifyColumnTypeSnafu {
+                name,
+                from: column.desc.data_type.clone(),
+                to: data_type.clone(),
+            }
+        );
+        if let Some(constraint) = column.desc.default_constraint() {
+            ensure!(
+                constraint
+                    .validate(data_type, column.desc.is_nullable())
+                    .is_ok(),
+                ModifyColumnDefaultSnafu {
+                    name,
+                    data_type: data_type.clone(),
+                }
+            );
+        }
+
+        Ok(())
+    }
+
     fn to_descriptor(&self) -> RegionDescriptor {
         let row_key = self.columns.to_row_key_descriptor();
         let mut builder = RegionDescriptorBuilder::default()
@@ -537,6 +617,13 @@ impl ColumnsMetadata {
         &self.columns[idx]
     }
 
+    /// Returns the metadata of column with given `name`.
+    pub fn column_metadata_by_name(&self, name: &str) -> Option<&ColumnMetadata> {
+        self.name_to_col_index
+            .get(name)
+            .map(|idx| &self.columns[*idx])
+    }
+
     fn to_row_key_descriptor(&self) -> RowKeyDescriptor {
         let mut builder =
             RowKeyDescriptorBuilder::default().enable_version_column(self.enable_version_column);
@@ -913,7 +1000,7 @@ mod tests {
     use datatypes::value::Value;
     use store_api::storage::{
         AddColumn, AlterOperation, ColumnDescriptorBuilder, ColumnFamilyDescriptorBuilder,
-        RowKeyDescriptorBuilder,
+        ModifyColumn, RowKeyDescriptorBuilder,
     };
 
     use super::*;
@@ -1374,6 +1461,87 @@ mod tests {
             names: vec![String::from("v0")],
         };
         metadata.validate_alter(&req).unwrap();
+
+        let new_modify_op =
+            |name: &str, data_type: ConcreteDataType| AlterOperation::ModifyColumns {
+                columns: vec![ModifyColumn {
+                    name: name.to_string(),
+                    data_type,
+                }],
+            };
+        // Modify absent column.
+        req.operation = new_modify_op("v2", ConcreteDataType::float64_datatype());
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyAbsentColumn { .. }
+        ));
+
+        // Modify key column.
+        req.operation = new_modify_op("k0", ConcreteDataType::int64_datatype());
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyKeyColumn { .. }
+        ));
+
+        // Modify internal column.
+        req.operation = new_modify_op(
+            consts::OP_TYPE_COLUMN_NAME,
+            ConcreteDataType::uint16_datatype(),
+        );
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyInternalColumn { .. }
+        ));
+
+        // Modify to incompatible type.
+        req.operation = new_modify_op("v0", ConcreteDataType::int32_datatype());
+        assert!(matches!(
+            metadata.validate_alter(&req).err().unwrap(),
+            Error::ModifyColumnType { .. }
+        ));
+
+        // Valid request
+        req.operation = new_modify_op("v0", ConcreteDataType::float64_datatype());
+        metadata.validate_alter(&req).unwrap();
+    }
+
+    #[test]
+    fn test_alter_metadata_modify_columns() {
+        let region_name = "region-0";
+        let metadata: RegionMetadata = RegionDescBuilder::new(region_name)
+            .enable_version_column(false)
+            .push_key_column(("k1", LogicalTypeId::Int32, false))
+            .push_value_column(("v1", LogicalTypeId::Float32, true))
+            .build()
+            .try_into()
+            .unwrap();
+        let column_id = metadata.columns.column_metadata_by_name("v1").unwrap().id();
+
+        let req = AlterRequest {
+            operation: AlterOperation::ModifyColumns {
+                columns: vec![ModifyColumn {
+                    name: String::from("v1"),
+                    data_type: ConcreteDataType::float64_datatype(),
+                }],
+            },
+            version: 0,
+        };
+        metadata.validate_alter(&req).unwrap();
+        let metadata = metadata.alter(&req).unwrap();
+
+        let builder: RegionMetadataBuilder = RegionDescBuilder::new(region_name)
+            .enable_version_column(false)
+            .push_key_column(("k1", LogicalTypeId::Int32, false))
+            .push_value_column(("v1", LogicalTypeId::Float64, true))
+            .build()
+            .try_into()
+            .unwrap();
+        let expect = builder.version(1).build().unwrap();
+        assert_eq!(expect, metadata);
+        assert_eq!(
+            column_id,
+            metadata.columns.column_metadata_by_name("v1").unwrap().id()
+        );
     }
 
     #[test]
@@ -19,16 +19,20 @@ use std::sync::Arc;
 
 use common_base::BitVec;
 use common_error::prelude::*;
+use common_time::timestamp::{TimeUnit, Timestamp};
 use datatypes::data_type::ConcreteDataType;
+use datatypes::json::Json;
 use datatypes::prelude::{ScalarVector, ScalarVectorBuilder};
 use datatypes::schema;
+use datatypes::types::Decimal128Type;
 use datatypes::vectors::{
-    BinaryVector, BinaryVectorBuilder, BooleanVector, BooleanVectorBuilder, Float32Vector,
-    Float32VectorBuilder, Float64Vector, Float64VectorBuilder, Int16Vector, Int16VectorBuilder,
-    Int32Vector, Int32VectorBuilder, Int64Vector, Int64VectorBuilder, Int8Vector,
-    Int8VectorBuilder, StringVector, StringVectorBuilder, TimestampVector, TimestampVectorBuilder,
-    UInt16Vector, UInt16VectorBuilder, UInt32Vector, UInt32VectorBuilder, UInt64Vector,
-    UInt64VectorBuilder, UInt8Vector, UInt8VectorBuilder, Vector, VectorRef,
+    BinaryVector, BinaryVectorBuilder, BooleanVector, BooleanVectorBuilder, Decimal128Vector,
+    Decimal128VectorBuilder, Float32Vector, Float32VectorBuilder, Float64Vector,
+    Float64VectorBuilder, Int16Vector, Int16VectorBuilder, Int32Vector, Int32VectorBuilder,
+    Int64Vector, Int64VectorBuilder, Int8Vector, Int8VectorBuilder, JsonVector, JsonVectorBuilder,
+    StringVector, StringVectorBuilder, TimestampVector, TimestampVectorBuilder, UInt16Vector,
+    UInt16VectorBuilder, UInt32Vector, UInt32VectorBuilder, UInt64Vector, UInt64VectorBuilder,
+    UInt8Vector, UInt8VectorBuilder, Vector, VectorRef,
 };
 use paste::paste;
 use snafu::OptionExt;
@@ -52,6 +56,12 @@ pub enum Error {
         #[snafu(backtrace)]
         source: datatypes::error::Error,
     },
+
+    #[snafu(display("Invalid json value, source: {}", source))]
+    InvalidJson {
+        #[snafu(backtrace)]
+        source: datatypes::error::Error,
+    },
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
@@ -107,6 +117,13 @@ impl From<&schema::ColumnSchema> for ColumnSchema {
             data_type: DataType::from(&cs.data_type).into(),
             is_nullable: cs.is_nullable(),
             is_time_index: cs.is_time_index(),
+            decimal_type: match &cs.data_type {
+                ConcreteDataType::Decimal128(t) => Some(DecimalType {
+                    precision: t.precision as u32,
+                    scale: t.scale as u32,
+                }),
+                _ => None,
+            },
         }
     }
 }
@@ -116,9 +133,15 @@ impl TryFrom<&ColumnSchema> for schema::ColumnSchema {
 
     fn try_from(column_schema: &ColumnSchema) -> Result<Self> {
         if let Some(data_type) = DataType::from_i32(column_schema.data_type) {
+            let data_type = match (data_type, &column_schema.decimal_type) {
+                (DataType::Decimal128, Some(t)) => ConcreteDataType::Decimal128(
+                    Decimal128Type::new(t.precision as u8, t.scale as u8),
+                ),
+                _ => data_type.into(),
+            };
             Ok(schema::ColumnSchema::new(
                 column_schema.name.clone(),
-                data_type.into(),
+                data_type,
                 column_schema.is_nullable,
             )
             .with_time_index(column_schema.is_time_index))
@@ -148,7 +171,14 @@ impl From<&ConcreteDataType> for DataType {
             ConcreteDataType::String(_) => DataType::String,
             ConcreteDataType::Null(_) => DataType::Null,
             ConcreteDataType::Binary(_) => DataType::Binary,
-            ConcreteDataType::Timestamp(_) => DataType::Timestamp,
+            ConcreteDataType::Timestamp(t) => match t.unit {
+                TimeUnit::Second => DataType::TimestampSecond,
+                TimeUnit::Millisecond => DataType::Timestamp,
+                TimeUnit::Microsecond => DataType::TimestampMicrosecond,
+                TimeUnit::Nanosecond => DataType::TimestampNanosecond,
+            },
+            ConcreteDataType::Decimal128(_) => DataType::Decimal128,
+            ConcreteDataType::Json(_) => DataType::Json,
             ConcreteDataType::Date(_)
             | ConcreteDataType::DateTime(_)
             | ConcreteDataType::List(_) => {
@@ -177,6 +207,15 @@ impl From<DataType> for ConcreteDataType {
             DataType::Binary => ConcreteDataType::binary_datatype(),
             DataType::Null => ConcreteDataType::null_datatype(),
             DataType::Timestamp => ConcreteDataType::timestamp_millis_datatype(),
+            DataType::TimestampSecond => ConcreteDataType::timestamp_datatype(TimeUnit::Second),
+            DataType::TimestampMicrosecond => {
+                ConcreteDataType::timestamp_datatype(TimeUnit::Microsecond)
+            }
+            DataType::TimestampNanosecond => {
+                ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond)
+            }
+            DataType::Decimal128 => ConcreteDataType::Decimal128(Decimal128Type::default()),
+            DataType::Json => ConcreteDataType::json_datatype(),
         }
     }
 }
@@ -240,6 +279,8 @@ gen_columns!(bool, BooleanVector, v, v);
 gen_columns!(binary, BinaryVector, v, v.to_vec());
 gen_columns!(string, StringVector, v, v.to_string());
 gen_columns!(timestamp, TimestampVector, v, v.value());
+gen_columns!(decimal128, Decimal128Vector, v, v.value().into());
+gen_columns!(json, JsonVector, v, v.as_bytes().to_vec());
 
 #[macro_export]
 macro_rules! gen_put_data {
@@ -287,7 +328,107 @@ gen_put_data!(f64, Float64VectorBuilder, v, *v as f64);
 gen_put_data!(bool, BooleanVectorBuilder, v, *v);
 gen_put_data!(binary, BinaryVectorBuilder, v, v.as_slice());
 gen_put_data!(string, StringVectorBuilder, v, v.as_str());
-gen_put_data!(timestamp, TimestampVectorBuilder, v, (*v).into());
+
+/// Builds a json vector from column, fails if any value is not a valid encoded document.
+pub fn gen_put_data_json(column: Column) -> Result<VectorRef> {
+    let values = column.values.context(EmptyColumnValuesSnafu {})?;
+    let mut vector_iter = values
+        .json_values
+        .iter()
+        .map(|v| Json::from_bytes(v).context(InvalidJsonSnafu));
+    let num_rows = column.num_rows as usize;
+    let mut builder = JsonVectorBuilder::with_capacity(num_rows);
+
+    if column.value_null_mask.is_empty() {
+        for _ in 0..num_rows {
+            let json = vector_iter.next().transpose()?;
+            builder.push(json.as_ref().map(Json::as_json_ref));
+        }
+    } else {
+        for is_null in BitVec::from_vec(column.value_null_mask)
+            .into_iter()
+            .take(num_rows)
+        {
+            if is_null {
+                builder.push(None);
+            } else {
+                let json = vector_iter.next().transpose()?;
+                builder.push(json.as_ref().map(Json::as_json_ref));
+            }
+        }
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
+
+/// Builds a timestamp vector from column, the raw values are in the time unit of the schema.
+pub fn gen_put_data_timestamp(column: Column, unit: TimeUnit) -> Result<VectorRef> {
+    let values = column.values.context(EmptyColumnValuesSnafu {})?;
+    let mut vector_iter = values.timestamp_values.iter();
+    let num_rows = column.num_rows as usize;
+    let mut builder = TimestampVectorBuilder::with_capacity_and_unit(num_rows, unit);
+
+    if column.value_null_mask.is_empty() {
+        (0..num_rows)
+            .for_each(|_| builder.push(vector_iter.next().map(|v| Timestamp::new(*v, unit))));
+    } else {
+        BitVec::from_vec(column.value_null_mask)
+            .into_iter()
+            .take(num_rows)
+            .for_each(|is_null| {
+                if is_null {
+                    builder.push(None);
+                } else {
+                    builder.push(vector_iter.next().map(|v| Timestamp::new(*v, unit)));
+                }
+            });
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
+
+impl From<i128> for Decimal128 {
+    fn from(value: i128) -> Self {
+        Self {
+            hi: (value >> 64) as i64,
+            lo: value as i64,
+        }
+    }
+}
+
+impl From<&Decimal128> for i128 {
+    fn from(value: &Decimal128) -> Self {
+        ((value.hi as i128) << 64) | (value.lo as u64 as i128)
+    }
+}
+
+/// Builds a decimal vector from column, the raw values are in the precision and scale of the schema.
+pub fn gen_put_data_decimal128(column: Column, data_type: Decimal128Type) -> Result<VectorRef> {
+    let values = column.values.context(EmptyColumnValuesSnafu {})?;
+    let mut vector_iter = values.decimal128_values.iter();
+    let num_rows = column.num_rows as usize;
+    let mut builder = Decimal128VectorBuilder::with_capacity_and_type(num_rows, data_type);
+    let to_decimal = |v: &Decimal128| {
+        datatypes::decimal::Decimal128::new(v.into(), data_type.precision, data_type.scale)
+    };
+
+    if column.value_null_mask.is_empty() {
+        (0..num_rows).for_each(|_| builder.push(vector_iter.next().map(to_decimal)));
+    } else {
+        BitVec::from_vec(column.value_null_mask)
+            .into_iter()
+            .take(num_rows)
+            .for_each(|is_null| {
+                if is_null {
+                    builder.push(None);
+                } else {
+                    builder.push(vector_iter.next().map(to_decimal));
+                }
+            });
+    }
+
+    Ok(Arc::new(builder.finish()))
+}
 
 pub fn gen_columns(vector: &VectorRef) -> Result<Column> {
     let data_type = vector.data_type();
@@ -306,6 +447,8 @@ pub fn gen_columns(vector: &VectorRef) -> Result<Column> {
         ConcreteDataType::Binary(_) => gen_columns_binary(vector),
         ConcreteDataType::String(_) => gen_columns_string(vector),
         ConcreteDataType::Timestamp(_) => gen_columns_timestamp(vector),
+        ConcreteDataType::Decimal128(_) => gen_columns_decimal128(vector),
+        ConcreteDataType::Json(_) => gen_columns_json(vector),
         ConcreteDataType::Null(_)
         | ConcreteDataType::Date(_)
         | ConcreteDataType::DateTime(_)
@@ -331,7 +474,9 @@ pub fn gen_put_data_vector(data_type: ConcreteDataType, column: Column) -> Resul
         ConcreteDataType::Float64(_) => gen_put_data_f64(column),
         ConcreteDataType::Binary(_) => gen_put_data_binary(column),
         ConcreteDataType::String(_) => gen_put_data_string(column),
-        ConcreteDataType::Timestamp(_) => gen_put_data_timestamp(column),
+        ConcreteDataType::Timestamp(t) => gen_put_data_timestamp(column, t.unit),
+        ConcreteDataType::Decimal128(t) => gen_put_data_decimal128(column, t),
+        ConcreteDataType::Json(_) => gen_put_data_json(column),
         ConcreteDataType::Null(_)
         | ConcreteDataType::Date(_)
         | ConcreteDataType::DateTime(_)
@@ -341,3 +486,55 @@ pub fn gen_put_data_vector(data_type: ConcreteDataType, column: Column) -> Resul
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decimal128_column_round_trip() {
+        let data_type = Decimal128Type::new(20, 3);
+        let mut builder = Decimal128VectorBuilder::with_capacity_and_type(3, data_type);
+        for v in [Some(10i128.pow(19) + 7), None, Some(-12345)] {
+            builder.push(v.map(|v| datatypes::decimal::Decimal128::new(v, 20, 3)));
+        }
+        let vector: VectorRef = Arc::new(builder.finish());
+
+        let column = gen_columns(&vector).unwrap();
+        let vector2 = gen_put_data_vector(ConcreteDataType::Decimal128(data_type), column).unwrap();
+        assert_eq!(vector, vector2);
+
+        let column_schema =
+            schema::ColumnSchema::new("price", ConcreteDataType::Decimal128(data_type), true);
+        let pb_schema = ColumnSchema::from(&column_schema);
+        assert_eq!(DataType::Decimal128 as i32, pb_schema.data_type);
+        assert_eq!(
+            column_schema,
+            schema::ColumnSchema::try_from(&pb_schema).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_column_round_trip() {
+        let vector: VectorRef = Arc::new(JsonVector::from(vec![
+            Some(r#"{"a": [1, "b"]}"#.parse().unwrap()),
+            None,
+            Some("null".parse().unwrap()),
+        ]));
+
+        let column = gen_columns(&vector).unwrap();
+        let vector2 = gen_put_data_vector(ConcreteDataType::json_datatype(), column).unwrap();
+        assert_eq!(vector, vector2);
+    }
+
+    #[test]
+    fn test_put_data_invalid_json() {
+        let vector: VectorRef =
+            Arc::new(JsonVector::from(vec![Some(r#"{"a": 1}"#.parse().unwrap())]));
+        let mut column = gen_columns(&vector).unwrap();
+        column.values.as_mut().unwrap().json_values[0].truncate(3);
+
+        let err = gen_put_data_vector(ConcreteDataType::json_datatype(), column).unwrap_err();
+        assert!(matches!(err, Error::InvalidJson { .. }), "{:?}", err);
+    }
+}
@@ -24,8 +24,8 @@ use snafu::ResultExt;
 use store_api::logstore::LogStore;
 use store_api::manifest::{self, Manifest, ManifestVersion, MetaActionIterator};
 use store_api::storage::{
-    AlterRequest, OpenOptions, ReadContext, Region, RegionId, RegionMeta, SequenceNumber,
-    WriteContext, WriteResponse,
+    AlterRequest, OpenOptions, ReadContext, Region, RegionId, RegionMeta, RegionStat,
+    SequenceNumber, WriteContext, WriteResponse,
 };
 
 use crate::error::{self, Error, Result};
@@ -45,6 +45,7 @@ use crate::version::{
 };
 use crate::wal::Wal;
 use crate::write_batch::WriteBatch;
+use crate::write_buffer::{FlushRequester, WriteBufferManagerRef};
 
 /// [Region] implementation.
 #[derive(Debug)]
@@ -97,6 +98,10 @@ impl<S: LogStore> Region for RegionImpl<S> {
     async fn alter(&self, request: AlterRequest) -> Result<()> {
         self.inner.alter(request).await
     }
+
+    fn stats(&self) -> RegionStat {
+        self.inner.stats()
+    }
 }
 
 /// Storage related config for region.
@@ -110,6 +115,7 @@ pub struct StoreConfig<S> {
     pub memtable_builder: MemtableBuilderRef,
     pub flush_scheduler: FlushSchedulerRef,
     pub flush_strategy: FlushStrategyRef,
+    pub write_buffer_manager: WriteBufferManagerRef,
 }
 
 pub type RecoverdMetadata = (SequenceNumber, (ManifestVersion, RawRegionMetadata));
@@ -159,13 +165,18 @@ impl<S: LogStore> RegionImpl<S> {
                 name,
                 version_control: Arc::new(version_control),
             }),
-            writer: Arc::new(RegionWriter::new(store_config.memtable_builder)),
+            writer: Arc::new(RegionWriter::new(
+                store_config.memtable_builder,
+                store_config.write_buffer_manager.clone(),
+            )),
             wal,
             flush_strategy: store_config.flush_strategy,
             flush_scheduler: store_config.flush_scheduler,
             sst_layer: store_config.sst_layer,
             manifest: store_config.manifest,
+            write_buffer_manager: store_config.write_buffer_manager,
         });
+        inner.register_to_write_buffer_manager();
 
         RegionImpl { inner }
     }
@@ -231,7 +242,10 @@ impl<S: LogStore> RegionImpl<S> {
             version_control,
         });
 
-        let writer = Arc::new(RegionWriter::new(store_config.memtable_builder));
+        let writer = Arc::new(RegionWriter::new(
+            store_config.memtable_builder,
+            store_config.write_buffer_manager.clone(),
+        ));
         let writer_ctx = WriterContext {
             shared: &shared,
             flush_strategy: &store_config.flush_strategy,
@@ -254,7 +268,9 @@ impl<S: LogStore> RegionImpl<S> {
             flush_scheduler: store_config.flush_scheduler,
             sst_layer: store_config.sst_layer,
             manifest: store_config.manifest,
+            write_buffer_manager: store_config.write_buffer_manager,
         });
+        inner.register_to_write_buffer_manager();
 
         Ok(Some(RegionImpl { inner }))
     }
@@ -425,6 +441,7 @@ struct RegionInner<S: LogStore> {
     flush_scheduler: FlushSchedulerRef,
     sst_layer: AccessLayerRef,
     manifest: RegionManifest,
+    write_buffer_manager: WriteBufferManagerRef,
 }
 
 impl<S: LogStore> RegionInner<S> {
@@ -439,6 +456,35 @@ impl<S: LogStore> RegionInner<S> {
         RegionMetaImpl::new(metadata)
     }
 
+    /// Registers the region to the write buffer manager and reports its memory usage.
+    fn register_to_write_buffer_manager(self: &Arc<Self>) {
+        let requester = Arc::downgrade(self);
+        self.write_buffer_manager
+            .register_region(self.shared.id, requester);
+        self.writer.report_memory_usage(&self.shared);
+    }
+
+    fn stats(&self) -> RegionStat {
+        let version = self.version_control().current();
+        let memtables = version.memtables();
+        let mut stat = RegionStat {
+            approximate_rows: 0,
+            approximate_bytes: memtables.total_bytes_allocated() as u64,
+        };
+        for memtable in
+            std::iter::once(memtables.mutable_memtable()).chain(memtables.immutable_memtables())
+        {
+            stat.approximate_rows += memtable.num_rows() as u64;
+        }
+        for level in version.ssts().levels() {
+            for file in level.files() {
+                stat.approximate_rows += file.num_rows();
+                stat.approximate_bytes += file.file_size();
+            }
+        }
+        stat
+    }
+
     fn create_snapshot(&self) -> SnapshotImpl {
         let version = self.version_control().current();
         let sequence = self.version_control().committed_sequence();
@@ -488,3 +534,26 @@ impl<S: LogStore> RegionInner<S> {
         self.writer.alter(alter_ctx, request).await
     }
 }
+
+#[async_trait]
+impl<S: LogStore> FlushRequester for RegionInner<S> {
+    async fn request_flush(&self) -> Result<()> {
+        let writer_ctx = WriterContext {
+            shared: &self.shared,
+            flush_strategy: &self.flush_strategy,
+            flush_scheduler: &self.flush_scheduler,
+            sst_layer: &self.sst_layer,
+            wal: &self.wal,
+            writer: &self.writer,
+            manifest: &self.manifest,
+        };
+
+        self.writer.flush(writer_ctx).await
+    }
+}
+
+impl<S: LogStore> Drop for RegionInner<S> {
+    fn drop(&mut self) {
+        self.write_buffer_manager.unregister_region(self.shared.id);
+    }
+}
@@ -23,13 +23,15 @@ use common_telemetry::logging;
 use common_time::timestamp::Timestamp;
 use datatypes::prelude::ScalarVector;
 use datatypes::type_id::LogicalTypeId;
+use datatypes::value::Value;
 use datatypes::vectors::{Int64Vector, TimestampVector};
 use log_store::fs::log::LocalFileLogStore;
 use log_store::fs::noop::NoopLogStore;
 use object_store::backend::fs;
 use object_store::ObjectStore;
 use store_api::storage::{
-    consts, Chunk, ChunkReader, PutOperation, ScanRequest, SequenceNumber, Snapshot, WriteRequest,
+    consts, Chunk, ChunkReader, GetRequest, PutOperation, ScanRequest, SequenceNumber, Snapshot,
+    WriteRequest,
 };
 use tempdir::TempDir;
 
@@ -120,6 +122,26 @@ impl<S: LogStore> TesterBase<S> {
         dst
     }
 
+    /// Get value of v0 by timestamp, returns `None` if the row is not found.
+    pub async fn get(&self, ts: i64) -> Option<Option<i64>> {
+        let snapshot = self.region.snapshot(&self.read_ctx).unwrap();
+
+        let request = GetRequest {
+            key: vec![Value::Timestamp(ts.into())],
+            ..Default::default()
+        };
+        let resp = snapshot.get(&self.read_ctx, request).await.unwrap();
+
+        resp.row.map(|row| {
+            assert_eq!(Value::Timestamp(ts.into()), row[0]);
+            match &row[1] {
+                Value::Int64(v) => Some(*v),
+                Value::Null => None,
+                v => panic!("Unexpected value {:?}", v),
+            }
+        })
+    }
+
     pub fn committed_sequence(&self) -> SequenceNumber {
         self.region.committed_sequence()
     }
@@ -14,11 +14,14 @@
 
 //! Region read/write tests.
 
+use datatypes::value::Value;
 use log_store::fs::log::LocalFileLogStore;
-use store_api::storage::{OpenOptions, SequenceNumber, WriteResponse};
+use store_api::storage::{
+    GetRequest, OpenOptions, ReadContext, Region, SequenceNumber, Snapshot, WriteResponse,
+};
 use tempdir::TempDir;
 
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::region::tests::{self, FileTesterBase};
 use crate::region::RegionImpl;
 use crate::test_util::config_util;
@@ -103,6 +106,10 @@ impl Tester {
         self.base().full_scan().await
     }
 
+    async fn get(&self, ts: i64) -> Option<Option<i64>> {
+        self.base().get(ts).await
+    }
+
     fn committed_sequence(&self) -> SequenceNumber {
         self.base().committed_sequence()
     }
@@ -202,3 +209,74 @@ async fn test_scan_different_batch() {
         assert_eq!(data, output);
     }
 }
+
+#[tokio::test]
+async fn test_concurrent_put() {
+    let dir = TempDir::new("concurrent-put").unwrap();
+    let store_dir = dir.path().to_str().unwrap();
+    let mut tester = Tester::new(REGION_NAME, store_dir).await;
+
+    let data: Vec<_> = (0..100).map(|i| (i, Some(i))).collect();
+    let puts = data.chunks(1).map(|chunk| tester.put(chunk));
+    futures::future::join_all(puts).await;
+
+    // Each WAL write bumps the sequence once. The first put holds the write lock while its WAL
+    // entry is being written, so the puts queued meanwhile must be committed together.
+    let committed_sequence = tester.committed_sequence();
+    assert!(
+        committed_sequence < data.len() as SequenceNumber,
+        "{} puts are committed with {} sequences",
+        data.len(),
+        committed_sequence
+    );
+    let output = tester.full_scan().await;
+    assert_eq!(data, output);
+
+    tester.reopen().await;
+    let output = tester.full_scan().await;
+    assert_eq!(data, output);
+}
+
+#[tokio::test]
+async fn test_put_get() {
+    let dir = TempDir::new("put-get").unwrap();
+    let store_dir = dir.path().to_str().unwrap();
+    let tester = Tester::new(REGION_NAME, store_dir).await;
+
+    tester.put(&[(1000, Some(100)), (2000, None)]).await;
+    let sequence = tester.committed_sequence();
+    tester.put(&[(1000, Some(101))]).await;
+
+    assert_eq!(Some(Some(101)), tester.get(1000).await);
+    assert_eq!(Some(None), tester.get(2000).await);
+    assert_eq!(None, tester.get(3000).await);
+
+    // Get with an old sequence.
+    let snapshot = tester
+        .base()
+        .region
+        .snapshot(&ReadContext::default())
+        .unwrap();
+    let request = GetRequest {
+        key: vec![Value::Timestamp(1000.into())],
+        sequence: Some(sequence),
+        ..Default::default()
+    };
+    let resp = snapshot
+        .get(&ReadContext::default(), request)
+        .await
+        .unwrap();
+    let expect = vec![Value::Timestamp(1000.into()), Value::Int64(100)];
+    assert_eq!(Some(expect), resp.row);
+
+    // Invalid key.
+    let request = GetRequest {
+        key: vec![Value::Int64(1000)],
+        ..Default::default()
+    };
+    let err = snapshot
+        .get(&ReadContext::default(), request)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::InvalidGetKey { .. }));
+}
@@ -16,6 +16,7 @@
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 use log_store::fs::log::LocalFileLogStore;
 use store_api::storage::{OpenOptions, WriteResponse};
@@ -26,6 +27,7 @@ use crate::flush::{FlushStrategy, FlushStrategyRef};
 use crate::region::tests::{self, FileTesterBase};
 use crate::region::{RegionImpl, SharedDataRef};
 use crate::test_util::config_util;
+use crate::write_buffer::WriteBufferManager;
 
 const REGION_NAME: &str = "region-flush-0";
 
@@ -88,6 +90,10 @@ impl FlushTester {
         self.base().full_scan().await
     }
 
+    async fn get(&self, ts: i64) -> Option<Option<i64>> {
+        self.base().get(ts).await
+    }
+
     async fn wait_flush_done(&self) {
         self.base().region.wait_flush_done().await.unwrap();
     }
@@ -258,6 +264,12 @@ async fn test_merge_read_after_flush() {
     let output = tester.full_scan().await;
     assert_eq!(expect, output);
 
+    // Get rows from both SSTs and memtables.
+    assert_eq!(Some(Some(300)), tester.get(3000).await);
+    assert_eq!(Some(Some(203)), tester.get(2000).await);
+    assert_eq!(Some(Some(100)), tester.get(1000).await);
+    assert_eq!(None, tester.get(4000).await);
+
     // Reopen
     let mut tester = tester;
     tester.reopen().await;
@@ -266,3 +278,57 @@ async fn test_merge_read_after_flush() {
     let output = tester.full_scan().await;
     assert_eq!(expect, output);
 }
+
+#[tokio::test]
+async fn test_flush_by_write_buffer_manager() {
+    let dir = TempDir::new("flush-write-buffer").unwrap();
+    let store_dir = dir.path().to_str().unwrap();
+
+    let metadata = tests::new_metadata(REGION_NAME, false);
+    let mut store_config = config_util::new_store_config(REGION_NAME, store_dir).await;
+    store_config.flush_strategy = Arc::new(FlushSwitch::default());
+    // Any data in mutable memtable exceeds the global write buffer size.
+    store_config.write_buffer_manager =
+        Arc::new(WriteBufferManager::new(1, usize::MAX, Duration::ZERO));
+    let region = RegionImpl::create(metadata, store_config).await.unwrap();
+    let tester = FileTesterBase::with_region(region);
+
+    tester.put(&[(1000, Some(100))]).await;
+    // Put element to trigger flush.
+    tester.put(&[(2000, Some(200))]).await;
+    tester.region.wait_flush_done().await.unwrap();
+
+    let sst_dir = format!("{}/{}", store_dir, engine::region_sst_dir("", REGION_NAME));
+    assert!(has_parquet_file(&sst_dir));
+
+    let expect = vec![(1000, Some(100)), (2000, Some(200))];
+    let output = tester.full_scan().await;
+    assert_eq!(expect, output);
+}
+
+#[tokio::test]
+async fn test_write_stall_flushes_region() {
+    let dir = TempDir::new("write-stall-flush").unwrap();
+    let store_dir = dir.path().to_str().unwrap();
+
+    let metadata = tests::new_metadata(REGION_NAME, false);
+    let mut store_config = config_util::new_store_config(REGION_NAME, store_dir).await;
+    store_config.flush_strategy = Arc::new(FlushSwitch::default());
+    // Any data in memtables stalls writes.
+    store_config.write_buffer_manager =
+        Arc::new(WriteBufferManager::new(1, 1, Duration::from_secs(10)));
+    let region = RegionImpl::create(metadata, store_config).await.unwrap();
+    let tester = FileTesterBase::with_region(region);
+
+    tester.put(&[(1000, Some(100))]).await;
+    // The write is stalled until the region flushes the data of the previous write, which
+    // needs the write lock of the region.
+    tester.put(&[(2000, Some(200))]).await;
+
+    let sst_dir = format!("{}/{}", store_dir, engine::region_sst_dir("", REGION_NAME));
+    assert!(has_parquet_file(&sst_dir));
+
+    let expect = vec![(1000, Some(100)), (2000, Some(200))];
+    let output = tester.full_scan().await;
+    assert_eq!(expect, output);
+}
@@ -12,7 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::sync::Arc;
+use std::fmt;
+use std::sync::{Arc, Mutex as SyncMutex};
 
 use common_telemetry::logging;
 use futures::TryStreamExt;
@@ -20,10 +21,11 @@ use snafu::ResultExt;
 use store_api::logstore::LogStore;
 use store_api::manifest::{Manifest, ManifestVersion, MetaAction};
 use store_api::storage::{AlterRequest, SequenceNumber, WriteContext, WriteResponse};
-use tokio::sync::Mutex;
+use tokio::sync::oneshot::error::TryRecvError;
+use tokio::sync::{oneshot, Mutex};
 
 use crate::background::JobHandle;
-use crate::error::{self, Result};
+use crate::error::{self, Error, Result};
 use crate::flush::{FlushJob, FlushSchedulerRef, FlushStrategyRef};
 use crate::manifest::action::{
     RawRegionMetadata, RegionChange, RegionEdit, RegionMetaAction, RegionMetaActionList,
@@ -36,11 +38,12 @@ use crate::schema::compat::CompatWrite;
 use crate::sst::AccessLayerRef;
 use crate::version::{VersionControl, VersionControlRef, VersionEdit};
 use crate::wal::{Payload, Wal};
-use crate::write_batch::WriteBatch;
+use crate::write_batch::{WriteBatch, MAX_BATCH_SIZE};
+use crate::write_buffer::WriteBufferManagerRef;
 
 pub type RegionWriterRef = Arc<RegionWriter>;
 
-// TODO(yingwen): Add benches for write and support group commit to improve write throughput.
+// TODO(yingwen): Add benches for write.
 
 /// Region writer manages all write operations to the region.
 #[derive(Debug)]
@@ -53,27 +56,81 @@ pub struct RegionWriter {
     ///
     /// Increasing committed sequence should be guarded by this lock.
     version_mutex: Mutex<()>,
+    /// Write requests waiting for the write lock, which would be committed together
+    /// by the next writer that holds the write lock.
+    pending_writes: SyncMutex<Vec<PendingWrite>>,
+    write_buffer_manager: WriteBufferManagerRef,
 }
 
 impl RegionWriter {
-    pub fn new(memtable_builder: MemtableBuilderRef) -> RegionWriter {
+    pub fn new(
+        memtable_builder: MemtableBuilderRef,
+        write_buffer_manager: WriteBufferManagerRef,
+    ) -> RegionWriter {
         RegionWriter {
-            inner: Mutex::new(WriterInner::new(memtable_builder)),
+            inner: Mutex::new(WriterInner::new(
+                memtable_builder,
+                write_buffer_manager.clone(),
+            )),
             version_mutex: Mutex::new(()),
+            pending_writes: SyncMutex::new(Vec::new()),
+            write_buffer_manager,
         }
     }
 
     /// Write to region in the write lock.
+    ///
+    /// Concurrent writes are committed in groups. The request is queued first, then the
+    /// writer that acquires the write lock takes all queued requests and commits them with
+    /// one WAL entry and one memtable insertion. Each writer gets the result of its own
+    /// request.
     pub async fn write<S: LogStore>(
         &self,
         ctx: &WriteContext,
         request: WriteBatch,
         writer_ctx: WriterContext<'_, S>,
     ) -> Result<WriteResponse> {
+        // Waits for memory before acquiring the write lock, since the flush that releases the
+        // memory of this region also needs the write lock.
+        self.write_buffer_manager
+            .wait_for_memory(writer_ctx.shared.name())
+            .await?;
+
+        let (sender, mut receiver) = oneshot::channel();
+        self.pending_writes
+            .lock()
+            .unwrap()
+            .push(PendingWrite { request, sender });
+
         let mut inner = self.inner.lock().await;
+        // Our request might already be committed by the previous group, the result is always
+        // sent before the write lock is released.
+        match receiver.try_recv() {
+            Ok(result) => return result,
+            Err(TryRecvError::Closed) => return error::CancelledSnafu.fail(),
+            Err(TryRecvError::Empty) => (),
+        }
+
+        // Now we are the leader of the group, which must contain our request.
+        let group = std::mem::take(&mut *self.pending_writes.lock().unwrap());
         inner
-            .write(&self.version_mutex, ctx, request, writer_ctx)
-            .await
+            .write_group(&self.version_mutex, ctx, group, writer_ctx)
+            .await;
+
+        receiver
+            .try_recv()
+            .map_err(|_| error::CancelledSnafu.build())?
+    }
+
+    /// Freeze the mutable memtable and schedule a flush job in the write lock.
+    pub async fn flush<S: LogStore>(&self, writer_ctx: WriterContext<'_, S>) -> Result<()> {
+        let mut inner = self.inner.lock().await;
+        inner.trigger_flush(&writer_ctx).await
+    }
+
+    /// Reports memory usage of memtables in the region to the write buffer manager.
+    pub(crate) fn report_memory_usage(&self, shared: &SharedDataRef) {
+        report_memory_usage(&self.write_buffer_manager, shared);
     }
 
     /// Replay data to memtables.
@@ -129,6 +186,8 @@ impl RegionWriter {
         // We could tolerate failure during persisting manifest version to the WAL, since it won't
         // affect how we applying the edit to the version.
         version_control.apply_edit(version_edit);
+        // Flushed memtables are removed, so some memory is released.
+        self.report_memory_usage(shared);
         // TODO(yingwen): We should set the flush handle to `None`, but we can't acquire
         // write lock here.
 
@@ -196,6 +255,7 @@ impl RegionWriter {
             manifest_version,
             new_mutable,
         );
+        self.report_memory_usage(alter_ctx.shared);
 
         self.persist_manifest_version(alter_ctx.wal, version_control, manifest_version)
             .await
@@ -266,43 +326,135 @@ impl<'a, S: LogStore> AlterContext<'a, S> {
     }
 }
 
+type WriteResultSender = oneshot::Sender<Result<WriteResponse>>;
+
+/// A write request waiting to be committed.
+struct PendingWrite {
+    request: WriteBatch,
+    sender: WriteResultSender,
+}
+
+impl fmt::Debug for PendingWrite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingWrite")
+            .field("num_rows", &self.request.num_rows())
+            .finish()
+    }
+}
+
+/// Sends the error that fails the whole group to all writers in it.
+fn notify_error(senders: impl IntoIterator<Item = WriteResultSender>, err: Error) {
+    let err = Arc::new(err);
+    for sender in senders {
+        let _ = sender.send(Err(Error::WriteGroup {
+            source: err.clone(),
+        }));
+    }
+}
+
+/// Reports memory usage of memtables in the region to the write buffer manager.
+fn report_memory_usage(write_buffer_manager: &WriteBufferManagerRef, shared: &SharedDataRef) {
+    let current = shared.version_control.current();
+    let memtables = current.memtables();
+    write_buffer_manager.update_region_usage(
+        shared.id(),
+        memtables.mutable_bytes_allocated(),
+        memtables.total_bytes_allocated(),
+    );
+}
+
 #[derive(Debug)]
 struct WriterInner {
     memtable_builder: MemtableBuilderRef,
     flush_handle: Option<JobHandle>,
+    write_buffer_manager: WriteBufferManagerRef,
 }
 
 impl WriterInner {
-    fn new(memtable_builder: MemtableBuilderRef) -> WriterInner {
+    fn new(
+        memtable_builder: MemtableBuilderRef,
+        write_buffer_manager: WriteBufferManagerRef,
+    ) -> WriterInner {
         WriterInner {
             memtable_builder,
             flush_handle: None,
+            write_buffer_manager,
         }
     }
 
-    /// Write `WriteBatch` to region, now the schema of batch needs to be validated outside.
+    /// Write a group of `WriteBatch` to region, now the schema of batch needs to be validated
+    /// outside.
+    ///
+    /// Batches in the group are merged and committed with one sequence, so they share the same
+    /// WAL entry. The result of each request is sent back to its writer.
     ///
     /// Mutable reference of writer ensure no other reference of this writer can modify the
     /// version control (write is exclusive).
-    async fn write<S: LogStore>(
+    async fn write_group<S: LogStore>(
         &mut self,
         version_mutex: &Mutex<()>,
         _ctx: &WriteContext,
-        mut request: WriteBatch,
+        group: Vec<PendingWrite>,
         writer_ctx: WriterContext<'_, S>,
-    ) -> Result<WriteResponse> {
-        self.preprocess_write(&writer_ctx).await?;
+    ) {
+        if let Err(e) = self.preprocess_write(&writer_ctx).await {
+            notify_error(group.into_iter().map(|w| w.sender), e);
+            return;
+        }
         let version_control = writer_ctx.version_control();
 
         let _lock = version_mutex.lock().await;
 
         let metadata = version_control.metadata();
-        // We need to check the schema again since it might has been altered. We need
-        // to compat request's schema before writing it into the WAL otherwise some
-        // default constraint like `current_timestamp()` would yield different value
-        // during replay.
-        request.compat_write(metadata.schema().user_schema())?;
+        let user_schema = metadata.schema().user_schema();
+        // Merged batches and the senders of requests in each batch.
+        let mut batches: Vec<(WriteBatch, Vec<WriteResultSender>)> = Vec::new();
+        for PendingWrite {
+            mut request,
+            sender,
+        } in group
+        {
+            // We need to check the schema again since it might has been altered. We need
+            // to compat request's schema before writing it into the WAL otherwise some
+            // default constraint like `current_timestamp()` would yield different value
+            // during replay.
+            if let Err(e) = request.compat_write(user_schema) {
+                let _ = sender.send(Err(e));
+                continue;
+            }
+
+            let can_merge = matches!(
+                batches.last(),
+                Some((batch, _)) if batch.num_rows() + request.num_rows() <= MAX_BATCH_SIZE
+            );
+            if can_merge {
+                let (batch, senders) = batches.last_mut().unwrap();
+                batch.merge(request);
+                senders.push(sender);
+            } else {
+                batches.push((request, vec![sender]));
+            }
+        }
+
+        for (batch, senders) in batches {
+            match self.commit_batch(&writer_ctx, &batch).await {
+                Ok(()) => {
+                    for sender in senders {
+                        let _ = sender.send(Ok(WriteResponse {}));
+                    }
+                }
+                Err(e) => notify_error(senders, e),
+            }
+        }
+    }
 
+    /// Commit a `WriteBatch` with next sequence, the caller should hold the `version_mutex`.
+    async fn commit_batch<S: LogStore>(
+        &self,
+        writer_ctx: &WriterContext<'_, S>,
+        request: &WriteBatch,
+    ) -> Result<()> {
+        let version_control = writer_ctx.version_control();
         let committed_sequence = version_control.committed_sequence();
         // Sequence for current write batch.
         let next_sequence = committed_sequence + 1;
@@ -311,22 +463,19 @@ impl WriterInner {
         let wal_header = WalHeader::with_last_manifest_version(version.manifest_version());
         writer_ctx
             .wal
-            .write_to_wal(
-                next_sequence,
-                wal_header,
-                Payload::WriteBatchArrow(&request),
-            )
+            .write_to_wal(next_sequence, wal_header, Payload::WriteBatchArrow(request))
             .await?;
 
         // Insert batch into memtable.
         let mut inserter = Inserter::new(next_sequence);
-        inserter.insert_memtable(&request, version.mutable_memtable())?;
+        inserter.insert_memtable(request, version.mutable_memtable())?;
 
         // Update committed_sequence to make current batch visible. The `&mut self` of WriterInner
         // guarantees the writer is exclusive.
         version_control.set_committed_sequence(next_sequence);
+        report_memory_usage(&self.write_buffer_manager, writer_ctx.shared);
 
-        Ok(WriteResponse {})
+        Ok(())
     }
 
     async fn replay<S: LogStore>(
@@ -399,6 +548,7 @@ impl WriterInner {
                     // out of memory during replay, but we need to do it carefully to avoid dead lock.
                     let mut inserter = Inserter::new(last_sequence);
                     inserter.insert_memtable(&request, version.mutable_memtable())?;
+                    report_memory_usage(&self.write_buffer_manager, writer_ctx.shared);
                 }
             }
 
@@ -477,7 +627,7 @@ impl WriterInner {
     /// Preprocess before write.
     ///
     /// Creates needed mutable memtables, ensures there is enough capacity in memtable and trigger
-    /// flush if necessary. Returns time ranges of the input write batch.
+    /// flush if necessary.
     async fn preprocess_write<S: LogStore>(
         &mut self,
         writer_ctx: &WriterContext<'_, S>,
@@ -491,6 +641,15 @@ impl WriterInner {
             writer_ctx.flush_strategy,
         ) {
             self.trigger_flush(writer_ctx).await?;
+        } else if let Some(region_id) = self.write_buffer_manager.region_to_flush() {
+            // Mutable memtables of all regions exceed the global write buffer size, flush the
+            // region with largest mutable memtable.
+            if region_id == writer_ctx.shared.id() {
+                self.trigger_flush(writer_ctx).await?;
+            } else {
+                // We can't flush other region in our write lock, so let the manager schedule it.
+                self.write_buffer_manager.schedule_flush(region_id);
+            }
         }
 
         Ok(())
@@ -520,6 +679,7 @@ impl WriterInner {
         let new_mutable = self.alloc_memtable(version_control);
         // Freeze all mutable memtables so we can flush them later.
         version_control.freeze_mutable(new_mutable);
+        report_memory_usage(&self.write_buffer_manager, ctx.shared);
 
         if let Some(flush_handle) = self.flush_handle.take() {
             // Previous flush job is incomplete, wait util it is finished (write stall).
@@ -18,7 +18,9 @@ use std::sync::Arc;
 
 use datatypes::arrow::array::Array;
 use datatypes::arrow::chunk::Chunk;
+use datatypes::arrow::compute::cast::{self, CastOptions};
 use datatypes::arrow::datatypes::Field;
+use datatypes::data_type::{ConcreteDataType, DataType};
 use datatypes::schema::SchemaRef;
 use datatypes::vectors::{Helper, VectorRef};
 use snafu::{ensure, OptionExt, ResultExt};
@@ -39,6 +41,27 @@ pub trait CompatWrite {
     fn compat_write(&mut self, dest_schema: &SchemaRef) -> Result<()>;
 }
 
+/// Casts `vector` of column `column` to `data_type`, returns the `vector` directly if
+/// it already has the expected type.
+pub(crate) fn cast_vector(
+    column: &str,
+    vector: &VectorRef,
+    data_type: &ConcreteDataType,
+) -> Result<VectorRef> {
+    if vector.data_type() == *data_type {
+        return Ok(vector.clone());
+    }
+
+    let array = vector.to_arrow_array();
+    let casted = cast::cast(&*array, &data_type.as_arrow_type(), CastOptions::default()).context(
+        error::CastColumnSnafu {
+            column,
+            data_type: data_type.clone(),
+        },
+    )?;
+    Helper::try_into_vector(casted).context(error::ConvertChunkSnafu { name: column })
+}
+
 /// Checks whether column with `source_column` could be read as a column with `dest_column`.
 ///
 /// Returns
@@ -64,8 +87,12 @@ fn is_source_column_compatible(
         return Ok(false);
     }
 
+    // Data of a column whose type has been modified would be casted to the new type.
     ensure!(
-        source_column.desc.data_type == dest_column.desc.data_type,
+        source_column
+            .desc
+            .data_type
+            .can_widen_to(&dest_column.desc.data_type),
         error::CompatReadSnafu {
             reason: format!(
                 "could not read column {} from {:?} type as {:?} type",
@@ -302,7 +329,7 @@ impl ReadAdapter {
             .zip(column_schemas)
             .map(|(index_opt, column_schema)| {
                 if let Some(idx) = index_opt {
-                    Ok(source[*idx].clone())
+                    cast_vector(&column_schema.name, &source[*idx], &column_schema.data_type)
                 } else {
                     let vector = column_schema
                         .create_default_vector(num_rows)
@@ -323,7 +350,7 @@ impl ReadAdapter {
 
 #[cfg(test)]
 mod tests {
-    use datatypes::data_type::ConcreteDataType;
+    use datatypes::vectors::{Float64Vector, Int32Vector, Int64Vector};
     use store_api::storage::{consts, ColumnDescriptorBuilder};
 
     use super::*;
@@ -582,6 +609,57 @@ mod tests {
         check_batch_with_null_padding(&batch, &new_batch, &[2]);
     }
 
+    #[test]
+    fn test_compat_modified_column() {
+        // (k0, timestamp, v0, v1) with version 0.
+        let region_schema_old = Arc::new(schema_util::new_region_schema(0, 2));
+
+        let mut descriptor = descriptor_util::desc_with_value_columns(tests::REGION_NAME, 2);
+        // Modify the type of v0 from int64 to float64.
+        descriptor.default_cf.columns[0].data_type = ConcreteDataType::float64_datatype();
+        let metadata: RegionMetadata = descriptor.try_into().unwrap();
+        let columns = metadata.columns;
+        // (k0, timestamp, v0, v1) with version 1.
+        let region_schema_new = Arc::new(RegionSchema::new(columns, 1).unwrap());
+
+        let projected_schema = Arc::new(ProjectedSchema::no_projection(region_schema_new));
+        let source_schema = region_schema_old.store_schema().clone();
+        let adapter = ReadAdapter::new(source_schema, projected_schema).unwrap();
+
+        assert_eq!(&[true, true], adapter.source_key_needed());
+        assert_eq!(&[true, true], adapter.source_value_needed());
+
+        let batch = tests::new_batch_with_num_values(2);
+        let check_batch = |new_batch: &Batch| {
+            assert_eq!(batch.num_columns(), new_batch.num_columns());
+            for (i, (old, new)) in batch.columns().iter().zip(new_batch.columns()).enumerate() {
+                if i == 2 {
+                    // v0 of the source batch is filled by 0.
+                    let expect: VectorRef = Arc::new(Float64Vector::from_slice(&[0.0, 0.0, 0.0]));
+                    assert_eq!(expect, *new);
+                } else {
+                    assert_eq!(old, new);
+                }
+            }
+        };
+
+        let new_batch = call_batch_from_parts(&adapter, &batch, 2);
+        check_batch(&new_batch);
+        let new_batch = call_arrow_chunk_to_batch(&adapter, &batch);
+        check_batch(&new_batch);
+    }
+
+    #[test]
+    fn test_cast_vector() {
+        let vector: VectorRef = Arc::new(Int32Vector::from(vec![Some(1), None, Some(3)]));
+        let same = cast_vector("v0", &vector, &ConcreteDataType::int32_datatype()).unwrap();
+        assert_eq!(vector, same);
+
+        let casted = cast_vector("v0", &vector, &ConcreteDataType::int64_datatype()).unwrap();
+        let expect: VectorRef = Arc::new(Int64Vector::from(vec![Some(1), None, Some(3)]));
+        assert_eq!(expect, casted);
+    }
+
     #[inline]
     fn new_column_desc_builder() -> ColumnDescriptorBuilder {
         ColumnDescriptorBuilder::new(10, "test", ConcreteDataType::int32_datatype())
@@ -603,6 +681,27 @@ mod tests {
             .unwrap();
         let dest = ColumnMetadata { cf_id: 1, desc };
         assert!(!is_source_column_compatible(&source, &dest).unwrap());
+
+        // Type could be widened.
+        let desc = new_column_desc_builder()
+            .data_type(ConcreteDataType::int64_datatype())
+            .build()
+            .unwrap();
+        let dest = ColumnMetadata { cf_id: 1, desc };
+        assert!(is_source_column_compatible(&source, &dest).unwrap());
+
+        // Type could not be narrowed.
+        let desc = new_column_desc_builder()
+            .data_type(ConcreteDataType::int16_datatype())
+            .build()
+            .unwrap();
+        let dest = ColumnMetadata { cf_id: 1, desc };
+        let err = is_source_column_compatible(&source, &dest).unwrap_err();
+        assert!(
+            matches!(err, Error::CompatRead { .. }),
+            "{:?} is not CompatRead",
+            err
+        );
     }
 
     #[test]
@@ -101,6 +101,11 @@ impl RegionSchema {
         self.user_schema.version()
     }
 
+    #[inline]
+    pub(crate) fn timestamp_key_index(&self) -> usize {
+        self.columns.timestamp_key_index()
+    }
+
     #[inline]
     pub(crate) fn row_key_end(&self) -> usize {
         self.columns.row_key_end()
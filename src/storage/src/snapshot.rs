@@ -13,15 +13,20 @@
 // limitations under the License.
 
 use std::cmp;
+use std::cmp::Ordering;
 
 use async_trait::async_trait;
+use datatypes::value::Value;
+use snafu::ensure;
 use store_api::storage::{
     GetRequest, GetResponse, ReadContext, ScanRequest, ScanResponse, SchemaRef, SequenceNumber,
     Snapshot,
 };
 
 use crate::chunk::{ChunkReaderBuilder, ChunkReaderImpl};
-use crate::error::{Error, Result};
+use crate::error::{self, Error, Result};
+use crate::read::{Batch, BatchReader, BoxedBatchReader};
+use crate::schema::{ProjectedSchemaRef, RegionSchema};
 use crate::sst::AccessLayerRef;
 use crate::version::VersionRef;
 
@@ -71,8 +76,48 @@ impl Snapshot for SnapshotImpl {
         Ok(ScanResponse { reader })
     }
 
-    async fn get(&self, _ctx: &ReadContext, _request: GetRequest) -> Result<GetResponse> {
-        unimplemented!()
+    async fn get(&self, ctx: &ReadContext, request: GetRequest) -> Result<GetResponse> {
+        let schema = self.version.schema();
+        validate_key(schema, &request.key)?;
+
+        let visible_sequence = self.sequence_to_read(request.sequence);
+        let memtable_version = self.version.memtables();
+
+        // Rows in memtables are always newer than rows in SSTs, so we only need to
+        // search SSTs if the key isn't found in memtables.
+        let mut builder = ChunkReaderBuilder::new(schema.clone(), self.sst_layer.clone())
+            .reserve_num_memtables(memtable_version.num_memtables())
+            .projection(request.projection.clone())
+            .batch_size(ctx.batch_size)
+            .visible_sequence(visible_sequence)
+            .pick_memtables(memtable_version.mutable_memtable().clone());
+        for memtable in memtable_version.immutable_memtables() {
+            builder = builder.pick_memtables(memtable.clone());
+        }
+        let (projected_schema, reader) = builder.build_batch_reader().await?;
+        if let Some(row) = find_row(&projected_schema, reader, &request.key).await? {
+            return Ok(GetResponse { row: Some(row) });
+        }
+
+        // Use the key to prune row groups by statistics.
+        let filters = table::predicate::equal_exprs(
+            schema
+                .row_key_columns()
+                .map(|column| column.desc.name.as_str())
+                .zip(request.key.iter()),
+        );
+        let (projected_schema, reader) =
+            ChunkReaderBuilder::new(schema.clone(), self.sst_layer.clone())
+                .projection(request.projection)
+                .filters(filters)
+                .batch_size(ctx.batch_size)
+                .visible_sequence(visible_sequence)
+                .pick_ssts(self.version.ssts())?
+                .build_batch_reader()
+                .await?;
+        let row = find_row(&projected_schema, reader, &request.key).await?;
+
+        Ok(GetResponse { row })
     }
 }
 
@@ -96,3 +141,85 @@ impl SnapshotImpl {
             .unwrap_or(self.visible_sequence)
     }
 }
+
+fn validate_key(schema: &RegionSchema, key: &[Value]) -> Result<()> {
+    ensure!(
+        key.len() == schema.num_row_key_columns(),
+        error::InvalidGetKeySnafu {
+            msg: format!(
+                "expect {} row key columns, given: {}",
+                schema.num_row_key_columns(),
+                key.len()
+            ),
+        }
+    );
+
+    for (column, value) in schema.row_key_columns().zip(key) {
+        if value.is_null() {
+            ensure!(
+                column.desc.is_nullable(),
+                error::InvalidGetKeySnafu {
+                    msg: format!("column {} is not nullable", column.desc.name),
+                }
+            );
+        } else {
+            ensure!(
+                value.data_type() == column.desc.data_type,
+                error::InvalidGetKeySnafu {
+                    msg: format!(
+                        "type of column {} is {:?}, given: {:?}",
+                        column.desc.name,
+                        column.desc.data_type,
+                        value.data_type()
+                    ),
+                }
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the row with given `key` from the `reader` and returns values of its projected
+/// columns.
+///
+/// Batches from the reader must be deduplicated and ordered by row key.
+async fn find_row(
+    schema: &ProjectedSchemaRef,
+    mut reader: BoxedBatchReader,
+    key: &[Value],
+) -> Result<Option<Vec<Value>>> {
+    while let Some(batch) = reader.next_batch().await? {
+        for i in 0..batch.num_rows() {
+            match compare_row_key(schema, &batch, i, key) {
+                Ordering::Less => continue,
+                Ordering::Equal => {
+                    let chunk = schema.batch_to_chunk(&batch);
+                    let row = chunk.columns.iter().map(|column| column.get(i)).collect();
+                    return Ok(Some(row));
+                }
+                // Rows are ordered by row key, so the key is absent.
+                Ordering::Greater => return Ok(None),
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Compares row key of the `i`th row in `batch` with `key`.
+fn compare_row_key(
+    schema: &ProjectedSchemaRef,
+    batch: &Batch,
+    i: usize,
+    key: &[Value],
+) -> Ordering {
+    for (idx, value) in schema.schema_to_read().row_key_indices().zip(key) {
+        let order = batch.column(idx).get_ref(i).cmp(&value.as_value_ref());
+        if order != Ordering::Equal {
+            return order;
+        }
+    }
+
+    Ordering::Equal
+}
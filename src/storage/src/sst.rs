@@ -118,7 +118,6 @@ impl LevelMeta {
         visitor.visit(self.level.into(), &self.files)
     }
 
-    #[cfg(test)]
     pub fn files(&self) -> &[FileHandle] {
         &self.files
     }
@@ -156,6 +155,16 @@ impl FileHandle {
     pub fn file_name(&self) -> &str {
         &self.inner.meta.file_name
     }
+
+    #[inline]
+    pub fn num_rows(&self) -> u64 {
+        self.inner.meta.num_rows
+    }
+
+    #[inline]
+    pub fn file_size(&self) -> u64 {
+        self.inner.meta.file_size
+    }
 }
 
 /// Actually data of [FileHandle].
@@ -173,11 +182,24 @@ impl FileHandleInner {
 }
 
 /// Immutable metadata of a sst file.
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct FileMeta {
     pub file_name: String,
     /// SST level of the file.
     pub level: u8,
+    /// Number of rows in the file, zero for files written before it is recorded.
+    #[serde(default)]
+    pub num_rows: u64,
+    /// Size of the file in bytes, zero for files written before it is recorded.
+    #[serde(default)]
+    pub file_size: u64,
+}
+
+/// Info of the sst file written.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SstInfo {
+    pub num_rows: u64,
+    pub file_size: u64,
 }
 
 #[derive(Debug, Default)]
@@ -204,7 +226,7 @@ pub trait AccessLayer: Send + Sync + std::fmt::Debug {
         file_name: &str,
         iter: BoxedBatchIterator,
         opts: &WriteOptions,
-    ) -> Result<()>;
+    ) -> Result<SstInfo>;
 
     /// Read SST file with given `file_name` and schema.
     async fn read_sst(&self, file_name: &str, opts: &ReadOptions) -> Result<BoxedBatchReader>;
@@ -240,14 +262,13 @@ impl AccessLayer for FsAccessLayer {
         file_name: &str,
         iter: BoxedBatchIterator,
         opts: &WriteOptions,
-    ) -> Result<()> {
+    ) -> Result<SstInfo> {
         // Now we only supports parquet format. We may allow caller to specific SST format in
         // WriteOptions in the future.
         let file_path = self.sst_file_path(file_name);
         let writer = ParquetWriter::new(&file_path, iter, self.object_store.clone());
 
-        writer.write_sst(opts).await?;
-        Ok(())
+        writer.write_sst(opts).await
     }
 
     async fn read_sst(&self, file_name: &str, opts: &ReadOptions) -> Result<BoxedBatchReader> {
@@ -66,14 +66,14 @@ impl<'a> ParquetWriter<'a> {
         }
     }
 
-    pub async fn write_sst(self, _opts: &sst::WriteOptions) -> Result<()> {
+    pub async fn write_sst(self, _opts: &sst::WriteOptions) -> Result<sst::SstInfo> {
         self.write_rows(None).await
     }
 
     /// Iterates memtable and writes rows to Parquet file.
     /// A chunk of records yielded from each iteration with a size given
     /// in config will be written to a single row group.
-    async fn write_rows(self, extra_meta: Option<HashMap<String, String>>) -> Result<()> {
+    async fn write_rows(self, extra_meta: Option<HashMap<String, String>>) -> Result<sst::SstInfo> {
         let projected_schema = self.iter.schema();
         let store_schema = projected_schema.schema_to_read();
         let schema = store_schema.arrow_schema();
@@ -83,7 +83,7 @@ impl<'a> ParquetWriter<'a> {
 
         // now all physical types use plain encoding, maybe let caller to choose encoding for each type.
         let encodings = get_encoding_for_schema(schema, |_| Encoding::Plain);
-        try_join!(
+        let (_, num_rows) = try_join!(
             async {
                 // FIXME(hl): writer size is not used in fs backend so just leave it to 0,
                 // but in s3/azblob backend the Content-Length field of HTTP request is set
@@ -107,8 +107,10 @@ impl<'a> ParquetWriter<'a> {
                 )
                 .context(error::WriteParquetSnafu)?;
 
+                let mut num_rows = 0;
                 for batch in self.iter {
                     let batch = batch?;
+                    num_rows += batch.num_rows() as u64;
                     sink.send(store_schema.batch_to_arrow_chunk(&batch))
                         .await
                         .context(error::WriteParquetSnafu)?;
@@ -135,9 +137,22 @@ impl<'a> ParquetWriter<'a> {
                     .context(error::WriteObjectSnafu {
                         path: self.file_path,
                     })
+                    .map(|_| num_rows)
             }
-        )
-        .map(|_| ())
+        )?;
+
+        let file_size = object
+            .metadata()
+            .await
+            .context(error::ReadObjectSnafu {
+                path: self.file_path,
+            })?
+            .content_length();
+
+        Ok(sst::SstInfo {
+            num_rows,
+            file_size,
+        })
     }
 }
 
@@ -359,10 +374,17 @@ mod tests {
         let iter = memtable.iter(&IterContext::default()).unwrap();
         let writer = ParquetWriter::new(sst_file_name, iter, object_store);
 
-        writer
+        let sst_info = writer
             .write_sst(&sst::WriteOptions::default())
             .await
             .unwrap();
+        assert_eq!(6, sst_info.num_rows);
+        assert_eq!(
+            std::fs::metadata(dir.path().join(sst_file_name))
+                .unwrap()
+                .len(),
+            sst_info.file_size
+        );
 
         // verify parquet file
 
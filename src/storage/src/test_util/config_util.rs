@@ -26,6 +26,7 @@ use crate::manifest::region::RegionManifest;
 use crate::memtable::DefaultMemtableBuilder;
 use crate::region::StoreConfig;
 use crate::sst::FsAccessLayer;
+use crate::write_buffer::WriteBufferManager;
 
 fn log_store_dir(store_dir: &str) -> String {
     format!("{}/logstore", store_dir)
@@ -59,5 +60,6 @@ pub async fn new_store_config(
         memtable_builder: Arc::new(DefaultMemtableBuilder::default()),
         flush_scheduler,
         flush_strategy: Arc::new(SizeBasedStrategy::default()),
+        write_buffer_manager: Arc::new(WriteBufferManager::default()),
     }
 }
@@ -20,6 +20,7 @@ use std::slice;
 use std::time::Duration;
 
 use common_error::prelude::*;
+use common_time::timestamp::TimeUnit;
 use common_time::timestamp_millis::BucketAligned;
 use common_time::RangeMillis;
 use datatypes::arrow::error::ArrowError;
@@ -158,7 +159,7 @@ pub enum Error {
 pub type Result<T> = std::result::Result<T, Error>;
 
 /// Max number of updates of a write batch.
-const MAX_BATCH_SIZE: usize = 1_000_000;
+pub(crate) const MAX_BATCH_SIZE: usize = 1_000_000;
 
 impl ErrorExt for Error {
     fn status_code(&self) -> StatusCode {
@@ -200,6 +201,9 @@ impl WriteRequest for WriteBatch {
 
     /// Aligns timestamps in write batch specified by schema to durations.
     ///
+    /// Timestamps in any time unit are converted to milliseconds, rounding towards negative
+    /// infinity, before alignment.
+    ///
     /// A negative timestamp means "before Unix epoch".
     /// Valid timestamp range is `[i64::MIN + duration, i64::MAX-(i64::MAX%duration))`.
     fn time_ranges(&self, duration: Duration) -> Result<Vec<RangeMillis>> {
@@ -223,8 +227,9 @@ impl WriteRequest for WriteBatch {
                             Value::Timestamp(ts) => ts,
                             _ => unreachable!(),
                         };
-                        let aligned = align_timestamp(ts.value(), durations_millis)
-                            .context(TimestampOverflowSnafu { ts: ts.value() })?;
+                        let ts = ts.convert_to(TimeUnit::Millisecond);
+                        let aligned = align_timestamp(ts, durations_millis)
+                            .context(TimestampOverflowSnafu { ts })?;
 
                         aligned_timestamps.insert(aligned);
                     } else {
@@ -233,8 +238,9 @@ impl WriteRequest for WriteBatch {
                                 let ts_vector =
                                     column.as_any().downcast_ref::<TimestampVector>().unwrap();
                                 for ts in ts_vector.iter_data().flatten() {
-                                    let aligned = align_timestamp(ts.value(), durations_millis)
-                                        .context(TimestampOverflowSnafu { ts: ts.value() })?;
+                                    let ts = ts.convert_to(TimeUnit::Millisecond);
+                                    let aligned = align_timestamp(ts, durations_millis)
+                                        .context(TimestampOverflowSnafu { ts })?;
                                     aligned_timestamps.insert(aligned);
                                 }
                             }
@@ -304,6 +310,24 @@ impl WriteBatch {
     pub fn is_empty(&self) -> bool {
         self.mutations.is_empty()
     }
+
+    /// Returns number of rows in this batch.
+    pub fn num_rows(&self) -> usize {
+        self.num_rows
+    }
+
+    /// Appends all mutations of `other` to the end of this batch.
+    ///
+    /// The caller should ensure both batches have the same schema, e.g. by
+    /// compatting them to the region's schema first, and the total number of
+    /// rows does not exceed [MAX_BATCH_SIZE].
+    pub(crate) fn merge(&mut self, other: WriteBatch) {
+        debug_assert_eq!(self.schema.version(), other.schema.version());
+        debug_assert!(self.num_rows + other.num_rows <= MAX_BATCH_SIZE);
+
+        self.num_rows += other.num_rows;
+        self.mutations.extend(other.mutations);
+    }
 }
 
 /// Enum to wrap different operations.
@@ -785,6 +809,7 @@ mod tests {
     use std::iter;
     use std::sync::Arc;
 
+    use datatypes::schema::SchemaBuilder;
     use datatypes::type_id::LogicalTypeId;
     use datatypes::vectors::{
         BooleanVector, ConstantVector, Int32Vector, Int64Vector, UInt64Vector,
@@ -864,6 +889,32 @@ mod tests {
         assert_eq!(3, put_data.num_rows());
     }
 
+    #[test]
+    fn test_write_batch_merge() {
+        let new_put_data = || {
+            let intv = Arc::new(UInt64Vector::from_slice(&[1, 2, 3]));
+            let boolv = Arc::new(BooleanVector::from(vec![true, false, true]));
+            let tsv = Arc::new(TimestampVector::from_vec(vec![0, 0, 0]));
+
+            let mut put_data = PutData::new();
+            put_data.add_key_column("k1", intv.clone()).unwrap();
+            put_data.add_version_column(intv).unwrap();
+            put_data.add_value_column("v1", boolv).unwrap();
+            put_data.add_key_column("ts", tsv).unwrap();
+            put_data
+        };
+
+        let mut batch = new_test_batch();
+        batch.put(new_put_data()).unwrap();
+        let mut other = new_test_batch();
+        other.put(new_put_data()).unwrap();
+        other.put(new_put_data()).unwrap();
+
+        batch.merge(other);
+        assert_eq!(9, batch.num_rows());
+        assert_eq!(3, batch.iter().count());
+    }
+
     fn check_err(err: Error, msg: &str) {
         assert_eq!(StatusCode::InvalidArguments, err.status_code());
         assert!(err.backtrace_opt().is_some());
@@ -1044,6 +1095,52 @@ mod tests {
         )
     }
 
+    #[test]
+    pub fn test_write_batch_time_range_nanosecond() {
+        let schema = Arc::new(
+            SchemaBuilder::try_from(vec![
+                ColumnSchema::new("k1", ConcreteDataType::uint64_datatype(), false),
+                ColumnSchema::new(
+                    consts::VERSION_COLUMN_NAME,
+                    ConcreteDataType::uint64_datatype(),
+                    false,
+                ),
+                ColumnSchema::new(
+                    "ts",
+                    ConcreteDataType::timestamp_datatype(TimeUnit::Nanosecond),
+                    false,
+                )
+                .with_time_index(true),
+            ])
+            .unwrap()
+            .build()
+            .unwrap(),
+        );
+        let intv = Arc::new(UInt64Vector::from_slice(&[1, 2, 3, 4]));
+        // -1ns belongs to [-20ms, 0ms), 20ms + 1ns belongs to [20ms, 40ms).
+        let tsv = Arc::new(TimestampVector::from_values_with_unit(
+            [-1, 0, 19_999_999, 20_000_001],
+            TimeUnit::Nanosecond,
+        ));
+
+        let mut put_data = PutData::new();
+        put_data.add_key_column("k1", intv.clone()).unwrap();
+        put_data.add_version_column(intv).unwrap();
+        put_data.add_key_column("ts", tsv).unwrap();
+
+        let mut batch = WriteBatch::new(schema);
+        batch.put(put_data).unwrap();
+
+        let duration_millis = 20i64;
+        let ranges = batch
+            .time_ranges(Duration::from_millis(duration_millis as u64))
+            .unwrap();
+        assert_eq!(
+            [-20, 0, 20].map(|v| RangeMillis::new(v, v + duration_millis).unwrap()),
+            ranges.as_slice()
+        )
+    }
+
     fn gen_new_batch_and_types() -> (WriteBatch, Vec<i32>) {
         let mut batch = new_test_batch();
         for i in 0..10 {
@@ -16,7 +16,7 @@ use datatypes::schema::{ColumnSchema, SchemaRef};
 use snafu::{ensure, ResultExt};
 
 use crate::error::{self, Result};
-use crate::schema::compat::CompatWrite;
+use crate::schema::compat::{self, CompatWrite};
 use crate::write_batch::{Mutation, PutData, WriteBatch};
 
 impl CompatWrite for WriteBatch {
@@ -70,7 +70,11 @@ impl CompatWrite for PutData {
         }
 
         for column_schema in dest_schema.column_schemas() {
-            if self.column_by_name(&column_schema.name).is_none() {
+            if let Some(vector) = self.columns.get_mut(&column_schema.name) {
+                // The type of the column might be modified, so we cast it to the new type.
+                *vector =
+                    compat::cast_vector(&column_schema.name, vector, &column_schema.data_type)?;
+            } else {
                 // We need to fill the column by null or its default value.
                 self.add_default_by_name(column_schema)
                     .context(error::AddDefaultSnafu {
@@ -99,7 +103,7 @@ mod tests {
 
     use datatypes::data_type::ConcreteDataType;
     use datatypes::schema::{ColumnDefaultConstraint, SchemaBuilder};
-    use datatypes::vectors::{Int32Vector, TimestampVector};
+    use datatypes::vectors::{Int32Vector, Int64Vector, TimestampVector, VectorRef};
     use store_api::storage::{PutOperation, WriteRequest};
 
     use super::*;
@@ -170,6 +174,31 @@ mod tests {
         put_data.column_by_name("v0").unwrap();
     }
 
+    #[test]
+    fn test_put_data_compat_write_modified_column() {
+        let mut put_data = new_put_data();
+        put_data
+            .add_value_column("v0", Arc::new(Int32Vector::from_slice(&[4, 5, 6])))
+            .unwrap();
+        let column_schemas = vec![
+            ColumnSchema::new("k0", ConcreteDataType::int32_datatype(), false),
+            ColumnSchema::new("ts", ConcreteDataType::timestamp_millis_datatype(), false)
+                .with_time_index(true),
+            ColumnSchema::new("v0", ConcreteDataType::int64_datatype(), true),
+        ];
+        let schema = Arc::new(
+            SchemaBuilder::try_from(column_schemas)
+                .unwrap()
+                .build()
+                .unwrap(),
+        );
+
+        put_data.compat_write(&schema).unwrap();
+        let v0 = put_data.column_by_name("v0").unwrap();
+        let expect: VectorRef = Arc::new(Int64Vector::from_slice(&[4, 5, 6]));
+        assert_eq!(expect, *v0);
+    }
+
     #[test]
     fn test_write_batch_compat_to_old() {
         let schema_old = new_test_schema(None);
@@ -0,0 +1,366 @@
+// Copyright 2022 Greptime Team
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memory management of memtables across all regions of an engine.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use common_telemetry::logging;
+use store_api::storage::RegionId;
+use tokio::sync::Notify;
+use tokio::time::Instant;
+
+use crate::error::{self, Result};
+
+/// Region that could be requested to flush its memtables.
+#[async_trait]
+pub trait FlushRequester: Send + Sync + std::fmt::Debug {
+    /// Freezes the mutable memtable of the region and schedules a flush job.
+    async fn request_flush(&self) -> Result<()>;
+}
+
+pub type FlushRequesterRef = Weak<dyn FlushRequester>;
+
+/// Memtable memory usage of a region.
+#[derive(Debug)]
+struct RegionUsage {
+    /// Bytes allocated by the mutable memtable.
+    mutable: usize,
+    /// Bytes allocated by all memtables, including the mutable one.
+    total: usize,
+    /// Whether a flush has been requested and not finished yet.
+    flush_requested: bool,
+    requester: FlushRequesterRef,
+}
+
+/// Tracks memtable memory of all regions in an engine.
+///
+/// The manager triggers flush of the region with largest mutable memtable if
+/// the mutable memory of all regions exceeds `flush_limit`, and stalls writes
+/// if memory of all memtables exceeds `stall_limit`.
+#[derive(Debug)]
+pub struct WriteBufferManager {
+    /// Mutable memory size that triggers flush.
+    flush_limit: usize,
+    /// Total memory size that stalls writes.
+    stall_limit: usize,
+    /// Max duration to wait for memory before rejecting the write.
+    max_stall_duration: Duration,
+    regions: Mutex<HashMap<RegionId, RegionUsage>>,
+    /// Bytes allocated by mutable memtables of all regions.
+    mutable_usage: AtomicUsize,
+    /// Bytes allocated by all memtables of all regions.
+    memory_usage: AtomicUsize,
+    /// Notifies stalled writers that some memory is released.
+    memory_released: Notify,
+}
+
+pub type WriteBufferManagerRef = Arc<WriteBufferManager>;
+
+impl Default for WriteBufferManager {
+    fn default() -> WriteBufferManager {
+        WriteBufferManager::new(usize::MAX, usize::MAX, Duration::ZERO)
+    }
+}
+
+impl WriteBufferManager {
+    pub fn new(
+        flush_limit: usize,
+        stall_limit: usize,
+        max_stall_duration: Duration,
+    ) -> WriteBufferManager {
+        WriteBufferManager {
+            flush_limit,
+            stall_limit,
+            max_stall_duration,
+            regions: Mutex::new(HashMap::new()),
+            mutable_usage: AtomicUsize::new(0),
+            memory_usage: AtomicUsize::new(0),
+            memory_released: Notify::new(),
+        }
+    }
+
+    /// Returns bytes allocated by all memtables.
+    #[inline]
+    pub fn memory_usage(&self) -> usize {
+        self.memory_usage.load(Ordering::Relaxed)
+    }
+
+    /// Returns bytes allocated by mutable memtables.
+    #[inline]
+    pub fn mutable_usage(&self) -> usize {
+        self.mutable_usage.load(Ordering::Relaxed)
+    }
+
+    /// Registers a region to the manager.
+    pub fn register_region(&self, region_id: RegionId, requester: FlushRequesterRef) {
+        let mut regions = self.regions.lock().unwrap();
+        regions.insert(
+            region_id,
+            RegionUsage {
+                mutable: 0,
+                total: 0,
+                flush_requested: false,
+                requester,
+            },
+        );
+    }
+
+    /// Removes the region from the manager and releases its memory.
+    pub fn unregister_region(&self, region_id: RegionId) {
+        let mut regions = self.regions.lock().unwrap();
+        if let Some(usage) = regions.remove(&region_id) {
+            self.mutable_usage
+                .fetch_sub(usage.mutable, Ordering::Relaxed);
+            self.memory_usage.fetch_sub(usage.total, Ordering::Relaxed);
+        }
+        self.memory_released.notify_waiters();
+    }
+
+    /// Updates memory usage of the region.
+    pub fn update_region_usage(&self, region_id: RegionId, mutable: usize, total: usize) {
+        let mut regions = self.regions.lock().unwrap();
+        let usage = match regions.get_mut(&region_id) {
+            Some(v) => v,
+            // The region isn't registered, e.g. created for tests.
+            None => return,
+        };
+
+        // Always update the counters under the lock so they are consistent
+        // with usage of regions.
+        self.mutable_usage.fetch_add(mutable, Ordering::Relaxed);
+        self.mutable_usage
+            .fetch_sub(usage.mutable, Ordering::Relaxed);
+        self.memory_usage.fetch_add(total, Ordering::Relaxed);
+        self.memory_usage.fetch_sub(usage.total, Ordering::Relaxed);
+
+        let released = total < usage.total;
+        usage.mutable = mutable;
+        usage.total = total;
+
+        if released {
+            self.memory_released.notify_waiters();
+        }
+    }
+
+    /// Returns the region with largest mutable memtable if mutable memory of all
+    /// regions exceeds the flush limit.
+    pub fn region_to_flush(&self) -> Option<RegionId> {
+        if self.mutable_usage() < self.flush_limit {
+            return None;
+        }
+
+        let regions = self.regions.lock().unwrap();
+        regions
+            .iter()
+            .filter(|(_, usage)| !usage.flush_requested && usage.mutable > 0)
+            .max_by_key(|(_, usage)| usage.mutable)
+            .map(|(region_id, _)| *region_id)
+    }
+
+    /// Requests the region to flush in background.
+    pub fn schedule_flush(self: &Arc<Self>, region_id: RegionId) {
+        let requester = {
+            let mut regions = self.regions.lock().unwrap();
+            let usage = match regions.get_mut(&region_id) {
+                Some(v) => v,
+                None => return,
+            };
+            match usage.requester.upgrade() {
+                Some(requester) => {
+                    usage.flush_requested = true;
+                    requester
+                }
+                None => {
+                    // The region has been dropped.
+                    drop(regions);
+                    self.unregister_region(region_id);
+                    return;
+                }
+            }
+        };
+
+        logging::info!(
+            "Schedule flush of region {} by write buffer manager, mutable_usage: {}, flush_limit: {}",
+            region_id,
+            self.mutable_usage(),
+            self.flush_limit
+        );
+
+        let manager = self.clone();
+        common_runtime::spawn_bg(async move {
+            if let Err(e) = requester.request_flush().await {
+                logging::error!(e; "Failed to flush region {}", region_id);
+            }
+
+            let mut regions = manager.regions.lock().unwrap();
+            if let Some(usage) = regions.get_mut(&region_id) {
+                usage.flush_requested = false;
+            }
+        });
+    }
+
+    /// Waits until memory of all memtables is below the stall limit, flushing the region with
+    /// largest mutable memtable if needed.
+    ///
+    /// Must not be called in the write lock of a region, as flushing the region needs the lock.
+    /// Returns error if memory is still above the limit after the max stall duration.
+    pub async fn wait_for_memory(self: &Arc<Self>, region_name: &str) -> Result<()> {
+        if self.memory_usage() < self.stall_limit {
+            return Ok(());
+        }
+        if let Some(region_id) = self.region_to_flush() {
+            self.schedule_flush(region_id);
+        }
+
+        logging::info!(
+            "Write stall, region: {}, memory_usage: {}, stall_limit: {}",
+            region_name,
+            self.memory_usage(),
+            self.stall_limit
+        );
+
+        let deadline = Instant::now() + self.max_stall_duration;
+        loop {
+            // Create the future before checking the usage so we won't miss the notification.
+            let released = self.memory_released.notified();
+            let memory_usage = self.memory_usage();
+            if memory_usage < self.stall_limit {
+                return Ok(());
+            }
+
+            if tokio::time::timeout_at(deadline, released).await.is_err() {
+                return error::WriteStallSnafu {
+                    region: region_name,
+                    memory_usage: self.memory_usage(),
+                    limit: self.stall_limit,
+                }
+                .fail();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct MockRequester {
+        flushed: Notify,
+    }
+
+    #[async_trait]
+    impl FlushRequester for MockRequester {
+        async fn request_flush(&self) -> Result<()> {
+            self.flushed.notify_one();
+            Ok(())
+        }
+    }
+
+    fn new_requester() -> (Arc<MockRequester>, FlushRequesterRef) {
+        let requester = Arc::new(MockRequester::default());
+        let weak = Arc::downgrade(&requester) as FlushRequesterRef;
+        (requester, weak)
+    }
+
+    #[test]
+    fn test_update_region_usage() {
+        let manager = WriteBufferManager::new(100, 200, Duration::ZERO);
+        let (_r1, w1) = new_requester();
+        let (_r2, w2) = new_requester();
+        manager.register_region(1, w1);
+        manager.register_region(2, w2);
+
+        manager.update_region_usage(1, 10, 10);
+        manager.update_region_usage(2, 20, 30);
+        assert_eq!(30, manager.mutable_usage());
+        assert_eq!(40, manager.memory_usage());
+
+        // Freeze the mutable memtable of region 1.
+        manager.update_region_usage(1, 0, 10);
+        assert_eq!(20, manager.mutable_usage());
+        assert_eq!(40, manager.memory_usage());
+
+        // Unknown region is ignored.
+        manager.update_region_usage(3, 100, 100);
+        assert_eq!(40, manager.memory_usage());
+
+        manager.unregister_region(2);
+        assert_eq!(0, manager.mutable_usage());
+        assert_eq!(10, manager.memory_usage());
+    }
+
+    #[test]
+    fn test_region_to_flush() {
+        let manager = WriteBufferManager::new(100, 200, Duration::ZERO);
+        let (_r1, w1) = new_requester();
+        let (_r2, w2) = new_requester();
+        manager.register_region(1, w1);
+        manager.register_region(2, w2);
+
+        manager.update_region_usage(1, 30, 30);
+        manager.update_region_usage(2, 50, 50);
+        assert_eq!(None, manager.region_to_flush());
+
+        manager.update_region_usage(1, 60, 60);
+        assert_eq!(Some(1), manager.region_to_flush());
+    }
+
+    #[tokio::test]
+    async fn test_schedule_flush() {
+        let manager = Arc::new(WriteBufferManager::new(100, 200, Duration::ZERO));
+        let (r1, w1) = new_requester();
+        manager.register_region(1, w1);
+        manager.update_region_usage(1, 120, 120);
+
+        manager.schedule_flush(1);
+        // Flush has been requested.
+        assert_eq!(None, manager.region_to_flush());
+
+        // The permit is stored even if the flush happens before we wait.
+        r1.flushed.notified().await;
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_memory() {
+        let manager = Arc::new(WriteBufferManager::new(
+            100,
+            200,
+            Duration::from_millis(100),
+        ));
+        let (_r1, w1) = new_requester();
+        manager.register_region(1, w1);
+
+        manager.update_region_usage(1, 0, 150);
+        manager.wait_for_memory("test").await.unwrap();
+
+        manager.update_region_usage(1, 0, 250);
+        let err = manager.wait_for_memory("test").await.unwrap_err();
+        assert!(matches!(err, error::Error::WriteStall { .. }));
+
+        // Release memory while waiting.
+        let waiter = {
+            let manager = manager.clone();
+            tokio::spawn(async move { manager.wait_for_memory("test").await })
+        };
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        manager.update_region_usage(1, 0, 50);
+        waiter.await.unwrap().unwrap();
+    }
+}
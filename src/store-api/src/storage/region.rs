@@ -72,6 +72,18 @@ pub trait Region: Send + Sync + Clone + std::fmt::Debug + 'static {
     fn write_request(&self) -> Self::WriteRequest;
 
     async fn alter(&self, request: AlterRequest) -> Result<(), Self::Error>;
+
+    /// Returns the approximate statistics of this region.
+    fn stats(&self) -> RegionStat;
+}
+
+/// Approximate statistics of a region.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegionStat {
+    /// Rows in memtables and SST files, rows updated or deleted may be counted more than once.
+    pub approximate_rows: u64,
+    /// Bytes allocated by memtables plus the size of SST files.
+    pub approximate_bytes: u64,
 }
 
 /// Context for write operations.
@@ -18,6 +18,8 @@ use std::time::Duration;
 use common_error::ext::ErrorExt;
 use common_query::logical_plan::Expr;
 use common_time::RangeMillis;
+use datatypes::prelude::ConcreteDataType;
+use datatypes::value::Value;
 use datatypes::vectors::VectorRef;
 
 use crate::storage::{ColumnDescriptor, RegionDescriptor, SequenceNumber};
@@ -72,8 +74,17 @@ pub struct ScanRequest {
     pub filters: Vec<Expr>,
 }
 
-#[derive(Debug)]
-pub struct GetRequest {}
+/// Request to get the latest visible row with given row key.
+#[derive(Debug, Default)]
+pub struct GetRequest {
+    /// Values of all row key columns, including the timestamp column, in the
+    /// same order as row key columns in the region schema.
+    pub key: Vec<Value>,
+    /// Max sequence number to read, None for latest sequence.
+    pub sequence: Option<SequenceNumber>,
+    /// Indices of columns to read, `None` to read all columns.
+    pub projection: Option<Vec<usize>>,
+}
 
 /// Operation to add a column.
 #[derive(Debug)]
@@ -84,6 +95,15 @@ pub struct AddColumn {
     pub is_key: bool,
 }
 
+/// Operation to change the data type of a column.
+#[derive(Debug)]
+pub struct ModifyColumn {
+    /// Name of the column to modify.
+    pub name: String,
+    /// New data type of the column.
+    pub data_type: ConcreteDataType,
+}
+
 /// Operation to alter a region.
 #[derive(Debug)]
 pub enum AlterOperation {
@@ -97,6 +117,11 @@ pub enum AlterOperation {
         /// Name of columns to drop.
         names: Vec<String>,
     },
+    /// Modify data types of columns, only value columns are allowed to modify.
+    ModifyColumns {
+        /// Columns to modify.
+        columns: Vec<ModifyColumn>,
+    },
 }
 
 impl AlterOperation {
@@ -109,6 +134,9 @@ impl AlterOperation {
             AlterOperation::DropColumns { names } => {
                 Self::apply_drop(names, descriptor);
             }
+            AlterOperation::ModifyColumns { columns } => {
+                Self::apply_modify(columns, descriptor);
+            }
         }
     }
 
@@ -140,6 +168,20 @@ impl AlterOperation {
             cf.columns.retain(|col| !name_set.contains(&col.name));
         }
     }
+
+    /// Change data types of value columns in the [RegionDescriptor].
+    ///
+    /// The id of the column is kept, non-value columns in `columns` would be ignored.
+    fn apply_modify(columns: &[ModifyColumn], descriptor: &mut RegionDescriptor) {
+        let cfs = std::iter::once(&mut descriptor.default_cf).chain(&mut descriptor.extra_cfs);
+        for cf in cfs {
+            for col in &mut cf.columns {
+                if let Some(modify) = columns.iter().find(|m| m.name == col.name) {
+                    col.data_type = modify.data_type.clone();
+                }
+            }
+        }
+    }
 }
 
 /// Alter region request.
@@ -226,5 +268,29 @@ mod tests {
         op.apply(&mut desc);
         assert_eq!(1, desc.row_key.columns.len());
         assert_eq!(1, desc.default_cf.columns.len());
+
+        let op = AlterOperation::ModifyColumns {
+            columns: vec![
+                ModifyColumn {
+                    name: String::from("4"),
+                    data_type: ConcreteDataType::float64_datatype(),
+                },
+                // Key columns are ignored.
+                ModifyColumn {
+                    name: String::from("3"),
+                    data_type: ConcreteDataType::float64_datatype(),
+                },
+            ],
+        };
+        op.apply(&mut desc);
+        assert_eq!(4, desc.default_cf.columns[0].id);
+        assert_eq!(
+            ConcreteDataType::float64_datatype(),
+            desc.default_cf.columns[0].data_type
+        );
+        assert_eq!(
+            ConcreteDataType::int64_datatype(),
+            desc.row_key.columns[0].data_type
+        );
     }
 }
@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use datatypes::value::Value;
+
 #[derive(Debug)]
 pub struct WriteResponse {}
 
@@ -22,4 +24,7 @@ pub struct ScanResponse<R> {
 }
 
 #[derive(Debug)]
-pub struct GetResponse {}
+pub struct GetResponse {
+    /// Values of projected columns of the row, `None` if the row is not found.
+    pub row: Option<Vec<Value>>,
+}
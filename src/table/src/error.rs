@@ -18,6 +18,7 @@ use common_error::prelude::*;
 use common_recordbatch::error::Error as RecordBatchError;
 use datafusion::error::DataFusionError;
 use datatypes::arrow::error::ArrowError;
+use datatypes::prelude::ConcreteDataType;
 
 common_error::define_opaque_error!(Error);
 
@@ -110,6 +111,32 @@ pub enum InnerError {
         column_name: String,
         backtrace: Backtrace,
     },
+
+    #[snafu(display(
+        "Not allowed to modify index column {} of table {}",
+        column_name,
+        table_name
+    ))]
+    ModifyColumnInIndex {
+        column_name: String,
+        table_name: String,
+        backtrace: Backtrace,
+    },
+
+    #[snafu(display(
+        "Not allowed to modify column {} of table {} from {:?} type to {:?} type",
+        column_name,
+        table_name,
+        from,
+        to
+    ))]
+    ModifyColumnType {
+        column_name: String,
+        table_name: String,
+        from: ConcreteDataType,
+        to: ConcreteDataType,
+        backtrace: Backtrace,
+    },
 }
 
 impl ErrorExt for InnerError {
@@ -121,6 +148,8 @@ impl ErrorExt for InnerError {
             | InnerError::TableProjection { .. } => StatusCode::EngineExecuteQuery,
             InnerError::MissingColumn { .. }
             | InnerError::RemoveColumnInIndex { .. }
+            | InnerError::ModifyColumnInIndex { .. }
+            | InnerError::ModifyColumnType { .. }
             | InnerError::BuildColumnDescriptor { .. } => StatusCode::InvalidArguments,
             InnerError::TablesRecordBatch { .. } => StatusCode::Unexpected,
             InnerError::ColumnExists { .. } => StatusCode::TableColumnExists,
@@ -21,11 +21,11 @@ pub use datatypes::error::{Error as ConvertError, Result as ConvertResult};
 use datatypes::schema::{ColumnSchema, RawSchema, Schema, SchemaBuilder, SchemaRef};
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
-use snafu::{ensure, ResultExt};
+use snafu::{ensure, OptionExt, ResultExt};
 use store_api::storage::{ColumnDescriptor, ColumnDescriptorBuilder, ColumnId};
 
 use crate::error::{self, Result};
-use crate::requests::{AddColumnRequest, AlterKind};
+use crate::requests::{AddColumnRequest, AlterKind, ModifyColumnRequest};
 
 pub type TableId = u32;
 pub type TableVersion = u64;
@@ -132,6 +132,10 @@ impl TableMeta {
         match alter_kind {
             AlterKind::AddColumns { columns } => self.add_columns(table_name, columns),
             AlterKind::DropColumns { names } => self.remove_columns(table_name, names),
+            // The name of the table is stored in `TableInfo`, so the meta is unchanged.
+            AlterKind::RenameTable { .. } => Ok(self.builder_with_same_schema()),
+            AlterKind::ModifyColumns { columns } => self.modify_columns(table_name, columns),
+            AlterKind::SetTableOptions { options } => Ok(self.set_table_options(options)),
         }
     }
 
@@ -320,6 +324,98 @@ impl TableMeta {
 
         Ok(meta_builder)
     }
+
+    fn builder_with_same_schema(&self) -> TableMetaBuilder {
+        let mut meta_builder = self.new_meta_builder();
+        meta_builder
+            .schema(self.schema.clone())
+            .primary_key_indices(self.primary_key_indices.clone());
+
+        meta_builder
+    }
+
+    fn modify_columns(
+        &self,
+        table_name: &str,
+        requests: &[ModifyColumnRequest],
+    ) -> Result<TableMetaBuilder> {
+        let table_schema = &self.schema;
+        let mut meta_builder = self.new_meta_builder();
+        let mut columns = table_schema.column_schemas().to_vec();
+
+        let timestamp_index = table_schema.timestamp_index();
+        for request in requests {
+            let column_name = &request.column_name;
+            let index = table_schema
+                .column_index_by_name(column_name)
+                .with_context(|| error::ColumnNotExistsSnafu {
+                    column_name,
+                    table_name,
+                })?;
+            ensure!(
+                !self.primary_key_indices.contains(&index) && Some(index) != timestamp_index,
+                error::ModifyColumnInIndexSnafu {
+                    column_name,
+                    table_name,
+                }
+            );
+
+            let column = &mut columns[index];
+            ensure!(
+                column.data_type.can_widen_to(&request.data_type),
+                error::ModifyColumnTypeSnafu {
+                    column_name,
+                    table_name,
+                    from: column.data_type.clone(),
+                    to: request.data_type.clone(),
+                }
+            );
+            column.data_type = request.data_type.clone();
+            // Validates the default constraint against the new type.
+            let default_constraint = column.default_constraint().cloned();
+            *column = column
+                .clone()
+                .with_default_constraint(default_constraint)
+                .with_context(|_| error::SchemaBuildSnafu {
+                    msg: format!(
+                        "Default constraint of column {} in table {} is invalid for {:?} type",
+                        column_name, table_name, request.data_type
+                    ),
+                })?;
+        }
+
+        let mut builder = SchemaBuilder::try_from_columns(columns)
+            .with_context(|_| error::SchemaBuildSnafu {
+                msg: format!(
+                    "Failed to convert column schemas into schema for table {}",
+                    table_name
+                ),
+            })?
+            // Also bump the schema version.
+            .version(table_schema.version() + 1);
+        for (k, v) in table_schema.metadata().iter() {
+            builder = builder.add_metadata(k, v);
+        }
+        let new_schema = builder.build().with_context(|_| error::SchemaBuildSnafu {
+            msg: format!("Table {} cannot modify columns", table_name),
+        })?;
+
+        // Columns are modified in place, so the indices are unchanged.
+        meta_builder
+            .schema(Arc::new(new_schema))
+            .primary_key_indices(self.primary_key_indices.clone());
+
+        Ok(meta_builder)
+    }
+
+    fn set_table_options(&self, options: &HashMap<String, String>) -> TableMetaBuilder {
+        let mut meta_builder = self.builder_with_same_schema();
+        let mut new_options = self.options.clone();
+        new_options.extend(options.iter().map(|(k, v)| (k.clone(), v.clone())));
+        meta_builder.options(new_options);
+
+        meta_builder
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Builder)]
@@ -731,6 +827,124 @@ mod tests {
         assert_eq!(StatusCode::InvalidArguments, err.status_code());
     }
 
+    #[test]
+    fn test_modify_columns() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema.clone())
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::ModifyColumns {
+            columns: vec![ModifyColumnRequest {
+                column_name: String::from("col2"),
+                data_type: ConcreteDataType::int64_datatype(),
+            }],
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+
+        let col2 = new_meta.schema.column_schema_by_name("col2").unwrap();
+        assert_eq!(ConcreteDataType::int64_datatype(), col2.data_type);
+        assert_eq!(schema.version() + 1, new_meta.schema.version());
+        assert_eq!(&[0], &new_meta.primary_key_indices[..]);
+        assert_eq!(&[1, 2], &new_meta.value_indices[..]);
+        assert_eq!(
+            schema.timestamp_column(),
+            new_meta.schema.timestamp_column()
+        );
+    }
+
+    #[test]
+    fn test_modify_invalid_columns() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .build()
+            .unwrap();
+
+        let modify = |column_name: &str, data_type| AlterKind::ModifyColumns {
+            columns: vec![ModifyColumnRequest {
+                column_name: column_name.to_string(),
+                data_type,
+            }],
+        };
+
+        // Unknown column.
+        let alter_kind = modify("unknown", ConcreteDataType::int64_datatype());
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::TableColumnNotFound, err.status_code());
+
+        // Key column and timestamp column.
+        for column_name in ["col1", "ts"] {
+            let alter_kind = modify(column_name, ConcreteDataType::int64_datatype());
+            let err = meta
+                .builder_with_alter_kind("my_table", &alter_kind)
+                .err()
+                .unwrap();
+            assert_eq!(StatusCode::InvalidArguments, err.status_code());
+        }
+
+        // Incompatible type.
+        let alter_kind = modify("col2", ConcreteDataType::string_datatype());
+        let err = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .err()
+            .unwrap();
+        assert_eq!(StatusCode::InvalidArguments, err.status_code());
+    }
+
+    #[test]
+    fn test_rename_table_and_set_options() {
+        let schema = Arc::new(new_test_schema());
+        let meta = TableMetaBuilder::default()
+            .schema(schema)
+            .primary_key_indices(vec![0])
+            .engine("engine")
+            .next_column_id(3)
+            .options(HashMap::from([
+                ("ttl".to_string(), "1d".to_string()),
+                ("write_buffer_size".to_string(), "1MB".to_string()),
+            ]))
+            .build()
+            .unwrap();
+
+        let alter_kind = AlterKind::RenameTable {
+            new_table_name: String::from("new_table"),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(meta.schema, new_meta.schema);
+        assert_eq!(meta.options, new_meta.options);
+
+        let alter_kind = AlterKind::SetTableOptions {
+            options: HashMap::from([("ttl".to_string(), "7d".to_string())]),
+        };
+        let new_meta = meta
+            .builder_with_alter_kind("my_table", &alter_kind)
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(meta.schema, new_meta.schema);
+        assert_eq!("7d", new_meta.options["ttl"]);
+        assert_eq!("1MB", new_meta.options["write_buffer_size"]);
+    }
+
     #[test]
     fn test_alloc_new_column() {
         let schema = Arc::new(new_test_schema());
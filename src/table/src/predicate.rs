@@ -17,8 +17,11 @@ mod stats;
 use common_query::logical_plan::Expr;
 use common_telemetry::{error, warn};
 use datafusion::physical_optimizer::pruning::PruningPredicate;
+use datafusion_common::Column;
+use datafusion_expr::{Expr as DfExpr, Operator};
 use datatypes::arrow::io::parquet::read::RowGroupMetaData;
 use datatypes::schema::SchemaRef;
+use datatypes::value::Value;
 
 use crate::predicate::stats::RowGroupPruningStatistics;
 
@@ -66,6 +69,25 @@ impl Predicate {
     }
 }
 
+/// Builds `column = value` expressions for given columns, which could be used to prune
+/// row groups that don't contain the value.
+///
+/// Columns with null values are ignored.
+pub fn equal_exprs<'a>(columns: impl IntoIterator<Item = (&'a str, &'a Value)>) -> Vec<Expr> {
+    columns
+        .into_iter()
+        .filter_map(|(name, value)| {
+            let scalar = value.try_to_scalar_value().ok()?;
+            let expr = DfExpr::BinaryExpr {
+                left: Box::new(DfExpr::Column(Column::from_name(name.to_string()))),
+                op: Operator::Eq,
+                right: Box::new(DfExpr::Literal(scalar)),
+            };
+            Some(expr.into())
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
@@ -195,6 +217,13 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_prune_equal_exprs() {
+        let value = Value::Int32(30);
+        let p = Predicate::new(equal_exprs([("cnt", &value), ("name", &Value::Null)]));
+        assert_prune(40, p, vec![false, false, false, true]).await;
+    }
+
     #[tokio::test]
     async fn test_prune_eq_expr() {
         let p = gen_predicate(30, Operator::Eq);
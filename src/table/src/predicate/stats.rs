@@ -19,7 +19,7 @@ use datafusion::parquet::statistics::{
 use datafusion::physical_optimizer::pruning::PruningStatistics;
 use datafusion_common::{Column, ScalarValue};
 use datatypes::arrow::array::ArrayRef;
-use datatypes::arrow::datatypes::DataType;
+use datatypes::arrow::datatypes::{DataType, TimeUnit};
 use datatypes::arrow::io::parquet::read::PhysicalType;
 use datatypes::prelude::Vector;
 use datatypes::vectors::Int64Vector;
@@ -76,7 +76,7 @@ macro_rules! impl_min_max_values {
                                     .downcast_ref::<PrimitiveStatistics<i64>>()
                                     .unwrap()
                                     .[<$min_max _value>];
-                                Some(ScalarValue::Int64($min_max))
+                                Some(int64_stats_to_scalar(data_type, $min_max))
                             }
                             PhysicalType::Int96 => {
                                 // INT96 currently not supported
@@ -128,6 +128,26 @@ macro_rules! impl_min_max_values {
     };
 }
 
+/// Converts statistics of an int64 column to a scalar of the column's logical type, so
+/// timestamps in any time unit are compared with predicates as timestamps.
+fn int64_stats_to_scalar(data_type: &DataType, value: Option<i64>) -> ScalarValue {
+    match data_type {
+        DataType::Timestamp(TimeUnit::Second, tz) => {
+            ScalarValue::TimestampSecond(value, tz.clone())
+        }
+        DataType::Timestamp(TimeUnit::Millisecond, tz) => {
+            ScalarValue::TimestampMillisecond(value, tz.clone())
+        }
+        DataType::Timestamp(TimeUnit::Microsecond, tz) => {
+            ScalarValue::TimestampMicrosecond(value, tz.clone())
+        }
+        DataType::Timestamp(TimeUnit::Nanosecond, tz) => {
+            ScalarValue::TimestampNanosecond(value, tz.clone())
+        }
+        _ => ScalarValue::Int64(value),
+    }
+}
+
 impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
     fn min_values(&self, column: &Column) -> Option<ArrayRef> {
         impl_min_max_values!(self, column, min)
@@ -15,7 +15,7 @@
 //! Table and TableEngine requests
 use std::collections::HashMap;
 
-use datatypes::prelude::VectorRef;
+use datatypes::prelude::{ConcreteDataType, VectorRef};
 use datatypes::schema::{ColumnSchema, SchemaRef};
 use store_api::storage::RegionNumber;
 
@@ -32,6 +32,7 @@ pub struct InsertRequest {
 
 #[derive(Debug, Clone)]
 pub struct CreateDatabaseRequest {
+    pub catalog_name: String,
     pub db_name: String,
 }
 
@@ -76,10 +77,21 @@ pub struct AddColumnRequest {
     pub is_key: bool,
 }
 
+/// Modify column request
+#[derive(Debug)]
+pub struct ModifyColumnRequest {
+    pub column_name: String,
+    /// New data type of the column, only lossless conversion from the old type is allowed.
+    pub data_type: ConcreteDataType,
+}
+
 #[derive(Debug)]
 pub enum AlterKind {
     AddColumns { columns: Vec<AddColumnRequest> },
     DropColumns { names: Vec<String> },
+    RenameTable { new_table_name: String },
+    ModifyColumns { columns: Vec<ModifyColumnRequest> },
+    SetTableOptions { options: HashMap<String, String> },
 }
 
 /// Drop table request
@@ -23,6 +23,7 @@ use async_trait::async_trait;
 use common_query::logical_plan::Expr;
 use common_query::physical_plan::PhysicalPlanRef;
 use datatypes::schema::SchemaRef;
+use store_api::storage::{RegionId, RegionNumber};
 
 use crate::error::Result;
 use crate::metadata::{FilterPushDownType, TableId, TableInfoRef, TableType};
@@ -73,6 +74,21 @@ pub trait Table: Send + Sync {
         let _ = request;
         unimplemented!()
     }
+
+    /// Returns the approximate statistics of the regions of this table, empty if the table
+    /// is not backed by regions.
+    fn region_stats(&self) -> Result<Vec<RegionStat>> {
+        Ok(vec![])
+    }
+}
+
+/// Approximate statistics of a region of the table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RegionStat {
+    pub region_id: RegionId,
+    pub region_number: RegionNumber,
+    pub approximate_rows: u64,
+    pub approximate_bytes: u64,
 }
 
 pub type TableRef = Arc<dyn Table>;
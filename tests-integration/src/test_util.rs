@@ -284,6 +284,8 @@ pub async fn setup_grpc_server(
         fe_instance_ref.clone(),
         fe_instance_ref,
         runtime,
+        Default::default(),
+        None,
     ));
     let grpc_server_clone = fe_grpc_server.clone();
 
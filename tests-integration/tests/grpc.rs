@@ -95,6 +95,7 @@ fn expect_data() -> (Column, Column, Column, Column) {
         null_mask: vec![2],
         semantic_type: SemanticType::Field as i32,
         datatype: ColumnDataType::Float64 as i32,
+        datatype_extension: None,
     };
     let expected_mem_col = Column {
         column_name: "memory".to_string(),
@@ -105,6 +106,7 @@ fn expect_data() -> (Column, Column, Column, Column) {
         null_mask: vec![4],
         semantic_type: SemanticType::Field as i32,
         datatype: ColumnDataType::Float64 as i32,
+        datatype_extension: None,
     };
     let expected_ts_col = Column {
         column_name: "ts".to_string(),
@@ -152,6 +154,7 @@ pub async fn test_insert_and_select(store_type: StorageType) {
         datatype: ColumnDataType::Int64.into(),
         is_nullable: true,
         default_constraint: None,
+        datatype_extension: None,
     };
     let kind = Kind::AddColumns(AddColumns {
         add_columns: vec![AddColumn {
@@ -229,24 +232,28 @@ fn testing_create_expr() -> CreateExpr {
             datatype: ColumnDataType::String as i32,
             is_nullable: false,
             default_constraint: None,
+            datatype_extension: None,
         },
         ColumnDef {
             name: "cpu".to_string(),
             datatype: ColumnDataType::Float64 as i32,
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         },
         ColumnDef {
             name: "memory".to_string(),
             datatype: ColumnDataType::Float64 as i32,
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         },
         ColumnDef {
             name: "ts".to_string(),
             datatype: 15, // timestamp
             is_nullable: true,
             default_constraint: None,
+            datatype_extension: None,
         },
     ];
     CreateExpr {
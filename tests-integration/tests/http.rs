@@ -92,6 +92,24 @@ pub async fn test_sql_api(store_type: StorageType) {
         })).unwrap()
     );
 
+    // test output formats
+    let res = client
+        .get("/v1/sql?sql=select * from numbers limit 3&format=csv")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.text().await, "number\n0\n1\n2\n");
+
+    let res = client
+        .get("/v1/sql?sql=select * from numbers limit 3&format=columnar")
+        .send()
+        .await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(
+        res.text().await,
+        "{\"columns\":[\"number\"],\"types\":[\"UInt32\"],\"values\":[[0,1,2]]}\n"
+    );
+
     // test insert and select
     let res = client
         .get("/v1/sql?sql=insert into demo values('host', 66.6, 1024, 0)")